@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::models::{Market, ZonePair};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EntsoeConfig {
+    pub security_token: String,
+    pub base_url: String,
+    /// Backup API base URLs, tried in order when the ones before them fail.
+    #[serde(default)]
+    pub fallback_base_urls: Vec<String>,
+    pub rate_limit_per_minute: u32,
+    pub timeout_seconds: u64,
+    pub circuit_breaker_failure_threshold: u32,
+    pub circuit_breaker_cooldown_seconds: u64,
+    /// Also fetch intraday auction results alongside day-ahead prices.
+    #[serde(default)]
+    pub fetch_intraday: bool,
+    /// Announced ENTSOE maintenance windows. Scheduled fetches are deferred
+    /// until a window ends and the circuit breaker is held open during it.
+    #[serde(default)]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    /// Borders to fetch cross-border flows for. Each pair is fetched in both
+    /// directions.
+    #[serde(default)]
+    pub flow_pairs: Vec<ZonePair>,
+    /// Sanity bounds for published prices.
+    #[serde(default)]
+    pub price_bounds: PriceBounds,
+    /// How positions missing from a price period are filled in.
+    #[serde(default)]
+    pub gap_fill: GapFillStrategy,
+}
+
+impl EntsoeConfig {
+    pub fn price_rules(&self) -> PriceRules {
+        PriceRules {
+            bounds: self.price_bounds,
+            gap_fill: self.gap_fill,
+        }
+    }
+}
+
+/// How published price periods are checked and completed when parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PriceRules {
+    pub bounds: PriceBounds,
+    pub gap_fill: GapFillStrategy,
+}
+
+/// What to do with positions a price period lacks, including points
+/// rejected by [`PriceBounds`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GapFillStrategy {
+    /// Repeat the previous published price.
+    #[default]
+    ForwardFill,
+    /// Interpolate between the published prices either side of the gap; a
+    /// gap at the end of the period is forward-filled.
+    LinearInterpolation,
+    /// Fail the whole period.
+    RejectPeriod,
+    /// Store nothing for the missing positions.
+    LeaveNull,
+}
+
+/// Range of plausible prices per MWh in the series' currency. The defaults
+/// are the harmonised minimum and maximum clearing prices: SDAC's for
+/// day-ahead prices, the intraday auctions' for intraday ones.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct PriceBounds {
+    #[serde(default = "default_min_price_mwh")]
+    pub min_mwh: f64,
+    #[serde(default = "default_max_price_mwh")]
+    pub max_mwh: f64,
+    #[serde(default = "default_intraday_min_price_mwh")]
+    pub intraday_min_mwh: f64,
+    #[serde(default = "default_intraday_max_price_mwh")]
+    pub intraday_max_mwh: f64,
+    #[serde(default)]
+    pub action: ImplausiblePriceAction,
+}
+
+fn default_min_price_mwh() -> f64 {
+    -500.0
+}
+
+fn default_max_price_mwh() -> f64 {
+    4000.0
+}
+
+fn default_intraday_min_price_mwh() -> f64 {
+    -9999.0
+}
+
+fn default_intraday_max_price_mwh() -> f64 {
+    9999.0
+}
+
+impl Default for PriceBounds {
+    fn default() -> Self {
+        Self {
+            min_mwh: default_min_price_mwh(),
+            max_mwh: default_max_price_mwh(),
+            intraday_min_mwh: default_intraday_min_price_mwh(),
+            intraday_max_mwh: default_intraday_max_price_mwh(),
+            action: ImplausiblePriceAction::default(),
+        }
+    }
+}
+
+impl PriceBounds {
+    pub fn contains(&self, amount_mwh: f64) -> bool {
+        (self.min_mwh..=self.max_mwh).contains(&amount_mwh)
+    }
+
+    /// The bounds prices for `market` are checked against, as `min_mwh` and
+    /// `max_mwh`.
+    pub fn for_market(&self, market: Market) -> Self {
+        match market {
+            Market::DayAhead => *self,
+            Market::Intraday => Self {
+                min_mwh: self.intraday_min_mwh,
+                max_mwh: self.intraday_max_mwh,
+                ..*self
+            },
+        }
+    }
+
+    /// Reject ranges no price can fall within.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_mwh >= self.max_mwh {
+            return Err("entsoe.price_bounds.min_mwh must be below max_mwh".to_string());
+        }
+        if self.intraday_min_mwh >= self.intraday_max_mwh {
+            return Err("entsoe.price_bounds.intraday_min_mwh must be below intraday_max_mwh".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// What happens to a published price outside [`PriceBounds`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImplausiblePriceAction {
+    /// Drop the point and fill it like a missing position.
+    #[default]
+    Reject,
+    /// Store the point as published.
+    Flag,
+}
+
+impl ImplausiblePriceAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImplausiblePriceAction::Reject => "reject",
+            ImplausiblePriceAction::Flag => "flag",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl MaintenanceWindow {
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_bounds_must_leave_room_for_prices() {
+        assert!(PriceBounds::default().validate().is_ok());
+        let inverted = PriceBounds {
+            min_mwh: 4000.0,
+            max_mwh: -500.0,
+            ..PriceBounds::default()
+        };
+        assert!(inverted.validate().is_err());
+        let empty_intraday = PriceBounds {
+            intraday_min_mwh: 0.0,
+            intraday_max_mwh: 0.0,
+            ..PriceBounds::default()
+        };
+        assert!(empty_intraday.validate().is_err());
+    }
+}
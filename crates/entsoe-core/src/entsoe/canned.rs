@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::models::{BiddingZone, CrossBorderFlow, FlowKind, GenerationPoint, LoadValue, Market, Price};
+
+use super::error::EntsoeError;
+use super::generation::parse_generation_document;
+use super::source::{parse_flow_document, parse_load_document, parse_price_document, EntsoePriceSource};
+
+enum CannedResponse {
+    Xml(String),
+    Error(fn() -> EntsoeError),
+}
+
+/// `EntsoePriceSource` that serves pre-recorded XML documents per zone/date,
+/// running them through the same parser as the live client.
+///
+/// Requests without a registered response return the ENTSOE "no data"
+/// acknowledgement, i.e. an empty price list.
+#[derive(Default)]
+pub struct CannedEntsoeSource {
+    responses: Mutex<HashMap<(String, NaiveDate, Market), CannedResponse>>,
+    generation: Mutex<HashMap<(String, NaiveDate), String>>,
+    load: Mutex<HashMap<(String, NaiveDate), String>>,
+    flows: Mutex<HashMap<(String, String, NaiveDate, FlowKind), String>>,
+    requests: Mutex<Vec<(String, NaiveDate)>>,
+}
+
+impl CannedEntsoeSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `xml` for day-ahead requests for `zone_code` on `date`.
+    pub fn with_xml(self, zone_code: &str, date: NaiveDate, xml: impl Into<String>) -> Self {
+        self.with_market_xml(zone_code, date, Market::DayAhead, xml)
+    }
+
+    /// Serve `xml` for `market` requests for `zone_code` on `date`.
+    pub fn with_market_xml(self, zone_code: &str, date: NaiveDate, market: Market, xml: impl Into<String>) -> Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .insert((zone_code.to_string(), date, market), CannedResponse::Xml(xml.into()));
+        self
+    }
+
+    /// Serve `xml` as the A75 generation document for `zone_code` on `date`.
+    pub fn with_generation_xml(self, zone_code: &str, date: NaiveDate, xml: impl Into<String>) -> Self {
+        self.generation
+            .lock()
+            .unwrap()
+            .insert((zone_code.to_string(), date), xml.into());
+        self
+    }
+
+    /// Serve `xml` as the A65 total load document for `zone_code` on `date`.
+    pub fn with_load_xml(self, zone_code: &str, date: NaiveDate, xml: impl Into<String>) -> Self {
+        self.load
+            .lock()
+            .unwrap()
+            .insert((zone_code.to_string(), date), xml.into());
+        self
+    }
+
+    /// Serve `xml` for `kind` flows from `from_zone` to `to_zone` on `date`.
+    pub fn with_flow_xml(self, from_zone: &str, to_zone: &str, date: NaiveDate, kind: FlowKind, xml: impl Into<String>) -> Self {
+        self.flows
+            .lock()
+            .unwrap()
+            .insert((from_zone.to_string(), to_zone.to_string(), date, kind), xml.into());
+        self
+    }
+
+    /// Fail day-ahead requests for `zone_code` on `date` with the error built by `error`.
+    pub fn with_error(self, zone_code: &str, date: NaiveDate, error: fn() -> EntsoeError) -> Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .insert((zone_code.to_string(), date, Market::DayAhead), CannedResponse::Error(error));
+        self
+    }
+
+    /// Zone/date pairs requested so far, in request order.
+    pub fn requests(&self) -> Vec<(String, NaiveDate)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl EntsoePriceSource for CannedEntsoeSource {
+    async fn fetch_prices(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+        market: Market,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        self.requests.lock().unwrap().push((zone.zone_code.clone(), date));
+
+        let responses = self.responses.lock().unwrap();
+        match responses.get(&(zone.zone_code.clone(), date, market)) {
+            Some(CannedResponse::Xml(xml)) => parse_price_document(xml, zone, market, &self.price_rules()),
+            Some(CannedResponse::Error(error)) => Err(error()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn fetch_generation(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<GenerationPoint>, EntsoeError> {
+        let generation = self.generation.lock().unwrap();
+        match generation.get(&(zone.zone_code.clone(), date)) {
+            Some(xml) => parse_generation_document(xml, &zone.zone_code),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn fetch_load(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<LoadValue>, EntsoeError> {
+        let load = self.load.lock().unwrap();
+        match load.get(&(zone.zone_code.clone(), date)) {
+            Some(xml) => parse_load_document(xml, &zone.zone_code),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn fetch_flows(
+        &self,
+        from: &BiddingZone,
+        to: &BiddingZone,
+        date: NaiveDate,
+        kind: FlowKind,
+    ) -> Result<Vec<CrossBorderFlow>, EntsoeError> {
+        let flows = self.flows.lock().unwrap();
+        match flows.get(&(from.zone_code.clone(), to.zone_code.clone(), date, kind)) {
+            Some(xml) => parse_flow_document(xml, &from.zone_code, &to.zone_code, kind),
+            None => Ok(Vec::new()),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::config::MaintenanceWindow;
+use crate::metrics;
+
+use super::error::EntsoeError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    fn as_gauge(&self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::HalfOpen => 1.0,
+            CircuitState::Open => 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub failure_threshold: u32,
+    pub cooldown_seconds: u64,
+    /// Seconds until an open circuit admits a trial request.
+    pub retry_in_seconds: Option<u64>,
+    /// End of the maintenance window holding the circuit open, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    #[schemars(with = "DateTime<Utc>")]
+    pub maintenance_until: Option<DateTime<Utc>>,
+}
+
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    trial_in_flight: bool,
+    /// Set while the circuit is held open for a maintenance window.
+    maintenance_until: Option<DateTime<Utc>>,
+}
+
+/// Circuit breaker shared by all zone fetches. Opens after `failure_threshold`
+/// consecutive transient failures (regardless of zone) and rejects requests
+/// until `cooldown` has elapsed, then lets a single trial request through.
+///
+/// During a configured maintenance window the circuit is opened up front and
+/// admits the trial request as soon as the window ends.
+pub struct CircuitBreaker {
+    /// `breaker` label of the circuit breaker metrics.
+    name: &'static str,
+    failure_threshold: u32,
+    cooldown: Duration,
+    maintenance_windows: Vec<MaintenanceWindow>,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    /// The breaker for price requests.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self::named("prices", failure_threshold, cooldown)
+    }
+
+    /// A breaker reported under `name`, for requests whose failures should
+    /// not hold back the others.
+    pub fn named(name: &'static str, failure_threshold: u32, cooldown: Duration) -> Self {
+        metrics::update_circuit_breaker_state(name, CircuitState::Closed.as_gauge());
+        Self {
+            name,
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            maintenance_windows: Vec::new(),
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                trial_in_flight: false,
+                maintenance_until: None,
+            }),
+        }
+    }
+
+    pub fn with_maintenance_windows(mut self, windows: Vec<MaintenanceWindow>) -> Self {
+        self.maintenance_windows = windows;
+        self
+    }
+
+    /// The maintenance window covering `at`, if any.
+    pub fn maintenance_window(&self, at: DateTime<Utc>) -> Option<&MaintenanceWindow> {
+        self.maintenance_windows.iter().find(|w| w.contains(at))
+    }
+
+    /// Admit a request, or fail fast with `EntsoeError::CircuitOpen`.
+    pub fn check(&self) -> Result<(), EntsoeError> {
+        self.check_at(Utc::now())
+    }
+
+    fn check_at(&self, now: DateTime<Utc>) -> Result<(), EntsoeError> {
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(window) = self.maintenance_window(now) {
+            if inner.maintenance_until != Some(window.end) {
+                warn!(
+                    breaker = self.name,
+                    until = %window.end,
+                    reason = window.reason.as_deref().unwrap_or(""),
+                    "ENTSOE maintenance window started, opening circuit breaker"
+                );
+                inner.state = CircuitState::Open;
+                inner.opened_at = Some(Instant::now());
+                inner.trial_in_flight = false;
+                inner.maintenance_until = Some(window.end);
+                metrics::update_circuit_breaker_state(self.name, CircuitState::Open.as_gauge());
+            }
+            return Err(EntsoeError::CircuitOpen {
+                retry_in_seconds: (window.end - now).num_seconds().max(0) as u64,
+            });
+        }
+
+        match inner.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => {
+                let elapsed = inner.opened_at.map(|t| t.elapsed()).unwrap_or_default();
+                if elapsed >= self.cooldown || inner.maintenance_until.is_some() {
+                    inner.maintenance_until = None;
+                    info!("Circuit breaker cool-down elapsed, admitting trial request");
+                    inner.state = CircuitState::HalfOpen;
+                    inner.trial_in_flight = true;
+                    metrics::update_circuit_breaker_state(self.name, CircuitState::HalfOpen.as_gauge());
+                    Ok(())
+                } else {
+                    Err(EntsoeError::CircuitOpen {
+                        retry_in_seconds: (self.cooldown - elapsed).as_secs(),
+                    })
+                }
+            }
+            CircuitState::HalfOpen => {
+                if inner.trial_in_flight {
+                    Err(EntsoeError::CircuitOpen { retry_in_seconds: 0 })
+                } else {
+                    inner.trial_in_flight = true;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != CircuitState::Closed {
+            info!(breaker = self.name, "Circuit breaker closed after successful request");
+            metrics::update_circuit_breaker_state(self.name, CircuitState::Closed.as_gauge());
+        }
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.trial_in_flight = false;
+        inner.maintenance_until = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        inner.trial_in_flight = false;
+
+        let should_open = match inner.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => inner.consecutive_failures >= self.failure_threshold,
+            CircuitState::Open => false,
+        };
+
+        if should_open {
+            warn!(
+                breaker = self.name,
+                consecutive_failures = inner.consecutive_failures,
+                cooldown_secs = self.cooldown.as_secs(),
+                "Circuit breaker opened, short-circuiting ENTSOE requests"
+            );
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+            metrics::update_circuit_breaker_state(self.name, CircuitState::Open.as_gauge());
+            metrics::record_circuit_breaker_trip(self.name);
+        }
+    }
+
+    pub fn status(&self) -> CircuitBreakerStatus {
+        self.status_at(Utc::now())
+    }
+
+    fn status_at(&self, now: DateTime<Utc>) -> CircuitBreakerStatus {
+        if let Some(window) = self.maintenance_window(now) {
+            let inner = self.inner.lock().unwrap();
+            return CircuitBreakerStatus {
+                state: CircuitState::Open,
+                consecutive_failures: inner.consecutive_failures,
+                failure_threshold: self.failure_threshold,
+                cooldown_seconds: self.cooldown.as_secs(),
+                retry_in_seconds: Some((window.end - now).num_seconds().max(0) as u64),
+                maintenance_until: Some(window.end),
+            };
+        }
+
+        let inner = self.inner.lock().unwrap();
+        let retry_in_seconds = match (inner.state, inner.opened_at) {
+            (CircuitState::Open, Some(opened_at)) => {
+                Some(self.cooldown.saturating_sub(opened_at.elapsed()).as_secs())
+            }
+            _ => None,
+        };
+
+        CircuitBreakerStatus {
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            failure_threshold: self.failure_threshold,
+            cooldown_seconds: self.cooldown.as_secs(),
+            retry_in_seconds,
+            maintenance_until: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold_consecutive_failures() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.check().is_ok());
+
+        breaker.record_failure();
+        assert_eq!(breaker.status().state, CircuitState::Open);
+        assert!(matches!(breaker.check(), Err(EntsoeError::CircuitOpen { .. })));
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_admits_single_trial_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure();
+
+        assert!(breaker.check().is_ok());
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+        assert!(breaker.check().is_err());
+
+        breaker.record_success();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_maintenance_window_holds_circuit_open_until_it_ends() {
+        let now = Utc::now();
+        let window = MaintenanceWindow {
+            start: now - chrono::Duration::hours(1),
+            end: now + chrono::Duration::hours(1),
+            reason: None,
+        };
+        let breaker = CircuitBreaker::new(5, Duration::from_secs(3600)).with_maintenance_windows(vec![window]);
+
+        match breaker.check_at(now) {
+            Err(EntsoeError::CircuitOpen { retry_in_seconds }) => assert_eq!(retry_in_seconds, 3600),
+            other => panic!("expected open circuit, got {:?}", other),
+        }
+        assert_eq!(breaker.status_at(now).state, CircuitState::Open);
+        assert_eq!(breaker.status_at(now).maintenance_until, Some(now + chrono::Duration::hours(1)));
+
+        // The trial request is admitted at the end of the window, not after the cooldown
+        let after = now + chrono::Duration::hours(2);
+        assert!(breaker.check_at(after).is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.status_at(after).state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_failed_trial_reopens_circuit() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure();
+        breaker.check().unwrap();
+        breaker.record_failure();
+        assert_eq!(breaker.status().state, CircuitState::Open);
+    }
+}
@@ -0,0 +1,968 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::Client;
+use tokio::sync::Mutex;
+use tracing::{debug, error, info, warn};
+
+use crate::config::{EntsoeConfig, MaintenanceWindow, PriceRules};
+use crate::metrics;
+use crate::models::{BiddingZone, CrossBorderFlow, DeliveryDay, FlowKind, GenerationPoint, LoadValue, Market, Price};
+
+use super::circuit_breaker::CircuitBreaker;
+use super::error::EntsoeError;
+use super::generation::parse_generation_document;
+use super::validation::{check_hour_count, keep_finest_resolution};
+use super::source::{parse_flow_document, parse_load_document, parse_price_document, EntsoePriceSource};
+use super::timing::{self, ConnectTimingLayer, TimingResolver};
+use super::trace;
+
+/// Documents (TimeSeries) ENTSOE returns per response; a full page means the
+/// rest follows at the next `offset`.
+const DOCUMENTS_PER_PAGE: usize = 100;
+/// Largest `offset` ENTSOE accepts.
+const MAX_OFFSET: usize = 4800;
+/// Longest period ENTSOE serves in one price request.
+pub const MAX_REQUEST_DAYS: i64 = 365;
+
+/// Token bucket rate limiter that enforces a per-minute rate limit.
+/// Tokens are replenished continuously based on elapsed time.
+struct TokenBucketRateLimiter {
+    tokens: f64,
+    max_tokens: f64,
+    refill_rate_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucketRateLimiter {
+    fn new(requests_per_minute: u32) -> Self {
+        let max_tokens = requests_per_minute as f64;
+        let refill_rate_per_sec = max_tokens / 60.0;
+        Self {
+            tokens: max_tokens,
+            max_tokens,
+            refill_rate_per_sec,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_rate_per_sec).min(self.max_tokens);
+        self.last_refill = now;
+    }
+
+    /// Attempt to acquire a token. Returns the duration to wait if no token is available.
+    fn try_acquire(&mut self) -> Option<Duration> {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let wait_secs = (1.0 - self.tokens) / self.refill_rate_per_sec;
+            Some(Duration::from_secs_f64(wait_secs))
+        }
+    }
+}
+
+/// An item parsed from a paged ENTSOE response. A page whose items all have
+/// keys seen on earlier pages is a repeat, e.g. when the server ignored the
+/// `offset`, and ends paging.
+trait PageItem {
+    type Key: Ord;
+
+    fn page_key(&self) -> Self::Key;
+}
+
+impl PageItem for Price {
+    type Key = (DateTime<Utc>, String);
+
+    fn page_key(&self) -> Self::Key {
+        (self.timestamp, self.resolution.clone())
+    }
+}
+
+impl PageItem for GenerationPoint {
+    type Key = (DateTime<Utc>, String, String);
+
+    fn page_key(&self) -> Self::Key {
+        (self.timestamp, self.psr_type.clone(), self.resolution.clone())
+    }
+}
+
+impl PageItem for LoadValue {
+    type Key = (DateTime<Utc>, String);
+
+    fn page_key(&self) -> Self::Key {
+        (self.timestamp, self.resolution.clone())
+    }
+}
+
+impl PageItem for CrossBorderFlow {
+    type Key = (DateTime<Utc>, String);
+
+    fn page_key(&self) -> Self::Key {
+        (self.timestamp, self.resolution.clone())
+    }
+}
+
+/// A configured API base URL. After a failure it is skipped in favour of
+/// the next endpoint until its cooldown ends.
+struct Endpoint {
+    base_url: String,
+    /// Own connection pool, so connection timings are attributed to this endpoint.
+    client: Client,
+    unhealthy_until: std::sync::Mutex<Option<Instant>>,
+}
+
+impl Endpoint {
+    fn new(base_url: String, timeout: Duration) -> Result<Self, EntsoeError> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .dns_resolver(Arc::new(TimingResolver::new(&base_url)))
+            .connector_layer(ConnectTimingLayer::new(&base_url))
+            .build()?;
+
+        Ok(Self {
+            base_url,
+            client,
+            unhealthy_until: std::sync::Mutex::new(None),
+        })
+    }
+
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.unhealthy_until.lock().unwrap().is_none_or(|until| now >= until)
+    }
+
+    fn mark_unhealthy(&self, cooldown: Duration) {
+        *self.unhealthy_until.lock().unwrap() = Some(Instant::now() + cooldown);
+    }
+
+    fn mark_healthy(&self) {
+        *self.unhealthy_until.lock().unwrap() = None;
+    }
+}
+
+pub struct EntsoeClient {
+    /// Primary base URL first, then the fallbacks in configured order.
+    endpoints: Vec<Endpoint>,
+    endpoint_cooldown: Duration,
+    security_token: String,
+    rate_limiter: Arc<Mutex<TokenBucketRateLimiter>>,
+    circuit_breaker: CircuitBreaker,
+    /// Breaker for generation, load and flow requests, so failures of those
+    /// documents do not stop price fetches.
+    actuals_circuit_breaker: CircuitBreaker,
+    price_rules: PriceRules,
+}
+
+impl EntsoeClient {
+    pub fn new(config: &EntsoeConfig) -> Result<Self, EntsoeError> {
+        let rate_limiter = TokenBucketRateLimiter::new(config.rate_limit_per_minute);
+
+        let endpoints = std::iter::once(&config.base_url)
+            .chain(&config.fallback_base_urls)
+            .map(|base_url| Endpoint::new(base_url.clone(), Duration::from_secs(config.timeout_seconds)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            endpoints,
+            endpoint_cooldown: Duration::from_secs(config.circuit_breaker_cooldown_seconds),
+            security_token: config.security_token.clone(),
+            rate_limiter: Arc::new(Mutex::new(rate_limiter)),
+            circuit_breaker: CircuitBreaker::new(
+                config.circuit_breaker_failure_threshold,
+                Duration::from_secs(config.circuit_breaker_cooldown_seconds),
+            )
+            .with_maintenance_windows(config.maintenance_windows.clone()),
+            actuals_circuit_breaker: CircuitBreaker::named(
+                "actuals",
+                config.circuit_breaker_failure_threshold,
+                Duration::from_secs(config.circuit_breaker_cooldown_seconds),
+            )
+            .with_maintenance_windows(config.maintenance_windows.clone()),
+            price_rules: config.price_rules(),
+        })
+    }
+
+    pub fn circuit_breaker(&self) -> &CircuitBreaker {
+        &self.circuit_breaker
+    }
+
+    pub fn primary_base_url(&self) -> &str {
+        &self.endpoints[0].base_url
+    }
+
+    /// Time a request to the primary endpoint without a security token. Any
+    /// HTTP response, including the 401 ENTSOE answers it with, shows the
+    /// API is reachable. Bypasses the rate limiter and circuit breaker.
+    pub async fn probe(&self, timeout: Duration) -> Result<Duration, EntsoeError> {
+        let endpoint = &self.endpoints[0];
+        let start = Instant::now();
+        endpoint.client.get(&endpoint.base_url).timeout(timeout).send().await?;
+        Ok(start.elapsed())
+    }
+
+    /// Endpoints in the order a request tries them: healthy ones in
+    /// configured order, then those still cooling down.
+    fn endpoint_order(&self) -> Vec<&Endpoint> {
+        let now = Instant::now();
+        let (mut healthy, unhealthy): (Vec<&Endpoint>, Vec<&Endpoint>) =
+            self.endpoints.iter().partition(|endpoint| endpoint.is_healthy(now));
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    async fn acquire_rate_limit_permit(&self) {
+        loop {
+            let wait_duration = {
+                let mut limiter = self.rate_limiter.lock().await;
+                limiter.try_acquire()
+            };
+            match wait_duration {
+                None => break,
+                Some(duration) => {
+                    metrics::record_rate_limit_wait();
+                    debug!(wait_ms = duration.as_millis(), "Rate limit reached, waiting");
+                    tokio::time::sleep(duration).await;
+                }
+            }
+        }
+    }
+
+    fn build_price_query(&self, eic_code: &str, market: Market, period_start: &str, period_end: &str) -> String {
+        // Intraday auction results are A44 documents selected by contract type
+        let market_param = match market {
+            Market::DayAhead => "processType=A01".to_string(),
+            Market::Intraday => format!("contract_MarketAgreement.type={}", market.contract_type()),
+        };
+        format!(
+            "?securityToken={}&documentType=A44&{}&in_Domain={}&out_Domain={}&periodStart={}&periodEnd={}",
+            self.security_token,
+            market_param,
+            eic_code,
+            eic_code,
+            period_start,
+            period_end
+        )
+    }
+
+    fn build_generation_query(&self, eic_code: &str, period_start: &str, period_end: &str) -> String {
+        format!(
+            "?securityToken={}&documentType=A75&processType=A16&in_Domain={}&periodStart={}&periodEnd={}",
+            self.security_token,
+            eic_code,
+            period_start,
+            period_end
+        )
+    }
+
+    fn build_load_query(&self, eic_code: &str, period_start: &str, period_end: &str) -> String {
+        format!(
+            "?securityToken={}&documentType=A65&processType=A16&outBiddingZone_Domain={}&periodStart={}&periodEnd={}",
+            self.security_token,
+            eic_code,
+            period_start,
+            period_end
+        )
+    }
+
+    /// Flows are requested from `out_Domain` into `in_Domain`; scheduled
+    /// exchanges are limited to the total across day-ahead and intraday.
+    fn build_flow_query(&self, from_eic: &str, to_eic: &str, kind: FlowKind, period_start: &str, period_end: &str) -> String {
+        let contract_param = match kind {
+            FlowKind::Physical => "",
+            FlowKind::Scheduled => "&contract_MarketAgreement.type=A05",
+        };
+        format!(
+            "?securityToken={}&documentType={}{}&in_Domain={}&out_Domain={}&periodStart={}&periodEnd={}",
+            self.security_token,
+            kind.document_type(),
+            contract_param,
+            to_eic,
+            from_eic,
+            period_start,
+            period_end
+        )
+    }
+
+    /// `zone`'s local delivery day for `date`.
+    fn delivery_day(zone: &BiddingZone, date: NaiveDate) -> Result<DeliveryDay, EntsoeError> {
+        let timezone = zone
+            .get_timezone()
+            .map_err(EntsoeError::InvalidResponse)?;
+
+        Ok(DeliveryDay::new(date, &timezone))
+    }
+
+    /// Prices from every page of a response as one series: where pages
+    /// overlap, e.g. a day published as PT60M on one page and PT15M on the
+    /// next or a page the server repeated, each interval is kept once.
+    fn reconcile_pages(prices: Vec<Price>, zone: &BiddingZone) -> Result<Vec<Price>, EntsoeError> {
+        let mut prices = keep_finest_resolution(prices, &zone.zone_code)?;
+        prices.sort_by_key(|p| p.timestamp);
+        Ok(prices)
+    }
+
+    /// `periodStart`/`periodEnd` parameters covering `date` in the zone's local time.
+    fn delivery_period(zone: &BiddingZone, date: NaiveDate) -> Result<(String, String), EntsoeError> {
+        let day = Self::delivery_day(zone, date)?;
+        Ok((Self::format_period(&day.start), Self::format_period(&day.end)))
+    }
+
+    fn format_period(dt: &DateTime<Utc>) -> String {
+        dt.format("%Y%m%d%H%M").to_string()
+    }
+
+    fn compute_backoff_with_jitter(attempt: u32, base_delay_ms: u64) -> Duration {
+        let exp_delay = base_delay_ms * 2u64.saturating_pow(attempt);
+        let capped_delay = exp_delay.min(60_000);
+        let jitter = (capped_delay as f64 * 0.2 * rand_jitter()) as u64;
+        Duration::from_millis(capped_delay + jitter)
+    }
+}
+
+impl EntsoeClient {
+    /// GET `query` behind `breaker` and the rate limiter, parsing each 200
+    /// body with `parse`. A response holding a full page of documents is
+    /// truncated, so the following pages are requested by `offset` and their
+    /// items appended. Records fetch metrics per zone.
+    async fn get_document<T: PageItem>(
+        &self,
+        breaker: &CircuitBreaker,
+        zone: &BiddingZone,
+        query: &str,
+        parse: impl Fn(&str) -> Result<Vec<T>, EntsoeError>,
+    ) -> Result<Vec<T>, EntsoeError> {
+        trace::record_status(None);
+        if let Err(e) = breaker.check() {
+            debug!("Circuit breaker open, skipping request");
+            metrics::record_fetch_error(&zone.zone_code, "circuit_open");
+            return Err(e);
+        }
+
+        let start_time = Instant::now();
+
+        let result = self.get_pages(zone, query, parse).await;
+        if let Ok(items) = &result {
+            info!(count = items.len(), "Successfully fetched document");
+        }
+
+        let duration = start_time.elapsed();
+        metrics::record_fetch_duration(&zone.zone_code, duration);
+
+        match &result {
+            Err(e) if e.trips_circuit_breaker() => breaker.record_failure(),
+            _ => breaker.record_success(),
+        }
+
+        match &result {
+            Ok(_) => {
+                metrics::record_fetch_attempt(&zone.zone_code, "success");
+            }
+            Err(e) => {
+                let error_type = match e {
+                    EntsoeError::RateLimited => "rate_limited",
+                    EntsoeError::TemporaryUnavailable(_) => "temporary",
+                    EntsoeError::InvalidResponse(_) => "invalid_response",
+                    EntsoeError::XmlParseError(_) => "parse_error",
+                    EntsoeError::NoData => "no_data",
+                    EntsoeError::HttpError(_) => "http_error",
+                    EntsoeError::InvalidResolution(_) => "invalid_resolution",
+                    EntsoeError::UnsupportedPriceUnit(_) => "unsupported_price_unit",
+                    EntsoeError::TimestampParseError(_) => "timestamp_parse_error",
+                    EntsoeError::MissingFirstPeriod => "missing_first_period",
+                    EntsoeError::ImplausiblePrice { .. } => "implausible_price",
+                    EntsoeError::PeriodCountMismatch { .. } => "period_count_mismatch",
+                    EntsoeError::CircuitOpen { .. } => "circuit_open",
+                };
+                metrics::record_fetch_error(&zone.zone_code, error_type);
+            }
+        }
+
+        result
+    }
+
+    /// Parse the response to `query` and, while a page comes back full, the
+    /// pages after it, up to ENTSOE's largest offset or a page that repeats
+    /// earlier ones. Items are not reconciled across pages.
+    async fn get_pages<T: PageItem>(
+        &self,
+        zone: &BiddingZone,
+        query: &str,
+        parse: impl Fn(&str) -> Result<Vec<T>, EntsoeError>,
+    ) -> Result<Vec<T>, EntsoeError> {
+        let mut items = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut offset = 0;
+        loop {
+            let body = if offset == 0 {
+                self.get_with_failover(query).await?
+            } else {
+                self.get_with_failover(&format!("{}&offset={}", query, offset)).await?
+            };
+            let page = parse(&body)?;
+            let mut new_keys = 0;
+            for item in &page {
+                if seen.insert(item.page_key()) {
+                    new_keys += 1;
+                }
+            }
+            if offset > 0 && new_keys == 0 {
+                warn!(
+                    zone_code = %zone.zone_code,
+                    offset = offset,
+                    "ENTSOE page repeats earlier pages, ignoring it and the pages after it"
+                );
+                return Ok(items);
+            }
+            items.extend(page);
+
+            if document_count(&body) < DOCUMENTS_PER_PAGE {
+                return Ok(items);
+            }
+            if offset + DOCUMENTS_PER_PAGE > MAX_OFFSET {
+                warn!(
+                    zone_code = %zone.zone_code,
+                    documents = offset + DOCUMENTS_PER_PAGE,
+                    "ENTSOE response still truncated at the largest offset, request a shorter period"
+                );
+                return Ok(items);
+            }
+            offset += DOCUMENTS_PER_PAGE;
+            debug!(offset = offset, "ENTSOE response truncated, fetching next page");
+        }
+    }
+
+    /// GET `query` from the endpoints in order and return the body of a 200
+    /// response. Server and transport errors fail over to the next endpoint.
+    async fn get_with_failover(&self, query: &str) -> Result<String, EntsoeError> {
+        let mut response = Err(EntsoeError::TemporaryUnavailable("No ENTSOE endpoint configured".to_string()));
+        for (attempt, endpoint) in self.endpoint_order().into_iter().enumerate() {
+            if attempt > 0 {
+                warn!(endpoint = %endpoint.base_url, "Failing over to next ENTSOE endpoint");
+                metrics::record_endpoint_failover(&endpoint.base_url);
+            }
+            self.acquire_rate_limit_permit().await;
+
+            let (status, body) = Self::get_body(endpoint, query).await;
+            if status.is_some() {
+                trace::record_status(status);
+            }
+            response = body;
+            match &response {
+                Err(e) if e.triggers_failover() => endpoint.mark_unhealthy(self.endpoint_cooldown),
+                _ => {
+                    endpoint.mark_healthy();
+                    if response.is_ok() {
+                        trace::record_served_by(&endpoint.base_url);
+                    }
+                    break;
+                }
+            }
+        }
+        response
+    }
+
+    /// GET `query` from `endpoint` and return the body of a 200 response,
+    /// along with the HTTP status when a response was received.
+    async fn get_body(endpoint: &Endpoint, query: &str) -> (Option<u16>, Result<String, EntsoeError>) {
+        let url = format!("{}{}", endpoint.base_url, query);
+        debug!(url = %url, "Fetching document");
+
+        let response = match timing::time_to_first_byte(&endpoint.base_url, endpoint.client.get(&url).send()).await {
+            Ok(response) => response,
+            Err(e) => return (None, Err(e.into())),
+        };
+        let status = response.status();
+
+        let body = match status.as_u16() {
+            200 => response.text().await.map_err(EntsoeError::from),
+            429 => {
+                warn!("Rate limited by ENTSOE API");
+                Err(EntsoeError::RateLimited)
+            }
+            500..=599 => {
+                let body = response.text().await.unwrap_or_default();
+                error!(status = %status, body = %body, "ENTSOE API server error");
+                Err(EntsoeError::TemporaryUnavailable(format!(
+                    "HTTP {}: {}",
+                    status, body
+                )))
+            }
+            _ => {
+                let body = response.text().await.unwrap_or_default();
+                error!(status = %status, body = %body, "ENTSOE API request failed");
+                Err(EntsoeError::InvalidResponse(format!(
+                    "Unexpected HTTP status {}: {}",
+                    status, body
+                )))
+            }
+        };
+        (Some(status.as_u16()), body)
+    }
+
+    /// Run `attempt_fn` until it succeeds, retrying transient errors with
+    /// exponential backoff and jitter.
+    async fn with_retry<T, F, Fut>(&self, mut attempt_fn: F) -> Result<Vec<T>, EntsoeError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<T>, EntsoeError>>,
+    {
+        const MAX_ATTEMPTS: u32 = 4;
+        const BASE_DELAY_MS: u64 = 1000;
+
+        let mut last_error = None;
+
+        for attempt in 0..MAX_ATTEMPTS {
+            match attempt_fn().await {
+                Ok(items) => return Ok(items),
+                Err(e) if e.is_transient() => {
+                    last_error = Some(e);
+                    if attempt + 1 < MAX_ATTEMPTS {
+                        let backoff = Self::compute_backoff_with_jitter(attempt, BASE_DELAY_MS);
+                        warn!(
+                            error = %last_error.as_ref().unwrap(),
+                            attempt = attempt + 1,
+                            max_attempts = MAX_ATTEMPTS,
+                            backoff_ms = backoff.as_millis(),
+                            "Transient error, retrying with exponential backoff"
+                        );
+                        tokio::time::sleep(backoff).await;
+                    }
+                }
+                Err(e) => {
+                    error!(error = %e, "Permanent error, not retrying");
+                    return Err(e);
+                }
+            }
+        }
+
+        error!(
+            error = %last_error.as_ref().unwrap(),
+            attempts = MAX_ATTEMPTS,
+            "All retry attempts exhausted"
+        );
+        Err(last_error.unwrap())
+    }
+}
+
+#[async_trait]
+impl EntsoePriceSource for EntsoeClient {
+    #[tracing::instrument(skip(self), fields(zone_code = %zone.zone_code, date = %date, market = %market))]
+    async fn fetch_prices(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+        market: Market,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        let day = Self::delivery_day(zone, date)?;
+        let query = self.build_price_query(&zone.eic_code, market, &Self::format_period(&day.start), &Self::format_period(&day.end));
+
+        let prices = self
+            .get_document(&self.circuit_breaker, zone, &query, |body| parse_price_document(body, zone, market, &self.price_rules))
+            .await?;
+        let prices = Self::reconcile_pages(prices, zone)?;
+        check_hour_count(&prices, &zone.zone_code, &day);
+        Ok(prices)
+    }
+
+    #[tracing::instrument(skip(self), fields(zone_code = %zone.zone_code, date = %date))]
+    async fn fetch_generation(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<GenerationPoint>, EntsoeError> {
+        let (period_start, period_end) = Self::delivery_period(zone, date)?;
+        let query = self.build_generation_query(&zone.eic_code, &period_start, &period_end);
+
+        self.get_document(&self.actuals_circuit_breaker, zone, &query, |body| parse_generation_document(body, &zone.zone_code))
+            .await
+    }
+
+    #[tracing::instrument(skip(self), fields(zone_code = %zone.zone_code, date = %date))]
+    async fn fetch_load(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<LoadValue>, EntsoeError> {
+        let (period_start, period_end) = Self::delivery_period(zone, date)?;
+        let query = self.build_load_query(&zone.eic_code, &period_start, &period_end);
+
+        self.get_document(&self.actuals_circuit_breaker, zone, &query, |body| parse_load_document(body, &zone.zone_code))
+            .await
+    }
+
+    #[tracing::instrument(skip(self), fields(from = %from.zone_code, to = %to.zone_code, date = %date, kind = %kind))]
+    async fn fetch_flows(
+        &self,
+        from: &BiddingZone,
+        to: &BiddingZone,
+        date: NaiveDate,
+        kind: FlowKind,
+    ) -> Result<Vec<CrossBorderFlow>, EntsoeError> {
+        let (period_start, period_end) = Self::delivery_period(from, date)?;
+        let query = self.build_flow_query(&from.eic_code, &to.eic_code, kind, &period_start, &period_end);
+
+        self.with_retry(|| {
+            self.get_document(&self.actuals_circuit_breaker, from, &query, |body| parse_flow_document(body, &from.zone_code, &to.zone_code, kind))
+        })
+        .await
+    }
+
+    fn maintenance_window(&self, at: DateTime<Utc>) -> Option<MaintenanceWindow> {
+        self.circuit_breaker.maintenance_window(at).cloned()
+    }
+
+    fn price_rules(&self) -> PriceRules {
+        self.price_rules
+    }
+
+    #[tracing::instrument(skip(self), fields(zone_code = %zone.zone_code, date = %date, market = %market))]
+    async fn fetch_prices_with_retry(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+        market: Market,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        self.with_retry(|| self.fetch_prices(zone, date, market)).await
+    }
+
+    /// One request per period of up to a year, following offsets when
+    /// ENTSOE truncates the response.
+    #[tracing::instrument(skip(self), fields(zone_code = %zone.zone_code, start = %start, end = %end))]
+    async fn fetch_day_ahead_prices_range(
+        &self,
+        zone: &BiddingZone,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        let mut prices = Vec::new();
+        let mut chunk_start = start;
+        while chunk_start <= end {
+            let chunk_end = end.min(chunk_start + chrono::Duration::days(MAX_REQUEST_DAYS - 1));
+            let period_start = Self::format_period(&Self::delivery_day(zone, chunk_start)?.start);
+            let period_end = Self::format_period(&Self::delivery_day(zone, chunk_end)?.end);
+            let query = self.build_price_query(&zone.eic_code, Market::DayAhead, &period_start, &period_end);
+
+            let chunk = self
+                .with_retry(|| {
+                    self.get_document(&self.circuit_breaker, zone, &query, |body| {
+                        parse_price_document(body, zone, Market::DayAhead, &self.price_rules)
+                    })
+                })
+                .await?;
+            let chunk = Self::reconcile_pages(chunk, zone)?;
+            for date in chunk_start.iter_days().take_while(|date| *date <= chunk_end) {
+                let day = Self::delivery_day(zone, date)?;
+                if chunk.iter().any(|p| p.timestamp >= day.start && p.timestamp < day.end) {
+                    check_hour_count(&chunk, &zone.zone_code, &day);
+                }
+            }
+            prices.extend(chunk);
+            chunk_start = chunk_end.succ_opt().expect("date before the end of time");
+        }
+        Ok(prices)
+    }
+
+    #[tracing::instrument(skip(self), fields(zone_code = %zone.zone_code, date = %date))]
+    async fn fetch_load_with_retry(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<LoadValue>, EntsoeError> {
+        self.with_retry(|| self.fetch_load(zone, date)).await
+    }
+}
+
+/// Documents in an ENTSOE response body, counted by their TimeSeries.
+fn document_count(body: &str) -> usize {
+    body.matches("<TimeSeries>").count()
+}
+
+fn rand_jitter() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    let state = RandomState::new();
+    let mut hasher = state.build_hasher();
+    hasher.write_u64(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64);
+    (hasher.finish() % 1000) as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GapFillStrategy, PriceBounds};
+    use crate::entsoe::CircuitState;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_format_period() {
+        let dt = Utc.with_ymd_and_hms(2025, 12, 31, 23, 0, 0).unwrap();
+        assert_eq!(EntsoeClient::format_period(&dt), "202512312300");
+    }
+
+    fn config(base_url: String, fallback_base_urls: Vec<String>) -> EntsoeConfig {
+        EntsoeConfig {
+            security_token: "token".to_string(),
+            base_url,
+            fallback_base_urls,
+            rate_limit_per_minute: 60,
+            timeout_seconds: 5,
+            circuit_breaker_failure_threshold: 5,
+            circuit_breaker_cooldown_seconds: 60,
+            fetch_intraday: false,
+            maintenance_windows: Vec::new(),
+            flow_pairs: Vec::new(),
+            price_bounds: PriceBounds::default(),
+            gap_fill: GapFillStrategy::default(),
+        }
+    }
+
+    #[test]
+    fn test_build_url_selects_market() {
+        let client = EntsoeClient::new(&config("https://example.test/api".to_string(), Vec::new())).unwrap();
+
+        let day_ahead = client.build_price_query("10YNO-1--------2", Market::DayAhead, "a", "b");
+        assert!(day_ahead.contains("documentType=A44&processType=A01&"));
+
+        let intraday = client.build_price_query("10YNO-1--------2", Market::Intraday, "a", "b");
+        assert!(intraday.contains("documentType=A44&contract_MarketAgreement.type=A07&"));
+        assert!(!intraday.contains("processType"));
+
+        let generation = client.build_generation_query("10YNO-1--------2", "a", "b");
+        assert!(generation.contains("documentType=A75&processType=A16&in_Domain=10YNO-1--------2&"));
+        assert!(!generation.contains("out_Domain"));
+
+        let load = client.build_load_query("10YNO-1--------2", "a", "b");
+        assert!(load.contains("documentType=A65&processType=A16&outBiddingZone_Domain=10YNO-1--------2&"));
+
+        let physical = client.build_flow_query("10YNO-1--------2", "10Y1001A1001A46L", FlowKind::Physical, "a", "b");
+        assert!(physical.contains("documentType=A11&in_Domain=10Y1001A1001A46L&out_Domain=10YNO-1--------2&"));
+        let scheduled = client.build_flow_query("10YNO-1--------2", "10Y1001A1001A46L", FlowKind::Scheduled, "a", "b");
+        assert!(scheduled.contains("documentType=A09&contract_MarketAgreement.type=A05&in_Domain="));
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_backup_endpoint_and_skips_failed_one() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use axum::{http::StatusCode, routing::get, Router};
+
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let calls = Arc::clone(&primary_calls);
+        let no_data = r#"<Acknowledgement_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+  <Reason><code>999</code><text>No matching data found</text></Reason>
+</Acknowledgement_MarketDocument>"#;
+        let app = Router::new()
+            .route(
+                "/primary",
+                get(move || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    StatusCode::SERVICE_UNAVAILABLE
+                }),
+            )
+            .route("/backup", get(move || async move { no_data }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let backup = format!("http://{}/backup", addr);
+        let client = EntsoeClient::new(&config(format!("http://{}/primary", addr), vec![backup.clone()])).unwrap();
+        let zone = BiddingZone::provisional("10YNO-1--------2", "Europe/Oslo");
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let (prices, trace) = trace::traced(client.fetch_prices(&zone, date, Market::DayAhead)).await;
+        assert!(prices.unwrap().is_empty());
+        assert_eq!(trace.served_by, std::collections::BTreeSet::from([backup]));
+        assert_eq!(trace.http_status, Some(200));
+        assert_eq!(client.circuit_breaker().status().consecutive_failures, 0);
+
+        client.fetch_prices(&zone, date, Market::DayAhead).await.unwrap();
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+    }
+
+    /// A44 TimeSeries covering the UTC day `date` at `minutes` resolution.
+    fn price_series(date: NaiveDate, minutes: i64) -> String {
+        let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let points: String = (1..=24 * 60 / minutes)
+            .map(|pos| format!("<Point><position>{}</position><price.amount>40</price.amount></Point>", pos))
+            .collect();
+        format!(
+            "<TimeSeries><currency_Unit.name>EUR</currency_Unit.name><price_Measure_Unit.name>MWH</price_Measure_Unit.name>\
+             <Period><timeInterval><start>{}</start><end>{}</end></timeInterval><resolution>PT{}M</resolution>{}</Period></TimeSeries>",
+            start.format("%Y-%m-%dT%H:%MZ"),
+            (start + chrono::Duration::days(1)).format("%Y-%m-%dT%H:%MZ"),
+            minutes,
+            points
+        )
+    }
+
+    /// A44 document holding `series`.
+    fn price_document(series: impl IntoIterator<Item = String>) -> String {
+        format!(
+            r#"<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">{}</Publication_MarketDocument>"#,
+            series.into_iter().collect::<String>()
+        )
+    }
+
+    /// A44 document with one 24-hour TimeSeries per UTC day from `first`.
+    fn price_page(first: NaiveDate, days: u64) -> String {
+        price_document(first.iter_days().take(days as usize).map(|date| price_series(date, 60)))
+    }
+
+    #[tokio::test]
+    async fn test_price_range_follows_offset_past_a_full_page() {
+        use std::collections::HashMap;
+
+        use axum::{extract::Query, routing::get, Router};
+
+        let first = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let offsets = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let requested = Arc::clone(&offsets);
+        let app = Router::new().route(
+            "/api",
+            get(move |Query(params): Query<HashMap<String, String>>| async move {
+                let offset: u64 = params.get("offset").map_or(0, |offset| offset.parse().unwrap());
+                requested.lock().unwrap().push(offset);
+                match offset {
+                    0 => price_page(first, 100),
+                    100 => price_page(first + chrono::Duration::days(100), 5),
+                    _ => price_page(first, 0),
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = EntsoeClient::new(&config(format!("http://{}/api", addr), Vec::new())).unwrap();
+        let zone = BiddingZone::provisional("10YNO-1--------2", "UTC");
+        let last = first + chrono::Duration::days(104);
+
+        let prices = client.fetch_day_ahead_prices_range(&zone, first, last).await.unwrap();
+        assert_eq!(prices.len(), 105 * 24);
+        assert_eq!(prices.last().unwrap().timestamp, last.and_hms_opt(23, 0, 0).unwrap().and_utc());
+        assert_eq!(*offsets.lock().unwrap(), vec![0, 100]);
+    }
+
+    #[tokio::test]
+    async fn test_price_range_reconciles_overlapping_pages() {
+        use std::collections::{HashMap, HashSet};
+
+        use axum::{extract::Query, routing::get, Router};
+
+        let first = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let split = first + chrono::Duration::days(99);
+        let offsets = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let requested = Arc::clone(&offsets);
+        let app = Router::new().route(
+            "/api",
+            get(move |Query(params): Query<HashMap<String, String>>| async move {
+                let offset: u64 = params.get("offset").map_or(0, |offset| offset.parse().unwrap());
+                requested.lock().unwrap().push(offset);
+                match offset {
+                    // The last day of the first page again, at PT15M
+                    100 => price_document(
+                        std::iter::once(price_series(split, 15))
+                            .chain((100..199).map(|day| price_series(first + chrono::Duration::days(day), 60))),
+                    ),
+                    // A server ignoring the offset repeats the first page
+                    _ => price_page(first, 100),
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = EntsoeClient::new(&config(format!("http://{}/api", addr), Vec::new())).unwrap();
+        let zone = BiddingZone::provisional("10YNO-1--------2", "UTC");
+        let last = first + chrono::Duration::days(198);
+
+        let prices = client.fetch_day_ahead_prices_range(&zone, first, last).await.unwrap();
+        assert_eq!(*offsets.lock().unwrap(), vec![0, 100, 200]);
+        assert_eq!(prices.len(), 198 * 24 + 96);
+        let timestamps: HashSet<_> = prices.iter().map(|p| p.timestamp).collect();
+        assert_eq!(timestamps.len(), prices.len());
+        let split_start = split.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        assert!(prices
+            .iter()
+            .filter(|p| p.timestamp >= split_start && p.timestamp < split_start + chrono::Duration::days(1))
+            .all(|p| p.resolution == "PT15M"));
+    }
+
+    #[tokio::test]
+    async fn test_transport_error_records_duration_and_error() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let client = EntsoeClient::new(&config(format!("http://{}/api", closed), Vec::new())).unwrap();
+        let zone = BiddingZone::provisional("10YNO-1--------2", "Europe/Oslo");
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        assert!(client.fetch_prices(&zone, date, Market::DayAhead).await.is_err());
+        assert_eq!(client.circuit_breaker().status().consecutive_failures, 1);
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let value = |name: &str| {
+            snapshot
+                .iter()
+                .find(|(key, _, _, _)| key.key().name() == name)
+                .map(|(_, _, _, value)| value)
+        };
+        assert!(matches!(value(metrics::ENTSOE_FETCH_DURATION_SECONDS), Some(DebugValue::Histogram(values)) if values.len() == 1));
+        assert!(matches!(value(metrics::ENTSOE_FETCH_ERRORS_TOTAL), Some(DebugValue::Counter(1))));
+    }
+
+    #[tokio::test]
+    async fn test_generation_failures_leave_the_price_breaker_closed() {
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let mut config = config(format!("http://{}/api", closed), Vec::new());
+        config.circuit_breaker_failure_threshold = 1;
+        let client = EntsoeClient::new(&config).unwrap();
+        let zone = BiddingZone::provisional("10YNO-1--------2", "Europe/Oslo");
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        assert!(client.fetch_generation(&zone, date).await.is_err());
+        assert!(matches!(client.fetch_load(&zone, date).await, Err(EntsoeError::CircuitOpen { .. })));
+        assert_eq!(client.circuit_breaker().status().state, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_probe_accepts_any_http_response() {
+        use axum::{http::StatusCode, routing::get, Router};
+
+        let app = Router::new().route("/api", get(|| async { StatusCode::UNAUTHORIZED }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = EntsoeClient::new(&config(format!("http://{}/api", addr), Vec::new())).unwrap();
+        assert!(client.probe(Duration::from_secs(1)).await.is_ok());
+
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let client = EntsoeClient::new(&config(format!("http://{}/api", closed), Vec::new())).unwrap();
+        assert!(client.probe(Duration::from_secs(1)).await.is_err());
+    }
+
+    #[test]
+    fn test_delivery_period_follows_dst() {
+        let zone = BiddingZone::provisional("10YDE-EON------1", "Europe/Berlin");
+        let winter = EntsoeClient::delivery_period(&zone, NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()).unwrap();
+        assert_eq!(winter, ("202501142300".to_string(), "202501152300".to_string()));
+        let spring = EntsoeClient::delivery_period(&zone, NaiveDate::from_ymd_opt(2025, 3, 30).unwrap()).unwrap();
+        assert_eq!(spring, ("202503292300".to_string(), "202503302200".to_string()));
+    }
+}
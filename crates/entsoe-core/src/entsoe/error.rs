@@ -23,12 +23,25 @@ pub enum EntsoeError {
     #[error("Invalid resolution format: {0}")]
     InvalidResolution(String),
 
+    #[error("Unsupported price unit: {0}")]
+    UnsupportedPriceUnit(String),
+
     #[error("Failed to parse timestamp: {0}")]
     TimestampParseError(String),
 
     #[error("Missing first period point at position 1, cannot forward-fill")]
     MissingFirstPeriod,
 
+    #[error("Implausible price {amount} for {bidding_zone} at {timestamp} rejected, cannot fill")]
+    ImplausiblePrice {
+        bidding_zone: String,
+        timestamp: String,
+        amount: f64,
+    },
+
+    #[error("ENTSOE circuit breaker open, retry in {retry_in_seconds}s")]
+    CircuitOpen { retry_in_seconds: u64 },
+
     #[error("Period validation failed: expected {expected} points, interval {start} to {end}")]
     PeriodCountMismatch {
         expected: usize,
@@ -41,4 +54,16 @@ impl EntsoeError {
     pub fn is_transient(&self) -> bool {
         matches!(self, Self::RateLimited | Self::TemporaryUnavailable(_))
     }
+
+    /// Whether the error indicates the upstream API is unhealthy and should
+    /// count towards opening the circuit breaker.
+    pub fn trips_circuit_breaker(&self) -> bool {
+        self.is_transient() || matches!(self, Self::HttpError(_))
+    }
+
+    /// Whether the same request should be retried against the next
+    /// configured endpoint.
+    pub fn triggers_failover(&self) -> bool {
+        matches!(self, Self::TemporaryUnavailable(_) | Self::HttpError(_))
+    }
 }
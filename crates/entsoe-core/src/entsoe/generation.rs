@@ -0,0 +1,171 @@
+use chrono::Utc;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::models::GenerationPoint;
+
+use super::error::EntsoeError;
+use super::xml::{parse_document, QuantityPeriod};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename = "GL_MarketDocument")]
+pub struct GlMarketDocument {
+    #[serde(rename = "TimeSeries", default)]
+    pub time_series: Vec<GenerationTimeSeries>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GenerationTimeSeries {
+    #[serde(rename = "MktPSRType")]
+    pub psr_type: Option<MktPsrType>,
+    /// Set on consumption series (e.g. pumped storage load), which are skipped.
+    #[serde(rename = "outBiddingZone_Domain.mRID", default)]
+    pub out_bidding_zone: Option<Domain>,
+    #[serde(rename = "Period", default)]
+    pub periods: Vec<QuantityPeriod>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MktPsrType {
+    #[serde(rename = "psrType")]
+    pub psr_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Domain {
+    #[serde(rename = "$text", default)]
+    #[allow(dead_code)]
+    pub value: String,
+}
+
+/// Parse an ENTSOE A75 (actual generation per production type) response body.
+/// Acknowledgement documents with reason code 999 (no data) yield an empty list.
+pub fn parse_generation_document(body: &str, zone_code: &str) -> Result<Vec<GenerationPoint>, EntsoeError> {
+    parse_document(body, |doc: GlMarketDocument| doc.extract_generation(zone_code))
+}
+
+impl GlMarketDocument {
+    /// Generation points for every production type, at the published resolution.
+    /// Missing positions are forward-filled.
+    pub fn extract_generation(&self, bidding_zone: &str) -> Result<Vec<GenerationPoint>, EntsoeError> {
+        let fetched_at = Utc::now();
+        let mut points = Vec::new();
+
+        for time_series in &self.time_series {
+            if time_series.out_bidding_zone.is_some() {
+                debug!(bidding_zone = %bidding_zone, "Skipping consumption time series");
+                continue;
+            }
+            let Some(psr) = &time_series.psr_type else {
+                return Err(EntsoeError::InvalidResponse(
+                    "Generation time series without MktPSRType".to_string(),
+                ));
+            };
+
+            for period in &time_series.periods {
+                points.extend(period.filled_quantities()?.into_iter().map(|(timestamp, quantity_mw)| {
+                    GenerationPoint {
+                        timestamp,
+                        bidding_zone: bidding_zone.to_string(),
+                        psr_type: psr.psr_type.clone(),
+                        quantity_mw,
+                        resolution: period.resolution.clone(),
+                        fetched_at,
+                    }
+                }));
+            }
+        }
+
+        points.sort_by(|a, b| (a.timestamp, &a.psr_type).cmp(&(b.timestamp, &b.psr_type)));
+
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::*;
+    use crate::entsoe::xml::parse_timestamp;
+
+    const GENERATION_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+  <mRID>abc</mRID>
+  <TimeSeries>
+    <mRID>1</mRID>
+    <inBiddingZone_Domain.mRID codingScheme="A01">10YNO-1--------2</inBiddingZone_Domain.mRID>
+    <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+    <MktPSRType><psrType>B12</psrType></MktPSRType>
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T02:00Z</end></timeInterval>
+      <resolution>PT60M</resolution>
+      <Point><position>1</position><quantity>1200</quantity></Point>
+      <Point><position>3</position><quantity>1250.5</quantity></Point>
+    </Period>
+  </TimeSeries>
+  <TimeSeries>
+    <mRID>2</mRID>
+    <outBiddingZone_Domain.mRID codingScheme="A01">10YNO-1--------2</outBiddingZone_Domain.mRID>
+    <MktPSRType><psrType>B10</psrType></MktPSRType>
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T02:00Z</end></timeInterval>
+      <resolution>PT60M</resolution>
+      <Point><position>1</position><quantity>50</quantity></Point>
+    </Period>
+  </TimeSeries>
+  <TimeSeries>
+    <mRID>3</mRID>
+    <inBiddingZone_Domain.mRID codingScheme="A01">10YNO-1--------2</inBiddingZone_Domain.mRID>
+    <MktPSRType><psrType>B19</psrType></MktPSRType>
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T02:00Z</end></timeInterval>
+      <resolution>PT60M</resolution>
+      <Point><position>1</position><quantity>10</quantity></Point>
+      <Point><position>2</position><quantity>20</quantity></Point>
+      <Point><position>3</position><quantity>30</quantity></Point>
+    </Period>
+  </TimeSeries>
+</GL_MarketDocument>"#;
+
+    #[test]
+    fn test_parse_generation_skips_consumption_and_forward_fills() {
+        let points = parse_generation_document(GENERATION_XML, "NO1").unwrap();
+
+        assert_eq!(points.len(), 6);
+        assert!(points.iter().all(|p| p.psr_type != "B10"));
+
+        let hydro: Vec<Decimal> = points
+            .iter()
+            .filter(|p| p.psr_type == "B12")
+            .map(|p| p.quantity_mw)
+            .collect();
+        assert_eq!(
+            hydro,
+            vec![Decimal::from(1200), Decimal::from(1200), Decimal::new(12505, 1)]
+        );
+        assert_eq!(points[0].timestamp, parse_timestamp("2025-01-14T23:00Z").unwrap());
+        assert_eq!(points[0].psr_type, "B12");
+        assert_eq!(points[1].psr_type, "B19");
+    }
+
+    #[test]
+    fn test_parse_generation_missing_first_position() {
+        let xml = GENERATION_XML.replace(
+            "<Point><position>1</position><quantity>1200</quantity></Point>",
+            "",
+        );
+        assert!(matches!(
+            parse_generation_document(&xml, "NO1"),
+            Err(EntsoeError::MissingFirstPeriod)
+        ));
+    }
+
+    #[test]
+    fn test_parse_generation_no_data_acknowledgement() {
+        let xml = r#"<Acknowledgement_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+  <Reason><code>999</code><text>No matching data found</text></Reason>
+</Acknowledgement_MarketDocument>"#;
+        assert!(parse_generation_document(xml, "NO1").unwrap().is_empty());
+    }
+}
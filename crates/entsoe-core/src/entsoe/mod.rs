@@ -0,0 +1,25 @@
+#[cfg(any(test, feature = "test-utils"))]
+mod canned;
+mod circuit_breaker;
+mod client;
+mod error;
+mod generation;
+mod source;
+mod timing;
+mod trace;
+mod validation;
+mod xml;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use canned::CannedEntsoeSource;
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerStatus, CircuitState};
+pub use client::{EntsoeClient, MAX_REQUEST_DAYS};
+pub use error::EntsoeError;
+pub use generation::parse_generation_document;
+pub use source::{
+    parse_flow_document, parse_load_document, parse_price_document, parse_price_document_all_markets,
+    price_document_domain, EntsoePriceSource,
+};
+pub use trace::{traced, RequestTrace};
+pub use validation::validate_and_fill_period;
+pub use xml::{parse_resolution, PriceUnit};
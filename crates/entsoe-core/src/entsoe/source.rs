@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::config::{MaintenanceWindow, PriceRules};
+use crate::models::{BiddingZone, CrossBorderFlow, FlowKind, GenerationPoint, LoadValue, Market, Price};
+
+use super::error::EntsoeError;
+use super::xml::{parse_document, FlowMarketDocument, LoadMarketDocument, PublicationMarketDocument};
+
+/// Source of auction prices for a zone, delivery date and market, and of
+/// actual generation per production type, total load and cross-border flows.
+///
+/// Implemented by [`EntsoeClient`](super::EntsoeClient) for the live API; tests
+/// and downstream users can provide their own implementation (see
+/// `CannedEntsoeSource` behind the `test-utils` feature).
+#[async_trait]
+pub trait EntsoePriceSource: Send + Sync {
+    async fn fetch_prices(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+        market: Market,
+    ) -> Result<Vec<Price>, EntsoeError>;
+
+    /// Fetch with the implementation's retry policy. Defaults to a single attempt.
+    async fn fetch_prices_with_retry(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+        market: Market,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        self.fetch_prices(zone, date, market).await
+    }
+
+    async fn fetch_day_ahead_prices(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        self.fetch_prices(zone, date, Market::DayAhead).await
+    }
+
+    async fn fetch_day_ahead_prices_with_retry(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        self.fetch_prices_with_retry(zone, date, Market::DayAhead).await
+    }
+
+    /// Day-ahead prices for each delivery date from `start` to `end`
+    /// inclusive, with the implementation's retry policy. Defaults to one
+    /// request per date, skipping dates without data.
+    async fn fetch_day_ahead_prices_range(
+        &self,
+        zone: &BiddingZone,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        let mut prices = Vec::new();
+        for date in start.iter_days().take_while(|date| *date <= end) {
+            match self.fetch_day_ahead_prices_with_retry(zone, date).await {
+                Ok(day) => prices.extend(day),
+                Err(EntsoeError::NoData) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(prices)
+    }
+
+    /// Actual generation per production type (A75) for a zone's delivery date.
+    async fn fetch_generation(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<GenerationPoint>, EntsoeError>;
+
+    /// Actual total load (A65) for a zone's delivery date.
+    async fn fetch_load(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<LoadValue>, EntsoeError>;
+
+    /// Fetch load with the implementation's retry policy. Defaults to a single attempt.
+    async fn fetch_load_with_retry(
+        &self,
+        zone: &BiddingZone,
+        date: NaiveDate,
+    ) -> Result<Vec<LoadValue>, EntsoeError> {
+        self.fetch_load(zone, date).await
+    }
+
+    /// Flows of `kind` from `from` to `to` for a delivery date in `from`'s
+    /// local time.
+    async fn fetch_flows(
+        &self,
+        from: &BiddingZone,
+        to: &BiddingZone,
+        date: NaiveDate,
+        kind: FlowKind,
+    ) -> Result<Vec<CrossBorderFlow>, EntsoeError>;
+
+    /// Announced maintenance window covering `at`; scheduled jobs wait for it
+    /// to end. Defaults to none.
+    fn maintenance_window(&self, _at: DateTime<Utc>) -> Option<MaintenanceWindow> {
+        None
+    }
+
+    /// Plausibility bounds and gap-fill strategy applied to parsed prices,
+    /// also when re-processing stored documents. Defaults to
+    /// [`PriceRules::default`].
+    fn price_rules(&self) -> PriceRules {
+        PriceRules::default()
+    }
+}
+
+/// Parse an ENTSOE A44 response body into `zone`'s prices for `market`.
+/// Acknowledgement documents with reason code 999 (no data) yield an empty list.
+pub fn parse_price_document(
+    body: &str,
+    zone: &BiddingZone,
+    market: Market,
+    rules: &PriceRules,
+) -> Result<Vec<Price>, EntsoeError> {
+    parse_document(body, |doc: PublicationMarketDocument| doc.extract_prices(zone, market, rules))
+}
+
+/// EIC code of the area an A44 response body is for, from its
+/// `in_Domain.mRID`. `None` for documents without time series, such as
+/// no-data acknowledgements.
+pub fn price_document_domain(body: &str) -> Result<Option<String>, EntsoeError> {
+    let domains = parse_document(body, |doc: PublicationMarketDocument| {
+        if doc.time_series.is_empty() {
+            return Ok(Vec::new());
+        }
+        doc.in_domain()
+            .map(|eic_code| vec![eic_code.to_string()])
+            .ok_or_else(|| EntsoeError::InvalidResponse("Price document has no in_Domain.mRID".to_string()))
+    })?;
+    Ok(domains.into_iter().next())
+}
+
+/// Parse an ENTSOE A44 response body into `zone`'s prices for every market
+/// it contains, as when re-processing stored documents.
+pub fn parse_price_document_all_markets(
+    body: &str,
+    zone: &BiddingZone,
+    rules: &PriceRules,
+) -> Result<Vec<Price>, EntsoeError> {
+    parse_document(body, |doc: PublicationMarketDocument| {
+        let mut prices = Vec::new();
+        for market in doc.markets() {
+            prices.extend(doc.extract_prices(zone, market, rules)?);
+        }
+        Ok(prices)
+    })
+}
+
+/// Parse an ENTSOE A65 response body into actual total load values.
+/// Acknowledgement documents with reason code 999 (no data) yield an empty list.
+pub fn parse_load_document(body: &str, zone_code: &str) -> Result<Vec<LoadValue>, EntsoeError> {
+    parse_document(body, |doc: LoadMarketDocument| doc.extract_load(zone_code))
+}
+
+/// Parse an ENTSOE A09/A11 response body into flows from `from_zone` to `to_zone`.
+/// Acknowledgement documents with reason code 999 (no data) yield an empty list.
+pub fn parse_flow_document(body: &str, from_zone: &str, to_zone: &str, kind: FlowKind) -> Result<Vec<CrossBorderFlow>, EntsoeError> {
+    parse_document(body, |doc: FlowMarketDocument| doc.extract_flows(from_zone, to_zone, kind))
+}
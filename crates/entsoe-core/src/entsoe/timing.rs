@@ -0,0 +1,187 @@
+//! Connection-level timing for ENTSOE requests: DNS lookups, connection
+//! setup and time to first byte, recorded per endpoint so slow fetches can
+//! be attributed to the network or to upstream processing.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use tower::{Layer, Service};
+
+use crate::metrics;
+
+tokio::task_local! {
+    /// DNS time spent inside the connection attempt being polled.
+    static DNS_TIME: Arc<Mutex<Duration>>;
+    /// Connection setup time spent inside the request being polled.
+    static CONNECTION_SETUP: Arc<Mutex<Duration>>;
+}
+
+fn add(slot: &Arc<Mutex<Duration>>, elapsed: Duration) {
+    *slot.lock().unwrap() += elapsed;
+}
+
+/// Resolves hosts through the system resolver and records the lookup time.
+pub(super) struct TimingResolver {
+    endpoint: String,
+}
+
+impl TimingResolver {
+    pub(super) fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+        }
+    }
+}
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let endpoint = self.endpoint.clone();
+        Box::pin(async move {
+            let start = Instant::now();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            let elapsed = start.elapsed();
+            metrics::record_http_phase(&endpoint, "dns", elapsed);
+            let _ = DNS_TIME.try_with(|dns| add(dns, elapsed));
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Connector layer recording TCP connect plus TLS handshake time for new
+/// connections. DNS time is excluded as [`TimingResolver`] records it.
+#[derive(Clone)]
+pub(super) struct ConnectTimingLayer {
+    endpoint: Arc<str>,
+}
+
+impl ConnectTimingLayer {
+    pub(super) fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+        }
+    }
+}
+
+impl<S> Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTiming {
+            inner,
+            endpoint: self.endpoint.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct ConnectTiming<S> {
+    inner: S,
+    endpoint: Arc<str>,
+}
+
+impl<S, R> Service<R> for ConnectTiming<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let endpoint = self.endpoint.clone();
+        let connecting = self.inner.call(request);
+        Box::pin(async move {
+            let dns = Arc::new(Mutex::new(Duration::ZERO));
+            let start = Instant::now();
+            let result = DNS_TIME.scope(dns.clone(), connecting).await;
+            let elapsed = start.elapsed();
+            if result.is_ok() {
+                let dns = *dns.lock().unwrap();
+                metrics::record_http_phase(&endpoint, "connect", elapsed.saturating_sub(dns));
+            }
+            // Absent when the connection was established in the background
+            // and the request was served from the pool instead
+            let _ = CONNECTION_SETUP.try_with(|setup| add(setup, elapsed));
+            result
+        })
+    }
+}
+
+/// Await `send`, recording the time to the response headers less any
+/// connection setup it waited for.
+pub(super) async fn time_to_first_byte<T, E>(
+    endpoint: &str,
+    send: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let setup = Arc::new(Mutex::new(Duration::ZERO));
+    let start = Instant::now();
+    let result = CONNECTION_SETUP.scope(setup.clone(), send).await;
+    if result.is_ok() {
+        let setup = *setup.lock().unwrap();
+        metrics::record_http_phase(endpoint, "ttfb", start.elapsed().saturating_sub(setup));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{routing::get, Router};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_records_connection_phases_per_endpoint() {
+        let app = Router::new().route("/api", get(|| async { "ok" }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+
+        let endpoint = format!("http://localhost:{}/api", port);
+        let client = reqwest::Client::builder()
+            .dns_resolver(Arc::new(TimingResolver::new(&endpoint)))
+            .connector_layer(ConnectTimingLayer::new(&endpoint))
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            let response = time_to_first_byte(&endpoint, client.get(&endpoint).send()).await.unwrap();
+            assert_eq!(response.text().await.unwrap(), "ok");
+        }
+
+        let samples: HashMap<String, usize> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, _, _, _)| key.key().name() == metrics::ENTSOE_HTTP_PHASE_DURATION_SECONDS)
+            .filter_map(|(key, _, _, value)| {
+                let labels: HashMap<&str, &str> = key.key().labels().map(|l| (l.key(), l.value())).collect();
+                assert_eq!(labels["endpoint"], endpoint);
+                match value {
+                    DebugValue::Histogram(values) => Some((labels["phase"].to_string(), values.len())),
+                    _ => None,
+                }
+            })
+            .collect();
+        // The second request reuses the pooled connection
+        assert_eq!(
+            samples,
+            HashMap::from([("dns".to_string(), 1), ("connect".to_string(), 1), ("ttfb".to_string(), 2)])
+        );
+    }
+}
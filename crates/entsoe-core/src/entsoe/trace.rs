@@ -0,0 +1,73 @@
+//! What answered the ENTSOE requests made inside one fetch: the endpoints
+//! that served them and the HTTP status of the latest response, recorded in
+//! the fetch log. Kept per fetch rather than per zone, so concurrent fetches
+//! for the same zone (another date, the intraday market) cannot overwrite
+//! each other's.
+
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+tokio::task_local! {
+    static TRACE: Arc<Mutex<RequestTrace>>;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestTrace {
+    /// Base URLs that served a successful request.
+    pub served_by: BTreeSet<String>,
+    /// HTTP status of the latest response, `None` when the latest request
+    /// got no response or was never sent.
+    pub http_status: Option<u16>,
+}
+
+/// Run `fetch`, returning its output along with the requests it made. The
+/// endpoints are also added to the trace of an enclosing `traced` call, so
+/// a run spanning several zones reports every endpoint that served it.
+pub async fn traced<F: Future>(fetch: F) -> (F::Output, RequestTrace) {
+    let trace = Arc::new(Mutex::new(RequestTrace::default()));
+    let output = TRACE.scope(Arc::clone(&trace), fetch).await;
+    let trace = trace.lock().unwrap().clone();
+    let _ = TRACE.try_with(|outer| outer.lock().unwrap().served_by.extend(trace.served_by.iter().cloned()));
+    (output, trace)
+}
+
+/// Record the status of a response, or `None` before a new request.
+pub(super) fn record_status(status: Option<u16>) {
+    let _ = TRACE.try_with(|trace| trace.lock().unwrap().http_status = status);
+}
+
+/// Record that `base_url` served a request.
+pub(super) fn record_served_by(base_url: &str) {
+    let _ = TRACE.try_with(|trace| {
+        trace.lock().unwrap().served_by.insert(base_url.to_string());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_concurrent_fetches_keep_their_own_trace() {
+        let fetch = |base_url: &'static str, status: u16| async move {
+            record_status(Some(status));
+            tokio::task::yield_now().await;
+            record_served_by(base_url);
+        };
+        let ((first, second), run) = traced(async {
+            let (first, second) = tokio::join!(traced(fetch("https://a.test", 200)), traced(fetch("https://b.test", 503)));
+            (first.1, second.1)
+        })
+        .await;
+
+        assert_eq!(first.http_status, Some(200));
+        assert_eq!(first.served_by, BTreeSet::from(["https://a.test".to_string()]));
+        assert_eq!(second.http_status, Some(503));
+        assert_eq!(second.served_by, BTreeSet::from(["https://b.test".to_string()]));
+        assert_eq!(run.served_by.len(), 2);
+
+        // Outside a trace nothing is recorded
+        record_status(Some(200));
+    }
+}
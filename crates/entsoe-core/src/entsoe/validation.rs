@@ -0,0 +1,510 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+use rust_decimal::Decimal;
+use tracing::{debug, warn};
+
+use crate::config::{GapFillStrategy, ImplausiblePriceAction, PriceRules};
+use crate::metrics;
+use crate::models::{DeliveryDay, EnergyUnit, GapFill, Price, RawPricePoint};
+
+use super::error::EntsoeError;
+use super::xml::{parse_resolution, parse_timestamp, Period, PriceUnit};
+
+/// Decimal places interpolated amounts are rounded to.
+const INTERPOLATED_DECIMALS: u32 = 6;
+
+/// Calculate expected number of periods for an interval and resolution
+pub fn expected_period_count(start: DateTime<Utc>, end: DateTime<Utc>, resolution: Duration) -> usize {
+    let interval_duration = end - start;
+    (interval_duration.num_seconds() / resolution.num_seconds()) as usize
+}
+
+/// Validate a period's points and fill its gaps by `rules.gap_fill`.
+/// Returns the published amounts in `unit` for the expected positions in the
+/// interval; with [`GapFillStrategy::LeaveNull`] missing positions are left
+/// out. Amounts outside `rules.bounds` are logged, counted and, unless the
+/// bounds only flag them, handled like missing positions.
+pub fn validate_and_fill_period(
+    period: &Period,
+    bidding_zone: &str,
+    unit: &PriceUnit,
+    rules: &PriceRules,
+) -> Result<Vec<RawPricePoint>, EntsoeError> {
+    let start_time = parse_timestamp(&period.time_interval.start)?;
+    let end_time = parse_timestamp(&period.time_interval.end)?;
+    let resolution = parse_resolution(&period.resolution)?;
+
+    let expected_count = expected_period_count(start_time, end_time, resolution);
+    if expected_count == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Build a map of position -> price_amount for quick lookup
+    let point_map: HashMap<u32, f64> = period
+        .points
+        .iter()
+        .map(|p| (p.position, p.price_amount))
+        .collect();
+    let timestamp_at = |index: usize| start_time + resolution * index as i32;
+
+    // Published amounts by position index, without rejected implausible ones
+    let bounds = &rules.bounds;
+    let mut implausible: u64 = 0;
+    let mut amounts: Vec<Option<Decimal>> = Vec::with_capacity(expected_count);
+    for index in 0..expected_count {
+        let kept = match point_map.get(&(index as u32 + 1)).copied() {
+            Some(amount) if !bounds.contains(amount_mwh(amount, unit)) => {
+                implausible += 1;
+                warn!(
+                    bidding_zone = %bidding_zone,
+                    timestamp = %timestamp_at(index),
+                    amount = amount,
+                    unit = unit.energy.label(),
+                    min_mwh = bounds.min_mwh,
+                    max_mwh = bounds.max_mwh,
+                    action = bounds.action.as_str(),
+                    "Published price outside plausible bounds"
+                );
+                (bounds.action == ImplausiblePriceAction::Flag).then_some(amount)
+            }
+            published => published,
+        };
+        amounts.push(kept.map(RawPricePoint::parse_amount));
+    }
+    if implausible > 0 {
+        metrics::record_implausible_prices(bidding_zone, bounds.action.as_str(), implausible);
+    }
+
+    if amounts.iter().any(Option::is_none) {
+        if rules.gap_fill == GapFillStrategy::RejectPeriod {
+            return Err(EntsoeError::PeriodCountMismatch {
+                expected: expected_count,
+                start: period.time_interval.start.clone(),
+                end: period.time_interval.end.clone(),
+            });
+        }
+        // Nothing precedes the first position to fill it from
+        if amounts[0].is_none() && rules.gap_fill != GapFillStrategy::LeaveNull {
+            return Err(match point_map.get(&1) {
+                Some(&amount) => EntsoeError::ImplausiblePrice {
+                    bidding_zone: bidding_zone.to_string(),
+                    timestamp: start_time.to_rfc3339(),
+                    amount,
+                },
+                None => EntsoeError::MissingFirstPeriod,
+            });
+        }
+    }
+
+    let mut prices = Vec::with_capacity(expected_count);
+    let mut previous: Option<(usize, Decimal)> = None;
+    let mut gaps_filled: u64 = 0;
+
+    for (index, amount) in amounts.iter().enumerate() {
+        let (amount, gap_fill) = match (*amount, previous) {
+            (Some(amount), _) => {
+                previous = Some((index, amount));
+                (amount, None)
+            }
+            (None, _) if rules.gap_fill == GapFillStrategy::LeaveNull => {
+                warn!(
+                    bidding_zone = %bidding_zone,
+                    timestamp = %timestamp_at(index),
+                    "Gap detected, leaving position empty"
+                );
+                continue;
+            }
+            (None, Some(prev)) => {
+                let next = amounts[index..]
+                    .iter()
+                    .enumerate()
+                    .find_map(|(offset, amount)| amount.map(|amount| (index + offset, amount)));
+                match (rules.gap_fill, next) {
+                    (GapFillStrategy::LinearInterpolation, Some(next)) => {
+                        (interpolate(prev, next, index), Some(GapFill::LinearInterpolation))
+                    }
+                    _ => (prev.1, Some(GapFill::ForwardFill)),
+                }
+            }
+            // Ruled out above
+            (None, None) => return Err(EntsoeError::MissingFirstPeriod),
+        };
+
+        if let Some(method) = gap_fill {
+            if !point_map.contains_key(&(index as u32 + 1)) {
+                gaps_filled += 1;
+            }
+            warn!(
+                bidding_zone = %bidding_zone,
+                position = index + 1,
+                resolution = %period.resolution,
+                method = method.as_str(),
+                "Gap detected at position {}, filling it",
+                index + 1
+            );
+        }
+
+        prices.push(RawPricePoint {
+            timestamp: timestamp_at(index),
+            bidding_zone: bidding_zone.to_string(),
+            amount,
+            unit: unit.energy,
+            currency: unit.currency.clone(),
+            resolution: period.resolution.clone(),
+            gap_fill,
+        });
+    }
+
+    if gaps_filled > 0 {
+        metrics::record_gaps_filled(bidding_zone, gaps_filled);
+    }
+
+    Ok(prices)
+}
+
+/// Drop prices that a finer-resolution price in `prices` also covers, so a
+/// document with e.g. PT15M and PT60M series for the same day yields each
+/// interval once.
+pub(super) fn keep_finest_resolution(prices: Vec<Price>, bidding_zone: &str) -> Result<Vec<Price>, EntsoeError> {
+    let mut by_resolution: Vec<(Duration, Price)> = prices
+        .into_iter()
+        .map(|price| Ok((parse_resolution(&price.resolution)?, price)))
+        .collect::<Result<_, EntsoeError>>()?;
+    by_resolution.sort_by_key(|(resolution, _)| *resolution);
+
+    let mut covered: BTreeSet<DateTime<Utc>> = BTreeSet::new();
+    let mut kept = Vec::with_capacity(by_resolution.len());
+    let mut dropped = 0;
+    for (resolution, price) in by_resolution {
+        if covered.range(price.timestamp..price.timestamp + resolution).next().is_some() {
+            dropped += 1;
+            continue;
+        }
+        covered.insert(price.timestamp);
+        kept.push(price);
+    }
+    if dropped > 0 {
+        debug!(
+            bidding_zone = %bidding_zone,
+            dropped = dropped,
+            "Dropped prices covered by a finer-resolution series"
+        );
+    }
+    Ok(kept)
+}
+
+/// The amount at `index` on the line between two published amounts.
+fn interpolate(previous: (usize, Decimal), next: (usize, Decimal), index: usize) -> Decimal {
+    let (start, from) = previous;
+    let (end, to) = next;
+    let step = Decimal::from(index - start) / Decimal::from(end - start);
+    (from + (to - from) * step).round_dp(INTERPOLATED_DECIMALS).normalize()
+}
+
+/// A published amount per MWh, the unit price bounds are given in.
+fn amount_mwh(amount: f64, unit: &PriceUnit) -> f64 {
+    match unit.energy {
+        EnergyUnit::MegawattHour => amount,
+        EnergyUnit::KilowattHour => amount * 1000.0,
+    }
+}
+
+/// Warn when prices for a delivery day do not cover each of its hours (23 or
+/// 25 on DST transition days). The prices are kept either way. Returns whether
+/// the count matched.
+pub fn check_hour_count(prices: &[Price], bidding_zone: &str, day: &DeliveryDay) -> bool {
+    if prices.is_empty() {
+        return true;
+    }
+
+    let hours: HashSet<DateTime<Utc>> = prices
+        .iter()
+        .filter(|p| p.timestamp >= day.start && p.timestamp < day.end)
+        .filter_map(|p| p.timestamp.with_minute(0))
+        .collect();
+    if hours.len() as i64 == day.hours() {
+        return true;
+    }
+
+    warn!(
+        bidding_zone = %bidding_zone,
+        date = %day.date,
+        expected_hours = day.hours(),
+        actual_hours = hours.len(),
+        "Delivery day has an unexpected number of hourly prices"
+    );
+    metrics::record_hour_count_mismatch(bidding_zone);
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PriceBounds;
+    use crate::entsoe::xml::{Point, TimeInterval};
+
+    fn create_period(
+        start: &str,
+        end: &str,
+        resolution: &str,
+        points: Vec<(u32, f64)>,
+    ) -> Period {
+        Period {
+            time_interval: TimeInterval {
+                start: start.to_string(),
+                end: end.to_string(),
+            },
+            resolution: resolution.to_string(),
+            points: points
+                .into_iter()
+                .map(|(pos, price)| Point {
+                    position: pos,
+                    price_amount: price,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_expected_period_count_pt60m() {
+        let start = DateTime::parse_from_rfc3339("2025-12-30T23:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let end = DateTime::parse_from_rfc3339("2025-12-31T23:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let resolution = Duration::minutes(60);
+
+        assert_eq!(expected_period_count(start, end, resolution), 24);
+    }
+
+    #[test]
+    fn test_expected_period_count_pt15m() {
+        let start = DateTime::parse_from_rfc3339("2025-12-30T23:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let end = DateTime::parse_from_rfc3339("2025-12-31T23:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let resolution = Duration::minutes(15);
+
+        assert_eq!(expected_period_count(start, end, resolution), 96);
+    }
+
+    #[test]
+    fn test_expected_period_count_pt30m() {
+        let start = DateTime::parse_from_rfc3339("2025-12-30T23:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let end = DateTime::parse_from_rfc3339("2025-12-31T23:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let resolution = Duration::minutes(30);
+
+        assert_eq!(expected_period_count(start, end, resolution), 48);
+    }
+
+    #[test]
+    fn test_validate_complete_period() {
+        let points: Vec<(u32, f64)> = (1..=24).map(|i| (i, 50.0 + i as f64)).collect();
+        let period = create_period(
+            "2025-12-30T23:00:00Z",
+            "2025-12-31T23:00:00Z",
+            "PT60M",
+            points,
+        );
+
+        let prices = validate_and_fill_period(&period, "DE-LU", &PriceUnit::default(), &PriceRules::default()).unwrap();
+        assert_eq!(prices.len(), 24);
+        assert_eq!(prices[0].price_kwh().to_string(), "0.051"); // 51.0 / 1000
+        assert_eq!(prices[23].price_kwh().to_string(), "0.074"); // 74.0 / 1000
+    }
+
+    #[test]
+    fn test_validate_period_with_gap_forward_fill() {
+        // Missing position 3, should be filled with position 2's value
+        let points = vec![
+            (1, 50.0),
+            (2, 55.0),
+            // position 3 missing
+            (4, 60.0),
+            (5, 65.0),
+        ];
+        let period = create_period(
+            "2025-12-31T00:00:00Z",
+            "2025-12-31T05:00:00Z",
+            "PT60M",
+            points,
+        );
+
+        let prices = validate_and_fill_period(&period, "DE-LU", &PriceUnit::default(), &PriceRules::default()).unwrap();
+        assert_eq!(prices.len(), 5);
+
+        // Position 3 should have position 2's value (55.0 / 1000 = 0.055)
+        assert_eq!(prices[2].price_kwh().to_string(), "0.055");
+        let filled: Vec<Option<GapFill>> = prices.iter().map(|p| p.gap_fill).collect();
+        assert_eq!(filled, [None, None, Some(GapFill::ForwardFill), None, None]);
+    }
+
+    #[test]
+    fn test_validate_period_with_multiple_gaps() {
+        // Missing positions 2, 3, 5
+        let points = vec![
+            (1, 50.0),
+            // position 2 missing
+            // position 3 missing
+            (4, 60.0),
+            // position 5 missing
+            (6, 70.0),
+        ];
+        let period = create_period(
+            "2025-12-31T00:00:00Z",
+            "2025-12-31T06:00:00Z",
+            "PT60M",
+            points,
+        );
+
+        let prices = validate_and_fill_period(&period, "DE-LU", &PriceUnit::default(), &PriceRules::default()).unwrap();
+        assert_eq!(prices.len(), 6);
+
+        // Position 2 and 3 filled with position 1's value
+        assert_eq!(prices[1].price_kwh().to_string(), "0.05");
+        assert_eq!(prices[2].price_kwh().to_string(), "0.05");
+        // Position 5 filled with position 4's value
+        assert_eq!(prices[4].price_kwh().to_string(), "0.06");
+    }
+
+    #[test]
+    fn test_validate_period_missing_first_position_error() {
+        // Missing position 1 - cannot forward-fill
+        let points = vec![(2, 55.0), (3, 60.0)];
+        let period = create_period(
+            "2025-12-31T00:00:00Z",
+            "2025-12-31T03:00:00Z",
+            "PT60M",
+            points,
+        );
+
+        let result = validate_and_fill_period(&period, "DE-LU", &PriceUnit::default(), &PriceRules::default());
+        assert!(matches!(result, Err(EntsoeError::MissingFirstPeriod)));
+    }
+
+    #[test]
+    fn test_validate_period_keeps_native_resolution() {
+        // 4 hours = 16 periods at 15-minute resolution, stored as published
+        let points: Vec<(u32, f64)> = (1..=16).map(|i| (i, 40.0 + i as f64)).collect();
+        let period = create_period(
+            "2025-12-31T00:00:00Z",
+            "2025-12-31T04:00:00Z",
+            "PT15M",
+            points,
+        );
+
+        let prices = validate_and_fill_period(&period, "AT", &PriceUnit::default(), &PriceRules::default()).unwrap();
+        assert_eq!(prices.len(), 16);
+        assert!(prices.iter().all(|p| p.resolution == "PT15M"));
+        assert_eq!((prices[1].timestamp.hour(), prices[1].timestamp.minute()), (0, 15));
+        assert_eq!(prices[15].price_kwh().to_string(), "0.056");
+
+        // 4 hours = 8 periods at 30-minute resolution
+        let points: Vec<(u32, f64)> = (1..=8).map(|i| (i, 30.0 + i as f64)).collect();
+        let period = create_period(
+            "2025-12-31T00:00:00Z",
+            "2025-12-31T04:00:00Z",
+            "PT30M",
+            points,
+        );
+
+        let prices = validate_and_fill_period(&period, "GB", &PriceUnit::default(), &PriceRules::default()).unwrap();
+        assert_eq!(prices.len(), 8);
+        assert_eq!((prices[7].timestamp.hour(), prices[7].timestamp.minute()), (3, 30));
+    }
+
+    #[test]
+    fn test_validate_period_rejects_or_flags_implausible_prices() {
+        let points = vec![(1, 50.0), (2, 9999.0), (3, -600.0), (4, 4000.0)];
+        let period = create_period("2025-12-31T00:00:00Z", "2025-12-31T04:00:00Z", "PT60M", points);
+
+        let prices = validate_and_fill_period(&period, "DE-LU", &PriceUnit::default(), &PriceRules::default()).unwrap();
+        let amounts: Vec<String> = prices.iter().map(|p| p.amount.to_string()).collect();
+        assert_eq!(amounts, ["50", "50", "50", "4000"]);
+        assert!(prices[1].gap_fill.is_some() && prices[2].gap_fill.is_some());
+
+        let flag = PriceRules {
+            bounds: PriceBounds {
+                action: ImplausiblePriceAction::Flag,
+                ..PriceBounds::default()
+            },
+            ..PriceRules::default()
+        };
+        let prices = validate_and_fill_period(&period, "DE-LU", &PriceUnit::default(), &flag).unwrap();
+        assert_eq!(prices[1].amount.to_string(), "9999");
+        assert!(prices.iter().all(|p| p.gap_fill.is_none()));
+
+        // Bounds are per MWh, so kWh amounts are scaled before the check
+        let kwh = PriceUnit {
+            energy: EnergyUnit::KilowattHour,
+            ..PriceUnit::default()
+        };
+        let period = create_period("2025-12-31T00:00:00Z", "2025-12-31T01:00:00Z", "PT60M", vec![(1, 5.0)]);
+        let result = validate_and_fill_period(&period, "DE-LU", &kwh, &PriceRules::default());
+        assert!(matches!(result, Err(EntsoeError::ImplausiblePrice { .. })));
+    }
+
+    #[test]
+    fn test_validate_period_applies_gap_fill_strategy() {
+        // Positions 2, 3 and 6 missing
+        let points = vec![(1, 50.0), (4, 62.0), (5, 70.0)];
+        let period = create_period("2025-12-31T00:00:00Z", "2025-12-31T06:00:00Z", "PT60M", points);
+        let fill = |gap_fill| {
+            let rules = PriceRules {
+                gap_fill,
+                ..PriceRules::default()
+            };
+            validate_and_fill_period(&period, "DE-LU", &PriceUnit::default(), &rules)
+        };
+
+        let prices = fill(GapFillStrategy::LinearInterpolation).unwrap();
+        let amounts: Vec<String> = prices.iter().map(|p| p.amount.to_string()).collect();
+        assert_eq!(amounts, ["50", "54", "58", "62", "70", "70"]);
+        let methods: Vec<Option<GapFill>> = prices.iter().map(|p| p.gap_fill).collect();
+        assert_eq!(
+            methods,
+            [
+                None,
+                Some(GapFill::LinearInterpolation),
+                Some(GapFill::LinearInterpolation),
+                None,
+                None,
+                // Nothing after the gap to interpolate towards
+                Some(GapFill::ForwardFill),
+            ]
+        );
+
+        let prices = fill(GapFillStrategy::LeaveNull).unwrap();
+        let hours: Vec<u32> = prices.iter().map(|p| p.timestamp.hour()).collect();
+        assert_eq!(hours, [0, 3, 4]);
+        assert!(prices.iter().all(|p| p.gap_fill.is_none()));
+
+        assert!(matches!(fill(GapFillStrategy::RejectPeriod), Err(EntsoeError::PeriodCountMismatch { expected: 6, .. })));
+    }
+
+    #[test]
+    fn test_check_hour_count_on_short_dst_day() {
+        let day = DeliveryDay::new(
+            chrono::NaiveDate::from_ymd_opt(2025, 3, 30).unwrap(),
+            &chrono_tz::Europe::Oslo,
+        );
+        let hourly = |count: i64| -> Vec<Price> {
+            (0..count)
+                .map(|h| Price::from_mwh(day.start + Duration::hours(h), "NO1".to_string(), 40.0, "PT60M".to_string()))
+                .collect()
+        };
+
+        assert!(check_hour_count(&hourly(23), "NO1", &day));
+        // An hour past the end of the day belongs to the next day
+        assert!(check_hour_count(&hourly(24), "NO1", &day));
+        assert!(!check_hour_count(&hourly(22), "NO1", &day));
+    }
+}
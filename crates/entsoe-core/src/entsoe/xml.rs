@@ -0,0 +1,775 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use tracing::{debug, warn};
+
+use crate::config::PriceRules;
+use crate::models::{BiddingZone, CrossBorderFlow, EnergyUnit, FlowKind, LoadValue, Market, Price};
+use super::error::EntsoeError;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename = "Publication_MarketDocument")]
+pub struct PublicationMarketDocument {
+    #[serde(rename = "@xmlns", default)]
+    #[allow(dead_code)]
+    pub xmlns: String,
+    #[serde(rename = "mRID", default)]
+    #[allow(dead_code)]
+    pub m_rid: String,
+    #[serde(rename = "TimeSeries", default)]
+    pub time_series: Vec<TimeSeries>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimeSeries {
+    /// `A01` for day-ahead, `A07` for intraday auction results.
+    #[serde(rename = "contract_MarketAgreement.type", default)]
+    pub contract_market_agreement_type: Option<String>,
+    #[serde(rename = "currency_Unit.name", default)]
+    pub currency_unit_name: String,
+    #[serde(rename = "price_Measure_Unit.name", default)]
+    pub price_measure_unit_name: String,
+    /// Area the prices are for; used to attribute archived documents.
+    #[serde(rename = "in_Domain.mRID", default)]
+    pub in_domain: Option<DomainId>,
+    #[serde(rename = "out_Domain.mRID", default)]
+    pub out_domain: Option<DomainId>,
+    /// `A01` or `A03`, see [`CurveType`]; absent means `A01`.
+    #[serde(rename = "curveType", default)]
+    pub curve_type: Option<String>,
+    #[serde(rename = "Period", default)]
+    pub periods: Vec<Period>,
+}
+
+/// How a series' points cover the positions of its periods.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CurveType {
+    /// `A01`: a point for every position; an omitted position is a gap.
+    #[default]
+    SequentialFixedSize,
+    /// `A03`: a point holds until the next one, so positions repeating the
+    /// previous price are omitted.
+    VariableSizedBlock,
+}
+
+impl CurveType {
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "A01" => Some(Self::SequentialFixedSize),
+            "A03" => Some(Self::VariableSizedBlock),
+            _ => None,
+        }
+    }
+}
+
+/// Currency and energy unit a series' `price.amount` values are quoted in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceUnit {
+    pub currency: String,
+    pub energy: EnergyUnit,
+}
+
+impl Default for PriceUnit {
+    /// EUR/MWh, which ENTSOE uses for all euro-area day-ahead prices.
+    fn default() -> Self {
+        Self {
+            currency: "EUR".to_string(),
+            energy: EnergyUnit::MegawattHour,
+        }
+    }
+}
+
+impl TimeSeries {
+    /// Whether the series is for `eic_code`: each of `in_Domain.mRID` and
+    /// `out_Domain.mRID` it carries names that area.
+    pub fn is_for_domain(&self, eic_code: &str) -> bool {
+        [&self.in_domain, &self.out_domain]
+            .into_iter()
+            .flatten()
+            .all(|domain| domain.eic_code.trim() == eic_code)
+    }
+
+    /// Curve type from `curveType`. Other curve types are read as `A01`.
+    pub fn curve_type(&self) -> CurveType {
+        match self.curve_type.as_deref().map(str::trim) {
+            None | Some("") => CurveType::default(),
+            Some(code) => CurveType::from_code(code).unwrap_or_else(|| {
+                warn!(curve_type = %code, "Unsupported curve type, reading points as A01");
+                CurveType::default()
+            }),
+        }
+    }
+
+    /// Unit from `currency_Unit.name` and `price_Measure_Unit.name`. Either
+    /// may be omitted, in which case EUR and MWh are assumed.
+    pub fn price_unit(&self) -> Result<PriceUnit, EntsoeError> {
+        let default = PriceUnit::default();
+        let currency = match self.currency_unit_name.trim() {
+            "" => default.currency,
+            code => code.to_ascii_uppercase(),
+        };
+        let energy = match self.price_measure_unit_name.trim() {
+            "" => default.energy,
+            code => EnergyUnit::from_code(code).ok_or_else(|| EntsoeError::UnsupportedPriceUnit(code.to_string()))?,
+        };
+        Ok(PriceUnit { currency, energy })
+    }
+}
+
+/// EIC code element such as `<in_Domain.mRID codingScheme="A01">`.
+#[derive(Debug, Deserialize)]
+pub struct DomainId {
+    #[serde(rename = "$text")]
+    pub eic_code: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Period {
+    #[serde(rename = "timeInterval")]
+    pub time_interval: TimeInterval,
+    pub resolution: String,
+    #[serde(rename = "Point", default)]
+    pub points: Vec<Point>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TimeInterval {
+    pub start: String,
+    #[allow(dead_code)]
+    pub end: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Point {
+    pub position: u32,
+    #[serde(rename = "price.amount")]
+    pub price_amount: f64,
+}
+
+impl Period {
+    /// This period read as an `A03` curve: every position after the first
+    /// point is present, an omitted one repeating the point before it.
+    pub fn expand_blocks(&self) -> Result<Period, EntsoeError> {
+        use super::validation::expected_period_count;
+
+        let start = parse_timestamp(&self.time_interval.start)?;
+        let end = parse_timestamp(&self.time_interval.end)?;
+        let resolution = parse_resolution(&self.resolution)?;
+
+        let published: BTreeMap<u32, f64> = self.points.iter().map(|p| (p.position, p.price_amount)).collect();
+        let mut points = Vec::new();
+        let mut current = None;
+        for position in 1..=(expected_period_count(start, end, resolution) as u32) {
+            if let Some(&amount) = published.get(&position) {
+                current = Some(amount);
+            }
+            if let Some(price_amount) = current {
+                points.push(Point { position, price_amount });
+            }
+        }
+
+        Ok(Period {
+            time_interval: self.time_interval.clone(),
+            resolution: self.resolution.clone(),
+            points,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename = "Acknowledgement_MarketDocument")]
+pub struct AcknowledgementMarketDocument {
+    #[serde(rename = "@xmlns", default)]
+    #[allow(dead_code)]
+    pub xmlns: String,
+    #[serde(rename = "Reason", default)]
+    pub reasons: Vec<Reason>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Reason {
+    pub code: String,
+    pub text: String,
+}
+
+/// Time series period of a `GL_MarketDocument` (generation and load), with
+/// quantities in MW instead of prices.
+#[derive(Debug, Deserialize)]
+pub struct QuantityPeriod {
+    #[serde(rename = "timeInterval")]
+    pub time_interval: TimeInterval,
+    pub resolution: String,
+    #[serde(rename = "Point", default)]
+    pub points: Vec<QuantityPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuantityPoint {
+    pub position: u32,
+    pub quantity: f64,
+}
+
+impl QuantityPeriod {
+    /// Interval start and quantity for every position in the period,
+    /// forward-filling missing positions.
+    pub fn filled_quantities(&self) -> Result<Vec<(DateTime<Utc>, Decimal)>, EntsoeError> {
+        use super::validation::expected_period_count;
+
+        let start = parse_timestamp(&self.time_interval.start)?;
+        let end = parse_timestamp(&self.time_interval.end)?;
+        let resolution = parse_resolution(&self.resolution)?;
+
+        let quantities: HashMap<u32, f64> = self.points.iter().map(|p| (p.position, p.quantity)).collect();
+
+        let mut filled = Vec::new();
+        let mut previous: Option<f64> = None;
+        for position in 1..=(expected_period_count(start, end, resolution) as u32) {
+            let quantity = match (quantities.get(&position), previous) {
+                (Some(&q), _) => q,
+                (None, Some(prev)) => prev,
+                (None, None) => return Err(EntsoeError::MissingFirstPeriod),
+            };
+            previous = Some(quantity);
+            let quantity = Decimal::from_str(&quantity.to_string()).map_err(|_| {
+                EntsoeError::InvalidResponse(format!("Quantity {} at position {} is not a number", quantity, position))
+            })?;
+            filled.push((start + resolution * (position - 1) as i32, quantity));
+        }
+        Ok(filled)
+    }
+}
+
+/// A65 total load document. Shares the `GL_MarketDocument` root with
+/// generation documents; load series carry `outBiddingZone_Domain.mRID`.
+#[derive(Debug, Deserialize)]
+#[serde(rename = "GL_MarketDocument")]
+pub struct LoadMarketDocument {
+    #[serde(rename = "TimeSeries", default)]
+    pub time_series: Vec<LoadTimeSeries>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoadTimeSeries {
+    #[serde(rename = "Period", default)]
+    pub periods: Vec<QuantityPeriod>,
+}
+
+impl LoadMarketDocument {
+    pub fn extract_load(&self, bidding_zone: &str) -> Result<Vec<LoadValue>, EntsoeError> {
+        let fetched_at = Utc::now();
+        let mut values = Vec::new();
+
+        for time_series in &self.time_series {
+            for period in &time_series.periods {
+                values.extend(period.filled_quantities()?.into_iter().map(|(timestamp, load_mw)| LoadValue {
+                    timestamp,
+                    bidding_zone: bidding_zone.to_string(),
+                    load_mw,
+                    resolution: period.resolution.clone(),
+                    fetched_at,
+                }));
+            }
+        }
+
+        values.sort_by_key(|v| v.timestamp);
+
+        Ok(values)
+    }
+}
+
+/// A09/A11 flow document. Uses the `Publication_MarketDocument` root like
+/// price documents, with quantities instead of prices.
+#[derive(Debug, Deserialize)]
+#[serde(rename = "Publication_MarketDocument")]
+pub struct FlowMarketDocument {
+    #[serde(rename = "TimeSeries", default)]
+    pub time_series: Vec<FlowTimeSeries>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlowTimeSeries {
+    #[serde(rename = "Period", default)]
+    pub periods: Vec<QuantityPeriod>,
+}
+
+impl FlowMarketDocument {
+    /// Flows from `from_zone` to `to_zone`, as requested via `out_Domain` and
+    /// `in_Domain`. Overlapping series keep the last value per interval.
+    pub fn extract_flows(&self, from_zone: &str, to_zone: &str, kind: FlowKind) -> Result<Vec<CrossBorderFlow>, EntsoeError> {
+        let fetched_at = Utc::now();
+        let mut by_timestamp = std::collections::BTreeMap::new();
+
+        for time_series in &self.time_series {
+            for period in &time_series.periods {
+                for (timestamp, quantity_mw) in period.filled_quantities()? {
+                    by_timestamp.insert(timestamp, CrossBorderFlow {
+                        timestamp,
+                        from_zone: from_zone.to_string(),
+                        to_zone: to_zone.to_string(),
+                        kind,
+                        quantity_mw,
+                        resolution: period.resolution.clone(),
+                        fetched_at,
+                    });
+                }
+            }
+        }
+
+        Ok(by_timestamp.into_values().collect())
+    }
+}
+
+/// Parse `body` as document `D` and extract items from it. Acknowledgement
+/// documents with reason code 999 (no data) yield an empty list.
+pub fn parse_document<D: DeserializeOwned, T>(
+    body: &str,
+    extract: impl FnOnce(D) -> Result<Vec<T>, EntsoeError>,
+) -> Result<Vec<T>, EntsoeError> {
+    if let Ok(doc) = quick_xml::de::from_str::<D>(body) {
+        return extract(doc);
+    }
+
+    if let Ok(ack) = quick_xml::de::from_str::<AcknowledgementMarketDocument>(body) {
+        for reason in &ack.reasons {
+            if reason.code == "999" {
+                warn!(reason = %reason.text, "No data available for requested period");
+                return Ok(Vec::new());
+            }
+        }
+        return Err(EntsoeError::InvalidResponse(format!(
+            "ENTSOE returned acknowledgement: {:?}",
+            ack.reasons
+        )));
+    }
+
+    Err(EntsoeError::XmlParseError(format!(
+        "Failed to parse response as either {} or Acknowledgement document. Body starts with: {}",
+        std::any::type_name::<D>().rsplit("::").next().unwrap_or_default(),
+        &body.chars().take(200).collect::<String>()
+    )))
+}
+
+pub fn parse_resolution(resolution: &str) -> Result<Duration, EntsoeError> {
+    // Handle common ENTSOE resolutions directly for reliability
+    match resolution {
+        "PT15M" => return Ok(Duration::minutes(15)),
+        "PT30M" => return Ok(Duration::minutes(30)),
+        "PT60M" => return Ok(Duration::minutes(60)),
+        "P1D" => return Ok(Duration::days(1)),
+        "P7D" => return Ok(Duration::days(7)),
+        "P1Y" => return Ok(Duration::days(365)), // Approximate
+        _ => {}
+    }
+
+    // Fallback to ISO8601 parsing for edge cases
+    let parsed = iso8601_duration::Duration::parse(resolution)
+        .map_err(|e| EntsoeError::InvalidResolution(format!("{}: {:?}", resolution, e)))?;
+
+    let minutes = parsed.minute as i64 + (parsed.hour as i64 * 60);
+    let days = parsed.day as i64;
+
+    if minutes == 0 && days == 0 {
+        return Err(EntsoeError::InvalidResolution(format!(
+            "Resolution must have non-zero duration: {}",
+            resolution
+        )));
+    }
+
+    if days > 0 {
+        Ok(Duration::days(days))
+    } else {
+        Ok(Duration::minutes(minutes))
+    }
+}
+
+impl PublicationMarketDocument {
+    /// Prices for `zone` from the time series belonging to `market`. Series
+    /// without a `contract_MarketAgreement.type` are attributed to the
+    /// requested market, and series for another area's EIC code are skipped.
+    /// Where series of different resolutions cover the same interval, only
+    /// the finest one's prices are kept.
+    pub fn extract_prices(&self, zone: &BiddingZone, market: Market, rules: &PriceRules) -> Result<Vec<Price>, EntsoeError> {
+        use super::validation::{keep_finest_resolution, validate_and_fill_period};
+
+        let bidding_zone = zone.zone_code.as_str();
+        let fetched_at = Utc::now();
+        let rules = &PriceRules {
+            bounds: rules.bounds.for_market(market),
+            ..*rules
+        };
+        let mut prices = Vec::new();
+
+        for time_series in &self.time_series {
+            if let Some(contract_type) = &time_series.contract_market_agreement_type {
+                if Market::from_contract_type(contract_type) != Some(market) {
+                    debug!(contract_type = %contract_type, market = %market, "Skipping time series for another market");
+                    continue;
+                }
+            }
+            if !time_series.is_for_domain(&zone.eic_code) {
+                debug!(bidding_zone = %bidding_zone, eic_code = %zone.eic_code, "Skipping time series for another area");
+                continue;
+            }
+            let unit = time_series.price_unit()?;
+            if unit.currency != "EUR" {
+                debug!(bidding_zone = %bidding_zone, currency = %unit.currency, "Time series priced in non-EUR currency");
+            }
+            let curve_type = time_series.curve_type();
+            for period in &time_series.periods {
+                // Omitted A03 positions are unchanged prices, not gaps to fill
+                let period = match curve_type {
+                    CurveType::VariableSizedBlock => Cow::Owned(period.expand_blocks()?),
+                    CurveType::SequentialFixedSize => Cow::Borrowed(period),
+                };
+                prices.extend(
+                    validate_and_fill_period(&period, bidding_zone, &unit, rules)?
+                        .into_iter()
+                        .map(|point| point.into_price(market, fetched_at)),
+                );
+            }
+        }
+
+        // Mixed resolutions, e.g. Austria returns PT15M and PT60M series for the same day
+        let mut prices = keep_finest_resolution(prices, bidding_zone)?;
+        prices.sort_by_key(|p| p.timestamp);
+
+        Ok(prices)
+    }
+
+    /// EIC code of the area in the first series' `in_Domain.mRID`.
+    pub fn in_domain(&self) -> Option<&str> {
+        self.time_series
+            .iter()
+            .find_map(|ts| ts.in_domain.as_ref())
+            .map(|domain| domain.eic_code.trim())
+    }
+
+    /// Markets with series in this document. Series without a
+    /// `contract_MarketAgreement.type` count as day-ahead.
+    pub fn markets(&self) -> Vec<Market> {
+        Market::ALL
+            .into_iter()
+            .filter(|market| {
+                self.time_series.iter().any(|ts| match &ts.contract_market_agreement_type {
+                    Some(contract_type) => Market::from_contract_type(contract_type) == Some(*market),
+                    None => *market == Market::DayAhead,
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn parse_timestamp(timestamp_str: &str) -> Result<DateTime<Utc>, EntsoeError> {
+    // Try RFC3339 first (with seconds)
+    if let Ok(dt) = DateTime::parse_from_rfc3339(timestamp_str) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    
+    // ENTSO-E sometimes returns timestamps without seconds (e.g., "2025-12-30T23:00Z")
+    // Try parsing with custom format
+    let normalized = if timestamp_str.ends_with('Z') && !timestamp_str.contains(':') {
+        timestamp_str.to_string()
+    } else if timestamp_str.len() == 17 && timestamp_str.ends_with('Z') {
+        // Format: "2025-12-30T23:00Z" -> add ":00" for seconds
+        format!("{}:00Z", &timestamp_str[..16])
+    } else {
+        timestamp_str.to_string()
+    };
+    
+    DateTime::parse_from_rfc3339(&normalized)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| EntsoeError::TimestampParseError(format!("{}: {}", timestamp_str, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PriceBounds;
+
+    #[test]
+    fn test_parse_resolution_15m() {
+        let result = parse_resolution("PT15M").unwrap();
+        assert_eq!(result, Duration::minutes(15));
+    }
+
+    #[test]
+    fn test_parse_resolution_60m() {
+        let result = parse_resolution("PT60M").unwrap();
+        assert_eq!(result, Duration::minutes(60));
+    }
+
+    #[test]
+    fn test_parse_resolution_1h() {
+        let result = parse_resolution("PT1H").unwrap();
+        assert_eq!(result, Duration::minutes(60));
+    }
+
+    #[test]
+    fn test_parse_resolution_invalid() {
+        let result = parse_resolution("invalid");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_resolution_30m() {
+        let result = parse_resolution("PT30M").unwrap();
+        assert_eq!(result, Duration::minutes(30));
+    }
+
+    #[test]
+    fn test_parse_resolution_p1d() {
+        let result = parse_resolution("P1D").unwrap();
+        assert_eq!(result, Duration::days(1));
+    }
+
+    #[test]
+    fn test_parse_resolution_p7d() {
+        let result = parse_resolution("P7D").unwrap();
+        assert_eq!(result, Duration::days(7));
+    }
+
+    #[test]
+    fn test_parse_load_document_forward_fills() {
+        let xml = r#"<GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+  <TimeSeries>
+    <outBiddingZone_Domain.mRID codingScheme="A01">10YNO-1--------2</outBiddingZone_Domain.mRID>
+    <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T00:00Z</end></timeInterval>
+      <resolution>PT15M</resolution>
+      <Point><position>1</position><quantity>4100</quantity></Point>
+      <Point><position>2</position><quantity>4150.5</quantity></Point>
+      <Point><position>4</position><quantity>4200</quantity></Point>
+    </Period>
+  </TimeSeries>
+</GL_MarketDocument>"#;
+        let values = parse_document(xml, |doc: LoadMarketDocument| doc.extract_load("NO1")).unwrap();
+        let loads: Vec<Decimal> = values.iter().map(|v| v.load_mw).collect();
+        assert_eq!(
+            loads,
+            vec![Decimal::from(4100), Decimal::new(41505, 1), Decimal::new(41505, 1), Decimal::from(4200)]
+        );
+        assert_eq!(values[3].timestamp, parse_timestamp("2025-01-14T23:45Z").unwrap());
+        assert_eq!(values[0].resolution, "PT15M");
+
+        // A quantity that is not a number is rejected rather than read as 0 MW
+        let nan = xml.replace("<quantity>4100</quantity>", "<quantity>NaN</quantity>");
+        let result = parse_document(&nan, |doc: LoadMarketDocument| doc.extract_load("NO1"));
+        assert!(matches!(result, Err(EntsoeError::InvalidResponse(_))));
+    }
+
+    #[test]
+    fn test_parse_flow_document_keeps_direction() {
+        let xml = r#"<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:0">
+  <TimeSeries>
+    <in_Domain.mRID codingScheme="A01">10Y1001A1001A46L</in_Domain.mRID>
+    <out_Domain.mRID codingScheme="A01">10YNO-1--------2</out_Domain.mRID>
+    <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T01:00Z</end></timeInterval>
+      <resolution>PT60M</resolution>
+      <Point><position>1</position><quantity>1500</quantity></Point>
+      <Point><position>2</position><quantity>0</quantity></Point>
+    </Period>
+  </TimeSeries>
+</Publication_MarketDocument>"#;
+        let flows = parse_document(xml, |doc: FlowMarketDocument| {
+            doc.extract_flows("NO1", "SE3", FlowKind::Physical)
+        })
+        .unwrap();
+        assert_eq!(flows.len(), 2);
+        assert_eq!((flows[0].from_zone.as_str(), flows[0].to_zone.as_str()), ("NO1", "SE3"));
+        assert_eq!(flows[0].quantity_mw, Decimal::from(1500));
+        assert_eq!(flows[1].quantity_mw, Decimal::ZERO);
+    }
+
+    fn price_document(currency: &str, unit: &str) -> String {
+        format!(
+            r#"<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+  <TimeSeries>
+    <in_Domain.mRID codingScheme="A01">10YGB----------A</in_Domain.mRID>
+    {currency}
+    {unit}
+    <Period>
+      <timeInterval><start>2025-01-15T00:00Z</start><end>2025-01-15T01:00Z</end></timeInterval>
+      <resolution>PT30M</resolution>
+      <Point><position>1</position><price.amount>82.5</price.amount></Point>
+      <Point><position>2</position><price.amount>90</price.amount></Point>
+    </Period>
+  </TimeSeries>
+</Publication_MarketDocument>"#
+        )
+    }
+
+    fn gb() -> BiddingZone {
+        BiddingZone::provisional("10YGB----------A", "Europe/London")
+    }
+
+    #[test]
+    fn test_extract_prices_uses_series_currency_and_unit() {
+        let xml = price_document(
+            "<currency_Unit.name>GBP</currency_Unit.name>",
+            "<price_Measure_Unit.name>MWH</price_Measure_Unit.name>",
+        );
+        let prices = parse_document(&xml, |doc: PublicationMarketDocument| doc.extract_prices(&gb(), Market::DayAhead, &PriceRules::default())).unwrap();
+        assert_eq!(prices.len(), 2);
+        assert!(prices.iter().all(|p| p.currency == "GBP"));
+        assert_eq!(prices[0].price_kwh.to_string(), "0.0825");
+
+        // Far outside the default bounds per MWh; only the unit is under test
+        let unbounded = PriceRules {
+            bounds: PriceBounds {
+                min_mwh: f64::MIN,
+                max_mwh: f64::MAX,
+                ..PriceBounds::default()
+            },
+            ..PriceRules::default()
+        };
+        let xml = price_document("", "<price_Measure_Unit.name>KWH</price_Measure_Unit.name>");
+        let prices = parse_document(&xml, |doc: PublicationMarketDocument| doc.extract_prices(&gb(), Market::DayAhead, &unbounded)).unwrap();
+        assert_eq!(prices[1].currency, "EUR");
+        assert_eq!(prices[1].price_kwh.to_string(), "90");
+    }
+
+    #[test]
+    fn test_extract_prices_checks_intraday_prices_against_intraday_bounds() {
+        // Above the day-ahead maximum, within the intraday auctions' one
+        let xml = price_document("", "").replace("<price.amount>90<", "<price.amount>6000<");
+        let extract = |market| {
+            parse_document(&xml, |doc: PublicationMarketDocument| doc.extract_prices(&gb(), market, &PriceRules::default()))
+                .unwrap()
+        };
+        let day_ahead = extract(Market::DayAhead);
+        assert_eq!(day_ahead[1].price_kwh.to_string(), "0.0825");
+        assert!(day_ahead[1].gap_fill.is_some());
+        let intraday = extract(Market::Intraday);
+        assert_eq!(intraday[1].price_kwh.to_string(), "6");
+        assert!(intraday[1].gap_fill.is_none());
+    }
+
+    #[test]
+    fn test_extract_prices_expands_a03_blocks_without_gap_fill() {
+        let document = |curve_type: &str| {
+            format!(
+                r#"<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+  <TimeSeries>
+    {}
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T05:00Z</end></timeInterval>
+      <resolution>PT60M</resolution>
+      <Point><position>1</position><price.amount>40</price.amount></Point>
+      <Point><position>4</position><price.amount>55</price.amount></Point>
+    </Period>
+  </TimeSeries>
+</Publication_MarketDocument>"#,
+                curve_type
+            )
+        };
+        let zone = BiddingZone::provisional("10YNO-1--------2", "Europe/Oslo");
+        let extract = |xml: &str| {
+            parse_document(xml, |doc: PublicationMarketDocument| doc.extract_prices(&zone, Market::DayAhead, &PriceRules::default()))
+                .unwrap()
+        };
+
+        let blocks = extract(&document("<curveType>A03</curveType>"));
+        let amounts: Vec<String> = blocks.iter().map(|p| p.price_kwh.to_string()).collect();
+        assert_eq!(amounts, vec!["0.04", "0.04", "0.04", "0.055", "0.055", "0.055"]);
+        assert!(blocks.iter().all(|p| p.gap_fill.is_none()));
+
+        let sequential = extract(&document("<curveType>A01</curveType>"));
+        assert_eq!(sequential.len(), 6);
+        assert!(sequential[1].gap_fill.is_some());
+        assert!(sequential[4].gap_fill.is_some());
+    }
+
+    /// A44 series for `eic_code` over 2025-01-15 00:00-02:00 UTC at `resolution`.
+    fn series(eic_code: &str, resolution: &str, amounts: &[u32]) -> String {
+        let points: String = amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| format!("<Point><position>{}</position><price.amount>{}</price.amount></Point>", i + 1, amount))
+            .collect();
+        format!(
+            r#"<TimeSeries>
+    <in_Domain.mRID codingScheme="A01">{eic_code}</in_Domain.mRID>
+    <out_Domain.mRID codingScheme="A01">{eic_code}</out_Domain.mRID>
+    <Period>
+      <timeInterval><start>2025-01-15T00:00Z</start><end>2025-01-15T02:00Z</end></timeInterval>
+      <resolution>{resolution}</resolution>
+      {points}
+    </Period>
+  </TimeSeries>"#
+        )
+    }
+
+    fn document(series: &[String]) -> String {
+        format!(
+            r#"<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">{}</Publication_MarketDocument>"#,
+            series.concat()
+        )
+    }
+
+    #[test]
+    fn test_extract_prices_skips_series_for_other_domains() {
+        let xml = document(&[
+            series("10YAT-APG------L", "PT60M", &[10, 20]),
+            series("10YGB----------A", "PT60M", &[80, 90]),
+        ]);
+        let prices = parse_document(&xml, |doc: PublicationMarketDocument| doc.extract_prices(&gb(), Market::DayAhead, &PriceRules::default())).unwrap();
+        let amounts: Vec<String> = prices.iter().map(|p| p.price_kwh.to_string()).collect();
+        assert_eq!(amounts, vec!["0.08", "0.09"]);
+    }
+
+    #[test]
+    fn test_extract_prices_keeps_finest_resolution_per_interval() {
+        let at = BiddingZone::provisional("10YAT-APG------L", "Europe/Vienna");
+        let extract = |xml: &str| {
+            parse_document(xml, |doc: PublicationMarketDocument| doc.extract_prices(&at, Market::DayAhead, &PriceRules::default()))
+                .unwrap()
+        };
+
+        let both = extract(&document(&[
+            series("10YAT-APG------L", "PT60M", &[60, 70]),
+            series("10YAT-APG------L", "PT15M", &[10, 11, 12, 13, 20, 21, 22, 23]),
+        ]));
+        assert_eq!(both.len(), 8);
+        assert!(both.iter().all(|p| p.resolution == "PT15M"));
+
+        // Quarter-hours only for the first hour: the second hour stays hourly
+        let partial = extract(&document(&[
+            series("10YAT-APG------L", "PT60M", &[60, 70]),
+            series("10YAT-APG------L", "PT15M", &[10, 11, 12, 13]).replace("02:00Z", "01:00Z"),
+        ]));
+        let resolutions: Vec<&str> = partial.iter().map(|p| p.resolution.as_str()).collect();
+        assert_eq!(resolutions, vec!["PT15M", "PT15M", "PT15M", "PT15M", "PT60M"]);
+        assert_eq!(partial[4].price_kwh.to_string(), "0.07");
+    }
+
+    #[test]
+    fn test_extract_prices_rejects_unknown_unit() {
+        let xml = price_document("", "<price_Measure_Unit.name>GWH</price_Measure_Unit.name>");
+        let result = parse_document(&xml, |doc: PublicationMarketDocument| doc.extract_prices(&gb(), Market::DayAhead, &PriceRules::default()));
+        assert!(matches!(result, Err(EntsoeError::UnsupportedPriceUnit(unit)) if unit == "GWH"));
+    }
+
+    #[test]
+    fn test_parse_document_no_data_acknowledgement() {
+        let xml = r#"<Acknowledgement_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+  <Reason><code>999</code><text>No matching data found</text></Reason>
+</Acknowledgement_MarketDocument>"#;
+        let values = parse_document(xml, |doc: LoadMarketDocument| doc.extract_load("NO1")).unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn test_parse_resolution_p1y() {
+        let result = parse_resolution("P1Y").unwrap();
+        assert_eq!(result, Duration::days(365));
+    }
+}
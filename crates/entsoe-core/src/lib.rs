@@ -0,0 +1,6 @@
+pub mod config;
+pub mod entsoe;
+pub mod metrics;
+pub mod models;
+
+pub use entsoe::{EntsoeClient, EntsoeError, EntsoePriceSource};
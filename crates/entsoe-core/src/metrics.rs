@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use metrics::{counter, gauge, histogram, Unit};
+
+pub const ENTSOE_FETCH_ATTEMPTS_TOTAL: &str = "entsoe_fetch_attempts_total";
+pub const ENTSOE_FETCH_ERRORS_TOTAL: &str = "entsoe_fetch_errors_total";
+pub const ENTSOE_FETCH_DURATION_SECONDS: &str = "entsoe_fetch_duration_seconds";
+pub const ENTSOE_RATE_LIMIT_WAITS_TOTAL: &str = "entsoe_rate_limit_waits_total";
+pub const ENTSOE_GAPS_FILLED_TOTAL: &str = "entsoe_gaps_filled_total";
+pub const ENTSOE_HOUR_COUNT_MISMATCHES_TOTAL: &str = "entsoe_hour_count_mismatches_total";
+pub const ENTSOE_NEGATIVE_PRICE_HOURS_TOTAL: &str = "entsoe_negative_price_hours_total";
+pub const ENTSOE_IMPLAUSIBLE_PRICES_TOTAL: &str = "entsoe_implausible_prices_total";
+pub const ENTSOE_CIRCUIT_BREAKER_STATE: &str = "entsoe_circuit_breaker_state";
+pub const ENTSOE_CIRCUIT_BREAKER_TRIPS_TOTAL: &str = "entsoe_circuit_breaker_trips_total";
+pub const ENTSOE_ENDPOINT_FAILOVERS_TOTAL: &str = "entsoe_endpoint_failovers_total";
+pub const ENTSOE_HTTP_PHASE_DURATION_SECONDS: &str = "entsoe_http_phase_duration_seconds";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+        }
+    }
+}
+
+/// Catalog entry for one exported metric.
+#[derive(Debug, Clone, Copy)]
+pub struct MetricDescription {
+    pub name: &'static str,
+    pub kind: MetricKind,
+    pub unit: Option<Unit>,
+    pub labels: &'static [&'static str],
+    pub description: &'static str,
+}
+
+pub const fn describe(
+    name: &'static str,
+    kind: MetricKind,
+    unit: Option<Unit>,
+    labels: &'static [&'static str],
+    description: &'static str,
+) -> MetricDescription {
+    MetricDescription { name, kind, unit, labels, description }
+}
+
+/// Metrics recorded by the ENTSOE client and period validation. Services
+/// that export metrics include these in their own catalog.
+pub const ENTSOE_METRICS: &[MetricDescription] = &[
+    describe(
+        ENTSOE_FETCH_ATTEMPTS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["zone_code", "status"],
+        "ENTSOE price fetch attempts by zone and outcome",
+    ),
+    describe(
+        ENTSOE_FETCH_ERRORS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["zone_code", "error_type"],
+        "Failed ENTSOE price fetches by zone and error type",
+    ),
+    describe(
+        ENTSOE_FETCH_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["zone_code"],
+        "Duration of ENTSOE price fetches, including retries",
+    ),
+    describe(
+        ENTSOE_RATE_LIMIT_WAITS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &[],
+        "Requests delayed by the client-side ENTSOE rate limiter",
+    ),
+    describe(
+        ENTSOE_GAPS_FILLED_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["zone_code"],
+        "Missing intervals filled when validating ENTSOE periods",
+    ),
+    describe(
+        ENTSOE_HOUR_COUNT_MISMATCHES_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["zone_code"],
+        "Fetched delivery days whose hourly prices do not match the local day length (23, 24 or 25 hours)",
+    ),
+    describe(
+        ENTSOE_NEGATIVE_PRICE_HOURS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["zone_code"],
+        "Hours priced below zero, counted when a fetch first stores them negative",
+    ),
+    describe(
+        ENTSOE_IMPLAUSIBLE_PRICES_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["zone_code", "action"],
+        "Published prices outside the configured plausibility bounds, by whether they were rejected or flagged",
+    ),
+    describe(
+        ENTSOE_CIRCUIT_BREAKER_STATE,
+        MetricKind::Gauge,
+        None,
+        &["breaker"],
+        "ENTSOE circuit breaker state by breaker (prices or actuals): 0 = closed, 1 = half-open, 2 = open",
+    ),
+    describe(
+        ENTSOE_CIRCUIT_BREAKER_TRIPS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["breaker"],
+        "Times an ENTSOE circuit breaker opened",
+    ),
+    describe(
+        ENTSOE_ENDPOINT_FAILOVERS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["endpoint"],
+        "Requests retried against a fallback ENTSOE endpoint, by the endpoint failed over to",
+    ),
+    describe(
+        ENTSOE_HTTP_PHASE_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["endpoint", "phase"],
+        "ENTSOE request time by phase: dns, connect (TCP and TLS handshake) and ttfb (response headers)",
+    ),
+];
+
+pub fn record_fetch_attempt(zone_code: &str, status: &str) {
+    counter!(ENTSOE_FETCH_ATTEMPTS_TOTAL, "zone_code" => zone_code.to_string(), "status" => status.to_string())
+        .increment(1);
+}
+
+pub fn record_fetch_error(zone_code: &str, error_type: &str) {
+    counter!(ENTSOE_FETCH_ERRORS_TOTAL, "zone_code" => zone_code.to_string(), "error_type" => error_type.to_string())
+        .increment(1);
+}
+
+pub fn record_fetch_duration(zone_code: &str, duration: Duration) {
+    histogram!(ENTSOE_FETCH_DURATION_SECONDS, "zone_code" => zone_code.to_string())
+        .record(duration.as_secs_f64());
+}
+
+pub fn record_http_phase(endpoint: &str, phase: &'static str, duration: Duration) {
+    histogram!(ENTSOE_HTTP_PHASE_DURATION_SECONDS, "endpoint" => endpoint.to_string(), "phase" => phase)
+        .record(duration.as_secs_f64());
+}
+
+/// 0 = closed, 1 = half-open, 2 = open
+pub fn update_circuit_breaker_state(breaker: &'static str, state: f64) {
+    gauge!(ENTSOE_CIRCUIT_BREAKER_STATE, "breaker" => breaker).set(state);
+}
+
+pub fn record_circuit_breaker_trip(breaker: &'static str) {
+    counter!(ENTSOE_CIRCUIT_BREAKER_TRIPS_TOTAL, "breaker" => breaker).increment(1);
+}
+
+pub fn record_endpoint_failover(endpoint: &str) {
+    counter!(ENTSOE_ENDPOINT_FAILOVERS_TOTAL, "endpoint" => endpoint.to_string()).increment(1);
+}
+
+pub fn record_rate_limit_wait() {
+    counter!(ENTSOE_RATE_LIMIT_WAITS_TOTAL).increment(1);
+}
+
+pub fn record_gaps_filled(zone_code: &str, count: u64) {
+    counter!(ENTSOE_GAPS_FILLED_TOTAL, "zone_code" => zone_code.to_string()).increment(count);
+}
+
+pub fn record_hour_count_mismatch(zone_code: &str) {
+    counter!(ENTSOE_HOUR_COUNT_MISMATCHES_TOTAL, "zone_code" => zone_code.to_string()).increment(1);
+}
+
+pub fn record_negative_price_hours(zone_code: &str, count: u64) {
+    counter!(ENTSOE_NEGATIVE_PRICE_HOURS_TOTAL, "zone_code" => zone_code.to_string()).increment(count);
+}
+
+pub fn record_implausible_prices(zone_code: &str, action: &'static str, count: u64) {
+    counter!(ENTSOE_IMPLAUSIBLE_PRICES_TOTAL, "zone_code" => zone_code.to_string(), "action" => action).increment(count);
+}
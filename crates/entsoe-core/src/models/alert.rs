@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Which side of the threshold fires an alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertComparison {
+    Above,
+    Below,
+}
+
+impl AlertComparison {
+    /// Value stored in the `alert_rules.comparison` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertComparison::Above => "above",
+            AlertComparison::Below => "below",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "above" => Some(AlertComparison::Above),
+            "below" => Some(AlertComparison::Below),
+            _ => None,
+        }
+    }
+
+    /// Strict comparison, so a price exactly at the threshold never fires.
+    pub fn matches(&self, price: Decimal, threshold: Decimal) -> bool {
+        match self {
+            AlertComparison::Above => price > threshold,
+            AlertComparison::Below => price < threshold,
+        }
+    }
+}
+
+/// How a fired alert is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertChannel {
+    /// POST to the target URL, signed like the service webhooks.
+    Webhook,
+    /// Publish to the target topic on the configured broker.
+    Mqtt,
+    /// Mail the target address through the configured SMTP relay.
+    Email,
+}
+
+impl AlertChannel {
+    /// Value stored in the `alert_rules.channel` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertChannel::Webhook => "webhook",
+            AlertChannel::Mqtt => "mqtt",
+            AlertChannel::Email => "email",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "webhook" => Some(AlertChannel::Webhook),
+            "mqtt" => Some(AlertChannel::Mqtt),
+            "email" => Some(AlertChannel::Email),
+            _ => None,
+        }
+    }
+}
+
+/// A registered alert rule. `threshold` is in EUR/kWh, like stored prices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: i64,
+    pub zone_code: String,
+    pub comparison: AlertComparison,
+    pub threshold: Decimal,
+    pub channel: AlertChannel,
+    /// Webhook URL, MQTT topic or email address, depending on `channel`.
+    pub target: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAlertRule {
+    pub zone_code: String,
+    pub comparison: AlertComparison,
+    pub threshold: Decimal,
+    pub channel: AlertChannel,
+    pub target: String,
+}
+
+/// A rule firing for one price hour. `delivered` stays `None` until the
+/// delivery attempt finishes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    pub rule_id: i64,
+    pub price_timestamp: DateTime<Utc>,
+    pub price_kwh: Decimal,
+    pub fired_at: DateTime<Utc>,
+    pub delivered: Option<bool>,
+    pub error_message: Option<String>,
+}
+
+impl AlertEvent {
+    pub fn new(rule_id: i64, price_timestamp: DateTime<Utc>, price_kwh: Decimal) -> Self {
+        Self {
+            rule_id,
+            price_timestamp,
+            price_kwh,
+            fired_at: Utc::now(),
+            delivered: None,
+            error_message: None,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What an API caller may do. Admin keys can also read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiRole {
+    /// The public `/api/v1` routes.
+    Read,
+    /// Also `/api/v1/admin`.
+    Admin,
+}
+
+impl ApiRole {
+    /// Value stored in the `api_keys.role` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiRole::Read => "read",
+            ApiRole::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "read" => Some(ApiRole::Read),
+            "admin" => Some(ApiRole::Admin),
+            _ => None,
+        }
+    }
+
+    /// Whether this role may call routes that require `required`.
+    pub fn allows(&self, required: ApiRole) -> bool {
+        *self >= required
+    }
+}
+
+/// An API key registered in the database. Only the SHA-256 of the key is
+/// stored, hex-encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    pub name: String,
+    pub key_sha256: String,
+    pub role: ApiRole,
+    pub created_at: DateTime<Utc>,
+}
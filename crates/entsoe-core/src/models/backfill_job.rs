@@ -0,0 +1,70 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Where a backfill job stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BackfillJobStatus {
+    Running,
+    Completed,
+    Cancelled,
+    /// Stopped by an error, or interrupted by the process running it exiting.
+    Failed,
+}
+
+impl BackfillJobStatus {
+    /// Value stored in the `backfill_jobs.status` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackfillJobStatus::Running => "running",
+            BackfillJobStatus::Completed => "completed",
+            BackfillJobStatus::Cancelled => "cancelled",
+            BackfillJobStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "running" => Some(BackfillJobStatus::Running),
+            "completed" => Some(BackfillJobStatus::Completed),
+            "cancelled" => Some(BackfillJobStatus::Cancelled),
+            "failed" => Some(BackfillJobStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// A backfill of `[start_date, end_date]` run in the background, with its
+/// progress so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillJob {
+    pub id: i64,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Zones to backfill; `None` for every active zone.
+    pub zones: Option<Vec<String>>,
+    pub status: BackfillJobStatus,
+    pub dates_total: i32,
+    pub dates_done: i32,
+    pub prices_stored: i64,
+    pub errors: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    /// Last progress update, used to spot jobs whose process went away.
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewBackfillJob {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub zones: Option<Vec<String>>,
+}
+
+impl NewBackfillJob {
+    /// Days in the inclusive date range.
+    pub fn dates_total(&self) -> i32 {
+        ((self.end_date - self.start_date).num_days() + 1) as i32
+    }
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::DeliveryDay;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct BiddingZone {
+    pub zone_code: String,
+    pub zone_name: String,
+    pub country_code: String,
+    pub country_name: String,
+    pub eic_code: String,
+    pub timezone: String,
+    pub active: bool,
+    /// First delivery date the zone exists on; `None` since always.
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    /// First delivery date the zone no longer exists on, e.g. after a split;
+    /// `None` while it is current.
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+    /// Zone that covered this one's area before `valid_from`, e.g. DE-AT-LU
+    /// for DE-LU.
+    #[serde(default)]
+    pub predecessor_code: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Static description of a bidding zone, used to seed the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneDefinition {
+    pub zone_code: String,
+    pub zone_name: String,
+    pub country_code: String,
+    pub country_name: String,
+    pub eic_code: String,
+    pub timezone: String,
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+    #[serde(default)]
+    pub predecessor_code: Option<String>,
+}
+
+impl BiddingZone {
+    /// A zone that is not in the registry, identified only by its EIC code,
+    /// for querying ENTSOE before onboarding it. The EIC doubles as the zone
+    /// code.
+    pub fn provisional(eic_code: &str, timezone: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            zone_code: eic_code.to_string(),
+            zone_name: eic_code.to_string(),
+            country_code: String::new(),
+            country_name: String::new(),
+            eic_code: eic_code.to_string(),
+            timezone: timezone.to_string(),
+            active: false,
+            valid_from: None,
+            valid_to: None,
+            predecessor_code: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether the zone exists on delivery date `date`: on or after
+    /// `valid_from` and before `valid_to`.
+    pub fn is_valid_on(&self, date: NaiveDate) -> bool {
+        self.valid_from.is_none_or(|from| date >= from) && self.valid_to.is_none_or(|to| date < to)
+    }
+
+    /// Start of `valid_from` in the zone's timezone. Prices before it are
+    /// stored under the predecessor zone.
+    pub fn valid_from_start(&self) -> Option<DateTime<Utc>> {
+        let timezone = self.get_timezone().ok()?;
+        self.valid_from.map(|date| DeliveryDay::new(date, &timezone).start)
+    }
+
+    /// Get timezone as chrono_tz::Tz
+    pub fn get_timezone(&self) -> Result<chrono_tz::Tz, String> {
+        self.timezone
+            .parse::<chrono_tz::Tz>()
+            .map_err(|e| format!("Invalid timezone {}: {}", self.timezone, e))
+    }
+}
+
+impl ZoneDefinition {
+    /// Whether the zone still exists on `today`; a zone past its `valid_to`
+    /// is registered inactive, for lookups of its historical prices only.
+    pub fn is_active_on(&self, today: NaiveDate) -> bool {
+        self.valid_to.is_none_or(|to| today < to)
+    }
+}
+
+/// Whether `code` has the shape of an EIC area code: 16 characters of
+/// uppercase letters, digits and `-`.
+pub fn is_valid_eic(code: &str) -> bool {
+    code.len() == 16 && code.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_on_excludes_valid_to() {
+        let mut zone = BiddingZone::provisional("10Y1001A1001A82H", "Europe/Berlin");
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert!(zone.is_valid_on(date(2015, 1, 1)));
+
+        zone.valid_from = Some(date(2018, 10, 1));
+        assert!(!zone.is_valid_on(date(2018, 9, 30)));
+        assert!(zone.is_valid_on(date(2018, 10, 1)));
+        assert_eq!(zone.valid_from_start().unwrap().to_rfc3339(), "2018-09-30T22:00:00+00:00");
+
+        zone.valid_to = Some(date(2030, 1, 1));
+        assert!(zone.is_valid_on(date(2029, 12, 31)));
+        assert!(!zone.is_valid_on(date(2030, 1, 1)));
+    }
+
+    #[test]
+    fn test_is_valid_eic() {
+        assert!(is_valid_eic("10YNO-1--------2"));
+        assert!(is_valid_eic("10Y1001A1001A46L"));
+        assert!(!is_valid_eic("10yno-1--------2"));
+        assert!(!is_valid_eic("NO1"));
+        assert!(!is_valid_eic("10YNO-1--------2&x=1"));
+    }
+}
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// A delivery date in a zone's local time and the UTC instants it spans.
+/// Days with a DST transition last 23 or 25 hours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeliveryDay {
+    pub date: NaiveDate,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl DeliveryDay {
+    pub fn new(date: NaiveDate, timezone: &Tz) -> Self {
+        let next = date.succ_opt().expect("date before the end of time");
+        Self {
+            date,
+            start: local_midnight(date, timezone),
+            end: local_midnight(next, timezone),
+        }
+    }
+
+    pub fn hours(&self) -> i64 {
+        (self.end - self.start).num_hours()
+    }
+
+    pub fn is_dst_transition(&self) -> bool {
+        self.hours() != 24
+    }
+}
+
+/// First instant of `date` in `timezone`. An ambiguous midnight (clocks
+/// turned back across it) resolves to the earlier offset; a midnight skipped
+/// by a DST jump resolves to the first local time after the gap.
+fn local_midnight(date: NaiveDate, timezone: &Tz) -> DateTime<Utc> {
+    let midnight: NaiveDateTime = date.and_hms_opt(0, 0, 0).unwrap();
+    if let Some(start) = timezone.from_local_datetime(&midnight).earliest() {
+        return start.with_timezone(&Utc);
+    }
+
+    // Gaps are at most a few hours; the UTC reading of midnight is a fallback
+    (1..=6 * 60)
+        .find_map(|minutes| timezone.from_local_datetime(&(midnight + Duration::minutes(minutes))).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, Timelike};
+
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32, tz: &str) -> DeliveryDay {
+        DeliveryDay::new(NaiveDate::from_ymd_opt(y, m, d).unwrap(), &tz.parse().unwrap())
+    }
+
+    #[test]
+    fn test_standard_and_summer_time_days() {
+        let winter = day(2025, 1, 15, "Europe/Berlin");
+        assert_eq!((winter.start.day(), winter.start.hour()), (14, 23));
+        assert_eq!((winter.end.day(), winter.end.hour()), (15, 23));
+        assert_eq!(winter.hours(), 24);
+
+        let summer = day(2025, 7, 15, "Europe/Berlin");
+        assert_eq!((summer.start.day(), summer.start.hour()), (14, 22));
+        assert_eq!((summer.end.day(), summer.end.hour()), (15, 22));
+        assert!(!summer.is_dst_transition());
+    }
+
+    #[test]
+    fn test_transition_days_are_23_and_25_hours() {
+        assert_eq!(day(2025, 3, 30, "Europe/Oslo").hours(), 23);
+        assert_eq!(day(2025, 10, 26, "Europe/Oslo").hours(), 25);
+        assert!(day(2025, 10, 26, "Europe/Oslo").is_dst_transition());
+    }
+
+    #[test]
+    fn test_skipped_and_ambiguous_midnights_do_not_panic() {
+        // Chile jumped from 00:00 to 01:00 on 2022-09-11
+        let skipped = day(2022, 9, 11, "America/Santiago");
+        assert_eq!(skipped.start, Utc.with_ymd_and_hms(2022, 9, 11, 4, 0, 0).unwrap());
+        assert_eq!(skipped.hours(), 23);
+
+        // Cuba turned 01:00 back to 00:00 on 2023-11-05, so midnight happened twice
+        let ambiguous = day(2023, 11, 5, "America/Havana");
+        assert_eq!(ambiguous.start, Utc.with_ymd_and_hms(2023, 11, 5, 4, 0, 0).unwrap());
+        assert_eq!(ambiguous.hours(), 25);
+    }
+}
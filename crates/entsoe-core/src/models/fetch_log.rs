@@ -0,0 +1,97 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "sqlx", sqlx(type_name = "text"))]
+#[cfg_attr(feature = "sqlx", sqlx(rename_all = "lowercase"))]
+pub enum FetchStatus {
+    Pending,
+    Success,
+    NoData,
+    Error,
+    RateLimited,
+}
+
+impl FetchStatus {
+    /// Value stored in the `fetch_log.status` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FetchStatus::Pending => "pending",
+            FetchStatus::Success => "success",
+            FetchStatus::NoData => "nodata",
+            FetchStatus::Error => "error",
+            FetchStatus::RateLimited => "ratelimited",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(FetchStatus::Pending),
+            "success" => Some(FetchStatus::Success),
+            "nodata" => Some(FetchStatus::NoData),
+            "error" => Some(FetchStatus::Error),
+            "ratelimited" => Some(FetchStatus::RateLimited),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct FetchLog {
+    pub id: i64,
+    pub fetch_started_at: DateTime<Utc>,
+    pub fetch_completed_at: Option<DateTime<Utc>>,
+    pub bidding_zone: Option<String>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub status: FetchStatus,
+    pub records_inserted: Option<i32>,
+    pub error_message: Option<String>,
+    pub http_status: Option<i32>,
+    pub duration_ms: Option<i32>,
+    /// ENTSOE base URL that served the fetch.
+    pub endpoint: Option<String>,
+    /// Correlation ID of the API request that triggered the fetch.
+    pub correlation_id: Option<String>,
+}
+
+impl FetchLog {
+    pub fn new(
+        bidding_zone: Option<String>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: 0,
+            fetch_started_at: Utc::now(),
+            fetch_completed_at: None,
+            bidding_zone,
+            period_start,
+            period_end,
+            status: FetchStatus::Pending,
+            records_inserted: None,
+            error_message: None,
+            http_status: None,
+            duration_ms: None,
+            endpoint: None,
+            correlation_id: None,
+        }
+    }
+}
+
+/// A zone and delivery date whose day-ahead fetch failed, queued for retry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct FetchRetry {
+    pub zone_code: String,
+    pub target_date: NaiveDate,
+    /// Retries made so far.
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub enqueued_at: DateTime<Utc>,
+    pub next_attempt_at: DateTime<Utc>,
+    /// Given up on once this passes.
+    pub expires_at: DateTime<Utc>,
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Cross-border exchange reported by ENTSOE. Stored in the
+/// `cross_border_flows.kind` column and selected on the flow endpoint with
+/// `?kind=`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "sqlx", sqlx(type_name = "varchar"))]
+#[cfg_attr(feature = "sqlx", sqlx(rename_all = "snake_case"))]
+#[serde(rename_all = "snake_case")]
+pub enum FlowKind {
+    /// Measured physical flows (A11).
+    #[default]
+    Physical,
+    /// Scheduled commercial exchanges (A09).
+    Scheduled,
+}
+
+impl FlowKind {
+    pub const ALL: [FlowKind; 2] = [FlowKind::Physical, FlowKind::Scheduled];
+
+    /// Value stored in the `cross_border_flows.kind` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlowKind::Physical => "physical",
+            FlowKind::Scheduled => "scheduled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "physical" => Some(FlowKind::Physical),
+            "scheduled" => Some(FlowKind::Scheduled),
+            _ => None,
+        }
+    }
+
+    /// ENTSOE `documentType` for this kind of flow.
+    pub fn document_type(&self) -> &'static str {
+        match self {
+            FlowKind::Physical => "A11",
+            FlowKind::Scheduled => "A09",
+        }
+    }
+}
+
+impl std::fmt::Display for FlowKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Ordered pair of bidding zones; flows are directional from `from` to `to`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct ZonePair {
+    pub from: String,
+    pub to: String,
+}
+
+impl ZonePair {
+    pub fn reversed(&self) -> Self {
+        Self {
+            from: self.to.clone(),
+            to: self.from.clone(),
+        }
+    }
+}
+
+/// Flow of one kind from one zone to another for one interval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct CrossBorderFlow {
+    pub timestamp: DateTime<Utc>,
+    pub from_zone: String,
+    pub to_zone: String,
+    pub kind: FlowKind,
+    pub quantity_mw: Decimal,
+    pub resolution: String,
+    pub fetched_at: DateTime<Utc>,
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Actual generation of one production type in a zone for one interval,
+/// from ENTSOE A75 documents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct GenerationPoint {
+    pub timestamp: DateTime<Utc>,
+    pub bidding_zone: String,
+    /// ENTSOE production type code (`B01`..`B25`).
+    pub psr_type: String,
+    pub quantity_mw: Decimal,
+    pub resolution: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Human-readable name of an ENTSOE production (PSR) type code.
+pub fn psr_type_name(code: &str) -> Option<&'static str> {
+    let name = match code {
+        "B01" => "Biomass",
+        "B02" => "Fossil Brown coal/Lignite",
+        "B03" => "Fossil Coal-derived gas",
+        "B04" => "Fossil Gas",
+        "B05" => "Fossil Hard coal",
+        "B06" => "Fossil Oil",
+        "B07" => "Fossil Oil shale",
+        "B08" => "Fossil Peat",
+        "B09" => "Geothermal",
+        "B10" => "Hydro Pumped Storage",
+        "B11" => "Hydro Run-of-river and poundage",
+        "B12" => "Hydro Water Reservoir",
+        "B13" => "Marine",
+        "B14" => "Nuclear",
+        "B15" => "Other renewable",
+        "B16" => "Solar",
+        "B17" => "Waste",
+        "B18" => "Wind Offshore",
+        "B19" => "Wind Onshore",
+        "B20" => "Other",
+        "B25" => "Energy storage",
+        _ => return None,
+    };
+    Some(name)
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+
+/// A request sent with an `Idempotency-Key`, and its response once one was
+/// stored. Retries of the request with the same key are answered from it
+/// until it expires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct IdempotencyRecord {
+    pub key: String,
+    /// Hash of the method, path and body the key was first used with.
+    pub fingerprint: String,
+    /// `None` while the first request is still running.
+    pub status_code: Option<i32>,
+    pub response_body: Option<String>,
+    /// When the first request claimed the key.
+    pub created_at: DateTime<Utc>,
+    /// When the key may be claimed again: the in-progress timeout while the
+    /// request runs, its retention period once a response is stored.
+    pub expires_at: DateTime<Utc>,
+}
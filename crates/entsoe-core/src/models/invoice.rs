@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+
+use crate::entsoe::parse_resolution;
+
+use super::{Price, Tariff};
+
+/// Energy consumed over one interval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Consumption {
+    pub timestamp: DateTime<Utc>,
+    pub duration: Duration,
+    pub kwh: Decimal,
+}
+
+/// Consumption-weighted cost of a set of prices, broken down the way an
+/// electricity invoice is. Amounts are in the prices' currency and exact;
+/// rounding is left to the caller.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Invoice {
+    /// Consumption that fell within priced intervals.
+    pub priced_kwh: Decimal,
+    pub spot: Decimal,
+    pub support: Decimal,
+    /// Supplier margin and surcharges.
+    pub fees: Decimal,
+    pub vat: Decimal,
+    /// Consumption intervals not fully covered by prices.
+    pub unpriced_intervals: usize,
+}
+
+impl Invoice {
+    /// Weight each price by the consumption falling within its interval.
+    /// Consumption coarser than the prices is spread evenly over its
+    /// interval, so an hourly profile against quarter-hour prices weights
+    /// each quarter equally. Support and VAT follow `tariff`.
+    pub fn compute(prices: &[Price], consumption: &[Consumption], tariff: Option<&Tariff>) -> Self {
+        let intervals: BTreeMap<DateTime<Utc>, (&Price, Duration)> = prices
+            .iter()
+            .filter_map(|price| {
+                let duration = parse_resolution(&price.resolution).ok()?;
+                Some((price.timestamp, (price, duration)))
+            })
+            .collect();
+        let longest = intervals.values().map(|(_, duration)| *duration).max().unwrap_or_default();
+        let tariff = tariff.cloned().unwrap_or_default();
+
+        let mut invoice = Self::default();
+        for used in consumption.iter().filter(|used| used.duration > Duration::zero()) {
+            let end = used.timestamp + used.duration;
+            let mut covered = Duration::zero();
+            for (&start, &(price, duration)) in intervals.range(used.timestamp - longest..end) {
+                let overlap = (start + duration).min(end) - start.max(used.timestamp);
+                if overlap <= Duration::zero() {
+                    continue;
+                }
+                covered += overlap;
+
+                let kwh = used.kwh * Decimal::from(overlap.num_seconds()) / Decimal::from(used.duration.num_seconds());
+                let spot = price.price_kwh;
+                let support = tariff.support(spot);
+                invoice.priced_kwh += kwh;
+                invoice.spot += kwh * spot;
+                invoice.support += kwh * support;
+                invoice.fees += kwh * (tariff.margin_kwh + tariff.surcharge_kwh);
+            }
+            if covered < used.duration {
+                invoice.unpriced_intervals += 1;
+            }
+        }
+        invoice.vat = invoice.net() * tariff.vat_rate;
+        invoice
+    }
+
+    /// Spot less support plus fees, before VAT.
+    pub fn net(&self) -> Decimal {
+        self.spot - self.support + self.fees
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.net() + self.vat
+    }
+
+    /// `amount` per priced kWh; zero when nothing was priced.
+    pub fn per_kwh(&self, amount: Decimal) -> Decimal {
+        if self.priced_kwh.is_zero() {
+            return Decimal::ZERO;
+        }
+        amount / self.priced_kwh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::models::SupportScheme;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn hour(h: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + Duration::hours(h)
+    }
+
+    fn quarter_prices(h: i64, kwh: [&str; 4]) -> Vec<Price> {
+        kwh.iter()
+            .enumerate()
+            .map(|(q, price)| Price {
+                price_kwh: dec(price),
+                ..Price::from_mwh(hour(h) + Duration::minutes(15 * q as i64), "NO1".into(), 0.0, "PT15M".into())
+            })
+            .collect()
+    }
+
+    fn hourly(h: i64, kwh: &str) -> Consumption {
+        Consumption {
+            timestamp: hour(h),
+            duration: Duration::hours(1),
+            kwh: dec(kwh),
+        }
+    }
+
+    #[test]
+    fn test_weights_prices_by_consumption() {
+        let prices = [quarter_prices(0, ["0.10"; 4]), quarter_prices(1, ["1.00", "2.00", "1.00", "2.00"])].concat();
+        let invoice = Invoice::compute(&prices, &[hourly(0, "3"), hourly(1, "1"), hourly(2, "5")], None);
+
+        assert_eq!(invoice.priced_kwh, dec("4"));
+        assert_eq!(invoice.spot, dec("1.8"));
+        assert_eq!(invoice.per_kwh(invoice.spot), dec("0.45"));
+        assert_eq!(invoice.total(), invoice.spot);
+        assert_eq!(invoice.unpriced_intervals, 1);
+    }
+
+    #[test]
+    fn test_support_and_vat_follow_tariff() {
+        let tariff = Tariff {
+            vat_rate: dec("0.25"),
+            surcharge_kwh: dec("0.2"),
+            margin_kwh: dec("0.05"),
+            support: Some(SupportScheme {
+                threshold_kwh: dec("0.75"),
+                coverage_rate: dec("0.9"),
+            }),
+        };
+        let prices = [quarter_prices(0, ["0.50"; 4]), quarter_prices(1, ["1.75"; 4])].concat();
+        let invoice = Invoice::compute(&prices, &[hourly(0, "2"), hourly(1, "2")], Some(&tariff));
+
+        assert_eq!(invoice.spot, dec("4.5"));
+        assert_eq!(invoice.support, dec("1.8"));
+        assert_eq!(invoice.fees, dec("1"));
+        assert_eq!(invoice.net(), dec("3.7"));
+        assert_eq!(invoice.vat, dec("0.925"));
+        assert_eq!(invoice.total(), dec("4.625"));
+        assert_eq!(invoice.unpriced_intervals, 0);
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Actual total load (consumption) of a zone for one interval, from ENTSOE
+/// A65 documents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct LoadValue {
+    pub timestamp: DateTime<Utc>,
+    pub bidding_zone: String,
+    pub load_mw: Decimal,
+    pub resolution: String,
+    pub fetched_at: DateTime<Utc>,
+}
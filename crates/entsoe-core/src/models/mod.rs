@@ -0,0 +1,33 @@
+pub mod price;
+pub mod alert;
+pub mod api_key;
+pub mod backfill_job;
+pub mod bidding_zone;
+pub mod delivery_day;
+pub mod fetch_log;
+pub mod flow;
+pub mod generation;
+pub mod idempotency;
+pub mod invoice;
+pub mod load;
+pub mod resolution;
+pub mod tariff;
+pub mod task;
+pub mod translation;
+
+pub use price::{EnergyUnit, GapFill, Market, Price, RawPricePoint};
+pub use alert::{AlertChannel, AlertComparison, AlertEvent, AlertRule, NewAlertRule};
+pub use api_key::{ApiKey, ApiRole};
+pub use backfill_job::{BackfillJob, BackfillJobStatus, NewBackfillJob};
+pub use bidding_zone::{is_valid_eic, BiddingZone, ZoneDefinition};
+pub use delivery_day::DeliveryDay;
+pub use fetch_log::{FetchLog, FetchRetry, FetchStatus};
+pub use flow::{CrossBorderFlow, FlowKind, ZonePair};
+pub use generation::{psr_type_name, GenerationPoint};
+pub use idempotency::IdempotencyRecord;
+pub use invoice::{Consumption, Invoice};
+pub use load::LoadValue;
+pub use resolution::PriceResolution;
+pub use tariff::{SupportScheme, Tariff};
+pub use task::{NewTask, Task, TaskStatus};
+pub use translation::{NameKind, NameTranslation, SUPPORTED_LOCALES};
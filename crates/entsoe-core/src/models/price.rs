@@ -0,0 +1,257 @@
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::entsoe::parse_resolution;
+
+/// Auction a price was cleared in. Stored in the `electricity_prices.market`
+/// column and selected on the price endpoints with `?market=`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "sqlx", sqlx(type_name = "varchar"))]
+#[cfg_attr(feature = "sqlx", sqlx(rename_all = "snake_case"))]
+#[serde(rename_all = "snake_case")]
+pub enum Market {
+    #[default]
+    DayAhead,
+    Intraday,
+}
+
+impl Market {
+    pub const ALL: [Market; 2] = [Market::DayAhead, Market::Intraday];
+
+    /// Value stored in the `electricity_prices.market` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Market::DayAhead => "day_ahead",
+            Market::Intraday => "intraday",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "day_ahead" => Some(Market::DayAhead),
+            "intraday" => Some(Market::Intraday),
+            _ => None,
+        }
+    }
+
+    /// ENTSOE `contract_MarketAgreement.type` for this market's A44 documents.
+    pub fn contract_type(&self) -> &'static str {
+        match self {
+            Market::DayAhead => "A01",
+            Market::Intraday => "A07",
+        }
+    }
+
+    pub fn from_contract_type(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.contract_type() == code)
+    }
+}
+
+impl std::fmt::Display for Market {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a price ENTSOE did not publish was filled in. Stored in the
+/// `electricity_prices.gap_fill` column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GapFill {
+    /// The previous published price.
+    ForwardFill,
+    /// On the line between the published prices either side of the gap.
+    LinearInterpolation,
+}
+
+impl GapFill {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GapFill::ForwardFill => "forward_fill",
+            GapFill::LinearInterpolation => "linear_interpolation",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "forward_fill" => Some(GapFill::ForwardFill),
+            "linear_interpolation" => Some(GapFill::LinearInterpolation),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for GapFill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Energy unit a price amount is quoted per, from ENTSOE's
+/// `price_Measure_Unit.name`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnergyUnit {
+    #[default]
+    MegawattHour,
+    KilowattHour,
+}
+
+impl EnergyUnit {
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_uppercase().as_str() {
+            "MWH" => Some(EnergyUnit::MegawattHour),
+            "KWH" => Some(EnergyUnit::KilowattHour),
+            _ => None,
+        }
+    }
+
+    /// Symbol used in API responses.
+    pub fn label(&self) -> &'static str {
+        match self {
+            EnergyUnit::MegawattHour => "MWh",
+            EnergyUnit::KilowattHour => "kWh",
+        }
+    }
+
+    /// kWh in one unit.
+    fn kwh(&self) -> Decimal {
+        match self {
+            EnergyUnit::MegawattHour => Decimal::ONE_THOUSAND,
+            EnergyUnit::KilowattHour => Decimal::ONE,
+        }
+    }
+
+    /// Express a per-kWh price per this unit. Exact, as stored prices are
+    /// decimal.
+    pub fn from_kwh(&self, price_kwh: Decimal) -> Decimal {
+        match self {
+            EnergyUnit::KilowattHour => price_kwh,
+            EnergyUnit::MegawattHour => (price_kwh * self.kwh()).normalize(),
+        }
+    }
+
+    /// Express a price per this unit per kWh.
+    pub fn to_kwh(&self, price: Decimal) -> Decimal {
+        price / self.kwh()
+    }
+}
+
+/// A price point as ENTSOE publishes it: the amount per `unit` in the time
+/// series' currency, before conversion to a per-kWh [`Price`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawPricePoint {
+    pub timestamp: DateTime<Utc>,
+    pub bidding_zone: String,
+    /// Amount as published, e.g. per MWh.
+    pub amount: Decimal,
+    pub unit: EnergyUnit,
+    pub currency: String,
+    pub resolution: String,
+    /// Set when ENTSOE omitted the position (or it was rejected as
+    /// implausible) and the value was filled in.
+    pub gap_fill: Option<GapFill>,
+}
+
+impl RawPricePoint {
+    /// Parse a published amount. Amounts that are not finite become zero.
+    pub fn parse_amount(amount: f64) -> Decimal {
+        Decimal::from_str(&amount.to_string()).unwrap_or(Decimal::ZERO)
+    }
+
+    /// The amount per kWh. Exact, so [`EnergyUnit::from_kwh`] recovers it.
+    pub fn price_kwh(&self) -> Decimal {
+        self.unit.to_kwh(self.amount)
+    }
+
+    pub fn into_price(self, market: Market, fetched_at: DateTime<Utc>) -> Price {
+        Price {
+            timestamp: self.timestamp,
+            price_kwh: self.price_kwh(),
+            bidding_zone: self.bidding_zone,
+            currency: self.currency,
+            resolution: self.resolution,
+            fetched_at,
+            market,
+            gap_fill: self.gap_fill,
+        }
+    }
+}
+
+/// A per-kWh price as the service stores, caches and serves it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Price {
+    pub timestamp: DateTime<Utc>,
+    pub bidding_zone: String,
+    pub price_kwh: Decimal,
+    pub currency: String,
+    pub resolution: String,
+    pub fetched_at: DateTime<Utc>,
+    pub market: Market,
+    /// How the price was filled in, when ENTSOE did not publish it.
+    #[serde(default)]
+    pub gap_fill: Option<GapFill>,
+}
+
+impl Price {
+    /// A day-ahead EUR price from an amount per MWh, fetched now.
+    pub fn from_mwh(
+        timestamp: DateTime<Utc>,
+        bidding_zone: String,
+        price_mwh: f64,
+        resolution: String,
+    ) -> Self {
+        RawPricePoint {
+            timestamp,
+            bidding_zone,
+            amount: RawPricePoint::parse_amount(price_mwh),
+            unit: EnergyUnit::MegawattHour,
+            currency: "EUR".to_string(),
+            resolution,
+            gap_fill: None,
+        }
+        .into_price(Market::DayAhead, Utc::now())
+    }
+
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.market = market;
+        self
+    }
+
+    /// Whether the price was filled in rather than published.
+    pub fn filled(&self) -> bool {
+        self.gap_fill.is_some()
+    }
+
+    /// End of the interval the price covers, from its resolution. A
+    /// resolution that does not parse is taken to be an hour.
+    pub fn interval_end(&self) -> DateTime<Utc> {
+        self.timestamp + parse_resolution(&self.resolution).unwrap_or_else(|_| Duration::hours(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_point_keeps_published_amount() {
+        let raw = RawPricePoint {
+            timestamp: Utc::now(),
+            bidding_zone: "GB".to_string(),
+            amount: RawPricePoint::parse_amount(87.33),
+            unit: EnergyUnit::MegawattHour,
+            currency: "GBP".to_string(),
+            resolution: "PT30M".to_string(),
+            gap_fill: Some(GapFill::ForwardFill),
+        };
+        let price = raw.clone().into_price(Market::Intraday, raw.timestamp);
+
+        assert_eq!(price.price_kwh.to_string(), "0.08733");
+        assert_eq!(raw.unit.from_kwh(price.price_kwh), raw.amount);
+        assert_eq!((price.currency.as_str(), price.market, price.gap_fill), ("GBP", Market::Intraday, Some(GapFill::ForwardFill)));
+    }
+}
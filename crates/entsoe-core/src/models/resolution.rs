@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entsoe::parse_resolution;
+
+use super::Price;
+
+/// Resolution a zone's prices are served at. Prices are stored at the
+/// resolution ENTSOE publishes and averaged up to this on read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum PriceResolution {
+    #[serde(rename = "PT15M")]
+    QuarterHour,
+    #[serde(rename = "PT30M")]
+    HalfHour,
+    #[default]
+    #[serde(rename = "PT60M")]
+    Hour,
+}
+
+impl PriceResolution {
+    /// ISO 8601 duration, as used for `Price::resolution`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceResolution::QuarterHour => "PT15M",
+            PriceResolution::HalfHour => "PT30M",
+            PriceResolution::Hour => "PT60M",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            PriceResolution::QuarterHour => Duration::minutes(15),
+            PriceResolution::HalfHour => Duration::minutes(30),
+            PriceResolution::Hour => Duration::hours(1),
+        }
+    }
+
+    /// Average prices finer than this resolution into intervals of this
+    /// resolution. Prices at this resolution or coarser pass through
+    /// unchanged. `prices` must be for a single zone and market; the result
+    /// is ordered by timestamp.
+    pub fn aggregate(&self, prices: Vec<Price>) -> Vec<Price> {
+        let target = self.duration();
+        let is_finer = |price: &Price| parse_resolution(&price.resolution).is_ok_and(|native| native < target);
+        if !prices.iter().any(is_finer) {
+            return prices;
+        }
+
+        let mut aggregated = Vec::with_capacity(prices.len());
+        let mut groups: BTreeMap<DateTime<Utc>, Vec<Price>> = BTreeMap::new();
+        for price in prices {
+            if is_finer(&price) {
+                let start = price.timestamp.duration_trunc(target).unwrap_or(price.timestamp);
+                groups.entry(start).or_default().push(price);
+            } else {
+                aggregated.push(price);
+            }
+        }
+
+        for (start, group) in groups {
+            let sum: Decimal = group.iter().map(|p| p.price_kwh).sum();
+            let first = &group[0];
+            aggregated.push(Price {
+                timestamp: start,
+                bidding_zone: first.bidding_zone.clone(),
+                price_kwh: sum / Decimal::from(group.len()),
+                currency: first.currency.clone(),
+                resolution: self.as_str().to_string(),
+                fetched_at: group.iter().map(|p| p.fetched_at).max().unwrap_or(first.fetched_at),
+                market: first.market,
+                // A mean over any filled point counts as filled, by the first one's method
+                gap_fill: group.iter().find_map(|p| p.gap_fill),
+            });
+        }
+
+        aggregated.sort_by_key(|p| p.timestamp);
+        aggregated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::{TimeZone, Timelike};
+
+    use super::*;
+
+    fn series(resolution: &str, minutes: i64, mwh: &[f64]) -> Vec<Price> {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        mwh.iter()
+            .enumerate()
+            .map(|(i, &price)| {
+                Price::from_mwh(
+                    start + Duration::minutes(minutes * i as i64),
+                    "GB".to_string(),
+                    price,
+                    resolution.to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quarter_hours_average_to_hours() {
+        let prices = series("PT15M", 15, &[40.0, 50.0, 60.0, 70.0, 80.0, 80.0, 80.0, 80.0]);
+
+        let hourly = PriceResolution::Hour.aggregate(prices);
+        assert_eq!(hourly.len(), 2);
+        assert_eq!(hourly[0].price_kwh, Decimal::from_str("0.055").unwrap());
+        assert_eq!(hourly[1].timestamp.hour(), 1);
+        assert!(hourly.iter().all(|p| p.resolution == "PT60M"));
+    }
+
+    #[test]
+    fn test_half_hours_served_natively_or_averaged() {
+        let prices = series("PT30M", 30, &[40.0, 60.0, 70.0, 90.0]);
+
+        let native = PriceResolution::HalfHour.aggregate(prices.clone());
+        assert_eq!(native.len(), 4);
+        assert_eq!(native[1].price_kwh, Decimal::from_str("0.06").unwrap());
+
+        let hourly = PriceResolution::Hour.aggregate(prices);
+        assert_eq!(hourly.len(), 2);
+        assert_eq!(hourly[0].price_kwh, Decimal::from_str("0.05").unwrap());
+        assert_eq!(hourly[1].price_kwh, Decimal::from_str("0.08").unwrap());
+    }
+
+    #[test]
+    fn test_coarser_prices_pass_through() {
+        let prices = series("PT60M", 60, &[40.0, 50.0]);
+        assert_eq!(PriceResolution::HalfHour.aggregate(prices.clone()).len(), 2);
+
+        // Hourly rows stored before a zone switched to quarter-hours are kept as-is
+        let mut mixed = prices;
+        mixed.extend(series("PT15M", 15, &[10.0, 20.0, 30.0, 40.0]).into_iter().map(|mut p| {
+            p.timestamp += Duration::hours(2);
+            p
+        }));
+        let hourly = PriceResolution::Hour.aggregate(mixed);
+        assert_eq!(hourly.len(), 3);
+        assert_eq!(hourly[2].price_kwh, Decimal::from_str("0.025").unwrap());
+    }
+}
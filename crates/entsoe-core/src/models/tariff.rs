@@ -0,0 +1,86 @@
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// What a household in a zone pays on top of the spot price. Amounts are per
+/// kWh in the zone's price currency.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Tariff {
+    /// VAT as a fraction, e.g. `0.25` for 25%.
+    #[serde(default)]
+    pub vat_rate: Decimal,
+    /// Fixed surcharge such as grid fees and energy taxes.
+    #[serde(default)]
+    pub surcharge_kwh: Decimal,
+    /// Supplier markup.
+    #[serde(default)]
+    pub margin_kwh: Decimal,
+    /// Government support paid back on high spot prices, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support: Option<SupportScheme>,
+}
+
+/// Support covering a share of each hour's spot price above a threshold, as
+/// with the Norwegian electricity support. Applied before VAT.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SupportScheme {
+    /// Spot price per kWh, excluding VAT, above which support is paid.
+    pub threshold_kwh: Decimal,
+    /// Share of the excess covered, e.g. `0.9` for 90%.
+    pub coverage_rate: Decimal,
+}
+
+impl Tariff {
+    /// `(spot - support + margin + surcharge) * (1 + vat)`, rounded to 6 decimals.
+    pub fn consumer_price(&self, spot: Decimal) -> Decimal {
+        let net = spot - self.support(spot) + self.margin_kwh + self.surcharge_kwh;
+        (net * (Decimal::ONE + self.vat_rate)).round_dp(6)
+    }
+
+    /// Support per kWh at `spot`, excluding VAT.
+    pub fn support(&self, spot: Decimal) -> Decimal {
+        self.support.as_ref().map_or(Decimal::ZERO, |support| {
+            (spot - support.threshold_kwh).max(Decimal::ZERO) * support.coverage_rate
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_consumer_price_adds_fees_before_vat() {
+        let tariff = Tariff {
+            vat_rate: dec("0.25"),
+            surcharge_kwh: dec("0.05"),
+            margin_kwh: dec("0.01"),
+            support: None,
+        };
+        assert_eq!(tariff.consumer_price(dec("0.04")), dec("0.125"));
+        // Negative spot prices lower the bill, VAT included
+        assert_eq!(tariff.consumer_price(dec("-0.02")), dec("0.05"));
+        assert_eq!(Tariff::default().consumer_price(dec("0.0425")), dec("0.0425"));
+    }
+
+    #[test]
+    fn test_support_covers_share_above_threshold() {
+        let tariff = Tariff {
+            vat_rate: dec("0.25"),
+            support: Some(SupportScheme {
+                threshold_kwh: dec("0.75"),
+                coverage_rate: dec("0.9"),
+            }),
+            ..Tariff::default()
+        };
+        assert_eq!(tariff.support(dec("0.50")), Decimal::ZERO);
+        assert_eq!(tariff.support(dec("1.75")), dec("0.9"));
+        assert_eq!(tariff.consumer_price(dec("1.75")), dec("1.0625"));
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Where a queued background task stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    /// Waiting for a worker, either new or backing off before a retry.
+    Queued,
+    Running,
+    Succeeded,
+    /// Failed on its last allowed attempt.
+    Failed,
+}
+
+impl TaskStatus {
+    pub const ALL: [TaskStatus; 4] = [
+        TaskStatus::Queued,
+        TaskStatus::Running,
+        TaskStatus::Succeeded,
+        TaskStatus::Failed,
+    ];
+
+    /// Value stored in the `tasks.status` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Queued => "queued",
+            TaskStatus::Running => "running",
+            TaskStatus::Succeeded => "succeeded",
+            TaskStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "queued" => Some(TaskStatus::Queued),
+            "running" => Some(TaskStatus::Running),
+            "succeeded" => Some(TaskStatus::Succeeded),
+            "failed" => Some(TaskStatus::Failed),
+            _ => None,
+        }
+    }
+
+    /// Whether the task will not run again.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TaskStatus::Succeeded | TaskStatus::Failed)
+    }
+}
+
+/// A unit of background work in the task queue, picked up by whichever
+/// replica's worker claims it first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub id: i64,
+    /// Selects the handler that runs the task.
+    pub kind: String,
+    /// JSON arguments for the handler.
+    pub payload: String,
+    /// At most one unfinished task exists per key.
+    pub dedupe_key: Option<String>,
+    pub status: TaskStatus,
+    /// Attempts started so far, including the running one.
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    /// Earliest time a worker may claim the task.
+    pub run_after: DateTime<Utc>,
+    /// While running, when the claim lapses unless renewed by the worker.
+    pub lease_expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTask {
+    pub kind: String,
+    pub payload: String,
+    pub dedupe_key: Option<String>,
+    pub max_attempts: i32,
+    pub run_after: DateTime<Utc>,
+}
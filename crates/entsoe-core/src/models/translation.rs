@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Locales with display-name translations, accepted as `?lang=`.
+pub const SUPPORTED_LOCALES: [&str; 3] = ["nb", "sv", "de"];
+
+/// What a translated display name belongs to. Stored in the
+/// `name_translations.kind` column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "sqlx", sqlx(type_name = "varchar"))]
+#[cfg_attr(feature = "sqlx", sqlx(rename_all = "snake_case"))]
+#[serde(rename_all = "snake_case")]
+pub enum NameKind {
+    /// A bidding zone, keyed by zone code.
+    Zone,
+    /// A country, keyed by ISO country code.
+    Country,
+}
+
+impl NameKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameKind::Zone => "zone",
+            NameKind::Country => "country",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "zone" => Some(NameKind::Zone),
+            "country" => Some(NameKind::Country),
+            _ => None,
+        }
+    }
+}
+
+/// Display name of a zone or country in one locale.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct NameTranslation {
+    pub kind: NameKind,
+    pub code: String,
+    pub locale: String,
+    pub name: String,
+}
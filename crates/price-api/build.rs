@@ -0,0 +1,12 @@
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // protoc and the well-known type definitions ship with the build, so no
+    // system protobuf install is needed
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    let well_known = protoc_bin_vendored::include_path()?;
+
+    tonic_prost_build::configure()
+        .compile_protos(&[PathBuf::from("proto/prices.proto")], &[PathBuf::from("proto"), well_known])?;
+    Ok(())
+}
@@ -0,0 +1,5 @@
+mod notification;
+mod service;
+
+pub use notification::{AlertNotification, AlertPrice};
+pub use service::AlertService;
@@ -0,0 +1,85 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::{AlertComparison, AlertEvent, AlertRule};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertPrice {
+    pub timestamp: DateTime<Utc>,
+    /// Per kWh, in the notification's `currency`.
+    pub price: Decimal,
+}
+
+/// Body of a webhook or MQTT alert: every newly matching price for one rule
+/// from a single evaluation, so a run of cheap hours arrives as one message.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertNotification {
+    pub id: Uuid,
+    pub fired_at: DateTime<Utc>,
+    pub rule_id: i64,
+    pub zone_code: String,
+    pub comparison: AlertComparison,
+    pub threshold: Decimal,
+    /// Currency the zone is priced in, as published by ENTSOE.
+    pub currency: String,
+    pub unit: &'static str,
+    pub prices: Vec<AlertPrice>,
+}
+
+impl AlertNotification {
+    pub fn new(rule: &AlertRule, events: &[AlertEvent], currency: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            fired_at: Utc::now(),
+            rule_id: rule.id,
+            zone_code: rule.zone_code.clone(),
+            comparison: rule.comparison,
+            threshold: rule.threshold,
+            currency: currency.to_string(),
+            unit: "kWh",
+            prices: events
+                .iter()
+                .map(|event| AlertPrice {
+                    timestamp: event.price_timestamp,
+                    price: event.price_kwh,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn subject(&self) -> String {
+        format!(
+            "Price alert: {} {} {} {}/kWh",
+            self.zone_code,
+            self.comparison.as_str(),
+            self.threshold,
+            self.currency
+        )
+    }
+
+    /// Plain-text body for email delivery.
+    pub fn text(&self) -> String {
+        let mut text = format!(
+            "Prices in {} {} {} {}/kWh (rule {}):\n\n",
+            self.zone_code,
+            self.comparison.as_str(),
+            self.threshold,
+            self.currency,
+            self.rule_id
+        );
+        for price in &self.prices {
+            let _ = writeln!(
+                text,
+                "{}  {} {}/kWh",
+                price.timestamp.format("%Y-%m-%d %H:%M UTC"),
+                price.price,
+                self.currency
+            );
+        }
+        text
+    }
+}
@@ -0,0 +1,454 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DurationRound, Utc};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Address, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use reqwest::Client;
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use tracing::{debug, error, info, warn};
+
+use crate::clock::{Clock, SystemClock};
+use crate::config::{AlertConfig, EmailConfig, MqttConfig};
+use crate::entsoe::parse_resolution;
+use crate::metrics;
+use crate::models::{AlertChannel, AlertEvent, AlertRule, Market, NewAlertRule, Price};
+use crate::storage::{PriceStore, StorageError};
+use crate::webhooks::sign_payload;
+
+use super::notification::AlertNotification;
+
+struct EmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+/// Evaluates the registered alert rules against newly stored prices and
+/// delivers each rule's matches over its channel. Every rule fires at most
+/// once per price interval, however often that interval is refetched.
+pub struct AlertService {
+    repository: Arc<dyn PriceStore>,
+    client: Client,
+    webhook_secret: String,
+    mqtt: Option<AsyncClient>,
+    email: Option<EmailSender>,
+    clock: Arc<dyn Clock>,
+}
+
+impl AlertService {
+    /// Build the service, connecting to the MQTT broker in the background
+    /// when one is configured, which needs a Tokio runtime.
+    pub fn from_config(config: &AlertConfig, repository: Arc<dyn PriceStore>) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            !config.webhook_secret.is_empty(),
+            "alerts.webhook_secret must be set when alerts are enabled"
+        );
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()?;
+
+        Ok(Self {
+            repository,
+            client,
+            webhook_secret: config.webhook_secret.clone(),
+            mqtt: config.mqtt.as_ref().map(connect_mqtt).transpose()?,
+            email: config.email.as_ref().map(email_sender).transpose()?,
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Check that `rule` can be delivered: its channel is configured and its
+    /// target is a valid URL, topic or address for that channel.
+    pub fn validate_rule(&self, rule: &NewAlertRule) -> Result<(), String> {
+        match rule.channel {
+            AlertChannel::Webhook => {
+                let url = reqwest::Url::parse(&rule.target).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    return Err("Webhook URL must use http or https".to_string());
+                }
+            }
+            AlertChannel::Mqtt => {
+                if self.mqtt.is_none() {
+                    return Err("MQTT alerts are not configured".to_string());
+                }
+                if rule.target.is_empty() || rule.target.contains(['+', '#']) {
+                    return Err("MQTT target must be a topic name without wildcards".to_string());
+                }
+            }
+            AlertChannel::Email => {
+                if self.email.is_none() {
+                    return Err("Email alerts are not configured".to_string());
+                }
+                rule.target
+                    .parse::<Address>()
+                    .map_err(|e| format!("Invalid email address: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate `prices` in the background so fetches are never held up by
+    /// slow alert receivers.
+    pub fn notify(self: &Arc<Self>, prices: Vec<Price>) {
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            if let Err(e) = service.evaluate(&prices).await {
+                error!(error = %e, "Failed to evaluate price alerts");
+            }
+        });
+    }
+
+    /// Fire every rule matched by a day-ahead price that is current or still
+    /// to come and has not fired for that interval before. Failed deliveries
+    /// of firings from the current hour on are resent for the zones in
+    /// `prices`. Returns the number of alert events recorded.
+    pub async fn evaluate(&self, prices: &[Price]) -> Result<usize, StorageError> {
+        let now = self.clock.now();
+        let upcoming: Vec<&Price> = prices
+            .iter()
+            .filter(|price| price.market == Market::DayAhead)
+            .filter(|price| {
+                let resolution = parse_resolution(&price.resolution).unwrap_or(chrono::Duration::hours(1));
+                price.timestamp + resolution > now
+            })
+            .collect();
+
+        let rules = self.repository.list_alert_rules(None).await?;
+        let mut candidates = Vec::new();
+        for rule in &rules {
+            for price in upcoming.iter().filter(|price| price.bidding_zone == rule.zone_code) {
+                if rule.comparison.matches(price.price_kwh, rule.threshold) {
+                    candidates.push(AlertEvent {
+                        fired_at: now,
+                        ..AlertEvent::new(rule.id, price.timestamp, price.price_kwh)
+                    });
+                }
+            }
+        }
+
+        let fired = if candidates.is_empty() {
+            Vec::new()
+        } else {
+            self.repository.insert_alert_events(&candidates).await?
+        };
+        let current_hour = now.duration_trunc(chrono::Duration::hours(1)).unwrap_or(now);
+        let zones: HashSet<&str> = prices.iter().map(|price| price.bidding_zone.as_str()).collect();
+        let failed: Vec<AlertEvent> = self
+            .repository
+            .failed_alert_events(current_hour)
+            .await?
+            .into_iter()
+            .filter(|event| rules.iter().any(|rule| rule.id == event.rule_id && zones.contains(rule.zone_code.as_str())))
+            .collect();
+        if !failed.is_empty() {
+            info!(events = failed.len(), "Retrying failed price alert deliveries");
+        }
+        let mut by_rule: BTreeMap<i64, Vec<AlertEvent>> = BTreeMap::new();
+        for event in failed.into_iter().chain(fired.iter().cloned()) {
+            by_rule.entry(event.rule_id).or_default().push(event);
+        }
+
+        for (rule_id, events) in by_rule {
+            let Some(rule) = rules.iter().find(|rule| rule.id == rule_id) else {
+                continue;
+            };
+            let currency = prices
+                .iter()
+                .find(|price| price.bidding_zone == rule.zone_code)
+                .map_or("EUR", |price| price.currency.as_str());
+            let notification = AlertNotification::new(rule, &events, currency);
+            let result = self.deliver(rule, &notification).await;
+            metrics::record_alert_fired(rule.channel.as_str(), result.is_ok());
+            match &result {
+                Ok(()) => debug!(rule_id, zone = %rule.zone_code, prices = events.len(), "Price alert delivered"),
+                Err(e) => warn!(rule_id, zone = %rule.zone_code, error = %e, "Price alert delivery failed"),
+            }
+
+            for event in &events {
+                if let Err(e) = self
+                    .repository
+                    .complete_alert_event(rule_id, event.price_timestamp, result.is_ok(), result.clone().err())
+                    .await
+                {
+                    warn!(rule_id, error = %e, "Failed to record alert delivery");
+                }
+            }
+        }
+
+        Ok(fired.len())
+    }
+
+    async fn deliver(&self, rule: &AlertRule, notification: &AlertNotification) -> Result<(), String> {
+        match rule.channel {
+            AlertChannel::Webhook => self.deliver_webhook(&rule.target, notification).await,
+            AlertChannel::Mqtt => {
+                let mqtt = self.mqtt.as_ref().ok_or("MQTT alerts are not configured")?;
+                let body = serde_json::to_vec(notification).map_err(|e| e.to_string())?;
+                mqtt.publish(rule.target.as_str(), QoS::AtLeastOnce, false, body)
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            AlertChannel::Email => {
+                let email = self.email.as_ref().ok_or("Email alerts are not configured")?;
+                let to: Address = rule.target.parse().map_err(|e: lettre::address::AddressError| e.to_string())?;
+                let message = Message::builder()
+                    .from(email.from.clone())
+                    .to(Mailbox::new(None, to))
+                    .subject(notification.subject())
+                    .header(ContentType::TEXT_PLAIN)
+                    .body(notification.text())
+                    .map_err(|e| e.to_string())?;
+                email.transport.send(message).await.map(|_| ()).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    async fn deliver_webhook(&self, url: &str, notification: &AlertNotification) -> Result<(), String> {
+        let body = serde_json::to_string(notification).map_err(|e| e.to_string())?;
+        let timestamp = Utc::now().timestamp();
+        let response = self
+            .client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("X-Webhook-Id", notification.id.to_string())
+            .header("X-Webhook-Event", "price_alert")
+            .header("X-Webhook-Timestamp", timestamp.to_string())
+            .header(
+                "X-Webhook-Signature",
+                format!("sha256={}", sign_payload(&self.webhook_secret, timestamp, &body)),
+            )
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Receiver responded with {}", response.status()))
+        }
+    }
+}
+
+/// The client only queues publishes; the event loop task does the network
+/// I/O and reconnects after errors.
+fn connect_mqtt(config: &MqttConfig) -> anyhow::Result<AsyncClient> {
+    let runtime = tokio::runtime::Handle::try_current().context("MQTT alerts need a Tokio runtime")?;
+    let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        options.set_credentials(username, password);
+    }
+
+    let (client, mut event_loop) = AsyncClient::new(options, 64);
+    let broker = format!("{}:{}", config.host, config.port);
+    runtime.spawn(async move {
+        loop {
+            if let Err(e) = event_loop.poll().await {
+                warn!(broker = %broker, error = %e, "MQTT connection error, retrying");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        }
+    });
+    info!(host = %config.host, port = config.port, "MQTT alert delivery enabled");
+    Ok(client)
+}
+
+fn email_sender(config: &EmailConfig) -> anyhow::Result<EmailSender> {
+    let mut builder = if config.plaintext {
+        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
+            .context("Invalid SMTP relay")?
+    }
+    .port(config.smtp_port);
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+
+    Ok(EmailSender {
+        transport: builder.build(),
+        from: config.from.parse().context("Invalid alerts.email.from mailbox")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use axum::{extract::State, http::{HeaderMap, StatusCode}, routing::post, Router};
+    use chrono::TimeZone;
+    use rust_decimal::Decimal;
+
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::config::MqttConfig;
+    use crate::models::AlertComparison;
+    use crate::storage::InMemoryPriceStore;
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+    async fn receive(State(received): State<Received>, headers: HeaderMap, body: String) -> StatusCode {
+        received.lock().unwrap().push((headers, body));
+        StatusCode::NO_CONTENT
+    }
+
+    fn config() -> AlertConfig {
+        AlertConfig {
+            enabled: true,
+            webhook_secret: "s3cret".to_string(),
+            timeout_seconds: 5,
+            mqtt: None,
+            email: None,
+        }
+    }
+
+    fn rule(channel: AlertChannel, target: &str) -> NewAlertRule {
+        NewAlertRule {
+            zone_code: "NO1".to_string(),
+            comparison: AlertComparison::Below,
+            threshold: Decimal::new(1, 2),
+            channel,
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rules_for_unconfigured_channels_are_rejected() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let service = AlertService::from_config(&config(), Arc::new(InMemoryPriceStore::new())).unwrap();
+
+        assert!(service.validate_rule(&rule(AlertChannel::Webhook, "https://example.com/hook")).is_ok());
+        assert!(service.validate_rule(&rule(AlertChannel::Webhook, "ftp://example.com")).is_err());
+        assert!(service.validate_rule(&rule(AlertChannel::Mqtt, "prices/no1")).is_err());
+        assert!(service.validate_rule(&rule(AlertChannel::Email, "ops@example.com")).is_err());
+    }
+
+    #[test]
+    fn test_config_needs_a_webhook_secret_and_a_runtime_for_mqtt() {
+        let store = || -> Arc<dyn PriceStore> { Arc::new(InMemoryPriceStore::new()) };
+        let unsigned = AlertConfig {
+            webhook_secret: String::new(),
+            ..config()
+        };
+        let error = AlertService::from_config(&unsigned, store()).err().unwrap();
+        assert_eq!(error.to_string(), "alerts.webhook_secret must be set when alerts are enabled");
+
+        let mqtt = AlertConfig {
+            mqtt: Some(MqttConfig {
+                host: "localhost".to_string(),
+                port: 1883,
+                client_id: "test".to_string(),
+                username: None,
+                password: None,
+            }),
+            ..config()
+        };
+        // An error rather than a panic outside a runtime
+        assert!(AlertService::from_config(&mqtt, store()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failed_deliveries_are_resent_on_the_next_evaluation() {
+        let attempts = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let app = Router::new().route(
+            "/hook",
+            post({
+                let attempts = Arc::clone(&attempts);
+                // The first delivery fails
+                move || async move {
+                    match attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                        0 => StatusCode::SERVICE_UNAVAILABLE,
+                        _ => StatusCode::NO_CONTENT,
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let store = Arc::new(InMemoryPriceStore::new());
+        let created = store
+            .create_alert_rule(&rule(AlertChannel::Webhook, &format!("http://{}/hook", addr)))
+            .await
+            .unwrap();
+        let hour = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let service = AlertService::from_config(&config(), store.clone())
+            .unwrap()
+            .with_clock(Arc::new(FixedClock::new(hour + chrono::Duration::minutes(20))));
+        let prices = vec![Price::from_mwh(hour + chrono::Duration::hours(1), "NO1".to_string(), 3.0, "PT60M".to_string())];
+
+        assert_eq!(service.evaluate(&prices).await.unwrap(), 1);
+        assert_eq!(store.get_alert_events(created.id, 10).await.unwrap()[0].delivered, Some(false));
+
+        assert_eq!(service.evaluate(&prices).await.unwrap(), 0);
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(store.get_alert_events(created.id, 10).await.unwrap()[0].delivered, Some(true));
+
+        // Nothing left to resend
+        service.evaluate(&prices).await.unwrap();
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_matching_upcoming_prices_fire_once_per_interval() {
+        let received = Received::default();
+        let app = Router::new().route("/hook", post(receive)).with_state(received.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let store = Arc::new(InMemoryPriceStore::new());
+        let created = store
+            .create_alert_rule(&rule(AlertChannel::Webhook, &format!("http://{}/hook", addr)))
+            .await
+            .unwrap();
+        let hour = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let now = hour + chrono::Duration::minutes(20);
+        let service = AlertService::from_config(&config(), store.clone())
+            .unwrap()
+            .with_clock(Arc::new(FixedClock::new(now)));
+        let past = hour - chrono::Duration::hours(2);
+        let prices = vec![
+            Price::from_mwh(past, "NO1".to_string(), -5.0, "PT60M".to_string()),
+            Price::from_mwh(hour, "NO1".to_string(), 3.0, "PT60M".to_string()),
+            Price::from_mwh(hour + chrono::Duration::hours(1), "NO1".to_string(), 45.0, "PT60M".to_string()),
+            Price::from_mwh(hour + chrono::Duration::hours(2), "NO1".to_string(), -1.5, "PT60M".to_string()),
+            Price::from_mwh(hour, "SE3".to_string(), -8.0, "PT60M".to_string()),
+        ];
+
+        assert_eq!(service.evaluate(&prices).await.unwrap(), 2);
+        assert_eq!(service.evaluate(&prices).await.unwrap(), 0);
+
+        let (headers, body) = {
+            let received = received.lock().unwrap();
+            assert_eq!(received.len(), 1);
+            received[0].clone()
+        };
+        let timestamp: i64 = headers["x-webhook-timestamp"].to_str().unwrap().parse().unwrap();
+        assert_eq!(
+            headers["x-webhook-signature"].to_str().unwrap(),
+            format!("sha256={}", sign_payload("s3cret", timestamp, &body))
+        );
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["rule_id"], created.id);
+        assert_eq!(json["currency"], "EUR");
+        assert_eq!(json["prices"].as_array().unwrap().len(), 2);
+        assert_eq!(json["prices"][1]["price"], "-0.0015");
+
+        let history = store.get_alert_events(created.id, 10).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history.iter().all(|event| event.delivered == Some(true) && event.fired_at == now));
+    }
+}
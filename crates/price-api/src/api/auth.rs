@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header::AUTHORIZATION, HeaderMap},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use tower::{Layer, Service};
+use tracing::warn;
+
+use crate::config::AuthConfig;
+use crate::metrics;
+use crate::models::{ApiKey, ApiRole};
+
+use super::error::AppError;
+use super::jwt::JwtVerifier;
+
+/// The caller a request was authenticated as, passed to handlers as a
+/// request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub name: String,
+    pub role: ApiRole,
+}
+
+/// Why a request was turned away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFailure {
+    /// No key or token on a route that needs one.
+    Missing,
+    /// A key that is not registered, or a token that fails verification.
+    Invalid,
+    /// A valid key or token whose role is too low for the route.
+    Forbidden,
+}
+
+impl AuthFailure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthFailure::Missing => "missing",
+            AuthFailure::Invalid => "invalid",
+            AuthFailure::Forbidden => "forbidden",
+        }
+    }
+}
+
+/// Resolves API keys and, when configured, JWT bearer tokens to principals.
+/// Keys are held by their SHA-256, so config and database keys are looked up
+/// the same way.
+pub struct ApiKeyAuth {
+    key_header: String,
+    require_read_key: bool,
+    keys: HashMap<String, Principal>,
+    jwt: Option<Arc<JwtVerifier>>,
+}
+
+impl ApiKeyAuth {
+    /// Keys from `config` and `stored`; a stored key with the same hash as a
+    /// config key is ignored.
+    pub fn new(config: &AuthConfig, stored: Vec<ApiKey>) -> Self {
+        let mut keys = HashMap::new();
+        for key in stored {
+            keys.insert(key.key_sha256.to_lowercase(), Principal { name: key.name, role: key.role });
+        }
+        for key in &config.keys {
+            keys.insert(
+                hash_api_key(&key.key),
+                Principal {
+                    name: key.name.clone(),
+                    role: key.role,
+                },
+            );
+        }
+        Self {
+            key_header: config.key_header.clone(),
+            require_read_key: config.require_read_key,
+            keys,
+            jwt: None,
+        }
+    }
+
+    /// Also accept `Authorization: Bearer` tokens verified by `jwt`.
+    pub fn with_jwt(mut self, jwt: Arc<JwtVerifier>) -> Self {
+        self.jwt = Some(jwt);
+        self
+    }
+
+    pub fn key_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// The caller of a request to a route that needs `required`. `Ok(None)`
+    /// is an anonymous request to a public route that does not need a key.
+    /// A bearer token takes precedence over the API key header.
+    pub async fn authorize(&self, headers: &HeaderMap, required: ApiRole) -> Result<Option<Principal>, AuthFailure> {
+        if let (Some(jwt), Some(token)) = (&self.jwt, bearer_token(headers)) {
+            let principal = jwt.verify(token).await?;
+            if !principal.role.allows(required) {
+                return Err(AuthFailure::Forbidden);
+            }
+            return Ok(Some(principal));
+        }
+
+        let key = headers
+            .get(&self.key_header)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        let Some(key) = key else {
+            return if required == ApiRole::Read && !self.require_read_key {
+                Ok(None)
+            } else {
+                Err(AuthFailure::Missing)
+            };
+        };
+
+        let principal = self.keys.get(&hash_api_key(key)).ok_or(AuthFailure::Invalid)?;
+        if !principal.role.allows(required) {
+            return Err(AuthFailure::Forbidden);
+        }
+        Ok(Some(principal.clone()))
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+/// Hex-encoded SHA-256 of an API key, as stored in `api_keys.key_sha256`.
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// A new random API key: 64 hex characters from two v4 UUIDs.
+pub fn generate_api_key() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// Rejects requests whose API key or bearer token does not grant `required`:
+/// 401 without valid credentials, 403 when their role is too low.
+#[derive(Clone)]
+pub struct AuthLayer {
+    auth: Arc<ApiKeyAuth>,
+    required: ApiRole,
+}
+
+impl AuthLayer {
+    pub fn new(auth: Arc<ApiKeyAuth>, required: ApiRole) -> Self {
+        Self { auth, required }
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = AuthMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthMiddleware {
+            inner,
+            auth: self.auth.clone(),
+            required: self.required,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthMiddleware<S> {
+    inner: S,
+    auth: Arc<ApiKeyAuth>,
+    required: ApiRole,
+}
+
+impl<S> Service<Request<Body>> for AuthMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let auth = self.auth.clone();
+        let required = self.required;
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            match auth.authorize(req.headers(), required).await {
+                Ok(principal) => {
+                    if let Some(principal) = principal {
+                        req.extensions_mut().insert(principal);
+                    }
+                    inner.call(req).await
+                }
+                Err(failure) => {
+                    metrics::record_auth_failure(failure.as_str());
+                    warn!(path = %req.uri().path(), reason = failure.as_str(), "Rejected API request");
+                    let error = match failure {
+                        AuthFailure::Missing => AppError::Unauthorized(format!("Missing {} header", auth.key_header)),
+                        AuthFailure::Invalid => AppError::Unauthorized("Invalid API key or token".to_string()),
+                        AuthFailure::Forbidden => {
+                            AppError::Forbidden(format!("This route needs credentials with the {} role", required.as_str()))
+                        }
+                    };
+                    Ok(error.into_response())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{http::StatusCode, routing::get, Router};
+    use chrono::Utc;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::config::ApiKeyConfig;
+
+    fn auth(require_read_key: bool) -> Arc<ApiKeyAuth> {
+        let config = AuthConfig {
+            enabled: true,
+            require_read_key,
+            keys: vec![ApiKeyConfig {
+                name: "ops".to_string(),
+                key: "admin-secret".to_string(),
+                role: ApiRole::Admin,
+            }],
+            ..AuthConfig::default()
+        };
+        let stored = vec![ApiKey {
+            name: "dashboard".to_string(),
+            key_sha256: hash_api_key("read-secret"),
+            role: ApiRole::Read,
+            created_at: Utc::now(),
+        }];
+        Arc::new(ApiKeyAuth::new(&config, stored))
+    }
+
+    async fn status(app: &Router, key: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri("/route");
+        if let Some(key) = key {
+            request = request.header("X-API-Key", key);
+        }
+        app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_admin_routes_need_admin_key() {
+        let app = Router::new()
+            .route("/route", get(|| async { "ok" }))
+            .layer(AuthLayer::new(auth(false), ApiRole::Admin));
+
+        assert_eq!(status(&app, Some("admin-secret")).await, StatusCode::OK);
+        assert_eq!(status(&app, Some("read-secret")).await, StatusCode::FORBIDDEN);
+        assert_eq!(status(&app, Some("guess")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(&app, None).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_read_routes_allow_anonymous_unless_key_required() {
+        for (require_read_key, anonymous) in [(false, StatusCode::OK), (true, StatusCode::UNAUTHORIZED)] {
+            let app = Router::new()
+                .route("/route", get(|| async { "ok" }))
+                .layer(AuthLayer::new(auth(require_read_key), ApiRole::Read));
+
+            assert_eq!(status(&app, None).await, anonymous);
+            assert_eq!(status(&app, Some("read-secret")).await, StatusCode::OK);
+            assert_eq!(status(&app, Some("admin-secret")).await, StatusCode::OK);
+            assert_eq!(status(&app, Some("guess")).await, StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authorize_returns_principal() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", "read-secret".parse().unwrap());
+        let principal = auth(false).authorize(&headers, ApiRole::Read).await.unwrap().unwrap();
+        assert_eq!(principal, Principal { name: "dashboard".to_string(), role: ApiRole::Read });
+        assert_eq!(generate_api_key().len(), 64);
+    }
+}
@@ -0,0 +1,161 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    extract::{Query, Request},
+    http::{header, header::HeaderValue, Method},
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use chrono_tz::Europe::Oslo;
+use serde::Deserialize;
+use tower::{Layer, Service};
+
+use crate::clock::Clock;
+use crate::config::CacheControlConfig;
+use crate::models::DeliveryDay;
+use crate::scheduler::todays_primary_fetch;
+
+const NO_STORE: HeaderValue = HeaderValue::from_static("no-store");
+
+/// Bounds of the requested range, read independently of the handler so a
+/// bad value is left to it to reject.
+#[derive(Debug, Default, Deserialize)]
+struct RangeBounds {
+    end: Option<String>,
+}
+
+/// `Cache-Control` for a successful GET asking for prices up to `end`
+/// (`None` when the handler's default range, reaching into tomorrow, applies).
+pub(super) fn cache_control(config: &CacheControlConfig, private: bool, end: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    let today = DeliveryDay::new(now.with_timezone(&Oslo).date_naive(), &Oslo);
+    let published = todays_primary_fetch(now);
+    let max_age = match end {
+        Some(end) if end <= today.start => config.historical_max_age_seconds,
+        end if now < published && end.is_none_or(|end| end > today.end) => {
+            config.pending_max_age_seconds.min((published - now).num_seconds() as u64)
+        }
+        _ => config.max_age_seconds,
+    };
+    let scope = if private { "private" } else { "public" };
+    format!("{}, max-age={}", scope, max_age)
+}
+
+struct CachePolicy {
+    config: CacheControlConfig,
+    clock: Arc<dyn Clock>,
+    private: bool,
+}
+
+/// Adds `Cache-Control` to responses that do not set their own: a max-age
+/// from [`CacheControlConfig`] for successful GETs, `no-store` for anything
+/// else, or `no-store` for everything on [`no_store`](Self::no_store) routes.
+#[derive(Clone)]
+pub struct CacheControlLayer {
+    policy: Option<Arc<CachePolicy>>,
+}
+
+impl CacheControlLayer {
+    /// Cache data responses; `private` keeps them out of shared caches when
+    /// callers authenticate.
+    pub fn new(config: CacheControlConfig, clock: Arc<dyn Clock>, private: bool) -> Self {
+        Self {
+            policy: Some(Arc::new(CachePolicy { config, clock, private })),
+        }
+    }
+
+    pub fn no_store() -> Self {
+        Self { policy: None }
+    }
+}
+
+impl<S> Layer<S> for CacheControlLayer {
+    type Service = CacheControlMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CacheControlMiddleware {
+            inner,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CacheControlMiddleware<S> {
+    inner: S,
+    policy: Option<Arc<CachePolicy>>,
+}
+
+impl<S> Service<Request<Body>> for CacheControlMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // Decided before the handler runs, against the time the request arrived
+        let cacheable = self.policy.as_ref().filter(|_| req.method() == Method::GET).map(|policy| {
+            let end = Query::<RangeBounds>::try_from_uri(req.uri())
+                .map(|Query(bounds)| bounds)
+                .unwrap_or_default()
+                .end
+                .and_then(|end| DateTime::parse_from_rfc3339(&end).ok())
+                .map(|end| end.with_timezone(&Utc));
+            cache_control(&policy.config, policy.private, end, policy.clock.now())
+        });
+
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            let mut response = inner.call(req).await?;
+            if !response.headers().contains_key(header::CACHE_CONTROL) {
+                let value = cacheable
+                    .filter(|_| response.status().is_success())
+                    .and_then(|value| HeaderValue::from_str(&value).ok())
+                    .unwrap_or(NO_STORE);
+                response.headers_mut().insert(header::CACHE_CONTROL, value);
+            }
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_max_age_follows_the_requested_range() {
+        let config = CacheControlConfig::default();
+        // 10:00 CET, before tomorrow's prices are fetched at 13:00 CET
+        let morning = at(15, 9);
+
+        assert_eq!(cache_control(&config, false, Some(at(14, 23)), morning), "public, max-age=86400");
+        assert_eq!(cache_control(&config, false, Some(at(15, 12)), morning), "public, max-age=300");
+        assert_eq!(cache_control(&config, false, Some(at(16, 12)), morning), "public, max-age=60");
+        assert_eq!(cache_control(&config, true, None, morning), "private, max-age=60");
+
+        // A minute before the fetch, the pending max-age does not outlive it
+        assert_eq!(cache_control(&config, false, None, at(15, 11) + chrono::Duration::minutes(59)), "public, max-age=60");
+        assert_eq!(
+            cache_control(&config, false, None, at(15, 11) + chrono::Duration::seconds(3570)),
+            "public, max-age=30"
+        );
+        assert_eq!(cache_control(&config, false, Some(at(16, 12)), at(15, 13)), "public, max-age=300");
+    }
+}
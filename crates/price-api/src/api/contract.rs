@@ -0,0 +1,671 @@
+//! API contract tests.
+//!
+//! `tests/contract/<API_VERSION>/schemas/` holds the JSON schema of every
+//! response body and `responses/` canonical example bodies. The suite fails
+//! when a generated schema differs from its snapshot:
+//!
+//! - breaking changes (removed fields, changed or widened types, fields that
+//!   are no longer always present) always fail; they need a new `API_VERSION`
+//!   with its own snapshot directory;
+//! - additive changes fail until the snapshot is re-recorded with
+//!   `UPDATE_CONTRACTS=1 cargo test contract`.
+//!
+//! Canonical examples and live responses from the router must also validate
+//! against the generated schemas.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use chrono::{TimeZone, Utc};
+use schemars::{schema_for, Schema};
+use serde_json::{Map, Value};
+use tower::ServiceExt;
+
+use super::dto::{
+    AlertHistoryResponse, AlertRuleInfo, AlertRulesResponse, BackfillJobInfo, BackfillResponse, GapReportResponse, CacheInvalidateResponse, ComparePricesResponse, CountriesResponse, CountryPricesResponse, EicFetchResponse, FetchResponse, FlowsResponse, GenerationResponse, HealthResponse, LimitsResponse, LoadResponse, LatestPricesResponse,
+    ErrorCatalogResponse, FxRateResponse, HomeAssistantResponse, MetricCatalogResponse, NegativePricesResponse, PriceAtResponse, FetchLogResponse, PruneFetchLogResponse, ReadyResponse, SchedulerJobInfo, SchedulerJobsResponse, StatusResponse, StorageStatsResponse, TasksResponse, ZoneFetchResponse,
+    ZoneInvoiceResponse, ZonePricesResponse, ZoneStatsResponse, ZonesResponse,
+};
+use super::error::ErrorResponse;
+use super::fields::{PointShape, METADATA};
+use super::routes::{create_scoped_router, AppState};
+use super::API_VERSION;
+use crate::cache::PriceCache;
+use crate::config::{CacheConfig, FxSourceKind, ListenerScope};
+use crate::entsoe::CircuitBreakerStatus;
+use crate::fx::{FxError, FxRate, FxRateService, PublicationCalendar, RateSource};
+use crate::models::{
+    AlertChannel, AlertComparison, AlertEvent, CrossBorderFlow, FetchStatus, FlowKind, GenerationPoint, LoadValue, NameKind,
+    NameTranslation, NewAlertRule, NewTask, Price,
+};
+use crate::scheduler::JobHistory;
+use crate::storage::{InMemoryPriceStore, PriceStore};
+
+struct Contract {
+    name: &'static str,
+    schema: fn() -> Schema,
+}
+
+macro_rules! contract {
+    ($name:literal, $ty:ty) => {
+        Contract {
+            name: $name,
+            schema: || schema_for!($ty),
+        }
+    };
+    ($name:literal, $ty:ty, fields: $shape:expr) => {
+        Contract {
+            name: $name,
+            schema: || sparse(schema_for!($ty), $shape),
+        }
+    };
+}
+
+/// Schema of a response narrowed with `?fields=`, in which the selectable
+/// point fields of `shape` and the metadata are no longer always present.
+fn sparse(schema: Schema, shape: PointShape) -> Schema {
+    fn ref_name(reference: Option<&Value>) -> Option<String> {
+        Some(reference?.as_str()?.trim_start_matches("#/$defs/").to_string())
+    }
+    fn drop_required(schema: &mut Value, fields: &[&str]) {
+        if let Some(Value::Array(required)) = schema.get_mut("required") {
+            required.retain(|field| !fields.contains(&field.as_str().unwrap_or_default()));
+        }
+    }
+
+    let mut schema = serde_json::to_value(schema).unwrap();
+    let mut objects = vec![None];
+    objects.extend(schema.get("$defs").and_then(Value::as_object).into_iter().flat_map(|defs| defs.keys().cloned().map(Some)));
+    let points: Vec<Option<String>> = match shape.points {
+        None => vec![None],
+        Some(key) => objects
+            .iter()
+            .filter_map(|name| {
+                let object = match name {
+                    Some(name) => &schema["$defs"][name],
+                    None => &schema,
+                };
+                ref_name(object.pointer(&format!("/properties/{}/items/$ref", key))).map(Some)
+            })
+            .collect(),
+    };
+    for name in objects {
+        let object = match &name {
+            Some(name) => &mut schema["$defs"][name],
+            None => &mut schema,
+        };
+        drop_required(object, METADATA);
+        if points.contains(&name) {
+            drop_required(object, shape.selectable);
+        }
+    }
+    serde_json::from_value(schema).unwrap()
+}
+
+const CONTRACTS: &[Contract] = &[
+    contract!("health", HealthResponse),
+    contract!("ready", ReadyResponse),
+    contract!("status", StatusResponse),
+    contract!("limits", LimitsResponse),
+    contract!("zones", ZonesResponse),
+    contract!("countries", CountriesResponse),
+    contract!("fx_rate", FxRateResponse),
+    contract!("zone_prices", ZonePricesResponse),
+    contract!("zone_prices_fields", ZonePricesResponse, fields: PointShape::ZONE),
+    contract!("zone_stats", ZoneStatsResponse),
+    contract!("negative_prices", NegativePricesResponse),
+    contract!("home_assistant", HomeAssistantResponse),
+    contract!("price_at", PriceAtResponse),
+    contract!("price_at_fields", PriceAtResponse, fields: PointShape::PRICE_AT),
+    contract!("country_prices", CountryPricesResponse),
+    contract!("country_prices_fields", CountryPricesResponse, fields: PointShape::COUNTRY),
+    contract!("latest_prices", LatestPricesResponse),
+    contract!("latest_prices_fields", LatestPricesResponse, fields: PointShape::LATEST),
+    contract!("compare_prices", ComparePricesResponse),
+    contract!("compare_prices_fields", ComparePricesResponse, fields: PointShape::COMPARE),
+    contract!("zone_invoice", ZoneInvoiceResponse),
+    contract!("generation", GenerationResponse),
+    contract!("load", LoadResponse),
+    contract!("flows", FlowsResponse),
+    contract!("metrics_catalog", MetricCatalogResponse),
+    contract!("fetch", FetchResponse),
+    contract!("zone_fetch", ZoneFetchResponse),
+    contract!("eic_fetch", EicFetchResponse),
+    contract!("backfill", BackfillResponse),
+    contract!("backfill_job", BackfillJobInfo),
+    contract!("gap_report", GapReportResponse),
+    contract!("tasks", TasksResponse),
+    contract!("scheduler", SchedulerJobsResponse),
+    contract!("scheduler_job", SchedulerJobInfo),
+    contract!("circuit_breaker", CircuitBreakerStatus),
+    contract!("storage_stats", StorageStatsResponse),
+    contract!("fetch_log", FetchLogResponse),
+    contract!("prune_fetch_log", PruneFetchLogResponse),
+    contract!("cache_invalidate", CacheInvalidateResponse),
+    contract!("alert_rule", AlertRuleInfo),
+    contract!("alert_rules", AlertRulesResponse),
+    contract!("alert_history", AlertHistoryResponse),
+    contract!("error", ErrorResponse),
+    contract!("error_catalog", ErrorCatalogResponse),
+];
+
+/// Publishes 11.5 NOK per EUR on Norwegian banking days, so the router can serve rates
+/// without calling Norges Bank.
+struct WeekdayNokRates;
+
+#[async_trait::async_trait]
+impl RateSource for WeekdayNokRates {
+    fn kind(&self) -> FxSourceKind {
+        FxSourceKind::NorgesBank
+    }
+
+    fn calendar(&self) -> PublicationCalendar {
+        PublicationCalendar::Norway
+    }
+
+    fn supports(&self, currency: &str) -> bool {
+        currency == "NOK"
+    }
+
+    async fn fetch_rate(&self, currency: &str, date: chrono::NaiveDate) -> Result<FxRate, FxError> {
+        if !self.supports(currency) {
+            return Err(FxError::UnsupportedCurrency {
+                currency: currency.to_string(),
+                source_name: "NorgesBank".to_string(),
+            });
+        }
+        Ok(FxRate {
+            currency: currency.to_string(),
+            rate: "11.5".parse().unwrap(),
+            rate_date: self.calendar().latest_publication_on_or_before(date),
+            source: self.kind(),
+        })
+    }
+}
+
+fn contract_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/contract")
+        .join(API_VERSION)
+}
+
+fn read_json(path: &PathBuf) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    Some(serde_json::from_str(&content).unwrap_or_else(|e| panic!("{}: invalid JSON: {}", path.display(), e)))
+}
+
+fn generated_schema(name: &str) -> Value {
+    let contract = CONTRACTS.iter().find(|c| c.name == name).unwrap();
+    serde_json::to_value((contract.schema)()).unwrap()
+}
+
+fn validate(name: &str, instance: &Value) -> Result<(), String> {
+    let schema = generated_schema(name);
+    let validator = jsonschema::validator_for(&schema).map_err(|e| e.to_string())?;
+    let errors: Vec<String> = validator
+        .iter_errors(instance)
+        .map(|e| format!("{} at {}", e, e.instance_path))
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Replace `$ref`s into `$defs` with the referenced schema so old and new
+/// schemas can be compared independently of type names.
+fn inline_refs(value: &Value, defs: &Map<String, Value>) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                let name = reference.trim_start_matches("#/$defs/");
+                let target = defs.get(name).unwrap_or_else(|| panic!("unresolved $ref {}", reference));
+                return inline_refs(target, defs);
+            }
+            Value::Object(
+                map.iter()
+                    .filter(|(key, _)| key.as_str() != "$defs")
+                    .map(|(key, value)| (key.clone(), inline_refs(value, defs)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| inline_refs(v, defs)).collect()),
+        other => other.clone(),
+    }
+}
+
+fn inline(schema: &Value) -> Value {
+    let defs = schema.get("$defs").and_then(Value::as_object).cloned().unwrap_or_default();
+    inline_refs(schema, &defs)
+}
+
+fn string_set(value: Option<&Value>) -> Vec<Value> {
+    match value {
+        Some(Value::Array(items)) => items.clone(),
+        Some(other) => vec![other.clone()],
+        None => Vec::new(),
+    }
+}
+
+fn variants(schema: &Value) -> Vec<Value> {
+    ["anyOf", "oneOf"]
+        .iter()
+        .find_map(|keyword| schema.get(*keyword).and_then(Value::as_array).cloned())
+        .unwrap_or_else(|| vec![schema.clone()])
+}
+
+/// Changes from `old` to `new` that can break a client of the old schema.
+fn breaking_changes(old: &Value, new: &Value, path: &str, out: &mut Vec<String>) {
+    if old == new {
+        return;
+    }
+
+    // Each new variant must be compatible with an old one, preferring old
+    // variants of the same type; a plain schema is a single variant
+    let (old_variants, new_variants) = (variants(old), variants(new));
+    if old_variants.len() > 1 || new_variants.len() > 1 {
+        for new_variant in new_variants {
+            let same_type: Vec<&Value> = old_variants
+                .iter()
+                .filter(|old_variant| old_variant.get("type") == new_variant.get("type"))
+                .collect();
+            let candidates = if same_type.is_empty() { old_variants.iter().collect() } else { same_type };
+            let closest = candidates
+                .into_iter()
+                .map(|old_variant| {
+                    let mut changes = Vec::new();
+                    breaking_changes(old_variant, &new_variant, path, &mut changes);
+                    changes
+                })
+                .min_by_key(Vec::len)
+                .unwrap_or_default();
+            out.extend(closest);
+        }
+        return;
+    }
+
+    // A changed type makes the rest of the comparison meaningless
+    for keyword in ["type", "enum"] {
+        let (old_values, new_values) = (string_set(old.get(keyword)), string_set(new.get(keyword)));
+        if !old_values.is_empty() && new_values.iter().any(|v| !old_values.contains(v)) {
+            out.push(format!(
+                "{}: {} widened from {} to {}",
+                path,
+                keyword,
+                Value::Array(old_values),
+                Value::Array(new_values)
+            ));
+            return;
+        }
+    }
+    if old.get("const").is_some() && old.get("const") != new.get("const") {
+        out.push(format!("{}: const changed", path));
+    }
+
+    if let Some(old_props) = old.get("properties").and_then(Value::as_object) {
+        let new_props = new.get("properties").and_then(Value::as_object);
+        for (key, old_prop) in old_props {
+            let field = format!("{}.{}", path, key);
+            match new_props.and_then(|p| p.get(key)) {
+                Some(new_prop) => breaking_changes(old_prop, new_prop, &field, out),
+                None => out.push(format!("{}: removed", field)),
+            }
+        }
+        let new_required = string_set(new.get("required"));
+        for key in string_set(old.get("required")) {
+            if !new_required.contains(&key) {
+                out.push(format!("{}.{}: no longer always present", path, key.as_str().unwrap_or_default()));
+            }
+        }
+    }
+
+    for keyword in ["items", "additionalProperties"] {
+        if let (Some(old_inner), Some(new_inner)) = (old.get(keyword), new.get(keyword)) {
+            breaking_changes(old_inner, new_inner, &format!("{}[]", path), out);
+        }
+    }
+}
+
+#[test]
+fn test_schemas_match_contract_snapshots() {
+    let update = std::env::var_os("UPDATE_CONTRACTS").is_some();
+    let mut failures = Vec::new();
+
+    for contract in CONTRACTS {
+        let path = contract_dir().join("schemas").join(format!("{}.json", contract.name));
+        let generated = generated_schema(contract.name);
+
+        match read_json(&path) {
+            Some(snapshot) if snapshot == generated => {}
+            Some(snapshot) => {
+                let mut breaking = Vec::new();
+                breaking_changes(&inline(&snapshot), &inline(&generated), contract.name, &mut breaking);
+                if !breaking.is_empty() {
+                    failures.push(format!(
+                        "{}: breaking change for API {}; bump API_VERSION instead: {}",
+                        contract.name,
+                        API_VERSION,
+                        breaking.join(", ")
+                    ));
+                } else if update {
+                    fs::write(&path, serde_json::to_string_pretty(&generated).unwrap() + "\n").unwrap();
+                } else {
+                    failures.push(format!(
+                        "{}: additive schema change; record it with UPDATE_CONTRACTS=1",
+                        contract.name
+                    ));
+                }
+            }
+            None if update => {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, serde_json::to_string_pretty(&generated).unwrap() + "\n").unwrap();
+            }
+            None => failures.push(format!("{}: missing snapshot {}", contract.name, path.display())),
+        }
+    }
+
+    assert!(failures.is_empty(), "API contract violations:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_canonical_responses_match_schemas() {
+    for contract in CONTRACTS {
+        let path = contract_dir().join("responses").join(format!("{}.json", contract.name));
+        let response = read_json(&path).unwrap_or_else(|| panic!("missing canonical response {}", path.display()));
+        if let Err(e) = validate(contract.name, &response) {
+            panic!("{} no longer matches its schema: {}", path.display(), e);
+        }
+    }
+}
+
+#[test]
+fn test_breaking_changes_are_detected() {
+    let old = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "zone": {"type": "string"},
+            "price": {"type": "string"}
+        },
+        "required": ["zone", "price"]
+    });
+    let additive = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "zone": {"type": "string"},
+            "price": {"type": "string"},
+            "unit": {"type": "string"}
+        },
+        "required": ["zone", "price", "unit"]
+    });
+    let breaking = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "zone": {"type": ["string", "null"]}
+        },
+        "required": []
+    });
+
+    let mut changes = Vec::new();
+    breaking_changes(&old, &additive, "r", &mut changes);
+    assert!(changes.is_empty());
+
+    breaking_changes(&old, &breaking, "r", &mut changes);
+    assert_eq!(changes.len(), 4, "{:?}", changes);
+}
+
+async fn contract_router() -> axum::Router {
+    let store = InMemoryPriceStore::with_zones(vec![
+        InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+        InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+    ]);
+    let prices: Vec<Price> = (0..3)
+        .map(|hour| {
+            Price::from_mwh(
+                Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap(),
+                "NO1".to_string(),
+                50.0 + hour as f64,
+                "PT60M".to_string(),
+            )
+        })
+        .collect();
+    store.upsert_prices(&prices).await.unwrap();
+    let generation: Vec<GenerationPoint> = ["B12", "B19"]
+        .into_iter()
+        .map(|psr_type| GenerationPoint {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap(),
+            bidding_zone: "NO1".to_string(),
+            psr_type: psr_type.to_string(),
+            quantity_mw: 1200.into(),
+            resolution: "PT60M".to_string(),
+            fetched_at: Utc::now(),
+        })
+        .collect();
+    store.upsert_generation(&generation).await.unwrap();
+    store
+        .upsert_load(&[LoadValue {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap(),
+            bidding_zone: "NO1".to_string(),
+            load_mw: 4100.into(),
+            resolution: "PT60M".to_string(),
+            fetched_at: Utc::now(),
+        }])
+        .await
+        .unwrap();
+    let flows: Vec<CrossBorderFlow> = [("NO1", "NO2", 300), ("NO2", "NO1", 0)]
+        .into_iter()
+        .map(|(from, to, mw)| CrossBorderFlow {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap(),
+            from_zone: from.to_string(),
+            to_zone: to.to_string(),
+            kind: FlowKind::Physical,
+            quantity_mw: mw.into(),
+            resolution: "PT60M".to_string(),
+            fetched_at: Utc::now(),
+        })
+        .collect();
+    store.upsert_flows(&flows).await.unwrap();
+    store
+        .upsert_name_translations(&[
+            NameTranslation {
+                kind: NameKind::Zone,
+                code: "NO1".to_string(),
+                locale: "nb".to_string(),
+                name: "Østlandet".to_string(),
+            },
+            NameTranslation {
+                kind: NameKind::Country,
+                code: "NO".to_string(),
+                locale: "nb".to_string(),
+                name: "Norge".to_string(),
+            },
+        ])
+        .await
+        .unwrap();
+    let period_start = Utc.with_ymd_and_hms(2025, 1, 14, 23, 0, 0).unwrap();
+    for (status, records, error, http_status) in [
+        (FetchStatus::Success, 24, None, 200),
+        (FetchStatus::Error, 0, Some("ENTSOE returned 503"), 503),
+    ] {
+        let id = store
+            .log_fetch_start(Some("NO1".to_string()), period_start, period_start + chrono::Duration::days(1), None)
+            .await
+            .unwrap();
+        store
+            .log_fetch_complete(id, status, records, error.map(str::to_string), Some(http_status), 850)
+            .await
+            .unwrap();
+    }
+    let rule = store
+        .create_alert_rule(&NewAlertRule {
+            zone_code: "NO1".to_string(),
+            comparison: AlertComparison::Above,
+            threshold: "0.0515".parse().unwrap(),
+            channel: AlertChannel::Webhook,
+            target: "https://example.com/hooks/prices".to_string(),
+        })
+        .await
+        .unwrap();
+    let fired_for = Utc.with_ymd_and_hms(2025, 1, 15, 2, 0, 0).unwrap();
+    store
+        .insert_alert_events(&[AlertEvent::new(rule.id, fired_for, "0.052".parse().unwrap())])
+        .await
+        .unwrap();
+    store
+        .complete_alert_event(rule.id, fired_for, false, Some("Receiver responded with 503".to_string()))
+        .await
+        .unwrap();
+
+    let queued_at = Utc.with_ymd_and_hms(2025, 1, 15, 2, 30, 0).unwrap();
+    store
+        .enqueue_task(
+            &NewTask {
+                kind: "retention".to_string(),
+                payload: r#"{"now":"2025-01-15T02:30:00Z"}"#.to_string(),
+                dedupe_key: Some("retention:2025-01-15".to_string()),
+                max_attempts: 5,
+                run_after: queued_at,
+            },
+            queued_at,
+        )
+        .await
+        .unwrap();
+    let task = store.claim_task(queued_at, queued_at + chrono::Duration::minutes(5)).await.unwrap().unwrap();
+    store
+        .retry_task(task.id, task.attempts, "Connection reset", queued_at + chrono::Duration::minutes(1), queued_at)
+        .await
+        .unwrap();
+
+    let store = Arc::new(store);
+    let cache = PriceCache::new(&CacheConfig {
+        enabled: true,
+        ttl_seconds: 60,
+    });
+    cache.warm(store.as_ref()).await.unwrap();
+
+    let history = Arc::new(JobHistory::default());
+    history.register("primary_fetch_13:00", "0 0 13 * * *");
+    history.register("retention_cleanup", "0 30 3 * * *");
+    history.record_success(
+        "primary_fetch_13:00",
+        Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap(),
+        std::time::Duration::from_millis(1200),
+        None,
+    );
+    history.set_running(true);
+
+    let state = AppState {
+        fx: Some(Arc::new(FxRateService::new(Arc::new(WeekdayNokRates), Default::default()))),
+        cache: Some(Arc::new(cache)),
+        ..AppState::for_tests(store)
+    }
+    .with_scheduler(history);
+    create_scoped_router(state, ListenerScope::All)
+}
+
+#[tokio::test]
+async fn test_live_responses_match_schemas() {
+    let router = contract_router().await;
+    let range = "start=2025-01-15T00:00:00Z&end=2025-01-16T00:00:00Z";
+    let cases = [
+        ("health", "GET", "/health".to_string(), StatusCode::OK),
+        ("ready", "GET", "/ready".to_string(), StatusCode::OK),
+        ("status", "GET", "/api/v1/status".to_string(), StatusCode::OK),
+        ("limits", "GET", "/api/v1/limits".to_string(), StatusCode::OK),
+        ("zones", "GET", "/api/v1/zones".to_string(), StatusCode::OK),
+        ("zones", "GET", "/api/v1/zones?lang=nb".to_string(), StatusCode::OK),
+        ("countries", "GET", "/api/v1/countries".to_string(), StatusCode::OK),
+        ("countries", "GET", "/api/v1/countries?lang=nb".to_string(), StatusCode::OK),
+        ("fx_rate", "GET", "/api/v1/fx/nok?date=2025-01-18".to_string(), StatusCode::OK),
+        ("fx_rate", "GET", "/api/v1/fx/EUR".to_string(), StatusCode::OK),
+        ("error", "GET", "/api/v1/fx/SEK".to_string(), StatusCode::BAD_REQUEST),
+        ("error", "GET", "/api/v1/fx/NOK?date=18.01.2025".to_string(), StatusCode::BAD_REQUEST),
+        ("zone_prices", "GET", format!("/api/v1/prices/zone/NO1?{}", range), StatusCode::OK),
+        ("zone_prices", "GET", format!("/api/v1/prices/zone/NO1?{}&clamp=true", range), StatusCode::OK),
+        ("zone_stats", "GET", format!("/api/v1/prices/zone/NO1/stats?{}", range), StatusCode::OK),
+        ("negative_prices", "GET", format!("/api/v1/prices/zone/NO1/negative?{}&threshold=0.0515", range), StatusCode::OK),
+        ("negative_prices", "GET", format!("/api/v1/prices/zone/NO1/negative?{}", range), StatusCode::OK),
+        ("home_assistant", "GET", "/api/v1/prices/zone/NO1/home-assistant".to_string(), StatusCode::OK),
+        ("home_assistant", "GET", "/api/v1/prices/zone/NO1/home-assistant?unit=mwh".to_string(), StatusCode::OK),
+        ("price_at", "GET", "/api/v1/prices/zone/NO1/at?timestamp=2025-01-15T01:30:00Z".to_string(), StatusCode::OK),
+        ("price_at", "GET", "/api/v1/prices/zone/NO1/at?timestamp=2025-01-15T02:30:00%2B01:00&unit=mwh".to_string(), StatusCode::OK),
+        ("error", "GET", "/api/v1/prices/zone/NO1/at?timestamp=2025-01-15T03:00:00Z".to_string(), StatusCode::NOT_FOUND),
+        ("error", "GET", "/api/v1/prices/zone/NO1/at".to_string(), StatusCode::BAD_REQUEST),
+        ("zone_prices", "GET", format!("/api/v1/prices/zone/NO1?{}&lang=nb", range), StatusCode::OK),
+        ("country_prices", "GET", format!("/api/v1/prices/country/NO?{}", range), StatusCode::OK),
+        ("country_prices", "GET", format!("/api/v1/prices/country/NO?{}&lang=nb", range), StatusCode::OK),
+        ("country_prices", "GET", format!("/api/v1/prices/country/NO?{}&aggregate=average", range), StatusCode::OK),
+        ("error", "GET", format!("/api/v1/prices/country/NO?{}&aggregate=average&clamp=true", range), StatusCode::BAD_REQUEST),
+        ("country_prices", "GET", format!("/api/v1/prices/country/NO?{}&include=stats", range), StatusCode::OK),
+        ("country_prices", "GET", format!("/api/v1/prices/country/NO?{}&include=stats,zones&unit=mwh", range), StatusCode::OK),
+        ("error", "GET", format!("/api/v1/prices/zone/NO1?{}&fields=timestamp,zone_code", range), StatusCode::BAD_REQUEST),
+        ("zone_prices_fields", "GET", format!("/api/v1/prices/zone/NO1?{}&fields=timestamp,price", range), StatusCode::OK),
+        ("country_prices_fields", "GET", format!("/api/v1/prices/country/NO?{}&fields=price", range), StatusCode::OK),
+        ("country_prices_fields", "GET", format!("/api/v1/prices/country/NO?{}&aggregate=average&fields=timestamp,zones", range), StatusCode::OK),
+        ("error", "GET", format!("/api/v1/prices/country/NO?{}&aggregate=average&include=zones", range), StatusCode::BAD_REQUEST),
+        ("latest_prices_fields", "GET", "/api/v1/prices/latest?fields=price".to_string(), StatusCode::OK),
+        ("compare_prices_fields", "GET", format!("/api/v1/prices/compare?zones=NO1,NO2&{}&fields=timestamp,prices", range), StatusCode::OK),
+        ("price_at_fields", "GET", "/api/v1/prices/zone/NO1/at?timestamp=2025-01-15T01:30:00Z&fields=price".to_string(), StatusCode::OK),
+        ("error", "GET", "/api/v1/prices/zone/NO1/at?timestamp=2025-01-15T01:30:00Z&fields=zone_code".to_string(), StatusCode::BAD_REQUEST),
+        ("compare_prices", "GET", format!("/api/v1/prices/compare?zones=NO1,NO2&{}", range), StatusCode::OK),
+        ("error", "GET", "/api/v1/prices/compare?zones=NO1".to_string(), StatusCode::BAD_REQUEST),
+        ("zone_invoice", "GET", "/api/v1/prices/zone/NO1/invoice?month=2025-01".to_string(), StatusCode::OK),
+        ("error", "GET", "/api/v1/prices/zone/NO1/invoice?month=2025-13".to_string(), StatusCode::BAD_REQUEST),
+        ("latest_prices", "GET", "/api/v1/prices/latest".to_string(), StatusCode::OK),
+        ("latest_prices", "GET", "/api/v1/prices/latest?lang=nb".to_string(), StatusCode::OK),
+        ("latest_prices", "GET", "/api/v1/prices/latest?unit=mwh".to_string(), StatusCode::OK),
+        ("zone_prices", "GET", format!("/api/v1/prices/zone/NO1?{}&unit=mwh", range), StatusCode::OK),
+        ("error", "GET", format!("/api/v1/prices/zone/NO1?{}&unit=gwh", range), StatusCode::BAD_REQUEST),
+        ("generation", "GET", format!("/api/v1/generation/zone/NO1?{}", range), StatusCode::OK),
+        ("load", "GET", format!("/api/v1/load/zone/NO1?{}", range), StatusCode::OK),
+        ("flows", "GET", format!("/api/v1/flows/zone/NO1?{}&kind=physical", range), StatusCode::OK),
+        ("metrics_catalog", "GET", "/metrics/catalog".to_string(), StatusCode::OK),
+        ("storage_stats", "GET", "/api/v1/admin/storage/stats".to_string(), StatusCode::OK),
+        ("fetch_log", "GET", "/api/v1/admin/storage/fetch-log".to_string(), StatusCode::OK),
+        ("fetch_log", "GET", "/api/v1/admin/storage/fetch-log?zone=no1&status=error&limit=1".to_string(), StatusCode::OK),
+        ("error", "GET", "/api/v1/admin/storage/fetch-log?offset=1&cursor=0.1".to_string(), StatusCode::BAD_REQUEST),
+        ("gap_report", "GET", "/api/v1/admin/gaps?start=2025-01-15&end=2025-01-16".to_string(), StatusCode::OK),
+        ("gap_report", "GET", "/api/v1/admin/gaps?start=2025-01-15&end=2025-01-15&zones=no1".to_string(), StatusCode::OK),
+        ("error", "GET", "/api/v1/admin/gaps?start=2025-01-16&end=2025-01-15".to_string(), StatusCode::BAD_REQUEST),
+        ("error", "GET", "/api/v1/admin/gaps?start=2025-01-15&end=2025-01-15&zones=XX1".to_string(), StatusCode::NOT_FOUND),
+        ("tasks", "GET", "/api/v1/admin/tasks".to_string(), StatusCode::OK),
+        ("tasks", "GET", "/api/v1/admin/tasks?status=failed&limit=10".to_string(), StatusCode::OK),
+        ("error", "GET", "/api/v1/admin/tasks?status=done".to_string(), StatusCode::BAD_REQUEST),
+        ("scheduler", "GET", "/api/v1/admin/scheduler".to_string(), StatusCode::OK),
+        ("scheduler_job", "POST", "/api/v1/admin/scheduler/jobs/retention_cleanup/pause".to_string(), StatusCode::OK),
+        ("scheduler_job", "POST", "/api/v1/admin/scheduler/jobs/retention_cleanup/resume".to_string(), StatusCode::OK),
+        ("error", "POST", "/api/v1/admin/scheduler/jobs/unknown/pause".to_string(), StatusCode::NOT_FOUND),
+        ("cache_invalidate", "POST", "/api/v1/admin/cache/invalidate?zone=no1".to_string(), StatusCode::OK),
+        ("error", "POST", "/api/v1/admin/cache/invalidate?date=tomorrow".to_string(), StatusCode::BAD_REQUEST),
+        ("alert_rules", "GET", "/api/v1/admin/alerts".to_string(), StatusCode::OK),
+        ("alert_history", "GET", "/api/v1/admin/alerts/1/history".to_string(), StatusCode::OK),
+        ("error", "GET", "/api/v1/admin/alerts/99/history".to_string(), StatusCode::NOT_FOUND),
+        ("error", "GET", "/api/v1/prices/zone/XX1".to_string(), StatusCode::NOT_FOUND),
+        ("error", "GET", "/api/v1/zones?lang=fr".to_string(), StatusCode::BAD_REQUEST),
+        ("error", "GET", "/api/v1/prices/zone/NO1/negative?threshold=low".to_string(), StatusCode::BAD_REQUEST),
+        ("error_catalog", "GET", "/api/v1/errors".to_string(), StatusCode::OK),
+    ];
+
+    for (name, method, uri, expected_status) in cases {
+        let request = Request::builder().method(method).uri(&uri).body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), expected_status, "{}", uri);
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+        // Seed missing canonical examples from live responses
+        let canonical = contract_dir().join("responses").join(format!("{}.json", name));
+        if std::env::var_os("UPDATE_CONTRACTS").is_some() && !canonical.exists() {
+            fs::create_dir_all(canonical.parent().unwrap()).unwrap();
+            fs::write(&canonical, serde_json::to_string_pretty(&body).unwrap() + "\n").unwrap();
+        }
+
+        if let Err(e) = validate(name, &body) {
+            panic!("{} response does not match the {} schema: {}", uri, name, e);
+        }
+    }
+}
@@ -0,0 +1,3860 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Datelike, Duration, DurationRound, NaiveDate, Timelike, Utc};
+use chrono_tz::Tz;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    psr_type_name, AlertChannel, AlertComparison, AlertEvent, AlertRule, BackfillJob, BackfillJobStatus, BiddingZone, Consumption, CrossBorderFlow, DeliveryDay, EnergyUnit, FetchLog, FetchStatus, FlowKind, GenerationPoint, Invoice, LoadValue, Market,
+    NameKind, NameTranslation, Price, PriceResolution, Tariff, Task, TaskStatus, SUPPORTED_LOCALES,
+};
+use crate::cache::{CacheInvalidation, CacheStats};
+use crate::config::{CountryAverageConfig, DefaultRangeConfig, FxSourceKind, ResolutionConfig, RetentionConfig, TariffConfig};
+use crate::entsoe::{parse_resolution, CircuitBreakerStatus, CircuitState, EntsoeError};
+use crate::fx::FxRate;
+use crate::metrics::MetricDescription;
+use crate::scheduler::{JobHistory, JobRun, JobSchedule, JobStatus};
+use crate::storage::{DayGap, FetchLogFilter, FetchLogPage, PoolStatus, PriceCoverage, StorageError, TableStats, ZoneStats};
+
+use super::error::{AppError, ErrorCode};
+use super::fields::{PointShape, PriceFields};
+use super::rate_limit::RateLimitStatus;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PricePoint {
+    pub timestamp: String,
+    pub timestamp_utc: DateTime<Utc>,
+    pub price: Decimal,
+    /// Filled in for a position ENTSOE did not publish, or averaged over one.
+    pub is_estimated: bool,
+    /// Spot price plus the zone's tariff, with `?include=consumer_price`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consumer_price: Option<Decimal>,
+}
+
+impl PricePoint {
+    pub fn new(price: &Price, tz: &Tz) -> Self {
+        let local_time = price.timestamp.with_timezone(tz);
+        Self {
+            timestamp: local_time.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+            timestamp_utc: price.timestamp,
+            price: price.price_kwh,
+            is_estimated: price.filled(),
+            consumer_price: None,
+        }
+    }
+
+    fn in_unit(&mut self, unit: EnergyUnit) {
+        self.price = unit.from_kwh(self.price);
+        self.consumer_price = self.consumer_price.map(|price| unit.from_kwh(price));
+    }
+}
+
+/// Set `consumer_price` on every point. Returns the tariff applied, if any.
+fn apply_tariff(points: &mut [PricePoint], tariff: Option<&Tariff>) -> Option<Tariff> {
+    let tariff = tariff?;
+    for point in points {
+        point.consumer_price = Some(tariff.consumer_price(point.price));
+    }
+    Some(tariff.clone())
+}
+
+/// A local day in the response's timezone that is not 24 hours long because
+/// of a DST transition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct DstDay {
+    pub date: NaiveDate,
+    pub hours: i64,
+}
+
+/// DST transition days covered by `prices`, in date order.
+pub fn dst_days(prices: &[Price], tz: &Tz) -> Vec<DstDay> {
+    let mut dates: Vec<NaiveDate> = prices
+        .iter()
+        .map(|p| p.timestamp.with_timezone(tz).date_naive())
+        .collect();
+    dates.sort_unstable();
+    dates.dedup();
+    dates
+        .into_iter()
+        .map(|date| DeliveryDay::new(date, tz))
+        .filter(|day| day.is_dst_transition())
+        .map(|day| DstDay {
+            date: day.date,
+            hours: day.hours(),
+        })
+        .collect()
+}
+
+/// Currency `prices` are quoted in, as published by ENTSOE. EUR when there
+/// are no prices.
+pub fn price_currency(prices: &[Price]) -> String {
+    prices.first().map_or("EUR", |p| p.currency.as_str()).to_string()
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZonePricesResponse {
+    pub zone_code: String,
+    pub zone_name: String,
+    pub country_code: String,
+    pub country_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_zone_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_country_name: Option<String>,
+    pub timezone: String,
+    pub market: Market,
+    pub currency: String,
+    pub unit: String,
+    /// Interval each price covers.
+    pub resolution: PriceResolution,
+    /// Tariff behind `consumer_price`, with `?include=consumer_price`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tariff: Option<Tariff>,
+    pub prices: Vec<PricePoint>,
+    /// Days in the range that have 23 or 25 hours.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub dst_days: Vec<DstDay>,
+    pub fetched_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<PriceMeta>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<RangeMeta>,
+}
+
+impl ZonePricesResponse {
+    /// `prices` are averaged up to `resolution` where they are finer.
+    pub fn new(
+        zone: &BiddingZone,
+        market: Market,
+        prices: Vec<Price>,
+        resolution: PriceResolution,
+        timezone: Option<&str>,
+    ) -> Self {
+        let tz: Tz = timezone
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+        let provenance = PriceProvenance::new(&prices, resolution, &tz);
+        let prices = resolution.aggregate(prices);
+
+        Self {
+            zone_code: zone.zone_code.clone(),
+            zone_name: zone.zone_name.clone(),
+            country_code: zone.country_code.clone(),
+            country_name: zone.country_name.clone(),
+            localized_zone_name: None,
+            localized_country_name: None,
+            timezone: tz.to_string(),
+            market,
+            currency: price_currency(&prices),
+            unit: "kWh".to_string(),
+            resolution,
+            tariff: None,
+            prices: prices.iter().map(|p| PricePoint::new(p, &tz)).collect(),
+            dst_days: dst_days(&prices, &tz),
+            fetched_at: Utc::now(),
+            meta: None,
+            context: RangeMeta::with_provenance(provenance),
+        }
+    }
+
+    pub fn with_clamp(mut self, clamp: RangeClamp) -> Self {
+        self.meta = Some(PriceMeta { clamp });
+        self
+    }
+
+    pub fn with_defaults(mut self, defaults: Option<AppliedRangeDefaults>) -> Self {
+        if defaults.is_some() {
+            self.context.get_or_insert_with(RangeMeta::default).defaults = defaults;
+        }
+        self
+    }
+
+    pub fn localize(mut self, translations: &Translations) -> Self {
+        self.localized_zone_name = translations.zone(&self.zone_code);
+        self.localized_country_name = translations.country(&self.country_code);
+        self
+    }
+
+    pub fn with_consumer_prices(mut self, tariff: Option<&Tariff>) -> Self {
+        self.tariff = apply_tariff(&mut self.prices, tariff);
+        self
+    }
+
+    /// Express prices and consumer prices per `unit`; stored prices are per kWh.
+    pub fn in_unit(mut self, unit: EnergyUnit) -> Self {
+        self.unit = unit.label().to_string();
+        for point in &mut self.prices {
+            point.in_unit(unit);
+        }
+        self
+    }
+}
+
+/// `meta` of zone and country prices, present only with `?clamp=true`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PriceMeta {
+    pub clamp: RangeClamp,
+}
+
+/// Range defaults applied to a request, and how its prices were derived.
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct RangeMeta {
+    /// Present when `start` or `end` was omitted and a default was applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub defaults: Option<AppliedRangeDefaults>,
+    /// Present when prices were averaged from finer data or forward-filled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub provenance: Option<PriceProvenance>,
+}
+
+impl RangeMeta {
+    fn with_defaults(defaults: Option<AppliedRangeDefaults>) -> Option<Self> {
+        defaults.map(|defaults| Self {
+            defaults: Some(defaults),
+            provenance: None,
+        })
+    }
+
+    fn with_provenance(provenance: Option<PriceProvenance>) -> Option<Self> {
+        provenance.map(|provenance| Self {
+            defaults: None,
+            provenance: Some(provenance),
+        })
+    }
+}
+
+/// How served prices were derived from the series ENTSOE published, so
+/// consumers can tell them from raw market output.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PriceProvenance {
+    /// Resolutions of the stored prices, finest first.
+    pub source_resolutions: Vec<String>,
+    /// `mean` when finer prices were averaged up to the served resolution.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregation: Option<String>,
+    /// How positions missing from ENTSOE documents were filled:
+    /// `forward_fill`, `linear_interpolation`, or both comma-separated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gap_fill: Option<String>,
+    /// Filled source positions per local date, for days with any.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default)]
+    pub filled_positions: BTreeMap<NaiveDate, usize>,
+}
+
+impl PriceProvenance {
+    /// Describe `prices`, as stored, being served at `resolution`. `None`
+    /// when they are served unchanged.
+    pub fn new(prices: &[Price], resolution: PriceResolution, tz: &Tz) -> Option<Self> {
+        let aggregated = prices
+            .iter()
+            .any(|p| parse_resolution(&p.resolution).is_ok_and(|native| native < resolution.duration()));
+        let mut filled_positions: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        let mut gap_fills: Vec<&'static str> = Vec::new();
+        for (price, method) in prices.iter().filter_map(|p| p.gap_fill.map(|method| (p, method))) {
+            *filled_positions.entry(price.timestamp.with_timezone(tz).date_naive()).or_default() += 1;
+            if !gap_fills.contains(&method.as_str()) {
+                gap_fills.push(method.as_str());
+            }
+        }
+        gap_fills.sort_unstable();
+        if !aggregated && filled_positions.is_empty() {
+            return None;
+        }
+
+        let mut source_resolutions: Vec<String> = Vec::new();
+        for price in prices {
+            if !source_resolutions.contains(&price.resolution) {
+                source_resolutions.push(price.resolution.clone());
+            }
+        }
+        source_resolutions.sort_by_key(|r| parse_resolution(r).ok());
+
+        Some(Self {
+            source_resolutions,
+            aggregation: aggregated.then(|| "mean".to_string()),
+            gap_fill: (!gap_fills.is_empty()).then(|| gap_fills.join(",")),
+            filled_positions,
+        })
+    }
+}
+
+/// Configured default range echoed back when a query omits `start` or `end`.
+/// Only the bounds that were actually defaulted are set.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AppliedRangeDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+    pub lookback_days: u32,
+    pub lookahead_days: u32,
+}
+
+/// Outcome of `?clamp=true`: the requested range narrowed to the data that is
+/// actually stored for a zone. `start`/`end` are `None` when the zone has no
+/// data inside the requested range.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RangeClamp {
+    pub requested_start: DateTime<Utc>,
+    pub requested_end: DateTime<Utc>,
+    pub available_start: Option<DateTime<Utc>>,
+    pub available_end: Option<DateTime<Utc>>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub clamped: bool,
+}
+
+impl RangeClamp {
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>, coverage: Option<&PriceCoverage>) -> Self {
+        let available = coverage.map(|c| (c.first_timestamp, c.end()));
+        let effective = available
+            .map(|(first, last)| (start.max(first), end.min(last)))
+            .filter(|(s, e)| s < e);
+
+        Self {
+            requested_start: start,
+            requested_end: end,
+            available_start: available.map(|(first, _)| first),
+            available_end: available.map(|(_, last)| last),
+            start: effective.map(|(s, _)| s),
+            end: effective.map(|(_, e)| e),
+            clamped: effective != Some((start, end)),
+        }
+    }
+
+    /// Range to query, or `None` if nothing stored overlaps the request.
+    pub fn range(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        self.start.zip(self.end)
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZoneStatsResponse {
+    pub zone_code: String,
+    pub market: Market,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub currency: String,
+    pub unit: String,
+    pub price_count: i64,
+    pub min_price: Option<Decimal>,
+    pub max_price: Option<Decimal>,
+    pub avg_price: Option<Decimal>,
+    pub completeness: Completeness,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RangeMeta>,
+}
+
+impl ZoneStatsResponse {
+    pub fn new(zone_code: String, market: Market, start: DateTime<Utc>, end: DateTime<Utc>, stats: ZoneStats) -> Self {
+        Self {
+            zone_code,
+            market,
+            start,
+            end,
+            currency: stats.currency.clone().unwrap_or_else(|| "EUR".to_string()),
+            unit: "kWh".to_string(),
+            price_count: stats.price_count,
+            min_price: stats.min_price,
+            max_price: stats.max_price,
+            avg_price: stats.avg_price.map(|avg| avg.round_dp(6)),
+            completeness: Completeness::new(start, end, &stats),
+            meta: None,
+        }
+    }
+
+    pub fn with_defaults(mut self, defaults: Option<AppliedRangeDefaults>) -> Self {
+        self.meta = RangeMeta::with_defaults(defaults);
+        self
+    }
+
+    pub fn in_unit(mut self, unit: EnergyUnit) -> Self {
+        self.unit = unit.label().to_string();
+        for price in [&mut self.min_price, &mut self.max_price, &mut self.avg_price] {
+            *price = price.map(|price| unit.from_kwh(price));
+        }
+        self
+    }
+}
+
+/// Runs of consecutive hours priced below `threshold` for one zone.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NegativePricesResponse {
+    pub zone_code: String,
+    pub market: Market,
+    pub timezone: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub currency: String,
+    pub unit: String,
+    pub threshold: Decimal,
+    pub total_hours: i64,
+    pub intervals: Vec<PriceInterval>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RangeMeta>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PriceInterval {
+    pub start: String,
+    pub end: String,
+    pub start_utc: DateTime<Utc>,
+    pub end_utc: DateTime<Utc>,
+    pub hours: i64,
+    pub min_price: Decimal,
+    pub avg_price: Decimal,
+}
+
+impl NegativePricesResponse {
+    pub fn new(zone: &BiddingZone, range: &QueryRange, threshold: Decimal, prices: &[Price], timezone: Option<&str>) -> Self {
+        let tz: Tz = timezone
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+
+        // Group hourly prices below the threshold into runs without gaps
+        let prices = PriceResolution::Hour.aggregate(prices.to_vec());
+        let mut runs: Vec<Vec<&Price>> = Vec::new();
+        for price in prices.iter().filter(|p| p.price_kwh < threshold) {
+            match runs.last_mut() {
+                Some(run) if run.last().unwrap().timestamp + Duration::hours(1) == price.timestamp => run.push(price),
+                _ => runs.push(vec![price]),
+            }
+        }
+
+        let format = |at: DateTime<Utc>| at.with_timezone(&tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+        let intervals: Vec<PriceInterval> = runs
+            .into_iter()
+            .map(|run| {
+                let start = run[0].timestamp;
+                let end = run[run.len() - 1].timestamp + Duration::hours(1);
+                let total: Decimal = run.iter().map(|p| p.price_kwh).sum();
+                PriceInterval {
+                    start: format(start),
+                    end: format(end),
+                    start_utc: start,
+                    end_utc: end,
+                    hours: run.len() as i64,
+                    min_price: run.iter().map(|p| p.price_kwh).min().unwrap(),
+                    avg_price: (total / Decimal::from(run.len())).round_dp(6),
+                }
+            })
+            .collect();
+
+        Self {
+            zone_code: zone.zone_code.clone(),
+            market: range.market,
+            timezone: tz.to_string(),
+            start: range.start,
+            end: range.end,
+            currency: price_currency(&prices),
+            unit: "kWh".to_string(),
+            threshold,
+            total_hours: intervals.iter().map(|interval| interval.hours).sum(),
+            intervals,
+            meta: RangeMeta::with_defaults(range.defaults.clone()),
+        }
+    }
+
+    pub fn in_unit(mut self, unit: EnergyUnit) -> Self {
+        self.unit = unit.label().to_string();
+        self.threshold = unit.from_kwh(self.threshold);
+        for interval in &mut self.intervals {
+            interval.min_price = unit.from_kwh(interval.min_price);
+            interval.avg_price = unit.from_kwh(interval.avg_price);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PriceAtQuery {
+    /// Instant to price (RFC 3339).
+    pub timestamp: Option<String>,
+    pub timezone: Option<String>,
+    /// `day_ahead` (default) or `intraday`.
+    pub market: Option<String>,
+}
+
+impl PriceAtQuery {
+    pub fn parse(&self) -> Result<(DateTime<Utc>, Market), AppError> {
+        let timestamp = self
+            .timestamp
+            .as_deref()
+            .ok_or_else(|| AppError::invalid_parameter("timestamp", "Missing timestamp. Use ISO8601/RFC3339."))?;
+        let at = DateTime::parse_from_rfc3339(timestamp)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| AppError::invalid_parameter("timestamp", format!("Invalid timestamp format: {}. Use ISO8601/RFC3339.", e)))?;
+        let market = match &self.market {
+            Some(m) => Market::parse(m)
+                .ok_or_else(|| AppError::invalid_parameter("market", format!("Invalid market: {}. Use day_ahead or intraday.", m)))?,
+            None => Market::DayAhead,
+        };
+        Ok((at, market))
+    }
+}
+
+/// The stored price interval covering an instant, at the resolution ENTSOE
+/// published it, for reconciling metered consumption against.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PriceAtResponse {
+    pub zone_code: String,
+    pub market: Market,
+    pub timezone: String,
+    pub currency: String,
+    pub unit: String,
+    /// Instant that was looked up.
+    pub requested_at: DateTime<Utc>,
+    /// Interval the price covers, in `timezone` and UTC.
+    pub interval_start: String,
+    pub interval_start_utc: DateTime<Utc>,
+    pub interval_end: String,
+    pub interval_end_utc: DateTime<Utc>,
+    pub resolution: String,
+    pub price: Decimal,
+    /// Filled in for a position ENTSOE did not publish.
+    pub is_estimated: bool,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl PriceAtResponse {
+    pub fn new(zone: &BiddingZone, price: Price, requested_at: DateTime<Utc>, timezone: Option<&str>) -> Self {
+        let tz: Tz = timezone
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+        let format = |at: DateTime<Utc>| at.with_timezone(&tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+        let interval_end = price.interval_end();
+
+        Self {
+            zone_code: zone.zone_code.clone(),
+            market: price.market,
+            timezone: tz.to_string(),
+            currency: price.currency.clone(),
+            unit: "kWh".to_string(),
+            requested_at,
+            interval_start: format(price.timestamp),
+            interval_start_utc: price.timestamp,
+            interval_end: format(interval_end),
+            interval_end_utc: interval_end,
+            is_estimated: price.filled(),
+            resolution: price.resolution,
+            price: price.price_kwh,
+            fetched_at: price.fetched_at,
+        }
+    }
+
+    pub fn in_unit(mut self, unit: EnergyUnit) -> Self {
+        self.unit = unit.label().to_string();
+        self.price = unit.from_kwh(self.price);
+        self
+    }
+}
+
+/// A zone's day-ahead prices for today and tomorrow in its local time, shaped
+/// like the sensor attributes of the Nordpool and ENTSO-e Home Assistant
+/// integrations so a REST sensor can read them without templates. Prices are
+/// JSON numbers here, as those integrations publish them, rather than the
+/// decimal strings used elsewhere.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HomeAssistantResponse {
+    pub zone_code: String,
+    pub country: String,
+    pub currency: String,
+    pub unit: String,
+    pub timezone: String,
+    /// Price of the interval containing now.
+    pub current_price: Option<f64>,
+    /// Today's average, lowest and highest price.
+    pub average: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Today's average from midnight to 08:00.
+    pub off_peak_1: Option<f64>,
+    /// Today's average from 08:00 to 20:00.
+    pub peak: Option<f64>,
+    /// Today's average from 20:00 to midnight.
+    pub off_peak_2: Option<f64>,
+    pub today: Vec<f64>,
+    pub tomorrow: Vec<f64>,
+    /// Whether tomorrow's prices cover the whole day.
+    pub tomorrow_valid: bool,
+    pub raw_today: Vec<HomeAssistantPrice>,
+    pub raw_tomorrow: Vec<HomeAssistantPrice>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HomeAssistantPrice {
+    pub start: String,
+    pub end: String,
+    pub value: f64,
+}
+
+impl HomeAssistantResponse {
+    /// `prices` must cover `today` and the day after, at any resolution;
+    /// they are averaged to `resolution` first and quoted per `unit`.
+    pub fn new(
+        zone: &BiddingZone,
+        tz: &Tz,
+        today: DeliveryDay,
+        prices: Vec<Price>,
+        resolution: PriceResolution,
+        unit: EnergyUnit,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let tomorrow = DeliveryDay::new(today.end.with_timezone(tz).date_naive(), tz);
+        let prices = resolution.aggregate(prices);
+        let format = |at: DateTime<Utc>| at.with_timezone(tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+        let number = |value: Decimal| value.to_f64().unwrap_or_default();
+        let intervals: Vec<(DateTime<Utc>, DateTime<Utc>, Decimal)> = prices
+            .iter()
+            .map(|price| {
+                let length = parse_resolution(&price.resolution).unwrap_or(Duration::hours(1));
+                (price.timestamp, price.timestamp + length, unit.from_kwh(price.price_kwh))
+            })
+            .collect();
+        let day = |day: &DeliveryDay| -> Vec<(DateTime<Utc>, DateTime<Utc>, Decimal)> {
+            intervals.iter().filter(|(start, _, _)| *start >= day.start && *start < day.end).copied().collect()
+        };
+        let (today_intervals, tomorrow_intervals) = (day(&today), day(&tomorrow));
+
+        let average = |values: Vec<Decimal>| -> Option<f64> {
+            (!values.is_empty()).then(|| number((values.iter().sum::<Decimal>() / Decimal::from(values.len())).round_dp(6)))
+        };
+        let between = |from: u32, to: u32| {
+            average(
+                today_intervals
+                    .iter()
+                    .filter(|(start, _, _)| (from..to).contains(&start.with_timezone(tz).hour()))
+                    .map(|(_, _, value)| *value)
+                    .collect(),
+            )
+        };
+        let values = |intervals: &[(DateTime<Utc>, DateTime<Utc>, Decimal)]| -> Vec<f64> {
+            intervals.iter().map(|(_, _, value)| number(*value)).collect()
+        };
+        let raw = |intervals: &[(DateTime<Utc>, DateTime<Utc>, Decimal)]| -> Vec<HomeAssistantPrice> {
+            intervals
+                .iter()
+                .map(|(start, end, value)| HomeAssistantPrice {
+                    start: format(*start),
+                    end: format(*end),
+                    value: number(*value),
+                })
+                .collect()
+        };
+        let covered: Duration = tomorrow_intervals.iter().map(|(start, end, _)| *end - *start).sum();
+
+        Self {
+            zone_code: zone.zone_code.clone(),
+            country: zone.country_code.clone(),
+            currency: price_currency(&prices),
+            unit: unit.label().to_string(),
+            timezone: tz.to_string(),
+            current_price: intervals
+                .iter()
+                .find(|(start, end, _)| *start <= now && now < *end)
+                .map(|(_, _, value)| number(*value)),
+            average: average(today_intervals.iter().map(|(_, _, value)| *value).collect()),
+            min: today_intervals.iter().map(|(_, _, value)| *value).min().map(number),
+            max: today_intervals.iter().map(|(_, _, value)| *value).max().map(number),
+            off_peak_1: between(0, 8),
+            peak: between(8, 20),
+            off_peak_2: between(20, 24),
+            today: values(&today_intervals),
+            tomorrow: values(&tomorrow_intervals),
+            tomorrow_valid: !tomorrow_intervals.is_empty() && covered == tomorrow.end - tomorrow.start,
+            raw_today: raw(&today_intervals),
+            raw_tomorrow: raw(&tomorrow_intervals),
+        }
+    }
+}
+
+/// Actual generation per production type for one zone, one series per type.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GenerationResponse {
+    pub zone_code: String,
+    pub zone_name: String,
+    pub timezone: String,
+    pub unit: String,
+    pub series: Vec<GenerationSeries>,
+    pub fetched_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RangeMeta>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GenerationSeries {
+    /// ENTSOE production type code, e.g. `B16`.
+    pub psr_type: String,
+    /// Human-readable production type, or the code if it is not known.
+    pub psr_name: String,
+    pub points: Vec<GenerationQuantity>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GenerationQuantity {
+    pub timestamp: String,
+    pub timestamp_utc: DateTime<Utc>,
+    pub quantity: Decimal,
+}
+
+impl GenerationResponse {
+    /// Group `points` (ordered by timestamp) into per-type series sorted by code.
+    pub fn new(zone: &BiddingZone, points: Vec<GenerationPoint>, timezone: Option<&str>) -> Self {
+        let tz: Tz = timezone
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+
+        let mut by_type: std::collections::BTreeMap<String, Vec<GenerationQuantity>> = Default::default();
+        for point in points {
+            by_type.entry(point.psr_type).or_default().push(GenerationQuantity {
+                timestamp: point.timestamp.with_timezone(&tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                timestamp_utc: point.timestamp,
+                quantity: point.quantity_mw,
+            });
+        }
+
+        Self {
+            zone_code: zone.zone_code.clone(),
+            zone_name: zone.zone_name.clone(),
+            timezone: tz.to_string(),
+            unit: "MW".to_string(),
+            series: by_type
+                .into_iter()
+                .map(|(psr_type, points)| GenerationSeries {
+                    psr_name: psr_type_name(&psr_type).unwrap_or(&psr_type).to_string(),
+                    psr_type,
+                    points,
+                })
+                .collect(),
+            fetched_at: Utc::now(),
+            meta: None,
+        }
+    }
+
+    pub fn with_defaults(mut self, defaults: Option<AppliedRangeDefaults>) -> Self {
+        self.meta = RangeMeta::with_defaults(defaults);
+        self
+    }
+}
+
+/// Actual total load for one zone.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LoadResponse {
+    pub zone_code: String,
+    pub zone_name: String,
+    pub timezone: String,
+    pub unit: String,
+    pub values: Vec<LoadPoint>,
+    pub fetched_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RangeMeta>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LoadPoint {
+    pub timestamp: String,
+    pub timestamp_utc: DateTime<Utc>,
+    pub load: Decimal,
+}
+
+impl LoadResponse {
+    pub fn new(zone: &BiddingZone, values: Vec<LoadValue>, timezone: Option<&str>) -> Self {
+        let tz: Tz = timezone
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+
+        Self {
+            zone_code: zone.zone_code.clone(),
+            zone_name: zone.zone_name.clone(),
+            timezone: tz.to_string(),
+            unit: "MW".to_string(),
+            values: values
+                .into_iter()
+                .map(|v| LoadPoint {
+                    timestamp: v.timestamp.with_timezone(&tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                    timestamp_utc: v.timestamp,
+                    load: v.load_mw,
+                })
+                .collect(),
+            fetched_at: Utc::now(),
+            meta: None,
+        }
+    }
+
+    pub fn with_defaults(mut self, defaults: Option<AppliedRangeDefaults>) -> Self {
+        self.meta = RangeMeta::with_defaults(defaults);
+        self
+    }
+}
+
+/// Hourly completeness of a zone's series over the queried range. Hours are
+/// UTC buckets starting at the hour containing `start`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Completeness {
+    pub expected_hours: i64,
+    pub covered_hours: i64,
+    pub missing_hours: i64,
+    /// Longest run of consecutive missing hours, including the edges of the range.
+    pub longest_gap_hours: i64,
+    pub first_timestamp: Option<DateTime<Utc>>,
+    pub last_timestamp: Option<DateTime<Utc>>,
+}
+
+impl Completeness {
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>, stats: &ZoneStats) -> Self {
+        let floor_hour = |ts: DateTime<Utc>| ts.duration_trunc(Duration::hours(1)).unwrap_or(ts);
+        let grid_start = floor_hour(start);
+        let expected_hours = ((end - grid_start).num_seconds() + 3599).div_euclid(3600).max(0);
+
+        let longest_gap_hours = match (stats.first_timestamp, stats.last_timestamp) {
+            (Some(first), Some(last)) => {
+                let leading = (floor_hour(first) - grid_start).num_hours();
+                let trailing = expected_hours - 1 - (floor_hour(last) - grid_start).num_hours();
+                leading.max(trailing).max(stats.longest_interior_gap_hours).max(0)
+            }
+            _ => expected_hours,
+        };
+
+        Self {
+            expected_hours,
+            covered_hours: stats.covered_hours,
+            missing_hours: (expected_hours - stats.covered_hours).max(0),
+            longest_gap_hours,
+            first_timestamp: stats.first_timestamp,
+            last_timestamp: stats.last_timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZonePrices {
+    pub zone_code: String,
+    pub zone_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_zone_name: Option<String>,
+    pub timezone: String,
+    /// Interval each price covers.
+    pub resolution: PriceResolution,
+    /// Tariff behind `consumer_price`, with `?include=consumer_price`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tariff: Option<Tariff>,
+    pub prices: Vec<PricePoint>,
+    /// Days in the range that have 23 or 25 hours.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub dst_days: Vec<DstDay>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<PriceMeta>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<RangeMeta>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CountryPricesResponse {
+    pub country_code: String,
+    pub country_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_country_name: Option<String>,
+    pub market: Market,
+    pub currency: String,
+    pub unit: String,
+    pub zones: Vec<ZonePrices>,
+    /// One series over all zones, with `?aggregate=average`; `zones` is then empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average: Option<CountryAverage>,
+    /// A price summary per zone, with `?include=stats`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<Vec<ZonePriceSummary>>,
+    pub fetched_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RangeMeta>,
+}
+
+/// Lowest, highest and average price of a zone's series as served.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZonePriceSummary {
+    pub zone_code: String,
+    pub price_count: i64,
+    pub min_price: Option<Decimal>,
+    pub max_price: Option<Decimal>,
+    pub avg_price: Option<Decimal>,
+}
+
+impl ZonePriceSummary {
+    fn new(zone: &ZonePrices) -> Self {
+        let prices = || zone.prices.iter().map(|point| point.price);
+        let total: Decimal = prices().sum();
+        Self {
+            zone_code: zone.zone_code.clone(),
+            price_count: zone.prices.len() as i64,
+            min_price: prices().min(),
+            max_price: prices().max(),
+            avg_price: (!zone.prices.is_empty()).then(|| (total / Decimal::from(zone.prices.len())).round_dp(6)),
+        }
+    }
+}
+
+/// How a country average weighs its zones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AverageMethod {
+    /// Every zone counts equally.
+    Mean,
+    /// Zones count by their configured weight.
+    Weighted,
+}
+
+/// A country's prices averaged over its zones per interval.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CountryAverage {
+    pub method: AverageMethod,
+    pub timezone: String,
+    /// Interval each price covers, the coarsest of the zones' resolutions.
+    pub resolution: PriceResolution,
+    /// Weight of each zone averaged, by zone code.
+    pub weights: BTreeMap<String, Decimal>,
+    pub prices: Vec<CountryAveragePoint>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CountryAveragePoint {
+    pub timestamp: String,
+    pub timestamp_utc: DateTime<Utc>,
+    pub price: Decimal,
+    /// Zones priced for the interval; intervals missing a zone average the rest.
+    pub zones: usize,
+    /// Any zone's price was filled in or averaged over one that was.
+    pub is_estimated: bool,
+}
+
+impl CountryAverage {
+    /// Average each interval over the zones priced in it, once every zone
+    /// is averaged up to the coarsest resolution among them. Weighted when
+    /// every zone with prices has a weight in `weights`.
+    pub fn new(
+        zones: &[BiddingZone],
+        prices_by_zone: &HashMap<String, Vec<Price>>,
+        resolutions: &ResolutionConfig,
+        weights: &CountryAverageConfig,
+        tz: &Tz,
+    ) -> Self {
+        let priced: Vec<&BiddingZone> = zones
+            .iter()
+            .filter(|zone| prices_by_zone.get(&zone.zone_code).is_some_and(|prices| !prices.is_empty()))
+            .collect();
+        let resolution = priced
+            .iter()
+            .map(|zone| resolutions.for_zone(&zone.zone_code))
+            .max()
+            .unwrap_or_default();
+        let configured: Option<BTreeMap<String, Decimal>> = priced
+            .iter()
+            .map(|zone| weights.for_zone(&zone.zone_code).map(|weight| (zone.zone_code.clone(), weight)))
+            .collect();
+        let (method, weights) = match configured {
+            Some(weights) if !weights.is_empty() => (AverageMethod::Weighted, weights),
+            _ => (
+                AverageMethod::Mean,
+                priced.iter().map(|zone| (zone.zone_code.clone(), Decimal::ONE)).collect(),
+            ),
+        };
+
+        // Weighted sum, total weight, zones and whether any was estimated, per interval
+        let mut intervals: BTreeMap<DateTime<Utc>, (Decimal, Decimal, usize, bool)> = BTreeMap::new();
+        for zone in &priced {
+            let weight = weights[&zone.zone_code];
+            for price in resolution.aggregate(prices_by_zone[&zone.zone_code].clone()) {
+                let interval = intervals.entry(price.timestamp).or_default();
+                interval.0 += weight * price.price_kwh;
+                interval.1 += weight;
+                interval.2 += 1;
+                interval.3 |= price.filled();
+            }
+        }
+
+        let prices = intervals
+            .into_iter()
+            .filter(|(_, (_, total_weight, _, _))| !total_weight.is_zero())
+            .map(|(timestamp, (sum, total_weight, zones, is_estimated))| CountryAveragePoint {
+                timestamp: timestamp.with_timezone(tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                timestamp_utc: timestamp,
+                price: (sum / total_weight).round_dp(6).normalize(),
+                zones,
+                is_estimated,
+            })
+            .collect();
+
+        Self {
+            method,
+            timezone: tz.to_string(),
+            resolution,
+            weights,
+            prices,
+        }
+    }
+}
+
+impl CountryPricesResponse {
+    pub fn new(
+        country_code: String,
+        country_name: String,
+        market: Market,
+        zones: &[BiddingZone],
+        prices_by_zone: HashMap<String, Vec<Price>>,
+        resolutions: &ResolutionConfig,
+        timezone: Option<&str>,
+    ) -> Self {
+        let currency = zones
+            .iter()
+            .find_map(|zone| prices_by_zone.get(&zone.zone_code))
+            .map_or_else(|| "EUR".to_string(), |prices| price_currency(prices));
+        let zone_prices: Vec<ZonePrices> = zones
+            .iter()
+            .filter_map(|zone| {
+                let tz: Tz = timezone
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+                
+                prices_by_zone.get(&zone.zone_code).map(|prices| {
+                    let resolution = resolutions.for_zone(&zone.zone_code);
+                    let provenance = PriceProvenance::new(prices, resolution, &tz);
+                    let prices = resolution.aggregate(prices.clone());
+                    ZonePrices {
+                        zone_code: zone.zone_code.clone(),
+                        zone_name: zone.zone_name.clone(),
+                        localized_zone_name: None,
+                        timezone: tz.to_string(),
+                        resolution,
+                        tariff: None,
+                        prices: prices.iter().map(|p| PricePoint::new(p, &tz)).collect(),
+                        dst_days: dst_days(&prices, &tz),
+                        meta: None,
+                        context: RangeMeta::with_provenance(provenance),
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            country_code,
+            country_name,
+            localized_country_name: None,
+            market,
+            currency,
+            unit: "kWh".to_string(),
+            zones: zone_prices,
+            average: None,
+            stats: None,
+            fetched_at: Utc::now(),
+            meta: None,
+        }
+    }
+
+    /// Summarise each zone's series when `sections` asks for stats, and
+    /// drop the series when it does not ask for zones.
+    pub fn with_sections(mut self, sections: CountrySections) -> Self {
+        if sections.stats {
+            self.stats = Some(self.zones.iter().map(ZonePriceSummary::new).collect());
+        }
+        if !sections.zones {
+            self.zones.clear();
+        }
+        self
+    }
+
+    /// Serve `average` in place of the per-zone series.
+    pub fn with_average(mut self, average: CountryAverage) -> Self {
+        self.zones.clear();
+        self.average = Some(average);
+        self
+    }
+
+    pub fn with_defaults(mut self, defaults: Option<AppliedRangeDefaults>) -> Self {
+        self.meta = RangeMeta::with_defaults(defaults);
+        self
+    }
+
+    pub fn with_clamps(mut self, mut clamps: HashMap<String, RangeClamp>) -> Self {
+        for zone in &mut self.zones {
+            if let Some(clamp) = clamps.remove(&zone.zone_code) {
+                zone.meta = Some(PriceMeta { clamp });
+            }
+        }
+        self
+    }
+
+    pub fn localize(mut self, translations: &Translations) -> Self {
+        self.localized_country_name = translations.country(&self.country_code);
+        for zone in &mut self.zones {
+            zone.localized_zone_name = translations.zone(&zone.zone_code);
+        }
+        self
+    }
+
+    /// Price zones that have a tariff in `tariffs`; `None` leaves prices as-is.
+    pub fn with_consumer_prices(mut self, tariffs: Option<&TariffConfig>) -> Self {
+        let Some(tariffs) = tariffs else {
+            return self;
+        };
+        for zone in &mut self.zones {
+            zone.tariff = apply_tariff(&mut zone.prices, tariffs.for_zone(&zone.zone_code));
+        }
+        self
+    }
+
+    pub fn in_unit(mut self, unit: EnergyUnit) -> Self {
+        self.unit = unit.label().to_string();
+        for point in self.zones.iter_mut().flat_map(|zone| &mut zone.prices) {
+            point.in_unit(unit);
+        }
+        for point in self.average.iter_mut().flat_map(|average| &mut average.prices) {
+            point.price = unit.from_kwh(point.price);
+        }
+        for summary in self.stats.iter_mut().flatten() {
+            for price in [&mut summary.min_price, &mut summary.max_price, &mut summary.avg_price] {
+                *price = price.map(|price| unit.from_kwh(price));
+            }
+        }
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CountryAggregateQuery {
+    /// `average` for one series averaged over the country's zones.
+    pub aggregate: Option<String>,
+}
+
+impl CountryAggregateQuery {
+    /// Whether `average` was requested.
+    pub fn average(&self) -> Result<bool, String> {
+        match self.aggregate.as_deref().map(str::trim) {
+            None | Some("") => Ok(false),
+            Some("average") => Ok(true),
+            Some(other) => Err(format!("Invalid aggregate: {}. Use average.", other)),
+        }
+    }
+}
+
+/// Most zones `/prices/compare` accepts in one request.
+pub const MAX_COMPARE_ZONES: usize = 10;
+
+#[derive(Debug, Deserialize)]
+pub struct CompareQuery {
+    /// Comma-separated zone codes, e.g. `NO1,SE3,DK1`.
+    pub zones: Option<String>,
+}
+
+impl CompareQuery {
+    /// Requested zone codes, uppercased and in request order without duplicates.
+    pub fn parse(&self) -> Result<Vec<String>, String> {
+        let mut zones: Vec<String> = Vec::new();
+        for zone in self.zones.iter().flat_map(|zones| zones.split(',')).map(str::trim) {
+            let zone = zone.to_uppercase();
+            if !zone.is_empty() && !zones.contains(&zone) {
+                zones.push(zone);
+            }
+        }
+        if zones.len() < 2 {
+            return Err("zones must list at least two zone codes, e.g. zones=NO1,SE3".to_string());
+        }
+        if zones.len() > MAX_COMPARE_ZONES {
+            return Err(format!("zones lists {} zones; at most {} can be compared", zones.len(), MAX_COMPARE_ZONES));
+        }
+        Ok(zones)
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CompareZone {
+    pub zone_code: String,
+    pub zone_name: String,
+    pub country_code: String,
+}
+
+/// Every zone's price for one interval.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CompareRow {
+    pub timestamp: String,
+    pub timestamp_utc: DateTime<Utc>,
+    /// Price per zone code; `null` where a zone has no price for the interval.
+    pub prices: BTreeMap<String, Option<Decimal>>,
+    /// Highest minus lowest price, when at least two zones have one.
+    pub spread: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ComparePricesResponse {
+    pub timezone: String,
+    pub market: Market,
+    pub currency: String,
+    pub unit: String,
+    /// Coarsest resolution among the zones; finer prices are averaged up to it.
+    pub resolution: PriceResolution,
+    /// In request order.
+    pub zones: Vec<CompareZone>,
+    /// One row per interval any zone has a price for, in time order.
+    pub rows: Vec<CompareRow>,
+    pub fetched_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RangeMeta>,
+}
+
+impl ComparePricesResponse {
+    /// Align `prices_by_zone` on common timestamps. Timestamps are local to
+    /// `timezone`, or to the first zone. Fails when the zones are priced in
+    /// different currencies, as their spread would be meaningless.
+    pub fn new(
+        zones: &[BiddingZone],
+        market: Market,
+        prices_by_zone: HashMap<String, Vec<Price>>,
+        resolutions: &ResolutionConfig,
+        timezone: Option<&str>,
+    ) -> Result<Self, String> {
+        let tz: Tz = timezone
+            .and_then(|s| s.parse().ok())
+            .or_else(|| zones.first().and_then(|zone| zone.timezone.parse().ok()))
+            .unwrap_or(chrono_tz::UTC);
+        let resolution = zones
+            .iter()
+            .map(|zone| resolutions.for_zone(&zone.zone_code))
+            .max()
+            .unwrap_or_default();
+
+        let mut currencies: Vec<&str> = prices_by_zone.values().flatten().map(|p| p.currency.as_str()).collect();
+        currencies.sort_unstable();
+        currencies.dedup();
+        if currencies.len() > 1 {
+            return Err(format!("Zones are priced in different currencies ({}) and cannot be compared", currencies.join(", ")));
+        }
+        let currency = currencies.first().map_or("EUR", |c| c).to_string();
+
+        let mut by_timestamp: BTreeMap<DateTime<Utc>, BTreeMap<String, Option<Decimal>>> = BTreeMap::new();
+        for zone in zones {
+            let prices = prices_by_zone.get(&zone.zone_code).cloned().unwrap_or_default();
+            for price in resolution.aggregate(prices) {
+                by_timestamp.entry(price.timestamp).or_default().insert(zone.zone_code.clone(), Some(price.price_kwh));
+            }
+        }
+
+        let rows = by_timestamp
+            .into_iter()
+            .map(|(timestamp, mut prices)| {
+                for zone in zones {
+                    prices.entry(zone.zone_code.clone()).or_insert(None);
+                }
+                let present: Vec<Decimal> = prices.values().flatten().copied().collect();
+                let spread = (present.len() >= 2).then(|| {
+                    present.iter().max().unwrap() - present.iter().min().unwrap()
+                });
+                CompareRow {
+                    timestamp: timestamp.with_timezone(&tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                    timestamp_utc: timestamp,
+                    prices,
+                    spread,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            timezone: tz.to_string(),
+            market,
+            currency,
+            unit: "kWh".to_string(),
+            resolution,
+            zones: zones
+                .iter()
+                .map(|zone| CompareZone {
+                    zone_code: zone.zone_code.clone(),
+                    zone_name: zone.zone_name.clone(),
+                    country_code: zone.country_code.clone(),
+                })
+                .collect(),
+            rows,
+            fetched_at: Utc::now(),
+            meta: None,
+        })
+    }
+
+    pub fn with_defaults(mut self, defaults: Option<AppliedRangeDefaults>) -> Self {
+        self.meta = RangeMeta::with_defaults(defaults);
+        self
+    }
+
+    pub fn in_unit(mut self, unit: EnergyUnit) -> Self {
+        self.unit = unit.label().to_string();
+        for row in &mut self.rows {
+            for price in row.prices.values_mut().chain(std::iter::once(&mut row.spread)) {
+                *price = price.map(|price| unit.from_kwh(price));
+            }
+        }
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FxRateQuery {
+    /// Day the rate applies to, e.g. `2025-03-14`; defaults to today (UTC).
+    pub date: Option<String>,
+}
+
+impl FxRateQuery {
+    pub fn parse(&self, today: NaiveDate) -> Result<NaiveDate, String> {
+        match self.date.as_deref() {
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .filter(|date| (1..=9999).contains(&date.year()))
+                .ok_or_else(|| format!("Invalid date: {}. Use YYYY-MM-DD.", date)),
+            None => Ok(today),
+        }
+    }
+}
+
+/// The EUR exchange rate in effect on a day, from the source configured for
+/// the currency (e.g. Norges Bank for NOK).
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FxRateResponse {
+    pub currency: String,
+    /// Units of `currency` per 1 EUR.
+    pub rate: Decimal,
+    /// Day that was asked for.
+    pub date: NaiveDate,
+    /// Publication the rate comes from; earlier than `date` on days the
+    /// source does not publish.
+    pub rate_date: NaiveDate,
+    pub source: FxSourceKind,
+}
+
+impl FxRateResponse {
+    pub fn new(rate: FxRate, date: NaiveDate) -> Self {
+        Self {
+            currency: rate.currency,
+            rate: rate.rate,
+            date,
+            rate_date: rate.rate_date,
+            source: rate.source,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InvoiceQuery {
+    /// Calendar month in the zone's local time, e.g. `2025-03`.
+    pub month: Option<String>,
+}
+
+impl InvoiceQuery {
+    /// First day of the requested month. Years are limited to four digits,
+    /// which `%Y` alone does not enforce (`+262142-12` parses).
+    pub fn parse(&self) -> Result<NaiveDate, String> {
+        let month = self.month.as_deref().ok_or("month is required, e.g. month=2025-03")?;
+        NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .ok()
+            .filter(|date| (1..=9999).contains(&date.year()))
+            .ok_or_else(|| format!("Invalid month: {}. Use YYYY-MM.", month))
+    }
+}
+
+/// A consumption profile uploaded to `/prices/zone/{zone}/invoice`.
+#[derive(Debug, Deserialize)]
+pub struct ConsumptionUpload {
+    /// Interval each entry covers; defaults to `PT60M`.
+    pub resolution: Option<String>,
+    pub consumption: Vec<ConsumptionEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConsumptionEntry {
+    /// Start of the interval.
+    pub timestamp: DateTime<Utc>,
+    pub kwh: Decimal,
+}
+
+impl ConsumptionUpload {
+    /// Entries as consumption intervals, all of which must fall within `start..end`.
+    pub fn parse(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Consumption>, String> {
+        let resolution = self.resolution.as_deref().unwrap_or("PT60M");
+        let duration = parse_resolution(resolution)
+            .ok()
+            .filter(|duration| *duration > Duration::zero())
+            .ok_or_else(|| format!("Invalid resolution: {}. Use an ISO 8601 duration such as PT60M.", resolution))?;
+        if self.consumption.is_empty() {
+            return Err("consumption must not be empty".to_string());
+        }
+
+        self.consumption
+            .iter()
+            .map(|entry| {
+                if entry.kwh.is_sign_negative() {
+                    return Err(format!("Consumption at {} is negative", entry.timestamp.to_rfc3339()));
+                }
+                if entry.timestamp < start || entry.timestamp + duration > end {
+                    return Err(format!("Consumption at {} is outside the month", entry.timestamp.to_rfc3339()));
+                }
+                Ok(Consumption {
+                    timestamp: entry.timestamp,
+                    duration,
+                    kwh: entry.kwh,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Where the consumption an invoice is weighted by came from.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsumptionProfile {
+    /// Posted with the request.
+    Uploaded,
+    /// The zone's actual total load, as for profile-settled customers.
+    ZoneLoad,
+}
+
+/// Amounts per kWh consumed, rounded to 6 decimals.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct InvoicePrices {
+    /// Consumption-weighted average spot price.
+    pub spot: Decimal,
+    pub support: Decimal,
+    pub fees: Decimal,
+    pub vat: Decimal,
+    pub total: Decimal,
+}
+
+/// Invoice lines rounded to 2 decimals, half away from zero. VAT is charged
+/// on the rounded lines so the lines add up to `total`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct InvoiceTotals {
+    pub consumption_kwh: Decimal,
+    pub spot: Decimal,
+    pub support: Decimal,
+    pub fees: Decimal,
+    pub vat: Decimal,
+    pub total: Decimal,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZoneInvoiceResponse {
+    pub zone_code: String,
+    pub month: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub currency: String,
+    pub profile: ConsumptionProfile,
+    /// Tariff applied; without one, the invoice is the spot cost alone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tariff: Option<Tariff>,
+    pub per_kwh: InvoicePrices,
+    /// Only for uploaded profiles; zone load totals are not a household's.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub totals: Option<InvoiceTotals>,
+    /// Consumption intervals left out because the month's prices do not cover them.
+    pub unpriced_intervals: usize,
+}
+
+impl ZoneInvoiceResponse {
+    pub fn new(
+        zone_code: String,
+        month: NaiveDate,
+        (start, end): (DateTime<Utc>, DateTime<Utc>),
+        currency: String,
+        profile: ConsumptionProfile,
+        tariff: Option<&Tariff>,
+        invoice: &Invoice,
+    ) -> Self {
+        let per_kwh = |amount: Decimal| invoice.per_kwh(amount).round_dp(6).normalize();
+        let money = |amount: Decimal| amount.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+        let totals = (profile == ConsumptionProfile::Uploaded).then(|| {
+            let (spot, support, fees) = (money(invoice.spot), money(invoice.support), money(invoice.fees));
+            let vat_rate = tariff.map_or(Decimal::ZERO, |tariff| tariff.vat_rate);
+            let vat = money((spot - support + fees) * vat_rate);
+            InvoiceTotals {
+                consumption_kwh: invoice.priced_kwh.normalize(),
+                spot,
+                support,
+                fees,
+                vat,
+                total: spot - support + fees + vat,
+            }
+        });
+
+        Self {
+            zone_code,
+            month: month.format("%Y-%m").to_string(),
+            start,
+            end,
+            currency,
+            profile,
+            tariff: tariff.cloned(),
+            per_kwh: InvoicePrices {
+                spot: per_kwh(invoice.spot),
+                support: per_kwh(invoice.support),
+                fees: per_kwh(invoice.fees),
+                vat: per_kwh(invoice.vat),
+                total: per_kwh(invoice.total()),
+            },
+            totals,
+            unpriced_intervals: invoice.unpriced_intervals,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LatestPriceEntry {
+    pub zone_code: String,
+    pub zone_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_zone_name: Option<String>,
+    pub country_code: String,
+    pub timezone: String,
+    pub timestamp: String,
+    pub timestamp_utc: DateTime<Utc>,
+    /// Interval the price covers, e.g. `PT60M`.
+    pub resolution: String,
+    pub currency: String,
+    pub price: Decimal,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LatestPricesResponse {
+    pub unit: String,
+    pub prices: Vec<LatestPriceEntry>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl LatestPricesResponse {
+    pub fn new(prices: Vec<Price>, zones: &[BiddingZone], timezone: Option<&str>) -> Self {
+        let zone_map: HashMap<&str, &BiddingZone> = zones
+            .iter()
+            .map(|z| (z.zone_code.as_str(), z))
+            .collect();
+
+        let entries: Vec<LatestPriceEntry> = prices
+            .into_iter()
+            .filter_map(|p| {
+                zone_map.get(p.bidding_zone.as_str()).map(|zone| {
+                    let tz: Tz = timezone
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+                    let local_time = p.timestamp.with_timezone(&tz);
+                    
+                    LatestPriceEntry {
+                        zone_code: p.bidding_zone,
+                        zone_name: zone.zone_name.clone(),
+                        localized_zone_name: None,
+                        country_code: zone.country_code.clone(),
+                        timezone: tz.to_string(),
+                        timestamp: local_time.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                        timestamp_utc: p.timestamp,
+                        resolution: p.resolution,
+                        currency: p.currency,
+                        price: p.price_kwh,
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            unit: "kWh".to_string(),
+            prices: entries,
+            fetched_at: Utc::now(),
+        }
+    }
+
+    pub fn localize(mut self, translations: &Translations) -> Self {
+        for entry in &mut self.prices {
+            entry.localized_zone_name = translations.zone(&entry.zone_code);
+        }
+        self
+    }
+
+    pub fn in_unit(mut self, unit: EnergyUnit) -> Self {
+        self.unit = unit.label().to_string();
+        for entry in &mut self.prices {
+            entry.price = unit.from_kwh(entry.price);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZoneInfo {
+    pub zone_code: String,
+    pub zone_name: String,
+    pub country_code: String,
+    pub country_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_zone_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_country_name: Option<String>,
+    pub eic_code: String,
+    pub timezone: String,
+    pub active: bool,
+    /// First delivery date the zone is valid for, if it replaced another.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<NaiveDate>,
+    /// First delivery date the zone is no longer valid for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_to: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub predecessor_code: Option<String>,
+}
+
+impl From<&BiddingZone> for ZoneInfo {
+    fn from(z: &BiddingZone) -> Self {
+        Self {
+            zone_code: z.zone_code.clone(),
+            zone_name: z.zone_name.clone(),
+            country_code: z.country_code.clone(),
+            country_name: z.country_name.clone(),
+            localized_zone_name: None,
+            localized_country_name: None,
+            eic_code: z.eic_code.clone(),
+            timezone: z.timezone.clone(),
+            active: z.active,
+            valid_from: z.valid_from,
+            valid_to: z.valid_to,
+            predecessor_code: z.predecessor_code.clone(),
+        }
+    }
+}
+
+impl ZoneInfo {
+    pub fn localize(mut self, translations: &Translations) -> Self {
+        self.localized_zone_name = translations.zone(&self.zone_code);
+        self.localized_country_name = translations.country(&self.country_code);
+        self
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZonesResponse {
+    pub zones: Vec<ZoneInfo>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CountryInfo {
+    pub country_code: String,
+    pub country_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_country_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CountriesResponse {
+    pub countries: Vec<CountryInfo>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HealthResponse {
+    pub status: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReadyResponse {
+    pub status: String,
+    pub database: String,
+    pub active_zones: usize,
+    /// Zones with today's prices; only reported while the readiness gate is closed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zones_with_today_data: Option<usize>,
+    pub checks: ReadinessChecks,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Dependencies `/ready` checks besides the database. Any failing check
+/// makes the replica report `degraded`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReadinessChecks {
+    pub scheduler: SchedulerCheck,
+    pub last_fetch: FetchAgeCheck,
+    /// `null` unless `readiness.probe_entsoe` is enabled.
+    pub entsoe: Option<EntsoeCheck>,
+}
+
+impl ReadinessChecks {
+    pub fn passed(&self) -> bool {
+        self.scheduler.status != "stopped"
+            && self.last_fetch.status != "stale"
+            && self.entsoe.as_ref().is_none_or(|entsoe| entsoe.status == "ok")
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SchedulerCheck {
+    /// `ok`, `stopped`, or `disabled` when this instance runs without a scheduler.
+    pub status: String,
+    pub running: bool,
+    pub jobs: Vec<JobSchedule>,
+}
+
+impl SchedulerCheck {
+    pub fn new(history: Option<&JobHistory>, now: DateTime<Utc>) -> Self {
+        let Some(history) = history else {
+            return Self {
+                status: "disabled".to_string(),
+                running: false,
+                jobs: Vec::new(),
+            };
+        };
+        let running = history.is_running();
+        Self {
+            status: if running { "ok" } else { "stopped" }.to_string(),
+            running,
+            jobs: history.next_runs(now),
+        }
+    }
+}
+
+/// Age of the newest successful ENTSOE fetch across all zones.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FetchAgeCheck {
+    /// `ok`, `stale` once older than `max_age_seconds`, `unknown` before the
+    /// first successful fetch, or `disabled` when no maximum age is configured.
+    pub status: String,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub age_seconds: Option<i64>,
+    pub max_age_seconds: Option<u64>,
+}
+
+impl FetchAgeCheck {
+    pub fn new(last_success_at: Option<DateTime<Utc>>, max_age_hours: Option<u64>, now: DateTime<Utc>) -> Self {
+        let age_seconds = last_success_at.map(|at| (now - at).num_seconds());
+        let max_age_seconds = max_age_hours.map(|hours| hours * 3600);
+        let status = match (age_seconds, max_age_seconds) {
+            (_, None) => "disabled",
+            (None, Some(_)) => "unknown",
+            (Some(age), Some(max)) if age > max as i64 => "stale",
+            (Some(_), Some(_)) => "ok",
+        };
+        Self {
+            status: status.to_string(),
+            last_success_at,
+            age_seconds,
+            max_age_seconds,
+        }
+    }
+}
+
+/// Outcome of a request to the primary ENTSOE endpoint.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct EntsoeCheck {
+    /// `ok` or `unreachable`.
+    pub status: String,
+    pub endpoint: String,
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl EntsoeCheck {
+    pub fn new(endpoint: &str, result: Result<std::time::Duration, EntsoeError>) -> Self {
+        let (status, latency_ms, error) = match result {
+            Ok(latency) => ("ok", Some(latency.as_millis() as u64), None),
+            Err(e) => ("unreachable", None, Some(e.to_string())),
+        };
+        Self {
+            status: status.to_string(),
+            endpoint: endpoint.to_string(),
+            latency_ms,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimezoneQuery {
+    pub timezone: Option<String>,
+}
+
+/// The caller's usage of the API rate limit, including this request.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LimitsResponse {
+    pub enabled: bool,
+    /// `null` when rate limiting is disabled.
+    pub current: Option<RateLimitStatus>,
+}
+
+/// Everything an operator checks in one document: scheduler jobs, database
+/// pool, cache, ENTSOE circuit breaker and each zone's price coverage and
+/// latest fetch.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct StatusResponse {
+    /// `ok`, or `degraded` when the database is unreachable, the circuit
+    /// breaker is not closed or a job's latest run failed.
+    pub status: String,
+    pub scheduler: SchedulerState,
+    pub database: DatabaseState,
+    /// `null` when the price cache is disabled.
+    pub cache: Option<CacheStats>,
+    /// `null` when this instance runs without a fetcher.
+    pub circuit_breaker: Option<CircuitBreakerStatus>,
+    pub zones: Vec<ZoneFreshness>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SchedulerState {
+    pub enabled: bool,
+    pub jobs: Vec<JobStatus>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DatabaseState {
+    /// `connected` or `error`.
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub active_connections: u32,
+    pub idle_connections: u32,
+    pub max_connections: u32,
+}
+
+impl DatabaseState {
+    /// Reports a failed health check by its error code's description only,
+    /// as the storage error itself can name hosts, tables or SQL.
+    pub fn new(pool: PoolStatus, error: Option<&StorageError>) -> Self {
+        let error = error.map(|e| {
+            let code = if e.is_connection_error() {
+                ErrorCode::DatabaseUnavailable
+            } else {
+                ErrorCode::DatabaseError
+            };
+            code.description().to_string()
+        });
+        Self {
+            status: if error.is_none() { "connected" } else { "error" }.to_string(),
+            error,
+            active_connections: pool.active_connections,
+            idle_connections: pool.idle_connections,
+            max_connections: pool.max_connections,
+        }
+    }
+}
+
+/// Newest stored day-ahead price for a zone, with its recent coverage and
+/// latest fetch.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZoneFreshness {
+    pub zone_code: String,
+    pub latest_price_at: Option<DateTime<Utc>>,
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// Whole hours from now until the end of the newest price; negative once
+    /// the zone has run out of prices.
+    pub hours_ahead: Option<i64>,
+    /// Every hour of tomorrow (UTC) has a day-ahead price.
+    pub tomorrow_available: bool,
+    /// Days among the last 30 up to today (UTC) missing any hourly price.
+    pub gap_days_last_30: usize,
+    /// `null` when the zone has never been fetched.
+    pub last_fetch: Option<ZoneLastFetch>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZoneLastFetch {
+    /// `pending`, `success`, `nodata`, `error` or `ratelimited`.
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    /// Summary of why the fetch failed: the HTTP status ENTSOE answered with
+    /// when known. The full error is only in the admin fetch history.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Days [`ZoneFreshness::gap_days_last_30`] looks back over, including today.
+pub const STATUS_GAP_DAYS: i64 = 30;
+
+impl ZoneFreshness {
+    /// One entry per zone from its latest price, the gaps `find_gaps`
+    /// reported from [`STATUS_GAP_DAYS`] ago up to tomorrow, and the
+    /// latest fetch logs.
+    pub fn for_zones(
+        zones: &[BiddingZone],
+        latest: Vec<Price>,
+        gaps: &[DayGap],
+        fetch_logs: Vec<FetchLog>,
+        now: DateTime<Utc>,
+    ) -> Vec<Self> {
+        let today = now.date_naive();
+        let mut latest: HashMap<String, Price> = latest.into_iter().map(|p| (p.bidding_zone.clone(), p)).collect();
+        let mut fetch_logs: HashMap<String, FetchLog> = fetch_logs
+            .into_iter()
+            .filter_map(|log| Some((log.bidding_zone.clone()?, log)))
+            .collect();
+        zones
+            .iter()
+            .map(|zone| {
+                let price = latest.remove(&zone.zone_code);
+                let zone_gaps = || gaps.iter().filter(|gap| gap.zone_code == zone.zone_code);
+                Self {
+                    zone_code: zone.zone_code.clone(),
+                    latest_price_at: price.as_ref().map(|p| p.timestamp),
+                    fetched_at: price.as_ref().map(|p| p.fetched_at),
+                    hours_ahead: price.as_ref().map(|p| (p.timestamp + Duration::hours(1) - now).num_hours()),
+                    tomorrow_available: !zone_gaps().any(|gap| gap.date > today),
+                    gap_days_last_30: zone_gaps().filter(|gap| gap.date <= today).count(),
+                    last_fetch: fetch_logs.remove(&zone.zone_code).map(|log| ZoneLastFetch {
+                        status: log.status.as_str().to_string(),
+                        started_at: log.fetch_started_at,
+                        error: log.error_message.as_ref().map(|_| match log.http_status {
+                            Some(http_status) => format!("ENTSOE answered with HTTP {}", http_status),
+                            None => "Fetch failed".to_string(),
+                        }),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+impl StatusResponse {
+    pub fn new(
+        scheduler: SchedulerState,
+        database: DatabaseState,
+        cache: Option<CacheStats>,
+        circuit_breaker: Option<CircuitBreakerStatus>,
+        zones: Vec<ZoneFreshness>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let job_failed = scheduler
+            .jobs
+            .iter()
+            .any(|job| job.last_run.as_ref().is_some_and(|run| run.status != "success"));
+        let circuit_open = circuit_breaker
+            .as_ref()
+            .is_some_and(|breaker| breaker.state != CircuitState::Closed);
+        let degraded = database.error.is_some() || circuit_open || job_failed;
+
+        Self {
+            status: if degraded { "degraded" } else { "ok" }.to_string(),
+            scheduler,
+            database,
+            cache,
+            circuit_breaker,
+            zones,
+            timestamp: now,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LangQuery {
+    /// Locale for `localized_*` names: `nb`, `sv` or `de`.
+    pub lang: Option<String>,
+}
+
+impl LangQuery {
+    pub fn parse(&self) -> Result<Option<&str>, String> {
+        match self.lang.as_deref() {
+            Some(lang) if SUPPORTED_LOCALES.contains(&lang) => Ok(Some(lang)),
+            Some(lang) => Err(format!("Invalid lang: {}. Use nb, sv or de.", lang)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IncludeQuery {
+    /// Comma-separated optional fields; `consumer_price` is supported, and
+    /// `stats` and `zones` on country responses.
+    pub include: Option<String>,
+}
+
+/// Sections of a country price response, chosen with `?include=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountrySections {
+    /// The per-zone price series.
+    pub zones: bool,
+    /// A price summary per zone.
+    pub stats: bool,
+}
+
+impl IncludeQuery {
+    /// Requested values, rejecting any outside `accepted`.
+    fn parse(&self, accepted: &[&str]) -> Result<Vec<&str>, String> {
+        let mut included = Vec::new();
+        for field in self.include.iter().flat_map(|include| include.split(',')).map(str::trim) {
+            match field {
+                "" => {}
+                field if accepted.contains(&field) => included.push(field),
+                other => return Err(format!("Invalid include: {}. Use {}.", other, accepted.join(", "))),
+            }
+        }
+        Ok(included)
+    }
+
+    /// Whether `consumer_price` was requested.
+    pub fn consumer_price(&self) -> Result<bool, String> {
+        Ok(self.parse(&["consumer_price"])?.contains(&"consumer_price"))
+    }
+
+    /// Whether `consumer_price` was requested, and the country sections to
+    /// serve: the per-zone series alone unless `include` names sections.
+    pub fn country(&self) -> Result<(bool, CountrySections), String> {
+        let included = self.parse(&["consumer_price", "stats", "zones"])?;
+        let stats = included.contains(&"stats");
+        let sections = CountrySections {
+            zones: included.contains(&"zones") || !stats,
+            stats,
+        };
+        Ok((included.contains(&"consumer_price"), sections))
+    }
+
+    /// Whether `include` names `value`, whether or not it is accepted.
+    pub fn names(&self, value: &str) -> bool {
+        self.include.iter().flat_map(|include| include.split(',')).any(|field| field.trim() == value)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FieldsQuery {
+    /// Comma-separated price point fields to keep, e.g. `timestamp,price`.
+    pub fields: Option<String>,
+}
+
+impl FieldsQuery {
+    /// Price point fields of a `shape` response to keep, `None` for whole
+    /// responses.
+    pub fn parse(&self, shape: PointShape) -> Result<Option<PriceFields>, String> {
+        PriceFields::parse(self.fields.as_deref(), shape)
+    }
+}
+
+/// Line format of a streamed price export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One [`PricePoint`] JSON object per line.
+    Ndjson,
+    /// InfluxDB line protocol, one point per line.
+    Influx,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Influx => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportFormatQuery {
+    /// `ndjson` (default) or `influx` line protocol.
+    pub format: Option<String>,
+}
+
+impl ExportFormatQuery {
+    pub fn parse(&self) -> Result<ExportFormat, String> {
+        match self.format.as_deref() {
+            Some("ndjson") | None => Ok(ExportFormat::Ndjson),
+            Some("influx") => Ok(ExportFormat::Influx),
+            Some(other) => Err(format!("Invalid format: {}. Use ndjson or influx.", other)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnitQuery {
+    /// Energy unit prices are quoted per: `kwh` (default) or `mwh`.
+    pub unit: Option<String>,
+}
+
+impl UnitQuery {
+    pub fn parse(&self) -> Result<EnergyUnit, String> {
+        match self.unit.as_deref() {
+            Some(unit @ ("kwh" | "mwh")) => Ok(EnergyUnit::from_code(unit).unwrap()),
+            Some(other) => Err(format!("Invalid unit: {}. Use kwh or mwh.", other)),
+            None => Ok(EnergyUnit::KilowattHour),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ThresholdQuery {
+    /// Price per `unit` below which an hour is reported; defaults to 0.
+    pub threshold: Option<String>,
+}
+
+impl ThresholdQuery {
+    /// The threshold per kWh, given in `unit`.
+    pub fn parse(&self, unit: EnergyUnit) -> Result<Decimal, String> {
+        match self.threshold.as_deref() {
+            Some(threshold) => threshold.parse().map(|threshold| unit.to_kwh(threshold)).map_err(|_| {
+                format!(
+                    "Invalid threshold: {}. Use a price per {} in the zone's currency, e.g. {}.",
+                    threshold,
+                    unit.label(),
+                    unit.from_kwh(Decimal::new(1, 2))
+                )
+            }),
+            None => Ok(Decimal::ZERO),
+        }
+    }
+}
+
+/// Zone and country display names in one locale, keyed by code. Empty when
+/// no `?lang=` was given, so `localize` leaves every name unset.
+#[derive(Debug, Default)]
+pub struct Translations {
+    zones: HashMap<String, String>,
+    countries: HashMap<String, String>,
+}
+
+impl Translations {
+    pub fn new(rows: Vec<NameTranslation>) -> Self {
+        let mut translations = Self::default();
+        for row in rows {
+            match row.kind {
+                NameKind::Zone => translations.zones.insert(row.code, row.name),
+                NameKind::Country => translations.countries.insert(row.code, row.name),
+            };
+        }
+        translations
+    }
+
+    pub fn zone(&self, zone_code: &str) -> Option<String> {
+        self.zones.get(zone_code).cloned()
+    }
+
+    pub fn country(&self, country_code: &str) -> Option<String> {
+        self.countries.get(country_code).cloned()
+    }
+}
+
+/// Cross-border flows into and out of one zone, one entry per neighbouring zone.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FlowsResponse {
+    pub zone_code: String,
+    pub zone_name: String,
+    pub timezone: String,
+    pub kind: FlowKind,
+    pub unit: String,
+    pub borders: Vec<FlowBorder>,
+    pub fetched_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RangeMeta>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FlowBorder {
+    pub neighbour: String,
+    pub points: Vec<FlowPoint>,
+}
+
+/// Flows across one border for one interval. A direction with no stored
+/// value counts as zero.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FlowPoint {
+    pub timestamp: String,
+    pub timestamp_utc: DateTime<Utc>,
+    /// Flow from the neighbour into the zone.
+    pub import: Decimal,
+    /// Flow from the zone into the neighbour.
+    pub export: Decimal,
+    /// `import - export`; negative when the zone is a net exporter.
+    pub net_import: Decimal,
+}
+
+impl FlowsResponse {
+    /// Pair up both directions of each border in `flows` (rows into or out of
+    /// `zone`), sorted by neighbour and timestamp.
+    pub fn new(zone: &BiddingZone, kind: FlowKind, flows: Vec<CrossBorderFlow>, timezone: Option<&str>) -> Self {
+        let tz: Tz = timezone
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+
+        let mut by_border: std::collections::BTreeMap<String, std::collections::BTreeMap<DateTime<Utc>, (Decimal, Decimal)>> =
+            Default::default();
+        for flow in flows {
+            let (neighbour, is_import) = if flow.to_zone == zone.zone_code {
+                (flow.from_zone, true)
+            } else {
+                (flow.to_zone, false)
+            };
+            let entry = by_border.entry(neighbour).or_default().entry(flow.timestamp).or_default();
+            if is_import {
+                entry.0 = flow.quantity_mw;
+            } else {
+                entry.1 = flow.quantity_mw;
+            }
+        }
+
+        Self {
+            zone_code: zone.zone_code.clone(),
+            zone_name: zone.zone_name.clone(),
+            timezone: tz.to_string(),
+            kind,
+            unit: "MW".to_string(),
+            borders: by_border
+                .into_iter()
+                .map(|(neighbour, points)| FlowBorder {
+                    neighbour,
+                    points: points
+                        .into_iter()
+                        .map(|(timestamp, (import, export))| FlowPoint {
+                            timestamp: timestamp.with_timezone(&tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                            timestamp_utc: timestamp,
+                            import,
+                            export,
+                            net_import: import - export,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            fetched_at: Utc::now(),
+            meta: None,
+        }
+    }
+
+    pub fn with_defaults(mut self, defaults: Option<AppliedRangeDefaults>) -> Self {
+        self.meta = RangeMeta::with_defaults(defaults);
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlowKindQuery {
+    /// `physical` (default) or `scheduled`.
+    pub kind: Option<String>,
+}
+
+impl FlowKindQuery {
+    pub fn parse(&self) -> Result<FlowKind, String> {
+        match &self.kind {
+            Some(k) => FlowKind::parse(k).ok_or_else(|| format!("Invalid flow kind: {}. Use physical or scheduled.", k)),
+            None => Ok(FlowKind::default()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DateRangeQuery {
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub timezone: Option<String>,
+    /// Narrow the range to the data actually stored for each zone and report
+    /// the adjustment in `meta.clamp`.
+    #[serde(default)]
+    pub clamp: bool,
+    /// `day_ahead` (default) or `intraday`.
+    pub market: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FetchResponse {
+    pub status: String,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub no_data: usize,
+    pub total_prices_stored: usize,
+    pub errors: Vec<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ZoneFetchQuery {
+    /// Delivery date (YYYY-MM-DD); defaults to today (UTC).
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZoneFetchResponse {
+    pub fetch_id: i64,
+    pub zone_code: String,
+    pub date: String,
+    pub status: String,
+    pub prices_stored: usize,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EicFetchQuery {
+    /// Delivery date (YYYY-MM-DD); defaults to today (UTC).
+    pub date: Option<String>,
+    /// `day_ahead` (default) or `intraday`.
+    pub market: Option<String>,
+    /// Timezone of the delivery day; defaults to CET (`Europe/Brussels`).
+    pub timezone: Option<String>,
+}
+
+/// Prices fetched for a raw EIC code. Nothing is stored.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct EicFetchResponse {
+    pub eic_code: String,
+    /// Registry zone with this EIC code, if one exists.
+    pub registered_zone: Option<String>,
+    pub date: String,
+    pub market: Market,
+    pub timezone: String,
+    pub currency: String,
+    pub unit: String,
+    pub prices: Vec<PricePoint>,
+    pub duration_ms: u64,
+}
+
+/// Longest date range `POST /admin/backfill` fills while holding the
+/// request open.
+pub const MAX_BACKFILL_DAYS: i64 = 366;
+
+/// Longest date range one backfill job covers.
+pub const MAX_BACKFILL_JOB_DAYS: i64 = 3660;
+
+#[derive(Debug, Deserialize)]
+pub struct BackfillRequest {
+    pub start: String,
+    pub end: String,
+    pub zones: Option<Vec<String>>,
+}
+
+impl BackfillRequest {
+    /// The inclusive date range, at most `max_days` long.
+    pub fn date_range(&self, max_days: i64) -> Result<(NaiveDate, NaiveDate), AppError> {
+        let start = NaiveDate::parse_from_str(&self.start, "%Y-%m-%d")
+            .map_err(|e| AppError::BadRequest(format!("Invalid start date: {}. Use YYYY-MM-DD format.", e)))?;
+        let end = NaiveDate::parse_from_str(&self.end, "%Y-%m-%d")
+            .map_err(|e| AppError::BadRequest(format!("Invalid end date: {}. Use YYYY-MM-DD format.", e)))?;
+        if start > end {
+            return Err(AppError::invalid_date_range("Start date must be before or equal to end date"));
+        }
+        let days = (end - start).num_days() + 1;
+        if days > max_days {
+            return Err(AppError::invalid_date_range(format!(
+                "range covers {} days; at most {} can be backfilled",
+                days, max_days
+            )));
+        }
+        Ok((start, end))
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GapInfo {
+    pub date: String,
+    pub zone: String,
+    pub missing_hours: i32,
+}
+
+/// A day whose gap-filled prices were re-fetched.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct EstimatedHoursInfo {
+    pub date: String,
+    pub zone: String,
+    pub estimated_hours: i32,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BackfillResponse {
+    pub status: String,
+    pub dates_checked: usize,
+    pub dates_with_gaps: usize,
+    pub prices_fetched: usize,
+    pub prices_stored: usize,
+    pub gaps_found: Vec<GapInfo>,
+    pub estimated_found: Vec<EstimatedHoursInfo>,
+    pub errors: Vec<String>,
+    pub duration_ms: u64,
+}
+
+/// A backfill job and its progress so far.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BackfillJobInfo {
+    pub id: i64,
+    pub status: BackfillJobStatus,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Zones backfilled; absent for every active zone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zones: Option<Vec<String>>,
+    pub dates_total: i32,
+    pub dates_done: i32,
+    pub prices_stored: i64,
+    pub errors: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<BackfillJob> for BackfillJobInfo {
+    fn from(job: BackfillJob) -> Self {
+        Self {
+            id: job.id,
+            status: job.status,
+            start: job.start_date,
+            end: job.end_date,
+            zones: job.zones,
+            dates_total: job.dates_total,
+            dates_done: job.dates_done,
+            prices_stored: job.prices_stored,
+            errors: job.errors,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            finished_at: job.finished_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TasksQuery {
+    /// `queued`, `running`, `succeeded` or `failed`; every status when omitted.
+    pub status: Option<String>,
+    /// Defaults to 100, capped at 1000.
+    pub limit: Option<i64>,
+}
+
+impl TasksQuery {
+    /// The status to list, if any, and the page size.
+    pub fn parse(&self) -> Result<(Option<TaskStatus>, i64), AppError> {
+        let status = self
+            .status
+            .as_deref()
+            .map(|status| {
+                TaskStatus::parse(status).ok_or_else(|| {
+                    AppError::invalid_parameter(
+                        "status",
+                        format!("Invalid status: {}. Use queued, running, succeeded or failed.", status),
+                    )
+                })
+            })
+            .transpose()?;
+        Ok((status, self.limit.unwrap_or(100).clamp(1, 1000)))
+    }
+}
+
+/// Tasks in the background task queue, newest first, with the number of
+/// tasks in each status.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TasksResponse {
+    pub counts: BTreeMap<String, i64>,
+    pub tasks: Vec<TaskInfo>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TaskInfo {
+    pub id: i64,
+    pub kind: String,
+    pub status: TaskStatus,
+    /// Attempts started so far, including a running one.
+    pub attempts: i32,
+    pub max_attempts: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// When a queued task may run; its next retry after a failed attempt.
+    pub run_after: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<Task> for TaskInfo {
+    fn from(task: Task) -> Self {
+        Self {
+            id: task.id,
+            kind: task.kind,
+            status: task.status,
+            attempts: task.attempts,
+            max_attempts: task.max_attempts,
+            last_error: task.last_error,
+            run_after: task.run_after,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            finished_at: task.finished_at,
+        }
+    }
+}
+
+/// Longest date range `/admin/gaps` checks in one request.
+pub const MAX_GAP_REPORT_DAYS: i64 = 366;
+
+#[derive(Debug, Deserialize)]
+pub struct GapReportQuery {
+    /// First delivery date to check (YYYY-MM-DD).
+    pub start: Option<String>,
+    /// Last delivery date to check (YYYY-MM-DD), inclusive.
+    pub end: Option<String>,
+    /// Comma-separated zone codes; all active zones when omitted.
+    pub zones: Option<String>,
+}
+
+impl GapReportQuery {
+    /// The inclusive date range, at most `MAX_GAP_REPORT_DAYS` long.
+    pub fn date_range(&self) -> Result<(NaiveDate, NaiveDate), AppError> {
+        let parse = |name: &'static str, value: Option<&String>| {
+            let value = value.ok_or_else(|| AppError::invalid_parameter(name, format!("{} is required (YYYY-MM-DD)", name)))?;
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| {
+                AppError::invalid_parameter(name, format!("Invalid {} date '{}': {}. Use YYYY-MM-DD format.", name, value, e))
+            })
+        };
+        let start = parse("start", self.start.as_ref())?;
+        let end = parse("end", self.end.as_ref())?;
+        if start > end {
+            return Err(AppError::invalid_date_range("start must be before or equal to end"));
+        }
+        let days = (end - start).num_days() + 1;
+        if days > MAX_GAP_REPORT_DAYS {
+            return Err(AppError::invalid_date_range(format!(
+                "range covers {} days; at most {} can be checked",
+                days, MAX_GAP_REPORT_DAYS
+            )));
+        }
+        Ok((start, end))
+    }
+
+    /// Requested zone codes, uppercased and without duplicates; empty means all.
+    pub fn zones(&self) -> Vec<String> {
+        let mut zones: Vec<String> = Vec::new();
+        for zone in self.zones.iter().flat_map(|zones| zones.split(',')).map(str::trim) {
+            let zone = zone.to_uppercase();
+            if !zone.is_empty() && !zones.contains(&zone) {
+                zones.push(zone);
+            }
+        }
+        zones
+    }
+}
+
+/// A zone's delivery day, in its local time, with fewer hourly prices than
+/// the day has hours.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ZoneDayGap {
+    pub date: NaiveDate,
+    pub zone_code: String,
+    pub hours: i64,
+    /// 24, or 23 or 25 on the days clocks change.
+    pub expected_hours: i64,
+    pub missing_hours: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GapReportResponse {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub zones: Vec<String>,
+    /// Hours in a day without a clock change. Each gap carries the hours of
+    /// its own day.
+    pub expected_hours: i64,
+    pub days_checked: i64,
+    /// Ordered by date, then zone.
+    pub gaps: Vec<ZoneDayGap>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl GapReportResponse {
+    pub fn new(
+        (start, end): (NaiveDate, NaiveDate),
+        zones: Vec<String>,
+        gaps: Vec<DayGap>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            start,
+            end,
+            zones,
+            expected_hours: 24,
+            days_checked: (end - start).num_days() + 1,
+            gaps: gaps
+                .into_iter()
+                .map(|gap| ZoneDayGap {
+                    missing_hours: gap.missing_hours(),
+                    date: gap.date,
+                    zone_code: gap.zone_code,
+                    hours: gap.hours,
+                    expected_hours: gap.expected_hours,
+                })
+                .collect(),
+            timestamp: now,
+        }
+    }
+}
+
+/// A registered scheduler job with its latest run and next fire time.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SchedulerJobInfo {
+    pub name: String,
+    pub cron: String,
+    pub paused: bool,
+    pub last_run: Option<JobRun>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// Fired even while paused; a paused job skips the run.
+    pub next_fire_at: Option<DateTime<Utc>>,
+}
+
+impl SchedulerJobInfo {
+    pub fn new(job: JobStatus, now: DateTime<Utc>) -> Self {
+        let next_fire_at = job.next_fire_at(now);
+        Self {
+            name: job.name,
+            cron: job.cron,
+            paused: job.paused,
+            last_run: job.last_run,
+            last_success_at: job.last_success_at,
+            next_fire_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SchedulerJobsResponse {
+    /// `false` when this instance runs without a scheduler.
+    pub enabled: bool,
+    pub running: bool,
+    /// In registration order.
+    pub jobs: Vec<SchedulerJobInfo>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl SchedulerJobsResponse {
+    pub fn new(history: Option<&JobHistory>, now: DateTime<Utc>) -> Self {
+        Self {
+            enabled: history.is_some(),
+            running: history.is_some_and(JobHistory::is_running),
+            jobs: history
+                .map(|history| history.jobs().into_iter().map(|job| SchedulerJobInfo::new(job, now)).collect())
+                .unwrap_or_default(),
+            timestamp: now,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MetricCatalogEntry {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub unit: Option<&'static str>,
+    pub labels: &'static [&'static str],
+    pub description: &'static str,
+}
+
+impl From<&MetricDescription> for MetricCatalogEntry {
+    fn from(metric: &MetricDescription) -> Self {
+        Self {
+            name: metric.name,
+            kind: metric.kind.as_str(),
+            unit: metric.unit.map(|unit| unit.as_str()),
+            labels: metric.labels,
+            description: metric.description,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MetricCatalogResponse {
+    pub metrics: Vec<MetricCatalogEntry>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ErrorCatalogEntry {
+    pub code: &'static str,
+    /// HTTP status responses with this code are sent with.
+    pub status: u16,
+    pub description: &'static str,
+}
+
+impl From<ErrorCode> for ErrorCatalogEntry {
+    fn from(code: ErrorCode) -> Self {
+        Self {
+            code: code.as_str(),
+            status: code.status().as_u16(),
+            description: code.description(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ErrorCatalogResponse {
+    pub errors: Vec<ErrorCatalogEntry>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TableStatsInfo {
+    pub table: String,
+    pub row_count: i64,
+    pub table_bytes: Option<i64>,
+    pub index_bytes: Option<i64>,
+    pub oldest_entry: Option<DateTime<Utc>>,
+}
+
+impl From<TableStats> for TableStatsInfo {
+    fn from(stats: TableStats) -> Self {
+        Self {
+            table: stats.table_name,
+            row_count: stats.row_count,
+            table_bytes: stats.table_bytes,
+            index_bytes: stats.index_bytes,
+            oldest_entry: stats.oldest_entry,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RetentionTableStatus {
+    pub table: String,
+    pub retention_days: u32,
+    pub cutoff: DateTime<Utc>,
+    /// Rows older than the cutoff are still stored, i.e. cleanup has not run
+    /// since they aged out (or retention is disabled).
+    pub overdue: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RetentionStatus {
+    pub enabled: bool,
+    pub cleanup_cron: String,
+    pub tables: Vec<RetentionTableStatus>,
+}
+
+impl RetentionStatus {
+    pub fn new(config: &RetentionConfig, tables: &[TableStats], now: DateTime<Utc>) -> Self {
+        let status = |table: &str, retention_days: u32| {
+            let cutoff = now - Duration::days(retention_days as i64);
+            let oldest = tables
+                .iter()
+                .find(|t| t.table_name == table)
+                .and_then(|t| t.oldest_entry);
+            RetentionTableStatus {
+                table: table.to_string(),
+                retention_days,
+                cutoff,
+                overdue: oldest.is_some_and(|oldest| oldest < cutoff),
+            }
+        };
+
+        Self {
+            enabled: config.enabled,
+            cleanup_cron: config.cleanup_cron.clone(),
+            tables: vec![
+                status("electricity_prices", config.price_retention_days),
+                status("fetch_log", config.fetch_log_retention_days),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct StorageStatsResponse {
+    pub tables: Vec<TableStatsInfo>,
+    /// Present when the retention configuration is known to the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retention: Option<RetentionStatus>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PruneFetchLogRequest {
+    pub older_than_days: u32,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PruneFetchLogResponse {
+    pub deleted: u64,
+    pub cutoff: DateTime<Utc>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchLogQuery {
+    /// Only this zone's logs.
+    pub zone: Option<String>,
+    /// `pending`, `success`, `nodata`, `error` or `ratelimited`.
+    pub status: Option<String>,
+    /// Logs started at or after this (RFC3339).
+    pub start: Option<String>,
+    /// Logs started before this (RFC3339).
+    pub end: Option<String>,
+    /// Defaults to 100, capped at 1000.
+    pub limit: Option<i64>,
+    /// Logs to skip.
+    pub offset: Option<i64>,
+    /// `next_cursor` of the previous page, instead of `offset`.
+    pub cursor: Option<String>,
+    /// Only fetches triggered by the request with this `X-Correlation-Id`.
+    pub correlation_id: Option<String>,
+}
+
+impl FetchLogQuery {
+    /// The filter, page and page size requested.
+    pub fn parse(&self) -> Result<(FetchLogFilter, FetchLogPage, i64), AppError> {
+        let time = |name: &'static str, value: Option<&String>| {
+            value
+                .map(|value| {
+                    DateTime::parse_from_rfc3339(value).map(|dt| dt.with_timezone(&Utc)).map_err(|e| {
+                        AppError::invalid_parameter(name, format!("Invalid {} date format: {}. Use ISO8601/RFC3339.", name, e))
+                    })
+                })
+                .transpose()
+        };
+        let filter = FetchLogFilter {
+            zone_code: self.zone.as_deref().map(|zone| zone.trim().to_uppercase()),
+            status: self
+                .status
+                .as_deref()
+                .map(|status| {
+                    FetchStatus::parse(status).ok_or_else(|| {
+                        AppError::invalid_parameter(
+                            "status",
+                            format!("Invalid status: {}. Use pending, success, nodata, error or ratelimited.", status),
+                        )
+                    })
+                })
+                .transpose()?,
+            started_from: time("start", self.start.as_ref())?,
+            started_before: time("end", self.end.as_ref())?,
+            correlation_id: self.correlation_id.clone(),
+        };
+        if let (Some(start), Some(end)) = (filter.started_from, filter.started_before) {
+            if start >= end {
+                return Err(AppError::invalid_date_range("Start date must be before end date"));
+            }
+        }
+
+        let page = match (self.offset, self.cursor.as_deref()) {
+            (Some(_), Some(_)) => return Err(AppError::invalid_parameter("cursor", "offset and cursor cannot be combined")),
+            (Some(offset), None) if offset < 0 => {
+                return Err(AppError::invalid_parameter("offset", "offset must not be negative"))
+            }
+            (Some(offset), None) => FetchLogPage::Offset(offset),
+            (None, Some(cursor)) => parse_fetch_log_cursor(cursor)
+                .ok_or_else(|| AppError::invalid_parameter("cursor", format!("Invalid cursor: {}", cursor)))?,
+            (None, None) => FetchLogPage::Offset(0),
+        };
+        Ok((filter, page, self.limit.unwrap_or(100).clamp(1, 1000)))
+    }
+}
+
+/// Cursor continuing a listing after `log`: its start in nanoseconds and
+/// its id, which together order the fetch log.
+fn fetch_log_cursor(log: &FetchLog) -> String {
+    format!("{}.{}", log.fetch_started_at.timestamp_nanos_opt().unwrap_or_default(), log.id)
+}
+
+fn parse_fetch_log_cursor(cursor: &str) -> Option<FetchLogPage> {
+    let (nanos, id) = cursor.split_once('.')?;
+    Some(FetchLogPage::After {
+        started_at: DateTime::from_timestamp_nanos(nanos.parse().ok()?),
+        id: id.parse().ok()?,
+    })
+}
+
+/// A page of the fetch log, newest first.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FetchLogResponse {
+    /// Logs matching the filters across all pages.
+    pub total: i64,
+    pub limit: i64,
+    pub logs: Vec<FetchLogEntry>,
+    /// Pass as `cursor` for the next page; absent on the last one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FetchLogEntry {
+    pub id: i64,
+    /// Absent for the summary log of a full fetch run.
+    pub zone_code: Option<String>,
+    /// `pending`, `success`, `nodata`, `error` or `ratelimited`.
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub records_inserted: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub http_status: Option<i32>,
+    pub duration_ms: Option<i32>,
+    /// ENTSOE base URL that served the fetch.
+    pub endpoint: Option<String>,
+    /// `X-Correlation-Id` of the API request that triggered the fetch;
+    /// absent for scheduled fetches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+impl From<FetchLog> for FetchLogEntry {
+    fn from(log: FetchLog) -> Self {
+        Self {
+            id: log.id,
+            zone_code: log.bidding_zone,
+            status: log.status.as_str().to_string(),
+            started_at: log.fetch_started_at,
+            completed_at: log.fetch_completed_at,
+            period_start: log.period_start,
+            period_end: log.period_end,
+            records_inserted: log.records_inserted,
+            error: log.error_message,
+            http_status: log.http_status,
+            duration_ms: log.duration_ms,
+            endpoint: log.endpoint,
+            correlation_id: log.correlation_id,
+        }
+    }
+}
+
+impl FetchLogResponse {
+    /// `logs` holds up to `limit + 1` entries; one past the page means a
+    /// next page follows.
+    pub fn new(mut logs: Vec<FetchLog>, total: i64, limit: i64) -> Self {
+        let next_cursor = (logs.len() as i64 > limit).then(|| {
+            logs.truncate(limit as usize);
+            fetch_log_cursor(&logs[logs.len() - 1])
+        });
+        Self {
+            total,
+            limit,
+            logs: logs.into_iter().map(FetchLogEntry::from).collect(),
+            next_cursor,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CacheInvalidateQuery {
+    /// Only this zone's entries; all zones when omitted.
+    pub zone: Option<String>,
+    /// Only entries covering this UTC date (YYYY-MM-DD); all dates when omitted.
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CacheInvalidateResponse {
+    pub zone_code: Option<String>,
+    pub date: Option<NaiveDate>,
+    pub invalidated: CacheInvalidation,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAlertRuleRequest {
+    pub zone_code: String,
+    pub comparison: AlertComparison,
+    /// Per kWh, in the currency the zone is priced in.
+    pub threshold: Decimal,
+    pub channel: AlertChannel,
+    /// Webhook URL, MQTT topic or email address, depending on `channel`.
+    pub target: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AlertRuleInfo {
+    pub id: i64,
+    pub zone_code: String,
+    pub comparison: AlertComparison,
+    /// Per kWh, in the currency the zone is priced in.
+    pub threshold: Decimal,
+    pub channel: AlertChannel,
+    pub target: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AlertRule> for AlertRuleInfo {
+    fn from(rule: AlertRule) -> Self {
+        Self {
+            id: rule.id,
+            zone_code: rule.zone_code,
+            comparison: rule.comparison,
+            threshold: rule.threshold,
+            channel: rule.channel,
+            target: rule.target,
+            created_at: rule.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AlertRulesResponse {
+    pub rules: Vec<AlertRuleInfo>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AlertEventInfo {
+    pub price_timestamp: DateTime<Utc>,
+    /// Per kWh, in the currency the zone is priced in.
+    pub price: Decimal,
+    pub fired_at: DateTime<Utc>,
+    /// `null` while delivery is in progress.
+    pub delivered: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<AlertEvent> for AlertEventInfo {
+    fn from(event: AlertEvent) -> Self {
+        Self {
+            price_timestamp: event.price_timestamp,
+            price: event.price_kwh,
+            fired_at: event.fired_at,
+            delivered: event.delivered,
+            error: event.error_message,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AlertHistoryResponse {
+    pub rule: AlertRuleInfo,
+    /// Newest first.
+    pub events: Vec<AlertEventInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlertHistoryQuery {
+    /// Defaults to 100, capped at 1000.
+    pub limit: Option<i64>,
+}
+
+/// A parsed query range and the defaults applied to fill it, if any.
+#[derive(Debug, Clone)]
+pub struct QueryRange {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub market: Market,
+    pub defaults: Option<AppliedRangeDefaults>,
+}
+
+impl DateRangeQuery {
+    /// Parse the range, filling missing bounds from `defaults` relative to `now`.
+    pub fn parse(&self, defaults: &DefaultRangeConfig, now: DateTime<Utc>) -> Result<QueryRange, AppError> {
+        let start = match &self.start {
+            Some(s) => DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| AppError::invalid_parameter("start", format!("Invalid start date format: {}. Use ISO8601/RFC3339.", e)))?,
+            None => now - Duration::days(defaults.lookback_days as i64),
+        };
+
+        let end = match &self.end {
+            Some(s) => DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| AppError::invalid_parameter("end", format!("Invalid end date format: {}. Use ISO8601/RFC3339.", e)))?,
+            None => {
+                let last_day = now.date_naive() + Duration::days(defaults.lookahead_days as i64);
+                last_day
+                    .and_hms_opt(23, 59, 59)
+                    .unwrap()
+                    .and_utc()
+            }
+        };
+
+        if start >= end {
+            return Err(AppError::invalid_date_range("Start date must be before end date"));
+        }
+
+        let market = match &self.market {
+            Some(m) => Market::parse(m)
+                .ok_or_else(|| AppError::invalid_parameter("market", format!("Invalid market: {}. Use day_ahead or intraday.", m)))?,
+            None => Market::DayAhead,
+        };
+
+        let applied = (self.start.is_none() || self.end.is_none()).then(|| AppliedRangeDefaults {
+            start: self.start.is_none().then_some(start),
+            end: self.end.is_none().then_some(end),
+            lookback_days: defaults.lookback_days,
+            lookahead_days: defaults.lookahead_days,
+        });
+
+        Ok(QueryRange {
+            start,
+            end,
+            market,
+            defaults: applied,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::models::GapFill;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap() + Duration::hours(hour as i64)
+    }
+
+    #[test]
+    fn test_completeness_counts_edge_and_interior_gaps() {
+        // Data for hours 2..=5 and 9..=10 of a 24-hour range
+        let stats = ZoneStats {
+            price_count: 6,
+            first_timestamp: Some(at(2)),
+            last_timestamp: Some(at(10)),
+            covered_hours: 6,
+            longest_interior_gap_hours: 3,
+            ..Default::default()
+        };
+        let completeness = Completeness::new(at(0), at(24), &stats);
+        assert_eq!(completeness.expected_hours, 24);
+        assert_eq!(completeness.missing_hours, 18);
+        assert_eq!(completeness.longest_gap_hours, 13);
+    }
+
+    #[test]
+    fn test_completeness_without_data_is_one_gap() {
+        let completeness = Completeness::new(at(0), at(24), &ZoneStats::default());
+        assert_eq!(completeness.missing_hours, 24);
+        assert_eq!(completeness.longest_gap_hours, 24);
+        assert!(completeness.first_timestamp.is_none());
+    }
+
+    #[test]
+    fn test_retention_status_flags_rows_past_cutoff() {
+        let config = RetentionConfig {
+            enabled: true,
+            price_retention_days: 30,
+            fetch_log_retention_days: 7,
+            cleanup_cron: "0 30 3 * * *".to_string(),
+        };
+        let now = at(0);
+        let table = |name: &str, days_old: i64| TableStats {
+            table_name: name.to_string(),
+            row_count: 1,
+            table_bytes: None,
+            index_bytes: None,
+            oldest_entry: Some(now - Duration::days(days_old)),
+        };
+
+        let status = RetentionStatus::new(&config, &[table("electricity_prices", 10), table("fetch_log", 8)], now);
+        assert!(!status.tables[0].overdue);
+        assert!(status.tables[1].overdue);
+        assert_eq!(status.tables[1].cutoff, now - Duration::days(7));
+    }
+
+    #[test]
+    fn test_default_range_uses_configured_lookback_and_lookahead() {
+        let query = DateRangeQuery {
+            start: None,
+            end: Some("2025-01-20T00:00:00Z".to_string()),
+            timezone: None,
+            clamp: false,
+            market: None,
+        };
+        let defaults = DefaultRangeConfig {
+            lookback_days: 2,
+            lookahead_days: 3,
+        };
+
+        let range = query.parse(&defaults, at(12)).unwrap();
+        assert_eq!(range.start, at(12) - Duration::days(2));
+        let applied = range.defaults.unwrap();
+        assert_eq!(applied.start, Some(range.start));
+        assert!(applied.end.is_none());
+
+        let open_ended = DateRangeQuery { end: None, ..query };
+        let range = open_ended.parse(&defaults, at(12)).unwrap();
+        assert_eq!(range.end, Utc.with_ymd_and_hms(2025, 1, 18, 23, 59, 59).unwrap());
+    }
+
+    #[test]
+    fn test_explicit_range_reports_no_defaults() {
+        let query = DateRangeQuery {
+            start: Some("2025-01-15T00:00:00Z".to_string()),
+            end: Some("2025-01-16T00:00:00Z".to_string()),
+            timezone: None,
+            clamp: false,
+            market: None,
+        };
+        let range = query.parse(&DefaultRangeConfig::default(), at(12)).unwrap();
+        assert!(range.defaults.is_none());
+        assert_eq!(range.market, Market::DayAhead);
+    }
+
+    #[test]
+    fn test_market_parameter_selects_intraday_and_rejects_unknown() {
+        let query = DateRangeQuery {
+            start: None,
+            end: None,
+            timezone: None,
+            clamp: false,
+            market: Some("intraday".to_string()),
+        };
+        let range = query.parse(&DefaultRangeConfig::default(), at(12)).unwrap();
+        assert_eq!(range.market, Market::Intraday);
+
+        let unknown = DateRangeQuery {
+            market: Some("balancing".to_string()),
+            ..query
+        };
+        assert!(unknown.parse(&DefaultRangeConfig::default(), at(12)).unwrap_err().to_string().contains("balancing"));
+    }
+
+    #[test]
+    fn test_query_errors_name_their_parameter() {
+        let parameter = |e: AppError| match e {
+            AppError::InvalidParameter { parameter, .. } => Some(parameter),
+            _ => None,
+        };
+        let range = |start: &str, end: &str, market: Option<&str>| DateRangeQuery {
+            start: Some(start.to_string()),
+            end: Some(end.to_string()),
+            timezone: None,
+            clamp: false,
+            market: market.map(str::to_string),
+        };
+        let parse = |query: DateRangeQuery| query.parse(&DefaultRangeConfig::default(), at(12)).unwrap_err();
+        assert_eq!(parameter(parse(range("today", "2025-01-16T00:00:00Z", None))), Some("start"));
+        assert_eq!(parameter(parse(range("2025-01-15T00:00:00Z", "today", None))), Some("end"));
+        assert_eq!(parameter(parse(range("2025-01-15T00:00:00Z", "2025-01-16T00:00:00Z", Some("spot")))), Some("market"));
+        assert!(matches!(
+            parse(range("2025-01-16T00:00:00Z", "2025-01-15T00:00:00Z", None)),
+            AppError::InvalidDateRange { .. }
+        ));
+
+        let price_at = PriceAtQuery { timestamp: None, timezone: None, market: None };
+        assert_eq!(parameter(price_at.parse().unwrap_err()), Some("timestamp"));
+
+        let gaps = GapReportQuery { start: Some("2025-01-15".to_string()), end: None, zones: None };
+        assert_eq!(parameter(gaps.date_range().unwrap_err()), Some("end"));
+
+        let tasks = TasksQuery { status: Some("done".to_string()), limit: None };
+        assert_eq!(parameter(tasks.parse().unwrap_err()), Some("status"));
+        assert_eq!(TasksQuery { status: None, limit: Some(0) }.parse().unwrap(), (None, 1));
+    }
+
+    #[test]
+    fn test_flows_response_pairs_directions_per_neighbour() {
+        let zone = crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo");
+        let flow = |from: &str, to: &str, mw: i64| CrossBorderFlow {
+            timestamp: at(0),
+            from_zone: from.to_string(),
+            to_zone: to.to_string(),
+            kind: FlowKind::Physical,
+            quantity_mw: Decimal::from(mw),
+            resolution: "PT60M".to_string(),
+            fetched_at: at(1),
+        };
+        let response = FlowsResponse::new(
+            &zone,
+            FlowKind::Physical,
+            vec![flow("SE3", "NO1", 400), flow("NO1", "SE3", 150), flow("NO1", "NO2", 90)],
+            None,
+        );
+
+        let neighbours: Vec<&str> = response.borders.iter().map(|b| b.neighbour.as_str()).collect();
+        assert_eq!(neighbours, vec!["NO2", "SE3"]);
+        let no2 = &response.borders[0].points[0];
+        assert_eq!((no2.import, no2.export, no2.net_import), (Decimal::ZERO, Decimal::from(90), Decimal::from(-90)));
+        let se3 = &response.borders[1].points[0];
+        assert_eq!((se3.import, se3.export, se3.net_import), (Decimal::from(400), Decimal::from(150), Decimal::from(250)));
+    }
+
+    #[test]
+    fn test_localize_fills_only_translated_names() {
+        let lang = LangQuery { lang: Some("nb".to_string()) };
+        assert_eq!(lang.parse().unwrap(), Some("nb"));
+        let unknown = LangQuery { lang: Some("fr".to_string()) };
+        assert!(unknown.parse().unwrap_err().contains("fr"));
+
+        let translations = Translations::new(vec![
+            NameTranslation {
+                kind: NameKind::Zone,
+                code: "NO1".to_string(),
+                locale: "nb".to_string(),
+                name: "Østlandet".to_string(),
+            },
+            NameTranslation {
+                kind: NameKind::Country,
+                code: "NO".to_string(),
+                locale: "nb".to_string(),
+                name: "Norge".to_string(),
+            },
+        ]);
+        let zone = |code: &str| crate::storage::InMemoryPriceStore::zone(code, "NO", "Europe/Oslo");
+
+        let no1 = ZoneInfo::from(&zone("NO1")).localize(&translations);
+        assert_eq!(no1.localized_zone_name.as_deref(), Some("Østlandet"));
+        assert_eq!(no1.localized_country_name.as_deref(), Some("Norge"));
+        let no2 = ZoneInfo::from(&zone("NO2")).localize(&translations);
+        assert_eq!(no2.localized_zone_name, None);
+        let untranslated = ZoneInfo::from(&zone("NO1")).localize(&Translations::default());
+        assert_eq!(untranslated.localized_country_name, None);
+    }
+
+    #[test]
+    fn test_zone_prices_flag_dst_transition_days() {
+        let zone = crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo");
+        // Hourly prices for Saturday and the 25-hour Sunday, local time
+        let start = Utc.with_ymd_and_hms(2025, 10, 24, 22, 0, 0).unwrap();
+        let prices: Vec<Price> = (0..49)
+            .map(|h| Price::from_mwh(start + Duration::hours(h), "NO1".to_string(), 40.0, "PT60M".to_string()))
+            .collect();
+
+        let response = ZonePricesResponse::new(&zone, Market::DayAhead, prices.clone(), PriceResolution::Hour, None);
+        let date = NaiveDate::from_ymd_opt(2025, 10, 26).unwrap();
+        assert_eq!(response.dst_days, vec![DstDay { date, hours: 25 }]);
+
+        let utc = ZonePricesResponse::new(&zone, Market::DayAhead, prices, PriceResolution::Hour, Some("UTC"));
+        assert!(utc.dst_days.is_empty());
+    }
+
+    #[test]
+    fn test_zone_prices_report_aggregation_and_filled_positions() {
+        let zone = crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo");
+        // Quarter-hours from 23:00 local on Jan 14 to 01:00 on Jan 15
+        let mut prices: Vec<Price> = (0..8)
+            .map(|q| Price::from_mwh(at(0) - Duration::hours(2) + Duration::minutes(15 * q), "NO1".to_string(), 40.0, "PT15M".to_string()))
+            .collect();
+        prices[1].gap_fill = Some(GapFill::ForwardFill);
+        prices[5].gap_fill = Some(GapFill::ForwardFill);
+        prices[6].gap_fill = Some(GapFill::LinearInterpolation);
+
+        let response = ZonePricesResponse::new(&zone, Market::DayAhead, prices.clone(), PriceResolution::Hour, None);
+        let provenance = response.context.and_then(|context| context.provenance).unwrap();
+        assert_eq!(provenance.source_resolutions, ["PT15M"]);
+        assert_eq!(provenance.aggregation.as_deref(), Some("mean"));
+        assert_eq!(provenance.gap_fill.as_deref(), Some("forward_fill,linear_interpolation"));
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        assert_eq!(provenance.filled_positions, BTreeMap::from([(day(14), 1), (day(15), 2)]));
+
+        let raw = ZonePricesResponse::new(&zone, Market::DayAhead, prices, PriceResolution::QuarterHour, None);
+        let provenance = raw.context.and_then(|context| context.provenance).unwrap();
+        assert_eq!(provenance.aggregation, None);
+
+        let untouched: Vec<Price> = (0..2)
+            .map(|h| Price::from_mwh(at(h), "NO1".to_string(), 40.0, "PT60M".to_string()))
+            .collect();
+        let response = ZonePricesResponse::new(&zone, Market::DayAhead, untouched, PriceResolution::Hour, None);
+        assert!(response.context.is_none());
+    }
+
+    #[test]
+    fn test_gb_served_half_hourly_other_zones_hourly() {
+        let zones = vec![
+            crate::storage::InMemoryPriceStore::zone("GB", "GB", "Europe/London"),
+            crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+        ];
+        let half_hours = |zone: &str| -> Vec<Price> {
+            (0..48)
+                .map(|i| Price::from_mwh(at(0) + Duration::minutes(30 * i), zone.to_string(), 40.0 + (i % 2) as f64 * 20.0, "PT30M".to_string()))
+                .collect()
+        };
+        let prices_by_zone = HashMap::from([("GB".to_string(), half_hours("GB")), ("NO1".to_string(), half_hours("NO1"))]);
+
+        let response = CountryPricesResponse::new(
+            "EU".to_string(),
+            "Europe".to_string(),
+            Market::DayAhead,
+            &zones,
+            prices_by_zone,
+            &ResolutionConfig::default(),
+            Some("UTC"),
+        );
+        let gb = response.zones.iter().find(|z| z.zone_code == "GB").unwrap();
+        assert_eq!((gb.resolution, gb.prices.len()), (PriceResolution::HalfHour, 48));
+        let no1 = response.zones.iter().find(|z| z.zone_code == "NO1").unwrap();
+        assert_eq!((no1.resolution, no1.prices.len()), (PriceResolution::Hour, 24));
+        assert_eq!(no1.prices[0].price.to_string(), "0.05");
+    }
+
+    #[test]
+    fn test_compare_aligns_zones_at_coarsest_resolution_with_spread() {
+        let zones = vec![
+            crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            crate::storage::InMemoryPriceStore::zone("GB", "GB", "Europe/London"),
+        ];
+        let gb: Vec<Price> = (0..4)
+            .map(|i| Price::from_mwh(at(0) + Duration::minutes(30 * i), "GB".to_string(), 60.0 + i as f64 * 20.0, "PT30M".to_string()))
+            .collect();
+        let no1 = vec![Price::from_mwh(at(0), "NO1".to_string(), 50.0, "PT60M".to_string())];
+        let prices_by_zone = HashMap::from([("GB".to_string(), gb), ("NO1".to_string(), no1)]);
+
+        let response =
+            ComparePricesResponse::new(&zones, Market::DayAhead, prices_by_zone, &ResolutionConfig::default(), None).unwrap();
+        assert_eq!((response.resolution, response.timezone.as_str()), (PriceResolution::Hour, "Europe/Oslo"));
+        assert_eq!(response.zones[0].zone_code, "NO1");
+        assert_eq!(response.rows.len(), 2);
+        assert_eq!(response.rows[0].prices["GB"].unwrap().to_string(), "0.07");
+        assert_eq!(response.rows[0].spread.unwrap().to_string(), "0.02");
+        assert_eq!(response.rows[1].prices["NO1"], None);
+        assert_eq!(response.rows[1].spread, None);
+    }
+
+    #[test]
+    fn test_compare_query_requires_two_distinct_zones() {
+        let query = |zones: &str| CompareQuery { zones: Some(zones.to_string()) }.parse();
+        assert_eq!(query("no1, SE3,NO1").unwrap(), vec!["NO1", "SE3"]);
+        assert!(query("NO1,no1").is_err());
+        assert!(CompareQuery { zones: None }.parse().is_err());
+        let too_many: Vec<String> = (0..=MAX_COMPARE_ZONES).map(|i| format!("Z{}", i)).collect();
+        assert!(query(&too_many.join(",")).is_err());
+    }
+
+    #[test]
+    fn test_gap_report_query_bounds_range_and_dedups_zones() {
+        let query = |start: &str, end: &str| GapReportQuery {
+            start: Some(start.to_string()),
+            end: Some(end.to_string()),
+            zones: Some("no1, SE3,NO1,".to_string()),
+        };
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        assert_eq!(query("2025-01-15", "2025-01-15").date_range().unwrap(), (day(15), day(15)));
+        assert_eq!(query("2025-01-15", "2025-01-15").zones(), vec!["NO1", "SE3"]);
+        assert!(query("2025-01-16", "2025-01-15").date_range().is_err());
+        assert!(query("2025-01-15", "tomorrow").date_range().is_err());
+        assert!(query("2024-01-01", "2025-01-15").date_range().unwrap_err().to_string().contains("at most 366"));
+        assert!(GapReportQuery { start: None, end: None, zones: None }.date_range().is_err());
+
+        let gap = DayGap {
+            date: day(16),
+            zone_code: "NO1".to_string(),
+            hours: 20,
+            expected_hours: 24,
+        };
+        let response = GapReportResponse::new((day(15), day(16)), vec!["NO1".to_string()], vec![gap], at(0));
+        assert_eq!(response.days_checked, 2);
+        assert_eq!(response.gaps[0].missing_hours, 4);
+    }
+
+    #[test]
+    fn test_backfill_request_bounds_range() {
+        let request = |start: &str, end: &str| BackfillRequest {
+            start: start.to_string(),
+            end: end.to_string(),
+            zones: None,
+        };
+        let day = |y: i32, d: u32| NaiveDate::from_ymd_opt(y, 1, d).unwrap();
+        assert_eq!(request("2025-01-01", "2025-01-15").date_range(MAX_BACKFILL_DAYS).unwrap(), (day(2025, 1), day(2025, 15)));
+        assert!(request("2025-01-16", "2025-01-15").date_range(MAX_BACKFILL_DAYS).is_err());
+        assert!(request("2025-01-15", "tomorrow").date_range(MAX_BACKFILL_DAYS).is_err());
+        let two_years = request("2023-01-01", "2025-01-01");
+        assert!(two_years.date_range(MAX_BACKFILL_DAYS).unwrap_err().to_string().contains("at most 366"));
+        assert!(two_years.date_range(MAX_BACKFILL_JOB_DAYS).is_ok());
+        assert!(request("0001-01-01", "9999-12-31").date_range(MAX_BACKFILL_JOB_DAYS).is_err());
+    }
+
+    #[test]
+    fn test_consumer_prices_only_for_zones_with_tariff() {
+        let include = IncludeQuery { include: Some("consumer_price".to_string()) };
+        assert!(include.consumer_price().unwrap());
+        assert!(!IncludeQuery { include: None }.consumer_price().unwrap());
+        let unknown = IncludeQuery { include: Some("consumer_price,fees".to_string()) };
+        assert!(unknown.consumer_price().unwrap_err().contains("fees"));
+
+        let zones = vec![
+            crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            crate::storage::InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+        ];
+        let prices_by_zone = HashMap::from([
+            ("NO1".to_string(), vec![Price::from_mwh(at(0), "NO1".to_string(), 40.0, "PT60M".to_string())]),
+            ("NO2".to_string(), vec![Price::from_mwh(at(0), "NO2".to_string(), 40.0, "PT60M".to_string())]),
+        ]);
+        let tariffs = TariffConfig {
+            zones: HashMap::from([(
+                "no1".to_string(),
+                Tariff {
+                    vat_rate: Decimal::new(25, 2),
+                    surcharge_kwh: Decimal::new(5, 2),
+                    margin_kwh: Decimal::new(1, 2),
+                    support: None,
+                },
+            )]),
+        };
+        let response = |tariffs: Option<&TariffConfig>| {
+            CountryPricesResponse::new(
+                "NO".to_string(),
+                "Norway".to_string(),
+                Market::DayAhead,
+                &zones,
+                prices_by_zone.clone(),
+                &ResolutionConfig::default(),
+                None,
+            )
+            .with_consumer_prices(tariffs)
+        };
+
+        let priced = response(Some(&tariffs));
+        let no1 = priced.zones.iter().find(|z| z.zone_code == "NO1").unwrap();
+        assert_eq!(no1.prices[0].consumer_price, Some(Decimal::new(125, 3)));
+        assert!(no1.tariff.is_some());
+        let no2 = priced.zones.iter().find(|z| z.zone_code == "NO2").unwrap();
+        assert_eq!((no2.prices[0].consumer_price, no2.tariff.is_none()), (None, true));
+
+        let spot_only = response(None);
+        assert!(spot_only.zones.iter().all(|z| z.tariff.is_none() && z.prices[0].consumer_price.is_none()));
+    }
+
+    #[test]
+    fn test_negative_prices_group_consecutive_hours() {
+        let zone = crate::storage::InMemoryPriceStore::zone("DE-LU", "DE", "Europe/Berlin");
+        // Hours 0-1 and 3 are negative; hour 5 is below zero but 4 is missing
+        let prices: Vec<Price> = [(0, -5.0), (1, -12.0), (2, 3.0), (3, -1.0), (5, -2.0)]
+            .into_iter()
+            .map(|(hour, mwh)| Price::from_mwh(at(hour), "DE-LU".to_string(), mwh, "PT60M".to_string()))
+            .collect();
+        let range = QueryRange {
+            start: at(0),
+            end: at(24),
+            market: Market::DayAhead,
+            defaults: None,
+        };
+
+        let response = NegativePricesResponse::new(&zone, &range, Decimal::ZERO, &prices, None);
+        let runs: Vec<(DateTime<Utc>, DateTime<Utc>, i64)> = response
+            .intervals
+            .iter()
+            .map(|interval| (interval.start_utc, interval.end_utc, interval.hours))
+            .collect();
+        assert_eq!(runs, vec![(at(0), at(2), 2), (at(3), at(4), 1), (at(5), at(6), 1)]);
+        assert_eq!(response.intervals[0].start, "2025-01-15T01:00:00+01:00");
+        assert_eq!(response.intervals[0].min_price.to_string(), "-0.012");
+        assert_eq!(response.total_hours, 4);
+
+        let raised = NegativePricesResponse::new(&zone, &range, "0.005".parse().unwrap(), &prices, None);
+        assert_eq!(raised.total_hours, 5);
+        assert!(ThresholdQuery { threshold: Some("low".to_string()) }.parse(EnergyUnit::KilowattHour).is_err());
+    }
+
+    #[test]
+    fn test_mwh_unit_restores_published_prices() {
+        let zone = crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo");
+        let prices: Vec<Price> = [45.67, -0.01, 123.45]
+            .into_iter()
+            .enumerate()
+            .map(|(hour, mwh)| Price::from_mwh(at(hour as u32), "NO1".to_string(), mwh, "PT60M".to_string()))
+            .collect();
+
+        let response = ZonePricesResponse::new(&zone, Market::DayAhead, prices.clone(), PriceResolution::Hour, None)
+            .in_unit(EnergyUnit::MegawattHour);
+        assert_eq!(response.unit, "MWh");
+        let mwh: Vec<String> = response.prices.iter().map(|p| p.price.to_string()).collect();
+        assert_eq!(mwh, vec!["45.67", "-0.01", "123.45"]);
+
+        let unit = UnitQuery { unit: Some("mwh".to_string()) }.parse().unwrap();
+        let threshold = ThresholdQuery { threshold: Some("50".to_string()) }.parse(unit).unwrap();
+        assert_eq!(threshold.to_string(), "0.05");
+        let range = QueryRange {
+            start: at(0),
+            end: at(24),
+            market: Market::DayAhead,
+            defaults: None,
+        };
+        let negative = NegativePricesResponse::new(&zone, &range, threshold, &prices, None).in_unit(unit);
+        assert_eq!(negative.threshold.to_string(), "50");
+        assert_eq!(negative.intervals[0].min_price.to_string(), "-0.01");
+        assert!(UnitQuery { unit: Some("gwh".to_string()) }.parse().is_err());
+    }
+
+    #[test]
+    fn test_country_average_weighs_zones_priced_in_each_interval() {
+        let zones: Vec<BiddingZone> = ["SE1", "SE3", "SE4"]
+            .into_iter()
+            .map(|code| crate::storage::InMemoryPriceStore::zone(code, "SE", "Europe/Stockholm"))
+            .collect();
+        let series = |zone: &str, mwh: &[f64]| -> Vec<Price> {
+            mwh.iter()
+                .enumerate()
+                .map(|(hour, mwh)| Price::from_mwh(at(hour as u32), zone.to_string(), *mwh, "PT60M".to_string()))
+                .collect()
+        };
+        // SE4 has no price for the second hour
+        let prices_by_zone = HashMap::from([
+            ("SE1".to_string(), series("SE1", &[10.0, 20.0])),
+            ("SE3".to_string(), series("SE3", &[40.0, 50.0])),
+            ("SE4".to_string(), series("SE4", &[100.0])),
+        ]);
+        let tz: Tz = "Europe/Stockholm".parse().unwrap();
+
+        let mean = CountryAverage::new(&zones, &prices_by_zone, &ResolutionConfig::default(), &CountryAverageConfig::default(), &tz);
+        assert_eq!(mean.method, AverageMethod::Mean);
+        assert_eq!(mean.prices[0].price, Decimal::new(5, 2));
+        assert_eq!((mean.prices[1].price, mean.prices[1].zones), (Decimal::new(35, 3), 2));
+        assert_eq!(mean.prices[0].timestamp, "2025-01-15T01:00:00+01:00");
+
+        let mut weights = CountryAverageConfig {
+            weights: HashMap::from([("SE1".to_string(), Decimal::ONE), ("se3".to_string(), Decimal::from(3))]),
+        };
+        // SE4 has prices but no weight, so the zones still count equally
+        assert_eq!(CountryAverage::new(&zones, &prices_by_zone, &ResolutionConfig::default(), &weights, &tz).method, AverageMethod::Mean);
+
+        weights.weights.insert("SE4".to_string(), Decimal::ZERO);
+        let weighted = CountryAverage::new(&zones, &prices_by_zone, &ResolutionConfig::default(), &weights, &tz);
+        assert_eq!(weighted.method, AverageMethod::Weighted);
+        assert_eq!(weighted.prices[0].price, Decimal::new(325, 4));
+        assert_eq!(weighted.prices[1].price, Decimal::new(425, 4));
+    }
+
+    #[test]
+    fn test_fetch_log_query_and_cursor_paging() {
+        let query = |offset: Option<i64>, cursor: Option<&str>| FetchLogQuery {
+            zone: Some("no1".to_string()),
+            status: Some("error".to_string()),
+            start: Some("2025-01-15T00:00:00Z".to_string()),
+            end: None,
+            limit: Some(5000),
+            offset,
+            cursor: cursor.map(str::to_string),
+            correlation_id: None,
+        };
+        let (filter, page, limit) = query(None, None).parse().unwrap();
+        assert_eq!(filter.zone_code.as_deref(), Some("NO1"));
+        assert_eq!(filter.status, Some(FetchStatus::Error));
+        assert_eq!(filter.started_from, Some(at(0)));
+        assert_eq!((page, limit), (FetchLogPage::Offset(0), 1000));
+        assert!(query(Some(10), Some("1.2")).parse().unwrap_err().to_string().contains("cannot be combined"));
+        assert!(query(None, Some("yesterday")).parse().is_err());
+
+        let logs: Vec<FetchLog> = (1..=3)
+            .rev()
+            .map(|id| FetchLog {
+                id,
+                fetch_started_at: at(id as u32),
+                ..FetchLog::new(Some("NO1".to_string()), at(0), at(24))
+            })
+            .collect();
+        let response = FetchLogResponse::new(logs, 7, 2);
+        assert_eq!(response.logs.iter().map(|log| log.id).collect::<Vec<_>>(), vec![3, 2]);
+        let cursor = response.next_cursor.unwrap();
+        assert_eq!(
+            query(None, Some(&cursor)).parse().unwrap().1,
+            FetchLogPage::After { started_at: at(2), id: 2 }
+        );
+        assert!(FetchLogResponse::new(Vec::new(), 0, 2).next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_country_sections_summarise_zones_and_drop_series() {
+        let include = |include: &str| IncludeQuery { include: Some(include.to_string()) }.country().unwrap();
+        assert_eq!(include("consumer_price"), (true, CountrySections { zones: true, stats: false }));
+        assert_eq!(include("stats"), (false, CountrySections { zones: false, stats: true }));
+        assert_eq!(include("stats,zones").1, CountrySections { zones: true, stats: true });
+        assert!(IncludeQuery { include: Some("stats".to_string()) }.consumer_price().is_err());
+
+        let zones = vec![crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")];
+        let prices = [40.0, 20.0, 30.0]
+            .iter()
+            .enumerate()
+            .map(|(hour, mwh)| Price::from_mwh(at(hour as u32), "NO1".to_string(), *mwh, "PT60M".to_string()))
+            .collect();
+        let response = CountryPricesResponse::new(
+            "NO".to_string(),
+            "Norway".to_string(),
+            Market::DayAhead,
+            &zones,
+            HashMap::from([("NO1".to_string(), prices)]),
+            &ResolutionConfig::default(),
+            None,
+        )
+        .with_sections(include("stats").1)
+        .in_unit(EnergyUnit::MegawattHour);
+
+        assert!(response.zones.is_empty());
+        let stats = &response.stats.unwrap()[0];
+        assert_eq!((stats.zone_code.as_str(), stats.price_count), ("NO1", 3));
+        assert_eq!(stats.min_price.unwrap().normalize(), Decimal::from(20));
+        assert_eq!(stats.max_price.unwrap().normalize(), Decimal::from(40));
+        assert_eq!(stats.avg_price.unwrap().normalize(), Decimal::from(30));
+    }
+
+    #[test]
+    fn test_home_assistant_splits_local_days_and_flags_incomplete_tomorrow() {
+        let zone = crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo");
+        let tz: Tz = "Europe/Oslo".parse().unwrap();
+        let today = DeliveryDay::new(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(), &tz);
+        // Priced at the local hour of day; tomorrow's last hour is missing
+        let prices: Vec<Price> = (0..47)
+            .map(|hour| Price::from_mwh(today.start + Duration::hours(hour), "NO1".to_string(), (hour % 24) as f64, "PT60M".to_string()))
+            .collect();
+
+        let response = HomeAssistantResponse::new(&zone, &tz, today, prices.clone(), PriceResolution::Hour, EnergyUnit::KilowattHour, at(9));
+        assert_eq!(response.today.len(), 24);
+        assert_eq!(response.tomorrow.len(), 23);
+        assert!(!response.tomorrow_valid);
+        assert_eq!(response.raw_today[0].start, "2025-01-15T00:00:00+01:00");
+        assert_eq!(response.raw_today[0].end, "2025-01-15T01:00:00+01:00");
+        assert_eq!(response.current_price, Some(0.01));
+        assert_eq!(response.off_peak_1, Some(0.0035));
+        assert_eq!(response.peak, Some(0.0135));
+        assert_eq!(response.max, Some(0.023));
+
+        let mut prices = prices;
+        prices.push(Price::from_mwh(today.start + Duration::hours(47), "NO1".to_string(), 23.0, "PT60M".to_string()));
+        let response = HomeAssistantResponse::new(&zone, &tz, today, prices, PriceResolution::Hour, EnergyUnit::MegawattHour, at(9));
+        assert!(response.tomorrow_valid);
+        assert_eq!(response.tomorrow[23], 23.0);
+        assert_eq!(response.unit, "MWh");
+    }
+
+    #[test]
+    fn test_status_is_degraded_by_failed_job_and_reports_hours_ahead() {
+        let zones = vec![
+            crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            crate::storage::InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+        ];
+        let latest = vec![Price::from_mwh(at(23), "NO1".to_string(), 40.0, "PT60M".to_string())];
+        let pool = || PoolStatus {
+            active_connections: 1,
+            idle_connections: 1,
+            max_connections: 5,
+        };
+        let history = crate::scheduler::JobHistory::default();
+        history.register("primary_fetch_13:00", "0 0 13 * * *");
+        let scheduler = || SchedulerState {
+            enabled: true,
+            jobs: history.jobs(),
+        };
+
+        let freshness = || ZoneFreshness::for_zones(&zones, latest.clone(), &[], Vec::new(), at(12));
+
+        let ok = StatusResponse::new(scheduler(), DatabaseState::new(pool(), None), None, None, freshness(), at(12));
+        assert_eq!(ok.status, "ok");
+        assert_eq!(ok.zones[0].hours_ahead, Some(12));
+        assert_eq!(ok.zones[1].latest_price_at, None);
+
+        history.record_failure("primary_fetch_13:00", at(12), std::time::Duration::ZERO, "timeout");
+        let failed = StatusResponse::new(scheduler(), DatabaseState::new(pool(), None), None, None, freshness(), at(12));
+        assert_eq!(failed.status, "degraded");
+    }
+
+    #[test]
+    fn test_database_state_hides_storage_error_text() {
+        let pool = || PoolStatus {
+            active_connections: 0,
+            idle_connections: 0,
+            max_connections: 5,
+        };
+        let unreachable = StorageError::PoolError("connect to db.internal:5432 refused".to_string());
+        let state = DatabaseState::new(pool(), Some(&unreachable));
+        assert_eq!(state.status, "error");
+        assert_eq!(state.error.as_deref(), Some(ErrorCode::DatabaseUnavailable.description()));
+
+        let failed = StorageError::QueryError("relation \"electricity_prices\" does not exist".to_string());
+        let state = DatabaseState::new(pool(), Some(&failed));
+        assert_eq!(state.error.as_deref(), Some(ErrorCode::DatabaseError.description()));
+    }
+
+    #[test]
+    fn test_zone_freshness_reports_gaps_tomorrow_and_last_fetch() {
+        let zones = vec![
+            crate::storage::InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            crate::storage::InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+        ];
+        let today = at(12).date_naive();
+        let days_ago = |days: i64| today - Duration::days(days);
+        let gap = |date: NaiveDate, zone_code: &str, hours| DayGap {
+            date,
+            zone_code: zone_code.to_string(),
+            hours,
+            expected_hours: 24,
+        };
+        let gaps = vec![
+            gap(days_ago(29), "NO1", 0),
+            gap(days_ago(3), "NO1", 20),
+            gap(today, "NO1", 23),
+            gap(today.succ_opt().unwrap(), "NO2", 0),
+        ];
+        let mut failed = FetchLog::new(Some("NO2".to_string()), at(0), at(24));
+        failed.status = crate::models::FetchStatus::Error;
+        failed.error_message = Some("connect to 10.0.0.7:443: timed out".to_string());
+
+        let zones = ZoneFreshness::for_zones(&zones, Vec::new(), &gaps, vec![failed], at(12));
+        assert_eq!((zones[0].gap_days_last_30, zones[0].tomorrow_available), (3, true));
+        assert!(zones[0].last_fetch.is_none());
+        assert_eq!((zones[1].gap_days_last_30, zones[1].tomorrow_available), (0, false));
+        let last_fetch = zones[1].last_fetch.as_ref().unwrap();
+        assert_eq!((last_fetch.status.as_str(), last_fetch.error.as_deref()), ("error", Some("Fetch failed")));
+    }
+
+    #[test]
+    fn test_readiness_checks_fail_on_stopped_scheduler_or_stale_fetch() {
+        let history = crate::scheduler::JobHistory::default();
+        history.register("primary_fetch_13:00", "0 0 13 * * *");
+        let checks = |scheduler: SchedulerCheck, last_fetch: FetchAgeCheck| ReadinessChecks {
+            scheduler,
+            last_fetch,
+            entsoe: None,
+        };
+
+        let stopped = SchedulerCheck::new(Some(&history), at(0));
+        assert_eq!((stopped.status.as_str(), stopped.jobs[0].next_fire_at), ("stopped", Some(at(12))));
+        assert!(!checks(stopped, FetchAgeCheck::new(None, None, at(0))).passed());
+
+        let disabled = || SchedulerCheck::new(None, at(0));
+        let unknown = FetchAgeCheck::new(None, Some(26), at(0));
+        assert_eq!((unknown.status.as_str(), unknown.max_age_seconds), ("unknown", Some(93_600)));
+        assert!(checks(disabled(), unknown).passed());
+
+        let fresh = FetchAgeCheck::new(Some(at(0)), Some(26), at(26));
+        assert_eq!((fresh.status.as_str(), fresh.age_seconds), ("ok", Some(93_600)));
+        let stale = FetchAgeCheck::new(Some(at(0)), Some(26), at(27));
+        assert_eq!(stale.status, "stale");
+        assert!(!checks(disabled(), stale).passed());
+
+        let unreachable = ReadinessChecks {
+            entsoe: Some(EntsoeCheck::new("https://example.test/api", Err(EntsoeError::TemporaryUnavailable("connection refused".to_string())))),
+            ..checks(disabled(), fresh)
+        };
+        assert!(!unreachable.passed());
+    }
+
+    #[test]
+    fn test_invoice_totals_round_lines_before_vat() {
+        let dec = |value: &str| value.parse::<Decimal>().unwrap();
+        let tariff = Tariff {
+            vat_rate: dec("0.25"),
+            ..Tariff::default()
+        };
+        let invoice = Invoice {
+            priced_kwh: dec("3"),
+            spot: dec("10.125"),
+            fees: dec("2.004"),
+            vat: dec("3.03225"),
+            ..Invoice::default()
+        };
+        let month = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let response = |profile| {
+            ZoneInvoiceResponse::new("NO1".into(), month, (at(0), at(24)), "EUR".into(), profile, Some(&tariff), &invoice)
+        };
+
+        let uploaded = response(ConsumptionProfile::Uploaded);
+        assert_eq!(uploaded.month, "2025-01");
+        assert_eq!(uploaded.per_kwh.spot, dec("3.375"));
+        let totals = uploaded.totals.unwrap();
+        assert_eq!((totals.spot, totals.fees, totals.vat, totals.total), (dec("10.13"), dec("2.00"), dec("3.03"), dec("15.16")));
+        assert!(response(ConsumptionProfile::ZoneLoad).totals.is_none());
+    }
+
+    #[test]
+    fn test_invoice_month_must_be_a_four_digit_year() {
+        let parse = |month: &str| InvoiceQuery { month: Some(month.to_string()) }.parse();
+        assert_eq!(parse("2025-03"), Ok(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()));
+        assert!(parse("2025-13").is_err());
+        assert!(parse("+262142-12").is_err());
+        assert!(parse("10000-01").is_err());
+        assert!(InvoiceQuery { month: None }.parse().is_err());
+    }
+
+    #[test]
+    fn test_consumption_upload_must_fall_within_month() {
+        let upload = |timestamp, kwh: &str| ConsumptionUpload {
+            resolution: None,
+            consumption: vec![ConsumptionEntry {
+                timestamp,
+                kwh: kwh.parse().unwrap(),
+            }],
+        };
+        let parsed = upload(at(23), "1.5").parse(at(0), at(24)).unwrap();
+        assert_eq!(parsed[0].duration, Duration::hours(1));
+        assert!(upload(at(24), "1.5").parse(at(0), at(24)).is_err());
+        assert!(upload(at(3), "-1").parse(at(0), at(24)).is_err());
+        assert!(ConsumptionUpload {
+            resolution: Some("hourly".into()),
+            consumption: Vec::new(),
+        }
+        .parse(at(0), at(24))
+        .is_err());
+    }
+}
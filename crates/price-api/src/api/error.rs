@@ -0,0 +1,324 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::storage::StorageError;
+
+/// Stable `code` of an error response, for clients to branch on instead of
+/// parsing the message. Codes are only ever added within an API version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    NotFound,
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    RateLimited,
+    Conflict,
+    InternalError,
+    DatabaseUnavailable,
+    DatabaseError,
+    ZoneNotFound,
+    InvalidDateRange,
+    JobAlreadyRunning,
+    IdempotencyInProgress,
+}
+
+impl ErrorCode {
+    /// Every code, in the order `/api/v1/errors` lists them.
+    pub const ALL: [ErrorCode; 13] = [
+        ErrorCode::NotFound,
+        ErrorCode::BadRequest,
+        ErrorCode::Unauthorized,
+        ErrorCode::Forbidden,
+        ErrorCode::RateLimited,
+        ErrorCode::Conflict,
+        ErrorCode::InternalError,
+        ErrorCode::DatabaseUnavailable,
+        ErrorCode::DatabaseError,
+        ErrorCode::ZoneNotFound,
+        ErrorCode::InvalidDateRange,
+        ErrorCode::JobAlreadyRunning,
+        ErrorCode::IdempotencyInProgress,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::DatabaseUnavailable => "DATABASE_UNAVAILABLE",
+            ErrorCode::DatabaseError => "DATABASE_ERROR",
+            ErrorCode::ZoneNotFound => "ZONE_NOT_FOUND",
+            ErrorCode::InvalidDateRange => "INVALID_DATE_RANGE",
+            ErrorCode::JobAlreadyRunning => "JOB_ALREADY_RUNNING",
+            ErrorCode::IdempotencyInProgress => "IDEMPOTENCY_IN_PROGRESS",
+        }
+    }
+
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::NotFound | ErrorCode::ZoneNotFound => StatusCode::NOT_FOUND,
+            ErrorCode::BadRequest | ErrorCode::InvalidDateRange => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::Conflict | ErrorCode::JobAlreadyRunning | ErrorCode::IdempotencyInProgress => StatusCode::CONFLICT,
+            ErrorCode::InternalError | ErrorCode::DatabaseError => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::DatabaseUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ErrorCode::NotFound => "The country, job or other resource named in the request does not exist",
+            ErrorCode::BadRequest => {
+                "A parameter or body is invalid; details.parameter names the offending query parameter when there is one"
+            }
+            ErrorCode::Unauthorized => "The API key or token is missing or not recognised",
+            ErrorCode::Forbidden => "The credentials are valid but lack the role this route needs",
+            ErrorCode::RateLimited => "The caller's request limit is used up; retry after the Retry-After header",
+            ErrorCode::Conflict => "The resource is in a state that does not allow the request, e.g. cancelling a finished backfill job",
+            ErrorCode::InternalError => "The service failed to handle the request",
+            ErrorCode::DatabaseUnavailable => "The database cannot be reached; retrying later may succeed",
+            ErrorCode::DatabaseError => "A database query failed",
+            ErrorCode::ZoneNotFound => "The bidding zone named in the request is not registered",
+            ErrorCode::InvalidDateRange => {
+                "The range starts after it ends or is longer than the endpoint allows; details.parameter names the query parameter when there is one"
+            }
+            ErrorCode::JobAlreadyRunning => "Another run of the same job holds its lock; retry once it has finished",
+            ErrorCode::IdempotencyInProgress => "A request with the same Idempotency-Key is still running; retry once it has finished",
+        }
+    }
+}
+
+/// Machine-readable context for an error, beyond its code.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ErrorDetails {
+    /// Query parameter whose value was rejected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<String>,
+}
+
+/// JSON body of every error response.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+    /// One of the codes listed on `/api/v1/errors`.
+    pub code: String,
+    pub timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<ErrorDetails>,
+}
+
+impl ErrorResponse {
+    fn new(code: ErrorCode, message: String, details: Option<ErrorDetails>, correlation_id: Option<String>) -> Self {
+        Self {
+            error: message,
+            code: code.as_str().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            correlation_id,
+            details,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AppError {
+    NotFound(String),
+    /// A zone code that is not registered.
+    ZoneNotFound(String),
+    BadRequest(String),
+    /// A bad request blamed on one query parameter, reported in
+    /// `details.parameter`.
+    InvalidParameter {
+        parameter: &'static str,
+        message: String,
+    },
+    /// A range that starts after it ends or is too long, blamed on a query
+    /// parameter when `parameter` is set.
+    InvalidDateRange {
+        parameter: Option<&'static str>,
+        message: String,
+    },
+    Unauthorized(String),
+    Forbidden(String),
+    RateLimited(String),
+    /// The resource's state does not allow the request.
+    Conflict(String),
+    /// Another run of the same job holds its lock.
+    JobAlreadyRunning(String),
+    /// A request with the same idempotency key is still running.
+    IdempotencyInProgress(String),
+    InternalError(String),
+    DatabaseError(StorageError),
+}
+
+#[derive(Debug)]
+pub struct AppErrorWithContext {
+    pub error: AppError,
+    pub correlation_id: Option<String>,
+}
+
+impl AppError {
+    pub fn invalid_parameter(parameter: &'static str, message: impl Into<String>) -> Self {
+        AppError::InvalidParameter {
+            parameter,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_date_range(message: impl Into<String>) -> Self {
+        AppError::InvalidDateRange {
+            parameter: None,
+            message: message.into(),
+        }
+    }
+
+    /// A failed zone lookup: a missing zone is `ZONE_NOT_FOUND`, anything
+    /// else a database error.
+    pub fn zone(e: StorageError) -> Self {
+        match e {
+            StorageError::NotFound(message) => AppError::ZoneNotFound(message),
+            e => AppError::DatabaseError(e),
+        }
+    }
+
+    pub fn with_correlation_id(self, correlation_id: Option<String>) -> AppErrorWithContext {
+        AppErrorWithContext {
+            error: self,
+            correlation_id,
+        }
+    }
+
+    /// The stable code, message and details this error is reported with.
+    fn into_parts(self) -> (ErrorCode, String, Option<ErrorDetails>) {
+        match self {
+            AppError::NotFound(msg) => (ErrorCode::NotFound, msg, None),
+            AppError::ZoneNotFound(msg) => (ErrorCode::ZoneNotFound, msg, None),
+            AppError::BadRequest(msg) => (ErrorCode::BadRequest, msg, None),
+            AppError::InvalidParameter { parameter, message } => (
+                ErrorCode::BadRequest,
+                message,
+                Some(ErrorDetails {
+                    parameter: Some(parameter.to_string()),
+                }),
+            ),
+            AppError::InvalidDateRange { parameter, message } => (
+                ErrorCode::InvalidDateRange,
+                message,
+                parameter.map(|parameter| ErrorDetails {
+                    parameter: Some(parameter.to_string()),
+                }),
+            ),
+            AppError::Unauthorized(msg) => (ErrorCode::Unauthorized, msg, None),
+            AppError::Forbidden(msg) => (ErrorCode::Forbidden, msg, None),
+            AppError::RateLimited(msg) => (ErrorCode::RateLimited, msg, None),
+            AppError::Conflict(msg) => (ErrorCode::Conflict, msg, None),
+            AppError::JobAlreadyRunning(msg) => (ErrorCode::JobAlreadyRunning, msg, None),
+            AppError::IdempotencyInProgress(msg) => (ErrorCode::IdempotencyInProgress, msg, None),
+            AppError::InternalError(msg) => (ErrorCode::InternalError, msg, None),
+            AppError::DatabaseError(e) => {
+                let code = if e.is_not_found() {
+                    ErrorCode::NotFound
+                } else if e.is_connection_error() {
+                    ErrorCode::DatabaseUnavailable
+                } else {
+                    ErrorCode::DatabaseError
+                };
+                (code, e.to_string(), None)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::NotFound(msg)
+            | AppError::ZoneNotFound(msg)
+            | AppError::BadRequest(msg)
+            | AppError::InvalidParameter { message: msg, .. }
+            | AppError::InvalidDateRange { message: msg, .. }
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::RateLimited(msg)
+            | AppError::Conflict(msg)
+            | AppError::JobAlreadyRunning(msg)
+            | AppError::IdempotencyInProgress(msg)
+            | AppError::InternalError(msg) => f.write_str(msg),
+            AppError::DatabaseError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        self.with_correlation_id(None).into_response()
+    }
+}
+
+impl IntoResponse for AppErrorWithContext {
+    fn into_response(self) -> Response {
+        let (code, message, details) = self.error.into_parts();
+        let body = ErrorResponse::new(code, message, details, self.correlation_id.clone());
+        let mut response = (code.status(), Json(body)).into_response();
+        if let Some(correlation_id) = self.correlation_id {
+            if let Ok(header_value) = axum::http::header::HeaderValue::from_str(&correlation_id) {
+                response.headers_mut().insert("X-Correlation-Id", header_value);
+            }
+        }
+        response
+    }
+}
+
+impl From<StorageError> for AppError {
+    fn from(e: StorageError) -> Self {
+        AppError::DatabaseError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_codes_are_unique() {
+        let codes: HashSet<&str> = ErrorCode::ALL.iter().map(|code| code.as_str()).collect();
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_parameter_names_the_parameter() {
+        let response = AppError::invalid_parameter("unit", "Invalid unit: gwh. Use kwh or mwh.")
+            .with_correlation_id(Some("req-1".to_string()))
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()["X-Correlation-Id"], "req-1");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "BAD_REQUEST");
+        assert_eq!(body["details"], serde_json::json!({"parameter": "unit"}));
+    }
+
+    #[test]
+    fn test_missing_zone_is_zone_not_found() {
+        let (code, message, _) = AppError::zone(StorageError::NotFound("Zone not found: XX1".to_string())).into_parts();
+        assert_eq!((code, message.as_str()), (ErrorCode::ZoneNotFound, "Zone not found: XX1"));
+        let (code, _, _) = AppError::zone(StorageError::QueryError("timeout".to_string())).into_parts();
+        assert_eq!(code, ErrorCode::DatabaseError);
+    }
+}
@@ -0,0 +1,113 @@
+//! `Query` and `Json` extractors that reject malformed requests with the
+//! same error body as every other error, instead of axum's plain-text
+//! rejections.
+
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::{request::Parts, Extensions};
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::error::{AppError, AppErrorWithContext};
+use super::middleware::CorrelationId;
+
+/// Query string deserialized into `T`; a query that does not fit `T` is a
+/// `BAD_REQUEST`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Query<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppErrorWithContext;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Query(value)) => Ok(Query(value)),
+            Err(rejection) => {
+                Err(AppError::BadRequest(rejection.body_text()).with_correlation_id(correlation_id(&parts.extensions)))
+            }
+        }
+    }
+}
+
+/// JSON request body deserialized into `T`, or a JSON response. A missing
+/// content type, malformed JSON or a body that does not fit `T` is a
+/// `BAD_REQUEST`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppErrorWithContext;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let cid = correlation_id(req.extensions());
+        match axum::Json::<T>::from_request(req, state).await {
+            Ok(axum::Json(value)) => Ok(Json(value)),
+            Err(rejection) => Err(AppError::BadRequest(rejection.body_text()).with_correlation_id(cid)),
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+fn correlation_id(extensions: &Extensions) -> Option<String> {
+    extensions.get::<CorrelationId>().map(|id| id.0.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::{get, post};
+    use axum::Router;
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    struct Limit {
+        limit: i64,
+    }
+
+    async fn error_body(app: Router, request: axum::http::Request<Body>) -> serde_json::Value {
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejections_use_the_error_body() {
+        let app = Router::new()
+            .route("/query", get(|Query(query): Query<Limit>| async move { Json(query) }))
+            .route("/body", post(|Json(body): Json<Limit>| async move { Json(body) }));
+
+        let request = axum::http::Request::builder().uri("/query?limit=ten").body(Body::empty()).unwrap();
+        let body = error_body(app.clone(), request).await;
+        assert_eq!(body["code"], "BAD_REQUEST");
+        assert!(body["error"].as_str().unwrap().contains("limit"));
+
+        let request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/body")
+            .header("Content-Type", "application/json")
+            .extension(CorrelationId("req-1".to_string()))
+            .body(Body::from(r#"{"limit":"ten"}"#))
+            .unwrap();
+        let body = error_body(app, request).await;
+        assert_eq!(body["code"], "BAD_REQUEST");
+        assert_eq!(body["correlation_id"], "req-1");
+    }
+}
@@ -0,0 +1,231 @@
+//! Sparse fieldsets for price responses: `?fields=timestamp,price` keeps
+//! only the named fields on each price point and drops response metadata,
+//! for clients that parse responses on constrained devices.
+
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::error::AppError;
+
+/// Keys dropped from every object of a shaped response.
+pub const METADATA: &[&str] = &["meta", "context", "dst_days"];
+
+/// Where a response keeps its price points, and which of their fields
+/// `?fields=` can select.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointShape {
+    /// Key of the arrays holding price points; `None` when the response is
+    /// itself a single point.
+    pub points: Option<&'static str>,
+    /// Point fields `?fields=` can select.
+    pub selectable: &'static [&'static str],
+    /// Point fields kept whatever is selected, since the price cannot be
+    /// read without them.
+    pub kept: &'static [&'static str],
+}
+
+impl PointShape {
+    /// `prices` of a zone price response.
+    pub const ZONE: Self = Self {
+        points: Some("prices"),
+        selectable: &["timestamp", "timestamp_utc", "price", "is_estimated", "consumer_price"],
+        kept: &[],
+    };
+    /// `prices` of each zone of a country price response, and of its
+    /// average, whose points also count the `zones` priced.
+    pub const COUNTRY: Self = Self {
+        points: Some("prices"),
+        selectable: &["timestamp", "timestamp_utc", "price", "is_estimated", "consumer_price", "zones"],
+        kept: &[],
+    };
+    /// `prices` of the latest prices response, one per zone.
+    pub const LATEST: Self = Self {
+        points: Some("prices"),
+        selectable: &["zone_name", "localized_zone_name", "country_code", "timezone", "timestamp", "timestamp_utc", "price"],
+        kept: &["zone_code", "currency", "resolution"],
+    };
+    /// `rows` of a comparison, each with every zone's price.
+    pub const COMPARE: Self = Self {
+        points: Some("rows"),
+        selectable: &["timestamp", "timestamp_utc", "prices", "spread"],
+        kept: &[],
+    };
+    /// The price covering one instant.
+    pub const PRICE_AT: Self = Self {
+        points: None,
+        selectable: &[
+            "market",
+            "timezone",
+            "requested_at",
+            "interval_start",
+            "interval_start_utc",
+            "interval_end",
+            "interval_end_utc",
+            "price",
+            "is_estimated",
+            "fetched_at",
+        ],
+        kept: &["zone_code", "currency", "unit", "resolution"],
+    };
+}
+
+/// Price point fields to keep, in request order without duplicates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceFields {
+    shape: PointShape,
+    selected: Vec<&'static str>,
+}
+
+impl PriceFields {
+    /// `None` when `fields` is absent, so responses are served whole.
+    pub fn parse(fields: Option<&str>, shape: PointShape) -> Result<Option<Self>, String> {
+        let Some(fields) = fields else {
+            return Ok(None);
+        };
+        let mut selected = Vec::new();
+        for field in fields.split(',').map(str::trim).filter(|field| !field.is_empty()) {
+            let Some(&known) = shape.selectable.iter().find(|known| **known == field) else {
+                return Err(format!("Invalid field: {}. Use {}.", field, shape.selectable.join(", ")));
+            };
+            if !selected.contains(&known) {
+                selected.push(known);
+            }
+        }
+        if selected.is_empty() {
+            return Err("fields must name at least one price field".to_string());
+        }
+        Ok(Some(Self { shape, selected }))
+    }
+
+    fn keeps(&self, field: &str) -> bool {
+        self.selected.contains(&field) || self.shape.kept.contains(&field)
+    }
+
+    /// Keep only the selected fields on every price point, and drop
+    /// metadata, throughout `value`.
+    pub fn apply(&self, value: &mut Value) {
+        match (self.shape.points, value.as_object_mut()) {
+            (None, Some(point)) => point.retain(|field, _| self.keeps(field)),
+            (Some(_), _) => self.apply_within(value),
+            (None, None) => {}
+        }
+    }
+
+    fn apply_within(&self, value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                object.retain(|key, _| !METADATA.contains(&key.as_str()));
+                for (key, value) in object.iter_mut() {
+                    match value {
+                        Value::Array(points) if Some(key.as_str()) == self.shape.points => {
+                            for point in points.iter_mut().filter_map(Value::as_object_mut) {
+                                point.retain(|field, _| self.keeps(field));
+                            }
+                        }
+                        value => self.apply_within(value),
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.apply_within(value)),
+            _ => {}
+        }
+    }
+}
+
+/// A JSON response narrowed to the requested [`PriceFields`], or served
+/// whole without them.
+pub struct Shaped<T>(pub T, pub Option<PriceFields>);
+
+impl<T: Serialize> IntoResponse for Shaped<T> {
+    fn into_response(self) -> Response {
+        let (response, fields) = match self {
+            Shaped(response, Some(fields)) => (response, fields),
+            Shaped(response, None) => return Json(response).into_response(),
+        };
+        match serde_json::to_value(&response) {
+            Ok(mut value) => {
+                fields.apply(&mut value);
+                Json(value).into_response()
+            }
+            Err(e) => AppError::InternalError(format!("Failed to serialize response: {}", e)).into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_fields() {
+        assert_eq!(PriceFields::parse(None, PointShape::ZONE).unwrap(), None);
+        assert_eq!(
+            PriceFields::parse(Some(" price,timestamp,price "), PointShape::ZONE).unwrap().unwrap().selected,
+            vec!["price", "timestamp"]
+        );
+        assert!(PriceFields::parse(Some("price,zone_code"), PointShape::ZONE).unwrap_err().contains("zone_code"));
+        assert!(PriceFields::parse(Some("price,zones"), PointShape::ZONE).is_err());
+        assert!(PriceFields::parse(Some("price,zones"), PointShape::COUNTRY).is_ok());
+        assert!(PriceFields::parse(Some(","), PointShape::ZONE).is_err());
+    }
+
+    #[test]
+    fn test_apply_keeps_selected_point_fields_and_drops_metadata() {
+        let mut value = json!({
+            "country_code": "NO",
+            "meta": {"defaults": {}},
+            "zones": [{
+                "zone_code": "NO1",
+                "dst_days": [{"date": "2025-03-30", "hours": 23}],
+                "prices": [{"timestamp": "2025-01-15T00:00:00+01:00", "timestamp_utc": "2025-01-14T23:00:00Z", "price": 0.04, "is_estimated": false}],
+            }],
+            "average": {"resolution": "PT60M", "prices": [{"timestamp": "2025-01-15T00:00:00+01:00", "price": 0.04, "zones": 1}]},
+        });
+        PriceFields::parse(Some("timestamp,price,zones"), PointShape::COUNTRY).unwrap().unwrap().apply(&mut value);
+
+        assert_eq!(
+            value,
+            json!({
+                "country_code": "NO",
+                "zones": [{
+                    "zone_code": "NO1",
+                    "prices": [{"timestamp": "2025-01-15T00:00:00+01:00", "price": 0.04}],
+                }],
+                "average": {"resolution": "PT60M", "prices": [{"timestamp": "2025-01-15T00:00:00+01:00", "price": 0.04, "zones": 1}]},
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_keeps_identifying_fields_of_other_shapes() {
+        let mut latest = json!({
+            "unit": "kWh",
+            "prices": [{"zone_code": "NO1", "zone_name": "Oslo", "currency": "EUR", "resolution": "PT60M", "timestamp": "2025-01-15T00:00:00+01:00", "price": 0.04}],
+        });
+        PriceFields::parse(Some("price"), PointShape::LATEST).unwrap().unwrap().apply(&mut latest);
+        assert_eq!(
+            latest,
+            json!({"unit": "kWh", "prices": [{"zone_code": "NO1", "currency": "EUR", "resolution": "PT60M", "price": 0.04}]})
+        );
+
+        let mut compare = json!({
+            "unit": "kWh",
+            "rows": [{"timestamp": "2025-01-15T00:00:00+01:00", "prices": {"NO1": 0.04, "NO2": null}, "spread": null}],
+        });
+        PriceFields::parse(Some("timestamp,prices"), PointShape::COMPARE).unwrap().unwrap().apply(&mut compare);
+        assert_eq!(
+            compare,
+            json!({"unit": "kWh", "rows": [{"timestamp": "2025-01-15T00:00:00+01:00", "prices": {"NO1": 0.04, "NO2": null}}]})
+        );
+
+        let mut at = json!({"zone_code": "NO1", "currency": "EUR", "unit": "kWh", "resolution": "PT60M", "price": 0.04, "requested_at": "2025-01-15T01:30:00Z"});
+        PriceFields::parse(Some("price"), PointShape::PRICE_AT).unwrap().unwrap().apply(&mut at);
+        assert_eq!(at, json!({"zone_code": "NO1", "currency": "EUR", "unit": "kWh", "resolution": "PT60M", "price": 0.04}));
+    }
+}
@@ -0,0 +1,389 @@
+//! GraphQL endpoint over the zone registry and prices, so a client can fetch
+//! zone metadata together with a price range in one request.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema, SimpleObject};
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+use tokio::sync::OnceCell;
+use tracing::error;
+
+use crate::models::{BiddingZone, EnergyUnit, Market, Price};
+use crate::storage::StorageError;
+
+use super::dto::{DateRangeQuery, UnitQuery};
+use super::error::ErrorCode;
+use super::extract::Json;
+use super::handlers::{active_zones, zone_by_code, zone_prices};
+use super::routes::AppState;
+
+/// Deepest selection accepted; `countries { zones { prices } }` needs four.
+const MAX_DEPTH: usize = 8;
+
+/// Most expensive query accepted. Fields cost 1 and `prices` costs
+/// [`PRICES_COMPLEXITY`], so a query can ask for a handful of price ranges
+/// but not alias `prices` hundreds of times.
+const MAX_COMPLEXITY: usize = 200;
+const PRICES_COMPLEXITY: usize = 20;
+
+type PriceSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+static SCHEMA: LazyLock<PriceSchema> = LazyLock::new(|| {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+});
+
+pub async fn graphql(
+    State(state): State<AppState>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(SCHEMA.execute(request.data(state).data(PriceLoader::default())).await)
+}
+
+/// A failed storage call as a GraphQL error: the error code and its generic
+/// description, as the storage error itself can name tables, hosts or SQL.
+fn storage_error(e: StorageError) -> async_graphql::Error {
+    let code = if e.is_connection_error() {
+        ErrorCode::DatabaseUnavailable
+    } else {
+        ErrorCode::DatabaseError
+    };
+    error!(error = %e, "GraphQL storage query failed");
+    async_graphql::Error::new(code.description()).extend_with(|_, extensions| extensions.set("code", code.as_str()))
+}
+
+/// Country, market and range of a batched load.
+type CountryRange = (String, Market, DateTime<Utc>, DateTime<Utc>);
+type CountryPrices = HashMap<String, Vec<Price>>;
+
+/// Per-request batching of the `prices` of listed zones: the first zone of
+/// a country to ask for a range loads the whole country in one query, and
+/// its siblings are answered from that load.
+#[derive(Default)]
+struct PriceLoader {
+    countries: Mutex<HashMap<CountryRange, Arc<OnceCell<CountryPrices>>>>,
+}
+
+impl PriceLoader {
+    async fn zone_prices(
+        &self,
+        state: &AppState,
+        zone: &BiddingZone,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Price>, StorageError> {
+        // The cache only holds day-ahead prices
+        if market == Market::DayAhead {
+            if let Some(prices) = state.cache.as_ref().and_then(|cache| cache.zone_prices(&zone.zone_code, start, end)) {
+                return Ok(prices);
+            }
+        }
+
+        let key = (zone.country_code.clone(), market, start, end);
+        let cell = Arc::clone(self.countries.lock().unwrap().entry(key).or_default());
+        let country = cell
+            .get_or_try_init(|| state.repository.get_prices_by_country(&zone.country_code, market, start, end))
+            .await?;
+        Ok(country.get(&zone.zone_code).cloned().unwrap_or_default())
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Active bidding zones, optionally of one country.
+    async fn zones(&self, ctx: &Context<'_>, country: Option<String>) -> async_graphql::Result<Vec<Zone>> {
+        let state = ctx.data::<AppState>()?;
+        let zones = active_zones(state).await.map_err(storage_error)?;
+        Ok(zones
+            .into_iter()
+            .filter(|zone| country.as_ref().is_none_or(|country| zone.country_code.eq_ignore_ascii_case(country)))
+            .map(|zone| Zone { zone, listed: true })
+            .collect())
+    }
+
+    /// A zone by code, or null when it is unknown.
+    async fn zone(&self, ctx: &Context<'_>, code: String) -> async_graphql::Result<Option<Zone>> {
+        let state = ctx.data::<AppState>()?;
+        match zone_by_code(state, &code).await {
+            Ok(zone) => Ok(Some(Zone { zone, listed: false })),
+            Err(e) if e.is_not_found() => Ok(None),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+
+    /// Countries with at least one active zone.
+    async fn countries(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Country>> {
+        let state = ctx.data::<AppState>()?;
+        let mut countries: Vec<Country> = Vec::new();
+        for zone in active_zones(state).await.map_err(storage_error)? {
+            if !countries.iter().any(|country| country.code == zone.country_code) {
+                countries.push(Country {
+                    code: zone.country_code,
+                    name: zone.country_name,
+                });
+            }
+        }
+        countries.sort_by(|a, b| a.code.cmp(&b.code));
+        Ok(countries)
+    }
+
+    /// A country by code, or null when it has no active zone.
+    async fn country(&self, ctx: &Context<'_>, code: String) -> async_graphql::Result<Option<Country>> {
+        Ok(self
+            .countries(ctx)
+            .await?
+            .into_iter()
+            .find(|country| country.code.eq_ignore_ascii_case(&code)))
+    }
+}
+
+pub struct Country {
+    code: String,
+    name: String,
+}
+
+#[Object]
+impl Country {
+    async fn code(&self) -> &str {
+        &self.code
+    }
+
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn zones(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Zone>> {
+        QueryRoot.zones(ctx, Some(self.code.clone())).await
+    }
+}
+
+pub struct Zone {
+    zone: BiddingZone,
+    /// Part of a list, so its prices are batched with its siblings'.
+    listed: bool,
+}
+
+#[Object]
+impl Zone {
+    async fn code(&self) -> &str {
+        &self.zone.zone_code
+    }
+
+    async fn name(&self) -> &str {
+        &self.zone.zone_name
+    }
+
+    async fn country_code(&self) -> &str {
+        &self.zone.country_code
+    }
+
+    async fn country_name(&self) -> &str {
+        &self.zone.country_name
+    }
+
+    async fn eic_code(&self) -> &str {
+        &self.zone.eic_code
+    }
+
+    async fn timezone(&self) -> &str {
+        &self.zone.timezone
+    }
+
+    /// ISO 8601 duration prices are served at, e.g. `PT60M`.
+    async fn resolution(&self, ctx: &Context<'_>) -> async_graphql::Result<&'static str> {
+        Ok(ctx.data::<AppState>()?.resolutions.for_zone(&self.zone.zone_code).as_str())
+    }
+
+    /// Prices between `start` and `end` (RFC 3339, defaulting as on
+    /// `/prices/zone/{zone}`), per `unit`, optionally only those strictly
+    /// below or above a price in that unit.
+    #[allow(clippy::too_many_arguments)]
+    #[graphql(complexity = "PRICES_COMPLEXITY + child_complexity")]
+    async fn prices(
+        &self,
+        ctx: &Context<'_>,
+        start: Option<String>,
+        end: Option<String>,
+        #[graphql(desc = "`day_ahead` (default) or `intraday`.")] market: Option<String>,
+        #[graphql(desc = "`kwh` (default) or `mwh`.")] unit: Option<String>,
+        below: Option<Decimal>,
+        above: Option<Decimal>,
+    ) -> async_graphql::Result<Vec<PricePoint>> {
+        let state = ctx.data::<AppState>()?;
+        let unit = UnitQuery { unit }.parse()?;
+        let range = DateRangeQuery {
+            start,
+            end,
+            timezone: None,
+            clamp: false,
+            market,
+        }
+        .parse(&state.query_defaults.zone, state.clock.now())?;
+
+        let zone = &self.zone;
+        // A range reaching back past a split needs the predecessor's prices
+        let batched = self.listed && zone.valid_from_start().is_none_or(|from| from <= range.start);
+        let prices = match ctx.data_opt::<PriceLoader>() {
+            Some(loader) if batched => loader.zone_prices(state, zone, range.market, range.start, range.end).await,
+            _ => zone_prices(state, &zone.zone_code, range.market, range.start, range.end).await,
+        }
+        .map_err(storage_error)?;
+        let tz: Tz = zone.timezone.parse().unwrap_or(chrono_tz::UTC);
+        Ok(state
+            .resolutions
+            .for_zone(&zone.zone_code)
+            .aggregate(prices)
+            .into_iter()
+            .map(|price| PricePoint::new(price, &tz, unit))
+            .filter(|point| below.is_none_or(|below| point.price < below))
+            .filter(|point| above.is_none_or(|above| point.price > above))
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct PricePoint {
+    /// Start of the interval in the zone's local time.
+    timestamp: String,
+    timestamp_utc: DateTime<Utc>,
+    price: Decimal,
+    currency: String,
+    /// Filled in for a position ENTSOE did not publish, or averaged over one.
+    is_estimated: bool,
+}
+
+impl PricePoint {
+    fn new(price: Price, tz: &Tz, unit: EnergyUnit) -> Self {
+        Self {
+            timestamp: price.timestamp.with_timezone(tz).format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+            timestamp_utc: price.timestamp,
+            price: unit.from_kwh(price.price_kwh),
+            is_estimated: price.filled(),
+            currency: price.currency,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::Request;
+    use chrono::{Duration, TimeZone};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::api::{create_scoped_router, AppState};
+    use crate::config::ListenerScope;
+    use crate::storage::{InMemoryPriceStore, PriceStore};
+
+    async fn query(body: Value) -> Value {
+        let store = InMemoryPriceStore::with_zones(vec![
+            InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+        ]);
+        let start = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+        let mut prices: Vec<Price> = [-5.0, 20.0, 80.0]
+            .into_iter()
+            .enumerate()
+            .map(|(hour, amount)| Price::from_mwh(start + Duration::hours(hour as i64), "NO1".into(), amount, "PT60M".into()))
+            .collect();
+        prices.push(Price::from_mwh(start, "NO2".into(), 40.0, "PT60M".into()));
+        store.upsert_prices(&prices).await.unwrap();
+        let router = create_scoped_router(AppState::for_tests(Arc::new(store)), ListenerScope::All);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/graphql")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_zone_with_filtered_prices_in_one_request() {
+        let response = query(json!({
+            "query": r#"{
+                zone(code: "NO1") {
+                    name
+                    countryCode
+                    prices(start: "2025-03-10T00:00:00Z", end: "2025-03-10T03:00:00Z", unit: "mwh", above: "0") {
+                        timestampUtc
+                        price
+                    }
+                }
+                missing: zone(code: "XX9") { code }
+            }"#
+        }))
+        .await;
+
+        assert_eq!(response.get("errors"), None);
+        let zone = &response["data"]["zone"];
+        assert_eq!(zone["countryCode"], "NO");
+        assert_eq!(
+            zone["prices"],
+            json!([
+                { "timestampUtc": "2025-03-10T01:00:00+00:00", "price": "20" },
+                { "timestampUtc": "2025-03-10T02:00:00+00:00", "price": "80" },
+            ])
+        );
+        assert_eq!(response["data"]["missing"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_arguments_are_reported_as_errors() {
+        let response = query(json!({
+            "query": r#"{ zone(code: "NO1") { prices(unit: "gwh") { price } } }"#
+        }))
+        .await;
+
+        assert_eq!(response["errors"][0]["message"], "Invalid unit: gwh. Use kwh or mwh.");
+    }
+
+    #[tokio::test]
+    async fn test_listed_zones_get_their_own_prices_from_the_country_load() {
+        let response = query(json!({
+            "query": r#"{
+                country(code: "NO") {
+                    zones {
+                        code
+                        prices(start: "2025-03-10T00:00:00Z", end: "2025-03-10T01:00:00Z", unit: "mwh") { price }
+                    }
+                }
+            }"#
+        }))
+        .await;
+
+        assert_eq!(response.get("errors"), None);
+        assert_eq!(
+            response["data"]["country"]["zones"],
+            json!([
+                { "code": "NO1", "prices": [{ "price": "-5" }] },
+                { "code": "NO2", "prices": [{ "price": "40" }] },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_queries_over_the_complexity_limit_are_rejected() {
+        let aliases: String = (0..10).map(|i| format!("p{}: prices {{ price }} ", i)).collect();
+        let response = query(json!({ "query": format!(r#"{{ zone(code: "NO1") {{ {} }} }}"#, aliases) })).await;
+
+        assert_eq!(response["errors"][0]["message"], "Query is too complex.");
+        assert_eq!(response["data"], Value::Null);
+    }
+}
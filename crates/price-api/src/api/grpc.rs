@@ -0,0 +1,319 @@
+//! gRPC price service (`proto/prices.proto`) for internal consumers that
+//! prefer protobuf and streaming to the REST API. It serves the same prices,
+//! through the same cache and request coalescing.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use rust_decimal::Decimal;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+use tracing::warn;
+
+use crate::config::GrpcConfig;
+use crate::models::{EnergyUnit, Market, Price};
+use crate::storage::StorageError;
+
+use super::dto::DateRangeQuery;
+use super::handlers::{active_zones, latest_at_resolution, latest_prices, zone_by_code, zone_prices};
+use super::routes::AppState;
+
+pub mod proto {
+    tonic::include_proto!("entsoe.prices.v1");
+}
+
+use proto::price_service_server::{PriceService, PriceServiceServer};
+
+/// Serve the price service on `listener` until it fails.
+pub async fn serve_grpc(listener: TcpListener, state: AppState, config: &GrpcConfig) -> Result<(), tonic::transport::Error> {
+    let service = GrpcPriceService {
+        state,
+        poll_interval: Duration::from_secs(config.stream_poll_seconds.max(1)),
+    };
+    tonic::transport::Server::builder()
+        .add_service(PriceServiceServer::new(service))
+        .serve_with_incoming(TcpIncoming::from(listener))
+        .await
+}
+
+pub struct GrpcPriceService {
+    state: AppState,
+    poll_interval: Duration,
+}
+
+type PriceStream = Pin<Box<dyn Stream<Item = Result<proto::Price, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl PriceService for GrpcPriceService {
+    async fn get_zone_prices(
+        &self,
+        request: Request<proto::GetZonePricesRequest>,
+    ) -> Result<Response<proto::GetZonePricesResponse>, Status> {
+        let request = request.into_inner();
+        let unit = energy_unit(request.unit());
+        let range = DateRangeQuery {
+            start: request.start.map(rfc3339).transpose()?,
+            end: request.end.map(rfc3339).transpose()?,
+            timezone: None,
+            clamp: false,
+            market: Some(market(request.market()).as_str().to_string()),
+        }
+        .parse(&self.state.query_defaults.zone, self.state.clock.now())
+        .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let zone = zone_by_code(&self.state, &request.zone_code.to_uppercase()).await.map_err(status)?;
+        let prices = zone_prices(&self.state, &zone.zone_code, range.market, range.start, range.end)
+            .await
+            .map_err(status)?;
+        let prices = self.state.resolutions.for_zone(&zone.zone_code).aggregate(prices);
+
+        Ok(Response::new(proto::GetZonePricesResponse {
+            zone_code: zone.zone_code,
+            timezone: zone.timezone,
+            prices: prices.iter().map(|price| price_message(price, unit)).collect(),
+        }))
+    }
+
+    async fn get_latest_prices(
+        &self,
+        request: Request<proto::GetLatestPricesRequest>,
+    ) -> Result<Response<proto::GetLatestPricesResponse>, Status> {
+        let request = request.into_inner();
+        let unit = energy_unit(request.unit());
+        let zone_codes = self.zone_codes(&request.zone_codes).await?;
+        let prices = latest_prices(&self.state).await.map_err(status)?;
+
+        Ok(Response::new(proto::GetLatestPricesResponse {
+            prices: prices
+                .iter()
+                .filter(|price| zone_codes.contains(&price.bidding_zone))
+                .map(|price| price_message(price, unit))
+                .collect(),
+        }))
+    }
+
+    type StreamPricesStream = PriceStream;
+
+    async fn stream_prices(
+        &self,
+        request: Request<proto::StreamPricesRequest>,
+    ) -> Result<Response<Self::StreamPricesStream>, Status> {
+        let request = request.into_inner();
+        let unit = energy_unit(request.unit());
+        let zone_codes = self.zone_codes(&request.zone_codes).await?;
+
+        let (tx, rx) = mpsc::channel(zone_codes.len().max(1));
+        let state = self.state.clone();
+        let mut ticker = tokio::time::interval(self.poll_interval);
+        tokio::spawn(async move {
+            let mut sent: HashMap<String, (DateTime<Utc>, Decimal)> = HashMap::new();
+            loop {
+                ticker.tick().await;
+                let prices = match current_prices(&state).await {
+                    Ok(prices) => prices,
+                    Err(e) => {
+                        warn!(error = %e, "Failed to load current prices for a gRPC price stream");
+                        continue;
+                    }
+                };
+                for price in prices.iter().filter(|price| zone_codes.contains(&price.bidding_zone)) {
+                    let current = (price.timestamp, price.price_kwh);
+                    if sent.get(&price.bidding_zone) == Some(&current) {
+                        continue;
+                    }
+                    if tx.send(Ok(price_message(price, unit))).await.is_err() {
+                        return;
+                    }
+                    sent.insert(price.bidding_zone.clone(), current);
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+impl GrpcPriceService {
+    /// Requested zone codes, uppercased, or every active zone when none are
+    /// named. Unknown zones are rejected.
+    async fn zone_codes(&self, requested: &[String]) -> Result<Vec<String>, Status> {
+        if requested.is_empty() {
+            let zones = active_zones(&self.state).await.map_err(status)?;
+            return Ok(zones.into_iter().map(|zone| zone.zone_code).collect());
+        }
+        let mut zone_codes = Vec::with_capacity(requested.len());
+        for zone_code in requested {
+            zone_codes.push(zone_by_code(&self.state, &zone_code.to_uppercase()).await.map_err(status)?.zone_code);
+        }
+        Ok(zone_codes)
+    }
+}
+
+/// Day-ahead price of each zone for the interval in progress, at the zone's
+/// resolution.
+async fn current_prices(state: &AppState) -> Result<Vec<Price>, StorageError> {
+    let prices = state.repository.get_current_prices(state.clock.now()).await?;
+    latest_at_resolution(state, prices).await
+}
+
+fn price_message(price: &Price, unit: EnergyUnit) -> proto::Price {
+    proto::Price {
+        zone_code: price.bidding_zone.clone(),
+        timestamp: Some(prost_types::Timestamp {
+            seconds: price.timestamp.timestamp(),
+            nanos: price.timestamp.timestamp_subsec_nanos() as i32,
+        }),
+        price: unit.from_kwh(price.price_kwh).to_string(),
+        currency: price.currency.clone(),
+        unit: match unit {
+            EnergyUnit::KilowattHour => proto::Unit::Kwh,
+            EnergyUnit::MegawattHour => proto::Unit::Mwh,
+        } as i32,
+        resolution: price.resolution.clone(),
+        market: match price.market {
+            Market::DayAhead => proto::Market::DayAhead,
+            Market::Intraday => proto::Market::Intraday,
+        } as i32,
+    }
+}
+
+fn energy_unit(unit: proto::Unit) -> EnergyUnit {
+    match unit {
+        proto::Unit::Kwh => EnergyUnit::KilowattHour,
+        proto::Unit::Mwh => EnergyUnit::MegawattHour,
+    }
+}
+
+fn market(market: proto::Market) -> Market {
+    match market {
+        proto::Market::DayAhead => Market::DayAhead,
+        proto::Market::Intraday => Market::Intraday,
+    }
+}
+
+fn rfc3339(timestamp: prost_types::Timestamp) -> Result<String, Status> {
+    DateTime::from_timestamp(timestamp.seconds, timestamp.nanos.try_into().unwrap_or(u32::MAX))
+        .map(|timestamp| timestamp.to_rfc3339())
+        .ok_or_else(|| Status::invalid_argument("Timestamp out of range"))
+}
+
+fn status(e: StorageError) -> Status {
+    if e.is_not_found() {
+        Status::not_found(e.to_string())
+    } else if e.is_connection_error() {
+        Status::unavailable(e.to_string())
+    } else {
+        Status::internal(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::TimeZone;
+
+    use super::proto::price_service_client::PriceServiceClient;
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::storage::{InMemoryPriceStore, PriceStore};
+
+    /// Server over two hours of prices in NO1 and NO2, ending with the hour
+    /// in progress, and a client connected to it.
+    async fn client() -> (PriceServiceClient<tonic::transport::Channel>, DateTime<Utc>) {
+        let store = InMemoryPriceStore::with_zones(vec![
+            InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+        ]);
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let prices: Vec<Price> = [("NO1", 40.0), ("NO1", 45.5), ("NO2", 30.0), ("NO2", 31.25)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (zone, amount))| {
+                Price::from_mwh(start + chrono::Duration::hours(i as i64 % 2), zone.into(), amount, "PT60M".into())
+            })
+            .collect();
+        store.upsert_prices(&prices).await.unwrap();
+
+        let state = AppState::for_tests(Arc::new(store)).with_clock(Arc::new(FixedClock::new(start + chrono::Duration::minutes(90))));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { serve_grpc(listener, state, &GrpcConfig::default()).await.unwrap() });
+        (PriceServiceClient::connect(format!("http://{}", addr)).await.unwrap(), start)
+    }
+
+    #[tokio::test]
+    async fn test_zone_prices_in_requested_unit() {
+        let (mut client, start) = client().await;
+        let response = client
+            .get_zone_prices(proto::GetZonePricesRequest {
+                zone_code: "no1".to_string(),
+                start: Some(prost_types::Timestamp { seconds: start.timestamp(), nanos: 0 }),
+                end: Some(prost_types::Timestamp { seconds: start.timestamp() + 7200, nanos: 0 }),
+                unit: proto::Unit::Mwh as i32,
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.zone_code, "NO1");
+        assert_eq!(response.timezone, "Europe/Oslo");
+        let prices: Vec<&str> = response.prices.iter().map(|price| price.price.as_str()).collect();
+        assert_eq!(prices, ["40", "45.5"]);
+        assert_eq!(response.prices[1].timestamp.unwrap().seconds, start.timestamp() + 3600);
+
+        let missing = client
+            .get_zone_prices(proto::GetZonePricesRequest {
+                zone_code: "XX1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_latest_prices_of_requested_zones() {
+        let (mut client, _) = client().await;
+        let response = client
+            .get_latest_prices(proto::GetLatestPricesRequest {
+                zone_codes: vec!["NO2".to_string()],
+                unit: proto::Unit::Kwh as i32,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.prices.len(), 1);
+        assert_eq!((response.prices[0].zone_code.as_str(), response.prices[0].price.as_str()), ("NO2", "0.03125"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_starts_with_current_prices() {
+        let (mut client, _) = client().await;
+        let mut stream = client
+            .stream_prices(proto::StreamPricesRequest {
+                zone_codes: vec![],
+                unit: proto::Unit::Mwh as i32,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        let mut current = Vec::new();
+        for _ in 0..2 {
+            let price = stream.message().await.unwrap().unwrap();
+            current.push((price.zone_code, price.price));
+        }
+        current.sort();
+        assert_eq!(current, [("NO1".to_string(), "45.5".to_string()), ("NO2".to_string(), "31.25".to_string())]);
+    }
+}
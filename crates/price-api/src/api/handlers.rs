@@ -0,0 +1,1881 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use chrono::{DateTime, DurationRound, Months, NaiveDate, Utc};
+use chrono_tz::Tz;
+use futures::StreamExt;
+use rust_decimal::Decimal;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::entsoe::{parse_resolution, CircuitBreakerStatus};
+use crate::fetcher::{with_correlation_id, JobLocked};
+use crate::fx::FxError;
+use crate::influx;
+use crate::metrics;
+use crate::models::{is_valid_eic, BiddingZone, Consumption, DeliveryDay, Invoice, Market, NewAlertRule, NewBackfillJob, Price, TaskStatus};
+use crate::storage::StorageError;
+
+use super::dto::{
+    price_currency, AlertEventInfo, AlertHistoryQuery, AlertHistoryResponse, AlertRuleInfo, AlertRulesResponse,
+    BackfillJobInfo, BackfillRequest, BackfillResponse, CacheInvalidateQuery, CacheInvalidateResponse,
+    ComparePricesResponse, CompareQuery, ConsumptionProfile, ConsumptionUpload, CountriesResponse,
+    CountryAggregateQuery, CountryAverage, CountryInfo, CountryPricesResponse, CreateAlertRuleRequest, DatabaseState,
+    DateRangeQuery, EicFetchQuery, EicFetchResponse, EntsoeCheck, ErrorCatalogEntry, ErrorCatalogResponse,
+    EstimatedHoursInfo, ExportFormat, ExportFormatQuery, FetchAgeCheck, FetchLogQuery, FetchLogResponse, FetchResponse,
+    FieldsQuery, FlowKindQuery, FlowsResponse, FxRateQuery, FxRateResponse, GapInfo, GapReportQuery, GapReportResponse,
+    GenerationResponse, HealthResponse, HomeAssistantResponse, IncludeQuery, InvoiceQuery, LangQuery,
+    LatestPricesResponse, LimitsResponse, LoadResponse, MetricCatalogEntry, MetricCatalogResponse,
+    NegativePricesResponse, PriceAtQuery, PriceAtResponse, PricePoint, PruneFetchLogRequest, PruneFetchLogResponse,
+    RangeClamp, ReadinessChecks, ReadyResponse, RetentionStatus, SchedulerCheck, SchedulerJobInfo,
+    SchedulerJobsResponse, SchedulerState, StatusResponse, StorageStatsResponse, TableStatsInfo, TaskInfo, TasksQuery,
+    TasksResponse, ThresholdQuery, TimezoneQuery, Translations, UnitQuery, ZoneFetchQuery, ZoneFetchResponse, ZoneInfo,
+    ZoneInvoiceResponse, ZonePricesResponse, ZoneStatsResponse, ZonesResponse, MAX_BACKFILL_DAYS,
+    MAX_BACKFILL_JOB_DAYS,
+};
+use super::error::{AppError, AppErrorWithContext, ErrorCode};
+use super::extract::{Json, Query};
+use super::fields::{PointShape, Shaped};
+use super::middleware::CorrelationId;
+use super::rate_limit::RateLimitStatus;
+use super::routes::AppState;
+use super::zone_metrics::ZoneRequestMetrics;
+
+/// Keeps `/ready` well inside a typical probe timeout when ENTSOE hangs.
+const ENTSOE_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Size at which an export sends the lines it has buffered.
+const EXPORT_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Export chunks queued ahead of a slow client before the query waits.
+const EXPORT_BUFFERED_CHUNKS: usize = 4;
+
+pub async fn health_check() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        timestamp: Utc::now(),
+    })
+}
+
+pub async fn metrics_catalog() -> Json<MetricCatalogResponse> {
+    Json(MetricCatalogResponse {
+        metrics: metrics::METRIC_CATALOG.iter().map(MetricCatalogEntry::from).collect(),
+    })
+}
+
+/// Every error `code` the API reports, for clients to branch on.
+pub async fn error_catalog() -> Json<ErrorCatalogResponse> {
+    Json(ErrorCatalogResponse {
+        errors: ErrorCode::ALL.into_iter().map(ErrorCatalogEntry::from).collect(),
+    })
+}
+
+pub async fn ready_check(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<(StatusCode, Json<ReadyResponse>), AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    // A lazily connected database has not answered yet
+    if state.readiness.database_pending() {
+        let now = state.clock.now();
+        return Ok((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadyResponse {
+                status: "starting".to_string(),
+                database: "connecting".to_string(),
+                active_zones: 0,
+                zones_with_today_data: None,
+                checks: ReadinessChecks {
+                    scheduler: SchedulerCheck::new(state.scheduler.as_deref(), now),
+                    last_fetch: FetchAgeCheck::new(None, state.readiness.config().max_fetch_age_hours, now),
+                    entsoe: None,
+                },
+                timestamp: now,
+            }),
+        ));
+    }
+
+    let result = state.repository.health_check().await;
+    result.map_err(|e| AppError::DatabaseError(e).with_correlation_id(cid.clone()))?;
+
+    let zones = state
+        .repository
+        .load_zones()
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    metrics::update_active_zones(zones.len() as u64);
+
+    // An empty zone registry means every fetch and query is vacuous; report
+    // degraded so the deployment is not considered healthy.
+    if zones.is_empty() {
+        metrics::record_empty_zone_registry("readiness");
+    }
+
+    // Until the first data load, a fresh replica would serve empty responses
+    let mut zones_with_today_data = None;
+    if !zones.is_empty() && !state.readiness.is_open() {
+        let today = state.clock.today();
+        let zone_codes: Vec<String> = zones.iter().map(|z| z.zone_code.clone()).collect();
+        let empty_zones = state
+            .repository
+            .find_gaps(today, today, &zone_codes)
+            .await
+            .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?
+            .into_iter()
+            .filter(|gap| gap.hours == 0)
+            .count();
+
+        let with_data = zones.len() - empty_zones;
+        if !state.readiness.evaluate(with_data, zones.len()) {
+            zones_with_today_data = Some(with_data);
+        }
+    }
+
+    let now = state.clock.now();
+    let last_success_at = state
+        .repository
+        .get_last_successful_fetch()
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    let config = state.readiness.config();
+    let entsoe = match &state.fetcher {
+        Some(fetcher) if config.probe_entsoe => {
+            let client = fetcher.client();
+            Some(EntsoeCheck::new(client.primary_base_url(), client.probe(ENTSOE_PROBE_TIMEOUT).await))
+        }
+        _ => None,
+    };
+    let checks = ReadinessChecks {
+        scheduler: SchedulerCheck::new(state.scheduler.as_deref(), now),
+        last_fetch: FetchAgeCheck::new(last_success_at, config.max_fetch_age_hours, now),
+        entsoe,
+    };
+
+    let (status_code, status) = if zones.is_empty() || !checks.passed() {
+        (StatusCode::SERVICE_UNAVAILABLE, "degraded")
+    } else if zones_with_today_data.is_some() {
+        (StatusCode::SERVICE_UNAVAILABLE, "warming_up")
+    } else {
+        (StatusCode::OK, "ready")
+    };
+
+    Ok((
+        status_code,
+        Json(ReadyResponse {
+            status: status.to_string(),
+            database: "connected".to_string(),
+            active_zones: zones.len(),
+            zones_with_today_data,
+            checks,
+            timestamp: now,
+        }),
+    ))
+}
+
+pub async fn service_status(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<StatusResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let db_error = state.repository.health_check().await.err();
+    if let Some(e) = &db_error {
+        warn!(error = %e, "Status database check failed");
+    }
+    let database = DatabaseState::new(state.repository.pool_status(), db_error.as_ref());
+
+    // Zone freshness needs the database; report the outage instead of failing
+    let now = state.clock.now();
+    let zones = if database.error.is_none() {
+        let zones = active_zones(&state)
+            .await
+            .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+        state
+            .zone_status
+            .zone_freshness(state.repository.as_ref(), &zones, now, state.clock.tomorrow())
+            .await
+            .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?
+    } else {
+        Vec::new()
+    };
+
+    let scheduler = SchedulerState {
+        enabled: state.scheduler.is_some(),
+        jobs: state.scheduler.as_ref().map(|history| history.jobs()).unwrap_or_default(),
+    };
+
+    Ok(Json(StatusResponse::new(
+        scheduler,
+        database,
+        state.cache.as_ref().map(|cache| cache.stats()),
+        state.fetcher.as_ref().map(|fetcher| fetcher.client().circuit_breaker().status()),
+        zones,
+        now,
+    )))
+}
+
+/// The rate limit middleware has already counted this request.
+pub async fn rate_limits(status: Option<Extension<RateLimitStatus>>) -> Json<LimitsResponse> {
+    Json(LimitsResponse {
+        enabled: status.is_some(),
+        current: status.map(|Extension(status)| status),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn get_prices_by_zone(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<DateRangeQuery>,
+    Query(lang_query): Query<LangQuery>,
+    Query(include_query): Query<IncludeQuery>,
+    Query(fields_query): Query<FieldsQuery>,
+    Query(unit_query): Query<UnitQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Shaped<ZonePricesResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("zone");
+    let cid = Some(correlation_id.0.clone());
+    let unit = unit_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("unit", e).with_correlation_id(cid.clone()))?;
+    let range = query
+        .parse(&state.query_defaults.zone, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+    let lang = lang_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("lang", e).with_correlation_id(cid.clone()))?;
+    let tariffs = include_query
+        .consumer_price()
+        .map_err(|e| AppError::invalid_parameter("include", e).with_correlation_id(cid.clone()))?
+        .then_some(&state.tariffs);
+    let fields = fields_query
+        .parse(PointShape::ZONE)
+        .map_err(|e| AppError::invalid_parameter("fields", e).with_correlation_id(cid.clone()))?;
+    let (start, end, market) = (range.start, range.end, range.market);
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+    let translations = translations(&state, lang)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    if query.clamp {
+        let clamp = clamp_range(&state, &zone_code, market, start, end)
+            .await
+            .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+        let prices = match clamp.range() {
+            Some((start, end)) => zone_prices(&state, &zone_code, market, start, end)
+                .await
+                .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?,
+            None => Vec::new(),
+        };
+        return Ok(Shaped(
+            ZonePricesResponse::new(
+                &zone,
+                market,
+                prices,
+                state.resolutions.for_zone(&zone.zone_code),
+                query.timezone.as_deref(),
+            )
+            .with_clamp(clamp)
+            .with_defaults(range.defaults)
+            .with_consumer_prices(tariffs.and_then(|tariffs| tariffs.for_zone(&zone.zone_code)))
+            .in_unit(unit)
+            .localize(&translations),
+            fields,
+        ));
+    }
+
+    let prices = zone_prices(&state, &zone_code, market, start, end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Shaped(
+        ZonePricesResponse::new(
+            &zone,
+            market,
+            prices,
+            state.resolutions.for_zone(&zone.zone_code),
+            query.timezone.as_deref(),
+        )
+        .with_defaults(range.defaults)
+        .with_consumer_prices(tariffs.and_then(|tariffs| tariffs.for_zone(&zone.zone_code)))
+        .in_unit(unit)
+        .localize(&translations),
+        fields,
+    ))
+}
+
+/// Stream a zone's stored prices as newline-delimited JSON, one
+/// [`PricePoint`] per line at the stored resolution, or as InfluxDB line
+/// protocol with `format=influx`. Rows are read from the database as the
+/// client consumes them, so multi-month exports keep memory flat. A database
+/// error mid-export aborts the response.
+pub async fn export_zone_prices(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<DateRangeQuery>,
+    Query(format_query): Query<ExportFormatQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Response, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+    let format = format_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("format", e).with_correlation_id(cid.clone()))?;
+    let range = query
+        .parse(&state.query_defaults.zone, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    let tz: Tz = query
+        .timezone
+        .as_deref()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(|| zone.timezone.parse().unwrap_or(chrono_tz::UTC));
+
+    // The stream borrows the repository, so it is driven on its own task
+    let (tx, mut rx) = mpsc::channel::<Result<Bytes, StorageError>>(EXPORT_BUFFERED_CHUNKS);
+    let repository = state.repository.clone();
+    tokio::spawn(async move {
+        let mut prices = repository.stream_prices_by_zone(&zone.zone_code, range.market, range.start, range.end);
+        let mut chunk = Vec::with_capacity(EXPORT_CHUNK_BYTES);
+        let mut line = String::new();
+        while let Some(price) = prices.next().await {
+            let price = match price {
+                Ok(price) => price,
+                Err(e) => {
+                    warn!(zone = %zone.zone_code, error = %e, "Price export failed");
+                    let _ = tx.send(Err(e)).await;
+                    return;
+                }
+            };
+            match format {
+                ExportFormat::Ndjson => {
+                    serde_json::to_writer(&mut chunk, &PricePoint::new(&price, &tz)).expect("price points serialize");
+                    chunk.push(b'\n');
+                }
+                ExportFormat::Influx => {
+                    line.clear();
+                    influx::write_line(&mut line, &price);
+                    chunk.extend_from_slice(line.as_bytes());
+                }
+            }
+            if chunk.len() >= EXPORT_CHUNK_BYTES && tx.send(Ok(std::mem::take(&mut chunk).into())).await.is_err() {
+                // Client went away
+                return;
+            }
+        }
+        if !chunk.is_empty() {
+            let _ = tx.send(Ok(chunk.into())).await;
+        }
+    });
+
+    let body = Body::from_stream(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)));
+    Ok(([(header::CONTENT_TYPE, format.content_type())], body).into_response())
+}
+
+pub async fn get_zone_stats(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<DateRangeQuery>,
+    Query(unit_query): Query<UnitQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<ZoneStatsResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("zone_stats");
+    let cid = Some(correlation_id.0.clone());
+    let unit = unit_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("unit", e).with_correlation_id(cid.clone()))?;
+    let range = query
+        .parse(&state.query_defaults.stats, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+    let (start, end) = (range.start, range.end);
+
+    let zone = state
+        .repository
+        .get_zone_by_code(&zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+
+    let stats = state
+        .repository
+        .get_zone_stats(&zone.zone_code, range.market, start, end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(
+        ZoneStatsResponse::new(zone.zone_code, range.market, start, end, stats)
+            .with_defaults(range.defaults)
+            .in_unit(unit),
+    ))
+}
+
+pub async fn get_negative_prices(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<DateRangeQuery>,
+    Query(threshold_query): Query<ThresholdQuery>,
+    Query(unit_query): Query<UnitQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<NegativePricesResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("negative_prices");
+    let cid = Some(correlation_id.0.clone());
+    let unit = unit_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("unit", e).with_correlation_id(cid.clone()))?;
+    let range = query
+        .parse(&state.query_defaults.negative, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+    let threshold = threshold_query
+        .parse(unit)
+        .map_err(|e| AppError::invalid_parameter("threshold", e).with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+
+    let prices = state
+        .repository
+        .get_prices_by_zone(&zone.zone_code, range.market, range.start, range.end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(
+        NegativePricesResponse::new(&zone, &range, threshold, &prices, query.timezone.as_deref()).in_unit(unit),
+    ))
+}
+
+/// The price interval covering `timestamp`, for billing systems pricing
+/// individual meter readings.
+pub async fn get_price_at(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<PriceAtQuery>,
+    Query(fields_query): Query<FieldsQuery>,
+    Query(unit_query): Query<UnitQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Shaped<PriceAtResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("price_at");
+    let cid = Some(correlation_id.0.clone());
+    let unit = unit_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("unit", e).with_correlation_id(cid.clone()))?;
+    let fields = fields_query
+        .parse(PointShape::PRICE_AT)
+        .map_err(|e| AppError::invalid_parameter("fields", e).with_correlation_id(cid.clone()))?;
+    let (at, market) = query
+        .parse()
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+
+    // Before the zone's valid_from, `at` is priced by the predecessor it replaced
+    let lineage = zone_lineage(&state, &zone.zone_code, at, at + chrono::Duration::nanoseconds(1))
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    let priced_zone = lineage.first().map_or(zone.zone_code.as_str(), |(code, _, _)| code.as_str());
+    let price = state
+        .repository
+        .get_price_at(priced_zone, market, at)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("No {} price for {} covers {}", market.as_str(), zone.zone_code, at.to_rfc3339()))
+                .with_correlation_id(cid.clone())
+        })?;
+
+    Ok(Shaped(PriceAtResponse::new(&zone, price, at, query.timezone.as_deref()).in_unit(unit), fields))
+}
+
+/// Today's and tomorrow's day-ahead prices in the zone's local time, for
+/// Home Assistant REST sensors.
+pub async fn get_home_assistant_prices(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(unit_query): Query<UnitQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<HomeAssistantResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("home_assistant");
+    let cid = Some(correlation_id.0.clone());
+    let unit = unit_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("unit", e).with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+    let tz: Tz = zone.timezone.parse().unwrap_or(chrono_tz::UTC);
+    let now = state.clock.now();
+    let today = DeliveryDay::new(now.with_timezone(&tz).date_naive(), &tz);
+    let tomorrow = DeliveryDay::new(today.end.with_timezone(&tz).date_naive(), &tz);
+
+    let prices = zone_prices(&state, &zone.zone_code, Market::DayAhead, today.start, tomorrow.end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(
+        HomeAssistantResponse::new(&zone, &tz, today, prices, state.resolutions.for_zone(&zone.zone_code), unit, now),
+    ))
+}
+
+/// Invoice for a month weighted by the zone's stored load.
+pub async fn get_zone_invoice(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<InvoiceQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<ZoneInvoiceResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("invoice");
+    let cid = Some(correlation_id.0.clone());
+    let month = query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("month", e).with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+    let (start, end) = month_bounds(&zone, month);
+
+    let load = state
+        .repository
+        .get_load_by_zone(&zone.zone_code, start, end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    let consumption: Vec<Consumption> = load
+        .iter()
+        .filter_map(|value| {
+            let duration = parse_resolution(&value.resolution).ok()?;
+            let hours = Decimal::from(duration.num_seconds()) / Decimal::from(3600);
+            Some(Consumption {
+                timestamp: value.timestamp,
+                duration,
+                kwh: value.load_mw * hours * Decimal::from(1000),
+            })
+        })
+        .collect();
+    if consumption.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No load stored for {} in {}",
+            zone.zone_code,
+            month.format("%Y-%m")
+        ))
+        .with_correlation_id(cid));
+    }
+
+    zone_invoice(&state, &zone, month, (start, end), ConsumptionProfile::ZoneLoad, &consumption)
+        .await
+        .map(Json)
+        .map_err(|e| e.with_correlation_id(cid))
+}
+
+/// Invoice for a month weighted by an uploaded consumption profile.
+pub async fn create_zone_invoice(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<InvoiceQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+    Json(upload): Json<ConsumptionUpload>,
+) -> Result<Json<ZoneInvoiceResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("invoice");
+    let cid = Some(correlation_id.0.clone());
+    let month = query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("month", e).with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+    let (start, end) = month_bounds(&zone, month);
+    let consumption = upload
+        .parse(start, end)
+        .map_err(|e| AppError::BadRequest(e).with_correlation_id(cid.clone()))?;
+
+    zone_invoice(&state, &zone, month, (start, end), ConsumptionProfile::Uploaded, &consumption)
+        .await
+        .map(Json)
+        .map_err(|e| e.with_correlation_id(cid))
+}
+
+/// UTC bounds of `month` in the zone's local time.
+fn month_bounds(zone: &BiddingZone, month: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let tz: Tz = zone.timezone.parse().unwrap_or(chrono_tz::UTC);
+    let next = month.checked_add_months(Months::new(1)).expect("InvoiceQuery limits years to 9999");
+    (DeliveryDay::new(month, &tz).start, DeliveryDay::new(next, &tz).start)
+}
+
+async fn zone_invoice(
+    state: &AppState,
+    zone: &BiddingZone,
+    month: NaiveDate,
+    (start, end): (DateTime<Utc>, DateTime<Utc>),
+    profile: ConsumptionProfile,
+    consumption: &[Consumption],
+) -> Result<ZoneInvoiceResponse, AppError> {
+    let prices = zone_prices(state, &zone.zone_code, Market::DayAhead, start, end).await?;
+    if prices.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No prices stored for {} in {}",
+            zone.zone_code,
+            month.format("%Y-%m")
+        )));
+    }
+
+    let tariff = state.tariffs.for_zone(&zone.zone_code);
+    let invoice = Invoice::compute(&prices, consumption, tariff);
+    Ok(ZoneInvoiceResponse::new(
+        zone.zone_code.clone(),
+        month,
+        (start, end),
+        price_currency(&prices),
+        profile,
+        tariff,
+        &invoice,
+    ))
+}
+
+pub async fn get_generation_by_zone(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<DateRangeQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<GenerationResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("generation");
+    let cid = Some(correlation_id.0.clone());
+    let range = query
+        .parse(&state.query_defaults.generation, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+
+    let points = state
+        .repository
+        .get_generation_by_zone(&zone.zone_code, range.start, range.end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(
+        GenerationResponse::new(&zone, points, query.timezone.as_deref()).with_defaults(range.defaults),
+    ))
+}
+
+pub async fn get_load_by_zone(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<DateRangeQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<LoadResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("load");
+    let cid = Some(correlation_id.0.clone());
+    let range = query
+        .parse(&state.query_defaults.load, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+
+    let values = state
+        .repository
+        .get_load_by_zone(&zone.zone_code, range.start, range.end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(
+        LoadResponse::new(&zone, values, query.timezone.as_deref()).with_defaults(range.defaults),
+    ))
+}
+
+pub async fn get_flows_by_zone(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<DateRangeQuery>,
+    Query(kind_query): Query<FlowKindQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<FlowsResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("flows");
+    let cid = Some(correlation_id.0.clone());
+    let range = query
+        .parse(&state.query_defaults.flows, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+    let kind = kind_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("kind", e).with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+    zone_metrics.resolved(&zone.zone_code);
+
+    let flows = state
+        .repository
+        .get_flows_by_zone(&zone.zone_code, kind, range.start, range.end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(
+        FlowsResponse::new(&zone, kind, flows, query.timezone.as_deref()).with_defaults(range.defaults),
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn get_prices_by_country(
+    State(state): State<AppState>,
+    Path(country_code): Path<String>,
+    Query(query): Query<DateRangeQuery>,
+    Query(lang_query): Query<LangQuery>,
+    Query(include_query): Query<IncludeQuery>,
+    Query(fields_query): Query<FieldsQuery>,
+    Query(unit_query): Query<UnitQuery>,
+    Query(aggregate_query): Query<CountryAggregateQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Shaped<CountryPricesResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("country");
+    let cid = Some(correlation_id.0.clone());
+    let unit = unit_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("unit", e).with_correlation_id(cid.clone()))?;
+    let range = query
+        .parse(&state.query_defaults.country, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+    let lang = lang_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("lang", e).with_correlation_id(cid.clone()))?;
+    let (consumer_price, sections) = include_query
+        .country()
+        .map_err(|e| AppError::invalid_parameter("include", e).with_correlation_id(cid.clone()))?;
+    let tariffs = consumer_price.then_some(&state.tariffs);
+    let fields = fields_query
+        .parse(PointShape::COUNTRY)
+        .map_err(|e| AppError::invalid_parameter("fields", e).with_correlation_id(cid.clone()))?;
+    let average = aggregate_query
+        .average()
+        .map_err(|e| AppError::invalid_parameter("aggregate", e).with_correlation_id(cid.clone()))?;
+    // Tariffs, clamps and series are per zone, which an average no longer has
+    if average && (tariffs.is_some() || query.clamp || include_query.names("zones")) {
+        return Err(AppError::BadRequest(
+            "aggregate=average cannot be combined with clamp, include=consumer_price or include=zones".to_string(),
+        )
+        .with_correlation_id(cid));
+    }
+    let (start, end, market) = (range.start, range.end, range.market);
+
+    let zones = state
+        .repository
+        .get_zones_by_country(&country_code)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    if zones.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "Country not found: {}",
+            country_code
+        ))
+        .with_correlation_id(cid));
+    }
+
+    let country_name = zones.first().map(|z| z.country_name.clone()).unwrap();
+    for zone in &zones {
+        zone_metrics.resolved(&zone.zone_code);
+    }
+    let translations = translations(&state, lang)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    if query.clamp {
+        let mut prices_by_zone = HashMap::new();
+        let mut clamps = HashMap::new();
+        for zone in &zones {
+            let clamp = clamp_range(&state, &zone.zone_code, market, start, end)
+                .await
+                .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+            if let Some((start, end)) = clamp.range() {
+                let prices = zone_prices(&state, &zone.zone_code, market, start, end)
+                    .await
+                    .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+                if !prices.is_empty() {
+                    prices_by_zone.insert(zone.zone_code.clone(), prices);
+                }
+            }
+            clamps.insert(zone.zone_code.clone(), clamp);
+        }
+
+        return Ok(Shaped(
+            CountryPricesResponse::new(
+                country_code,
+                country_name,
+                market,
+                &zones,
+                prices_by_zone,
+                &state.resolutions,
+                query.timezone.as_deref(),
+            )
+            .with_clamps(clamps)
+            .with_defaults(range.defaults)
+            .with_consumer_prices(tariffs)
+            .with_sections(sections)
+            .in_unit(unit)
+            .localize(&translations),
+            fields,
+        ));
+    }
+
+    let prices_by_zone = state
+        .repository
+        .get_prices_by_country(&country_code, market, start, end)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    if average {
+        let tz: Tz = query
+            .timezone
+            .as_deref()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| zones[0].timezone.parse().unwrap_or(chrono_tz::UTC));
+        let average = CountryAverage::new(&zones, &prices_by_zone, &state.resolutions, &state.country_average, &tz);
+        return Ok(Shaped(
+            CountryPricesResponse::new(country_code, country_name, market, &zones, prices_by_zone, &state.resolutions, None)
+                .with_sections(sections)
+                .with_average(average)
+                .with_defaults(range.defaults)
+                .in_unit(unit)
+                .localize(&translations),
+            fields,
+        ));
+    }
+
+    Ok(Shaped(
+        CountryPricesResponse::new(
+            country_code,
+            country_name,
+            market,
+            &zones,
+            prices_by_zone,
+            &state.resolutions,
+            query.timezone.as_deref(),
+        )
+        .with_defaults(range.defaults)
+        .with_consumer_prices(tariffs)
+        .with_sections(sections)
+        .in_unit(unit)
+        .localize(&translations),
+        fields,
+    ))
+}
+
+pub async fn compare_prices(
+    State(state): State<AppState>,
+    Query(compare_query): Query<CompareQuery>,
+    Query(query): Query<DateRangeQuery>,
+    Query(fields_query): Query<FieldsQuery>,
+    Query(unit_query): Query<UnitQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Shaped<ComparePricesResponse>, AppErrorWithContext> {
+    let mut zone_metrics = ZoneRequestMetrics::start("compare");
+    let cid = Some(correlation_id.0.clone());
+    let unit = unit_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("unit", e).with_correlation_id(cid.clone()))?;
+    let fields = fields_query
+        .parse(PointShape::COMPARE)
+        .map_err(|e| AppError::invalid_parameter("fields", e).with_correlation_id(cid.clone()))?;
+    let zone_codes = compare_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("zones", e).with_correlation_id(cid.clone()))?;
+    let range = query
+        .parse(&state.query_defaults.compare, state.clock.now())
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    let mut zones = Vec::with_capacity(zone_codes.len());
+    let mut prices_by_zone = HashMap::new();
+    for zone_code in &zone_codes {
+        let zone = zone_by_code(&state, zone_code)
+            .await
+            .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+        zone_metrics.resolved(&zone.zone_code);
+        let prices = zone_prices(&state, &zone.zone_code, range.market, range.start, range.end)
+            .await
+            .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+        prices_by_zone.insert(zone.zone_code.clone(), prices);
+        zones.push(zone);
+    }
+
+    let response = ComparePricesResponse::new(
+        &zones,
+        range.market,
+        prices_by_zone,
+        &state.resolutions,
+        query.timezone.as_deref(),
+    )
+    .map_err(|e| AppError::BadRequest(e).with_correlation_id(cid))?;
+
+    Ok(Shaped(response.with_defaults(range.defaults).in_unit(unit), fields))
+}
+
+pub async fn get_latest_prices(
+    State(state): State<AppState>,
+    Query(query): Query<TimezoneQuery>,
+    Query(lang_query): Query<LangQuery>,
+    Query(fields_query): Query<FieldsQuery>,
+    Query(unit_query): Query<UnitQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Shaped<LatestPricesResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+    let unit = unit_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("unit", e).with_correlation_id(cid.clone()))?;
+    let fields = fields_query
+        .parse(PointShape::LATEST)
+        .map_err(|e| AppError::invalid_parameter("fields", e).with_correlation_id(cid.clone()))?;
+    let lang = lang_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("lang", e).with_correlation_id(cid.clone()))?;
+
+    let prices = latest_prices(&state)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    let zones = active_zones(&state)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    let translations = translations(&state, lang)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Shaped(
+        LatestPricesResponse::new(prices, &zones, query.timezone.as_deref())
+            .in_unit(unit)
+            .localize(&translations),
+        fields,
+    ))
+}
+
+pub async fn list_zones(
+    State(state): State<AppState>,
+    Query(lang_query): Query<LangQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<ZonesResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+    let lang = lang_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("lang", e).with_correlation_id(cid.clone()))?;
+
+    let zones = active_zones(&state)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    let translations = translations(&state, lang)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    let zone_infos: Vec<ZoneInfo> = zones
+        .iter()
+        .map(|zone| ZoneInfo::from(zone).localize(&translations))
+        .collect();
+
+    Ok(Json(ZonesResponse { zones: zone_infos }))
+}
+
+/// The EUR rate for `currency` on `?date=`, as published by the source
+/// configured for it. Billing in NOK or SEK has to use the national bank's
+/// rate, not the ECB reference rate.
+pub async fn get_fx_rate(
+    State(state): State<AppState>,
+    Path(currency): Path<String>,
+    Query(query): Query<FxRateQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<FxRateResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+    let fx = state
+        .fx
+        .as_ref()
+        .ok_or_else(|| AppError::NotFound("Exchange rates are not enabled".to_string()).with_correlation_id(cid.clone()))?;
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(AppError::BadRequest(format!("Invalid currency: {}. Use an ISO 4217 code, e.g. NOK.", currency))
+            .with_correlation_id(cid));
+    }
+    let date = query
+        .parse(state.clock.now().date_naive())
+        .map_err(|e| AppError::invalid_parameter("date", e).with_correlation_id(cid.clone()))?;
+
+    let rate = fx.rate(&currency, date).await.map_err(|e| {
+        let error = match e {
+            FxError::NoRate { .. } => AppError::NotFound(e.to_string()),
+            FxError::UnsupportedCurrency { .. } => AppError::BadRequest(e.to_string()),
+            _ => {
+                warn!(currency = %currency, error = %e, "Failed to fetch exchange rate");
+                AppError::InternalError(e.to_string())
+            }
+        };
+        error.with_correlation_id(cid.clone())
+    })?;
+    Ok(Json(FxRateResponse::new(rate, date)))
+}
+
+pub async fn list_countries(
+    State(state): State<AppState>,
+    Query(lang_query): Query<LangQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<CountriesResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+    let lang = lang_query
+        .parse()
+        .map_err(|e| AppError::invalid_parameter("lang", e).with_correlation_id(cid.clone()))?;
+
+    let countries = state
+        .repository
+        .get_countries()
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    let translations = translations(&state, lang)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    let country_infos: Vec<CountryInfo> = countries
+        .into_iter()
+        .map(|(code, name)| CountryInfo {
+            localized_country_name: translations.country(&code),
+            country_code: code,
+            country_name: name,
+        })
+        .collect();
+
+    Ok(Json(CountriesResponse {
+        countries: country_infos,
+    }))
+}
+
+pub async fn trigger_fetch(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<FetchResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let fetcher = state
+        .fetcher
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Fetcher not configured".into()).with_correlation_id(cid.clone()))?;
+
+    let start = Instant::now();
+    let summary = with_correlation_id(correlation_id.0, fetcher.fetch_all_prices())
+        .await
+        .map_err(|e| fetch_error(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(FetchResponse {
+        status: if summary.failed == 0 {
+            "success".to_string()
+        } else {
+            "partial".to_string()
+        },
+        succeeded: summary.succeeded,
+        failed: summary.failed,
+        no_data: summary.no_data,
+        total_prices_stored: summary.total_prices_stored,
+        errors: summary.errors,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }))
+}
+
+pub async fn trigger_zone_fetch(
+    State(state): State<AppState>,
+    Path(zone_code): Path<String>,
+    Query(query): Query<ZoneFetchQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<ZoneFetchResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let fetcher = state
+        .fetcher
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Fetcher not configured".into()).with_correlation_id(cid.clone()))?;
+
+    let date = match &query.date {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| {
+            AppError::invalid_parameter("date", format!("Invalid date: {}. Use YYYY-MM-DD format.", e))
+                .with_correlation_id(cid.clone())
+        })?,
+        None => state.clock.today(),
+    };
+
+    let zone = state
+        .repository
+        .get_zone_by_code(&zone_code)
+        .await
+        .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+
+    let start = Instant::now();
+    let result = with_correlation_id(correlation_id.0, fetcher.fetch_zone(&zone, date))
+        .await
+        .map_err(|e| fetch_error(e).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(ZoneFetchResponse {
+        fetch_id: result.fetch_id,
+        zone_code: result.zone_code,
+        date: date.to_string(),
+        status: result.status.as_str().to_string(),
+        prices_stored: result.prices_stored,
+        error: result.error,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }))
+}
+
+/// 409 while a scheduled or other manual run of the same fetch holds its lock.
+fn fetch_error(e: anyhow::Error) -> AppError {
+    if e.is::<JobLocked>() {
+        AppError::JobAlreadyRunning(e.to_string())
+    } else {
+        AppError::InternalError(e.to_string())
+    }
+}
+
+/// Fetch prices for an EIC code that need not be in the zone registry, without
+/// storing them, to evaluate an area before onboarding it.
+pub async fn trigger_eic_fetch(
+    State(state): State<AppState>,
+    Path(eic_code): Path<String>,
+    Query(query): Query<EicFetchQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<EicFetchResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let fetcher = state
+        .fetcher
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Fetcher not configured".into()).with_correlation_id(cid.clone()))?;
+
+    if !is_valid_eic(&eic_code) {
+        return Err(AppError::BadRequest(format!("Invalid EIC code: {}", eic_code)).with_correlation_id(cid));
+    }
+
+    let date = match &query.date {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| {
+            AppError::invalid_parameter("date", format!("Invalid date: {}. Use YYYY-MM-DD format.", e))
+                .with_correlation_id(cid.clone())
+        })?,
+        None => state.clock.today(),
+    };
+    let market = match &query.market {
+        Some(m) => Market::parse(m).ok_or_else(|| {
+            AppError::invalid_parameter("market", format!("Invalid market: {}. Use day_ahead or intraday.", m))
+                .with_correlation_id(cid.clone())
+        })?,
+        None => Market::DayAhead,
+    };
+    let timezone = query.timezone.as_deref().unwrap_or("Europe/Brussels");
+    let tz: chrono_tz::Tz = timezone.parse().map_err(|_| {
+        AppError::invalid_parameter("timezone", format!("Invalid timezone: {}", timezone)).with_correlation_id(cid.clone())
+    })?;
+
+    let registered_zone = match state.repository.get_zone_by_eic(&eic_code).await {
+        Ok(zone) => Some(zone.zone_code),
+        Err(StorageError::NotFound(_)) => None,
+        Err(e) => return Err(AppError::from(e).with_correlation_id(cid)),
+    };
+
+    let start = Instant::now();
+    let prices = fetcher
+        .preview_eic(&eic_code, date, market, timezone)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()).with_correlation_id(cid.clone()))?;
+
+    Ok(Json(EicFetchResponse {
+        eic_code,
+        registered_zone,
+        date: date.to_string(),
+        market,
+        timezone: tz.to_string(),
+        currency: price_currency(&prices),
+        unit: "kWh".to_string(),
+        prices: prices.iter().map(|p| PricePoint::new(p, &tz)).collect(),
+        duration_ms: start.elapsed().as_millis() as u64,
+    }))
+}
+
+/// Fill gaps in a date range of at most `MAX_BACKFILL_DAYS`, answering once
+/// it is done. Longer ranges go through [`start_backfill_job`].
+pub async fn backfill_prices(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+    Json(request): Json<BackfillRequest>,
+) -> Result<Json<BackfillResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let fetcher = state
+        .fetcher
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Fetcher not configured".into()).with_correlation_id(cid.clone()))?;
+
+    let (start_date, end_date) = request
+        .date_range(MAX_BACKFILL_DAYS)
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    let start = Instant::now();
+    let summary = with_correlation_id(correlation_id.0, fetcher.backfill_missing(start_date, end_date, request.zones))
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()).with_correlation_id(cid.clone()))?;
+
+    let status = if summary.errors.is_empty() {
+        if summary.dates_with_gaps == 0 && summary.estimated_found.is_empty() {
+            "no_gaps".to_string()
+        } else {
+            "success".to_string()
+        }
+    } else {
+        "partial".to_string()
+    };
+
+    Ok(Json(BackfillResponse {
+        status,
+        dates_checked: summary.dates_checked,
+        dates_with_gaps: summary.dates_with_gaps,
+        prices_fetched: summary.prices_fetched,
+        prices_stored: summary.prices_stored,
+        gaps_found: summary.gaps_found.into_iter().map(|(date, zone, missing)| GapInfo {
+            date: date.to_string(),
+            zone,
+            missing_hours: missing as i32,
+        }).collect(),
+        estimated_found: summary.estimated_found.into_iter().map(|(date, zone, estimated)| EstimatedHoursInfo {
+            date: date.to_string(),
+            zone,
+            estimated_hours: estimated as i32,
+        }).collect(),
+        errors: summary.errors,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }))
+}
+
+/// Start a backfill job; poll its progress with [`backfill_job`].
+pub async fn start_backfill_job(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+    Json(request): Json<BackfillRequest>,
+) -> Result<(StatusCode, Json<BackfillJobInfo>), AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let fetcher = state
+        .fetcher
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Fetcher not configured".into()).with_correlation_id(cid.clone()))?;
+
+    let (start_date, end_date) = request
+        .date_range(MAX_BACKFILL_JOB_DAYS)
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    let job = with_correlation_id(
+        correlation_id.0,
+        fetcher.start_backfill_job(NewBackfillJob {
+            start_date,
+            end_date,
+            zones: request.zones,
+        }),
+    )
+    .await
+    .map_err(|e| AppError::InternalError(e.to_string()).with_correlation_id(cid.clone()))?;
+
+    Ok((StatusCode::ACCEPTED, Json(job.into())))
+}
+
+pub async fn backfill_job(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<BackfillJobInfo>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let fetcher = state
+        .fetcher
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Fetcher not configured".into()).with_correlation_id(cid.clone()))?;
+
+    let job = fetcher
+        .backfill_job(id)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+    Ok(Json(job.into()))
+}
+
+/// Cancel a running backfill job. It stops after the chunk of days in
+/// progress; 409 once it has finished.
+pub async fn cancel_backfill_job(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<BackfillJobInfo>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let fetcher = state
+        .fetcher
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Fetcher not configured".into()).with_correlation_id(cid.clone()))?;
+
+    let job = fetcher
+        .backfill_job(id)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    let cancelled = fetcher
+        .cancel_backfill_job(id)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    if !cancelled {
+        return Err(AppError::Conflict(format!("Backfill job {} is already {}", id, job.status.as_str()))
+            .with_correlation_id(cid));
+    }
+
+    let job = fetcher
+        .backfill_job(id)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+    Ok(Json(job.into()))
+}
+
+/// Recent background tasks with their attempts and errors, and how many
+/// tasks are in each status.
+pub async fn list_tasks(
+    State(state): State<AppState>,
+    Query(query): Query<TasksQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<TasksResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+    let (status, limit) = query.parse().map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    let tasks = state
+        .repository
+        .list_tasks(status, limit)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    let counts = state
+        .repository
+        .count_tasks_by_status()
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+
+    Ok(Json(TasksResponse {
+        counts: TaskStatus::ALL
+            .iter()
+            .map(|status| (status.as_str().to_string(), counts.get(status).copied().unwrap_or(0)))
+            .collect(),
+        tasks: tasks.into_iter().map(TaskInfo::from).collect(),
+    }))
+}
+
+/// Report UTC days with missing hourly prices, without fetching anything,
+/// so completeness can be checked after an incident before backfilling.
+pub async fn gap_report(
+    State(state): State<AppState>,
+    Query(query): Query<GapReportQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<GapReportResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let range = query
+        .date_range()
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    let requested = query.zones();
+    let zone_codes = if requested.is_empty() {
+        active_zones(&state)
+            .await
+            .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?
+            .into_iter()
+            .map(|zone| zone.zone_code)
+            .collect()
+    } else {
+        for zone_code in &requested {
+            zone_by_code(&state, zone_code)
+                .await
+                .map_err(|e| AppError::zone(e).with_correlation_id(cid.clone()))?;
+        }
+        requested
+    };
+
+    let gaps = state
+        .repository
+        .find_gaps(range.0, range.1, &zone_codes)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+
+    Ok(Json(GapReportResponse::new(range, zone_codes, gaps, state.clock.now())))
+}
+
+/// Registered scheduler jobs with their latest run and next fire time.
+pub async fn scheduler_jobs(State(state): State<AppState>) -> Json<SchedulerJobsResponse> {
+    if let Some(history) = &state.scheduler {
+        // Pauses may have been made on another replica
+        if let Err(e) = history.refresh_pauses().await {
+            warn!(error = %e, "Failed to read paused scheduler jobs");
+        }
+    }
+    Json(SchedulerJobsResponse::new(state.scheduler.as_deref(), state.clock.now()))
+}
+
+pub async fn pause_scheduler_job(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<SchedulerJobInfo>, AppErrorWithContext> {
+    set_scheduler_job_paused(&state, &name, true, correlation_id).await
+}
+
+pub async fn resume_scheduler_job(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<SchedulerJobInfo>, AppErrorWithContext> {
+    set_scheduler_job_paused(&state, &name, false, correlation_id).await
+}
+
+async fn set_scheduler_job_paused(
+    state: &AppState,
+    name: &str,
+    paused: bool,
+    correlation_id: CorrelationId,
+) -> Result<Json<SchedulerJobInfo>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0);
+
+    let scheduler = state
+        .scheduler
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Scheduler not enabled".into()).with_correlation_id(cid.clone()))?;
+
+    let job = scheduler
+        .set_paused(name, paused)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?
+        .ok_or_else(|| AppError::NotFound(format!("Scheduler job not found: {}", name)).with_correlation_id(cid))?;
+
+    Ok(Json(SchedulerJobInfo::new(job, state.clock.now())))
+}
+
+pub async fn circuit_breaker_status(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<CircuitBreakerStatus>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let fetcher = state
+        .fetcher
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Fetcher not configured".into()).with_correlation_id(cid))?;
+
+    Ok(Json(fetcher.client().circuit_breaker().status()))
+}
+
+pub(super) async fn zone_by_code(state: &AppState, zone_code: &str) -> Result<BiddingZone, StorageError> {
+    if let Some(zone) = state.cache.as_ref().and_then(|cache| cache.zone(zone_code)) {
+        return Ok(zone);
+    }
+
+    let zone = state.repository.get_zone_by_code(zone_code).await?;
+    Ok(zone)
+}
+
+pub(super) async fn active_zones(state: &AppState) -> Result<Vec<BiddingZone>, StorageError> {
+    if let Some(zones) = state.cache.as_ref().and_then(|cache| cache.zones()) {
+        return Ok(zones);
+    }
+
+    let zones = state.repository.load_zones().await?;
+    Ok(zones)
+}
+
+async fn translations(state: &AppState, lang: Option<&str>) -> Result<Translations, StorageError> {
+    let Some(lang) = lang else {
+        return Ok(Translations::default());
+    };
+
+    let rows = state.repository.get_name_translations(lang).await?;
+    Ok(Translations::new(rows))
+}
+
+/// Prices for `zone_code` over `[start, end)`. The part of the range before
+/// the zone's `valid_from` is read from the predecessor it replaced.
+pub(super) async fn zone_prices(
+    state: &AppState,
+    zone_code: &str,
+    market: Market,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<Price>, StorageError> {
+    let mut prices = Vec::new();
+    for (zone_code, start, end) in zone_lineage(state, zone_code, start, end).await? {
+        prices.extend(lineage_prices(state, &zone_code, market, start, end).await?);
+    }
+    Ok(prices)
+}
+
+/// `[start, end)` split across `zone_code` and its predecessors, oldest first.
+async fn zone_lineage(
+    state: &AppState,
+    zone_code: &str,
+    start: DateTime<Utc>,
+    mut end: DateTime<Utc>,
+) -> Result<Vec<(String, DateTime<Utc>, DateTime<Utc>)>, StorageError> {
+    let mut segments = Vec::new();
+    let mut seen = HashSet::new();
+    let mut code = zone_code.to_string();
+    loop {
+        let zone = match zone_by_code(state, &code).await {
+            Ok(zone) => Some(zone),
+            Err(StorageError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        let valid_from = zone.as_ref().and_then(BiddingZone::valid_from_start);
+        match (valid_from, zone.and_then(|z| z.predecessor_code)) {
+            (Some(valid_from), Some(predecessor)) if valid_from > start && seen.insert(code.clone()) => {
+                if valid_from < end {
+                    segments.push((code, valid_from, end));
+                    end = valid_from;
+                }
+                code = predecessor;
+            }
+            _ => {
+                segments.push((code, start, end));
+                break;
+            }
+        }
+    }
+    segments.reverse();
+    Ok(segments)
+}
+
+async fn lineage_prices(
+    state: &AppState,
+    zone_code: &str,
+    market: Market,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<Price>, StorageError> {
+    // The cache only holds day-ahead prices
+    let cached = match market {
+        Market::DayAhead => state.cache.as_ref().and_then(|cache| cache.zone_prices(zone_code, start, end)),
+        Market::Intraday => None,
+    };
+    if let Some(prices) = cached {
+        return Ok(prices);
+    }
+
+    let (start, end) = quarter_hour_range(start, end);
+    let key = format!("zone:{}:{}:{}:{}", zone_code, market, start.to_rfc3339(), end.to_rfc3339());
+    state
+        .price_queries
+        .run(key, || async {
+            let prices = state.repository.get_prices_by_zone(zone_code, market, start, end).await?;
+            Ok(prices)
+        })
+        .await
+}
+
+/// `[start, end)` with both ends rounded up to a quarter hour. Prices start
+/// on quarter hours, so the range selects the same prices, and requests whose
+/// ranges differ by less than that, such as defaults relative to now, share
+/// one query.
+fn quarter_hour_range(start: DateTime<Utc>, end: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let round_up = |at: DateTime<Utc>| at.duration_round_up(chrono::Duration::minutes(15)).unwrap_or(at);
+    (round_up(start), round_up(end))
+}
+
+/// Latest price of each zone from the last 24 hours, at the zone's resolution.
+pub(super) async fn latest_prices(state: &AppState) -> Result<Vec<Price>, StorageError> {
+    let prices = match state.cache.as_ref().and_then(|cache| cache.latest_prices()) {
+        Some(prices) => prices,
+        None => {
+            state
+                .price_queries
+                .run("latest".to_string(), || async {
+                    let since = state.clock.now() - chrono::Duration::hours(24);
+                    let prices = state.repository.get_latest_prices(Some(since)).await?;
+                    Ok::<_, StorageError>(prices)
+                })
+                .await?
+        }
+    };
+    latest_at_resolution(state, prices).await
+}
+
+/// Replace latest prices finer than their zone's resolution with the average
+/// over the enclosing interval, e.g. the hour containing the latest quarter-hour.
+pub(super) async fn latest_at_resolution(state: &AppState, prices: Vec<Price>) -> Result<Vec<Price>, StorageError> {
+    let mut served = Vec::with_capacity(prices.len());
+    for price in prices {
+        let resolution = state.resolutions.for_zone(&price.bidding_zone);
+        let target = resolution.duration();
+        if parse_resolution(&price.resolution).is_ok_and(|native| native >= target) {
+            served.push(price);
+            continue;
+        }
+
+        let start = price.timestamp.duration_trunc(target).unwrap_or(price.timestamp);
+        let interval = zone_prices(state, &price.bidding_zone, price.market, start, start + target).await?;
+        served.extend(resolution.aggregate(interval));
+    }
+    Ok(served)
+}
+
+async fn clamp_range(
+    state: &AppState,
+    zone_code: &str,
+    market: Market,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<RangeClamp, StorageError> {
+    let coverage = state.repository.get_price_coverage(zone_code, market).await?;
+    Ok(RangeClamp::new(start, end, coverage.as_ref()))
+}
+
+pub async fn storage_stats(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<StorageStatsResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let tables = state
+        .repository
+        .get_table_stats()
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+
+    let now = state.clock.now();
+    let retention = state
+        .retention
+        .as_ref()
+        .map(|config| RetentionStatus::new(config, &tables, now));
+
+    Ok(Json(StorageStatsResponse {
+        tables: tables.into_iter().map(TableStatsInfo::from).collect(),
+        retention,
+        timestamp: now,
+    }))
+}
+
+/// Page through the fetch log, newest first, for fetch history in the admin
+/// UI. `total` counts every log the filters match.
+pub async fn list_fetch_logs(
+    State(state): State<AppState>,
+    Query(query): Query<FetchLogQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<FetchLogResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+    let (filter, page, limit) = query
+        .parse()
+        .map_err(|e| e.with_correlation_id(cid.clone()))?;
+
+    // One past the page tells whether another follows
+    let logs = state
+        .repository
+        .list_fetch_logs(&filter, page, limit + 1)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    let total = state
+        .repository
+        .count_fetch_logs(&filter)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+
+    Ok(Json(FetchLogResponse::new(logs, total, limit)))
+}
+
+pub async fn prune_fetch_log(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+    Json(request): Json<PruneFetchLogRequest>,
+) -> Result<Json<PruneFetchLogResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    if request.older_than_days == 0 {
+        return Err(AppError::BadRequest("older_than_days must be at least 1".into()).with_correlation_id(cid));
+    }
+
+    let cutoff = state.clock.now() - chrono::Duration::days(request.older_than_days as i64);
+    let start = Instant::now();
+    let deleted = state
+        .repository
+        .delete_old_fetch_logs(cutoff)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+    metrics::record_retention_deleted("fetch_log", deleted);
+
+    Ok(Json(PruneFetchLogResponse {
+        deleted,
+        cutoff,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }))
+}
+
+/// Drop cached prices and zone registry entries after a manual price
+/// correction or registry edit, instead of waiting for the TTL.
+pub async fn invalidate_cache(
+    State(state): State<AppState>,
+    Query(query): Query<CacheInvalidateQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<CacheInvalidateResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let cache = state
+        .cache
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Cache not enabled".into()).with_correlation_id(cid.clone()))?;
+
+    let zone_code = query.zone.as_deref().map(str::to_uppercase);
+    let date = query
+        .date
+        .as_deref()
+        .map(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .transpose()
+        .map_err(|e| {
+            AppError::invalid_parameter("date", format!("Invalid date: {}. Use YYYY-MM-DD format.", e))
+                .with_correlation_id(cid)
+        })?;
+
+    let invalidated = cache.invalidate_matching(zone_code.as_deref(), date);
+
+    Ok(Json(CacheInvalidateResponse {
+        zone_code,
+        date,
+        invalidated,
+        timestamp: state.clock.now(),
+    }))
+}
+
+pub async fn create_alert_rule(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+    Json(request): Json<CreateAlertRuleRequest>,
+) -> Result<(StatusCode, Json<AlertRuleInfo>), AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let alerts = state
+        .alerts
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Alerts not enabled".into()).with_correlation_id(cid.clone()))?;
+
+    let zone = zone_by_code(&state, &request.zone_code.to_uppercase()).await.map_err(|e| match e {
+        StorageError::NotFound(msg) => AppError::BadRequest(msg).with_correlation_id(cid.clone()),
+        e => AppError::from(e).with_correlation_id(cid.clone()),
+    })?;
+    let rule = NewAlertRule {
+        zone_code: zone.zone_code,
+        comparison: request.comparison,
+        threshold: request.threshold,
+        channel: request.channel,
+        target: request.target.trim().to_string(),
+    };
+    alerts
+        .validate_rule(&rule)
+        .map_err(|e| AppError::BadRequest(e).with_correlation_id(cid.clone()))?;
+
+    let created = state
+        .repository
+        .create_alert_rule(&rule)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+
+    Ok((StatusCode::CREATED, Json(AlertRuleInfo::from(created))))
+}
+
+pub async fn list_alert_rules(
+    State(state): State<AppState>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<AlertRulesResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let rules = state
+        .repository
+        .list_alert_rules(None)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+
+    Ok(Json(AlertRulesResponse {
+        rules: rules.into_iter().map(AlertRuleInfo::from).collect(),
+    }))
+}
+
+pub async fn delete_alert_rule(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<StatusCode, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+
+    let deleted = state
+        .repository
+        .delete_alert_rule(id)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+
+    if !deleted {
+        return Err(AppError::NotFound(format!("Alert rule not found: {}", id)).with_correlation_id(cid));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn alert_rule_history(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Query(query): Query<AlertHistoryQuery>,
+    Extension(correlation_id): Extension<CorrelationId>,
+) -> Result<Json<AlertHistoryResponse>, AppErrorWithContext> {
+    let cid = Some(correlation_id.0.clone());
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+
+    let rule = state
+        .repository
+        .get_alert_rule(id)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid.clone()))?;
+    let events = state
+        .repository
+        .get_alert_events(id, limit)
+        .await
+        .map_err(|e| AppError::from(e).with_correlation_id(cid))?;
+
+    Ok(Json(AlertHistoryResponse {
+        rule: AlertRuleInfo::from(rule),
+        events: events.into_iter().map(AlertEventInfo::from).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_ranges_within_a_quarter_hour_share_a_query() {
+        let at = |minute, second, nano| {
+            Utc.with_ymd_and_hms(2025, 1, 15, 10, minute, second).unwrap() + chrono::Duration::nanoseconds(nano)
+        };
+        let day = chrono::Duration::days(1);
+        let first = quarter_hour_range(at(3, 12, 345), at(3, 12, 345) + day);
+        let second = quarter_hour_range(at(14, 59, 999), at(14, 59, 999) + day);
+        assert_eq!(first, second);
+        assert_eq!(first.0, at(15, 0, 0));
+
+        // A range already on quarter hours is kept, so its first price stays in
+        assert_eq!(quarter_hour_range(at(0, 0, 0), at(0, 0, 0) + day), (at(0, 0, 0), at(0, 0, 0) + day));
+    }
+}
@@ -0,0 +1,417 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::Request,
+    http::{header, header::HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use tower::{Layer, Service};
+use tracing::{info, warn};
+
+use crate::clock::Clock;
+use crate::config::IdempotencyConfig;
+use crate::storage::PriceStore;
+
+use super::auth::Principal;
+use super::error::AppError;
+use super::middleware::CorrelationId;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Set on responses replayed from an earlier request with the same key.
+pub const REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Longest key accepted, the width of the `idempotency_keys.key` column.
+const MAX_KEY_LEN: usize = 255;
+
+/// Largest request body buffered to fingerprint a request.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Hash of what a key was used for, so reusing a key for a different request
+/// is caught instead of answered with the wrong response.
+fn fingerprint(method: &Method, path_and_query: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path_and_query.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LEN && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// The key as stored: prefixed with the authenticated caller, so callers
+/// choosing the same key never see each other's responses. Keys contain no
+/// spaces, so the last space always separates the two.
+fn scoped_key(principal: Option<&Principal>, key: &str) -> String {
+    match principal {
+        Some(principal) => format!("{} {}", principal.name, key),
+        None => key.to_string(),
+    }
+}
+
+struct Idempotency {
+    repository: Arc<dyn PriceStore>,
+    clock: Arc<dyn Clock>,
+    ttl: Duration,
+    in_progress_timeout: Duration,
+}
+
+/// Runs POSTs carrying an `Idempotency-Key` header once per key: the first
+/// response is stored and returned again to retries with the same key, method,
+/// path and body. A retry while the first request is still running gets 409,
+/// and a key reused for a different request gets 400. Server errors and
+/// conflicts with a running job are not stored, so the request can be retried
+/// with the same key. Keys are scoped to the authenticated caller.
+///
+/// Once a key is claimed the request runs to completion on its own task, so
+/// a client that disconnects does not leave the key in progress.
+#[derive(Clone)]
+pub struct IdempotencyLayer {
+    idempotency: Arc<Idempotency>,
+}
+
+impl IdempotencyLayer {
+    pub fn new(repository: Arc<dyn PriceStore>, config: &IdempotencyConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            idempotency: Arc::new(Idempotency {
+                repository,
+                clock,
+                ttl: Duration::hours(config.ttl_hours as i64),
+                in_progress_timeout: Duration::seconds(config.in_progress_timeout_seconds as i64),
+            }),
+        }
+    }
+}
+
+impl<S> Layer<S> for IdempotencyLayer {
+    type Service = IdempotencyMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        IdempotencyMiddleware {
+            inner,
+            idempotency: self.idempotency.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct IdempotencyMiddleware<S> {
+    inner: S,
+    idempotency: Arc<Idempotency>,
+}
+
+impl<S> Service<Request<Body>> for IdempotencyMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut inner = self.inner.clone();
+        if req.method() != Method::POST || !req.headers().contains_key(IDEMPOTENCY_KEY_HEADER) {
+            return Box::pin(inner.call(req));
+        }
+
+        let idempotency = self.idempotency.clone();
+        Box::pin(async move {
+            let cid = req.extensions().get::<CorrelationId>().map(|id| id.0.clone());
+            let reject = |error: AppError| Ok(error.with_correlation_id(cid.clone()).into_response());
+
+            let requested = match req.headers()[IDEMPOTENCY_KEY_HEADER].to_str() {
+                Ok(key) if is_valid_key(key) => key.to_string(),
+                _ => {
+                    return reject(AppError::BadRequest(format!(
+                        "{} must be 1 to {} visible ASCII characters",
+                        IDEMPOTENCY_KEY_HEADER, MAX_KEY_LEN
+                    )))
+                }
+            };
+            let key = scoped_key(req.extensions().get::<Principal>(), &requested);
+
+            let (parts, body) = req.into_parts();
+            let body = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+                Ok(body) => body,
+                Err(_) => return reject(AppError::BadRequest("Request body too large".to_string())),
+            };
+            let path_and_query = parts.uri.path_and_query().map_or("", |pq| pq.as_str());
+            let fingerprint = fingerprint(&parts.method, path_and_query, &body);
+
+            let now = idempotency.clock.now();
+            let claimed = match idempotency
+                .repository
+                .claim_idempotency_key(&key, &fingerprint, now, now + idempotency.in_progress_timeout)
+                .await
+            {
+                Ok(claimed) => claimed,
+                Err(e) => return reject(AppError::from(e)),
+            };
+
+            if !claimed {
+                let record = match idempotency.repository.get_idempotency_key(&key).await {
+                    Ok(record) => record,
+                    Err(e) => return reject(AppError::from(e)),
+                };
+                return match record {
+                    Some(record) if record.fingerprint != fingerprint => reject(AppError::BadRequest(format!(
+                        "{} {} was already used for a different request",
+                        IDEMPOTENCY_KEY_HEADER, requested
+                    ))),
+                    Some(record) if record.status_code.is_some() => {
+                        info!(key = %key, path = %parts.uri.path(), "Replaying stored response");
+                        Ok(replay(record.status_code, record.response_body.unwrap_or_default()))
+                    }
+                    // Still running, or released by a failure since the claim
+                    _ => reject(AppError::IdempotencyInProgress(format!(
+                        "A request with {} {} is in progress",
+                        IDEMPOTENCY_KEY_HEADER, requested
+                    ))),
+                };
+            }
+
+            // Detached from the connection: dropping this future when the
+            // client disconnects must not strand the claimed key
+            let run = tokio::spawn({
+                let idempotency = idempotency.clone();
+                let key = key.clone();
+                async move {
+                    let response = inner.call(Request::from_parts(parts, Body::from(body))).await?;
+                    Ok(record_response(&idempotency, &key, now, response).await)
+                }
+            });
+            match run.await {
+                Ok(result) => result,
+                Err(e) => {
+                    warn!(key = %key, error = %e, "Request with idempotency key failed");
+                    release(&idempotency, &key, now).await;
+                    Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+                }
+            }
+        })
+    }
+}
+
+/// Store `response` as the outcome of `key`, or free the key when the
+/// response is not the request's outcome, and return it to the caller.
+async fn record_response(idempotency: &Idempotency, key: &str, claimed_at: DateTime<Utc>, response: Response) -> Response {
+    // Neither is the outcome of the request: retrying it later can succeed
+    if response.status().is_server_error() || response.status() == StatusCode::CONFLICT {
+        release(idempotency, key, claimed_at).await;
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(key = %key, error = %e, "Failed to buffer response for idempotency key");
+            release(idempotency, key, claimed_at).await;
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let expires_at = idempotency.clock.now() + idempotency.ttl;
+    if let Err(e) = idempotency
+        .repository
+        .complete_idempotency_key(key, claimed_at, parts.status.as_u16() as i32, &String::from_utf8_lossy(&bytes), expires_at)
+        .await
+    {
+        warn!(key = %key, error = %e, "Failed to store response for idempotency key");
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+async fn release(idempotency: &Idempotency, key: &str, claimed_at: DateTime<Utc>) {
+    if let Err(e) = idempotency.repository.release_idempotency_key(key, claimed_at).await {
+        warn!(key = %key, error = %e, "Failed to release idempotency key");
+    }
+}
+
+fn replay(status_code: Option<i32>, body: String) -> Response {
+    let status = status_code
+        .and_then(|code| StatusCode::from_u16(code as u16).ok())
+        .unwrap_or(StatusCode::OK);
+    let mut response = (status, Bytes::from(body)).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use axum::{routing::post, Router};
+    use chrono::TimeZone;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::storage::InMemoryPriceStore;
+
+    fn now() -> chrono::DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap()
+    }
+
+    fn app(calls: Arc<AtomicU32>, status: StatusCode) -> Router {
+        app_with_store(calls, status, Arc::new(InMemoryPriceStore::new()))
+    }
+
+    fn app_with_store(calls: Arc<AtomicU32>, status: StatusCode, store: Arc<InMemoryPriceStore>) -> Router {
+        let clock = Arc::new(FixedClock::new(now()));
+        Router::new()
+            .route(
+                "/backfill",
+                post(move |body: String| async move {
+                    let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    (status, format!(r#"{{"call":{},"body":{}}}"#, call, body))
+                }),
+            )
+            .layer(IdempotencyLayer::new(store, &IdempotencyConfig::default(), clock))
+    }
+
+    async fn send(app: &Router, key: Option<&str>, body: &str) -> Response {
+        send_as(app, None, key, body).await
+    }
+
+    async fn send_as(app: &Router, caller: Option<&str>, key: Option<&str>, body: &str) -> Response {
+        let mut req = Request::builder().method("POST").uri("/backfill");
+        if let Some(key) = key {
+            req = req.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        if let Some(name) = caller {
+            req = req.extension(Principal { name: name.to_string(), role: crate::models::ApiRole::Admin });
+        }
+        app.clone().oneshot(req.body(Body::from(body.to_string())).unwrap()).await.unwrap()
+    }
+
+    async fn body(response: Response) -> String {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retries_with_the_same_key_replay_the_first_response() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let app = app(calls.clone(), StatusCode::ACCEPTED);
+
+        let first = send(&app, Some("retry-1"), "1").await;
+        assert_eq!(first.status(), StatusCode::ACCEPTED);
+        assert!(!first.headers().contains_key(REPLAYED_HEADER));
+        assert_eq!(body(first).await, r#"{"call":1,"body":1}"#);
+
+        let retry = send(&app, Some("retry-1"), "1").await;
+        assert_eq!(retry.status(), StatusCode::ACCEPTED);
+        assert_eq!(retry.headers()[REPLAYED_HEADER], "true");
+        assert_eq!(body(retry).await, r#"{"call":1,"body":1}"#);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Reusing the key for another request is refused
+        assert_eq!(send(&app, Some("retry-1"), "2").await.status(), StatusCode::BAD_REQUEST);
+        // Without a key every request runs
+        send(&app, None, "1").await;
+        send(&app, None, "1").await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        assert_eq!(send(&app, Some(""), "1").await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(send(&app, Some(&"k".repeat(256)), "1").await.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_not_replayed() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let app = app(calls.clone(), StatusCode::BAD_GATEWAY);
+
+        assert_eq!(send(&app, Some("retry-1"), "1").await.status(), StatusCode::BAD_GATEWAY);
+        let retry = send(&app, Some("retry-1"), "1").await;
+        assert!(!retry.headers().contains_key(REPLAYED_HEADER));
+        assert_eq!(body(retry).await, r#"{"call":2,"body":1}"#);
+    }
+
+    #[tokio::test]
+    async fn test_retry_while_the_first_request_runs_conflicts() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let store = Arc::new(InMemoryPriceStore::new());
+        let app = app_with_store(calls.clone(), StatusCode::OK, store.clone());
+        let body = "1";
+        let key_fingerprint = fingerprint(&Method::POST, "/backfill", body.as_bytes());
+        store
+            .claim_idempotency_key("retry-1", &key_fingerprint, now(), now() + Duration::minutes(30))
+            .await
+            .unwrap();
+
+        assert_eq!(send(&app, Some("retry-1"), body).await.status(), StatusCode::CONFLICT);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_scoped_to_the_caller() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let app = app(calls.clone(), StatusCode::ACCEPTED);
+
+        let first = send_as(&app, Some("ops"), Some("retry-1"), "1").await;
+        assert_eq!(body(first).await, r#"{"call":1,"body":1}"#);
+        // Another caller's request with the same key runs, and reusing the
+        // key with a different body is not refused
+        let other = send_as(&app, Some("dashboard"), Some("retry-1"), "2").await;
+        assert!(!other.headers().contains_key(REPLAYED_HEADER));
+        assert_eq!(body(other).await, r#"{"call":2,"body":2}"#);
+
+        let retry = send_as(&app, Some("ops"), Some("retry-1"), "1").await;
+        assert_eq!(retry.headers()[REPLAYED_HEADER], "true");
+        assert_eq!(body(retry).await, r#"{"call":1,"body":1}"#);
+    }
+
+    #[tokio::test]
+    async fn test_request_completes_when_the_client_disconnects() {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let store = Arc::new(InMemoryPriceStore::new());
+        let clock = Arc::new(FixedClock::new(now()));
+        let app = Router::new()
+            .route(
+                "/backfill",
+                post({
+                    let release = release.clone();
+                    move || async move {
+                        release.notified().await;
+                        (StatusCode::ACCEPTED, "done")
+                    }
+                }),
+            )
+            .layer(IdempotencyLayer::new(store.clone(), &IdempotencyConfig::default(), clock));
+
+        // The client gives up while the handler is still running
+        let gave_up = tokio::time::timeout(std::time::Duration::from_millis(50), send(&app, Some("retry-1"), "1")).await;
+        assert!(gave_up.is_err());
+        release.notify_one();
+
+        for _ in 0..100 {
+            let record = store.get_idempotency_key("retry-1").await.unwrap().unwrap();
+            if record.status_code.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let retry = send(&app, Some("retry-1"), "1").await;
+        assert_eq!(retry.status(), StatusCode::ACCEPTED);
+        assert_eq!(retry.headers()[REPLAYED_HEADER], "true");
+        assert_eq!(body(retry).await, "done");
+    }
+}
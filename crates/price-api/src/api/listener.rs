@@ -0,0 +1,78 @@
+use std::io;
+use std::net::SocketAddr;
+
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::net::TcpListener;
+
+use crate::config::ListenerConfig;
+
+const LISTEN_BACKLOG: i32 = 1024;
+
+/// Bind a listener from config. IPv6 sockets are bound v6-only so that an
+/// IPv4 and an IPv6 listener can share a port.
+pub async fn bind_listener(config: &ListenerConfig) -> io::Result<TcpListener> {
+    let addrs = tokio::net::lookup_host((config.host.as_str(), config.port)).await?;
+    bind_first(config, addrs)
+}
+
+/// Bind the first address the listener's host resolved to.
+fn bind_first(
+    config: &ListenerConfig,
+    mut addrs: impl Iterator<Item = SocketAddr>,
+) -> io::Result<TcpListener> {
+    let addr = addrs.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            format!("listener '{}': no address for host '{}'", config.name, config.host),
+        )
+    })?;
+
+    bind_addr(addr)
+}
+
+fn bind_addr(addr: SocketAddr) -> io::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    if addr.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(LISTEN_BACKLOG)?;
+
+    TcpListener::from_std(socket.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ListenerScope;
+
+    fn listener(host: &str, port: u16) -> ListenerConfig {
+        ListenerConfig {
+            name: "test".to_string(),
+            host: host.to_string(),
+            port,
+            scope: ListenerScope::All,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bind_ipv4_and_ipv6_on_same_port() {
+        let v4 = bind_listener(&listener("127.0.0.1", 0)).await.unwrap();
+        let port = v4.local_addr().unwrap().port();
+
+        // Hosts without IPv6 support cannot exercise the shared-port case
+        let Ok(v6) = bind_listener(&listener("::1", port)).await else {
+            return;
+        };
+        assert_eq!(v6.local_addr().unwrap().port(), port);
+        assert!(v6.local_addr().unwrap().is_ipv6());
+    }
+
+    #[tokio::test]
+    async fn test_bind_host_without_addresses_fails() {
+        let err = bind_first(&listener("no-such-host.invalid", 0), std::iter::empty()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AddrNotAvailable);
+    }
+}
@@ -0,0 +1,380 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    http::{header::HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tower::{Layer, Service};
+use tracing::{error, info_span, Instrument};
+use uuid::Uuid;
+
+use crate::config::SigningConfig;
+use crate::metrics;
+
+/// Longest `X-Correlation-Id` a client can set; longer ones are replaced,
+/// as the ID is logged with every line and stored on fetch logs.
+const MAX_CORRELATION_ID_LEN: usize = 128;
+
+#[derive(Clone, Debug)]
+pub struct CorrelationId(pub String);
+
+impl CorrelationId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+}
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct CorrelationIdLayer;
+
+impl<S> Layer<S> for CorrelationIdLayer {
+    type Service = CorrelationIdMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CorrelationIdMiddleware { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct CorrelationIdMiddleware<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for CorrelationIdMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let correlation_id = req
+            .headers()
+            .get("X-Correlation-Id")
+            .and_then(|v| v.to_str().ok())
+            .filter(|s| !s.is_empty() && s.len() <= MAX_CORRELATION_ID_LEN)
+            .map(|s| CorrelationId(s.to_string()))
+            .unwrap_or_default();
+
+        req.extensions_mut().insert(correlation_id.clone());
+
+        // Everything the handler does (including sqlx slow-statement warnings)
+        // is emitted inside this span, so log lines carry the correlation ID
+        // and the route that issued them.
+        let handler = format!("{} {}", req.method(), route_label(&req));
+        let span = info_span!("request", correlation_id = %correlation_id.0, handler = %handler);
+
+        let mut inner = self.inner.clone();
+        Box::pin(
+            async move {
+                let mut response = inner.call(req).await?;
+                response.headers_mut().insert(
+                    "X-Correlation-Id",
+                    HeaderValue::from_str(&correlation_id.0).unwrap_or_else(|_| HeaderValue::from_static("unknown")),
+                );
+                Ok(response)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct MetricsLayer;
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsMiddleware { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct MetricsMiddleware<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for MetricsMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let start = Instant::now();
+        let method = req.method().to_string();
+        let path = route_label(&req);
+
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+            let duration = start.elapsed();
+            let status = response.status().as_u16();
+
+            metrics::record_http_request(&method, &path, status, duration);
+
+            Ok(response)
+        })
+    }
+}
+
+/// Signs response bodies so consumers that redistribute our data can prove
+/// it came from this service unmodified.
+#[derive(Clone)]
+pub struct ResponseSigner {
+    key_id: HeaderValue,
+    secret: Vec<u8>,
+}
+
+impl ResponseSigner {
+    pub fn from_config(config: &SigningConfig) -> Result<Self, String> {
+        if config.secret.is_empty() {
+            return Err("signing.secret must be set when signing is enabled".to_string());
+        }
+        let key_id = HeaderValue::from_str(&config.key_id).map_err(|_| format!("Invalid signing.key_id: {}", config.key_id))?;
+        Ok(Self {
+            key_id,
+            secret: config.secret.as_bytes().to_vec(),
+        })
+    }
+
+    /// Hex HMAC-SHA256 over the exact response body bytes.
+    pub fn sign(&self, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+/// Adds `X-Signature: sha256=<hex>` and `X-Signature-Key-Id` to every response.
+#[derive(Clone)]
+pub struct ResponseSigningLayer {
+    signer: ResponseSigner,
+}
+
+impl ResponseSigningLayer {
+    pub fn new(signer: ResponseSigner) -> Self {
+        Self { signer }
+    }
+}
+
+impl<S> Layer<S> for ResponseSigningLayer {
+    type Service = ResponseSigningMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseSigningMiddleware {
+            inner,
+            signer: self.signer.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ResponseSigningMiddleware<S> {
+    inner: S,
+    signer: ResponseSigner,
+}
+
+impl<S> Service<Request<Body>> for ResponseSigningMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let signer = self.signer.clone();
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+
+            // The signature covers the whole body, so it has to be buffered
+            let (mut parts, body) = response.into_parts();
+            let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    error!(error = %e, "Failed to buffer response body for signing");
+                    return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+                }
+            };
+
+            let signature = format!("sha256={}", signer.sign(&bytes));
+            parts.headers.insert(
+                "X-Signature",
+                HeaderValue::from_str(&signature).expect("hex signature is a valid header value"),
+            );
+            parts.headers.insert("X-Signature-Key-Id", signer.key_id.clone());
+            Ok(Response::from_parts(parts, Body::from(bytes)))
+        })
+    }
+}
+
+/// Label used for unmatched requests so arbitrary 404 paths cannot grow
+/// metric cardinality.
+pub const UNMATCHED_ROUTE_LABEL: &str = "unmatched";
+
+/// Metric label for a request: the route template it matched (e.g.
+/// `/api/v1/prices/zone/{zone}`), or a single bucket for unmatched paths.
+fn route_label<B>(req: &Request<B>) -> String {
+    req.extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE_LABEL.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use axum::{routing::get, Router};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn recorded_path_labels(paths: &[String]) -> HashSet<String> {
+        let app = Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .nest(
+                "/api/v1",
+                Router::new().route("/prices/zone/{zone}", get(|| async { "prices" })),
+            )
+            .layer(MetricsLayer);
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            runtime.block_on(async {
+                for path in paths {
+                    let req = Request::builder().uri(path.as_str()).body(Body::empty()).unwrap();
+                    app.clone().oneshot(req).await.unwrap();
+                }
+            });
+        });
+
+        snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, _, _, value)| {
+                key.key().name() == metrics::HTTP_REQUESTS_TOTAL && matches!(value, DebugValue::Counter(_))
+            })
+            .filter_map(|(key, _, _, _)| {
+                key.key()
+                    .labels()
+                    .find(|label| label.key() == "path")
+                    .map(|label| label.value().to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matched_routes_use_template() {
+        let labels = recorded_path_labels(&[
+            "/api/v1/prices/zone/NO1".to_string(),
+            "/api/v1/prices/zone/SE3".to_string(),
+            "/health".to_string(),
+        ]);
+        assert_eq!(
+            labels,
+            HashSet::from(["/api/v1/prices/zone/{zone}".to_string(), "/health".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signature_covers_response_body() {
+        let signer = ResponseSigner::from_config(&SigningConfig {
+            enabled: true,
+            secret: "s3cret".to_string(),
+            key_id: "2025-01".to_string(),
+        })
+        .unwrap();
+        let app = Router::new()
+            .route("/zones", get(|| async { r#"{"zones":[]}"# }))
+            .layer(ResponseSigningLayer::new(signer));
+
+        let req = Request::builder().uri("/zones").body(Body::empty()).unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        let signature = response.headers()["X-Signature"].to_str().unwrap().to_string();
+        assert_eq!(response.headers()["X-Signature-Key-Id"], "2025-01");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], br#"{"zones":[]}"#);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(&body);
+        let expected = hex::decode(signature.strip_prefix("sha256=").unwrap()).unwrap();
+        assert!(mac.verify_slice(&expected).is_ok());
+    }
+
+    #[test]
+    fn test_signing_requires_secret() {
+        let config = SigningConfig {
+            enabled: true,
+            secret: String::new(),
+            key_id: "default".to_string(),
+        };
+        assert!(ResponseSigner::from_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_long_correlation_ids_are_replaced() {
+        let app = Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .layer(CorrelationIdLayer);
+        let correlation_id = |id: String| {
+            let app = app.clone();
+            async move {
+                let req = Request::builder().uri("/health").header("X-Correlation-Id", &id).body(Body::empty()).unwrap();
+                let response = app.oneshot(req).await.unwrap();
+                response.headers()["X-Correlation-Id"].to_str().unwrap().to_string()
+            }
+        };
+
+        assert_eq!(correlation_id("req-1".to_string()).await, "req-1");
+        let long = "x".repeat(MAX_CORRELATION_ID_LEN + 1);
+        let replaced = correlation_id(long).await;
+        assert!(Uuid::parse_str(&replaced).is_ok(), "{}", replaced);
+    }
+
+    #[test]
+    fn test_unmatched_paths_share_single_label() {
+        let paths: Vec<String> = (0..100).map(|i| format!("/random/{}/path", i)).collect();
+        let labels = recorded_path_labels(&paths);
+        assert_eq!(labels, HashSet::from([UNMATCHED_ROUTE_LABEL.to_string()]));
+    }
+}
@@ -0,0 +1,34 @@
+mod auth;
+mod cache_control;
+#[cfg(test)]
+mod contract;
+mod dto;
+mod error;
+mod extract;
+mod fields;
+mod graphql;
+mod grpc;
+mod handlers;
+mod idempotency;
+mod jwt;
+mod listener;
+pub mod middleware;
+mod rate_limit;
+mod readiness;
+mod routes;
+mod status;
+mod zone_metrics;
+
+/// Version segment of the public API paths. Bump it (and add a new contract
+/// snapshot directory under `tests/contract/`) for any change to a response shape.
+pub const API_VERSION: &str = "v1";
+
+pub use auth::{generate_api_key, hash_api_key, ApiKeyAuth, Principal};
+pub use jwt::JwtVerifier;
+pub use error::{AppError, ErrorCode};
+pub use grpc::{proto as grpc_proto, serve_grpc};
+pub use middleware::{CorrelationId, ResponseSigner};
+pub use rate_limit::RateLimiter;
+pub use readiness::ReadinessGate;
+pub use listener::bind_listener;
+pub use routes::{create_router, create_scoped_router, AppState};
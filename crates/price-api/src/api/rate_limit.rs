@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Request},
+    http::{header::HeaderValue, HeaderMap},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, TimeZone, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tower::{Layer, Service};
+
+use crate::config::RateLimitConfig;
+use crate::metrics;
+
+use super::auth::Principal;
+use super::error::AppError;
+
+/// What a caller's requests are counted against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// The authenticated API key or token.
+    ApiKey,
+    /// The connecting address, or the client a trusted proxy forwarded for.
+    ClientIp,
+    /// Requests with neither share one bucket.
+    Anonymous,
+}
+
+impl KeySource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeySource::ApiKey => "api_key",
+            KeySource::ClientIp => "client_ip",
+            KeySource::Anonymous => "anonymous",
+        }
+    }
+}
+
+/// A caller's consumption of the current window, after counting the request
+/// it was computed for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct RateLimitStatus {
+    pub key_source: KeySource,
+    pub limit: u32,
+    pub used: u32,
+    pub remaining: u32,
+    pub window_seconds: u64,
+    /// When the current window ends and the count starts over.
+    pub reset: DateTime<Utc>,
+}
+
+impl RateLimitStatus {
+    pub fn is_exceeded(&self) -> bool {
+        self.used > self.limit
+    }
+
+    fn insert_headers(&self, headers: &mut HeaderMap) {
+        headers.insert("X-RateLimit-Limit", HeaderValue::from(self.limit));
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from(self.remaining));
+        headers.insert("X-RateLimit-Reset", HeaderValue::from(self.reset.timestamp()));
+    }
+}
+
+struct Window {
+    start: i64,
+    counts: HashMap<String, u32>,
+}
+
+/// Fixed-window request counter per caller. Windows are aligned to the Unix
+/// epoch, so every caller's window resets at the same instant and counts from
+/// earlier windows can be dropped wholesale. At most `max_callers` are
+/// counted per window.
+pub struct RateLimiter {
+    limit: u32,
+    window_seconds: i64,
+    trusted_proxies: Vec<IpAddr>,
+    max_callers: usize,
+    window: Mutex<Window>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            limit: config.requests_per_window,
+            window_seconds: config.window_seconds.max(1) as i64,
+            trusted_proxies: config.trusted_proxies.clone(),
+            max_callers: config.max_tracked_callers.max(1),
+            window: Mutex::new(Window {
+                start: 0,
+                counts: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Bucket key for a request: the principal authentication resolved, else
+    /// the client address.
+    pub fn key(&self, req: &Request<Body>) -> (KeySource, String) {
+        if let Some(principal) = req.extensions().get::<Principal>() {
+            return (KeySource::ApiKey, format!("principal:{}", principal.name));
+        }
+        let peer = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|info| info.0.ip());
+        match peer.map(|peer| self.client_ip(peer, req.headers())) {
+            Some(ip) => (KeySource::ClientIp, format!("ip:{}", ip)),
+            None => (KeySource::Anonymous, "anonymous".to_string()),
+        }
+    }
+
+    /// The connecting address, or when it is a trusted proxy, the nearest
+    /// `X-Forwarded-For` hop that is not. Hops further left were written by
+    /// the client and cannot be believed.
+    fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let mut client = peer;
+        if !self.trusted_proxies.contains(&peer) {
+            return client;
+        }
+        let hops: Vec<&str> = headers
+            .get_all("X-Forwarded-For")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .collect();
+        for hop in hops.into_iter().rev() {
+            match hop.parse::<IpAddr>() {
+                Ok(ip) => client = ip,
+                Err(_) => break,
+            }
+            if !self.trusted_proxies.contains(&client) {
+                break;
+            }
+        }
+        client
+    }
+
+    /// Count one request for `key` at `now`.
+    pub fn acquire(&self, key_source: KeySource, key: &str, now: DateTime<Utc>) -> RateLimitStatus {
+        let start = now.timestamp() - now.timestamp().rem_euclid(self.window_seconds);
+
+        let mut window = self.window.lock().unwrap();
+        if window.start != start {
+            window.start = start;
+            window.counts.clear();
+        }
+        if window.counts.len() >= self.max_callers && !window.counts.contains_key(key) {
+            // Forgetting the lightest caller lets them least over the limit
+            let lightest = window.counts.iter().min_by_key(|(_, used)| **used).map(|(key, _)| key.clone());
+            if let Some(lightest) = lightest {
+                window.counts.remove(&lightest);
+            }
+        }
+        let used = window.counts.entry(key.to_string()).or_insert(0);
+        *used = used.saturating_add(1);
+
+        RateLimitStatus {
+            key_source,
+            limit: self.limit,
+            used: *used,
+            remaining: self.limit.saturating_sub(*used),
+            window_seconds: self.window_seconds as u64,
+            reset: Utc.timestamp_opt(start + self.window_seconds, 0).unwrap(),
+        }
+    }
+}
+
+/// Counts every request against its caller, rejects it with 429 once the
+/// window's limit is used up and adds `X-RateLimit-*` headers to the response.
+/// The status is passed to handlers as a request extension.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: Arc<RateLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitMiddleware {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitMiddleware<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S> Service<Request<Body>> for RateLimitMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let (key_source, key) = self.limiter.key(&req);
+        let status = self.limiter.acquire(key_source, &key, Utc::now());
+
+        if status.is_exceeded() {
+            metrics::record_rate_limited(key_source.as_str());
+            let retry_after = (status.reset - Utc::now()).num_seconds().max(1);
+            let mut response = AppError::RateLimited(format!(
+                "Rate limit of {} requests per {}s exceeded",
+                status.limit, status.window_seconds
+            ))
+            .into_response();
+            status.insert_headers(response.headers_mut());
+            response.headers_mut().insert("Retry-After", HeaderValue::from(retry_after));
+            return Box::pin(async move { Ok(response) });
+        }
+
+        req.extensions_mut().insert(status.clone());
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            let mut response = inner.call(req).await?;
+            status.insert_headers(response.headers_mut());
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn limiter(requests_per_window: u32) -> RateLimiter {
+        RateLimiter::new(&RateLimitConfig {
+            enabled: true,
+            requests_per_window,
+            window_seconds: 60,
+            trusted_proxies: vec!["10.0.0.1".parse().unwrap()],
+            max_tracked_callers: 3,
+        })
+    }
+
+    fn request(peer: &str, forwarded_for: Option<&str>) -> Request<Body> {
+        let mut req = Request::builder().uri("/zones");
+        if let Some(forwarded_for) = forwarded_for {
+            req = req.header("X-Forwarded-For", forwarded_for);
+        }
+        let mut req = req.body(Body::empty()).unwrap();
+        req.extensions_mut().insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 40000)));
+        req
+    }
+
+    #[test]
+    fn test_counts_per_key_and_resets_with_window() {
+        let limiter = limiter(2);
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 30).unwrap();
+
+        let first = limiter.acquire(KeySource::ApiKey, "key:a", now);
+        assert_eq!((first.used, first.remaining), (1, 1));
+        assert_eq!(first.reset, Utc.with_ymd_and_hms(2025, 1, 15, 12, 1, 0).unwrap());
+        limiter.acquire(KeySource::ApiKey, "key:a", now);
+        assert!(limiter.acquire(KeySource::ApiKey, "key:a", now).is_exceeded());
+        assert_eq!(limiter.acquire(KeySource::ApiKey, "key:b", now).remaining, 1);
+
+        let next_window = limiter.acquire(KeySource::ApiKey, "key:a", now + chrono::Duration::seconds(30));
+        assert_eq!((next_window.used, next_window.is_exceeded()), (1, false));
+    }
+
+    #[test]
+    fn test_full_window_forgets_the_lightest_caller() {
+        let limiter = limiter(10);
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 30).unwrap();
+        for (key, requests) in [("ip:a", 3), ("ip:b", 1), ("ip:c", 2)] {
+            for _ in 0..requests {
+                limiter.acquire(KeySource::ClientIp, key, now);
+            }
+        }
+
+        assert_eq!(limiter.acquire(KeySource::ClientIp, "ip:d", now).used, 1);
+        let counts = &limiter.window.lock().unwrap().counts;
+        assert_eq!(counts.len(), 3);
+        assert!(!counts.contains_key("ip:b"));
+        assert_eq!(counts["ip:a"], 3);
+    }
+
+    #[test]
+    fn test_key_prefers_principal_and_trusts_only_configured_proxies() {
+        let limiter = limiter(10);
+        let anonymous = Request::builder().uri("/zones").body(Body::empty()).unwrap();
+        assert_eq!(limiter.key(&anonymous).0, KeySource::Anonymous);
+
+        // A direct client cannot pick its bucket with X-Forwarded-For
+        let direct = request("203.0.113.7", Some("198.51.100.1"));
+        assert_eq!(limiter.key(&direct), (KeySource::ClientIp, "ip:203.0.113.7".to_string()));
+
+        // Behind the proxy, the hop it appended is the client
+        let proxied = request("10.0.0.1", Some("198.51.100.1, 203.0.113.7"));
+        assert_eq!(limiter.key(&proxied), (KeySource::ClientIp, "ip:203.0.113.7".to_string()));
+        let proxied = request("10.0.0.1", None);
+        assert_eq!(limiter.key(&proxied), (KeySource::ClientIp, "ip:10.0.0.1".to_string()));
+
+        let mut authenticated = request("203.0.113.7", None);
+        authenticated.extensions_mut().insert(Principal {
+            name: "dashboard".to_string(),
+            role: crate::models::ApiRole::Read,
+        });
+        assert_eq!(limiter.key(&authenticated), (KeySource::ApiKey, "principal:dashboard".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_headers_on_every_response_and_429_when_exceeded() {
+        let app = Router::new()
+            .route("/zones", get(|| async { "ok" }))
+            .layer(RateLimitLayer::new(Arc::new(limiter(1))));
+        let ok = app.clone().oneshot(request("203.0.113.7", None)).await.unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+        assert_eq!(ok.headers()["X-RateLimit-Limit"], "1");
+        assert_eq!(ok.headers()["X-RateLimit-Remaining"], "0");
+        assert!(ok.headers().contains_key("X-RateLimit-Reset"));
+
+        let limited = app.oneshot(request("203.0.113.7", None)).await.unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(limited.headers().contains_key("Retry-After"));
+        assert_eq!(limited.headers()["X-RateLimit-Remaining"], "0");
+    }
+}
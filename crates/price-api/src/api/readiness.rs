@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config::ReadinessConfig;
+
+/// Optional gate that keeps `/ready` failing on a fresh replica until
+/// today's prices are loaded for enough zones. Once it opens it stays open,
+/// so readiness does not flap as data ages.
+pub struct ReadinessGate {
+    config: ReadinessConfig,
+    open: AtomicBool,
+    /// Cleared once a lazily connected database first answers.
+    database_pending: AtomicBool,
+}
+
+impl ReadinessGate {
+    pub fn new(config: ReadinessConfig) -> Self {
+        let open = !config.require_today_data;
+        Self {
+            config,
+            open: AtomicBool::new(open),
+            database_pending: AtomicBool::new(false),
+        }
+    }
+
+    /// Report `/ready` as still connecting until
+    /// [`database_connected`](Self::database_connected) is called.
+    pub fn awaiting_database(self) -> Self {
+        self.database_pending.store(true, Ordering::Relaxed);
+        self
+    }
+
+    pub fn database_pending(&self) -> bool {
+        self.database_pending.load(Ordering::Relaxed)
+    }
+
+    pub fn database_connected(&self) {
+        self.database_pending.store(false, Ordering::Relaxed);
+    }
+
+    pub fn config(&self) -> &ReadinessConfig {
+        &self.config
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Relaxed)
+    }
+
+    /// Open the gate if `zones_with_data` of `active_zones` meets the
+    /// configured fraction. Returns whether the gate is open.
+    pub fn evaluate(&self, zones_with_data: usize, active_zones: usize) -> bool {
+        if self.is_open() {
+            return true;
+        }
+        if active_zones == 0 {
+            return false;
+        }
+
+        let fraction = zones_with_data as f64 / active_zones as f64;
+        if fraction >= self.config.min_zone_fraction {
+            self.open.store(true, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate(min_zone_fraction: f64) -> ReadinessGate {
+        ReadinessGate::new(ReadinessConfig {
+            require_today_data: true,
+            min_zone_fraction,
+            max_fetch_age_hours: None,
+            probe_entsoe: false,
+        })
+    }
+
+    #[test]
+    fn test_gate_opens_at_fraction_and_stays_open() {
+        let gate = gate(0.8);
+        assert!(!gate.evaluate(3, 5));
+        assert!(gate.evaluate(4, 5));
+        assert!(gate.evaluate(0, 5));
+    }
+
+    #[test]
+    fn test_gate_requires_zones() {
+        assert!(!gate(0.0).evaluate(0, 0));
+    }
+
+    #[test]
+    fn test_gate_disabled_is_open() {
+        let gate = ReadinessGate::new(ReadinessConfig {
+            require_today_data: false,
+            min_zone_fraction: 1.0,
+            max_fetch_age_hours: None,
+            probe_entsoe: false,
+        });
+        assert!(gate.is_open());
+    }
+}
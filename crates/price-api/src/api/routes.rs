@@ -0,0 +1,651 @@
+use std::sync::Arc;
+
+use axum::{
+    http::{header, HeaderMap},
+    response::IntoResponse,
+    routing::{delete, get, post},
+    Router,
+};
+use metrics_exporter_prometheus::PrometheusHandle;
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use tracing::warn;
+
+use crate::alerts::AlertService;
+use crate::cache::{PriceCache, SingleFlight};
+use crate::clock::{Clock, SystemClock};
+use crate::config::{
+    CacheControlConfig, CountryAverageConfig, DeploymentConfig, IdempotencyConfig, ListenerScope, QueryDefaultsConfig,
+    ResolutionConfig, RetentionConfig, TariffConfig,
+};
+use crate::fetcher::FetcherService;
+use crate::fx::FxRateService;
+use crate::metrics;
+use crate::models::{ApiRole, Price};
+use crate::scheduler::JobHistory;
+use crate::storage::PriceStore;
+
+use super::auth::{ApiKeyAuth, AuthLayer};
+use super::cache_control::CacheControlLayer;
+use super::graphql;
+use super::handlers;
+use super::idempotency::IdempotencyLayer;
+use super::API_VERSION;
+use super::middleware::{CorrelationIdLayer, MetricsLayer, ResponseSigner, ResponseSigningLayer};
+use super::rate_limit::{RateLimitLayer, RateLimiter};
+use super::readiness::ReadinessGate;
+use super::status::ZoneStatusCache;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub repository: Arc<dyn PriceStore>,
+    pub metrics_handle: PrometheusHandle,
+    pub fetcher: Option<Arc<FetcherService>>,
+    /// EUR exchange rates on `/api/v1/fx`; `None` when FX is disabled.
+    pub fx: Option<Arc<FxRateService>>,
+    pub cache: Option<Arc<PriceCache>>,
+    /// Shares one database query between identical concurrent price requests.
+    pub price_queries: Arc<SingleFlight<String, Vec<Price>>>,
+    /// Per-zone coverage on `/api/v1/status`, reloaded once a minute.
+    pub zone_status: Arc<ZoneStatusCache>,
+    pub readiness: Arc<ReadinessGate>,
+    pub retention: Option<RetentionConfig>,
+    pub query_defaults: QueryDefaultsConfig,
+    /// Resolution each zone's prices are served at.
+    pub resolutions: ResolutionConfig,
+    /// Consumer tariffs for `?include=consumer_price`.
+    pub tariffs: TariffConfig,
+    /// Zone weights for country prices with `?aggregate=average`.
+    pub country_average: CountryAverageConfig,
+    pub signer: Option<ResponseSigner>,
+    /// Per-caller request limit on `/api/v1`; `None` when rate limiting is disabled.
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    /// API key checks on `/api/v1` and its admin routes; `None` when auth is disabled.
+    pub auth: Option<Arc<ApiKeyAuth>>,
+    /// Added to the price gauges rendered on `/metrics`.
+    pub deployment_labels: Vec<(&'static str, String)>,
+    /// Current prices behind the `/metrics` price gauges; `None` when they
+    /// are not exported.
+    pub price_gauges: Option<Arc<metrics::PriceGaugeCache>>,
+    /// Scheduled jobs and their latest runs; `None` when the scheduler is disabled.
+    pub scheduler: Option<Arc<JobHistory>>,
+    /// Validates new alert rules; `None` when alerts are disabled.
+    pub alerts: Option<Arc<AlertService>>,
+    /// Time that default ranges, "today" and current prices are resolved against.
+    pub clock: Arc<dyn Clock>,
+    /// Max-age of `/api/v1` responses; `None` leaves `Cache-Control` unset.
+    pub cache_control: Option<CacheControlConfig>,
+    /// Replay of admin fetch and backfill POSTs retried with an
+    /// `Idempotency-Key`; `None` ignores the header.
+    pub idempotency: Option<IdempotencyConfig>,
+}
+
+impl AppState {
+    pub fn new(
+        repository: Arc<dyn PriceStore>,
+        metrics_handle: PrometheusHandle,
+        fetcher: Option<Arc<FetcherService>>,
+        fx: Option<Arc<FxRateService>>,
+        cache: Option<Arc<PriceCache>>,
+        readiness: ReadinessGate,
+    ) -> Self {
+        Self {
+            repository,
+            metrics_handle,
+            fetcher,
+            fx,
+            cache,
+            price_queries: Arc::new(SingleFlight::new("prices")),
+            zone_status: Arc::new(ZoneStatusCache::new()),
+            readiness: Arc::new(readiness),
+            retention: None,
+            query_defaults: QueryDefaultsConfig::default(),
+            resolutions: ResolutionConfig::default(),
+            tariffs: TariffConfig::default(),
+            country_average: CountryAverageConfig::default(),
+            signer: None,
+            rate_limiter: None,
+            auth: None,
+            deployment_labels: Vec::new(),
+            price_gauges: None,
+            scheduler: None,
+            alerts: None,
+            clock: Arc::new(SystemClock),
+            cache_control: None,
+            idempotency: None,
+        }
+    }
+
+    /// State over `repository` for the API tests: no fetcher, FX or cache,
+    /// and a readiness gate that is open from the start.
+    #[cfg(test)]
+    pub(crate) fn for_tests(repository: Arc<dyn PriceStore>) -> Self {
+        Self::new(
+            repository,
+            metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder().handle(),
+            None,
+            None,
+            None,
+            ReadinessGate::new(crate::config::ReadinessConfig {
+                require_today_data: false,
+                min_zone_fraction: 1.0,
+                max_fetch_age_hours: None,
+                probe_entsoe: false,
+            }),
+        )
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Accept alert rules on `/api/v1/admin/alerts`.
+    pub fn with_alerts(mut self, alerts: Arc<AlertService>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Report scheduler jobs on `/api/v1/status` and let admins pause them.
+    pub fn with_scheduler(mut self, history: Arc<JobHistory>) -> Self {
+        self.scheduler = Some(history);
+        self
+    }
+
+    /// Export each zone's current price on `/metrics`.
+    pub fn with_price_gauges(mut self) -> Self {
+        self.price_gauges = Some(Arc::new(metrics::PriceGaugeCache::new()));
+        self
+    }
+
+    pub fn with_deployment(mut self, deployment: &DeploymentConfig) -> Self {
+        self.deployment_labels = deployment.labels();
+        self
+    }
+
+    pub fn with_query_defaults(mut self, query_defaults: QueryDefaultsConfig) -> Self {
+        self.query_defaults = query_defaults;
+        self
+    }
+
+    pub fn with_resolutions(mut self, resolutions: ResolutionConfig) -> Self {
+        self.resolutions = resolutions;
+        self
+    }
+
+    pub fn with_tariffs(mut self, tariffs: TariffConfig) -> Self {
+        self.tariffs = tariffs;
+        self
+    }
+
+    pub fn with_country_average(mut self, country_average: CountryAverageConfig) -> Self {
+        self.country_average = country_average;
+        self
+    }
+
+    /// Sign every `/api/v1` data response.
+    pub fn with_signer(mut self, signer: ResponseSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Limit requests per caller on `/api/v1` and report usage in headers.
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Require API keys: an admin key on `/api/v1/admin`, and a read key on
+    /// `/api/v1` when configured.
+    pub fn with_auth(mut self, auth: Arc<ApiKeyAuth>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Send `Cache-Control` on `/api/v1` responses, `no-store` on admin ones.
+    pub fn with_cache_control(mut self, config: CacheControlConfig) -> Self {
+        self.cache_control = Some(config);
+        self
+    }
+
+    /// Run admin fetch and backfill POSTs once per `Idempotency-Key`.
+    pub fn with_idempotency(mut self, config: IdempotencyConfig) -> Self {
+        self.idempotency = Some(config);
+        self
+    }
+
+    /// Report retention status alongside the storage stats.
+    pub fn with_retention(mut self, retention: RetentionConfig) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+}
+
+async fn metrics_handler(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let format = metrics::ExpositionFormat::negotiate(headers.get(header::ACCEPT).and_then(|accept| accept.to_str().ok()));
+    let mut body = state.metrics_handle.render();
+
+    if let Some(price_gauges) = &state.price_gauges {
+        match price_gauges.current_prices(state.repository.as_ref(), state.clock.now()).await {
+            Ok(prices) => body.push_str(&metrics::render_price_gauges(&prices, &state.deployment_labels, format)),
+            Err(e) => warn!(error = %e, "Failed to load current prices for /metrics"),
+        }
+    }
+    if format == metrics::ExpositionFormat::OpenMetrics {
+        body.push_str("# EOF\n");
+    }
+
+    ([(header::CONTENT_TYPE, format.content_type())], body)
+}
+
+pub fn create_router(
+    repository: Arc<dyn PriceStore>,
+    metrics_handle: PrometheusHandle,
+    fetcher: Option<Arc<FetcherService>>,
+    fx: Option<Arc<FxRateService>>,
+    cache: Option<Arc<PriceCache>>,
+    readiness: ReadinessGate,
+) -> Router {
+    let state = AppState::new(repository, metrics_handle, fetcher, fx, cache, readiness);
+    create_scoped_router(state, ListenerScope::All)
+}
+
+/// Build the router for one listener. Listeners share `state`, so caches,
+/// request coalescing and the readiness gate are common to all of them.
+pub fn create_scoped_router(state: AppState, scope: ListenerScope) -> Router {
+    let mut api_routes = Router::new()
+        .route("/prices/zone/{zone}", get(handlers::get_prices_by_zone))
+        .route("/prices/zone/{zone}/stats", get(handlers::get_zone_stats))
+        .route("/prices/zone/{zone}/export", get(handlers::export_zone_prices))
+        .route("/prices/zone/{zone}/negative", get(handlers::get_negative_prices))
+        .route("/prices/zone/{zone}/at", get(handlers::get_price_at))
+        .route("/prices/zone/{zone}/home-assistant", get(handlers::get_home_assistant_prices))
+        .route(
+            "/prices/zone/{zone}/invoice",
+            get(handlers::get_zone_invoice).post(handlers::create_zone_invoice),
+        )
+        .route(
+            "/prices/country/{country}",
+            get(handlers::get_prices_by_country),
+        )
+        .route("/prices/latest", get(handlers::get_latest_prices))
+        .route("/prices/compare", get(handlers::compare_prices))
+        .route("/generation/zone/{zone}", get(handlers::get_generation_by_zone))
+        .route("/load/zone/{zone}", get(handlers::get_load_by_zone))
+        .route("/flows/zone/{zone}", get(handlers::get_flows_by_zone))
+        .route("/zones", get(handlers::list_zones))
+        .route("/countries", get(handlers::list_countries))
+        .route("/fx/{currency}", get(handlers::get_fx_rate))
+        .route("/graphql", post(graphql::graphql))
+        .route("/status", get(handlers::service_status))
+        .route("/errors", get(handlers::error_catalog))
+        .route("/limits", get(handlers::rate_limits).layer(CacheControlLayer::no_store()));
+    if let Some(signer) = state.signer.clone() {
+        api_routes = api_routes.layer(ResponseSigningLayer::new(signer));
+    }
+    // Outside the signature so rejected requests are not signed. Inside auth
+    // so authenticated callers are counted by who they are, not by a header
+    // anyone can vary
+    if let Some(limiter) = state.rate_limiter.clone() {
+        api_routes = api_routes.layer(RateLimitLayer::new(limiter));
+    }
+    if let Some(auth) = state.auth.clone() {
+        api_routes = api_routes.layer(AuthLayer::new(auth, ApiRole::Read));
+    }
+    // Outermost so rejected requests are marked uncacheable too
+    if let Some(config) = state.cache_control.clone() {
+        api_routes = api_routes.layer(CacheControlLayer::new(config, state.clock.clone(), state.auth.is_some()));
+    }
+
+    let mut replayable_routes = Router::new()
+        .route("/fetch", post(handlers::trigger_fetch))
+        .route("/fetch/zone/{zone}", post(handlers::trigger_zone_fetch))
+        .route("/fetch/eic/{eic}", post(handlers::trigger_eic_fetch))
+        .route("/backfill", post(handlers::backfill_prices))
+        .route("/backfill/jobs", post(handlers::start_backfill_job));
+    if let Some(config) = &state.idempotency {
+        replayable_routes =
+            replayable_routes.layer(IdempotencyLayer::new(state.repository.clone(), config, state.clock.clone()));
+    }
+
+    // Auth wraps the idempotency layer so rejected requests never claim a key
+    let admin_routes = Router::new()
+        .merge(replayable_routes)
+        .route("/backfill/jobs/{id}", get(handlers::backfill_job).delete(handlers::cancel_backfill_job))
+        .route("/gaps", get(handlers::gap_report))
+        .route("/tasks", get(handlers::list_tasks))
+        .route("/scheduler", get(handlers::scheduler_jobs))
+        .route("/scheduler/jobs/{name}/pause", post(handlers::pause_scheduler_job))
+        .route("/scheduler/jobs/{name}/resume", post(handlers::resume_scheduler_job))
+        .route("/circuit-breaker", get(handlers::circuit_breaker_status))
+        .route("/storage/stats", get(handlers::storage_stats))
+        .route("/storage/fetch-log", get(handlers::list_fetch_logs))
+        .route("/storage/fetch-log/prune", post(handlers::prune_fetch_log))
+        .route("/cache/invalidate", post(handlers::invalidate_cache))
+        .route("/alerts", get(handlers::list_alert_rules).post(handlers::create_alert_rule))
+        .route("/alerts/{id}", delete(handlers::delete_alert_rule))
+        .route("/alerts/{id}/history", get(handlers::alert_rule_history));
+    let admin_routes = match state.auth.clone() {
+        Some(auth) => admin_routes.layer(AuthLayer::new(auth, ApiRole::Admin)),
+        None => admin_routes,
+    };
+    let admin_routes = match state.cache_control {
+        Some(_) => admin_routes.layer(CacheControlLayer::no_store()),
+        None => admin_routes,
+    };
+
+    let cors = if std::env::var("APP_ENV").as_deref() == Ok("development") {
+        CorsLayer::permissive()
+    } else {
+        CorsLayer::new()
+            .allow_methods([axum::http::Method::GET, axum::http::Method::POST])
+            .allow_headers([axum::http::header::CONTENT_TYPE])
+            .allow_origin(["https://your-ui.example.com".parse().unwrap()])
+    };
+
+    let mut router = Router::new()
+        .route("/health", get(handlers::health_check))
+        .route("/ready", get(handlers::ready_check));
+    if scope.serves_public() {
+        router = router.nest(&format!("/api/{}", API_VERSION), api_routes);
+    }
+    if scope.serves_internal() {
+        router = router
+            .route("/metrics", get(metrics_handler))
+            .route("/metrics/catalog", get(handlers::metrics_catalog))
+            .nest(&format!("/api/{}/admin", API_VERSION), admin_routes);
+    }
+
+    // The correlation ID span wraps the trace layer, so its request and
+    // response lines carry the ID too
+    router
+        .layer(MetricsLayer)
+        .layer(TraceLayer::new_for_http())
+        .layer(CorrelationIdLayer)
+        .layer(cors)
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::{DurationRound, TimeZone, Utc};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::storage::InMemoryPriceStore;
+
+    fn router(scope: ListenerScope) -> Router {
+        router_with_store(scope, InMemoryPriceStore::new())
+    }
+
+    fn router_with_store(scope: ListenerScope, store: InMemoryPriceStore) -> Router {
+        create_scoped_router(state_with_store(store), scope)
+    }
+
+    fn state_with_store(store: InMemoryPriceStore) -> AppState {
+        AppState::for_tests(Arc::new(store))
+    }
+
+    async fn status(router: &Router, method: &str, uri: &str) -> StatusCode {
+        let req = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+        router.clone().oneshot(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_public_scope_hides_metrics_and_admin() {
+        let router = router(ListenerScope::Public);
+        assert_eq!(status(&router, "GET", "/health").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/api/v1/zones").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/metrics").await, StatusCode::NOT_FOUND);
+        assert_eq!(
+            status(&router, "GET", "/api/v1/admin/circuit-breaker").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_internal_scope_hides_price_api() {
+        let router = router(ListenerScope::Internal);
+        assert_eq!(status(&router, "GET", "/health").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/metrics").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/metrics/catalog").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/api/v1/zones").await, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_ready_reports_connecting_until_lazy_database_answers() {
+        let state = state_with_store(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")]));
+        let state = AppState {
+            readiness: Arc::new(ReadinessGate::new(state.readiness.config().clone()).awaiting_database()),
+            ..state
+        };
+        let readiness = Arc::clone(&state.readiness);
+        let router = create_scoped_router(state, ListenerScope::All);
+
+        let req = Request::builder().uri("/ready").body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let ready: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!((ready["status"].as_str(), ready["database"].as_str()), (Some("starting"), Some("connecting")));
+
+        readiness.database_connected();
+        assert_eq!(status(&router, "GET", "/ready").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_covers_price_api_only() {
+        let state = state_with_store(InMemoryPriceStore::new()).with_rate_limiter(Arc::new(RateLimiter::new(&crate::config::RateLimitConfig {
+            enabled: true,
+            requests_per_window: 2,
+            window_seconds: 3600,
+            ..Default::default()
+        })));
+        let router = create_scoped_router(state, ListenerScope::All);
+
+        assert_eq!(status(&router, "GET", "/api/v1/zones").await, StatusCode::OK);
+        let req = Request::builder().uri("/api/v1/limits").body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(req).await.unwrap();
+        assert_eq!(response.headers()["X-RateLimit-Remaining"], "0");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let limits: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(limits["current"]["used"], 2);
+        assert_eq!(limits["current"]["key_source"], "anonymous");
+
+        assert_eq!(status(&router, "GET", "/api/v1/zones").await, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(status(&router, "GET", "/health").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_auth_gates_admin_routes_on_admin_role() {
+        let config = crate::config::AuthConfig {
+            enabled: true,
+            keys: [("ops", "admin-secret", ApiRole::Admin), ("dashboard", "read-secret", ApiRole::Read)]
+                .into_iter()
+                .map(|(name, key, role)| crate::config::ApiKeyConfig {
+                    name: name.to_string(),
+                    key: key.to_string(),
+                    role,
+                })
+                .collect(),
+            ..crate::config::AuthConfig::default()
+        };
+        let state = state_with_store(InMemoryPriceStore::new()).with_auth(Arc::new(ApiKeyAuth::new(&config, Vec::new())));
+        let router = create_scoped_router(state, ListenerScope::All);
+        let with_key = |uri: &str, key: &str| {
+            let req = Request::builder().uri(uri).header("X-API-Key", key).body(Body::empty()).unwrap();
+            let router = router.clone();
+            async move { router.oneshot(req).await.unwrap().status() }
+        };
+
+        assert_eq!(status(&router, "GET", "/api/v1/zones").await, StatusCode::OK);
+        assert_eq!(with_key("/api/v1/zones", "read-secret").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/api/v1/admin/storage/stats").await, StatusCode::UNAUTHORIZED);
+        assert_eq!(with_key("/api/v1/admin/storage/stats", "read-secret").await, StatusCode::FORBIDDEN);
+        assert_eq!(with_key("/api/v1/admin/storage/stats", "admin-secret").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/health").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/metrics").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_include_current_price() {
+        // Just before midnight, so the next hour's price belongs to another day
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 23, 59, 30).unwrap();
+        let hour = now.duration_trunc(chrono::Duration::hours(1)).unwrap();
+        let store = InMemoryPriceStore::new();
+        store
+            .upsert_prices(&[
+                Price::from_mwh(hour - chrono::Duration::hours(1), "NO1".to_string(), 10.0, "PT60M".to_string()),
+                Price::from_mwh(hour, "NO1".to_string(), 42.0, "PT60M".to_string()),
+                Price::from_mwh(hour + chrono::Duration::hours(1), "NO1".to_string(), 99.0, "PT60M".to_string()),
+            ])
+            .await
+            .unwrap();
+        let state = state_with_store(store).with_clock(Arc::new(FixedClock::new(now)));
+        let metrics_body = |state: AppState, accept: &'static str| async move {
+            let router = create_scoped_router(state, ListenerScope::Internal);
+            let req = Request::builder().uri("/metrics").header("accept", accept).body(Body::empty()).unwrap();
+            let response = router.oneshot(req).await.unwrap();
+            let content_type = response.headers()["content-type"].to_str().unwrap().to_string();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (content_type, String::from_utf8(body.to_vec()).unwrap())
+        };
+
+        let (content_type, body) = metrics_body(state.clone().with_price_gauges(), "text/plain").await;
+        assert!(content_type.starts_with("text/plain; version=0.0.4"));
+        let expected = r#"electricity_price_eur_kwh{zone_code="NO1",currency="EUR"} 0.042"#;
+        assert!(body.lines().any(|line| line == expected), "{}", body);
+
+        // Stamped with the start of its hour when the scraper accepts OpenMetrics
+        let (content_type, body) = metrics_body(state.clone().with_price_gauges(), "application/openmetrics-text;version=1.0.0").await;
+        assert!(content_type.starts_with("application/openmetrics-text"));
+        let expected = format!(r#"electricity_price_eur_kwh{{zone_code="NO1",currency="EUR"}} 0.042 {}"#, hour.timestamp());
+        assert!(body.lines().any(|line| line == expected), "{}", body);
+        assert!(body.ends_with("# EOF\n"));
+
+        // Not exported unless enabled
+        assert!(!metrics_body(state, "text/plain").await.1.contains("electricity_price_eur_kwh{"));
+    }
+
+    #[tokio::test]
+    async fn test_export_streams_one_price_per_line_in_order() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let store = InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")]);
+        let prices: Vec<Price> = (0..48)
+            .rev()
+            .map(|hour| Price::from_mwh(start + chrono::Duration::hours(hour), "NO1".to_string(), hour as f64, "PT60M".to_string()))
+            .collect();
+        store.upsert_prices(&prices).await.unwrap();
+        let router = router_with_store(ListenerScope::Public, store);
+
+        let uri = "/api/v1/prices/zone/NO1/export?start=2025-01-15T00:00:00Z&end=2025-01-16T00:00:00Z";
+        let response = router.clone().oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let lines: Vec<serde_json::Value> = body.split(|b| *b == b'\n').filter(|line| !line.is_empty()).map(|line| serde_json::from_slice(line).unwrap()).collect();
+        assert_eq!(lines.len(), 24);
+        assert_eq!(lines[0]["timestamp"], "2025-01-15T01:00:00+01:00");
+        assert_eq!(lines[23]["timestamp_utc"], "2025-01-15T23:00:00Z");
+
+        assert_eq!(status(&router, "GET", "/api/v1/prices/zone/XX9/export").await, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_cache_control_depends_on_range_and_route() {
+        let store = InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")]);
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap();
+        let state = state_with_store(store)
+            .with_clock(Arc::new(FixedClock::new(now)))
+            .with_cache_control(CacheControlConfig::default());
+        let router = create_scoped_router(state, ListenerScope::All);
+        let cache_control = |uri: &'static str| {
+            let router = router.clone();
+            async move {
+                let response = router.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+                response.headers()["cache-control"].to_str().unwrap().to_string()
+            }
+        };
+
+        assert_eq!(
+            cache_control("/api/v1/prices/zone/NO1?start=2025-01-01T00:00:00Z&end=2025-01-02T00:00:00Z").await,
+            "public, max-age=86400"
+        );
+        assert_eq!(cache_control("/api/v1/prices/zone/NO1").await, "public, max-age=60");
+        assert_eq!(cache_control("/api/v1/prices/zone/XX9").await, "no-store");
+        assert_eq!(cache_control("/api/v1/limits").await, "no-store");
+        assert_eq!(cache_control("/api/v1/admin/tasks").await, "no-store");
+    }
+
+    #[tokio::test]
+    async fn test_export_serves_influx_line_protocol() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let store = InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")]);
+        let prices: Vec<Price> = (0..24)
+            .map(|hour| Price::from_mwh(start + chrono::Duration::hours(hour), "NO1".to_string(), 40.0, "PT60M".to_string()))
+            .collect();
+        store.upsert_prices(&prices).await.unwrap();
+        let router = router_with_store(ListenerScope::Public, store);
+
+        let uri = "/api/v1/prices/zone/NO1/export?start=2025-01-15T00:00:00Z&end=2025-01-16T00:00:00Z&format=influx";
+        let response = router.clone().oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/plain; charset=utf-8");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert_eq!(body.lines().count(), 24);
+        assert_eq!(
+            body.lines().next().unwrap(),
+            "electricity_price,zone=NO1,market=day_ahead,currency=EUR,resolution=PT60M price_kwh=0.04,gap_filled=false 1736899200000000000"
+        );
+
+        assert_eq!(status(&router, "GET", "/api/v1/prices/zone/NO1/export?format=csv").await, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_zone_prices_before_a_split_come_from_the_predecessor() {
+        let split = chrono::NaiveDate::from_ymd_opt(2018, 10, 1).unwrap();
+        let mut de_at_lu = InMemoryPriceStore::zone("DE-AT-LU", "DE", "Europe/Berlin");
+        de_at_lu.valid_to = Some(split);
+        de_at_lu.active = false;
+        let mut de_lu = InMemoryPriceStore::zone("DE-LU", "DE", "Europe/Berlin");
+        de_lu.valid_from = Some(split);
+        de_lu.predecessor_code = Some("DE-AT-LU".to_string());
+        let store = InMemoryPriceStore::with_zones(vec![de_at_lu, de_lu]);
+        // Midnight in Berlin, where the split takes effect
+        let split_start = Utc.with_ymd_and_hms(2018, 9, 30, 22, 0, 0).unwrap();
+        let prices: Vec<Price> = (-2..2)
+            .map(|hour| {
+                let zone = if hour < 0 { "DE-AT-LU" } else { "DE-LU" };
+                Price::from_mwh(split_start + chrono::Duration::hours(hour), zone.to_string(), hour as f64, "PT60M".to_string())
+            })
+            .collect();
+        store.upsert_prices(&prices).await.unwrap();
+        let router = router_with_store(ListenerScope::Public, store);
+
+        let uri = "/api/v1/prices/zone/DE-LU?start=2018-09-30T20:00:00Z&end=2018-10-01T00:00:00Z";
+        let response = router.clone().oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let hours: Vec<&str> = body["prices"].as_array().unwrap().iter().map(|p| p["timestamp_utc"].as_str().unwrap()).collect();
+        assert_eq!(
+            hours,
+            ["2018-09-30T20:00:00Z", "2018-09-30T21:00:00Z", "2018-09-30T22:00:00Z", "2018-09-30T23:00:00Z"]
+        );
+
+        for (at, price) in [("2018-09-30T21:30:00Z", "-0.001"), ("2018-09-30T22:30:00Z", "0")] {
+            let uri = format!("/api/v1/prices/zone/DE-LU/at?timestamp={}", at);
+            let response = router.clone().oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!((body["zone_code"].as_str(), body["price"].as_str()), (Some("DE-LU"), Some(price)), "{}", uri);
+        }
+    }
+}
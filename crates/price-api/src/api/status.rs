@@ -0,0 +1,101 @@
+use chrono::{DateTime, DurationRound, NaiveDate, Utc};
+use tokio::sync::Mutex;
+
+use crate::models::{BiddingZone, FetchLog, Price};
+use crate::storage::{DayGap, PriceStore, StorageError};
+
+use super::dto::{ZoneFreshness, STATUS_GAP_DAYS};
+
+/// What `/api/v1/status` reports per zone, read from storage once per
+/// minute instead of on every call: the gap scan over [`STATUS_GAP_DAYS`]
+/// and the latest fetch per zone are too costly for an unauthenticated
+/// endpoint.
+#[derive(Default)]
+pub struct ZoneStatusCache {
+    loaded: Mutex<Option<(DateTime<Utc>, ZoneStatusRows)>>,
+}
+
+#[derive(Clone)]
+struct ZoneStatusRows {
+    latest: Vec<Price>,
+    gaps: Vec<DayGap>,
+    fetch_logs: Vec<FetchLog>,
+}
+
+impl ZoneStatusCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Each zone's freshness at `now`, loading the rows it is built from when
+    /// the cached ones belong to an earlier minute. Concurrent calls share
+    /// one load.
+    pub async fn zone_freshness(
+        &self,
+        store: &dyn PriceStore,
+        zones: &[BiddingZone],
+        now: DateTime<Utc>,
+        tomorrow: NaiveDate,
+    ) -> Result<Vec<ZoneFreshness>, StorageError> {
+        let minute = now.duration_trunc(chrono::Duration::minutes(1)).unwrap_or(now);
+        let mut loaded = self.loaded.lock().await;
+        let rows = match loaded.as_ref() {
+            Some((at, rows)) if *at == minute => rows.clone(),
+            _ => {
+                let today = now.date_naive();
+                let zone_codes: Vec<String> = zones.iter().map(|z| z.zone_code.clone()).collect();
+                let rows = ZoneStatusRows {
+                    latest: store.get_latest_prices(None).await?,
+                    gaps: store
+                        .find_gaps(today - chrono::Duration::days(STATUS_GAP_DAYS - 1), tomorrow, &zone_codes)
+                        .await?,
+                    fetch_logs: store.get_latest_fetch_logs().await?,
+                };
+                *loaded = Some((minute, rows.clone()));
+                rows
+            }
+        };
+        Ok(ZoneFreshness::for_zones(zones, rows.latest, &rows.gaps, rows.fetch_logs, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::storage::InMemoryPriceStore;
+
+    #[tokio::test]
+    async fn test_rows_reload_once_per_minute() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let tomorrow = now.date_naive().succ_opt().unwrap();
+        let store = InMemoryPriceStore::new();
+        let zones = vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")];
+        store
+            .upsert_prices(&[Price::from_mwh(now, "NO1".to_string(), 40.0, "PT60M".to_string())])
+            .await
+            .unwrap();
+        let cache = ZoneStatusCache::new();
+        let first = cache.zone_freshness(&store, &zones, now, tomorrow).await.unwrap();
+        assert_eq!(first[0].latest_price_at, Some(now));
+
+        // Not read again within the minute
+        let next = now + chrono::Duration::hours(1);
+        store
+            .upsert_prices(&[Price::from_mwh(next, "NO1".to_string(), 80.0, "PT60M".to_string())])
+            .await
+            .unwrap();
+        let cached = cache
+            .zone_freshness(&store, &zones, now + chrono::Duration::seconds(59), tomorrow)
+            .await
+            .unwrap();
+        assert_eq!(cached[0].latest_price_at, Some(now));
+
+        let reloaded = cache
+            .zone_freshness(&store, &zones, now + chrono::Duration::minutes(1), tomorrow)
+            .await
+            .unwrap();
+        assert_eq!(reloaded[0].latest_price_at, Some(next));
+    }
+}
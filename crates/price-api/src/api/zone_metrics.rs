@@ -0,0 +1,82 @@
+use std::time::Instant;
+
+use crate::metrics;
+
+/// Records per-zone request count and latency for a price endpoint when
+/// dropped, so early error returns are counted too. Only zones resolved
+/// from the registry are used as labels; anything else is counted as
+/// `metrics::OTHER_ZONE_LABEL`.
+pub(super) struct ZoneRequestMetrics {
+    endpoint: &'static str,
+    zones: Vec<String>,
+    start: Instant,
+}
+
+impl ZoneRequestMetrics {
+    pub(super) fn start(endpoint: &'static str) -> Self {
+        Self {
+            endpoint,
+            zones: Vec::new(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Attribute the request to a zone loaded from the registry.
+    pub(super) fn resolved(&mut self, zone_code: &str) {
+        self.zones.push(zone_code.to_string());
+    }
+}
+
+impl Drop for ZoneRequestMetrics {
+    fn drop(&mut self) {
+        let duration = self.start.elapsed();
+        if self.zones.is_empty() {
+            metrics::record_zone_request(self.endpoint, metrics::OTHER_ZONE_LABEL, duration);
+        }
+        for zone in &self.zones {
+            metrics::record_zone_request(self.endpoint, zone, duration);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use super::*;
+
+    #[test]
+    fn test_unresolved_zones_fold_into_other() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let mut known = ZoneRequestMetrics::start("zone");
+            known.resolved("NO1");
+            drop(known);
+            for _ in 0..10 {
+                drop(ZoneRequestMetrics::start("zone"));
+            }
+        });
+
+        let counts: HashSet<(String, u64)> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, _, _, _)| key.key().name() == metrics::API_ZONE_REQUESTS_TOTAL)
+            .filter_map(|(key, _, _, value)| {
+                let zone = key.key().labels().find(|l| l.key() == "zone_code")?.value().to_string();
+                match value {
+                    DebugValue::Counter(count) => Some((zone, count)),
+                    _ => None,
+                }
+            })
+            .collect();
+        assert_eq!(
+            counts,
+            HashSet::from([("NO1".to_string(), 1), (metrics::OTHER_ZONE_LABEL.to_string(), 10)])
+        );
+    }
+}
@@ -0,0 +1,362 @@
+mod single_flight;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::Serialize;
+use tracing::info;
+
+use crate::clock::{Clock, SystemClock};
+use crate::config::CacheConfig;
+use crate::metrics;
+use crate::models::{BiddingZone, DeliveryDay, Market, Price};
+use crate::storage::{PriceStore, StorageError};
+
+pub use single_flight::SingleFlight;
+
+/// Data behind the hot read endpoints, loaded in one go after a fetch.
+/// Invalidated entries are removed individually and read as misses.
+struct Snapshot {
+    warmed_at: DateTime<Utc>,
+    /// Span of the UTC today/tomorrow and every zone's window.
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    zones: Option<Vec<BiddingZone>>,
+    zone_prices: HashMap<String, ZoneWindow>,
+    latest: Option<Vec<Price>>,
+}
+
+/// A zone's day-ahead prices from the start of its local today to the end
+/// of its local tomorrow.
+struct ZoneWindow {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    prices: Vec<Price>,
+}
+
+impl ZoneWindow {
+    /// Window of `zone`'s local today and tomorrow at `now`.
+    fn bounds(zone: &BiddingZone, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let tz: Tz = zone.timezone.parse().unwrap_or(chrono_tz::UTC);
+        let today = DeliveryDay::new(now.with_timezone(&tz).date_naive(), &tz);
+        let tomorrow = DeliveryDay::new(today.end.with_timezone(&tz).date_naive(), &tz);
+        (today.start, tomorrow.end)
+    }
+}
+
+/// Read-through cache for today/tomorrow day-ahead prices per zone, the
+/// latest prices and the zone registry. It is warmed after every fetch that
+/// stores prices, so the client polls that follow publication are served
+/// without touching the database. Entries older than the TTL are ignored.
+pub struct PriceCache {
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+    snapshot: RwLock<Option<Snapshot>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// State of the price cache and its lookups since startup.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CacheStats {
+    pub warmed_at: Option<DateTime<Utc>>,
+    /// Whether the snapshot is within its TTL and serving reads.
+    pub fresh: bool,
+    pub ttl_seconds: i64,
+    pub zones: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Entries dropped by [`PriceCache::invalidate_matching`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, JsonSchema)]
+pub struct CacheInvalidation {
+    /// Zones whose cached prices were dropped.
+    pub zone_prices: Vec<String>,
+    /// Whether the latest prices were dropped.
+    pub latest: bool,
+    /// Whether the zone registry was dropped.
+    pub zones: bool,
+}
+
+impl PriceCache {
+    pub fn new(config: &CacheConfig) -> Self {
+        Self {
+            ttl: Duration::seconds(config.ttl_seconds as i64),
+            clock: Arc::new(SystemClock),
+            snapshot: RwLock::new(None),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Reload the cached data from storage, replacing the previous snapshot.
+    pub async fn warm(&self, store: &dyn PriceStore) -> Result<(), StorageError> {
+        let start = Instant::now();
+        let now = self.clock.now();
+        let mut window_start = now.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let mut window_end = window_start + Duration::days(2);
+
+        let zones = store.load_zones().await?;
+        let mut zone_prices = HashMap::with_capacity(zones.len());
+        for zone in &zones {
+            let (start, end) = ZoneWindow::bounds(zone, now);
+            let prices = store
+                .get_prices_by_zone(&zone.zone_code, Market::DayAhead, start, end)
+                .await?;
+            window_start = window_start.min(start);
+            window_end = window_end.max(end);
+            zone_prices.insert(zone.zone_code.clone(), ZoneWindow { start, end, prices });
+        }
+        let latest = store.get_latest_prices(Some(now - Duration::hours(24))).await?;
+
+        *self.snapshot.write().unwrap() = Some(Snapshot {
+            warmed_at: self.clock.now(),
+            window_start,
+            window_end,
+            zones: Some(zones),
+            zone_prices,
+            latest: Some(latest),
+        });
+
+        metrics::record_cache_warm(start.elapsed());
+        info!(duration_ms = start.elapsed().as_millis(), "Price cache warmed");
+        Ok(())
+    }
+
+    pub fn invalidate(&self) {
+        *self.snapshot.write().unwrap() = None;
+    }
+
+    /// Drop the cached entries for `zone_code` and/or the UTC `date`, or the
+    /// whole snapshot when neither is given. A zone without a date also drops
+    /// the zone registry, for registry edits; the latest prices are dropped
+    /// whenever the affected prices may be among them.
+    pub fn invalidate_matching(&self, zone_code: Option<&str>, date: Option<NaiveDate>) -> CacheInvalidation {
+        let mut guard = self.snapshot.write().unwrap();
+        let Some(snapshot) = guard.as_mut() else {
+            return CacheInvalidation::default();
+        };
+
+        if zone_code.is_none() && date.is_none() {
+            let mut zone_prices: Vec<String> = snapshot.zone_prices.keys().cloned().collect();
+            zone_prices.sort();
+            let invalidation = CacheInvalidation {
+                zone_prices,
+                latest: snapshot.latest.is_some(),
+                zones: snapshot.zones.is_some(),
+            };
+            *guard = None;
+            return invalidation;
+        }
+
+        // Whether `date` overlaps `[start, end)`; any date when none is given
+        let touches = |start: DateTime<Utc>, end: DateTime<Utc>| {
+            date.is_none_or(|date| {
+                let day_start = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+                day_start < end && day_start + Duration::days(1) > start
+            })
+        };
+
+        let mut invalidation = CacheInvalidation::default();
+        let mut zone_prices: Vec<String> = snapshot
+            .zone_prices
+            .iter()
+            .filter(|(code, window)| {
+                zone_code.is_none_or(|zone_code| zone_code == code.as_str()) && touches(window.start, window.end)
+            })
+            .map(|(code, _)| code.clone())
+            .collect();
+        zone_prices.sort();
+        for code in &zone_prices {
+            snapshot.zone_prices.remove(code);
+        }
+        invalidation.zone_prices = zone_prices;
+        // Latest prices reach back up to 24 hours before the window
+        if touches(snapshot.window_start - Duration::days(1), snapshot.window_end) {
+            invalidation.latest = snapshot.latest.take().is_some();
+        }
+        if zone_code.is_some() && date.is_none() {
+            invalidation.zones = snapshot.zones.take().is_some();
+        }
+        invalidation
+    }
+
+    fn read<T>(&self, entry: &str, f: impl FnOnce(&Snapshot) -> Option<T>) -> Option<T> {
+        let guard = self.snapshot.read().unwrap();
+        let value = guard
+            .as_ref()
+            .filter(|snapshot| self.clock.now() - snapshot.warmed_at < self.ttl)
+            .and_then(f);
+        metrics::record_cache_lookup(entry, value.is_some());
+        let counter = if value.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let guard = self.snapshot.read().unwrap();
+        CacheStats {
+            warmed_at: guard.as_ref().map(|snapshot| snapshot.warmed_at),
+            fresh: guard.as_ref().is_some_and(|snapshot| self.clock.now() - snapshot.warmed_at < self.ttl),
+            ttl_seconds: self.ttl.num_seconds(),
+            zones: guard.as_ref().and_then(|snapshot| snapshot.zones.as_ref()).map_or(0, Vec::len),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn zone(&self, zone_code: &str) -> Option<BiddingZone> {
+        self.read("zone", |s| s.zones.as_ref()?.iter().find(|z| z.zone_code == zone_code).cloned())
+    }
+
+    pub fn zones(&self) -> Option<Vec<BiddingZone>> {
+        self.read("zones", |s| s.zones.clone())
+    }
+
+    /// Prices for a zone in `[start, end)`, if that range lies within the
+    /// zone's cached local today/tomorrow window.
+    pub fn zone_prices(&self, zone_code: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Option<Vec<Price>> {
+        self.read("zone_prices", |s| {
+            let window = s.zone_prices.get(zone_code)?;
+            if start < window.start || end > window.end {
+                return None;
+            }
+            Some(
+                window
+                    .prices
+                    .iter()
+                    .filter(|p| p.timestamp >= start && p.timestamp < end)
+                    .cloned()
+                    .collect(),
+            )
+        })
+    }
+
+    /// Latest price per zone no older than 24 hours, as `/prices/latest` serves.
+    pub fn latest_prices(&self) -> Option<Vec<Price>> {
+        let cutoff = self.clock.now() - Duration::hours(24);
+        self.read("latest", |s| {
+            Some(s.latest.as_ref()?.iter().filter(|p| p.timestamp >= cutoff).cloned().collect())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::storage::InMemoryPriceStore;
+
+    /// Cache whose clock stands at `now`.
+    fn cache_at(now: DateTime<Utc>, ttl_seconds: u64) -> PriceCache {
+        PriceCache::new(&CacheConfig { enabled: true, ttl_seconds }).with_clock(Arc::new(FixedClock::new(now)))
+    }
+
+    fn hourly_prices(zone: &str, start: DateTime<Utc>, hours: i64) -> Vec<Price> {
+        (0..hours)
+            .map(|h| Price::from_mwh(start + Duration::hours(h), zone.to_string(), 50.0, "PT60M".to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_warm_serves_ranges_within_window() {
+        let store = InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "UTC")]);
+        let today = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        store.upsert_prices(&hourly_prices("NO1", today, 48)).await.unwrap();
+
+        let cache = cache_at(today + Duration::hours(10), 60);
+        assert!(cache.zone("NO1").is_none());
+
+        cache.warm(&store).await.unwrap();
+
+        let tomorrow = today + Duration::days(1);
+        assert_eq!(cache.zone_prices("NO1", tomorrow, tomorrow + Duration::days(1)).unwrap().len(), 24);
+        assert!(cache.zone_prices("NO1", today - Duration::days(1), tomorrow).is_none());
+        assert_eq!(cache.zone("NO1").unwrap().zone_code, "NO1");
+        assert!(cache.latest_prices().is_some());
+
+        assert!(cache.stats().fresh);
+
+        cache.invalidate();
+        assert!(cache.zones().is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.fresh), (3, 3, false));
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_matching_drops_only_affected_entries() {
+        let store = InMemoryPriceStore::with_zones(vec![
+            InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+        ]);
+        let today = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let tomorrow = today + Duration::days(1);
+        store.upsert_prices(&hourly_prices("NO1", today, 48)).await.unwrap();
+        store.upsert_prices(&hourly_prices("NO2", today, 48)).await.unwrap();
+        let cache = cache_at(today + Duration::hours(10), 60);
+        cache.warm(&store).await.unwrap();
+
+        let old = (today - Duration::days(30)).date_naive();
+        assert_eq!(cache.invalidate_matching(Some("NO1"), Some(old)), CacheInvalidation::default());
+        assert!(cache.zone_prices("NO1", today, tomorrow).is_some());
+
+        let dropped = cache.invalidate_matching(Some("NO1"), Some(tomorrow.date_naive()));
+        assert_eq!(dropped.zone_prices, vec!["NO1".to_string()]);
+        assert!(dropped.latest && !dropped.zones);
+        assert!(cache.zone_prices("NO1", today, tomorrow).is_none());
+        assert!(cache.zone_prices("NO2", today, tomorrow).is_some());
+        assert!(cache.latest_prices().is_none());
+        assert!(cache.zone("NO1").is_some());
+
+        assert!(cache.invalidate_matching(Some("NO2"), None).zones);
+        assert!(cache.zones().is_none());
+
+        cache.warm(&store).await.unwrap();
+        let dropped = cache.invalidate_matching(None, None);
+        assert_eq!(dropped.zone_prices, vec!["NO1".to_string(), "NO2".to_string()]);
+        assert!(!cache.stats().fresh);
+    }
+
+    #[tokio::test]
+    async fn test_warm_serves_local_today_and_tomorrow() {
+        let store = InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")]);
+        let oslo: Tz = "Europe/Oslo".parse().unwrap();
+        // Local days start at 22:00Z the day before in summer
+        let now = Utc.with_ymd_and_hms(2025, 6, 15, 21, 30, 0).unwrap();
+        let today = DeliveryDay::new(now.with_timezone(&oslo).date_naive(), &oslo);
+        let tomorrow = DeliveryDay::new(today.end.with_timezone(&oslo).date_naive(), &oslo);
+        assert_eq!(today.start, Utc.with_ymd_and_hms(2025, 6, 14, 22, 0, 0).unwrap());
+        store.upsert_prices(&hourly_prices("NO1", today.start - Duration::hours(2), 52)).await.unwrap();
+
+        let cache = cache_at(now, 60);
+        cache.warm(&store).await.unwrap();
+
+        let prices = cache.zone_prices("NO1", today.start, tomorrow.end).unwrap();
+        assert_eq!(prices.len(), 48);
+        assert_eq!(prices.first().unwrap().timestamp, today.start);
+        assert_eq!(cache.zone_prices("NO1", today.start, today.end).unwrap().len(), 24);
+        assert!(cache.zone_prices("NO1", today.start - Duration::hours(1), today.end).is_none());
+        assert_eq!((cache.stats().hits, cache.stats().misses), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_expired_snapshot_is_ignored() {
+        let store = InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")]);
+        let cache = PriceCache::new(&CacheConfig { enabled: true, ttl_seconds: 0 });
+        cache.warm(&store).await.unwrap();
+        assert!(cache.zones().is_none());
+    }
+}
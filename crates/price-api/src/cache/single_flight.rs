@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OnceCell;
+
+use crate::metrics;
+
+/// Coalesces identical concurrent loads: while a load for a key is in
+/// flight, further callers for the same key wait for it and share its result
+/// instead of issuing their own query. Errors are not shared; a waiter whose
+/// leader failed runs its own load.
+pub struct SingleFlight<K, V> {
+    name: &'static str,
+    in_flight: Mutex<HashMap<K, Arc<OnceCell<V>>>>,
+}
+
+impl<K, V> SingleFlight<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn run<F, Fut, E>(&self, key: K, load: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let (cell, _leader) = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key) {
+                Some(cell) => {
+                    metrics::record_coalesced_request(self.name);
+                    (Arc::clone(cell), None)
+                }
+                None => {
+                    let cell = Arc::new(OnceCell::new());
+                    in_flight.insert(key.clone(), Arc::clone(&cell));
+                    let guard = LeaderGuard {
+                        in_flight: &self.in_flight,
+                        key,
+                        cell: Arc::clone(&cell),
+                    };
+                    (cell, Some(guard))
+                }
+            }
+        };
+
+        cell.get_or_try_init(load).await.cloned()
+    }
+
+    #[cfg(test)]
+    fn in_flight_len(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+}
+
+/// Removes the leader's entry once it finishes or is cancelled, so completed
+/// results are never served to later callers.
+struct LeaderGuard<'a, K: Eq + Hash, V> {
+    in_flight: &'a Mutex<HashMap<K, Arc<OnceCell<V>>>>,
+    key: K,
+    cell: Arc<OnceCell<V>>,
+}
+
+impl<K: Eq + Hash, V> Drop for LeaderGuard<'_, K, V> {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.get(&self.key).is_some_and(|cell| Arc::ptr_eq(cell, &self.cell)) {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_concurrent_identical_loads_run_once() {
+        let flight = Arc::new(SingleFlight::<String, usize>::new("test"));
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..50)
+            .map(|_| {
+                let flight = Arc::clone(&flight);
+                let loads = Arc::clone(&loads);
+                tokio::spawn(async move {
+                    flight
+                        .run("NO1".to_string(), || async move {
+                            loads.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok::<_, ()>(42)
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), Ok(42));
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert_eq!(flight.in_flight_len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sequential_loads_are_not_cached() {
+        let flight = SingleFlight::<&str, usize>::new("test");
+        assert_eq!(flight.run("k", || async { Ok::<_, ()>(1) }).await, Ok(1));
+        assert_eq!(flight.run("k", || async { Ok::<_, ()>(2) }).await, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_shared() {
+        let flight = SingleFlight::<&str, usize>::new("test");
+        assert_eq!(flight.run("k", || async { Err::<usize, _>("boom") }).await, Err("boom"));
+        assert_eq!(flight.run("k", || async { Ok::<_, &str>(3) }).await, Ok(3));
+    }
+}
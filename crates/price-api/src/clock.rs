@@ -0,0 +1,54 @@
+//! Wall-clock time for the fetcher, scheduler and API, so "today" and
+//! "tomorrow" can be pinned in tests around midnight and DST changes.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Source of the current time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current UTC date.
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+
+    /// UTC date after [`today`](Self::today).
+    fn tomorrow(&self) -> NaiveDate {
+        self.today().succ_opt().expect("date within chrono's range")
+    }
+}
+
+/// The host's wall clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that stays at a set time until moved.
+#[cfg(any(test, feature = "test-utils"))]
+pub struct FixedClock(std::sync::Mutex<DateTime<Utc>>);
+
+#[cfg(any(test, feature = "test-utils"))]
+impl FixedClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self(std::sync::Mutex::new(now))
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.0.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, by: chrono::Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
+    }
+}
@@ -0,0 +1,821 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use crate::models::{ApiRole, PriceResolution, Tariff};
+
+pub use entsoe_core::config::{EntsoeConfig, MaintenanceWindow};
+pub use price_store::config::{DatabaseBackend, DatabaseConfig};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub entsoe: EntsoeConfig,
+    pub scheduler: SchedulerConfig,
+    pub retention: RetentionConfig,
+    #[serde(default)]
+    pub tasks: TaskQueueConfig,
+    pub fx: FxConfig,
+    pub cache: CacheConfig,
+    pub webhooks: WebhookConfig,
+    #[serde(default)]
+    pub influx: InfluxConfig,
+    pub alerts: AlertConfig,
+    pub signing: SigningConfig,
+    pub readiness: ReadinessConfig,
+    pub zones: ZoneRegistryConfig,
+    #[serde(default)]
+    pub query_defaults: QueryDefaultsConfig,
+    #[serde(default)]
+    pub deployment: DeploymentConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub log_file: LogFileConfig,
+    #[serde(default)]
+    pub resolution: ResolutionConfig,
+    #[serde(default)]
+    pub tariffs: TariffConfig,
+    #[serde(default)]
+    pub country_average: CountryAverageConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+    #[serde(default)]
+    pub cache_control: CacheControlConfig,
+    #[serde(default)]
+    pub idempotency: IdempotencyConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// Explicit listeners; when empty a single listener on `host:port` serves every route.
+    #[serde(default)]
+    pub listeners: Vec<ListenerConfig>,
+}
+
+impl ServerConfig {
+    pub fn listeners(&self) -> Vec<ListenerConfig> {
+        if !self.listeners.is_empty() {
+            return self.listeners.clone();
+        }
+        vec![ListenerConfig {
+            name: "default".to_string(),
+            host: self.host.clone(),
+            port: self.port,
+            scope: ListenerScope::All,
+        }]
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListenerConfig {
+    pub name: String,
+    /// IPv4 or IPv6 address (e.g. `0.0.0.0`, `::`). IPv6 listeners are v6-only so
+    /// both families can be bound on the same port.
+    pub host: String,
+    pub port: u16,
+    #[serde(default)]
+    pub scope: ListenerScope,
+}
+
+/// Which routes a listener serves. `/health` and `/ready` are served by every listener.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListenerScope {
+    #[default]
+    All,
+    /// Price and zone API only.
+    Public,
+    /// `/metrics` and the admin API only.
+    Internal,
+}
+
+impl ListenerScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListenerScope::All => "all",
+            ListenerScope::Public => "public",
+            ListenerScope::Internal => "internal",
+        }
+    }
+
+    pub fn serves_public(&self) -> bool {
+        matches!(self, ListenerScope::All | ListenerScope::Public)
+    }
+
+    pub fn serves_internal(&self) -> bool {
+        matches!(self, ListenerScope::All | ListenerScope::Internal)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SchedulerConfig {
+    pub enabled: bool,
+    pub fetch_times_cet: Vec<String>,
+    /// What to do when the primary fetch was missed (e.g. the host was suspended).
+    #[serde(default)]
+    pub misfire_policy: MisfirePolicy,
+    /// Cron expression (Europe/Oslo) for fetching actual generation per
+    /// production type. Generation is not fetched when unset.
+    #[serde(default)]
+    pub generation_cron: Option<String>,
+    /// Cron expression (Europe/Oslo) for fetching actual total load. Load is
+    /// not fetched when unset.
+    #[serde(default)]
+    pub load_cron: Option<String>,
+    /// Cron expression (Europe/Oslo) for fetching cross-border flows for the
+    /// configured `entsoe.flow_pairs`. Flows are not fetched when unset.
+    #[serde(default)]
+    pub flows_cron: Option<String>,
+    /// How long a job's database lock holds before another run may take it
+    /// over, in case its holder died. A running job renews its locks every
+    /// third of this.
+    pub job_lock_ttl_seconds: u64,
+    pub retry_queue: RetryQueueConfig,
+}
+
+/// Zones whose day-ahead fetch failed are queued and retried on their own,
+/// instead of waiting for the next fetch of every zone.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetryQueueConfig {
+    pub enabled: bool,
+    /// Cron expression (Europe/Oslo) for the job that retries due zones.
+    pub cron: String,
+    /// Delay before the first retry; doubles with every failed retry.
+    pub initial_backoff_seconds: u64,
+    pub max_backoff_seconds: u64,
+    /// A zone/date is given up on this long after its fetch first failed.
+    pub expiry_hours: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    /// Only log and count the misfire.
+    Skip,
+    /// Run the primary fetch (today and tomorrow) once.
+    #[default]
+    RunOnce,
+    /// Queue a repair of the gaps since the last recorded run (at most a
+    /// week) for the task worker.
+    Backfill,
+}
+
+impl MisfirePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MisfirePolicy::Skip => "skip",
+            MisfirePolicy::RunOnce => "run_once",
+            MisfirePolicy::Backfill => "backfill",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionConfig {
+    pub enabled: bool,
+    pub price_retention_days: u32,
+    pub fetch_log_retention_days: u32,
+    pub cleanup_cron: String,
+}
+
+/// Worker for the database-backed task queue that runs backfill jobs, gap
+/// repairs and retention runs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaskQueueConfig {
+    /// Run queued tasks on this replica. Tasks are queued either way.
+    pub enabled: bool,
+    /// Tasks run at once by this replica.
+    pub concurrency: usize,
+    /// How often an idle worker checks for due tasks.
+    pub poll_interval_seconds: u64,
+    /// How long a claimed task stays with its worker without a renewal,
+    /// before another worker may take it over.
+    pub lease_seconds: u64,
+    /// Attempts before a task is marked failed.
+    pub max_attempts: i32,
+    /// Delay before the first retry; doubles with every failed attempt.
+    pub initial_backoff_seconds: u64,
+    pub max_backoff_seconds: u64,
+}
+
+impl Default for TaskQueueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            concurrency: 2,
+            poll_interval_seconds: 5,
+            lease_seconds: 300,
+            max_attempts: 5,
+            initial_backoff_seconds: 60,
+            max_backoff_seconds: 3600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FxSourceKind {
+    Ecb,
+    NorgesBank,
+    Riksbank,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FxConfig {
+    pub enabled: bool,
+    pub timeout_seconds: u64,
+    pub default_source: FxSourceKind,
+    /// Per-currency source override, e.g. `NOK = "norges_bank"`.
+    #[serde(default)]
+    pub sources: HashMap<String, FxSourceKind>,
+}
+
+/// Range used by a price endpoint when `start` and/or `end` are omitted.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct DefaultRangeConfig {
+    /// Default `start`: this many days before now.
+    pub lookback_days: u32,
+    /// Default `end`: the end of the UTC day this many days after today.
+    pub lookahead_days: u32,
+}
+
+impl Default for DefaultRangeConfig {
+    fn default() -> Self {
+        Self {
+            lookback_days: 7,
+            lookahead_days: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct QueryDefaultsConfig {
+    #[serde(default)]
+    pub zone: DefaultRangeConfig,
+    #[serde(default)]
+    pub country: DefaultRangeConfig,
+    #[serde(default)]
+    pub stats: DefaultRangeConfig,
+    #[serde(default)]
+    pub generation: DefaultRangeConfig,
+    #[serde(default)]
+    pub load: DefaultRangeConfig,
+    #[serde(default)]
+    pub flows: DefaultRangeConfig,
+    #[serde(default)]
+    pub negative: DefaultRangeConfig,
+    #[serde(default)]
+    pub compare: DefaultRangeConfig,
+}
+
+/// Resolution each zone's prices are served at. Prices are stored as
+/// published and averaged up to this on read.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResolutionConfig {
+    /// For zones without an entry in `zones`.
+    #[serde(default)]
+    pub default: PriceResolution,
+    /// Per-zone overrides keyed by zone code.
+    #[serde(default)]
+    pub zones: HashMap<String, PriceResolution>,
+}
+
+impl Default for ResolutionConfig {
+    /// Hourly, except GB which trades in half-hours.
+    fn default() -> Self {
+        Self {
+            default: PriceResolution::Hour,
+            zones: HashMap::from([("GB".to_string(), PriceResolution::HalfHour)]),
+        }
+    }
+}
+
+impl ResolutionConfig {
+    pub fn for_zone(&self, zone_code: &str) -> PriceResolution {
+        // Config keys may arrive lowercased from environment variables
+        self.zones
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(zone_code))
+            .map_or(self.default, |(_, resolution)| *resolution)
+    }
+}
+
+/// Per-zone consumer tariffs for `?include=consumer_price`. Zones without an
+/// entry get no consumer price.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TariffConfig {
+    /// Keyed by zone code.
+    #[serde(default)]
+    pub zones: HashMap<String, Tariff>,
+}
+
+impl TariffConfig {
+    pub fn for_zone(&self, zone_code: &str) -> Option<&Tariff> {
+        // Config keys may arrive lowercased from environment variables
+        self.zones
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(zone_code))
+            .map(|(_, tariff)| tariff)
+    }
+}
+
+/// Zone weights for country prices with `?aggregate=average`, e.g. each
+/// zone's annual consumption. A country is averaged by weight only when
+/// every zone with prices has one; otherwise its zones count equally.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CountryAverageConfig {
+    /// Keyed by zone code.
+    #[serde(default)]
+    pub weights: HashMap<String, Decimal>,
+}
+
+impl CountryAverageConfig {
+    pub fn for_zone(&self, zone_code: &str) -> Option<Decimal> {
+        // Config keys may arrive lowercased from environment variables
+        self.weights
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(zone_code))
+            .map(|(_, weight)| *weight)
+    }
+
+    /// A weight of zero or less would zero out or flip a country's average.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut invalid: Vec<&str> = self
+            .weights
+            .iter()
+            .filter(|(_, weight)| **weight <= Decimal::ZERO)
+            .map(|(code, _)| code.as_str())
+            .collect();
+        if invalid.is_empty() {
+            return Ok(());
+        }
+        invalid.sort_unstable();
+        Err(format!("country_average.weights must be above zero: {}", invalid.join(", ")))
+    }
+}
+
+/// Per-caller limit on `/api/v1` requests, counted in fixed windows.
+/// Authenticated callers are counted by API key or token, anyone else by
+/// their address.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    pub requests_per_window: u32,
+    pub window_seconds: u64,
+    /// Addresses of reverse proxies in front of the service. Only their
+    /// `X-Forwarded-For` headers are believed; anyone else is counted by the
+    /// address they connect from.
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Callers counted per window at most; past it the caller with the
+    /// fewest requests is forgotten to make room.
+    pub max_tracked_callers: usize,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            requests_per_window: 600,
+            window_seconds: 60,
+            trusted_proxies: Vec::new(),
+            max_tracked_callers: 10_000,
+        }
+    }
+}
+
+/// gRPC price service for internal consumers, on its own port. It has no
+/// rate limit, so bind it to an internal address.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GrpcConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// How often `StreamPrices` checks for a new current price.
+    pub stream_poll_seconds: u64,
+}
+
+impl GrpcConfig {
+    pub fn listener(&self) -> ListenerConfig {
+        ListenerConfig {
+            name: "grpc".to_string(),
+            host: self.host.clone(),
+            port: self.port,
+            scope: ListenerScope::All,
+        }
+    }
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: 50051,
+            stream_poll_seconds: 15,
+        }
+    }
+}
+
+/// `Cache-Control` max-age of `/api/v1` responses, picked from the range a
+/// request asks for.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CacheControlConfig {
+    pub enabled: bool,
+    /// Ranges that ended before today (CET), whose prices no longer change.
+    pub historical_max_age_seconds: u64,
+    /// Ranges reaching into tomorrow before its prices are fetched at 13:00
+    /// CET; never extends past that time.
+    pub pending_max_age_seconds: u64,
+    /// Every other successful response.
+    pub max_age_seconds: u64,
+}
+
+impl Default for CacheControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            historical_max_age_seconds: 86400,
+            pending_max_age_seconds: 60,
+            max_age_seconds: 300,
+        }
+    }
+}
+
+/// Replay of admin fetch and backfill POSTs retried with the same
+/// `Idempotency-Key` header.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdempotencyConfig {
+    pub enabled: bool,
+    /// How long a stored response is replayed for.
+    pub ttl_hours: u64,
+    /// How long a key stays claimed by a request that never finished, e.g.
+    /// because its replica died.
+    pub in_progress_timeout_seconds: u64,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_hours: 24,
+            in_progress_timeout_seconds: 1800,
+        }
+    }
+}
+
+/// API key authentication. Admin routes need an `admin` key; the public
+/// routes need a `read` or `admin` key only with `require_read_key`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthConfig {
+    pub enabled: bool,
+    /// Header carrying the API key.
+    pub key_header: String,
+    /// Also reject anonymous requests to the public `/api/v1` routes.
+    pub require_read_key: bool,
+    /// Also accept the keys in the `api_keys` table, loaded at startup.
+    pub database_keys: bool,
+    #[serde(default)]
+    pub keys: Vec<ApiKeyConfig>,
+    #[serde(default)]
+    pub jwt: JwtConfig,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            key_header: "X-API-Key".to_string(),
+            require_read_key: false,
+            database_keys: false,
+            keys: Vec::new(),
+            jwt: JwtConfig::default(),
+        }
+    }
+}
+
+/// Bearer tokens from an OIDC identity provider, accepted alongside API keys.
+/// Roles are read from `roles_claim`, a string, space-separated string or
+/// array of strings; a dotted path reaches nested claims such as
+/// `realm_access.roles`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JwtConfig {
+    pub enabled: bool,
+    /// Expected `iss` claim.
+    pub issuer: String,
+    pub jwks_url: String,
+    /// Required `aud` claim.
+    pub audience: String,
+    pub roles_claim: String,
+    /// Role value that grants admin access.
+    pub admin_role: String,
+    /// Role value needed to read; any valid token may read when unset.
+    #[serde(default)]
+    pub read_role: Option<String>,
+    /// Refetch the key set after this long even if every `kid` is known.
+    pub jwks_cache_seconds: u64,
+    /// Clock skew allowed on `exp` and `nbf`.
+    pub leeway_seconds: u64,
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            issuer: String::new(),
+            jwks_url: String::new(),
+            audience: String::new(),
+            roles_claim: "roles".to_string(),
+            admin_role: "admin".to_string(),
+            read_role: None,
+            jwks_cache_seconds: 3600,
+            leeway_seconds: 60,
+        }
+    }
+}
+
+/// A key assigned in the config file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeyConfig {
+    /// Identifies the caller in logs.
+    pub name: String,
+    pub key: String,
+    pub role: ApiRole,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ZoneRegistryConfig {
+    /// Insert bundled bidding zones missing from the registry at startup.
+    pub sync_on_startup: bool,
+    /// Country codes to sync; empty syncs every bundled zone.
+    #[serde(default)]
+    pub sync_countries: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReadinessConfig {
+    /// Keep `/ready` failing until today's prices exist for enough zones.
+    pub require_today_data: bool,
+    /// Fraction (0.0-1.0) of active zones that must have today's prices.
+    pub min_zone_fraction: f64,
+    /// Report degraded once the last successful fetch is older than this;
+    /// unset to leave fetch age out of readiness.
+    #[serde(default)]
+    pub max_fetch_age_hours: Option<u64>,
+    /// Check that the primary ENTSOE endpoint answers on every `/ready` call.
+    #[serde(default)]
+    pub probe_entsoe: bool,
+}
+
+impl ReadinessConfig {
+    /// A fraction outside 0-1 would make the gate open at once or never.
+    pub fn validate(&self) -> Result<(), String> {
+        if (0.0..=1.0).contains(&self.min_zone_fraction) {
+            return Ok(());
+        }
+        Err(format!(
+            "readiness.min_zone_fraction must be between 0.0 and 1.0, got {}",
+            self.min_zone_fraction
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub ttl_seconds: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    pub enabled: bool,
+    /// Endpoints that receive every event.
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Shared secret for the `X-Webhook-Signature` HMAC-SHA256 header.
+    pub secret: String,
+    pub timeout_seconds: u64,
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+}
+
+/// InfluxDB v2 bucket that stored prices are pushed to in line protocol.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InfluxConfig {
+    /// Push prices after every fetch or backfill that stores them.
+    pub enabled: bool,
+    /// Base URL of the InfluxDB server, e.g. `http://influxdb:8086`.
+    pub url: String,
+    pub org: String,
+    pub bucket: String,
+    /// API token with write access to `bucket`.
+    pub token: String,
+    pub timeout_seconds: u64,
+}
+
+impl Default for InfluxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            org: String::new(),
+            bucket: String::new(),
+            token: String::new(),
+            timeout_seconds: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertConfig {
+    /// Evaluate alert rules on new prices and serve `/api/v1/admin/alerts`.
+    pub enabled: bool,
+    /// Signs webhook-channel alerts the same way as `webhooks.secret`.
+    /// Required when alerts are enabled.
+    pub webhook_secret: String,
+    pub timeout_seconds: u64,
+    /// Broker for the `mqtt` channel; rules using it are rejected when unset.
+    #[serde(default)]
+    pub mqtt: Option<MqttConfig>,
+    /// SMTP relay for the `email` channel; rules using it are rejected when unset.
+    #[serde(default)]
+    pub email: Option<EmailConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MqttConfig {
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailConfig {
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    /// Skip STARTTLS and send in plain text; only for relays on localhost.
+    #[serde(default)]
+    pub plaintext: bool,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender mailbox, e.g. `Price alerts <alerts@example.com>`.
+    pub from: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SigningConfig {
+    /// Sign every `/api/v1` response body with HMAC-SHA256.
+    pub enabled: bool,
+    /// Shared with the consumers that verify signatures.
+    pub secret: String,
+    /// Sent in `X-Signature-Key-Id` so consumers can pick the key during rotation.
+    pub key_id: String,
+}
+
+/// Identifies this deployment; every metric and log line carries the set fields.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DeploymentConfig {
+    /// e.g. `staging` or `prod`.
+    pub environment: Option<String>,
+    pub region: Option<String>,
+    pub instance_id: Option<String>,
+}
+
+impl DeploymentConfig {
+    /// `(label, value)` pairs for the fields that are set.
+    pub fn labels(&self) -> Vec<(&'static str, String)> {
+        [
+            ("environment", &self.environment),
+            ("region", &self.region),
+            ("instance_id", &self.instance_id),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.clone().map(|value| (label, value)))
+        .collect()
+    }
+}
+
+/// Optional series on `/metrics`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MetricsConfig {
+    /// Export `electricity_price_eur_kwh{zone_code,currency}` with each zone's
+    /// current day-ahead price from storage.
+    #[serde(default)]
+    pub price_gauges: bool,
+}
+
+/// Log lines written to rotated files as well as stdout, for hosts without a
+/// log collector. Lines use the same format as stdout (`LOG_FORMAT`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogFileConfig {
+    pub enabled: bool,
+    /// Created if missing.
+    pub directory: PathBuf,
+    pub file_name: String,
+    pub rotation: LogRotation,
+    /// File size that starts a new file with `rotation = "size"`.
+    pub max_size_mb: u64,
+    /// Log files kept, including the one being written; older ones are deleted.
+    pub max_files: usize,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: PathBuf::from("logs"),
+            file_name: "entsoe-price-fetcher.log".to_string(),
+            rotation: LogRotation::Daily,
+            max_size_mb: 100,
+            max_files: 7,
+        }
+    }
+}
+
+/// When [`LogFileConfig`] starts a new file. Time-based files are suffixed
+/// with the UTC date (and hour or minute); size-based ones are renumbered,
+/// `.1` being the newest rotated file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogRotation {
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    /// Once the file reaches `max_size_mb`.
+    Size,
+    /// A single file that is never rotated.
+    Never,
+}
+
+impl AppConfig {
+    pub fn load() -> Result<Self, config::ConfigError> {
+        let config_dir =
+            std::env::var("CONFIG_DIR").unwrap_or_else(|_| "config".to_string());
+
+        let builder = config::Config::builder()
+            .add_source(config::File::from(
+                PathBuf::from(&config_dir).join("default.toml"),
+            ))
+            .add_source(
+                config::File::from(PathBuf::from(&config_dir).join("local.toml"))
+                    .required(false),
+            )
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("_")
+                    .separator("__")
+                    .try_parsing(true)
+            );
+
+        builder.build()?.try_deserialize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_country_average_weights_must_be_positive() {
+        let config = |weights: &[(&str, i64)]| CountryAverageConfig {
+            weights: weights.iter().map(|(code, weight)| (code.to_string(), Decimal::from(*weight))).collect(),
+        };
+        assert!(CountryAverageConfig::default().validate().is_ok());
+        assert!(config(&[("SE1", 8), ("SE2", 16)]).validate().is_ok());
+        assert_eq!(
+            config(&[("SE1", 8), ("SE3", -1), ("SE2", 0)]).validate().unwrap_err(),
+            "country_average.weights must be above zero: SE2, SE3"
+        );
+    }
+
+    #[test]
+    fn test_readiness_min_zone_fraction_must_be_a_fraction() {
+        let config = |min_zone_fraction| ReadinessConfig {
+            require_today_data: true,
+            min_zone_fraction,
+            max_fetch_age_hours: None,
+            probe_entsoe: false,
+        };
+        assert!(config(0.0).validate().is_ok());
+        assert!(config(1.0).validate().is_ok());
+        assert_eq!(
+            config(80.0).validate().unwrap_err(),
+            "readiness.min_zone_fraction must be between 0.0 and 1.0, got 80"
+        );
+        assert!(config(-0.1).validate().is_err());
+        assert!(config(f64::NAN).validate().is_err());
+    }
+}
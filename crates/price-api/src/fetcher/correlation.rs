@@ -0,0 +1,33 @@
+//! Correlation ID of the API request a fetch runs for, recorded on the
+//! fetch log entries the fetch opens so a log row can be traced back to the
+//! request (and its log lines) that triggered it.
+
+use std::future::Future;
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+/// Run `fetch` with `correlation_id` recorded on the fetch log entries it
+/// opens. Scheduled runs are not scoped and log none.
+pub async fn with_correlation_id<F: Future>(correlation_id: String, fetch: F) -> F::Output {
+    CORRELATION_ID.scope(correlation_id, fetch).await
+}
+
+/// Correlation ID of the request the current fetch runs for, if any.
+pub(super) fn current_correlation_id() -> Option<String> {
+    CORRELATION_ID.try_with(Clone::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_correlation_id_is_scoped_to_the_fetch() {
+        assert_eq!(current_correlation_id(), None);
+        let inside = with_correlation_id("req-1".to_string(), async { current_correlation_id() }).await;
+        assert_eq!(inside.as_deref(), Some("req-1"));
+        assert_eq!(current_correlation_id(), None);
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::clock::Clock;
+use crate::storage::PriceStore;
+
+/// How long a lock holds when not configured; a holder that dies is taken
+/// over after this. A running holder renews its locks every third of it.
+pub const DEFAULT_JOB_LOCK_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Another run, on this replica or another, holds the lock on the same job
+/// and date.
+#[derive(Debug, thiserror::Error)]
+#[error("{job} for {date} is already running")]
+pub struct JobLocked {
+    pub job: String,
+    pub date: NaiveDate,
+}
+
+/// A run's lock was taken over by another run, e.g. after renewals failed
+/// until it expired, so the run was stopped.
+#[derive(Debug, thiserror::Error)]
+#[error("lock on {job} for {date} was taken over by another run")]
+pub struct JobLockLost {
+    pub job: String,
+    pub date: NaiveDate,
+}
+
+/// Run `run` while holding the database lock on `job` for `date`, failing
+/// with [`JobLocked`] when it is held. The lock is renewed while `run` runs
+/// and released afterwards, or expires `ttl` after its last renewal if the
+/// release fails.
+pub(super) async fn run_locked<T, F>(
+    repository: &dyn PriceStore,
+    clock: &dyn Clock,
+    ttl: Duration,
+    job: &str,
+    date: NaiveDate,
+    run: F,
+) -> Result<T, anyhow::Error>
+where
+    F: Future<Output = Result<T, anyhow::Error>>,
+{
+    let holder = Uuid::new_v4().to_string();
+    let now = clock.now();
+    let expires_at = now + chrono::Duration::from_std(ttl)?;
+    if !repository.try_acquire_job_lock(job, date, &holder, now, expires_at).await? {
+        return Err(JobLocked {
+            job: job.to_string(),
+            date,
+        }
+        .into());
+    }
+
+    let result = hold_while(repository, clock, ttl, job, &BTreeSet::from([date]), &holder, run).await;
+    if let Err(e) = repository.release_job_lock(job, date, &holder).await {
+        warn!(job = %job, date = %date, error = %e, "Failed to release job lock, it expires on its own");
+    }
+    result
+}
+
+/// Run `run` while holding the database lock on `job` for each of `dates`
+/// that no other run holds. `run` is given the dates it holds; the others
+/// are left to whichever run has them. The locks are released afterwards,
+/// as in [`run_locked`].
+pub(super) async fn run_locked_dates<T, F, Fut>(
+    repository: &dyn PriceStore,
+    clock: &dyn Clock,
+    ttl: Duration,
+    job: &str,
+    dates: BTreeSet<NaiveDate>,
+    run: F,
+) -> Result<T, anyhow::Error>
+where
+    F: FnOnce(BTreeSet<NaiveDate>) -> Fut,
+    Fut: Future<Output = Result<T, anyhow::Error>>,
+{
+    let holder = Uuid::new_v4().to_string();
+    let now = clock.now();
+    let expires_at = now + chrono::Duration::from_std(ttl)?;
+    let mut held = BTreeSet::new();
+    let mut acquire_error = None;
+    for date in dates {
+        match repository.try_acquire_job_lock(job, date, &holder, now, expires_at).await {
+            Ok(true) => {
+                held.insert(date);
+            }
+            Ok(false) => {}
+            Err(e) => {
+                acquire_error = Some(e);
+                break;
+            }
+        }
+    }
+
+    let result = match acquire_error {
+        Some(e) => Err(e.into()),
+        None => hold_while(repository, clock, ttl, job, &held, &holder, run(held.clone())).await,
+    };
+    for date in held {
+        if let Err(e) = repository.release_job_lock(job, date, &holder).await {
+            warn!(job = %job, date = %date, error = %e, "Failed to release job lock, it expires on its own");
+        }
+    }
+    result
+}
+
+/// Drive `run` to completion while renewing `holder`'s locks on `job` for
+/// `dates` every third of `ttl`. A failed renewal is retried on the next
+/// beat; a lock another run has taken over stops `run` with
+/// [`JobLockLost`], as the dates are no longer exclusive.
+async fn hold_while<T, F>(
+    repository: &dyn PriceStore,
+    clock: &dyn Clock,
+    ttl: Duration,
+    job: &str,
+    dates: &BTreeSet<NaiveDate>,
+    holder: &str,
+    run: F,
+) -> Result<T, anyhow::Error>
+where
+    F: Future<Output = Result<T, anyhow::Error>>,
+{
+    let mut run = std::pin::pin!(run);
+    let mut heartbeat = tokio::time::interval_at(tokio::time::Instant::now() + ttl / 3, ttl / 3);
+    heartbeat.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            result = &mut run => return result,
+            _ = heartbeat.tick() => {
+                let expires_at = clock.now() + chrono::Duration::from_std(ttl)?;
+                for &date in dates {
+                    match repository.renew_job_lock(job, date, holder, expires_at).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            return Err(JobLockLost {
+                                job: job.to_string(),
+                                date,
+                            }
+                            .into())
+                        }
+                        Err(e) => warn!(job = %job, date = %date, error = %e, "Failed to renew job lock, retrying on the next beat"),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::storage::InMemoryPriceStore;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_lock_is_renewed_while_the_job_runs_past_its_ttl() {
+        let store = InMemoryPriceStore::new();
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 15, 13, 0, 0).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 1, 16).unwrap();
+        let ttl = Duration::from_secs(30 * 60);
+
+        run_locked_dates(&store, &clock, ttl, "backfill", BTreeSet::from([date]), |_| async {
+            // Twice the TTL, with a heartbeat in every third of it
+            for _ in 0..6 {
+                tokio::time::sleep(ttl / 3).await;
+                clock.advance(chrono::Duration::from_std(ttl / 3).unwrap());
+                let now = clock.now();
+                let taken = store
+                    .try_acquire_job_lock("backfill", date, "other", now, now + chrono::Duration::minutes(30))
+                    .await?;
+                assert!(!taken, "lock lost at {}", now);
+            }
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        // Released once the job is done
+        let now = clock.now();
+        assert!(store.try_acquire_job_lock("backfill", date, "other", now, now).await.unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_job_stops_when_its_lock_is_taken_over() {
+        let store = InMemoryPriceStore::new();
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 15, 13, 0, 0).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 1, 16).unwrap();
+        let ttl = Duration::from_secs(30 * 60);
+
+        let err = run_locked(&store, &clock, ttl, "backfill", date, async {
+            // Expired before the first heartbeat, e.g. while the database was unreachable
+            clock.advance(chrono::Duration::from_std(ttl).unwrap());
+            let now = clock.now();
+            assert!(store.try_acquire_job_lock("backfill", date, "other", now, now + chrono::Duration::minutes(30)).await?);
+            tokio::time::sleep(ttl).await;
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+        assert!(err.is::<JobLockLost>());
+    }
+}
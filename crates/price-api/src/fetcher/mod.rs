@@ -0,0 +1,11 @@
+mod correlation;
+mod job_lock;
+mod retry_queue;
+mod service;
+mod tasks;
+
+pub use correlation::with_correlation_id;
+pub use job_lock::{JobLocked, DEFAULT_JOB_LOCK_TTL};
+pub use retry_queue::RetryQueueSummary;
+pub use service::{BackfillSummary, FetchSummary, FetcherService, ZoneFetchResult, ZoneOnboarding};
+pub use tasks::{BackfillJobTask, GapRepairTask};
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::config::RetryQueueConfig;
+use crate::models::FetchRetry;
+
+/// Outcome of one pass over the fetch retry queue.
+#[derive(Debug, Clone, Default)]
+pub struct RetryQueueSummary {
+    /// Zone/dates fetched, by this pass or since they were queued.
+    pub succeeded: usize,
+    pub rescheduled: usize,
+    pub expired: u64,
+    /// Retries left due because another run held the date's price fetch lock.
+    pub locked: usize,
+    /// Retries left due after a storage or fetch error; tried again on the
+    /// next pass.
+    pub failed: usize,
+}
+
+/// What became of one due retry.
+pub(super) enum RetryOutcome {
+    Succeeded,
+    Rescheduled,
+    Locked,
+    /// Its zone is no longer in the registry.
+    Dropped,
+}
+
+/// Delay after the `attempts`-th failed retry, doubling from the initial
+/// backoff up to the maximum.
+pub(super) fn retry_backoff(config: &RetryQueueConfig, attempts: i32) -> Duration {
+    let factor = 2u64.saturating_pow(attempts.max(0) as u32);
+    let seconds = config.initial_backoff_seconds.saturating_mul(factor).min(config.max_backoff_seconds);
+    Duration::seconds(seconds as i64)
+}
+
+/// Queue entry for a zone/date whose fetch failed at `now`.
+pub(super) fn queued_retry(
+    config: &RetryQueueConfig,
+    zone_code: &str,
+    target_date: NaiveDate,
+    error: Option<String>,
+    now: DateTime<Utc>,
+) -> FetchRetry {
+    FetchRetry {
+        zone_code: zone_code.to_string(),
+        target_date,
+        attempts: 0,
+        last_error: error,
+        enqueued_at: now,
+        next_attempt_at: now + retry_backoff(config, 0),
+        expires_at: now + Duration::hours(config.expiry_hours as i64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_maximum() {
+        let config = RetryQueueConfig {
+            enabled: true,
+            cron: "0 */5 * * * *".to_string(),
+            initial_backoff_seconds: 300,
+            max_backoff_seconds: 3600,
+            expiry_hours: 48,
+        };
+        let backoffs: Vec<i64> = (0..6).map(|attempts| retry_backoff(&config, attempts).num_seconds()).collect();
+        assert_eq!(backoffs, vec![300, 600, 1200, 2400, 3600, 3600]);
+        assert_eq!(retry_backoff(&config, 100).num_seconds(), 3600);
+    }
+}
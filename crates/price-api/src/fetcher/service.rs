@@ -0,0 +1,2646 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use crate::alerts::AlertService;
+use crate::cache::PriceCache;
+use crate::clock::{Clock, SystemClock};
+use crate::config::{RetryQueueConfig, TaskQueueConfig};
+use crate::entsoe::{
+    parse_price_document_all_markets, price_document_domain, traced, EntsoeClient, EntsoeError, EntsoePriceSource,
+    RequestTrace, MAX_REQUEST_DAYS,
+};
+use crate::influx::InfluxExporter;
+use crate::metrics;
+use crate::models::{
+    is_valid_eic, BackfillJob, BackfillJobStatus, BiddingZone, CrossBorderFlow, FetchRetry, FetchStatus, FlowKind, GenerationPoint, LoadValue, Market, NewBackfillJob, Price,
+    PriceResolution, Task, ZoneDefinition, ZonePair,
+};
+use crate::storage::{PriceStore, StorageError, WriteCoordinator, WritePriority};
+use crate::tasks;
+use crate::webhooks::{WebhookEvent, WebhookNotifier, ZoneFetchDiff};
+
+use super::correlation::current_correlation_id;
+use super::job_lock::{run_locked, run_locked_dates, JobLocked, DEFAULT_JOB_LOCK_TTL};
+use super::retry_queue::{queued_retry, retry_backoff, RetryOutcome, RetryQueueSummary};
+use super::tasks::{BackfillJobPayload, GapRepairPayload};
+
+/// Lock name shared by every day-ahead price fetch, scheduled or manual.
+const PRICE_FETCH_LOCK: &str = "price_fetch";
+
+/// Days a backfill job covers between saving its progress and checking
+/// whether it was cancelled: one ENTSOE request per zone and gap run.
+const BACKFILL_JOB_CHUNK_DAYS: i64 = MAX_REQUEST_DAYS;
+
+#[derive(Debug, Clone, Default)]
+pub struct FetchSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub no_data: usize,
+    pub total_prices_stored: usize,
+    pub errors: Vec<String>,
+}
+
+impl FetchSummary {
+    pub fn merge(&mut self, other: FetchSummary) {
+        self.succeeded += other.succeeded;
+        self.failed += other.failed;
+        self.no_data += other.no_data;
+        self.total_prices_stored += other.total_prices_stored;
+        self.errors.extend(other.errors);
+    }
+
+    /// Whether zones were attempted and every one of them failed.
+    pub fn all_failed(&self) -> bool {
+        self.failed > 0 && self.succeeded == 0 && self.no_data == 0
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BackfillSummary {
+    pub dates_checked: usize,
+    pub dates_with_gaps: usize,
+    pub prices_fetched: usize,
+    pub prices_stored: usize,
+    pub gaps_found: Vec<(NaiveDate, String, i64)>, // (date, zone, missing_hours)
+    /// Days re-fetched to replace gap-filled prices with published ones.
+    pub estimated_found: Vec<(NaiveDate, String, i64)>, // (date, zone, estimated_hours)
+    pub errors: Vec<String>,
+}
+
+/// Outcome of [`FetcherService::onboard_zone`].
+#[derive(Debug, Clone)]
+pub struct ZoneOnboarding {
+    /// Day-ahead prices returned by the test fetch.
+    pub test_prices: usize,
+    pub backfill: Option<BackfillSummary>,
+}
+
+/// Outcome of a single zone/date fetch, tied to its fetch_log entry.
+#[derive(Debug, Clone)]
+pub struct ZoneFetchResult {
+    pub fetch_id: i64,
+    /// The zone fetched: the requested one, or the predecessor that was
+    /// valid on the date.
+    pub zone_code: String,
+    pub status: FetchStatus,
+    pub prices_stored: usize,
+    pub error: Option<String>,
+}
+
+/// One zone's request within a multi-zone price fetch.
+struct ZoneFetch {
+    fetch_id: i64,
+    zone: BiddingZone,
+    result: Result<Vec<Price>, EntsoeError>,
+    trace: RequestTrace,
+    duration_ms: i32,
+}
+
+/// Fetch log entry for one zone, completed once the run's prices are stored.
+struct ZoneFetchLog {
+    fetch_id: i64,
+    zone_code: String,
+    status: FetchStatus,
+    records: usize,
+    error: Option<String>,
+    http_status: Option<i32>,
+    served_by: BTreeSet<String>,
+    duration_ms: i32,
+}
+
+impl ZoneFetchLog {
+    fn new(fetch: &ZoneFetch) -> Self {
+        let (status, records, error) = match &fetch.result {
+            Ok(prices) if prices.is_empty() => (FetchStatus::NoData, 0, None),
+            Ok(prices) => (FetchStatus::Success, prices.len(), None),
+            Err(EntsoeError::NoData) => (FetchStatus::NoData, 0, None),
+            Err(EntsoeError::RateLimited) => (FetchStatus::RateLimited, 0, Some(EntsoeError::RateLimited.to_string())),
+            Err(e) => (FetchStatus::Error, 0, Some(e.to_string())),
+        };
+        Self {
+            fetch_id: fetch.fetch_id,
+            zone_code: fetch.zone.zone_code.clone(),
+            status,
+            records,
+            error,
+            http_status: fetch.trace.http_status.map(i32::from),
+            served_by: fetch.trace.served_by.clone(),
+            duration_ms: fetch.duration_ms,
+        }
+    }
+}
+
+/// Sorted `dates` grouped into inclusive runs of consecutive days.
+fn consecutive_runs(mut dates: Vec<NaiveDate>) -> Vec<(NaiveDate, NaiveDate)> {
+    dates.sort();
+    let mut runs: Vec<(NaiveDate, NaiveDate)> = Vec::new();
+    for date in dates {
+        match runs.last_mut() {
+            Some((_, last)) if last.succ_opt() == Some(date) => *last = date,
+            _ => runs.push((date, date)),
+        }
+    }
+    runs
+}
+
+/// UTC bounds of `date`, logged as the fetch period of a zone/date fetch.
+fn delivery_period(date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    (start, start + chrono::Duration::days(1))
+}
+
+/// Starts of the hours whose mean price is below zero. Prices are stored at
+/// their native resolution; only the metric counts hours.
+fn negative_hours(prices: Vec<Price>) -> BTreeSet<DateTime<Utc>> {
+    PriceResolution::Hour
+        .aggregate(prices)
+        .into_iter()
+        .filter(|p| p.price_kwh < rust_decimal::Decimal::ZERO)
+        .map(|p| p.timestamp)
+        .collect()
+}
+
+/// Orchestrates fetching prices from an [`EntsoePriceSource`] into storage.
+/// Defaults to the live [`EntsoeClient`]; tests can substitute a canned source.
+pub struct FetcherService<S = EntsoeClient> {
+    client: Arc<S>,
+    repository: Arc<dyn PriceStore>,
+    /// Serialises price upserts between scheduled fetches and backfills.
+    writes: WriteCoordinator,
+    cache: Option<Arc<PriceCache>>,
+    webhooks: Option<Arc<WebhookNotifier>>,
+    alerts: Option<Arc<AlertService>>,
+    influx: Option<Arc<InfluxExporter>>,
+    fetch_intraday: bool,
+    flow_pairs: Vec<ZonePair>,
+    clock: Arc<dyn Clock>,
+    job_lock_ttl: Duration,
+    retry_queue: Option<RetryQueueConfig>,
+    task_queue: TaskQueueConfig,
+}
+
+impl<S: EntsoePriceSource + 'static> FetcherService<S> {
+    pub fn new(client: Arc<S>, repository: Arc<dyn PriceStore>) -> Self {
+        Self {
+            client,
+            repository,
+            writes: WriteCoordinator::new(1),
+            cache: None,
+            webhooks: None,
+            alerts: None,
+            influx: None,
+            fetch_intraday: false,
+            flow_pairs: Vec::new(),
+            clock: Arc::new(SystemClock),
+            job_lock_ttl: DEFAULT_JOB_LOCK_TTL,
+            retry_queue: None,
+            task_queue: TaskQueueConfig::default(),
+        }
+    }
+
+    /// Decide "today" and "tomorrow" by `clock` instead of the system time.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// How long a job lock holds before another run may take it over.
+    pub fn with_job_lock_ttl(mut self, ttl: Duration) -> Self {
+        self.job_lock_ttl = ttl;
+        self
+    }
+
+    /// Run `run` under the database lock on `job` for `date`, so no other
+    /// run of it executes concurrently on any replica. Fails with
+    /// [`JobLocked`](super::JobLocked) while another run holds the lock.
+    pub async fn run_locked<T>(
+        &self,
+        job: &str,
+        date: NaiveDate,
+        run: impl Future<Output = Result<T, anyhow::Error>>,
+    ) -> Result<T, anyhow::Error> {
+        run_locked(self.repository.as_ref(), self.clock.as_ref(), self.job_lock_ttl, job, date, run).await
+    }
+
+    /// Queue zones whose day-ahead fetch failed, to be retried by
+    /// [`drain_retry_queue`](Self::drain_retry_queue).
+    pub fn with_retry_queue(mut self, config: RetryQueueConfig) -> Self {
+        self.retry_queue = Some(config);
+        self
+    }
+
+    /// Attempts given to the tasks this service queues.
+    pub fn with_task_queue(mut self, config: TaskQueueConfig) -> Self {
+        self.task_queue = config;
+        self
+    }
+
+    /// Also fetch intraday auction results for today and tomorrow in
+    /// [`fetch_all_prices`](Self::fetch_all_prices).
+    pub fn with_intraday(mut self) -> Self {
+        self.fetch_intraday = true;
+        self
+    }
+
+    /// Borders fetched by [`fetch_flows_all_pairs`](Self::fetch_flows_all_pairs).
+    pub fn with_flow_pairs(mut self, pairs: Vec<ZonePair>) -> Self {
+        self.flow_pairs = pairs;
+        self
+    }
+
+    /// Send webhook events for new tomorrow prices, completed and failed
+    /// fetches and repaired gaps.
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookNotifier>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Evaluate price alert rules whenever prices are stored.
+    pub fn with_alerts(mut self, alerts: Arc<AlertService>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Push prices to InfluxDB whenever they are stored.
+    pub fn with_influx(mut self, influx: Arc<InfluxExporter>) -> Self {
+        self.influx = Some(influx);
+        self
+    }
+
+    /// Upsert prices once the write coordinator admits `priority`.
+    async fn store_prices(&self, prices: &[Price], priority: WritePriority) -> Result<usize, StorageError> {
+        let new_negative = self.new_negative_hours(prices).await;
+        let stored = self.writes.run(priority, || self.repository.upsert_prices(prices)).await?;
+        for (zone_code, count) in new_negative {
+            metrics::record_negative_price_hours(&zone_code, count);
+        }
+        if let Some(alerts) = &self.alerts {
+            alerts.notify(prices.to_vec());
+        }
+        if let Some(influx) = &self.influx {
+            influx.push(prices.to_vec());
+        }
+        Ok(stored)
+    }
+
+    /// Hours per zone priced below zero in `prices` that are not stored as
+    /// negative yet, so a re-fetched day is not counted again. Must run
+    /// before `prices` are upserted. A failed read counts none for the zone.
+    async fn new_negative_hours(&self, prices: &[Price]) -> BTreeMap<String, u64> {
+        let mut by_series: BTreeMap<(&str, Market), Vec<Price>> = BTreeMap::new();
+        for price in prices {
+            by_series.entry((price.bidding_zone.as_str(), price.market)).or_default().push(price.clone());
+        }
+
+        let mut counts = BTreeMap::new();
+        for ((zone_code, market), fetched) in by_series {
+            let fetched = negative_hours(fetched);
+            let (Some(start), Some(last)) = (fetched.first(), fetched.last()) else {
+                continue;
+            };
+            let end = *last + chrono::Duration::hours(1);
+            let stored = match self.repository.get_prices_by_zone(zone_code, market, *start, end).await {
+                Ok(stored) => negative_hours(stored),
+                Err(e) => {
+                    warn!(zone_code = zone_code, error = %e, "Failed to read stored prices, not counting negative hours");
+                    continue;
+                }
+            };
+            let count = fetched.difference(&stored).count() as u64;
+            if count > 0 {
+                *counts.entry(zone_code.to_string()).or_default() += count;
+            }
+        }
+        counts
+    }
+
+    /// Record the endpoints that served a fetch on its log entry,
+    /// comma-separated when there were several. The fetch is done by then, so
+    /// a failure to record them is only logged.
+    async fn log_endpoints(&self, fetch_id: i64, served_by: &BTreeSet<String>) {
+        if served_by.is_empty() {
+            return;
+        }
+        let endpoints: Vec<&str> = served_by.iter().map(String::as_str).collect();
+        if let Err(e) = self.repository.log_fetch_endpoint(fetch_id, &endpoints.join(", ")).await {
+            warn!(fetch_id, error = %e, "Failed to record the endpoints that served a fetch");
+        }
+    }
+
+    /// Fetch `market` prices for `zones` on `date`, five at a time, opening a
+    /// fetch log entry per zone. When an entry cannot be opened the run
+    /// fails, and the entries opened for the other zones are closed as errors
+    /// instead of being left pending.
+    async fn fetch_zones(
+        &self,
+        zones: Vec<BiddingZone>,
+        date: NaiveDate,
+        market: Market,
+    ) -> Result<Vec<ZoneFetch>, StorageError> {
+        let (period_start, period_end) = delivery_period(date);
+        let fetches: Vec<Result<ZoneFetch, StorageError>> = stream::iter(zones)
+            .map(|zone| {
+                let client = Arc::clone(&self.client);
+                let repository = Arc::clone(&self.repository);
+                async move {
+                    let fetch_id = repository
+                        .log_fetch_start(Some(zone.zone_code.clone()), period_start, period_end, current_correlation_id())
+                        .await?;
+                    let start = Instant::now();
+                    let (result, trace) = traced(client.fetch_prices_with_retry(&zone, date, market)).await;
+                    Ok(ZoneFetch {
+                        fetch_id,
+                        trace,
+                        duration_ms: start.elapsed().as_millis() as i32,
+                        zone,
+                        result,
+                    })
+                }
+            })
+            .buffer_unordered(5)
+            .collect()
+            .await;
+
+        let mut fetched = Vec::with_capacity(fetches.len());
+        let mut log_error = None;
+        for fetch in fetches {
+            match fetch {
+                Ok(fetch) => fetched.push(fetch),
+                Err(e) => {
+                    log_error.get_or_insert(e);
+                }
+            }
+        }
+        let Some(log_error) = log_error else {
+            return Ok(fetched);
+        };
+        let message = format!("Run aborted, a fetch log entry could not be opened: {}", log_error);
+        for fetch in &fetched {
+            if let Err(e) = self
+                .repository
+                .log_fetch_complete(fetch.fetch_id, FetchStatus::Error, 0, Some(message.clone()), None, fetch.duration_ms)
+                .await
+            {
+                warn!(fetch_id = fetch.fetch_id, error = %e, "Failed to close the fetch log entry of an aborted run");
+            }
+        }
+        Err(log_error)
+    }
+
+    /// Diff and upsert the prices fetched in one run, returning the diffs
+    /// for the `fetch_completed` webhook and the number of rows stored.
+    async fn store_fetched(&self, prices: &[Price], market: Market) -> Result<(Vec<ZoneFetchDiff>, usize), anyhow::Error> {
+        if prices.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+        let diffs = self.diff_with_stored(prices, market).await;
+        let stored = self.store_prices(prices, WritePriority::Scheduled).await?;
+        Ok((diffs, stored))
+    }
+
+    /// Complete each zone's fetch log entry. Zones whose prices could not be
+    /// stored are logged as errors.
+    async fn complete_zone_logs(
+        &self,
+        logs: &mut [ZoneFetchLog],
+        store_error: Option<&anyhow::Error>,
+    ) -> Result<(), StorageError> {
+        for log in logs.iter_mut() {
+            if let (Some(e), FetchStatus::Success) = (store_error, &log.status) {
+                log.status = FetchStatus::Error;
+                log.records = 0;
+                log.error = Some(format!("Failed to store prices: {}", e));
+            }
+            self.repository
+                .log_fetch_complete(
+                    log.fetch_id,
+                    log.status.clone(),
+                    log.records as i32,
+                    log.error.clone(),
+                    log.http_status,
+                    log.duration_ms,
+                )
+                .await?;
+            if !matches!(log.status, FetchStatus::Error | FetchStatus::RateLimited) {
+                self.log_endpoints(log.fetch_id, &log.served_by).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Queue the zones whose day-ahead fetch for `date` failed for retry. A
+    /// failure to queue them is logged rather than failing the run, which
+    /// has its own result to report; the hourly retries still cover them.
+    async fn enqueue_failed_zones(&self, date: NaiveDate, logs: &[ZoneFetchLog]) {
+        let Some(config) = &self.retry_queue else {
+            return;
+        };
+        let now = self.clock.now();
+        let retries: Vec<FetchRetry> = logs
+            .iter()
+            .filter(|log| matches!(log.status, FetchStatus::Error | FetchStatus::RateLimited))
+            .map(|log| queued_retry(config, &log.zone_code, date, log.error.clone(), now))
+            .collect();
+        if retries.is_empty() {
+            return;
+        }
+        match self.repository.enqueue_fetch_retries(&retries).await {
+            Ok(()) => {
+                info!(date = %date, zone_count = retries.len(), "Queued failed zones for retry");
+                metrics::record_fetch_retries("queued", retries.len() as u64);
+            }
+            Err(e) => warn!(date = %date, zone_count = retries.len(), error = %e, "Failed to queue failed zones for retry"),
+        }
+    }
+
+    fn notify(&self, event: WebhookEvent) {
+        if let Some(webhooks) = &self.webhooks {
+            webhooks.notify(event);
+        }
+    }
+
+    /// Compare fetched prices with the stored ones per zone, for the
+    /// `fetch_completed` webhook. Must run before `prices` are upserted;
+    /// skipped when no webhooks are configured. The webhook must not cost the
+    /// fetch its prices, so a failed read is logged and yields no diffs.
+    async fn diff_with_stored(&self, prices: &[Price], market: Market) -> Vec<ZoneFetchDiff> {
+        if self.webhooks.is_none() {
+            return Vec::new();
+        }
+
+        let mut by_zone: BTreeMap<&str, Vec<&Price>> = BTreeMap::new();
+        for price in prices {
+            by_zone.entry(price.bidding_zone.as_str()).or_default().push(price);
+        }
+
+        let mut diffs = Vec::with_capacity(by_zone.len());
+        for (zone_code, fetched) in by_zone {
+            let start = fetched.iter().map(|p| p.timestamp).min().unwrap();
+            let end = fetched.iter().map(|p| p.timestamp).max().unwrap() + chrono::Duration::seconds(1);
+            let stored = match self.repository.get_prices_by_zone(zone_code, market, start, end).await {
+                Ok(stored) => stored,
+                Err(e) => {
+                    warn!(zone_code = zone_code, error = %e, "Failed to read stored prices to diff, storing without a diff");
+                    return Vec::new();
+                }
+            };
+            diffs.push(ZoneFetchDiff::between(zone_code, &stored, fetched));
+        }
+        diffs
+    }
+
+    fn notify_completed(&self, date: NaiveDate, market: Market, zones: Vec<ZoneFetchDiff>) {
+        if !zones.is_empty() {
+            self.notify(WebhookEvent::FetchCompleted { date, market, zones });
+        }
+    }
+
+    /// Warm `cache` after every fetch that stores prices.
+    pub fn with_cache(mut self, cache: Arc<PriceCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    async fn refresh_cache(&self, prices_stored: usize) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        if prices_stored == 0 {
+            return;
+        }
+        if let Err(e) = cache.warm(self.repository.as_ref()).await {
+            // Stale data must not be served once new prices are stored
+            cache.invalidate();
+            warn!(error = %e, "Failed to warm price cache, invalidated it instead");
+        }
+    }
+
+    pub fn client(&self) -> &S {
+        &self.client
+    }
+
+    /// Load active zones, treating an empty registry as an error so that a
+    /// misconfigured deployment does not appear to fetch successfully.
+    async fn load_active_zones(&self, context: &str) -> Result<Vec<BiddingZone>, anyhow::Error> {
+        let zones = self.repository.load_zones().await?;
+        metrics::update_active_zones(zones.len() as u64);
+
+        if zones.is_empty() {
+            metrics::record_empty_zone_registry(context);
+            error!(context = context, "No active bidding zones loaded, zone registry is empty");
+            return Err(anyhow::anyhow!("No active bidding zones found in zone registry"));
+        }
+
+        Ok(zones)
+    }
+
+    /// [`Self::load_active_zones`], keeping only the zones valid on `date`,
+    /// so a split zone and its successors are never fetched for the same day.
+    async fn zones_valid_on(&self, context: &str, date: NaiveDate) -> Result<Vec<BiddingZone>, anyhow::Error> {
+        let mut zones = self.load_active_zones(context).await?;
+        zones.retain(|zone| zone.is_valid_on(date));
+        Ok(zones)
+    }
+
+    /// Fetch and store day-ahead prices for every active zone.
+    pub async fn fetch_date_all_zones(&self, date: NaiveDate) -> Result<FetchSummary, anyhow::Error> {
+        self.fetch_market_all_zones(date, Market::DayAhead).await
+    }
+
+    #[tracing::instrument(skip(self), fields(date = %date, market = %market))]
+    pub async fn fetch_market_all_zones(&self, date: NaiveDate, market: Market) -> Result<FetchSummary, anyhow::Error> {
+        let start = Instant::now();
+        
+        let zones = self.zones_valid_on("fetch", date).await?;
+        info!(zone_count = zones.len(), "Loaded active zones for fetching");
+
+        // Zones still missing tomorrow's day-ahead prices, so that only their
+        // first successful fetch is announced.
+        let announce = self.webhooks.is_some() && market == Market::DayAhead && date > self.clock.today();
+        let awaiting_tomorrow: HashSet<String> = if announce {
+            let zone_codes: Vec<String> = zones.iter().map(|z| z.zone_code.clone()).collect();
+            self.repository
+                .find_gaps(date, date, &zone_codes)
+                .await?
+                .into_iter()
+                .map(|gap| gap.zone_code)
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        let mut newly_available = Vec::new();
+
+        let fetches = self.fetch_zones(zones, date, market).await?;
+
+        let mut summary = FetchSummary::default();
+        let mut all_prices: Vec<Price> = Vec::new();
+        let mut logs = Vec::with_capacity(fetches.len());
+
+        for fetch in fetches {
+            logs.push(ZoneFetchLog::new(&fetch));
+            let zone = fetch.zone;
+            match fetch.result {
+                Ok(prices) if prices.is_empty() => {
+                    summary.no_data += 1;
+                    warn!(zone_code = %zone.zone_code, "No data available for zone");
+                }
+                Ok(prices) => {
+                    summary.succeeded += 1;
+                    info!(zone_code = %zone.zone_code, count = prices.len(), "Fetched prices for zone");
+                    if awaiting_tomorrow.contains(&zone.zone_code) && !prices.is_empty() {
+                        newly_available.push((zone.zone_code.clone(), prices.len()));
+                    }
+                    all_prices.extend(prices);
+                }
+                Err(EntsoeError::NoData) => {
+                    summary.no_data += 1;
+                    warn!(zone_code = %zone.zone_code, "No data available (NoData error)");
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    let error_msg = format!("{}: {}", zone.zone_code, e);
+                    error!(zone_code = %zone.zone_code, error = %e, "Failed to fetch prices");
+                    summary.errors.push(error_msg);
+                    self.notify(WebhookEvent::FetchFailed {
+                        zone_code: zone.zone_code.clone(),
+                        date,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        let stored = self.store_fetched(&all_prices, market).await;
+        self.complete_zone_logs(&mut logs, stored.as_ref().err()).await?;
+        if market == Market::DayAhead {
+            self.enqueue_failed_zones(date, &logs).await;
+        }
+        let (diffs, stored) = stored?;
+        if !all_prices.is_empty() {
+            summary.total_prices_stored = stored;
+            info!(
+                count = stored,
+                duration_ms = start.elapsed().as_millis(),
+                "Batch upserted prices"
+            );
+        }
+
+        for (zone_code, price_count) in newly_available {
+            self.notify(WebhookEvent::TomorrowPricesAvailable {
+                zone_code,
+                date,
+                price_count,
+            });
+        }
+        self.notify_completed(date, market, diffs);
+
+        info!(
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            no_data = summary.no_data,
+            total_prices = summary.total_prices_stored,
+            duration_ms = start.elapsed().as_millis(),
+            "Completed fetch for date"
+        );
+
+        Ok(summary)
+    }
+
+    /// Fetch and store actual generation per production type for every
+    /// active zone. Generation fetches are not recorded in the fetch log,
+    /// which tracks price fetches only. `total_prices_stored` in the summary
+    /// counts stored generation points.
+    #[tracing::instrument(skip(self), fields(date = %date))]
+    pub async fn fetch_generation_all_zones(&self, date: NaiveDate) -> Result<FetchSummary, anyhow::Error> {
+        let start = Instant::now();
+
+        let zones = self.zones_valid_on("generation", date).await?;
+
+        let results: Vec<(BiddingZone, Result<Vec<GenerationPoint>, EntsoeError>)> = stream::iter(zones)
+            .map(|zone| {
+                let client = Arc::clone(&self.client);
+                async move {
+                    let result = client.fetch_generation(&zone, date).await;
+                    (zone, result)
+                }
+            })
+            .buffer_unordered(5)
+            .collect()
+            .await;
+
+        let mut summary = FetchSummary::default();
+        let mut all_points: Vec<GenerationPoint> = Vec::new();
+
+        for (zone, result) in results {
+            match result {
+                Ok(points) if points.is_empty() => summary.no_data += 1,
+                Ok(points) => {
+                    summary.succeeded += 1;
+                    all_points.extend(points);
+                }
+                Err(EntsoeError::NoData) => summary.no_data += 1,
+                Err(e) => {
+                    summary.failed += 1;
+                    error!(zone_code = %zone.zone_code, error = %e, "Failed to fetch generation");
+                    summary.errors.push(format!("{}: {}", zone.zone_code, e));
+                }
+            }
+        }
+
+        if !all_points.is_empty() {
+            summary.total_prices_stored = self.repository.upsert_generation(&all_points).await?;
+        }
+
+        info!(
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            no_data = summary.no_data,
+            points_stored = summary.total_prices_stored,
+            duration_ms = start.elapsed().as_millis(),
+            "Completed generation fetch for date"
+        );
+
+        Ok(summary)
+    }
+
+    /// Fetch and store actual total load for every active zone, retrying
+    /// transient errors. Like generation, load fetches are not recorded in the
+    /// fetch log; `total_prices_stored` counts stored load values.
+    #[tracing::instrument(skip(self), fields(date = %date))]
+    pub async fn fetch_load_all_zones(&self, date: NaiveDate) -> Result<FetchSummary, anyhow::Error> {
+        let start = Instant::now();
+
+        let zones = self.zones_valid_on("load", date).await?;
+
+        let results: Vec<(BiddingZone, Result<Vec<LoadValue>, EntsoeError>)> = stream::iter(zones)
+            .map(|zone| {
+                let client = Arc::clone(&self.client);
+                async move {
+                    let result = client.fetch_load_with_retry(&zone, date).await;
+                    (zone, result)
+                }
+            })
+            .buffer_unordered(5)
+            .collect()
+            .await;
+
+        let mut summary = FetchSummary::default();
+        let mut all_values: Vec<LoadValue> = Vec::new();
+
+        for (zone, result) in results {
+            match result {
+                Ok(values) if values.is_empty() => summary.no_data += 1,
+                Ok(values) => {
+                    summary.succeeded += 1;
+                    all_values.extend(values);
+                }
+                Err(EntsoeError::NoData) => summary.no_data += 1,
+                Err(e) => {
+                    summary.failed += 1;
+                    error!(zone_code = %zone.zone_code, error = %e, "Failed to fetch load");
+                    summary.errors.push(format!("{}: {}", zone.zone_code, e));
+                }
+            }
+        }
+
+        if !all_values.is_empty() {
+            summary.total_prices_stored = self.repository.upsert_load(&all_values).await?;
+        }
+
+        info!(
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            no_data = summary.no_data,
+            values_stored = summary.total_prices_stored,
+            duration_ms = start.elapsed().as_millis(),
+            "Completed load fetch for date"
+        );
+
+        Ok(summary)
+    }
+
+    /// Fetch and store physical flows and scheduled exchanges in both
+    /// directions for every configured border. Pairs naming a zone missing
+    /// from the active registry count as failed. Like load, flow fetches are
+    /// not recorded in the fetch log; `total_prices_stored` counts stored
+    /// flow values.
+    #[tracing::instrument(skip(self), fields(date = %date))]
+    pub async fn fetch_flows_all_pairs(&self, date: NaiveDate) -> Result<FetchSummary, anyhow::Error> {
+        let start = Instant::now();
+        let mut summary = FetchSummary::default();
+
+        if self.flow_pairs.is_empty() {
+            return Ok(summary);
+        }
+
+        let zones = self.zones_valid_on("flows", date).await?;
+        let zone = |code: &str| zones.iter().find(|z| z.zone_code == code).cloned();
+
+        let mut requests: Vec<(BiddingZone, BiddingZone, FlowKind)> = Vec::new();
+        for pair in self.flow_pairs.iter().flat_map(|p| [p.clone(), p.reversed()]) {
+            match (zone(&pair.from), zone(&pair.to)) {
+                (Some(from), Some(to)) => {
+                    for kind in FlowKind::ALL {
+                        requests.push((from.clone(), to.clone(), kind));
+                    }
+                }
+                _ => {
+                    summary.failed += 1;
+                    warn!(from = %pair.from, to = %pair.to, "Flow pair names an unknown or inactive zone");
+                    summary.errors.push(format!("{}->{}: unknown zone", pair.from, pair.to));
+                }
+            }
+        }
+
+        let results: Vec<(String, Result<Vec<CrossBorderFlow>, EntsoeError>)> = stream::iter(requests)
+            .map(|(from, to, kind)| {
+                let client = Arc::clone(&self.client);
+                async move {
+                    let result = client.fetch_flows(&from, &to, date, kind).await;
+                    (format!("{}->{} {}", from.zone_code, to.zone_code, kind), result)
+                }
+            })
+            .buffer_unordered(5)
+            .collect()
+            .await;
+
+        let mut all_flows: Vec<CrossBorderFlow> = Vec::new();
+
+        for (label, result) in results {
+            match result {
+                Ok(flows) if flows.is_empty() => summary.no_data += 1,
+                Ok(flows) => {
+                    summary.succeeded += 1;
+                    all_flows.extend(flows);
+                }
+                Err(EntsoeError::NoData) => summary.no_data += 1,
+                Err(e) => {
+                    summary.failed += 1;
+                    error!(flow = %label, error = %e, "Failed to fetch cross-border flows");
+                    summary.errors.push(format!("{}: {}", label, e));
+                }
+            }
+        }
+
+        if !all_flows.is_empty() {
+            summary.total_prices_stored = self.repository.upsert_flows(&all_flows).await?;
+        }
+
+        info!(
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            no_data = summary.no_data,
+            flows_stored = summary.total_prices_stored,
+            duration_ms = start.elapsed().as_millis(),
+            "Completed cross-border flow fetch for date"
+        );
+
+        Ok(summary)
+    }
+
+    /// Fetch today's and tomorrow's prices for every zone, holding the price
+    /// fetch locks for both days.
+    pub async fn fetch_all_prices(&self) -> Result<FetchSummary, anyhow::Error> {
+        let today = self.clock.today();
+        let tomorrow = self.clock.tomorrow();
+        self.run_locked(PRICE_FETCH_LOCK, today, self.run_locked(PRICE_FETCH_LOCK, tomorrow, self.fetch_all_prices_unlocked()))
+            .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn fetch_all_prices_unlocked(&self) -> Result<FetchSummary, anyhow::Error> {
+        let start = Instant::now();
+        let now = self.clock.now();
+        let today = now.date_naive();
+        let tomorrow = today.succ_opt().unwrap();
+
+        info!(today = %today, tomorrow = %tomorrow, "Starting fetch for today and tomorrow");
+
+        // The run is logged without a zone, next to the per-zone entries
+        let period_start = now;
+        let period_end = now + chrono::Duration::days(2);
+        let fetch_id = self
+            .repository
+            .log_fetch_start(None, period_start, period_end, current_correlation_id())
+            .await?;
+
+        // Fail the run up front when the zones cannot be read
+        if let Err(e) = self.load_active_zones("fetch").await {
+            self.repository
+                .log_fetch_complete(
+                    fetch_id,
+                    FetchStatus::Error,
+                    0,
+                    Some(e.to_string()),
+                    None,
+                    start.elapsed().as_millis() as i32,
+                )
+                .await?;
+            return Err(e);
+        }
+
+        // Every endpoint that served the run, across zones and dates
+        let (combined_summary, trace) = traced(async {
+            let mut combined_summary = FetchSummary::default();
+
+            match self.fetch_date_all_zones(today).await {
+                Ok(summary) => combined_summary.merge(summary),
+                Err(e) => {
+                    error!(error = %e, "Failed to fetch today's prices");
+                    combined_summary.errors.push(format!("Today fetch failed: {}", e));
+                }
+            }
+
+            match self.fetch_date_all_zones(tomorrow).await {
+                Ok(summary) => combined_summary.merge(summary),
+                Err(e) => {
+                    error!(error = %e, "Failed to fetch tomorrow's prices");
+                    combined_summary.errors.push(format!("Tomorrow fetch failed: {}", e));
+                }
+            }
+
+            if self.fetch_intraday {
+                for date in [today, tomorrow] {
+                    match self.fetch_market_all_zones(date, Market::Intraday).await {
+                        Ok(summary) => combined_summary.merge(summary),
+                        Err(e) => {
+                            error!(error = %e, date = %date, "Failed to fetch intraday prices");
+                            combined_summary.errors.push(format!("Intraday fetch for {} failed: {}", date, e));
+                        }
+                    }
+                }
+            }
+            combined_summary
+        })
+        .await;
+
+        let duration_ms = start.elapsed().as_millis() as i32;
+        let status = if combined_summary.failed > 0 {
+            FetchStatus::Error
+        } else if combined_summary.succeeded == 0 && combined_summary.no_data > 0 {
+            FetchStatus::NoData
+        } else {
+            FetchStatus::Success
+        };
+
+        let error_message = if combined_summary.errors.is_empty() {
+            None
+        } else {
+            Some(combined_summary.errors.join("; "))
+        };
+
+        self.repository
+            .log_fetch_complete(
+                fetch_id,
+                status,
+                combined_summary.total_prices_stored as i32,
+                error_message,
+                None,
+                duration_ms,
+            )
+            .await?;
+        self.log_endpoints(fetch_id, &trace.served_by).await;
+
+        info!(
+            succeeded = combined_summary.succeeded,
+            failed = combined_summary.failed,
+            no_data = combined_summary.no_data,
+            total_prices = combined_summary.total_prices_stored,
+            duration_ms = duration_ms,
+            "Completed full fetch operation"
+        );
+
+        self.refresh_cache(combined_summary.total_prices_stored).await;
+
+        Ok(combined_summary)
+    }
+
+    /// Fetch prices for a raw EIC code and delivery date (in `timezone`)
+    /// without storing or logging them, to evaluate an area before adding it
+    /// to the zone registry. Prices carry the EIC as their zone code.
+    #[tracing::instrument(skip(self), fields(eic_code = %eic_code, date = %date, market = %market))]
+    pub async fn preview_eic(
+        &self,
+        eic_code: &str,
+        date: NaiveDate,
+        market: Market,
+        timezone: &str,
+    ) -> Result<Vec<Price>, EntsoeError> {
+        let zone = BiddingZone::provisional(eic_code, timezone);
+        match self.client.fetch_prices_with_retry(&zone, date, market).await {
+            Err(EntsoeError::NoData) => Ok(Vec::new()),
+            result => result,
+        }
+    }
+
+    /// Register a new zone after checking its timezone and that ENTSOE
+    /// publishes day-ahead prices for its EIC code on `test_date`, then
+    /// backfill the `backfill_days` delivery days ending on `test_date`.
+    #[tracing::instrument(skip(self, zone), fields(zone_code = %zone.zone_code, eic_code = %zone.eic_code))]
+    pub async fn onboard_zone(
+        &self,
+        zone: &ZoneDefinition,
+        test_date: NaiveDate,
+        backfill_days: u32,
+    ) -> Result<ZoneOnboarding, anyhow::Error> {
+        anyhow::ensure!(is_valid_eic(&zone.eic_code), "Invalid EIC code: {}", zone.eic_code);
+        zone.timezone
+            .parse::<chrono_tz::Tz>()
+            .map_err(|_| anyhow::anyhow!("Invalid timezone: {}", zone.timezone))?;
+        if self.repository.get_zone_by_code(&zone.zone_code).await.is_ok() {
+            anyhow::bail!("Zone {} is already registered", zone.zone_code);
+        }
+        if let Ok(existing) = self.repository.get_zone_by_eic(&zone.eic_code).await {
+            anyhow::bail!("EIC code {} is already registered as zone {}", zone.eic_code, existing.zone_code);
+        }
+
+        let test_prices = self
+            .preview_eic(&zone.eic_code, test_date, Market::DayAhead, &zone.timezone)
+            .await?
+            .len();
+        anyhow::ensure!(
+            test_prices > 0,
+            "ENTSOE returned no day-ahead prices for {} on {}; check the EIC code",
+            zone.eic_code,
+            test_date
+        );
+
+        let inserted = self.repository.insert_missing_zones(std::slice::from_ref(zone)).await?;
+        anyhow::ensure!(!inserted.is_empty(), "Zone {} was not inserted", zone.zone_code);
+        info!(test_prices = test_prices, "Zone registered");
+
+        let backfill = if backfill_days > 0 {
+            let start_date = test_date - chrono::Duration::days(backfill_days as i64 - 1);
+            Some(
+                self.backfill_missing(start_date, test_date, Some(vec![zone.zone_code.clone()]))
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        Ok(ZoneOnboarding { test_prices, backfill })
+    }
+
+    /// Re-parse stored A44 response bodies, as `(name, body)` pairs, through
+    /// the current validation and aggregation and upsert the prices, e.g.
+    /// after fixing a parsing bug. Each document is attributed to the
+    /// registered zone with its `in_Domain.mRID` EIC code. No fetch log
+    /// entries or webhooks are written.
+    #[tracing::instrument(skip(self, documents), fields(documents = documents.len()))]
+    pub async fn reprocess_documents(&self, documents: &[(String, String)]) -> Result<FetchSummary, anyhow::Error> {
+        let mut summary = FetchSummary::default();
+
+        for (name, body) in documents {
+            let eic_code = match price_document_domain(body) {
+                Ok(Some(eic_code)) => eic_code,
+                Ok(None) => {
+                    summary.no_data += 1;
+                    continue;
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    summary.errors.push(format!("{}: {}", name, e));
+                    continue;
+                }
+            };
+
+            let zone = match self.repository.get_zone_by_eic(&eic_code).await {
+                Ok(zone) => zone,
+                Err(e) if e.is_not_found() => {
+                    summary.failed += 1;
+                    summary.errors.push(format!("{}: no registered zone for EIC {}", name, eic_code));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            match parse_price_document_all_markets(body, &zone, &self.client.price_rules()) {
+                Ok(prices) if prices.is_empty() => summary.no_data += 1,
+                Ok(prices) => {
+                    let stored = self.store_prices(&prices, WritePriority::Backfill).await?;
+                    info!(document = %name, zone_code = %zone.zone_code, count = stored, "Reprocessed archived document");
+                    summary.succeeded += 1;
+                    summary.total_prices_stored += stored;
+                }
+                Err(e) => {
+                    warn!(document = %name, error = %e, "Failed to reprocess archived document");
+                    summary.failed += 1;
+                    summary.errors.push(format!("{}: {}", name, e));
+                }
+            }
+        }
+
+        self.refresh_cache(summary.total_prices_stored).await;
+
+        Ok(summary)
+    }
+
+    /// Fetch and store one zone for one delivery date, recording it in the
+    /// fetch log under that zone. Dates before the zone's `valid_from` are
+    /// fetched for its predecessor. Holds the price fetch lock for `date`.
+    pub async fn fetch_zone(&self, zone: &BiddingZone, date: NaiveDate) -> Result<ZoneFetchResult, anyhow::Error> {
+        let zone = self.zone_valid_on(zone, date).await?;
+        self.run_locked(PRICE_FETCH_LOCK, date, self.fetch_zone_unlocked(&zone, date)).await
+    }
+
+    /// `zone`, or the predecessor it replaced that was valid on `date`.
+    async fn zone_valid_on(&self, zone: &BiddingZone, date: NaiveDate) -> Result<BiddingZone, anyhow::Error> {
+        let mut current = zone.clone();
+        let mut seen = HashSet::new();
+        while !current.is_valid_on(date) {
+            let predecessor = match &current.predecessor_code {
+                Some(code) if current.valid_from.is_some_and(|from| date < from) && seen.insert(code.clone()) => {
+                    code.clone()
+                }
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Zone {} is not valid on {} and has no predecessor for that date",
+                        zone.zone_code,
+                        date
+                    ))
+                }
+            };
+            current = self.repository.get_zone_by_code(&predecessor).await?;
+        }
+        Ok(current)
+    }
+
+    #[tracing::instrument(skip(self, zone), fields(zone_code = %zone.zone_code, date = %date))]
+    async fn fetch_zone_unlocked(&self, zone: &BiddingZone, date: NaiveDate) -> Result<ZoneFetchResult, anyhow::Error> {
+        let start = Instant::now();
+        let (period_start, period_end) = delivery_period(date);
+        let fetch_id = self
+            .repository
+            .log_fetch_start(Some(zone.zone_code.clone()), period_start, period_end, current_correlation_id())
+            .await?;
+
+        let (fetched, trace) = traced(self.client.fetch_day_ahead_prices_with_retry(zone, date)).await;
+        let (status, prices_stored, error) = match fetched {
+            Ok(prices) if prices.is_empty() => (FetchStatus::NoData, 0, None),
+            Ok(prices) => {
+                let diffs = self.diff_with_stored(&prices, Market::DayAhead).await;
+                // Logged as a failed fetch like any other, so the entry does
+                // not stay pending
+                match self.store_prices(&prices, WritePriority::Scheduled).await {
+                    Ok(stored) => {
+                        self.notify_completed(date, Market::DayAhead, diffs);
+                        (FetchStatus::Success, stored, None)
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Failed to store prices");
+                        (FetchStatus::Error, 0, Some(format!("Failed to store prices: {}", e)))
+                    }
+                }
+            }
+            Err(EntsoeError::NoData) => (FetchStatus::NoData, 0, None),
+            Err(e) => {
+                error!(error = %e, "Failed to fetch prices");
+                self.notify(WebhookEvent::FetchFailed {
+                    zone_code: zone.zone_code.clone(),
+                    date,
+                    error: e.to_string(),
+                });
+                let status = match e {
+                    EntsoeError::RateLimited => FetchStatus::RateLimited,
+                    _ => FetchStatus::Error,
+                };
+                (status, 0, Some(e.to_string()))
+            }
+        };
+
+        self.repository
+            .log_fetch_complete(
+                fetch_id,
+                status.clone(),
+                prices_stored as i32,
+                error.clone(),
+                trace.http_status.map(i32::from),
+                start.elapsed().as_millis() as i32,
+            )
+            .await?;
+        if !matches!(status, FetchStatus::Error | FetchStatus::RateLimited) {
+            self.log_endpoints(fetch_id, &trace.served_by).await;
+        }
+
+        info!(fetch_id = fetch_id, status = status.as_str(), count = prices_stored, "Completed zone fetch");
+
+        self.refresh_cache(prices_stored).await;
+
+        Ok(ZoneFetchResult {
+            fetch_id,
+            zone_code: zone.zone_code.clone(),
+            status,
+            prices_stored,
+            error,
+        })
+    }
+
+    /// Retry the queued zone/dates that are due, after dropping expired ones.
+    /// A zone/date is removed once it is fetched, and rescheduled with a
+    /// longer backoff when it fails again.
+    #[tracing::instrument(skip(self))]
+    pub async fn drain_retry_queue(&self) -> Result<RetryQueueSummary, anyhow::Error> {
+        let Some(config) = &self.retry_queue else {
+            return Ok(RetryQueueSummary::default());
+        };
+        let now = self.clock.now();
+        let mut summary = RetryQueueSummary {
+            expired: self.repository.delete_expired_fetch_retries(now).await?,
+            ..Default::default()
+        };
+        metrics::record_fetch_retries("expired", summary.expired);
+
+        for retry in self.repository.get_due_fetch_retries(now).await? {
+            let (zone_code, date) = (retry.zone_code.as_str(), retry.target_date);
+            // One entry's storage error must not hold up the rest of the queue
+            match self.retry_queued(config, &retry, now).await {
+                Ok(RetryOutcome::Succeeded) => summary.succeeded += 1,
+                Ok(RetryOutcome::Rescheduled) => summary.rescheduled += 1,
+                Ok(RetryOutcome::Locked) => summary.locked += 1,
+                Ok(RetryOutcome::Dropped) => {}
+                Err(e) => {
+                    summary.failed += 1;
+                    metrics::record_fetch_retries("failed", 1);
+                    warn!(zone_code = %zone_code, date = %date, error = %e, "Queued fetch retry failed, left due");
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Retry one queued zone/date, removing or rescheduling its entry.
+    async fn retry_queued(
+        &self,
+        config: &RetryQueueConfig,
+        retry: &FetchRetry,
+        now: DateTime<Utc>,
+    ) -> Result<RetryOutcome, anyhow::Error> {
+        let (zone_code, date) = (retry.zone_code.as_str(), retry.target_date);
+        let zone = match self.repository.get_zone_by_code(zone_code).await {
+            Ok(zone) => zone,
+            Err(StorageError::NotFound(_)) => {
+                warn!(zone_code = %zone_code, date = %date, "Dropping retry for zone no longer in the registry");
+                self.repository.delete_fetch_retry(zone_code, date).await?;
+                return Ok(RetryOutcome::Dropped);
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        // Fetched since it was queued, e.g. by the hourly retries
+        if self.repository.has_day_ahead_data(zone_code, date).await? {
+            self.repository.delete_fetch_retry(zone_code, date).await?;
+            metrics::record_fetch_retries("succeeded", 1);
+            return Ok(RetryOutcome::Succeeded);
+        }
+
+        match self.fetch_zone(&zone, date).await {
+            Ok(result) if matches!(result.status, FetchStatus::Success) => {
+                self.repository.delete_fetch_retry(zone_code, date).await?;
+                metrics::record_fetch_retries("succeeded", 1);
+                info!(zone_code = %zone_code, date = %date, attempts = retry.attempts + 1, "Queued fetch retry succeeded");
+                Ok(RetryOutcome::Succeeded)
+            }
+            Ok(result) => {
+                let next_attempt_at = now + retry_backoff(config, retry.attempts + 1);
+                let error = result.error.as_deref().unwrap_or(result.status.as_str());
+                self.repository
+                    .reschedule_fetch_retry(zone_code, date, next_attempt_at, Some(error))
+                    .await?;
+                metrics::record_fetch_retries("rescheduled", 1);
+                warn!(
+                    zone_code = %zone_code,
+                    date = %date,
+                    error = %error,
+                    next_attempt_at = %next_attempt_at,
+                    "Queued fetch retry failed, rescheduled"
+                );
+                Ok(RetryOutcome::Rescheduled)
+            }
+            Err(e) if e.is::<JobLocked>() => Ok(RetryOutcome::Locked),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn should_fetch_tomorrow(&self) -> Result<bool, anyhow::Error> {
+        let tomorrow = self.clock.tomorrow();
+        let zones = self.zones_valid_on("conditional_fetch", tomorrow).await?;
+        let mut zones_with_data = 0;
+        let mut zones_missing_data = 0;
+
+        for zone in &zones {
+            if self.repository.has_day_ahead_data(&zone.zone_code, tomorrow).await? {
+                zones_with_data += 1;
+            } else {
+                zones_missing_data += 1;
+            }
+        }
+
+        metrics::update_zones_with_tomorrow_data(zones_with_data as u64);
+
+        info!(
+            zones_with_data = zones_with_data,
+            zones_missing_data = zones_missing_data,
+            "Checked tomorrow data availability"
+        );
+
+        Ok(zones_missing_data > 0)
+    }
+
+    /// Fetch tomorrow's prices for zones that have none yet, holding the
+    /// price fetch lock for tomorrow.
+    pub async fn fetch_tomorrow_if_missing(&self) -> Result<FetchSummary, anyhow::Error> {
+        self.run_locked(PRICE_FETCH_LOCK, self.clock.tomorrow(), self.fetch_tomorrow_if_missing_unlocked())
+            .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn fetch_tomorrow_if_missing_unlocked(&self) -> Result<FetchSummary, anyhow::Error> {
+        if !self.should_fetch_tomorrow().await? {
+            info!("Tomorrow's data already exists for all zones, skipping fetch");
+            return Ok(FetchSummary::default());
+        }
+
+        let start = Instant::now();
+        let tomorrow = self.clock.tomorrow();
+
+        info!(date = %tomorrow, "Fetching tomorrow's prices for zones missing data");
+
+        let zones = self.zones_valid_on("conditional_fetch", tomorrow).await?;
+        let mut zones_to_fetch = Vec::new();
+
+        for zone in zones {
+            if !self.repository.has_day_ahead_data(&zone.zone_code, tomorrow).await? {
+                zones_to_fetch.push(zone);
+            }
+        }
+
+        if zones_to_fetch.is_empty() {
+            info!("No zones need fetching");
+            return Ok(FetchSummary::default());
+        }
+
+        info!(zone_count = zones_to_fetch.len(), "Zones needing tomorrow's data");
+
+        let (tomorrow_start, tomorrow_end) = delivery_period(tomorrow);
+        let fetch_id = self
+            .repository
+            .log_fetch_start(None, tomorrow_start, tomorrow_end, current_correlation_id())
+            .await?;
+
+        let fetches = self.fetch_zones(zones_to_fetch, tomorrow, Market::DayAhead).await?;
+
+        let mut summary = FetchSummary::default();
+        let mut newly_available = Vec::new();
+        let mut all_prices: Vec<Price> = Vec::new();
+        let mut logs = Vec::with_capacity(fetches.len());
+        let mut served = BTreeSet::new();
+
+        for fetch in fetches {
+            logs.push(ZoneFetchLog::new(&fetch));
+            if matches!(fetch.result, Ok(_) | Err(EntsoeError::NoData)) {
+                served.extend(fetch.trace.served_by);
+            }
+            let zone = fetch.zone;
+            match fetch.result {
+                Ok(prices) if prices.is_empty() => {
+                    summary.no_data += 1;
+                    warn!(zone_code = %zone.zone_code, "No data available for zone");
+                }
+                Ok(prices) => {
+                    summary.succeeded += 1;
+                    info!(zone_code = %zone.zone_code, count = prices.len(), "Fetched prices for zone");
+                    if !prices.is_empty() {
+                        newly_available.push((zone.zone_code.clone(), prices.len()));
+                    }
+                    all_prices.extend(prices);
+                }
+                Err(EntsoeError::NoData) => {
+                    summary.no_data += 1;
+                    warn!(zone_code = %zone.zone_code, "No data available (NoData error)");
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    let error_msg = format!("{}: {}", zone.zone_code, e);
+                    error!(zone_code = %zone.zone_code, error = %e, "Failed to fetch prices");
+                    summary.errors.push(error_msg);
+                    self.notify(WebhookEvent::FetchFailed {
+                        zone_code: zone.zone_code.clone(),
+                        date: tomorrow,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        let stored = self.store_fetched(&all_prices, Market::DayAhead).await;
+        self.complete_zone_logs(&mut logs, stored.as_ref().err()).await?;
+        self.enqueue_failed_zones(tomorrow, &logs).await;
+        let (diffs, stored) = stored?;
+        if !all_prices.is_empty() {
+            summary.total_prices_stored = stored;
+            info!(count = stored, "Batch upserted tomorrow's prices");
+        }
+
+        // Only zones without any tomorrow data were fetched, so every success is new
+        for (zone_code, price_count) in newly_available {
+            self.notify(WebhookEvent::TomorrowPricesAvailable {
+                zone_code,
+                date: tomorrow,
+                price_count,
+            });
+        }
+        self.notify_completed(tomorrow, Market::DayAhead, diffs);
+
+        let duration_ms = start.elapsed().as_millis() as i32;
+        let status = if summary.failed > 0 {
+            FetchStatus::Error
+        } else if summary.succeeded == 0 && summary.no_data > 0 {
+            FetchStatus::NoData
+        } else {
+            FetchStatus::Success
+        };
+
+        let error_message = if summary.errors.is_empty() {
+            None
+        } else {
+            Some(summary.errors.join("; "))
+        };
+
+        self.repository
+            .log_fetch_complete(
+                fetch_id,
+                status,
+                summary.total_prices_stored as i32,
+                error_message,
+                None,
+                duration_ms,
+            )
+            .await?;
+        self.log_endpoints(fetch_id, &served).await;
+
+        info!(
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            no_data = summary.no_data,
+            total_prices = summary.total_prices_stored,
+            duration_ms = duration_ms,
+            "Completed conditional tomorrow fetch"
+        );
+
+        self.refresh_cache(summary.total_prices_stored).await;
+
+        Ok(summary)
+    }
+
+    #[tracing::instrument(skip(self), fields(start = %start_date, end = %end_date))]
+    pub async fn backfill_missing(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_filter: Option<Vec<String>>,
+    ) -> Result<BackfillSummary, anyhow::Error> {
+        let start = Instant::now();
+        
+        // Get zones to check. Zones replaced by a split are inactive, but
+        // their days before the split are still backfilled
+        let mut all_zones = self.load_active_zones("backfill").await?;
+        let predecessors: Vec<String> = all_zones
+            .iter()
+            .filter(|z| z.valid_from.is_some_and(|from| start_date < from))
+            .filter_map(|z| z.predecessor_code.clone())
+            .collect();
+        for zone_code in predecessors {
+            if !all_zones.iter().any(|z| z.zone_code == zone_code) {
+                all_zones.push(self.repository.get_zone_by_code(&zone_code).await?);
+            }
+        }
+        let zone_codes: Vec<String> = match &zone_filter {
+            Some(filter) => {
+                let filter_set: HashSet<&str> = filter.iter().map(|s| s.as_str()).collect();
+                all_zones
+                    .iter()
+                    .filter(|z| filter_set.contains(z.zone_code.as_str()))
+                    .map(|z| z.zone_code.clone())
+                    .collect()
+            }
+            None => all_zones.iter().map(|z| z.zone_code.clone()).collect(),
+        };
+
+        if zone_codes.is_empty() {
+            return Err(anyhow::anyhow!("No valid zones found"));
+        }
+
+        info!(zones = ?zone_codes, "Checking for gaps in price data");
+
+        // Calculate dates to check
+        let mut current = start_date;
+        let mut dates_checked = 0;
+        while current <= end_date {
+            dates_checked += 1;
+            current = current.succ_opt().unwrap();
+        }
+
+        // Find gaps in database, and days whose filled prices ENTSOE may
+        // since have published. Days outside a zone's validity are not gaps
+        let valid_on = |date: NaiveDate, zone_code: &str| {
+            all_zones
+                .iter()
+                .find(|z| z.zone_code == zone_code)
+                .is_some_and(|z| z.is_valid_on(date))
+        };
+        let mut gaps = self.repository.find_gaps(start_date, end_date, &zone_codes).await?;
+        gaps.retain(|gap| valid_on(gap.date, &gap.zone_code));
+        let mut estimated = self.repository.find_estimated_hours(start_date, end_date, &zone_codes).await?;
+        estimated.retain(|(date, zone_code, _)| valid_on(*date, zone_code));
+        
+        let mut summary = BackfillSummary {
+            dates_checked,
+            dates_with_gaps: 0,
+            prices_fetched: 0,
+            prices_stored: 0,
+            gaps_found: gaps.iter().map(|gap| (gap.date, gap.zone_code.clone(), gap.missing_hours())).collect(),
+            estimated_found: estimated.clone(),
+            errors: Vec::new(),
+        };
+
+        if gaps.is_empty() && estimated.is_empty() {
+            info!("No gaps found in date range");
+            return Ok(summary);
+        }
+
+        // Collect unique date-zone pairs to fetch
+        let dates_to_fetch: HashSet<(NaiveDate, String)> = gaps
+            .iter()
+            .map(|gap| (gap.date, gap.zone_code.clone()))
+            .chain(estimated.iter().map(|(date, zone, _)| (*date, zone.clone())))
+            .collect();
+
+        summary.dates_with_gaps = gaps.iter().map(|gap| gap.date).collect::<HashSet<_>>().len();
+
+        info!(
+            gaps_count = gaps.len(),
+            estimated_count = estimated.len(),
+            unique_date_zones = dates_to_fetch.len(),
+            "Found gaps, starting backfill"
+        );
+
+        // Create zone lookup map
+        let zone_map: std::collections::HashMap<String, BiddingZone> = all_zones
+            .into_iter()
+            .map(|z| (z.zone_code.clone(), z))
+            .collect();
+
+        // Under the scheduled fetch's lock, so the two never write the same
+        // day at once. Days another run holds are left to that run
+        let dates: BTreeSet<NaiveDate> = dates_to_fetch.iter().map(|(date, _)| *date).collect();
+        let repaired = run_locked_dates(
+            self.repository.as_ref(),
+            self.clock.as_ref(),
+            self.job_lock_ttl,
+            PRICE_FETCH_LOCK,
+            dates,
+            |held| self.backfill_dates(dates_to_fetch, held, &zone_map, &mut summary),
+        )
+        .await?;
+
+        for (zone_code, date, price_count) in repaired {
+            self.notify(WebhookEvent::GapRepaired {
+                zone_code,
+                date,
+                price_count,
+            });
+        }
+
+        info!(
+            dates_checked = summary.dates_checked,
+            dates_with_gaps = summary.dates_with_gaps,
+            prices_fetched = summary.prices_fetched,
+            prices_stored = summary.prices_stored,
+            errors = summary.errors.len(),
+            duration_ms = start.elapsed().as_millis(),
+            "Completed backfill operation"
+        );
+
+        self.refresh_cache(summary.prices_stored).await;
+
+        Ok(summary)
+    }
+
+    /// Fetch and store the gap days of [`backfill_missing`](Self::backfill_missing)
+    /// whose price fetch lock is `held`, returning the repaired zone/dates
+    /// and their price counts.
+    async fn backfill_dates(
+        &self,
+        dates_to_fetch: HashSet<(NaiveDate, String)>,
+        held: BTreeSet<NaiveDate>,
+        zone_map: &std::collections::HashMap<String, BiddingZone>,
+        summary: &mut BackfillSummary,
+    ) -> Result<Vec<(String, NaiveDate, usize)>, anyhow::Error> {
+        // Fetch missing data
+        let mut all_prices: Vec<Price> = Vec::new();
+        let mut repaired = Vec::new();
+
+        // One request per zone and run of consecutive dates, which the
+        // client pages through when ENTSOE truncates it
+        let mut dates_by_zone: BTreeMap<String, Vec<NaiveDate>> = BTreeMap::new();
+        for (date, zone_code) in dates_to_fetch {
+            if held.contains(&date) {
+                dates_by_zone.entry(zone_code).or_default().push(date);
+            } else {
+                let locked = JobLocked {
+                    job: PRICE_FETCH_LOCK.to_string(),
+                    date,
+                };
+                warn!(zone = %zone_code, date = %date, "Skipping backfill of a day another fetch is running for");
+                summary.errors.push(format!("{} on {}: {}", zone_code, date, locked));
+            }
+        }
+
+        for (zone_code, dates) in dates_by_zone {
+            let Some(zone) = zone_map.get(&zone_code) else {
+                summary.errors.push(format!("Zone {} not found", zone_code));
+                continue;
+            };
+
+            for (first, last) in consecutive_runs(dates) {
+                let period = if first == last { first.to_string() } else { format!("{}..{}", first, last) };
+                match self.client.fetch_day_ahead_prices_range(zone, first, last).await {
+                    Ok(prices) => {
+                        info!(zone = %zone_code, period = %period, count = prices.len(), "Fetched prices");
+                        summary.prices_fetched += prices.len();
+                        let timezone = zone.get_timezone().map_err(|e| anyhow::anyhow!(e))?;
+                        let mut per_date: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+                        for price in &prices {
+                            *per_date.entry(price.timestamp.with_timezone(&timezone).date_naive()).or_default() += 1;
+                        }
+                        repaired.extend(per_date.into_iter().map(|(date, count)| (zone_code.clone(), date, count)));
+                        all_prices.extend(prices);
+                    }
+                    Err(EntsoeError::NoData) => {
+                        warn!(zone = %zone_code, period = %period, "No data available from ENTSO-E");
+                    }
+                    Err(e) => {
+                        let msg = format!("{} on {}: {}", zone_code, period, e);
+                        error!(zone = %zone_code, period = %period, error = %e, "Failed to fetch");
+                        summary.errors.push(msg);
+                        for date in first.iter_days().take_while(|date| *date <= last) {
+                            self.notify(WebhookEvent::FetchFailed {
+                                zone_code: zone_code.clone(),
+                                date,
+                                error: e.to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        // Store fetched prices
+        if !all_prices.is_empty() {
+            let stored = self.store_prices(&all_prices, WritePriority::Backfill).await?;
+            summary.prices_stored = stored;
+            info!(count = stored, "Stored backfilled prices");
+        }
+
+        Ok(repaired)
+    }
+
+    /// Queue a task for the [`TaskWorker`](crate::tasks::TaskWorker) of any
+    /// replica. Returns `None`, queuing nothing, while an unfinished task
+    /// holds the same dedupe key.
+    pub async fn enqueue_task<T: Serialize>(
+        &self,
+        kind: &str,
+        payload: &T,
+        dedupe_key: Option<String>,
+    ) -> Result<Option<Task>, anyhow::Error> {
+        let now = self.clock.now();
+        let task = tasks::new_task(&self.task_queue, kind, payload, dedupe_key, now)?;
+        let queued = self.repository.enqueue_task(&task, now).await?;
+        match &queued {
+            Some(queued) => info!(task_id = queued.id, kind = kind, "Queued task"),
+            None => info!(kind = kind, dedupe_key = ?task.dedupe_key, "Task already queued"),
+        }
+        Ok(queued)
+    }
+
+    /// Queue a repair of the gaps between `start_date` and `end_date` in
+    /// every active zone, unless the same repair is already queued.
+    pub async fn enqueue_gap_repair(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Option<Task>, anyhow::Error> {
+        self.enqueue_task(
+            tasks::GAP_REPAIR,
+            &GapRepairPayload {
+                start: start_date,
+                end: end_date,
+            },
+            Some(format!("{}:{}:{}", tasks::GAP_REPAIR, start_date, end_date)),
+        )
+        .await
+    }
+
+    /// Record a backfill job and queue it to run in the background,
+    /// returning the job record to poll with [`Self::backfill_job`].
+    pub async fn start_backfill_job(&self, job: NewBackfillJob) -> Result<BackfillJob, anyhow::Error> {
+        let mut job = self.repository.create_backfill_job(&job, self.clock.now()).await?;
+        let payload = BackfillJobPayload {
+            job_id: job.id,
+            correlation_id: current_correlation_id(),
+        };
+        let dedupe_key = format!("{}:{}", tasks::BACKFILL_JOB, job.id);
+        if let Err(e) = self.enqueue_task(tasks::BACKFILL_JOB, &payload, Some(dedupe_key)).await {
+            // Nothing would ever run it
+            let now = self.clock.now();
+            job.status = BackfillJobStatus::Failed;
+            job.errors.push(format!("Not queued: {}", e));
+            job.updated_at = now;
+            job.finished_at = Some(now);
+            if let Err(update_error) = self.repository.update_backfill_job(&job).await {
+                error!(job_id = job.id, error = %update_error, "Failed to mark unqueued backfill job failed");
+            }
+            return Err(e);
+        }
+
+        info!(job_id = job.id, start = %job.start_date, end = %job.end_date, "Started backfill job");
+        Ok(job)
+    }
+
+    /// A backfill job and its progress.
+    pub async fn backfill_job(&self, id: i64) -> Result<BackfillJob, StorageError> {
+        self.repository.get_backfill_job(id).await
+    }
+
+    /// Cancel a running backfill job. It stops before its next chunk of
+    /// days; returns false if it was no longer running.
+    pub async fn cancel_backfill_job(&self, id: i64) -> Result<bool, StorageError> {
+        let cancelled = self.repository.cancel_backfill_job(id, self.clock.now()).await?;
+        if cancelled {
+            info!(job_id = id, "Cancelled backfill job");
+        }
+        Ok(cancelled)
+    }
+
+    /// Backfill the rest of a job's range a chunk of days at a time, saving
+    /// progress after each, until it is done or was cancelled. An error
+    /// leaves the job running, for the task's next attempt to resume from
+    /// the saved progress.
+    #[tracing::instrument(skip(self))]
+    pub async fn run_backfill_job(&self, job_id: i64) -> Result<(), anyhow::Error> {
+        let mut job = self.repository.get_backfill_job(job_id).await?;
+        while job.status == BackfillJobStatus::Running {
+            let chunk_start = job.start_date + chrono::Duration::days(job.dates_done as i64);
+            let chunk_end = (chunk_start + chrono::Duration::days(BACKFILL_JOB_CHUNK_DAYS - 1)).min(job.end_date);
+            if chunk_start <= chunk_end {
+                let summary = self.backfill_missing(chunk_start, chunk_end, job.zones.clone()).await?;
+                job.dates_done += ((chunk_end - chunk_start).num_days() + 1) as i32;
+                job.prices_stored += summary.prices_stored as i64;
+                job.errors.extend(summary.errors);
+            }
+
+            let now = self.clock.now();
+            if chunk_end >= job.end_date {
+                job.status = BackfillJobStatus::Completed;
+                job.finished_at = Some(now);
+            }
+            job.updated_at = now;
+            if !self.repository.update_backfill_job(&job).await? {
+                info!(dates_done = job.dates_done, "Backfill job no longer running, stopping");
+                return Ok(());
+            }
+        }
+
+        info!(
+            status = job.status.as_str(),
+            dates_done = job.dates_done,
+            prices_stored = job.prices_stored,
+            errors = job.errors.len(),
+            "Backfill job finished"
+        );
+        Ok(())
+    }
+
+    /// Mark a running backfill job failed with `error`, once its task ran
+    /// out of attempts.
+    pub async fn fail_backfill_job(&self, job_id: i64, error: &str) -> Result<(), StorageError> {
+        let mut job = self.repository.get_backfill_job(job_id).await?;
+        if job.status != BackfillJobStatus::Running {
+            return Ok(());
+        }
+        let now = self.clock.now();
+        job.status = BackfillJobStatus::Failed;
+        job.errors.push(error.to_string());
+        job.updated_at = now;
+        job.finished_at = Some(now);
+        self.repository.update_backfill_job(&job).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetcher::with_correlation_id;
+    use crate::entsoe::{parse_price_document, CannedEntsoeSource};
+    use crate::webhooks::DiffStatus;
+    use rust_decimal::Decimal;
+    use crate::clock::FixedClock;
+    use crate::storage::InMemoryPriceStore;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn client() -> Arc<CannedEntsoeSource> {
+        Arc::new(CannedEntsoeSource::new())
+    }
+
+    /// A44 publication document with 24 hourly points for one UTC day.
+    fn day_ahead_xml(date: NaiveDate) -> String {
+        let day = crate::models::DeliveryDay::new(date, &chrono_tz::Europe::Oslo);
+        let (start, end) = (day.start, day.end);
+        let points: String = (1..=24)
+            .map(|pos| format!("<Point><position>{}</position><price.amount>{}.5</price.amount></Point>", pos, pos))
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+  <mRID>test</mRID>
+  <TimeSeries>
+    <currency_Unit.name>EUR</currency_Unit.name>
+    <price_Measure_Unit.name>MWH</price_Measure_Unit.name>
+    <Period>
+      <timeInterval><start>{}</start><end>{}</end></timeInterval>
+      <resolution>PT60M</resolution>
+      {}
+    </Period>
+  </TimeSeries>
+</Publication_MarketDocument>"#,
+            start.format("%Y-%m-%dT%H:%MZ"),
+            end.format("%Y-%m-%dT%H:%MZ"),
+            points
+        )
+    }
+
+    /// Intraday auction results for one UTC day, tagged with contract type A07.
+    fn intraday_xml(date: NaiveDate) -> String {
+        day_ahead_xml(date).replace(
+            "<TimeSeries>",
+            "<TimeSeries>\n    <contract_MarketAgreement.type>A07</contract_MarketAgreement.type>",
+        )
+    }
+
+    fn oslo_store() -> Arc<InMemoryPriceStore> {
+        Arc::new(InMemoryPriceStore::with_zones(vec![
+            InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_diff_with_stored_reports_per_zone_changes() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let store = oslo_store();
+        let webhooks = WebhookNotifier::from_config(&crate::config::WebhookConfig {
+            enabled: true,
+            urls: Vec::new(),
+            secret: "s3cret".to_string(),
+            timeout_seconds: 1,
+            max_attempts: 1,
+            initial_backoff_ms: 1,
+        })
+        .unwrap();
+        let fetcher = FetcherService::new(client(), store.clone()).with_webhooks(Arc::new(webhooks));
+
+        let zone = InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo");
+        let prices = parse_price_document(&day_ahead_xml(date), &zone, Market::DayAhead, &Default::default()).unwrap();
+        let diffs = fetcher.diff_with_stored(&prices, Market::DayAhead).await;
+        assert_eq!(diffs.len(), 1);
+        assert_eq!((diffs[0].status, diffs[0].new_count), (DiffStatus::New, 24));
+
+        store.upsert_prices(&prices).await.unwrap();
+        let mut revised = prices.clone();
+        revised[3].price_kwh += Decimal::ONE;
+        let diffs = fetcher.diff_with_stored(&revised, Market::DayAhead).await;
+        assert_eq!(diffs[0].status, DiffStatus::Revised);
+        assert_eq!((diffs[0].revised_count, diffs[0].unchanged_count), (1, 23));
+
+        let diffs = fetcher.diff_with_stored(&prices, Market::Intraday).await;
+        assert_eq!(diffs[0].status, DiffStatus::New);
+    }
+
+    #[tokio::test]
+    async fn test_preview_eic_returns_prices_without_storing() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let eic = "10YDE-EON------1";
+        let source = Arc::new(CannedEntsoeSource::new().with_xml(eic, date, day_ahead_xml(date)));
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source, store.clone());
+
+        let prices = fetcher.preview_eic(eic, date, Market::DayAhead, "Europe/Berlin").await.unwrap();
+        assert_eq!(prices.len(), 24);
+        assert!(prices.iter().all(|p| p.bidding_zone == eic));
+        assert!(store.prices().is_empty());
+        assert!(store.fetch_logs().is_empty());
+
+        let other = NaiveDate::from_ymd_opt(2025, 1, 16).unwrap();
+        assert!(fetcher.preview_eic(eic, other, Market::DayAhead, "Europe/Berlin").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reprocess_documents_attributes_prices_by_domain() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let with_domain = |xml: String, eic: &str| {
+            xml.replace(
+                "<TimeSeries>",
+                &format!("<TimeSeries>\n    <in_Domain.mRID codingScheme=\"A01\">{}</in_Domain.mRID>", eic),
+            )
+        };
+        let no_data = r#"<Acknowledgement_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+  <Reason><code>999</code><text>No matching data found</text></Reason>
+</Acknowledgement_MarketDocument>"#;
+        let documents = vec![
+            ("no1.xml".to_string(), with_domain(day_ahead_xml(date), "EIC-NO1")),
+            ("no2-ida.xml".to_string(), with_domain(intraday_xml(date), "EIC-NO2")),
+            ("de.xml".to_string(), with_domain(day_ahead_xml(date), "10Y1001A1001A82H")),
+            ("anonymous.xml".to_string(), day_ahead_xml(date)),
+            ("empty.xml".to_string(), no_data.to_string()),
+        ];
+        let store = oslo_store();
+        let fetcher = FetcherService::new(client(), store.clone());
+
+        let summary = fetcher.reprocess_documents(&documents).await.unwrap();
+        assert_eq!((summary.succeeded, summary.failed, summary.no_data), (2, 2, 1));
+        assert_eq!(summary.total_prices_stored, 48);
+        assert!(summary.errors[0].starts_with("de.xml: no registered zone"));
+        assert!(summary.errors[1].contains("in_Domain.mRID"));
+
+        let prices = store.prices();
+        assert_eq!(prices.iter().filter(|p| p.bidding_zone == "NO1" && p.market == Market::DayAhead).count(), 24);
+        assert_eq!(prices.iter().filter(|p| p.bidding_zone == "NO2" && p.market == Market::Intraday).count(), 24);
+        assert!(store.fetch_logs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_load_stores_values() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let xml = r#"<GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+  <TimeSeries>
+    <outBiddingZone_Domain.mRID codingScheme="A01">EIC-NO2</outBiddingZone_Domain.mRID>
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T01:00Z</end></timeInterval>
+      <resolution>PT60M</resolution>
+      <Point><position>1</position><quantity>3900</quantity></Point>
+      <Point><position>2</position><quantity>3850</quantity></Point>
+    </Period>
+  </TimeSeries>
+</GL_MarketDocument>"#;
+        let source = Arc::new(CannedEntsoeSource::new().with_load_xml("NO2", date, xml));
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source, store.clone());
+
+        let summary = fetcher.fetch_load_all_zones(date).await.unwrap();
+        assert_eq!((summary.succeeded, summary.no_data), (1, 1));
+        assert_eq!(summary.total_prices_stored, 2);
+
+        let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc() - Duration::days(1);
+        let stored = store.get_load_by_zone("NO2", start, start + Duration::days(2)).await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert!(store.fetch_logs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_flows_covers_both_directions_and_kinds() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let xml = r#"<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:0">
+  <TimeSeries>
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T00:00Z</end></timeInterval>
+      <resolution>PT60M</resolution>
+      <Point><position>1</position><quantity>700</quantity></Point>
+    </Period>
+  </TimeSeries>
+</Publication_MarketDocument>"#;
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_flow_xml("NO1", "NO2", date, FlowKind::Physical, xml)
+                .with_flow_xml("NO2", "NO1", date, FlowKind::Scheduled, xml),
+        );
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source, store.clone()).with_flow_pairs(vec![
+            ZonePair { from: "NO1".to_string(), to: "NO2".to_string() },
+            ZonePair { from: "NO1".to_string(), to: "SE3".to_string() },
+        ]);
+
+        let summary = fetcher.fetch_flows_all_pairs(date).await.unwrap();
+        assert_eq!((summary.succeeded, summary.no_data, summary.failed), (2, 2, 2));
+        assert_eq!(summary.total_prices_stored, 2);
+
+        let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc() - Duration::days(1);
+        let physical = store
+            .get_flows_by_zone("NO2", FlowKind::Physical, start, start + Duration::days(2))
+            .await
+            .unwrap();
+        assert_eq!((physical[0].from_zone.as_str(), physical[0].to_zone.as_str()), ("NO1", "NO2"));
+        let scheduled = store
+            .get_flows_by_zone("NO2", FlowKind::Scheduled, start, start + Duration::days(2))
+            .await
+            .unwrap();
+        assert_eq!((scheduled[0].from_zone.as_str(), scheduled[0].to_zone.as_str()), ("NO2", "NO1"));
+        assert!(store.fetch_logs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_generation_stores_points_without_fetch_log() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let xml = r#"<GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+  <TimeSeries>
+    <inBiddingZone_Domain.mRID codingScheme="A01">EIC-NO1</inBiddingZone_Domain.mRID>
+    <MktPSRType><psrType>B12</psrType></MktPSRType>
+    <Period>
+      <timeInterval><start>2025-01-14T23:00Z</start><end>2025-01-15T01:00Z</end></timeInterval>
+      <resolution>PT60M</resolution>
+      <Point><position>1</position><quantity>1200</quantity></Point>
+      <Point><position>2</position><quantity>1300</quantity></Point>
+    </Period>
+  </TimeSeries>
+</GL_MarketDocument>"#;
+        let source = Arc::new(CannedEntsoeSource::new().with_generation_xml("NO1", date, xml));
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source, store.clone());
+
+        let summary = fetcher.fetch_generation_all_zones(date).await.unwrap();
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.no_data, 1);
+        assert_eq!(summary.total_prices_stored, 2);
+
+        let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc() - Duration::days(1);
+        let stored = store.get_generation_by_zone("NO1", start, start + Duration::days(2)).await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert!(store.fetch_logs().is_empty());
+        assert!(store.prices().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_date_stores_canned_prices_and_counts_outcomes() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let source = Arc::new(CannedEntsoeSource::new().with_xml("NO1", date, day_ahead_xml(date)));
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source.clone(), store.clone());
+
+        let summary = fetcher.fetch_date_all_zones(date).await.unwrap();
+
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.no_data, 1);
+        assert_eq!(summary.total_prices_stored, 24);
+        assert_eq!(source.requests().len(), 2);
+        assert_eq!(store.prices()[0].price_kwh, rust_decimal::Decimal::new(15, 4));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_date_records_errors_per_zone() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml("NO1", date, day_ahead_xml(date))
+                .with_error("NO2", date, || EntsoeError::RateLimited),
+        );
+        let fetcher = FetcherService::new(source, oslo_store());
+
+        let summary = fetcher.fetch_date_all_zones(date).await.unwrap();
+
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.failed, 1);
+        assert!(summary.errors[0].starts_with("NO2"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_date_logs_one_entry_per_zone() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml("NO1", date, day_ahead_xml(date))
+                .with_error("NO2", date, || EntsoeError::RateLimited),
+        );
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source, store.clone());
+
+        fetcher.fetch_date_all_zones(date).await.unwrap();
+
+        let mut logs = store.fetch_logs();
+        logs.sort_by(|a, b| a.bidding_zone.cmp(&b.bidding_zone));
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].bidding_zone.as_deref(), Some("NO1"));
+        assert!(matches!(logs[0].status, FetchStatus::Success));
+        assert_eq!(logs[0].records_inserted, Some(24));
+        assert_eq!(logs[0].period_start, date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+        assert!(logs[0].duration_ms.is_some());
+        assert_eq!(logs[1].bidding_zone.as_deref(), Some("NO2"));
+        assert!(matches!(logs[1].status, FetchStatus::RateLimited));
+        assert_eq!(logs[1].records_inserted, Some(0));
+        assert!(logs[1].error_message.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_zone_logs_under_zone_and_reports_stored_rows() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml("NO1", date, day_ahead_xml(date))
+                .with_error("NO2", date, || EntsoeError::RateLimited),
+        );
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source.clone(), store.clone());
+
+        let no1 = store.get_zone_by_code("NO1").await.unwrap();
+        let result = with_correlation_id("req-1".to_string(), fetcher.fetch_zone(&no1, date)).await.unwrap();
+        assert!(matches!(result.status, FetchStatus::Success));
+        assert_eq!(result.prices_stored, 24);
+        assert_eq!(source.requests().len(), 1);
+
+        let no2 = store.get_zone_by_code("NO2").await.unwrap();
+        let failed = fetcher.fetch_zone(&no2, date).await.unwrap();
+        assert!(matches!(failed.status, FetchStatus::RateLimited));
+        assert!(failed.error.is_some());
+
+        let logs = store.fetch_logs();
+        assert_eq!(logs[0].id, result.fetch_id);
+        assert_eq!(logs[0].bidding_zone.as_deref(), Some("NO1"));
+        assert_eq!(logs[0].records_inserted, Some(24));
+        assert_eq!(logs[0].correlation_id.as_deref(), Some("req-1"));
+        assert!(matches!(logs[1].status, FetchStatus::RateLimited));
+        assert_eq!(logs[1].correlation_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetches_the_zone_valid_on_each_date() {
+        let split = NaiveDate::from_ymd_opt(2018, 10, 1).unwrap();
+        let before = split.pred_opt().unwrap();
+        let mut de_at_lu = InMemoryPriceStore::zone("DE-AT-LU", "DE", "Europe/Berlin");
+        de_at_lu.valid_to = Some(split);
+        de_at_lu.active = false;
+        let mut de_lu = InMemoryPriceStore::zone("DE-LU", "DE", "Europe/Berlin");
+        de_lu.valid_from = Some(split);
+        de_lu.predecessor_code = Some("DE-AT-LU".to_string());
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![de_at_lu, de_lu.clone()]));
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml("DE-AT-LU", before, day_ahead_xml(before))
+                .with_xml("DE-LU", split, day_ahead_xml(split)),
+        );
+        let fetcher = FetcherService::new(source.clone(), store.clone());
+
+        // The inactive predecessor is backfilled for the days before the split
+        fetcher.backfill_missing(before, split, None).await.unwrap();
+        assert_eq!(
+            source.requests(),
+            vec![("DE-AT-LU".to_string(), before), ("DE-LU".to_string(), split)]
+        );
+
+        // A date before the split is fetched for the predecessor
+        let result = fetcher.fetch_zone(&de_lu, before).await.unwrap();
+        assert_eq!(result.zone_code, "DE-AT-LU");
+
+        let mut orphan = de_lu.clone();
+        orphan.predecessor_code = None;
+        assert!(fetcher.fetch_zone(&orphan, before).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refetching_a_day_does_not_count_its_negative_hours_again() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let xml = day_ahead_xml(date)
+            .replace("<price.amount>1.5<", "<price.amount>-1.5<")
+            .replace("<price.amount>2.5<", "<price.amount>-2.5<");
+        let source = Arc::new(CannedEntsoeSource::new().with_xml("NO1", date, xml));
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source, store.clone());
+        let no1 = store.get_zone_by_code("NO1").await.unwrap();
+
+        fetcher.fetch_zone(&no1, date).await.unwrap();
+        fetcher.fetch_zone(&no1, date).await.unwrap();
+
+        let counted: Vec<DebugValue> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, _, _, _)| key.key().name() == metrics::ENTSOE_NEGATIVE_PRICE_HOURS_TOTAL)
+            .map(|(_, _, _, value)| value)
+            .collect();
+        assert_eq!(counted, vec![DebugValue::Counter(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_zone_refused_while_another_run_holds_the_lock() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let source = Arc::new(CannedEntsoeSource::new().with_xml("NO1", date, day_ahead_xml(date)));
+        let store = oslo_store();
+        let clock = Arc::new(FixedClock::new(now));
+        let fetcher = FetcherService::new(source.clone(), store.clone())
+            .with_clock(clock.clone())
+            .with_job_lock_ttl(std::time::Duration::from_secs(600));
+
+        // A scheduled run elsewhere holds today's price fetch
+        assert!(store
+            .try_acquire_job_lock("price_fetch", date, "other", now, now + Duration::minutes(10))
+            .await
+            .unwrap());
+        let no1 = store.get_zone_by_code("NO1").await.unwrap();
+        let err = fetcher.fetch_zone(&no1, date).await.unwrap_err();
+        assert!(err.is::<crate::fetcher::JobLocked>());
+        assert!(source.requests().is_empty());
+
+        // Once that holder's lock expires, the fetch takes it over and releases it
+        clock.advance(Duration::minutes(10));
+        fetcher.fetch_zone(&no1, date).await.unwrap();
+        assert_eq!(source.requests().len(), 1);
+        assert!(store
+            .try_acquire_job_lock("price_fetch", date, "other", clock.now(), clock.now() + Duration::minutes(10))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_retry_queue_backs_off_until_the_zone_is_fetched() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 1, 14, 12, 0, 0).unwrap();
+        let clock = Arc::new(FixedClock::new(now));
+        let config = RetryQueueConfig {
+            enabled: true,
+            cron: "0 */5 * * * *".to_string(),
+            initial_backoff_seconds: 300,
+            max_backoff_seconds: 3600,
+            expiry_hours: 48,
+        };
+        let store = oslo_store();
+        let failing = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml("NO1", date, day_ahead_xml(date))
+                .with_error("NO2", date, || EntsoeError::RateLimited),
+        );
+        let fetcher = FetcherService::new(failing, store.clone())
+            .with_clock(clock.clone())
+            .with_retry_queue(config.clone());
+
+        fetcher.fetch_date_all_zones(date).await.unwrap();
+        assert!(store.get_due_fetch_retries(now).await.unwrap().is_empty());
+
+        // Still failing on the first retry: rescheduled with a doubled backoff
+        clock.advance(Duration::minutes(5));
+        let summary = fetcher.drain_retry_queue().await.unwrap();
+        assert_eq!((summary.succeeded, summary.rescheduled), (0, 1));
+        let retried_at = clock.now();
+        assert!(store.get_due_fetch_retries(retried_at + Duration::minutes(9)).await.unwrap().is_empty());
+        let due = store.get_due_fetch_retries(retried_at + Duration::minutes(10)).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].zone_code, "NO2");
+        assert_eq!(due[0].attempts, 1);
+
+        let recovered = Arc::new(CannedEntsoeSource::new().with_xml("NO2", date, day_ahead_xml(date)));
+        let fetcher = FetcherService::new(recovered.clone(), store.clone())
+            .with_clock(clock.clone())
+            .with_retry_queue(config);
+        clock.advance(Duration::minutes(10));
+        let summary = fetcher.drain_retry_queue().await.unwrap();
+        assert_eq!((summary.succeeded, summary.rescheduled), (1, 0));
+        assert_eq!(recovered.requests(), vec![("NO2".to_string(), date)]);
+        assert!(store.has_day_ahead_data("NO2", date).await.unwrap());
+        assert!(store.get_due_fetch_retries(now + Duration::days(1)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retry_queue_error_leaves_the_entry_due_and_drains_the_rest() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 1, 14, 12, 0, 0).unwrap();
+        let config = RetryQueueConfig {
+            enabled: true,
+            cron: "0 */5 * * * *".to_string(),
+            initial_backoff_seconds: 0,
+            max_backoff_seconds: 3600,
+            expiry_hours: 48,
+        };
+        // NO2 only exists from the day after, with no predecessor to fetch instead
+        let mut no2 = InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo");
+        no2.valid_from = date.succ_opt();
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"), no2]));
+        store
+            .enqueue_fetch_retries(&[
+                queued_retry(&config, "NO2", date, None, now - Duration::minutes(2)),
+                queued_retry(&config, "NO1", date, None, now - Duration::minutes(1)),
+            ])
+            .await
+            .unwrap();
+        let source = Arc::new(CannedEntsoeSource::new().with_xml("NO1", date, day_ahead_xml(date)));
+        let fetcher = FetcherService::new(source, store.clone())
+            .with_clock(Arc::new(FixedClock::new(now)))
+            .with_retry_queue(config);
+
+        let summary = fetcher.drain_retry_queue().await.unwrap();
+        assert_eq!((summary.succeeded, summary.failed), (1, 1));
+        let due = store.get_due_fetch_retries(now).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!((due[0].zone_code.as_str(), due[0].attempts), ("NO2", 0));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_intraday_stores_prices_under_intraday_market() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_market_xml("NO1", date, Market::Intraday, intraday_xml(date))
+                // A day-ahead series in an intraday response is not intraday data
+                .with_market_xml("NO2", date, Market::Intraday, day_ahead_xml(date).replace(
+                    "<TimeSeries>",
+                    "<TimeSeries><contract_MarketAgreement.type>A01</contract_MarketAgreement.type>",
+                )),
+        );
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source, store.clone());
+
+        let summary = fetcher.fetch_market_all_zones(date, Market::Intraday).await.unwrap();
+
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.no_data, 1);
+        assert_eq!(summary.total_prices_stored, 24);
+        assert!(store.prices().iter().all(|p| p.market == Market::Intraday));
+
+        // Day-ahead reads and gap detection ignore intraday rows
+        let day_start = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let day_ahead = store
+            .get_prices_by_zone("NO1", Market::DayAhead, day_start, day_start + Duration::days(1))
+            .await
+            .unwrap();
+        assert!(day_ahead.is_empty());
+        let gaps = store.find_gaps(date, date, &["NO1".to_string()]).await.unwrap();
+        let missing = crate::storage::DayGap {
+            date,
+            zone_code: "NO1".to_string(),
+            hours: 0,
+            expected_hours: 24,
+        };
+        assert_eq!(gaps, vec![missing]);
+    }
+
+    fn hourly_prices(zone: &str, date: NaiveDate) -> Vec<Price> {
+        let start = crate::models::DeliveryDay::new(date, &chrono_tz::Europe::Oslo).start;
+        (0..24)
+            .map(|h| Price::from_mwh(start + Duration::hours(h), zone.to_string(), 42.0, "PT60M".to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_prices_fails_on_empty_zone_registry() {
+        let store = Arc::new(InMemoryPriceStore::new());
+        let fetcher = FetcherService::new(client(), store.clone());
+
+        assert!(fetcher.fetch_all_prices().await.is_err());
+
+        let logs = store.fetch_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(matches!(logs[0].status, FetchStatus::Error));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_tomorrow_skipped_when_data_present() {
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone(
+            "NO1",
+            "NO",
+            "Europe/Oslo",
+        )]));
+        let tomorrow = Utc::now().date_naive().succ_opt().unwrap();
+        store.upsert_prices(&hourly_prices("NO1", tomorrow)).await.unwrap();
+
+        let fetcher = FetcherService::new(client(), store.clone());
+        let summary = fetcher.fetch_tomorrow_if_missing().await.unwrap();
+
+        assert_eq!(summary.succeeded + summary.failed + summary.no_data, 0);
+        assert!(store.fetch_logs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_tomorrow_follows_clock_across_midnight() {
+        // 00:59 in Oslo on the night clocks go forward, still 29 March in UTC
+        let clock = Arc::new(FixedClock::new(Utc.with_ymd_and_hms(2025, 3, 29, 23, 59, 0).unwrap()));
+        let march_30 = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone(
+            "NO1",
+            "NO",
+            "Europe/Oslo",
+        )]));
+        store.upsert_prices(&hourly_prices("NO1", march_30)).await.unwrap();
+        let source = client();
+        let fetcher = FetcherService::new(source.clone(), store.clone()).with_clock(clock.clone());
+
+        fetcher.fetch_tomorrow_if_missing().await.unwrap();
+        assert!(source.requests().is_empty());
+
+        clock.advance(Duration::minutes(2));
+        fetcher.fetch_tomorrow_if_missing().await.unwrap();
+        assert_eq!(source.requests(), vec![("NO1".to_string(), march_30.succ_opt().unwrap())]);
+    }
+
+    #[tokio::test]
+    async fn test_onboard_zone_validates_registers_and_backfills() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let eic = "10Y1001A1001A82H";
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml(eic, date, day_ahead_xml(date))
+                .with_xml("DE_LU", date, day_ahead_xml(date)),
+        );
+        let store = oslo_store();
+        let fetcher = FetcherService::new(source, store.clone());
+        let zone = |zone_code: &str, eic_code: &str| ZoneDefinition {
+            zone_code: zone_code.to_string(),
+            zone_name: "Germany-Luxembourg".to_string(),
+            country_code: "DE".to_string(),
+            country_name: "Germany".to_string(),
+            eic_code: eic_code.to_string(),
+            timezone: "Europe/Berlin".to_string(),
+            valid_from: None,
+            valid_to: None,
+            predecessor_code: None,
+        };
+
+        assert!(fetcher.onboard_zone(&zone("NO1", eic), date, 0).await.is_err());
+        assert!(fetcher.onboard_zone(&zone("DE_LU", "10YDE-EON------1"), date, 0).await.is_err());
+        assert!(store.get_zone_by_code("DE_LU").await.is_err());
+
+        let onboarding = fetcher.onboard_zone(&zone("DE_LU", eic), date, 1).await.unwrap();
+        assert_eq!(onboarding.test_prices, 24);
+        assert_eq!(onboarding.backfill.unwrap().prices_stored, 24);
+        assert_eq!(store.get_zone_by_code("DE_LU").await.unwrap().eic_code, eic);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_reports_no_gaps_for_complete_data() {
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone(
+            "NO1",
+            "NO",
+            "Europe/Oslo",
+        )]));
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        store.upsert_prices(&hourly_prices("NO1", date)).await.unwrap();
+
+        let fetcher = FetcherService::new(client(), store);
+        let summary = fetcher.backfill_missing(date, date, None).await.unwrap();
+
+        assert_eq!(summary.dates_checked, 1);
+        assert!(summary.gaps_found.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_requests_each_run_of_gap_days_once() {
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone(
+            "NO1",
+            "NO",
+            "Europe/Oslo",
+        )]));
+        let dates: Vec<NaiveDate> = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap().iter_days().take(4).collect();
+        store.upsert_prices(&hourly_prices("NO1", dates[2])).await.unwrap();
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml("NO1", dates[0], day_ahead_xml(dates[0]))
+                .with_xml("NO1", dates[1], day_ahead_xml(dates[1]))
+                .with_xml("NO1", dates[3], day_ahead_xml(dates[3])),
+        );
+        let fetcher = FetcherService::new(source.clone(), store);
+
+        let summary = fetcher.backfill_missing(dates[0], dates[3], None).await.unwrap();
+
+        assert_eq!(summary.prices_stored, 72);
+        assert!(summary.errors.is_empty());
+        let requested: Vec<NaiveDate> = source.requests().into_iter().map(|(_, date)| date).collect();
+        assert_eq!(requested, vec![dates[0], dates[1], dates[3]]);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_skips_days_another_fetch_holds() {
+        let dates: Vec<NaiveDate> = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap().iter_days().take(2).collect();
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml("NO1", dates[0], day_ahead_xml(dates[0]))
+                .with_xml("NO1", dates[1], day_ahead_xml(dates[1])),
+        );
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone(
+            "NO1",
+            "NO",
+            "Europe/Oslo",
+        )]));
+        let fetcher = FetcherService::new(source.clone(), store.clone()).with_clock(Arc::new(FixedClock::new(now)));
+
+        // The scheduled fetch holds today's price fetch
+        assert!(store
+            .try_acquire_job_lock("price_fetch", dates[1], "scheduler", now, now + Duration::minutes(10))
+            .await
+            .unwrap());
+        let summary = fetcher.backfill_missing(dates[0], dates[1], None).await.unwrap();
+
+        assert_eq!(summary.prices_stored, 24);
+        assert_eq!(summary.errors, vec![format!("NO1 on {}: price_fetch for {} is already running", dates[1], dates[1])]);
+        let requested: Vec<NaiveDate> = source.requests().into_iter().map(|(_, date)| date).collect();
+        assert_eq!(requested, vec![dates[0]]);
+        // The backfill released the day it held
+        assert!(store
+            .try_acquire_job_lock("price_fetch", dates[0], "scheduler", now, now + Duration::minutes(10))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_job_reports_progress_and_can_be_cancelled() {
+        let dates: Vec<NaiveDate> = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().iter_days().take(10).collect();
+        let source = dates
+            .iter()
+            .fold(CannedEntsoeSource::new(), |source, date| source.with_xml("NO1", *date, day_ahead_xml(*date)));
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone(
+            "NO1",
+            "NO",
+            "Europe/Oslo",
+        )]));
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let clock = Arc::new(FixedClock::new(now));
+        let config = TaskQueueConfig {
+            max_attempts: 2,
+            ..TaskQueueConfig::default()
+        };
+        let fetcher = Arc::new(
+            FetcherService::new(Arc::new(source), store.clone())
+                .with_clock(clock.clone())
+                .with_task_queue(config.clone()),
+        );
+        let worker = crate::tasks::TaskWorker::new(store.clone(), config)
+            .with_clock(clock.clone())
+            .with_handler(Arc::new(crate::fetcher::BackfillJobTask::new(Arc::clone(&fetcher))));
+        let request = NewBackfillJob {
+            start_date: dates[0],
+            end_date: dates[9],
+            zones: None,
+        };
+
+        let job = fetcher.start_backfill_job(request.clone()).await.unwrap();
+        assert_eq!((job.status, job.dates_total, job.dates_done), (BackfillJobStatus::Running, 10, 0));
+        assert_eq!(worker.run_due().await, 1);
+        let job = fetcher.backfill_job(job.id).await.unwrap();
+        assert_eq!(job.status, BackfillJobStatus::Completed);
+        assert_eq!((job.dates_done, job.prices_stored), (10, 240));
+        assert!(job.errors.is_empty());
+        assert_eq!(job.finished_at, Some(now));
+
+        // Cancelled before its task ran
+        let job = fetcher.start_backfill_job(request.clone()).await.unwrap();
+        assert!(fetcher.cancel_backfill_job(job.id).await.unwrap());
+        assert_eq!(worker.run_due().await, 1);
+        let job = fetcher.backfill_job(job.id).await.unwrap();
+        assert_eq!((job.status, job.dates_done), (BackfillJobStatus::Cancelled, 0));
+        assert!(!fetcher.cancel_backfill_job(job.id).await.unwrap());
+
+        // Retried with backoff, then failed once its attempts run out
+        let job = fetcher
+            .start_backfill_job(NewBackfillJob {
+                zones: Some(vec!["XX".to_string()]),
+                ..request
+            })
+            .await
+            .unwrap();
+        assert_eq!(worker.run_due().await, 1);
+        assert_eq!(fetcher.backfill_job(job.id).await.unwrap().status, BackfillJobStatus::Running);
+        clock.advance(Duration::minutes(1));
+        assert_eq!(worker.run_due().await, 1);
+        let job = fetcher.backfill_job(job.id).await.unwrap();
+        assert_eq!(job.status, BackfillJobStatus::Failed);
+        assert_eq!(job.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gap_repair_task_retries_only_the_days_still_missing() {
+        let dates: Vec<NaiveDate> = NaiveDate::from_ymd_opt(2025, 1, 12).unwrap().iter_days().take(3).collect();
+        let source = Arc::new(
+            CannedEntsoeSource::new()
+                .with_xml("NO1", dates[0], day_ahead_xml(dates[0]))
+                .with_error("NO1", dates[2], || EntsoeError::RateLimited),
+        );
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone(
+            "NO1",
+            "NO",
+            "Europe/Oslo",
+        )]));
+        store.upsert_prices(&hourly_prices("NO1", dates[1])).await.unwrap();
+        let clock = Arc::new(FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap()));
+        let config = TaskQueueConfig {
+            max_attempts: 2,
+            ..TaskQueueConfig::default()
+        };
+        let fetcher = Arc::new(
+            FetcherService::new(source.clone(), store.clone())
+                .with_clock(clock.clone())
+                .with_task_queue(config.clone()),
+        );
+        let worker = crate::tasks::TaskWorker::new(store.clone(), config)
+            .with_clock(clock.clone())
+            .with_handler(Arc::new(crate::fetcher::GapRepairTask::new(Arc::clone(&fetcher))));
+
+        let task = fetcher.enqueue_gap_repair(dates[0], dates[2]).await.unwrap().unwrap();
+        assert!(fetcher.enqueue_gap_repair(dates[0], dates[2]).await.unwrap().is_none());
+
+        assert_eq!(worker.run_due().await, 1);
+        let retried = store.get_task(task.id).await.unwrap();
+        assert_eq!(retried.status, crate::models::TaskStatus::Queued);
+        assert!(retried.last_error.unwrap().starts_with("1 zone/dates not repaired"));
+        let start = crate::models::DeliveryDay::new(dates[0], &chrono_tz::Europe::Oslo).start;
+        let stored = store
+            .get_prices_by_zone("NO1", Market::DayAhead, start, start + Duration::days(3))
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 48);
+
+        clock.advance(Duration::minutes(1));
+        assert_eq!(worker.run_due().await, 1);
+        assert_eq!(store.get_task(task.id).await.unwrap().status, crate::models::TaskStatus::Failed);
+        let requested: Vec<NaiveDate> = source.requests().into_iter().map(|(_, date)| date).collect();
+        assert_eq!(requested, vec![dates[0], dates[2], dates[2]]);
+    }
+
+    #[test]
+    fn test_consecutive_runs_groups_adjacent_dates() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        assert_eq!(
+            consecutive_runs(vec![day(20), day(14), day(15), day(17), day(16)]),
+            vec![(day(14), day(17)), (day(20), day(20))]
+        );
+        assert!(consecutive_runs(Vec::new()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_rejects_unknown_zone_filter() {
+        let store = Arc::new(InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone(
+            "NO1",
+            "NO",
+            "Europe/Oslo",
+        )]));
+        let fetcher = FetcherService::new(client(), store);
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let result = fetcher
+            .backfill_missing(date, date, Some(vec!["XX".to_string()]))
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::entsoe::EntsoePriceSource;
+use crate::models::Task;
+use crate::tasks::{self, TaskHandler};
+
+use super::{with_correlation_id, FetcherService};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct BackfillJobPayload {
+    pub job_id: i64,
+    /// Of the request that started the job, for the fetch log entries it opens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct GapRepairPayload {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// Runs admin backfill jobs, resuming from their saved progress on retries.
+pub struct BackfillJobTask<S> {
+    fetcher: Arc<FetcherService<S>>,
+}
+
+impl<S> BackfillJobTask<S> {
+    pub fn new(fetcher: Arc<FetcherService<S>>) -> Self {
+        Self { fetcher }
+    }
+}
+
+#[async_trait]
+impl<S: EntsoePriceSource + 'static> TaskHandler for BackfillJobTask<S> {
+    fn kind(&self) -> &'static str {
+        tasks::BACKFILL_JOB
+    }
+
+    async fn run(&self, task: &Task) -> anyhow::Result<()> {
+        let payload: BackfillJobPayload = serde_json::from_str(&task.payload)?;
+        let run = self.fetcher.run_backfill_job(payload.job_id);
+        match payload.correlation_id {
+            Some(correlation_id) => with_correlation_id(correlation_id, run).await,
+            None => run.await,
+        }
+    }
+
+    async fn failed(&self, task: &Task, error: &str) {
+        let Ok(payload) = serde_json::from_str::<BackfillJobPayload>(&task.payload) else {
+            return;
+        };
+        if let Err(e) = self.fetcher.fail_backfill_job(payload.job_id, error).await {
+            error!(job_id = payload.job_id, error = %e, "Failed to mark backfill job failed");
+        }
+    }
+}
+
+/// Fetches the days missing in every active zone after a missed primary
+/// fetch. Zones that still failed retry the whole repair, which only
+/// fetches what is still missing.
+pub struct GapRepairTask<S> {
+    fetcher: Arc<FetcherService<S>>,
+}
+
+impl<S> GapRepairTask<S> {
+    pub fn new(fetcher: Arc<FetcherService<S>>) -> Self {
+        Self { fetcher }
+    }
+}
+
+#[async_trait]
+impl<S: EntsoePriceSource + 'static> TaskHandler for GapRepairTask<S> {
+    fn kind(&self) -> &'static str {
+        tasks::GAP_REPAIR
+    }
+
+    async fn run(&self, task: &Task) -> anyhow::Result<()> {
+        let payload: GapRepairPayload = serde_json::from_str(&task.payload)?;
+        let summary = self.fetcher.backfill_missing(payload.start, payload.end, None).await?;
+        info!(
+            start = %payload.start,
+            end = %payload.end,
+            prices_stored = summary.prices_stored,
+            errors = summary.errors.len(),
+            "Gap repair completed"
+        );
+        match summary.errors.first() {
+            Some(first) => anyhow::bail!("{} zone/dates not repaired, first: {}", summary.errors.len(), first),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+
+/// Business-day calendar a rate source publishes reference rates on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicationCalendar {
+    /// ECB euro foreign exchange reference rates (TARGET2 closing days).
+    Target,
+    /// Norges Bank exchange rates (Norwegian bank holidays).
+    Norway,
+    /// Riksbank fixing rates (Swedish bank holidays).
+    Sweden,
+}
+
+impl PublicationCalendar {
+    /// Local time the daily rates are normally available.
+    pub fn publication_time(&self) -> (Tz, NaiveTime) {
+        match self {
+            Self::Target => (chrono_tz::Europe::Berlin, NaiveTime::from_hms_opt(16, 0, 0).unwrap()),
+            Self::Norway => (chrono_tz::Europe::Oslo, NaiveTime::from_hms_opt(16, 0, 0).unwrap()),
+            Self::Sweden => (chrono_tz::Europe::Stockholm, NaiveTime::from_hms_opt(16, 15, 0).unwrap()),
+        }
+    }
+
+    pub fn is_publication_day(&self, date: NaiveDate) -> bool {
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        !self.is_holiday(date)
+    }
+
+    /// Most recent publication day on or before `date`.
+    pub fn latest_publication_on_or_before(&self, date: NaiveDate) -> NaiveDate {
+        let mut current = date;
+        while !self.is_publication_day(current) {
+            current = current.pred_opt().unwrap();
+        }
+        current
+    }
+
+    /// Date of the most recent rates already published at instant `now`.
+    pub fn latest_available(&self, now: DateTime<Utc>) -> NaiveDate {
+        let (tz, publish_at) = self.publication_time();
+        let local = now.with_timezone(&tz);
+        let today = local.date_naive();
+
+        if self.is_publication_day(today) && local.time() >= publish_at {
+            today
+        } else {
+            self.latest_publication_on_or_before(today.pred_opt().unwrap())
+        }
+    }
+
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        let year = date.year();
+        let easter = easter_sunday(year);
+        let (month, day) = (date.month(), date.day());
+
+        match self {
+            Self::Target => {
+                matches!((month, day), (1, 1) | (5, 1) | (12, 25) | (12, 26))
+                    || date == easter - Duration::days(2)
+                    || date == easter + Duration::days(1)
+            }
+            Self::Norway => {
+                matches!((month, day), (1, 1) | (5, 1) | (5, 17) | (12, 24) | (12, 25) | (12, 26) | (12, 31))
+                    || date == easter - Duration::days(3)
+                    || date == easter - Duration::days(2)
+                    || date == easter + Duration::days(1)
+                    || date == easter + Duration::days(39)
+                    || date == easter + Duration::days(50)
+            }
+            Self::Sweden => {
+                matches!((month, day), (1, 1) | (1, 6) | (5, 1) | (6, 6) | (12, 24) | (12, 25) | (12, 26) | (12, 31))
+                    || date == easter - Duration::days(2)
+                    || date == easter + Duration::days(1)
+                    || date == easter + Duration::days(39)
+                    || is_midsummer_eve(date)
+            }
+        }
+    }
+}
+
+/// Midsummer Eve is the Friday between 19 and 25 June.
+fn is_midsummer_eve(date: NaiveDate) -> bool {
+    date.month() == 6 && (19..=25).contains(&date.day()) && date.weekday() == Weekday::Fri
+}
+
+/// Gregorian Easter Sunday (anonymous Gregorian algorithm).
+fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_easter_sunday() {
+        assert_eq!(easter_sunday(2024), date(2024, 3, 31));
+        assert_eq!(easter_sunday(2025), date(2025, 4, 20));
+        assert_eq!(easter_sunday(2026), date(2026, 4, 5));
+    }
+
+    #[test]
+    fn test_target_skips_good_friday_to_previous_thursday() {
+        let good_friday = date(2025, 4, 18);
+        assert!(!PublicationCalendar::Target.is_publication_day(good_friday));
+        assert_eq!(
+            PublicationCalendar::Target.latest_publication_on_or_before(good_friday),
+            date(2025, 4, 17)
+        );
+    }
+
+    #[test]
+    fn test_norway_skips_maundy_thursday_and_constitution_day() {
+        assert!(!PublicationCalendar::Norway.is_publication_day(date(2025, 4, 17)));
+        assert!(!PublicationCalendar::Norway.is_publication_day(date(2024, 5, 17)));
+        assert!(PublicationCalendar::Target.is_publication_day(date(2024, 5, 17)));
+    }
+
+    #[test]
+    fn test_sweden_skips_midsummer_eve_and_national_day() {
+        assert!(!PublicationCalendar::Sweden.is_publication_day(date(2025, 6, 20)));
+        assert!(!PublicationCalendar::Sweden.is_publication_day(date(2025, 6, 6)));
+        assert!(PublicationCalendar::Norway.is_publication_day(date(2025, 6, 20)));
+    }
+
+    #[test]
+    fn test_weekend_rolls_back_to_friday() {
+        assert_eq!(
+            PublicationCalendar::Target.latest_publication_on_or_before(date(2025, 1, 12)),
+            date(2025, 1, 10)
+        );
+    }
+
+    #[test]
+    fn test_latest_available_before_and_after_publication_time() {
+        // 2025-01-15 is a Wednesday; 14:00 UTC = 15:00 CET, before the 16:00 release
+        let before = Utc.with_ymd_and_hms(2025, 1, 15, 14, 0, 0).unwrap();
+        assert_eq!(PublicationCalendar::Target.latest_available(before), date(2025, 1, 14));
+
+        let after = Utc.with_ymd_and_hms(2025, 1, 15, 15, 30, 0).unwrap();
+        assert_eq!(PublicationCalendar::Target.latest_available(after), date(2025, 1, 15));
+    }
+}
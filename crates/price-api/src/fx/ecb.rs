@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use reqwest::Client;
+use tracing::debug;
+
+use crate::config::FxSourceKind;
+
+use super::calendar::PublicationCalendar;
+use super::error::FxError;
+use super::{latest_observation, sdmx, FxRate, RateSource};
+
+const ECB_BASE_URL: &str = "https://data-api.ecb.europa.eu/service/data/EXR";
+
+/// ECB euro foreign exchange reference rates. Covers every currency in the
+/// ECB reference basket and publishes on TARGET business days.
+pub struct EcbRateSource {
+    client: Client,
+    base_url: String,
+}
+
+impl EcbRateSource {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            base_url: ECB_BASE_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, currency: &str, start: NaiveDate, end: NaiveDate) -> String {
+        format!(
+            "{}/D.{}.EUR.SP00.A?startPeriod={}&endPeriod={}&format=csvdata",
+            self.base_url, currency, start, end
+        )
+    }
+}
+
+#[async_trait]
+impl RateSource for EcbRateSource {
+    fn kind(&self) -> FxSourceKind {
+        FxSourceKind::Ecb
+    }
+
+    fn calendar(&self) -> PublicationCalendar {
+        PublicationCalendar::Target
+    }
+
+    fn supports(&self, currency: &str) -> bool {
+        currency.len() == 3 && currency != "EUR"
+    }
+
+    async fn fetch_rate(&self, currency: &str, date: NaiveDate) -> Result<FxRate, FxError> {
+        let end = self.calendar().latest_publication_on_or_before(date);
+        let start = end - Duration::days(10);
+        let url = self.build_url(currency, start, end);
+        debug!(url = %url, "Fetching ECB reference rate");
+
+        let response = self.client.get(&url).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(FxError::UnexpectedStatus {
+                status: status.as_u16(),
+                body,
+            });
+        }
+
+        let observations = sdmx::parse_observations(&body)?;
+        latest_observation(observations, currency, date, self.kind())
+    }
+}
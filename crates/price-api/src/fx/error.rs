@@ -0,0 +1,32 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FxError {
+    #[error("No exchange rate published for {currency} on or before {date}")]
+    NoRate { currency: String, date: String },
+
+    #[error("Currency {currency} is not supported by rate source {source_name}")]
+    UnsupportedCurrency {
+        currency: String,
+        source_name: String,
+    },
+
+    #[error("HTTP request failed: {0}")]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("Rate source returned HTTP {status}: {body}")]
+    UnexpectedStatus { status: u16, body: String },
+
+    #[error("Failed to parse rate source response: {0}")]
+    ParseError(String),
+}
+
+impl FxError {
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::HttpError(_) => true,
+            Self::UnexpectedStatus { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
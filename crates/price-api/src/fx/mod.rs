@@ -0,0 +1,200 @@
+mod calendar;
+mod ecb;
+mod error;
+mod norges_bank;
+mod riksbank;
+mod sdmx;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::config::{FxConfig, FxSourceKind};
+
+pub use calendar::PublicationCalendar;
+pub use ecb::EcbRateSource;
+pub use error::FxError;
+pub use norges_bank::NorgesBankRateSource;
+pub use riksbank::RiksbankRateSource;
+
+/// A EUR-based exchange rate as published by a rate source.
+#[derive(Debug, Clone, Serialize)]
+pub struct FxRate {
+    pub currency: String,
+    /// Units of `currency` per 1 EUR.
+    pub rate: Decimal,
+    /// Publication date of the rate (may precede the requested date on holidays).
+    pub rate_date: NaiveDate,
+    pub source: FxSourceKind,
+}
+
+#[async_trait]
+pub trait RateSource: Send + Sync {
+    fn kind(&self) -> FxSourceKind;
+
+    fn calendar(&self) -> PublicationCalendar;
+
+    fn supports(&self, currency: &str) -> bool;
+
+    /// Fetch the rate in effect on `date`, i.e. the most recent publication on
+    /// or before it according to the source's calendar.
+    async fn fetch_rate(&self, currency: &str, date: NaiveDate) -> Result<FxRate, FxError>;
+}
+
+/// Pick the most recent observation on or before `date`.
+fn latest_observation(
+    observations: Vec<(NaiveDate, Decimal)>,
+    currency: &str,
+    date: NaiveDate,
+    source: FxSourceKind,
+) -> Result<FxRate, FxError> {
+    observations
+        .into_iter()
+        .filter(|(obs_date, _)| *obs_date <= date)
+        .max_by_key(|(obs_date, _)| *obs_date)
+        .map(|(rate_date, rate)| FxRate {
+            currency: currency.to_string(),
+            rate,
+            rate_date,
+            source,
+        })
+        .ok_or_else(|| FxError::NoRate {
+            currency: currency.to_string(),
+            date: date.to_string(),
+        })
+}
+
+/// Routes currency lookups to the rate source configured for that currency.
+pub struct FxRateService {
+    default_source: Arc<dyn RateSource>,
+    sources: HashMap<String, Arc<dyn RateSource>>,
+}
+
+impl FxRateService {
+    pub fn from_config(config: &FxConfig) -> Result<Self, FxError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()?;
+
+        let build = |kind: FxSourceKind| -> Arc<dyn RateSource> {
+            match kind {
+                FxSourceKind::Ecb => Arc::new(EcbRateSource::new(client.clone())),
+                FxSourceKind::NorgesBank => Arc::new(NorgesBankRateSource::new(client.clone())),
+                FxSourceKind::Riksbank => Arc::new(RiksbankRateSource::new(client.clone())),
+            }
+        };
+
+        let default_source = build(config.default_source);
+        let mut sources = HashMap::new();
+        for (currency, kind) in &config.sources {
+            // config-rs lowercases keys, so normalise back to ISO 4217 form
+            let currency = currency.to_uppercase();
+            let source = build(*kind);
+            if !source.supports(&currency) {
+                return Err(FxError::UnsupportedCurrency {
+                    currency,
+                    source_name: format!("{:?}", kind),
+                });
+            }
+            sources.insert(currency, source);
+        }
+
+        Ok(Self::new(default_source, sources))
+    }
+
+    pub fn new(
+        default_source: Arc<dyn RateSource>,
+        sources: HashMap<String, Arc<dyn RateSource>>,
+    ) -> Self {
+        Self {
+            default_source,
+            sources,
+        }
+    }
+
+    pub fn source_for(&self, currency: &str) -> &Arc<dyn RateSource> {
+        self.sources.get(currency).unwrap_or(&self.default_source)
+    }
+
+    pub async fn rate(&self, currency: &str, date: NaiveDate) -> Result<FxRate, FxError> {
+        let currency = currency.to_uppercase();
+        let source = self.source_for(&currency);
+
+        if currency == "EUR" {
+            return Ok(FxRate {
+                currency,
+                rate: Decimal::ONE,
+                rate_date: date,
+                source: source.kind(),
+            });
+        }
+
+        source.fetch_rate(&currency, date).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_latest_observation_uses_previous_publication() {
+        let observations = vec![
+            (date(2025, 4, 16), Decimal::from_str("11.60").unwrap()),
+            (date(2025, 4, 17), Decimal::from_str("11.62").unwrap()),
+            (date(2025, 4, 22), Decimal::from_str("11.70").unwrap()),
+        ];
+        let rate = latest_observation(observations, "NOK", date(2025, 4, 21), FxSourceKind::NorgesBank).unwrap();
+        assert_eq!(rate.rate_date, date(2025, 4, 17));
+        assert_eq!(rate.rate, Decimal::from_str("11.62").unwrap());
+    }
+
+    #[test]
+    fn test_latest_observation_none_before_date() {
+        let observations = vec![(date(2025, 4, 22), Decimal::ONE)];
+        let result = latest_observation(observations, "SEK", date(2025, 4, 21), FxSourceKind::Riksbank);
+        assert!(matches!(result, Err(FxError::NoRate { .. })));
+    }
+
+    #[test]
+    fn test_from_config_routes_per_currency() {
+        let config = FxConfig {
+            enabled: true,
+            timeout_seconds: 10,
+            default_source: FxSourceKind::Ecb,
+            sources: HashMap::from([
+                ("nok".to_string(), FxSourceKind::NorgesBank),
+                ("SEK".to_string(), FxSourceKind::Riksbank),
+            ]),
+        };
+        let service = FxRateService::from_config(&config).unwrap();
+        assert_eq!(service.source_for("NOK").kind(), FxSourceKind::NorgesBank);
+        assert_eq!(service.source_for("SEK").kind(), FxSourceKind::Riksbank);
+        assert_eq!(service.source_for("DKK").kind(), FxSourceKind::Ecb);
+    }
+
+    #[test]
+    fn test_from_config_rejects_unsupported_mapping() {
+        let config = FxConfig {
+            enabled: true,
+            timeout_seconds: 10,
+            default_source: FxSourceKind::Ecb,
+            sources: HashMap::from([("DKK".to_string(), FxSourceKind::NorgesBank)]),
+        };
+        assert!(matches!(
+            FxRateService::from_config(&config),
+            Err(FxError::UnsupportedCurrency { .. })
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use reqwest::Client;
+use tracing::debug;
+
+use crate::config::FxSourceKind;
+
+use super::calendar::PublicationCalendar;
+use super::error::FxError;
+use super::{latest_observation, sdmx, FxRate, RateSource};
+
+const NORGES_BANK_BASE_URL: &str = "https://data.norges-bank.no/api/data/EXR";
+
+/// Norges Bank daily exchange rates. Only quotes NOK, but is the reference
+/// required for Norwegian billing.
+pub struct NorgesBankRateSource {
+    client: Client,
+    base_url: String,
+}
+
+impl NorgesBankRateSource {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            base_url: NORGES_BANK_BASE_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, start: NaiveDate, end: NaiveDate) -> String {
+        format!(
+            "{}/B.EUR.NOK.SP?startPeriod={}&endPeriod={}&format=csv&locale=en",
+            self.base_url, start, end
+        )
+    }
+}
+
+#[async_trait]
+impl RateSource for NorgesBankRateSource {
+    fn kind(&self) -> FxSourceKind {
+        FxSourceKind::NorgesBank
+    }
+
+    fn calendar(&self) -> PublicationCalendar {
+        PublicationCalendar::Norway
+    }
+
+    fn supports(&self, currency: &str) -> bool {
+        currency == "NOK"
+    }
+
+    async fn fetch_rate(&self, currency: &str, date: NaiveDate) -> Result<FxRate, FxError> {
+        if !self.supports(currency) {
+            return Err(FxError::UnsupportedCurrency {
+                currency: currency.to_string(),
+                source_name: "norges_bank".to_string(),
+            });
+        }
+
+        let end = self.calendar().latest_publication_on_or_before(date);
+        let start = end - Duration::days(10);
+        let url = self.build_url(start, end);
+        debug!(url = %url, "Fetching Norges Bank exchange rate");
+
+        let response = self.client.get(&url).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(FxError::UnexpectedStatus {
+                status: status.as_u16(),
+                body,
+            });
+        }
+
+        let observations = sdmx::parse_observations(&body)?;
+        latest_observation(observations, currency, date, self.kind())
+    }
+}
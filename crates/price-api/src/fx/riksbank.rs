@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::config::FxSourceKind;
+
+use super::calendar::PublicationCalendar;
+use super::error::FxError;
+use super::{latest_observation, FxRate, RateSource};
+
+const RIKSBANK_BASE_URL: &str = "https://api.riksbank.se/swea/v1/Observations";
+
+/// Riksbank SWEA fixing rates. Only quotes SEK.
+pub struct RiksbankRateSource {
+    client: Client,
+    base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Observation {
+    date: NaiveDate,
+    value: Decimal,
+}
+
+impl RiksbankRateSource {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            base_url: RIKSBANK_BASE_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, start: NaiveDate, end: NaiveDate) -> String {
+        format!("{}/SEKEURPMI/{}/{}", self.base_url, start, end)
+    }
+}
+
+#[async_trait]
+impl RateSource for RiksbankRateSource {
+    fn kind(&self) -> FxSourceKind {
+        FxSourceKind::Riksbank
+    }
+
+    fn calendar(&self) -> PublicationCalendar {
+        PublicationCalendar::Sweden
+    }
+
+    fn supports(&self, currency: &str) -> bool {
+        currency == "SEK"
+    }
+
+    async fn fetch_rate(&self, currency: &str, date: NaiveDate) -> Result<FxRate, FxError> {
+        if !self.supports(currency) {
+            return Err(FxError::UnsupportedCurrency {
+                currency: currency.to_string(),
+                source_name: "riksbank".to_string(),
+            });
+        }
+
+        let end = self.calendar().latest_publication_on_or_before(date);
+        let start = end - Duration::days(10);
+        let url = self.build_url(start, end);
+        debug!(url = %url, "Fetching Riksbank exchange rate");
+
+        let response = self.client.get(&url).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(FxError::UnexpectedStatus {
+                status: status.as_u16(),
+                body,
+            });
+        }
+
+        let observations: Vec<Observation> = serde_json::from_str(&body)
+            .map_err(|e| FxError::ParseError(e.to_string()))?;
+        let observations = observations.into_iter().map(|o| (o.date, o.value)).collect();
+        latest_observation(observations, currency, date, self.kind())
+    }
+}
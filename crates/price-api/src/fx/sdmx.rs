@@ -0,0 +1,86 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use super::error::FxError;
+
+/// Parse an SDMX CSV export (as served by the ECB and Norges Bank data APIs)
+/// into `(date, value)` observations. Values are scaled down by `UNIT_MULT`
+/// when that column is present.
+pub fn parse_observations(body: &str) -> Result<Vec<(NaiveDate, Decimal)>, FxError> {
+    let mut lines = body.lines().filter(|l| !l.trim().is_empty());
+    let header = lines
+        .next()
+        .ok_or_else(|| FxError::ParseError("Empty CSV response".to_string()))?;
+    let delimiter = if header.contains(';') { ';' } else { ',' };
+
+    let columns: Vec<&str> = header
+        .trim_start_matches('\u{feff}')
+        .split(delimiter)
+        .map(|c| c.trim().trim_matches('"'))
+        .collect();
+    let position = |name: &str| columns.iter().position(|c| *c == name);
+
+    let date_idx = position("TIME_PERIOD")
+        .ok_or_else(|| FxError::ParseError("Missing TIME_PERIOD column".to_string()))?;
+    let value_idx = position("OBS_VALUE")
+        .ok_or_else(|| FxError::ParseError("Missing OBS_VALUE column".to_string()))?;
+    let unit_mult_idx = position("UNIT_MULT");
+
+    let mut observations = Vec::new();
+    for line in lines {
+        let fields: Vec<&str> = line.split(delimiter).map(|f| f.trim().trim_matches('"')).collect();
+        let (Some(date_str), Some(value_str)) = (fields.get(date_idx), fields.get(value_idx)) else {
+            continue;
+        };
+        if value_str.is_empty() || *value_str == "NaN" {
+            continue;
+        }
+
+        let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+            .map_err(|e| FxError::ParseError(format!("Invalid date {}: {}", date_str, e)))?;
+        let mut value = Decimal::from_str(value_str)
+            .map_err(|e| FxError::ParseError(format!("Invalid value {}: {}", value_str, e)))?;
+
+        if let Some(exp) = unit_mult_idx
+            .and_then(|idx| fields.get(idx))
+            .and_then(|m| m.parse::<u32>().ok())
+        {
+            value /= Decimal::from(10u64.pow(exp));
+        }
+
+        observations.push((date, value));
+    }
+
+    observations.sort_by_key(|(date, _)| *date);
+    Ok(observations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ecb_csv() {
+        let body = "KEY,FREQ,CURRENCY,CURRENCY_DENOM,EXR_TYPE,EXR_SUFFIX,TIME_PERIOD,OBS_VALUE\n\
+                    EXR.D.SEK.EUR.SP00.A,D,SEK,EUR,SP00,A,2025-01-14,11.4935\n\
+                    EXR.D.SEK.EUR.SP00.A,D,SEK,EUR,SP00,A,2025-01-13,11.5100\n";
+        let obs = parse_observations(body).unwrap();
+        assert_eq!(obs.len(), 2);
+        assert_eq!(obs[1].0, NaiveDate::from_ymd_opt(2025, 1, 14).unwrap());
+        assert_eq!(obs[1].1, Decimal::from_str("11.4935").unwrap());
+    }
+
+    #[test]
+    fn test_parse_norges_bank_csv_with_unit_mult() {
+        let body = "FREQ;Frequency;BASE_CUR;QUOTE_CUR;TENOR;DECIMALS;UNIT_MULT;TIME_PERIOD;OBS_VALUE\n\
+                    B;Business;SEK;NOK;SP;4;2;2025-01-14;101.25\n";
+        let obs = parse_observations(body).unwrap();
+        assert_eq!(obs[0].1, Decimal::from_str("1.0125").unwrap());
+    }
+
+    #[test]
+    fn test_parse_missing_columns() {
+        assert!(parse_observations("A,B\n1,2\n").is_err());
+    }
+}
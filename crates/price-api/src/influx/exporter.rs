@@ -0,0 +1,181 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Client;
+use tracing::{debug, warn};
+
+use crate::config::InfluxConfig;
+use crate::metrics;
+use crate::models::Price;
+
+use super::to_line_protocol;
+
+/// Points sent per write request, the batch size InfluxDB recommends.
+const POINTS_PER_WRITE: usize = 5000;
+
+/// Writes stored prices to an InfluxDB v2 bucket. A failed write is logged
+/// and dropped, not retried.
+pub struct InfluxExporter {
+    client: Client,
+    write_url: String,
+    org: String,
+    bucket: String,
+    token: String,
+}
+
+impl InfluxExporter {
+    pub fn from_config(config: &InfluxConfig) -> Result<Self, String> {
+        let required = [("url", &config.url), ("org", &config.org), ("bucket", &config.bucket), ("token", &config.token)];
+        let missing: Vec<String> = required
+            .iter()
+            .filter(|(_, value)| value.trim().is_empty())
+            .map(|(name, _)| format!("influx.{}", name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("{} must be set when influx is enabled", missing.join(", ")));
+        }
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .map_err(|e| format!("Failed to build InfluxDB client: {}", e))?;
+
+        Ok(Self {
+            client,
+            write_url: format!("{}/api/v2/write", config.url.trim_end_matches('/')),
+            org: config.org.clone(),
+            bucket: config.bucket.clone(),
+            token: config.token.clone(),
+        })
+    }
+
+    /// Write `prices` in the background so fetches are never held up by a
+    /// slow InfluxDB.
+    pub fn push(self: &Arc<Self>, prices: Vec<Price>) {
+        if prices.is_empty() {
+            return;
+        }
+        let exporter = Arc::clone(self);
+        tokio::spawn(async move {
+            exporter.write(&prices).await;
+        });
+    }
+
+    /// Write `prices` in batches, returning how many points were accepted.
+    pub async fn write(&self, prices: &[Price]) -> usize {
+        let mut written = 0;
+        for batch in prices.chunks(POINTS_PER_WRITE) {
+            let ok = self.write_batch(batch).await;
+            metrics::record_influx_points(ok, batch.len());
+            if ok {
+                written += batch.len();
+            }
+        }
+        written
+    }
+
+    async fn write_batch(&self, batch: &[Price]) -> bool {
+        let result = self
+            .client
+            .post(&self.write_url)
+            .query(&[("org", self.org.as_str()), ("bucket", self.bucket.as_str()), ("precision", "ns")])
+            .header("Authorization", format!("Token {}", self.token))
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .body(to_line_protocol(batch))
+            .send()
+            .await;
+
+        match result {
+            Ok(response) if response.status().is_success() => {
+                debug!(points = batch.len(), "Prices written to InfluxDB");
+                true
+            }
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                warn!(points = batch.len(), status = %status, body = %body, "InfluxDB rejected price write");
+                false
+            }
+            Err(e) => {
+                warn!(points = batch.len(), error = %e, "InfluxDB price write failed");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{extract::Query, extract::State, http::HeaderMap, http::StatusCode, routing::post, Router};
+    use chrono::{TimeZone, Utc};
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    type Writes = Arc<Mutex<Vec<(HashMap<String, String>, HeaderMap, String)>>>;
+
+    async fn write_handler(
+        State(writes): State<Writes>,
+        Query(query): Query<HashMap<String, String>>,
+        headers: HeaderMap,
+        body: String,
+    ) -> StatusCode {
+        writes.lock().await.push((query, headers, body));
+        StatusCode::NO_CONTENT
+    }
+
+    fn config(url: String) -> InfluxConfig {
+        InfluxConfig {
+            enabled: true,
+            url,
+            org: "home".to_string(),
+            bucket: "prices".to_string(),
+            token: "t0ken".to_string(),
+            timeout_seconds: 5,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_posts_line_protocol_to_the_bucket() {
+        let writes = Writes::default();
+        let app = Router::new().route("/api/v2/write", post(write_handler)).with_state(writes.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let prices: Vec<Price> = (0..3)
+            .map(|hour| Price::from_mwh(start + chrono::Duration::hours(hour), "NO1".to_string(), 40.0, "PT60M".to_string()))
+            .collect();
+        let exporter = InfluxExporter::from_config(&config(format!("http://{}/", addr))).unwrap();
+        assert_eq!(exporter.write(&prices).await, 3);
+
+        let writes = writes.lock().await;
+        let (query, headers, body) = &writes[0];
+        assert_eq!(query["org"], "home");
+        assert_eq!(query["bucket"], "prices");
+        assert_eq!(query["precision"], "ns");
+        assert_eq!(headers["authorization"], "Token t0ken");
+        assert_eq!(body.lines().count(), 3);
+        assert!(body.starts_with("electricity_price,zone=NO1,"), "{}", body);
+    }
+
+    #[test]
+    fn test_from_config_requires_every_connection_setting() {
+        let config = InfluxConfig {
+            org: String::new(),
+            token: " ".to_string(),
+            ..config("http://influxdb:8086".to_string())
+        };
+        let error = InfluxExporter::from_config(&config).err().unwrap();
+        assert_eq!(error, "influx.org, influx.token must be set when influx is enabled");
+    }
+
+    #[tokio::test]
+    async fn test_write_reports_nothing_written_when_unreachable() {
+        let exporter = InfluxExporter::from_config(&config("http://127.0.0.1:9".to_string())).unwrap();
+        let hour = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        assert_eq!(exporter.write(&[Price::from_mwh(hour, "NO1".to_string(), 40.0, "PT60M".to_string())]).await, 0);
+    }
+}
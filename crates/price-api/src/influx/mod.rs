@@ -0,0 +1,87 @@
+//! InfluxDB line protocol for stored prices, pushed to a bucket by
+//! [`InfluxExporter`] and served by the zone export endpoint for Telegraf's
+//! `http` input to poll.
+
+mod exporter;
+
+use std::fmt::Write;
+
+use crate::models::Price;
+
+pub use exporter::InfluxExporter;
+
+/// Measurement every price point is written to.
+pub const MEASUREMENT: &str = "electricity_price";
+
+/// Append `price` to `out` as one line of line protocol, terminated by a
+/// newline. Points are tagged by zone, market, currency and resolution and
+/// carry a nanosecond timestamp, so re-fetched prices overwrite the earlier
+/// point instead of adding one.
+pub fn write_line(out: &mut String, price: &Price) {
+    out.push_str(MEASUREMENT);
+    let tags = [
+        ("zone", price.bidding_zone.as_str()),
+        ("market", price.market.as_str()),
+        ("currency", price.currency.as_str()),
+        ("resolution", price.resolution.as_str()),
+    ];
+    // Line protocol has no empty tag values
+    for (key, value) in tags.into_iter().filter(|(_, value)| !value.is_empty()) {
+        out.push(',');
+        out.push_str(key);
+        out.push('=');
+        escape_tag_value(out, value);
+    }
+    let nanos = price.timestamp.timestamp_nanos_opt().expect("price timestamps fit in nanoseconds");
+    // gap_filled is always written so a published price replaces the flag of a filled one
+    let _ = writeln!(
+        out,
+        " price_kwh={},gap_filled={} {}",
+        price.price_kwh.normalize(),
+        price.gap_fill.is_some(),
+        nanos
+    );
+}
+
+/// Line protocol for `prices`, one line each.
+pub fn to_line_protocol(prices: &[Price]) -> String {
+    let mut out = String::with_capacity(prices.len() * 128);
+    for price in prices {
+        write_line(&mut out, price);
+    }
+    out
+}
+
+/// Tag values escape commas, spaces and equals signs with a backslash.
+fn escape_tag_value(out: &mut String, value: &str) {
+    for c in value.chars() {
+        if matches!(c, ',' | ' ' | '=' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::models::GapFill;
+
+    #[test]
+    fn test_line_protocol_tags_fields_and_timestamp() {
+        let hour = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let mut price = Price::from_mwh(hour, "NO1".to_string(), 41.5, "PT60M".to_string());
+        assert_eq!(
+            to_line_protocol(std::slice::from_ref(&price)),
+            "electricity_price,zone=NO1,market=day_ahead,currency=EUR,resolution=PT60M price_kwh=0.0415,gap_filled=false 1736899200000000000\n"
+        );
+
+        price.bidding_zone = "IT-North Sud,1=x".to_string();
+        price.gap_fill = Some(GapFill::ForwardFill);
+        let line = to_line_protocol(&[price]);
+        assert!(line.starts_with(r"electricity_price,zone=IT-North\ Sud\,1\=x,market="), "{}", line);
+        assert!(line.contains("gap_filled=true "), "{}", line);
+    }
+}
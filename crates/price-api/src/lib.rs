@@ -0,0 +1,34 @@
+pub mod alerts;
+pub mod api;
+pub mod cache;
+pub mod clock;
+pub mod config;
+pub mod fetcher;
+pub mod fx;
+pub mod influx;
+pub mod logging;
+pub mod metrics;
+pub mod scheduler;
+pub mod tasks;
+pub mod webhooks;
+
+pub use entsoe_core::{entsoe, models};
+pub use price_store as storage;
+
+pub use alerts::AlertService;
+pub use api::{
+    bind_listener, create_router, create_scoped_router, generate_api_key, hash_api_key, serve_grpc, ApiKeyAuth, AppError,
+    AppState, CorrelationId, JwtVerifier, RateLimiter, ReadinessGate, ResponseSigner,
+};
+pub use cache::PriceCache;
+pub use clock::{Clock, SystemClock};
+pub use config::AppConfig;
+pub use entsoe::{EntsoeClient, EntsoeError, EntsoePriceSource};
+pub use fetcher::{FetchSummary, FetcherService};
+pub use fx::{FxError, FxRate, FxRateService, RateSource};
+pub use influx::InfluxExporter;
+pub use logging::init_tracing;
+pub use metrics::init_metrics;
+pub use scheduler::PriceFetchScheduler;
+pub use storage::{PoolStatus, PriceRepository, PriceStore, SqlitePriceStore, StorageError};
+pub use webhooks::{WebhookEvent, WebhookNotifier};
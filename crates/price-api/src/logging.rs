@@ -0,0 +1,311 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use tracing::{Event, Subscriber};
+use tracing_appender::non_blocking::{NonBlockingBuilder, WorkerGuard};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::config::{DeploymentConfig, LogFileConfig, LogRotation};
+
+/// Install the global subscriber: JSON lines unless `LOG_FORMAT` is set to
+/// something else, filtered by `RUST_LOG`. Every event carries the deployment
+/// labels. Lines go to stdout, and to `log_file` when it is enabled; the
+/// returned guard flushes the file on drop, so hold it until exit. File lines
+/// are written from a background thread; once it falls far enough behind,
+/// logging waits for it rather than dropping lines.
+pub fn init_tracing(deployment: &DeploymentConfig, log_file: &LogFileConfig) -> io::Result<Option<WorkerGuard>> {
+    let log_format = std::env::var("LOG_FORMAT").unwrap_or_else(|_| "json".to_string());
+    let default_filter = std::env::var("RUST_LOG").unwrap_or_else(|_| "entsoe_price_fetcher=info,price_api=info,price_store=info,entsoe_core=info,tower_http=info,sqlx=warn".to_string());
+    let env_filter = EnvFilter::new(default_filter);
+
+    let (file_writer, guard) = if log_file.enabled {
+        let (writer, guard) = NonBlockingBuilder::default().lossy(false).finish(log_file_writer(log_file)?);
+        (Some(writer), Some(guard))
+    } else {
+        (None, None)
+    };
+
+    if log_format == "json" {
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(tracing_subscriber::fmt::layer().json().event_format(DeploymentFields::json(
+                tracing_subscriber::fmt::format().json(),
+                deployment,
+            )))
+            .with(file_writer.map(|writer| {
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .event_format(DeploymentFields::json(tracing_subscriber::fmt::format().json(), deployment))
+                    .with_writer(writer)
+            }))
+            .init();
+    } else {
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(tracing_subscriber::fmt::layer().event_format(DeploymentFields::text(
+                tracing_subscriber::fmt::format(),
+                deployment,
+            )))
+            .with(file_writer.map(|writer| {
+                tracing_subscriber::fmt::layer()
+                    .event_format(DeploymentFields::text(tracing_subscriber::fmt::format(), deployment))
+                    .with_ansi(false)
+                    .with_writer(writer)
+            }))
+            .init();
+    }
+
+    Ok(guard)
+}
+
+/// The file `config` describes, rotated on its schedule or by size.
+fn log_file_writer(config: &LogFileConfig) -> io::Result<Box<dyn Write + Send>> {
+    if config.max_files == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "log_file.max_files must be at least 1"));
+    }
+    if config.rotation == LogRotation::Size && config.max_size_mb == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "log_file.max_size_mb must be at least 1"));
+    }
+    fs::create_dir_all(&config.directory)?;
+    let rotation = match config.rotation {
+        LogRotation::Minutely => Rotation::MINUTELY,
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+        LogRotation::Weekly => Rotation::WEEKLY,
+        LogRotation::Never => Rotation::NEVER,
+        LogRotation::Size => {
+            let max_bytes = config.max_size_mb.saturating_mul(1024 * 1024);
+            let file = SizeRotatingFile::open(config.directory.join(&config.file_name), max_bytes, config.max_files)?;
+            return Ok(Box::new(file));
+        }
+    };
+    let appender = RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix(&config.file_name)
+        .max_log_files(config.max_files)
+        .build(&config.directory)
+        .map_err(io::Error::other)?;
+    Ok(Box::new(appender))
+}
+
+/// A log file that is renamed to `<path>.1` once writing to it would take it
+/// past `max_bytes`, shifting earlier rotations up by one and deleting the
+/// ones beyond `max_files`.
+struct SizeRotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    len: u64,
+}
+
+impl SizeRotatingFile {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            file,
+            len,
+        })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let kept = self.max_files - 1;
+        if kept == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            remove_if_exists(&self.rotated(kept))?;
+            for n in (1..kept).rev() {
+                rename_if_exists(&self.rotated(n), &self.rotated(n + 1))?;
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+            self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        }
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl Write for SizeRotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Lines arrive one per write, so a line is never split across files
+        if self.len > 0 && self.len + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.len += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Event formatter that adds the deployment labels to another formatter's
+/// output: as top-level keys of a JSON line, or as a `[key=value ...]` prefix
+/// of a text line.
+struct DeploymentFields<E> {
+    inner: E,
+    labels: Vec<(&'static str, String)>,
+    json: bool,
+}
+
+impl<E> DeploymentFields<E> {
+    fn json(inner: E, deployment: &DeploymentConfig) -> Self {
+        Self {
+            inner,
+            labels: deployment.labels(),
+            json: true,
+        }
+    }
+
+    fn text(inner: E, deployment: &DeploymentConfig) -> Self {
+        Self {
+            inner,
+            labels: deployment.labels(),
+            json: false,
+        }
+    }
+}
+
+impl<S, N, E> FormatEvent<S, N> for DeploymentFields<E>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    E: FormatEvent<S, N>,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        if self.labels.is_empty() {
+            return self.inner.format_event(ctx, writer, event);
+        }
+
+        if !self.json {
+            let labels: Vec<String> = self.labels.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+            write!(writer, "[{}] ", labels.join(" "))?;
+            return self.inner.format_event(ctx, writer, event);
+        }
+
+        let mut line = String::new();
+        self.inner.format_event(ctx, Writer::new(&mut line), event)?;
+        let Some(close) = line.rfind('}') else {
+            return writer.write_str(&line);
+        };
+        let labels: String = self
+            .labels
+            .iter()
+            .map(|(key, value)| format!(",\"{}\":{}", key, serde_json::Value::from(value.as_str())))
+            .collect();
+        line.insert_str(close, &labels);
+        writer.write_str(&line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_lines_carry_deployment_labels() {
+        let deployment = DeploymentConfig {
+            environment: Some("staging".to_string()),
+            region: Some("eu-north-1".to_string()),
+            instance_id: None,
+        };
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .event_format(DeploymentFields::json(tracing_subscriber::fmt::format().json(), &deployment))
+                .with_writer(move || writer.clone()),
+        );
+
+        tracing::subscriber::with_default(subscriber, || tracing::info!(zone = "NO1", "Prices stored"));
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["environment"], "staging");
+        assert_eq!(line["region"], "eu-north-1");
+        assert!(line.get("instance_id").is_none());
+        assert_eq!(line["fields"]["zone"], "NO1");
+    }
+
+    #[test]
+    fn test_size_rotation_needs_a_size() {
+        let config = LogFileConfig {
+            enabled: true,
+            directory: std::env::temp_dir().join(format!("log-rotation-{}", uuid::Uuid::new_v4())),
+            rotation: LogRotation::Size,
+            max_size_mb: 0,
+            ..LogFileConfig::default()
+        };
+        let error = log_file_writer(&config).err().unwrap();
+        assert_eq!(error.to_string(), "log_file.max_size_mb must be at least 1");
+        assert!(!config.directory.exists());
+    }
+
+    #[test]
+    fn test_size_rotation_keeps_max_files() {
+        let directory = std::env::temp_dir().join(format!("log-rotation-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("service.log");
+        let mut file = SizeRotatingFile::open(path.clone(), 10, 3).unwrap();
+        for line in ["first 1\n", "second\n", "third 3\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        let read = |path: PathBuf| fs::read_to_string(path).unwrap_or_default();
+        assert_eq!(read(path.clone()), "fourth\n");
+        assert_eq!(read(file.rotated(1)), "third 3\n");
+        assert_eq!(read(file.rotated(2)), "second\n");
+        assert!(!file.rotated(3).exists());
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,511 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram, Unit};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+
+use crate::config::DeploymentConfig;
+
+mod price_gauges;
+
+pub use entsoe_core::metrics::*;
+pub use price_gauges::{render_price_gauges, ExpositionFormat, PriceGaugeCache};
+pub use price_store::metrics::*;
+
+// Zone registry metrics
+pub const ENTSOE_ZONES_WITH_TOMORROW_DATA: &str = "entsoe_zones_with_tomorrow_data";
+pub const ENTSOE_ACTIVE_ZONES: &str = "entsoe_active_zones";
+pub const ENTSOE_EMPTY_ZONE_REGISTRY_TOTAL: &str = "entsoe_empty_zone_registry_total";
+
+// HTTP request metrics
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+pub const API_ZONE_REQUESTS_TOTAL: &str = "api_zone_requests_total";
+pub const API_ZONE_REQUEST_DURATION_SECONDS: &str = "api_zone_request_duration_seconds";
+pub const API_RATE_LIMITED_TOTAL: &str = "api_rate_limited_total";
+pub const API_AUTH_FAILURES_TOTAL: &str = "api_auth_failures_total";
+
+/// Zone label for price requests that did not resolve to a registry zone, so
+/// arbitrary path input cannot create new series.
+pub const OTHER_ZONE_LABEL: &str = "other";
+
+// Scheduler metrics
+pub const SCHEDULER_JOB_EXECUTIONS_TOTAL: &str = "scheduler_job_executions_total";
+pub const SCHEDULER_JOB_DURATION_SECONDS: &str = "scheduler_job_duration_seconds";
+pub const SCHEDULER_MISFIRES_TOTAL: &str = "scheduler_misfires_total";
+pub const SCHEDULER_JOBS_DEFERRED_TOTAL: &str = "scheduler_jobs_deferred_total";
+pub const FETCH_RETRIES_TOTAL: &str = "fetch_retries_total";
+
+// Task queue metrics
+pub const TASKS_TOTAL: &str = "tasks_total";
+pub const TASK_DURATION_SECONDS: &str = "task_duration_seconds";
+pub const TASK_QUEUE_TASKS: &str = "task_queue_tasks";
+
+// Retention metrics
+pub const RETENTION_ROWS_DELETED_TOTAL: &str = "retention_rows_deleted_total";
+
+// Webhook metrics
+pub const WEBHOOK_DELIVERIES_TOTAL: &str = "webhook_deliveries_total";
+pub const WEBHOOK_DELIVERY_ATTEMPTS_TOTAL: &str = "webhook_delivery_attempts_total";
+pub const WEBHOOK_DELIVERY_DURATION_SECONDS: &str = "webhook_delivery_duration_seconds";
+
+// InfluxDB export metrics
+pub const INFLUX_POINTS_WRITTEN_TOTAL: &str = "influx_points_written_total";
+
+// Alert metrics
+pub const ALERTS_FIRED_TOTAL: &str = "alerts_fired_total";
+
+// Cache metrics
+pub const CACHE_LOOKUPS_TOTAL: &str = "cache_lookups_total";
+pub const CACHE_WARM_DURATION_SECONDS: &str = "cache_warm_duration_seconds";
+pub const CACHE_COALESCED_REQUESTS_TOTAL: &str = "cache_coalesced_requests_total";
+
+// Price metrics, rendered from storage on scrape
+pub const ELECTRICITY_PRICE_EUR_KWH: &str = "electricity_price_eur_kwh";
+
+/// Metrics recorded by the API, scheduler and notification layers.
+const SERVICE_METRICS: &[MetricDescription] = &[
+    describe(
+        ENTSOE_ZONES_WITH_TOMORROW_DATA,
+        MetricKind::Gauge,
+        Some(Unit::Count),
+        &[],
+        "Active zones that have tomorrow's prices stored",
+    ),
+    describe(
+        ENTSOE_ACTIVE_ZONES,
+        MetricKind::Gauge,
+        Some(Unit::Count),
+        &[],
+        "Active bidding zones in the zone registry",
+    ),
+    describe(
+        ENTSOE_EMPTY_ZONE_REGISTRY_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["context"],
+        "Times the zone registry was found empty",
+    ),
+    describe(
+        HTTP_REQUEST_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["method", "path", "status"],
+        "API request latency by route template",
+    ),
+    describe(
+        HTTP_REQUESTS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["method", "path", "status"],
+        "API requests by route template and status code",
+    ),
+    describe(
+        API_ZONE_REQUESTS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["endpoint", "zone_code"],
+        "Price endpoint requests by zone; unknown zones are labelled \"other\"",
+    ),
+    describe(
+        API_ZONE_REQUEST_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["endpoint", "zone_code"],
+        "Price endpoint latency by zone; unknown zones are labelled \"other\"",
+    ),
+    describe(
+        API_RATE_LIMITED_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["key_source"],
+        "API requests rejected with 429 by the per-key rate limit",
+    ),
+    describe(
+        API_AUTH_FAILURES_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["reason"],
+        "API requests rejected by API key authentication: missing, invalid or forbidden",
+    ),
+    describe(
+        SCHEDULER_JOB_EXECUTIONS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["job_name", "status"],
+        "Scheduled job runs by job and outcome",
+    ),
+    describe(
+        SCHEDULER_JOB_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["job_name"],
+        "Scheduled job duration",
+    ),
+    describe(
+        SCHEDULER_MISFIRES_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["trigger", "policy"],
+        "Missed primary fetches detected at startup or after a suspend",
+    ),
+    describe(
+        SCHEDULER_JOBS_DEFERRED_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["job_name"],
+        "Fetch jobs held back until an ENTSOE maintenance window ended",
+    ),
+    describe(
+        FETCH_RETRIES_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["outcome"],
+        "Failed zone fetches in the retry queue: queued, succeeded, rescheduled, expired or failed (left due after an error)",
+    ),
+    describe(
+        TASKS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["kind", "outcome"],
+        "Task attempts run by this replica: succeeded, retried, failed or lease_lost",
+    ),
+    describe(
+        TASK_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["kind"],
+        "Duration of individual task attempts",
+    ),
+    describe(
+        TASK_QUEUE_TASKS,
+        MetricKind::Gauge,
+        Some(Unit::Count),
+        &["status"],
+        "Tasks in the queue table by status, sampled by idle workers",
+    ),
+    describe(
+        RETENTION_ROWS_DELETED_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["table"],
+        "Rows deleted by retention cleanup or admin pruning",
+    ),
+    describe(
+        WEBHOOK_DELIVERIES_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["event", "result"],
+        "Webhook deliveries by event type and final result",
+    ),
+    describe(
+        WEBHOOK_DELIVERY_ATTEMPTS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["event", "outcome"],
+        "Individual webhook HTTP attempts, including retries",
+    ),
+    describe(
+        WEBHOOK_DELIVERY_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["event"],
+        "Duration of individual webhook HTTP attempts",
+    ),
+    describe(
+        INFLUX_POINTS_WRITTEN_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["result"],
+        "Price points pushed to InfluxDB by result",
+    ),
+    describe(
+        ALERTS_FIRED_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["channel", "result"],
+        "Price alert notifications by delivery channel and result",
+    ),
+    describe(
+        CACHE_LOOKUPS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["entry", "result"],
+        "In-memory price cache lookups by entry and hit/miss",
+    ),
+    describe(
+        CACHE_WARM_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &[],
+        "Time taken to warm the in-memory price cache",
+    ),
+    describe(
+        CACHE_COALESCED_REQUESTS_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["query"],
+        "Price queries served by joining an identical in-flight query",
+    ),
+    describe(
+        ELECTRICITY_PRICE_EUR_KWH,
+        MetricKind::Gauge,
+        None,
+        &["zone_code", "currency"],
+        "Day-ahead price per kWh for the current interval by zone, in the currency named by the currency label; OpenMetrics scrapes carry the interval start as timestamp",
+    ),
+];
+
+/// Every metric this service exports: the ENTSOE client's and storage
+/// layer's own catalogs followed by the service metrics above.
+/// `describe_metrics` registers these with the recorder.
+pub static METRIC_CATALOG: LazyLock<Vec<MetricDescription>> = LazyLock::new(|| {
+    ENTSOE_METRICS
+        .iter()
+        .chain(STORAGE_METRICS)
+        .chain(SERVICE_METRICS)
+        .copied()
+        .collect()
+});
+
+/// Register descriptions and units for every metric in `METRIC_CATALOG`
+/// with the installed recorder.
+pub fn describe_metrics() {
+    for metric in METRIC_CATALOG.iter() {
+        match (metric.kind, metric.unit) {
+            (MetricKind::Counter, Some(unit)) => describe_counter!(metric.name, unit, metric.description),
+            (MetricKind::Counter, None) => describe_counter!(metric.name, metric.description),
+            (MetricKind::Gauge, Some(unit)) => describe_gauge!(metric.name, unit, metric.description),
+            (MetricKind::Gauge, None) => describe_gauge!(metric.name, metric.description),
+            (MetricKind::Histogram, Some(unit)) => describe_histogram!(metric.name, unit, metric.description),
+            (MetricKind::Histogram, None) => describe_histogram!(metric.name, metric.description),
+        }
+    }
+}
+
+pub fn init_metrics(deployment: &DeploymentConfig) -> PrometheusHandle {
+    let handle = prometheus_builder(deployment)
+        .install_recorder()
+        .expect("Failed to install Prometheus recorder");
+
+    describe_metrics();
+    handle
+}
+
+/// Exporter with our histogram buckets and the deployment labels added to
+/// every series.
+fn prometheus_builder(deployment: &DeploymentConfig) -> PrometheusBuilder {
+    let builder = PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Suffix(ENTSOE_FETCH_DURATION_SECONDS.to_string()),
+            &[0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0],
+        )
+        .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Suffix(ENTSOE_HTTP_PHASE_DURATION_SECONDS.to_string()),
+            &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0],
+        )
+        .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Suffix(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+            &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0],
+        )
+        .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Suffix(API_ZONE_REQUEST_DURATION_SECONDS.to_string()),
+            &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0],
+        )
+        .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Suffix(DATABASE_QUERY_DURATION_SECONDS.to_string()),
+            &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0],
+        )
+        .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Suffix(DATABASE_WRITE_WAIT_SECONDS.to_string()),
+            &[0.001, 0.01, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0],
+        )
+        .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Suffix(SCHEDULER_JOB_DURATION_SECONDS.to_string()),
+            &[1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0],
+        )
+        .unwrap();
+
+    deployment
+        .labels()
+        .into_iter()
+        .fold(builder, |builder, (label, value)| builder.add_global_label(label, value))
+}
+
+pub fn record_http_request(method: &str, path: &str, status: u16, duration: Duration) {
+    let status_str = status.to_string();
+    counter!(HTTP_REQUESTS_TOTAL, "method" => method.to_string(), "path" => path.to_string(), "status" => status_str.clone())
+        .increment(1);
+    histogram!(HTTP_REQUEST_DURATION_SECONDS, "method" => method.to_string(), "path" => path.to_string(), "status" => status_str)
+        .record(duration.as_secs_f64());
+}
+
+pub fn record_rate_limited(key_source: &str) {
+    counter!(API_RATE_LIMITED_TOTAL, "key_source" => key_source.to_string()).increment(1);
+}
+
+pub fn record_auth_failure(reason: &str) {
+    counter!(API_AUTH_FAILURES_TOTAL, "reason" => reason.to_string()).increment(1);
+}
+
+pub fn record_zone_request(endpoint: &str, zone_code: &str, duration: Duration) {
+    counter!(API_ZONE_REQUESTS_TOTAL, "endpoint" => endpoint.to_string(), "zone_code" => zone_code.to_string())
+        .increment(1);
+    histogram!(API_ZONE_REQUEST_DURATION_SECONDS, "endpoint" => endpoint.to_string(), "zone_code" => zone_code.to_string())
+        .record(duration.as_secs_f64());
+}
+
+pub fn update_zones_with_tomorrow_data(count: u64) {
+    gauge!(ENTSOE_ZONES_WITH_TOMORROW_DATA).set(count as f64);
+}
+
+pub fn update_active_zones(count: u64) {
+    gauge!(ENTSOE_ACTIVE_ZONES).set(count as f64);
+}
+
+pub fn record_empty_zone_registry(context: &str) {
+    counter!(ENTSOE_EMPTY_ZONE_REGISTRY_TOTAL, "context" => context.to_string()).increment(1);
+}
+
+pub fn record_scheduler_job_execution(job_name: &str, status: &str) {
+    counter!(SCHEDULER_JOB_EXECUTIONS_TOTAL, "job_name" => job_name.to_string(), "status" => status.to_string())
+        .increment(1);
+}
+
+pub fn record_scheduler_job_duration(job_name: &str, duration: Duration) {
+    histogram!(SCHEDULER_JOB_DURATION_SECONDS, "job_name" => job_name.to_string())
+        .record(duration.as_secs_f64());
+}
+
+pub fn record_scheduler_misfire(trigger: &str, policy: &str) {
+    counter!(SCHEDULER_MISFIRES_TOTAL, "trigger" => trigger.to_string(), "policy" => policy.to_string())
+        .increment(1);
+}
+
+pub fn record_scheduler_job_deferred(job_name: &str) {
+    counter!(SCHEDULER_JOBS_DEFERRED_TOTAL, "job_name" => job_name.to_string()).increment(1);
+}
+
+pub fn record_fetch_retries(outcome: &str, count: u64) {
+    counter!(FETCH_RETRIES_TOTAL, "outcome" => outcome.to_string()).increment(count);
+}
+
+pub fn record_task_attempt(kind: &str, outcome: &str, duration: Duration) {
+    counter!(TASKS_TOTAL, "kind" => kind.to_string(), "outcome" => outcome.to_string()).increment(1);
+    histogram!(TASK_DURATION_SECONDS, "kind" => kind.to_string()).record(duration.as_secs_f64());
+}
+
+pub fn update_task_queue_tasks(status: &str, count: i64) {
+    gauge!(TASK_QUEUE_TASKS, "status" => status.to_string()).set(count as f64);
+}
+
+pub fn record_retention_deleted(table: &str, count: u64) {
+    counter!(RETENTION_ROWS_DELETED_TOTAL, "table" => table.to_string()).increment(count);
+}
+
+pub fn record_cache_lookup(entry: &str, hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    counter!(CACHE_LOOKUPS_TOTAL, "entry" => entry.to_string(), "result" => result).increment(1);
+}
+
+pub fn record_coalesced_request(query: &str) {
+    counter!(CACHE_COALESCED_REQUESTS_TOTAL, "query" => query.to_string()).increment(1);
+}
+
+pub fn record_cache_warm(duration: Duration) {
+    histogram!(CACHE_WARM_DURATION_SECONDS).record(duration.as_secs_f64());
+}
+
+pub fn record_webhook_delivery(event: &str, delivered: bool) {
+    let result = if delivered { "delivered" } else { "failed" };
+    counter!(WEBHOOK_DELIVERIES_TOTAL, "event" => event.to_string(), "result" => result).increment(1);
+}
+
+pub fn record_influx_points(written: bool, points: usize) {
+    let result = if written { "written" } else { "failed" };
+    counter!(INFLUX_POINTS_WRITTEN_TOTAL, "result" => result).increment(points as u64);
+}
+
+pub fn record_alert_fired(channel: &str, delivered: bool) {
+    let result = if delivered { "delivered" } else { "failed" };
+    counter!(ALERTS_FIRED_TOTAL, "channel" => channel.to_string(), "result" => result).increment(1);
+}
+
+pub fn record_webhook_attempt(event: &str, outcome: &str) {
+    counter!(WEBHOOK_DELIVERY_ATTEMPTS_TOTAL, "event" => event.to_string(), "outcome" => outcome.to_string())
+        .increment(1);
+}
+
+pub fn record_webhook_attempt_duration(event: &str, duration: Duration) {
+    histogram!(WEBHOOK_DELIVERY_DURATION_SECONDS, "event" => event.to_string()).record(duration.as_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use metrics_util::debugging::DebuggingRecorder;
+
+    use super::*;
+
+    #[test]
+    fn test_catalog_names_are_unique() {
+        let names: HashSet<_> = METRIC_CATALOG.iter().map(|m| m.name).collect();
+        assert_eq!(names.len(), METRIC_CATALOG.len());
+    }
+
+    #[test]
+    fn test_describe_registers_every_catalog_entry() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            describe_metrics();
+            for metric in METRIC_CATALOG.iter() {
+                match metric.kind {
+                    MetricKind::Counter => counter!(metric.name).increment(1),
+                    MetricKind::Gauge => gauge!(metric.name).set(1.0),
+                    MetricKind::Histogram => histogram!(metric.name).record(1.0),
+                }
+            }
+        });
+
+        let described: HashSet<String> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(_, _, description, _)| description.is_some())
+            .map(|(key, _, _, _)| key.key().name().to_string())
+            .collect();
+        for metric in METRIC_CATALOG.iter() {
+            assert!(described.contains(metric.name), "{} has no description", metric.name);
+        }
+    }
+
+    #[test]
+    fn test_deployment_labels_are_added_to_every_series() {
+        let deployment = DeploymentConfig {
+            environment: Some("staging".to_string()),
+            region: None,
+            instance_id: Some("pod-1".to_string()),
+        };
+        let recorder = prometheus_builder(&deployment).build_recorder();
+        let handle = recorder.handle();
+
+        ::metrics::with_local_recorder(&recorder, || record_fetch_attempt("NO1", "success"));
+
+        let rendered = handle.render();
+        assert!(
+            rendered.contains(r#"{environment="staging",instance_id="pod-1",zone_code="NO1",status="success"}"#),
+            "{}",
+            rendered
+        );
+        assert!(!rendered.contains("region="));
+    }
+}
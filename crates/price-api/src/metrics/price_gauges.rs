@@ -0,0 +1,204 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, DurationRound, Utc};
+use tokio::sync::Mutex;
+
+use crate::models::Price;
+use crate::storage::{PriceStore, StorageError};
+
+use super::{ELECTRICITY_PRICE_EUR_KWH, METRIC_CATALOG};
+
+/// Each zone's current price for `/metrics`, read from storage once per
+/// quarter hour (the shortest interval ENTSOE publishes) instead of on every
+/// scrape.
+#[derive(Default)]
+pub struct PriceGaugeCache {
+    current: Mutex<Option<(DateTime<Utc>, Vec<Price>)>>,
+}
+
+impl PriceGaugeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The prices current at `now`, loaded from `store` when the cached ones
+    /// belong to an earlier quarter hour. Concurrent scrapes share one load.
+    pub async fn current_prices(&self, store: &dyn PriceStore, now: DateTime<Utc>) -> Result<Vec<Price>, StorageError> {
+        let quarter = now.duration_trunc(chrono::Duration::minutes(15)).unwrap_or(now);
+        let mut current = self.current.lock().await;
+        if let Some((loaded, prices)) = current.as_ref() {
+            if *loaded == quarter {
+                return Ok(prices.clone());
+            }
+        }
+        let prices = store.get_current_prices(now).await?;
+        *current = Some((quarter, prices.clone()));
+        Ok(prices)
+    }
+}
+
+/// Exposition format of a `/metrics` response, negotiated from the
+/// scraper's `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpositionFormat {
+    /// Prometheus text format 0.0.4, the default.
+    Prometheus,
+    /// OpenMetrics 1.0, which Prometheus asks for first by default.
+    OpenMetrics,
+}
+
+impl ExpositionFormat {
+    /// OpenMetrics when `accept` lists it with a non-zero quality.
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let openmetrics = accept.is_some_and(|accept| {
+            accept.split(',').any(|range| {
+                let mut params = range.split(';').map(str::trim);
+                params.next() == Some("application/openmetrics-text") && !params.any(|param| param == "q=0" || param == "q=0.0")
+            })
+        });
+        if openmetrics {
+            Self::OpenMetrics
+        } else {
+            Self::Prometheus
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Prometheus => "text/plain; version=0.0.4; charset=utf-8",
+            Self::OpenMetrics => "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        }
+    }
+}
+
+/// Render `electricity_price_eur_kwh`, one sample per zone labelled with the
+/// currency the zone is priced in (GBP for GB, EUR elsewhere).
+///
+/// In OpenMetrics each sample carries the start of the interval its price is
+/// valid for as its timestamp (seconds), so a scrape just after a boundary
+/// never records the previous interval's price as the new one's. Prometheus
+/// does not mark timestamped samples stale, and an instant query more than
+/// its lookback delta (5 minutes by default) after the interval start finds
+/// none, so query the gauge with `last_over_time(...[1h])`. In the Prometheus
+/// text format samples carry no timestamp and are stamped with the scrape
+/// time like every other series.
+///
+/// These samples bypass the `metrics` recorder, so `labels` carries the
+/// deployment labels it would add.
+pub fn render_price_gauges(prices: &[Price], labels: &[(&'static str, String)], format: ExpositionFormat) -> String {
+    if prices.is_empty() {
+        return String::new();
+    }
+
+    let help = METRIC_CATALOG
+        .iter()
+        .find(|metric| metric.name == ELECTRICITY_PRICE_EUR_KWH)
+        .map_or("", |metric| metric.description);
+    let mut out = String::new();
+    let _ = writeln!(out, "# HELP {} {}", ELECTRICITY_PRICE_EUR_KWH, help);
+    let _ = writeln!(out, "# TYPE {} gauge", ELECTRICITY_PRICE_EUR_KWH);
+    for price in prices {
+        let label_set: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .chain([("zone_code", price.bidding_zone.as_str()), ("currency", price.currency.as_str())])
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+            .collect();
+        let _ = match format {
+            ExpositionFormat::Prometheus => {
+                writeln!(out, "{}{{{}}} {}", ELECTRICITY_PRICE_EUR_KWH, label_set.join(","), price.price_kwh)
+            }
+            ExpositionFormat::OpenMetrics => writeln!(
+                out,
+                "{}{{{}}} {} {}",
+                ELECTRICITY_PRICE_EUR_KWH,
+                label_set.join(","),
+                price.price_kwh,
+                price.timestamp.timestamp()
+            ),
+        };
+    }
+    out
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::storage::InMemoryPriceStore;
+
+    #[test]
+    fn test_openmetrics_samples_carry_their_interval_start() {
+        let hour = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let prices = vec![
+            Price::from_mwh(hour, "NO1".to_string(), 51.2, "PT60M".to_string()),
+            Price {
+                currency: "GBP".to_string(),
+                ..Price::from_mwh(hour, "GB".to_string(), -3.0, "PT30M".to_string())
+            },
+        ];
+
+        let labels = [("environment", "prod".to_string())];
+        let samples = |format| {
+            render_price_gauges(&prices, &labels, format)
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            samples(ExpositionFormat::OpenMetrics),
+            vec![
+                r#"electricity_price_eur_kwh{environment="prod",zone_code="NO1",currency="EUR"} 0.0512 1736935200"#,
+                r#"electricity_price_eur_kwh{environment="prod",zone_code="GB",currency="GBP"} -0.003 1736935200"#,
+            ]
+        );
+        assert_eq!(
+            samples(ExpositionFormat::Prometheus),
+            vec![
+                r#"electricity_price_eur_kwh{environment="prod",zone_code="NO1",currency="EUR"} 0.0512"#,
+                r#"electricity_price_eur_kwh{environment="prod",zone_code="GB",currency="GBP"} -0.003"#,
+            ]
+        );
+        assert!(render_price_gauges(&prices, &labels, ExpositionFormat::Prometheus).contains("# TYPE electricity_price_eur_kwh gauge"));
+        assert!(render_price_gauges(&[], &[], ExpositionFormat::OpenMetrics).is_empty());
+    }
+
+    #[test]
+    fn test_negotiates_openmetrics_from_accept() {
+        let prometheus_default = "application/openmetrics-text;version=1.0.0;q=0.5,application/openmetrics-text;version=0.0.1;q=0.4,text/plain;version=0.0.4;q=0.3,*/*;q=0.2";
+        assert_eq!(ExpositionFormat::negotiate(Some(prometheus_default)), ExpositionFormat::OpenMetrics);
+        assert_eq!(ExpositionFormat::negotiate(Some("text/plain;version=0.0.4")), ExpositionFormat::Prometheus);
+        assert_eq!(ExpositionFormat::negotiate(Some("application/openmetrics-text; q=0")), ExpositionFormat::Prometheus);
+        assert_eq!(ExpositionFormat::negotiate(None), ExpositionFormat::Prometheus);
+    }
+
+    #[tokio::test]
+    async fn test_cache_reloads_once_per_quarter_hour() {
+        let quarter = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let store = InMemoryPriceStore::new();
+        store
+            .upsert_prices(&[Price::from_mwh(quarter, "NO1".to_string(), 40.0, "PT15M".to_string())])
+            .await
+            .unwrap();
+        let cache = PriceGaugeCache::new();
+        assert_eq!(cache.current_prices(&store, quarter).await.unwrap().len(), 1);
+
+        // Not read again within the quarter hour
+        let next = quarter + chrono::Duration::minutes(15);
+        store
+            .upsert_prices(&[Price::from_mwh(next, "NO1".to_string(), 80.0, "PT15M".to_string())])
+            .await
+            .unwrap();
+        let cached = cache.current_prices(&store, quarter + chrono::Duration::minutes(14)).await.unwrap();
+        assert_eq!(cached[0].timestamp, quarter);
+
+        let reloaded = cache.current_prices(&store, next).await.unwrap();
+        assert_eq!(reloaded[0].timestamp, next);
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use croner::Cron;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::clock::Clock;
+
+use super::runner::{next_fire_time, parse_cron, JobFn, JobRunner};
+
+/// Wall clock driven by tokio's clock, so it follows `tokio::time::pause`
+/// and `advance` in tests. [`suspend`](Self::suspend) moves only the wall
+/// clock, as when the host sleeps.
+pub struct VirtualClock {
+    origin_wall: DateTime<Utc>,
+    origin: Instant,
+    suspended: Mutex<Duration>,
+}
+
+impl VirtualClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            origin_wall: start,
+            origin: Instant::now(),
+            suspended: Mutex::new(Duration::zero()),
+        }
+    }
+
+    pub fn suspend(&self, duration: Duration) {
+        *self.suspended.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> DateTime<Utc> {
+        let elapsed = Duration::from_std(self.origin.elapsed()).unwrap_or(Duration::zero());
+        self.origin_wall + elapsed + *self.suspended.lock().unwrap()
+    }
+}
+
+/// A job execution recorded by [`EmbeddedJobRunner`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobFiring {
+    pub name: String,
+    pub at: DateTime<Utc>,
+}
+
+struct EmbeddedJob {
+    name: String,
+    cron: Cron,
+    job: JobFn,
+}
+
+/// In-process [`JobRunner`] for tests. Sleeps on tokio's clock until the next
+/// fire time according to a [`VirtualClock`] and runs due jobs one at a time,
+/// so with paused time a day of jobs runs instantly and in a fixed order.
+/// Fire times missed while the clock is suspended are skipped, like a host
+/// that was asleep.
+#[derive(Clone)]
+pub struct EmbeddedJobRunner {
+    clock: Arc<VirtualClock>,
+    jobs: Arc<Mutex<Vec<EmbeddedJob>>>,
+    firings: Arc<Mutex<Vec<JobFiring>>>,
+    task: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl EmbeddedJobRunner {
+    pub fn new(clock: Arc<VirtualClock>) -> Self {
+        Self {
+            clock,
+            jobs: Arc::default(),
+            firings: Arc::default(),
+            task: Arc::default(),
+        }
+    }
+
+    /// Jobs run so far, in order.
+    pub fn firings(&self) -> Vec<JobFiring> {
+        self.firings.lock().unwrap().clone()
+    }
+
+    /// Earliest fire time after `now` and the index of the job it belongs to.
+    fn next_due(&self, now: DateTime<Utc>) -> Option<(usize, DateTime<Utc>)> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, job)| Some((index, next_fire_time(&job.cron, now)?)))
+            .min_by_key(|(_, at)| *at)
+    }
+}
+
+#[async_trait]
+impl JobRunner for EmbeddedJobRunner {
+    async fn add(&self, name: &str, cron_expr: &str, job: JobFn) -> Result<()> {
+        let cron = parse_cron(cron_expr)?;
+        self.jobs.lock().unwrap().push(EmbeddedJob {
+            name: name.to_string(),
+            cron,
+            job,
+        });
+        Ok(())
+    }
+
+    async fn start(&self) -> Result<()> {
+        let runner = self.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let now = runner.clock.now();
+                let Some((index, at)) = runner.next_due(now) else {
+                    return;
+                };
+                tokio::time::sleep((at - now).to_std().unwrap_or_default()).await;
+                // A suspend during the sleep leaves this fire time in the past
+                if runner.clock.now() - at > Duration::seconds(1) {
+                    continue;
+                }
+
+                let (name, job) = {
+                    let jobs = runner.jobs.lock().unwrap();
+                    (jobs[index].name.clone(), Arc::clone(&jobs[index].job))
+                };
+                runner.firings.lock().unwrap().push(JobFiring { name, at });
+                job(at).await;
+            }
+        });
+        *self.task.lock().unwrap() = Some(task);
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+        Ok(())
+    }
+}
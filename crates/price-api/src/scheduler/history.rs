@@ -0,0 +1,336 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::fetcher::FetchSummary;
+use crate::storage::{PriceStore, StorageError};
+
+use super::runner::{next_fire_time, parse_cron};
+
+/// Zone counts from a fetch job run.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JobRunSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub no_data: usize,
+    pub records_stored: usize,
+}
+
+impl From<&FetchSummary> for JobRunSummary {
+    fn from(summary: &FetchSummary) -> Self {
+        Self {
+            succeeded: summary.succeeded,
+            failed: summary.failed,
+            no_data: summary.no_data,
+            records_stored: summary.total_prices_stored,
+        }
+    }
+}
+
+/// One completed run of a scheduled job.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JobRun {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// `success` or `failure`.
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<JobRunSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JobStatus {
+    pub name: String,
+    pub cron: String,
+    /// Paused jobs skip their firings on every replica until resumed.
+    pub paused: bool,
+    pub last_run: Option<JobRun>,
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+impl JobStatus {
+    /// First fire time after `now`.
+    pub fn next_fire_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        parse_cron(&self.cron).ok().and_then(|cron| next_fire_time(&cron, now))
+    }
+}
+
+/// When a registered job fires next.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JobSchedule {
+    pub name: String,
+    pub next_fire_at: Option<DateTime<Utc>>,
+}
+
+/// Registered scheduler jobs and the outcome of each one's latest run, kept
+/// in memory for the status endpoint. History starts empty on every restart.
+#[derive(Default)]
+pub struct JobHistory {
+    jobs: Mutex<Vec<JobStatus>>,
+    running: AtomicBool,
+    /// Where pauses are kept, so they hold on every replica and across
+    /// restarts; `None` keeps them in memory.
+    pauses: Option<Arc<dyn PriceStore>>,
+}
+
+impl JobHistory {
+    /// History whose pauses are stored in `store`.
+    pub fn with_pause_store(store: Arc<dyn PriceStore>) -> Self {
+        Self {
+            pauses: Some(store),
+            ..Self::default()
+        }
+    }
+
+    /// Whether the scheduler has started and not been shut down.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::Relaxed);
+    }
+
+    pub fn register(&self, name: &str, cron: &str) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if !jobs.iter().any(|job| job.name == name) {
+            jobs.push(JobStatus {
+                name: name.to_string(),
+                cron: cron.to_string(),
+                paused: false,
+                last_run: None,
+                last_success_at: None,
+            });
+        }
+    }
+
+    pub fn record_success(
+        &self,
+        name: &str,
+        started_at: DateTime<Utc>,
+        duration: Duration,
+        summary: Option<&FetchSummary>,
+    ) {
+        self.record(name, started_at, JobRun {
+            started_at,
+            duration_ms: duration.as_millis() as u64,
+            status: "success".to_string(),
+            error: None,
+            summary: summary.map(JobRunSummary::from),
+        });
+    }
+
+    /// Record a completed fetch run, as a failure when every zone failed.
+    pub fn record_fetch(&self, name: &str, started_at: DateTime<Utc>, duration: Duration, summary: &FetchSummary) {
+        if !summary.all_failed() {
+            return self.record_success(name, started_at, duration, Some(summary));
+        }
+        self.record(name, started_at, JobRun {
+            started_at,
+            duration_ms: duration.as_millis() as u64,
+            status: "failure".to_string(),
+            error: Some(format!("all {} zones failed", summary.failed)),
+            summary: Some(JobRunSummary::from(summary)),
+        });
+    }
+
+    pub fn record_failure(&self, name: &str, started_at: DateTime<Utc>, duration: Duration, error: &str) {
+        self.record(name, started_at, JobRun {
+            started_at,
+            duration_ms: duration.as_millis() as u64,
+            status: "failure".to_string(),
+            error: Some(error.to_string()),
+            summary: None,
+        });
+    }
+
+    /// Pause or resume `name`, returning its updated status, or `None` for
+    /// an unregistered job.
+    pub async fn set_paused(&self, name: &str, paused: bool) -> Result<Option<JobStatus>, StorageError> {
+        if self.job(name).is_none() {
+            return Ok(None);
+        }
+        if let Some(store) = &self.pauses {
+            store.set_job_paused(name, paused, Utc::now()).await?;
+        }
+        Ok(self.mark_paused(name, paused))
+    }
+
+    /// Whether `name` is paused, as stored when pauses are kept in the
+    /// database. Falls back to the last known state if the store fails.
+    pub async fn is_paused(&self, name: &str) -> bool {
+        if let Err(e) = self.refresh_pauses().await {
+            warn!(job = %name, error = %e, "Failed to read paused jobs, using the last known state");
+        }
+        self.job(name).is_some_and(|job| job.paused)
+    }
+
+    /// Reload which jobs are paused from the store, picking up pauses made
+    /// on other replicas.
+    pub async fn refresh_pauses(&self) -> Result<(), StorageError> {
+        let Some(store) = &self.pauses else {
+            return Ok(());
+        };
+        let paused = store.paused_jobs().await?;
+        for job in self.jobs.lock().unwrap().iter_mut() {
+            job.paused = paused.contains(&job.name);
+        }
+        Ok(())
+    }
+
+    fn mark_paused(&self, name: &str, paused: bool) -> Option<JobStatus> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs.iter_mut().find(|job| job.name == name)?;
+        job.paused = paused;
+        Some(job.clone())
+    }
+
+    fn record(&self, name: &str, started_at: DateTime<Utc>, run: JobRun) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.iter_mut().find(|job| job.name == name) {
+            if run.status == "success" {
+                job.last_success_at = Some(started_at);
+            }
+            job.last_run = Some(run);
+        }
+    }
+
+    /// Jobs in registration order.
+    pub fn jobs(&self) -> Vec<JobStatus> {
+        self.jobs.lock().unwrap().clone()
+    }
+
+    pub fn job(&self, name: &str) -> Option<JobStatus> {
+        self.jobs.lock().unwrap().iter().find(|job| job.name == name).cloned()
+    }
+
+    /// Each job's first fire time after `now`, in registration order.
+    pub fn next_runs(&self, now: DateTime<Utc>) -> Vec<JobSchedule> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|job| JobSchedule {
+                name: job.name.clone(),
+                next_fire_at: job.next_fire_at(now),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_failure_keeps_last_success() {
+        let history = JobHistory::default();
+        history.register("primary_fetch_13:00", "0 0 13 * * *");
+
+        let first = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let summary = FetchSummary {
+            succeeded: 2,
+            ..Default::default()
+        };
+        history.record_success("primary_fetch_13:00", first, Duration::from_millis(1500), Some(&summary));
+        let second = first + chrono::Duration::days(1);
+        history.record_failure("primary_fetch_13:00", second, Duration::from_millis(20), "database unavailable");
+        history.record_success("unregistered", second, Duration::ZERO, None);
+
+        let jobs = history.jobs();
+        assert_eq!(jobs.len(), 1);
+        let run = jobs[0].last_run.as_ref().unwrap();
+        assert_eq!((run.status.as_str(), run.error.as_deref()), ("failure", Some("database unavailable")));
+        assert_eq!(jobs[0].last_success_at, Some(first));
+    }
+
+    #[test]
+    fn test_fetch_with_every_zone_failed_is_a_failure() {
+        let history = JobHistory::default();
+        history.register("primary_fetch_13:00", "0 0 13 * * *");
+
+        let first = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let partial = FetchSummary {
+            succeeded: 1,
+            failed: 1,
+            ..Default::default()
+        };
+        history.record_fetch("primary_fetch_13:00", first, Duration::ZERO, &partial);
+        let second = first + chrono::Duration::days(1);
+        let failed = FetchSummary {
+            failed: 3,
+            ..Default::default()
+        };
+        history.record_fetch("primary_fetch_13:00", second, Duration::ZERO, &failed);
+
+        let job = history.job("primary_fetch_13:00").unwrap();
+        let run = job.last_run.as_ref().unwrap();
+        assert_eq!((run.status.as_str(), run.error.as_deref()), ("failure", Some("all 3 zones failed")));
+        assert_eq!(run.summary.as_ref().unwrap().failed, 3);
+        assert_eq!(job.last_success_at, Some(first));
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_registered_jobs_only() {
+        let history = JobHistory::default();
+        history.register("retention_cleanup", "0 30 3 * * *");
+
+        assert!(history.set_paused("retention_cleanup", true).await.unwrap().unwrap().paused);
+        assert!(history.is_paused("retention_cleanup").await);
+        assert!(history.set_paused("unregistered", true).await.unwrap().is_none());
+        assert!(!history.is_paused("unregistered").await);
+
+        assert!(!history.set_paused("retention_cleanup", false).await.unwrap().unwrap().paused);
+        assert!(!history.is_paused("retention_cleanup").await);
+    }
+
+    #[tokio::test]
+    async fn test_stored_pauses_are_shared_and_survive_restarts() {
+        let store: Arc<dyn PriceStore> = Arc::new(crate::storage::InMemoryPriceStore::new());
+        let replica = |store: &Arc<dyn PriceStore>| {
+            let history = JobHistory::with_pause_store(Arc::clone(store));
+            history.register("retention_cleanup", "0 30 3 * * *");
+            history
+        };
+        let first = replica(&store);
+        let second = replica(&store);
+
+        first.set_paused("retention_cleanup", true).await.unwrap();
+        assert!(second.is_paused("retention_cleanup").await);
+        let restarted = replica(&store);
+        restarted.refresh_pauses().await.unwrap();
+        assert!(restarted.job("retention_cleanup").unwrap().paused);
+
+        second.set_paused("retention_cleanup", false).await.unwrap();
+        assert!(!first.is_paused("retention_cleanup").await);
+        assert!(store.paused_jobs().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_next_runs_follow_oslo_time() {
+        let history = JobHistory::default();
+        history.register("primary_fetch_13:00", "0 0 13 * * *");
+        history.register("retention_cleanup", "0 30 3 * * *");
+
+        // 13:00 in Oslo is 11:00 UTC in summer
+        let now = Utc.with_ymd_and_hms(2025, 7, 1, 12, 0, 0).unwrap();
+        let next: Vec<_> = history.next_runs(now).into_iter().map(|job| job.next_fire_at).collect();
+        assert_eq!(
+            next,
+            vec![
+                Some(Utc.with_ymd_and_hms(2025, 7, 2, 11, 0, 0).unwrap()),
+                Some(Utc.with_ymd_and_hms(2025, 7, 2, 1, 30, 0).unwrap()),
+            ]
+        );
+        assert!(!history.is_running());
+    }
+}
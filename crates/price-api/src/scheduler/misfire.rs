@@ -0,0 +1,239 @@
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+use chrono_tz::Europe::Oslo;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::clock::Clock;
+use crate::config::MisfirePolicy;
+use crate::entsoe::EntsoePriceSource;
+use crate::fetcher::FetcherService;
+use crate::metrics;
+use crate::storage::PriceStore;
+
+/// Local (Europe/Oslo) hour of the primary fetch job.
+pub(super) const PRIMARY_FETCH_HOUR: u32 = 13;
+
+/// How often the watchdog compares wall-clock and monotonic time.
+const WATCHDOG_INTERVAL: StdDuration = StdDuration::from_secs(60);
+
+/// Wall-clock time that may pass beyond the monotonic clock between two
+/// watchdog ticks before we assume the process was suspended.
+const SUSPEND_THRESHOLD: Duration = Duration::minutes(2);
+
+/// Oldest catch-up window for the backfill policy.
+const MAX_BACKFILL_DAYS: i64 = 7;
+
+fn primary_fetch_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(PRIMARY_FETCH_HOUR, 0, 0).unwrap()
+}
+
+/// Primary fetch time on the local day of `now`, from which the next day's
+/// prices are expected to be stored.
+pub fn todays_primary_fetch(now: DateTime<Utc>) -> DateTime<Utc> {
+    let local_date = now.with_timezone(&Oslo).date_naive();
+    Oslo.from_local_datetime(&local_date.and_time(primary_fetch_time()))
+        .earliest()
+        .map_or(now, |fire| fire.with_timezone(&Utc))
+}
+
+/// Most recent primary fetch time at or before `now`.
+fn last_scheduled_fire(now: DateTime<Utc>) -> DateTime<Utc> {
+    let local_date = now.with_timezone(&Oslo).date_naive();
+    [local_date, local_date.pred_opt().unwrap()]
+        .into_iter()
+        .filter_map(|date| Oslo.from_local_datetime(&date.and_time(primary_fetch_time())).earliest())
+        .map(|fire| fire.with_timezone(&Utc))
+        .find(|fire| *fire <= now)
+        .unwrap()
+}
+
+/// The primary fire time that was missed, if no full fetch has been recorded
+/// since the most recent one.
+pub fn detect_misfire(now: DateTime<Utc>, last_execution: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+    let expected = last_scheduled_fire(now);
+    match last_execution {
+        Some(last) if last >= expected => None,
+        _ => Some(expected),
+    }
+}
+
+/// Compare expected and recorded executions and catch up according to `policy`.
+pub async fn run_misfire_check<S: EntsoePriceSource + 'static>(
+    fetcher: &FetcherService<S>,
+    repository: &dyn PriceStore,
+    clock: &dyn Clock,
+    policy: MisfirePolicy,
+    trigger: &str,
+) -> Result<()> {
+    // Full runs log a summary without a zone; per-zone entries do not count.
+    let last_execution = repository.get_last_fetch_run().await?;
+
+    let now = clock.now();
+    let Some(missed) = detect_misfire(now, last_execution) else {
+        info!(trigger = trigger, "No missed scheduler executions");
+        return Ok(());
+    };
+
+    metrics::record_scheduler_misfire(trigger, policy.as_str());
+    warn!(
+        trigger = trigger,
+        missed_at = %missed,
+        last_execution = ?last_execution,
+        policy = policy.as_str(),
+        "Primary fetch missed its scheduled time"
+    );
+
+    let job_name = "misfire_catch_up";
+    let start;
+    let result = match policy {
+        MisfirePolicy::Skip => return Ok(()),
+        MisfirePolicy::RunOnce => {
+            super::wait_for_maintenance(fetcher, clock, job_name).await;
+            start = Instant::now();
+            fetcher.fetch_all_prices().await.and_then(|summary| {
+                if summary.all_failed() {
+                    anyhow::bail!("all {} zones failed", summary.failed);
+                }
+                Ok(summary.total_prices_stored)
+            })
+        }
+        MisfirePolicy::Backfill => {
+            super::wait_for_maintenance(fetcher, clock, job_name).await;
+            start = Instant::now();
+            let today = now.with_timezone(&Oslo).date_naive();
+            let earliest = today - Duration::days(MAX_BACKFILL_DAYS);
+            let start_date = last_execution
+                .map(|last| last.with_timezone(&Oslo).date_naive())
+                .unwrap_or(earliest)
+                .max(earliest);
+            // Run by the task worker, which retries zones that still fail
+            let end_date = today.succ_opt().unwrap();
+            match fetcher.enqueue_gap_repair(start_date, end_date).await {
+                Ok(task) => {
+                    metrics::record_scheduler_job_execution(job_name, "success");
+                    info!(
+                        trigger = trigger,
+                        start = %start_date,
+                        end = %end_date,
+                        task_id = ?task.map(|task| task.id),
+                        "Misfire gap repair queued"
+                    );
+                    return Ok(());
+                }
+                Err(e) => Err(e),
+            }
+        }
+    };
+
+    metrics::record_scheduler_job_duration(job_name, start.elapsed());
+    match result {
+        Ok(stored) => {
+            metrics::record_scheduler_job_execution(job_name, "success");
+            info!(trigger = trigger, prices_stored = stored, "Misfire catch-up completed");
+            Ok(())
+        }
+        Err(e) => {
+            metrics::record_scheduler_job_execution(job_name, "failure");
+            Err(e)
+        }
+    }
+}
+
+/// Detect resume from suspend: the monotonic clock does not advance while
+/// the host sleeps, so a wall-clock jump larger than the tick interval means
+/// scheduled jobs may have been skipped. The monotonic side is tokio's clock,
+/// so paused time in tests behaves like a host that never slept.
+pub fn spawn_resume_watchdog<S: EntsoePriceSource + 'static>(
+    fetcher: Arc<FetcherService<S>>,
+    repository: Arc<dyn PriceStore>,
+    clock: Arc<dyn Clock>,
+    policy: MisfirePolicy,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(WATCHDOG_INTERVAL);
+        interval.tick().await;
+        let mut last_wall = clock.now();
+        let mut last_mono = tokio::time::Instant::now();
+
+        loop {
+            interval.tick().await;
+            let wall_elapsed = clock.now() - last_wall;
+            let mono_elapsed = Duration::from_std(last_mono.elapsed()).unwrap_or(Duration::zero());
+            last_wall = clock.now();
+            last_mono = tokio::time::Instant::now();
+
+            if wall_elapsed - mono_elapsed > SUSPEND_THRESHOLD {
+                warn!(
+                    suspended_secs = (wall_elapsed - mono_elapsed).num_seconds(),
+                    "Detected resume after suspend, checking for missed jobs"
+                );
+                if let Err(e) = run_misfire_check(&fetcher, repository.as_ref(), clock.as_ref(), policy, "resume").await {
+                    error!(error = %e, "Misfire catch-up after resume failed");
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_last_scheduled_fire_uses_oslo_time() {
+        // 13:00 Oslo is 12:00 UTC in winter and 11:00 UTC in summer
+        assert_eq!(last_scheduled_fire(utc(2025, 1, 15, 12, 30)), utc(2025, 1, 15, 12, 0));
+        assert_eq!(last_scheduled_fire(utc(2025, 1, 15, 11, 59)), utc(2025, 1, 14, 12, 0));
+        assert_eq!(last_scheduled_fire(utc(2025, 7, 15, 11, 0)), utc(2025, 7, 15, 11, 0));
+    }
+
+    #[test]
+    fn test_misfire_when_no_execution_since_last_fire() {
+        let now = utc(2025, 1, 15, 14, 0);
+        assert_eq!(detect_misfire(now, None), Some(utc(2025, 1, 15, 12, 0)));
+        assert_eq!(
+            detect_misfire(now, Some(utc(2025, 1, 14, 12, 0))),
+            Some(utc(2025, 1, 15, 12, 0))
+        );
+    }
+
+    #[test]
+    fn test_no_misfire_after_recorded_execution() {
+        let now = utc(2025, 1, 15, 14, 0);
+        assert_eq!(detect_misfire(now, Some(utc(2025, 1, 15, 12, 0))), None);
+        // Before today's fire time, yesterday's run is sufficient
+        assert_eq!(detect_misfire(utc(2025, 1, 15, 9, 0), Some(utc(2025, 1, 14, 12, 1))), None);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_policy_queues_one_gap_repair_up_to_tomorrow() {
+        use crate::clock::FixedClock;
+        use crate::entsoe::CannedEntsoeSource;
+        use crate::storage::InMemoryPriceStore;
+
+        let store = Arc::new(InMemoryPriceStore::new());
+        let clock = FixedClock::new(utc(2025, 1, 15, 14, 0));
+        let fetcher = FetcherService::new(Arc::new(CannedEntsoeSource::new()), store.clone())
+            .with_clock(Arc::new(FixedClock::new(clock.now())));
+
+        for trigger in ["startup", "resume"] {
+            run_misfire_check(&fetcher, store.as_ref(), &clock, MisfirePolicy::Backfill, trigger)
+                .await
+                .unwrap();
+        }
+
+        let tasks = store.list_tasks(None, 10).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].kind, crate::tasks::GAP_REPAIR);
+        let payload: serde_json::Value = serde_json::from_str(&tasks[0].payload).unwrap();
+        assert_eq!(payload, serde_json::json!({ "start": "2025-01-08", "end": "2025-01-16" }));
+    }
+}
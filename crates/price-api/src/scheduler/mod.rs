@@ -0,0 +1,748 @@
+#[cfg(any(test, feature = "test-utils"))]
+mod embedded;
+mod history;
+mod misfire;
+mod retention;
+mod runner;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use futures::future::BoxFuture;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::clock::{Clock, SystemClock};
+use crate::config::{RetentionConfig, SchedulerConfig};
+use crate::entsoe::{EntsoeClient, EntsoePriceSource};
+use crate::fetcher::{FetchSummary, FetcherService, JobLocked};
+use crate::metrics;
+use crate::storage::{PriceStore, PRICE_PARTITION_MONTHS_AHEAD};
+use crate::tasks;
+
+use retention::RetentionPayload;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use embedded::{EmbeddedJobRunner, JobFiring, VirtualClock};
+pub use history::{JobHistory, JobRun, JobRunSummary, JobSchedule, JobStatus};
+pub use misfire::todays_primary_fetch;
+pub use retention::RetentionTask;
+pub use runner::{CronJobRunner, JobFn, JobRunner};
+
+pub struct PriceFetchScheduler<S = EntsoeClient> {
+    runner: Box<dyn JobRunner>,
+    clock: Arc<dyn Clock>,
+    fetcher: Arc<FetcherService<S>>,
+    repository: Arc<dyn PriceStore>,
+    config: SchedulerConfig,
+    retention: RetentionConfig,
+    history: Arc<JobHistory>,
+    watchdog: Option<JoinHandle<()>>,
+}
+
+impl PriceFetchScheduler {
+    pub async fn new(
+        fetcher: Arc<FetcherService>,
+        repository: Arc<dyn PriceStore>,
+        config: SchedulerConfig,
+        retention: RetentionConfig,
+    ) -> Result<Self> {
+        let runner = CronJobRunner::new().await?;
+        Ok(Self::with_runner(
+            fetcher,
+            repository,
+            config,
+            retention,
+            Box::new(runner),
+            Arc::new(SystemClock),
+        ))
+    }
+}
+
+impl<S: EntsoePriceSource + 'static> PriceFetchScheduler<S> {
+    /// Schedule jobs on `runner`, reading wall-clock time from `clock`. Tests
+    /// pass an `EmbeddedJobRunner` and `VirtualClock` to run on tokio's
+    /// paused time.
+    pub fn with_runner(
+        fetcher: Arc<FetcherService<S>>,
+        repository: Arc<dyn PriceStore>,
+        config: SchedulerConfig,
+        retention: RetentionConfig,
+        runner: Box<dyn JobRunner>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        let history = Arc::new(JobHistory::with_pause_store(Arc::clone(&repository)));
+        Self {
+            runner,
+            clock,
+            fetcher,
+            repository,
+            config,
+            retention,
+            history,
+            watchdog: None,
+        }
+    }
+
+    /// Registered jobs and their latest runs, shared with the status endpoint.
+    pub fn history(&self) -> Arc<JobHistory> {
+        Arc::clone(&self.history)
+    }
+
+    async fn add_primary_fetch_job(&self) -> Result<()> {
+        let fetcher = Arc::clone(&self.fetcher);
+        let clock = Arc::clone(&self.clock);
+        let history = Arc::clone(&self.history);
+        let job_name = "primary_fetch_13:00";
+
+        let cron_expr = format!("0 0 {} * * *", misfire::PRIMARY_FETCH_HOUR);
+        let job: JobFn = Arc::new(move |_fired_at| -> BoxFuture<'static, ()> {
+            let fetcher = Arc::clone(&fetcher);
+            let clock = Arc::clone(&clock);
+            let history = Arc::clone(&history);
+            Box::pin(async move {
+                if skip_paused(&history, job_name).await {
+                    return;
+                }
+                wait_for_maintenance(&fetcher, clock.as_ref(), job_name).await;
+                let start = Instant::now();
+                let started_at = clock.now();
+                info!("Starting primary daily fetch job (13:00 CET)");
+                match fetcher.fetch_all_prices().await {
+                    Ok(summary) => {
+                        metrics::record_scheduler_job_execution(job_name, fetch_status(&summary));
+                        metrics::record_scheduler_job_duration(job_name, start.elapsed());
+                        history.record_fetch(job_name, started_at, start.elapsed(), &summary);
+                        info!(
+                            succeeded = summary.succeeded,
+                            failed = summary.failed,
+                            no_data = summary.no_data,
+                            total_prices = summary.total_prices_stored,
+                            "Primary fetch job completed"
+                        );
+                    }
+                    Err(e) if e.is::<JobLocked>() => record_locked(job_name, &e),
+                    Err(e) => {
+                        metrics::record_scheduler_job_execution(job_name, "failure");
+                        metrics::record_scheduler_job_duration(job_name, start.elapsed());
+                        history.record_failure(job_name, started_at, start.elapsed(), &e.to_string());
+                        error!(error = %e, "Primary fetch job failed");
+                    }
+                }
+            })
+        });
+
+        self.runner.add(job_name, &cron_expr, job).await?;
+        self.history.register(job_name, &cron_expr);
+        info!("Added primary fetch job at 13:00 CET");
+        Ok(())
+    }
+
+    async fn add_conditional_fetch_job(&self, cron_expr: &str, job_name: &str) -> Result<()> {
+        let fetcher = Arc::clone(&self.fetcher);
+        let clock = Arc::clone(&self.clock);
+        let history = Arc::clone(&self.history);
+        let name = job_name.to_string();
+
+        let job: JobFn = Arc::new(move |_fired_at| -> BoxFuture<'static, ()> {
+            let fetcher = Arc::clone(&fetcher);
+            let clock = Arc::clone(&clock);
+            let history = Arc::clone(&history);
+            let job_name = name.clone();
+            Box::pin(async move {
+                if skip_paused(&history, &job_name).await {
+                    return;
+                }
+                wait_for_maintenance(&fetcher, clock.as_ref(), &job_name).await;
+                let start = Instant::now();
+                let started_at = clock.now();
+                info!(job = %job_name, "Starting conditional fetch job");
+                match fetcher.fetch_tomorrow_if_missing().await {
+                    Ok(summary) => {
+                        metrics::record_scheduler_job_execution(&job_name, fetch_status(&summary));
+                        metrics::record_scheduler_job_duration(&job_name, start.elapsed());
+                        history.record_fetch(&job_name, started_at, start.elapsed(), &summary);
+                        if summary.succeeded == 0 && summary.no_data == 0 && summary.failed == 0 {
+                            info!(job = %job_name, "Conditional fetch skipped - data already exists");
+                        } else {
+                            info!(
+                                job = %job_name,
+                                succeeded = summary.succeeded,
+                                failed = summary.failed,
+                                no_data = summary.no_data,
+                                total_prices = summary.total_prices_stored,
+                                "Conditional fetch job completed"
+                            );
+                        }
+                    }
+                    Err(e) if e.is::<JobLocked>() => record_locked(&job_name, &e),
+                    Err(e) => {
+                        metrics::record_scheduler_job_execution(&job_name, "failure");
+                        metrics::record_scheduler_job_duration(&job_name, start.elapsed());
+                        history.record_failure(&job_name, started_at, start.elapsed(), &e.to_string());
+                        error!(job = %job_name, error = %e, "Conditional fetch job failed");
+                    }
+                }
+            })
+        });
+
+        self.runner.add(job_name, cron_expr, job).await?;
+        self.history.register(job_name, cron_expr);
+        info!(job = %job_name, cron = %cron_expr, "Added conditional fetch job");
+        Ok(())
+    }
+
+    /// Fetch yesterday's and today's actuals; ENTSOE publishes them with a
+    /// delay, so yesterday is refetched to complete it.
+    async fn add_actuals_job(&self, cron_expr: &str, actuals: Actuals) -> Result<()> {
+        let fetcher = Arc::clone(&self.fetcher);
+        let clock = Arc::clone(&self.clock);
+        let history = Arc::clone(&self.history);
+
+        let job: JobFn = Arc::new(move |fired_at| -> BoxFuture<'static, ()> {
+            let fetcher = Arc::clone(&fetcher);
+            let clock = Arc::clone(&clock);
+            let history = Arc::clone(&history);
+            Box::pin(async move {
+                let job_name = actuals.job_name();
+                if skip_paused(&history, job_name).await {
+                    return;
+                }
+                wait_for_maintenance(&fetcher, clock.as_ref(), job_name).await;
+                let start = Instant::now();
+                let started_at = clock.now();
+                info!(job = job_name, "Starting actuals fetch job");
+
+                let today = fired_at.date_naive();
+                let mut result = actuals.fetch(&fetcher, today - Duration::days(1)).await;
+                if let Ok(summary) = &mut result {
+                    match actuals.fetch(&fetcher, today).await {
+                        Ok(today_summary) => summary.merge(today_summary),
+                        Err(e) => result = Err(e),
+                    }
+                }
+
+                match result {
+                    Ok(summary) => {
+                        metrics::record_scheduler_job_duration(job_name, start.elapsed());
+                        metrics::record_scheduler_job_execution(job_name, fetch_status(&summary));
+                        history.record_fetch(job_name, started_at, start.elapsed(), &summary);
+                        info!(
+                            job = job_name,
+                            succeeded = summary.succeeded,
+                            failed = summary.failed,
+                            no_data = summary.no_data,
+                            values_stored = summary.total_prices_stored,
+                            "Actuals fetch job completed"
+                        );
+                    }
+                    Err(e) if e.is::<JobLocked>() => record_locked(job_name, &e),
+                    Err(e) => {
+                        metrics::record_scheduler_job_execution(job_name, "failure");
+                        metrics::record_scheduler_job_duration(job_name, start.elapsed());
+                        history.record_failure(job_name, started_at, start.elapsed(), &e.to_string());
+                        error!(job = job_name, error = %e, "Actuals fetch job failed");
+                    }
+                }
+            })
+        });
+
+        self.runner.add(actuals.job_name(), cron_expr, job).await?;
+        self.history.register(actuals.job_name(), cron_expr);
+        info!(job = actuals.job_name(), cron = %cron_expr, "Added actuals fetch job");
+        Ok(())
+    }
+
+    /// Queue a retention run for the task worker, once per day across
+    /// replicas.
+    async fn add_retention_job(&self) -> Result<()> {
+        let fetcher = Arc::clone(&self.fetcher);
+        let history = Arc::clone(&self.history);
+        let cron_expr = self.retention.cleanup_cron.clone();
+        let job_name = "retention_cleanup";
+
+        let job: JobFn = Arc::new(move |fired_at| -> BoxFuture<'static, ()> {
+            let fetcher = Arc::clone(&fetcher);
+            let history = Arc::clone(&history);
+            Box::pin(async move {
+                if skip_paused(&history, job_name).await {
+                    return;
+                }
+                let start = Instant::now();
+                let dedupe_key = format!("{}:{}", tasks::RETENTION, fired_at.date_naive());
+                let queued = fetcher
+                    .enqueue_task(tasks::RETENTION, &RetentionPayload { now: fired_at }, Some(dedupe_key))
+                    .await;
+                metrics::record_scheduler_job_duration(job_name, start.elapsed());
+                match queued {
+                    Ok(task) => {
+                        metrics::record_scheduler_job_execution(job_name, "success");
+                        history.record_success(job_name, fired_at, start.elapsed(), None);
+                        info!(task_id = ?task.map(|task| task.id), "Retention cleanup queued");
+                    }
+                    Err(e) => {
+                        metrics::record_scheduler_job_execution(job_name, "failure");
+                        history.record_failure(job_name, fired_at, start.elapsed(), &e.to_string());
+                        error!(error = %e, "Failed to queue retention cleanup");
+                    }
+                }
+            })
+        });
+
+        self.runner.add(job_name, &cron_expr, job).await?;
+        self.history.register(job_name, &cron_expr);
+        info!(cron = %cron_expr, "Added retention cleanup job");
+        Ok(())
+    }
+
+    /// Retry the zone/dates queued after failed fetches, as they come due.
+    async fn add_retry_queue_job(&self) -> Result<()> {
+        let fetcher = Arc::clone(&self.fetcher);
+        let clock = Arc::clone(&self.clock);
+        let history = Arc::clone(&self.history);
+        let cron_expr = self.config.retry_queue.cron.clone();
+        let job_name = "fetch_retry_queue";
+
+        let job: JobFn = Arc::new(move |fired_at| -> BoxFuture<'static, ()> {
+            let fetcher = Arc::clone(&fetcher);
+            let clock = Arc::clone(&clock);
+            let history = Arc::clone(&history);
+            Box::pin(async move {
+                if skip_paused(&history, job_name).await {
+                    return;
+                }
+                wait_for_maintenance(&fetcher, clock.as_ref(), job_name).await;
+                let start = Instant::now();
+                let drain = fetcher.drain_retry_queue();
+                match fetcher.run_locked(job_name, fired_at.date_naive(), drain).await {
+                    Ok(summary) => {
+                        metrics::record_scheduler_job_execution(job_name, "success");
+                        metrics::record_scheduler_job_duration(job_name, start.elapsed());
+                        history.record_success(job_name, fired_at, start.elapsed(), None);
+                        if summary.succeeded + summary.rescheduled + summary.locked + summary.failed > 0 || summary.expired > 0 {
+                            info!(
+                                succeeded = summary.succeeded,
+                                rescheduled = summary.rescheduled,
+                                expired = summary.expired,
+                                locked = summary.locked,
+                                failed = summary.failed,
+                                "Fetch retry queue job completed"
+                            );
+                        }
+                    }
+                    Err(e) if e.is::<JobLocked>() => record_locked(job_name, &e),
+                    Err(e) => {
+                        metrics::record_scheduler_job_execution(job_name, "failure");
+                        metrics::record_scheduler_job_duration(job_name, start.elapsed());
+                        history.record_failure(job_name, fired_at, start.elapsed(), &e.to_string());
+                        error!(error = %e, "Fetch retry queue job failed");
+                    }
+                }
+            })
+        });
+
+        self.runner.add(job_name, &cron_expr, job).await?;
+        self.history.register(job_name, &cron_expr);
+        info!(cron = %cron_expr, "Added fetch retry queue job");
+        Ok(())
+    }
+
+    /// Keep the coming months' price partitions created so upserts never
+    /// land in the default partition.
+    async fn add_partition_job(&self) -> Result<()> {
+        let repository = Arc::clone(&self.repository);
+        let history = Arc::clone(&self.history);
+        let cron_expr = "0 0 3 * * *";
+        let job_name = "price_partitions";
+
+        let job: JobFn = Arc::new(move |fired_at| -> BoxFuture<'static, ()> {
+            let repository = Arc::clone(&repository);
+            let history = Arc::clone(&history);
+            Box::pin(async move {
+                if skip_paused(&history, job_name).await {
+                    return;
+                }
+                let start = Instant::now();
+                let result = repository
+                    .ensure_price_partitions(fired_at.date_naive(), PRICE_PARTITION_MONTHS_AHEAD)
+                    .await;
+                metrics::record_scheduler_job_duration(job_name, start.elapsed());
+                match result {
+                    Ok(created) => {
+                        metrics::record_scheduler_job_execution(job_name, "success");
+                        history.record_success(job_name, fired_at, start.elapsed(), None);
+                        if !created.is_empty() {
+                            info!(partitions = ?created, "Price partitions created");
+                        }
+                    }
+                    Err(e) => {
+                        metrics::record_scheduler_job_execution(job_name, "failure");
+                        history.record_failure(job_name, fired_at, start.elapsed(), &e.to_string());
+                        error!(error = %e, "Price partition job failed");
+                    }
+                }
+            })
+        });
+
+        self.runner.add(job_name, cron_expr, job).await?;
+        self.history.register(job_name, cron_expr);
+        info!(cron = %cron_expr, "Added price partition job");
+        Ok(())
+    }
+
+    pub async fn start(&mut self) -> Result<()> {
+        if self.config.enabled {
+            self.add_fetch_jobs().await?;
+        } else {
+            info!("Price fetch jobs disabled in configuration");
+        }
+
+        if self.retention.enabled {
+            self.add_retention_job().await?;
+        } else {
+            info!("Retention cleanup disabled in configuration");
+        }
+
+        if let Err(e) = self.history.refresh_pauses().await {
+            warn!(error = %e, "Failed to read paused scheduler jobs, starting with none paused");
+        }
+        self.runner.start().await?;
+        self.history.set_running(true);
+        info!("Price fetch scheduler started");
+
+        if self.config.enabled {
+            self.start_misfire_detection();
+        }
+
+        Ok(())
+    }
+
+    async fn add_fetch_jobs(&self) -> Result<()> {
+        self.add_primary_fetch_job().await?;
+        
+        self.add_conditional_fetch_job("0 0 14 * * *", "retry_1_14:00").await?;
+        self.add_conditional_fetch_job("0 0 15 * * *", "retry_2_15:00").await?;
+        self.add_conditional_fetch_job("0 0 16 * * *", "retry_3_16:00").await?;
+
+        if let Some(cron_expr) = self.config.generation_cron.clone() {
+            self.add_actuals_job(&cron_expr, Actuals::Generation).await?;
+        }
+        if let Some(cron_expr) = self.config.load_cron.clone() {
+            self.add_actuals_job(&cron_expr, Actuals::Load).await?;
+        }
+        if let Some(cron_expr) = self.config.flows_cron.clone() {
+            self.add_actuals_job(&cron_expr, Actuals::Flows).await?;
+        }
+
+        self.add_partition_job().await?;
+
+        if self.config.retry_queue.enabled {
+            self.add_retry_queue_job().await?;
+        }
+        Ok(())
+    }
+
+    /// Catch up on a missed primary fetch now, and again whenever the host
+    /// resumes from suspend.
+    fn start_misfire_detection(&mut self) {
+        let policy = self.config.misfire_policy;
+        let fetcher = Arc::clone(&self.fetcher);
+        let repository = Arc::clone(&self.repository);
+        let clock = Arc::clone(&self.clock);
+        tokio::spawn(async move {
+            if let Err(e) = misfire::run_misfire_check(&fetcher, repository.as_ref(), clock.as_ref(), policy, "startup").await {
+                error!(error = %e, "Misfire catch-up on startup failed");
+            }
+        });
+
+        self.watchdog = Some(misfire::spawn_resume_watchdog(
+            Arc::clone(&self.fetcher),
+            Arc::clone(&self.repository),
+            Arc::clone(&self.clock),
+            policy,
+        ));
+    }
+
+    pub async fn shutdown(mut self) -> Result<()> {
+        if let Some(watchdog) = self.watchdog.take() {
+            watchdog.abort();
+        }
+        self.runner.shutdown().await?;
+        self.history.set_running(false);
+        info!("Price fetch scheduler stopped");
+        Ok(())
+    }
+}
+
+/// Data ENTSOE publishes after delivery rather than ahead of it.
+#[derive(Debug, Clone, Copy)]
+enum Actuals {
+    Generation,
+    Load,
+    Flows,
+}
+
+impl Actuals {
+    fn job_name(self) -> &'static str {
+        match self {
+            Actuals::Generation => "generation_fetch",
+            Actuals::Load => "load_fetch",
+            Actuals::Flows => "flows_fetch",
+        }
+    }
+
+    /// Fetch `date` under the job's lock for that date.
+    async fn fetch<S: EntsoePriceSource + 'static>(self, fetcher: &FetcherService<S>, date: NaiveDate) -> Result<FetchSummary> {
+        let fetch = async move {
+            match self {
+                Actuals::Generation => fetcher.fetch_generation_all_zones(date).await,
+                Actuals::Load => fetcher.fetch_load_all_zones(date).await,
+                Actuals::Flows => fetcher.fetch_flows_all_pairs(date).await,
+            }
+        };
+        fetcher.run_locked(self.job_name(), date, fetch).await
+    }
+}
+
+/// Whether `job_name` is paused, counting the skipped firing if so.
+async fn skip_paused(history: &JobHistory, job_name: &str) -> bool {
+    if !history.is_paused(job_name).await {
+        return false;
+    }
+    metrics::record_scheduler_job_execution(job_name, "paused");
+    info!(job = %job_name, "Job paused, skipping firing");
+    true
+}
+
+/// Metric status of a completed fetch run; a run where every zone failed
+/// counts as a failure.
+fn fetch_status(summary: &FetchSummary) -> &'static str {
+    if summary.all_failed() {
+        "failure"
+    } else {
+        "success"
+    }
+}
+
+/// Count a firing skipped because another run holds the job's lock.
+fn record_locked(job_name: &str, error: &anyhow::Error) {
+    metrics::record_scheduler_job_execution(job_name, "locked");
+    info!(job = %job_name, reason = %error, "Job already running elsewhere, skipping firing");
+}
+
+/// Hold a fetch job until any ENTSOE maintenance window covering now has
+/// ended, counting each deferral.
+async fn wait_for_maintenance<S: EntsoePriceSource + 'static>(
+    fetcher: &FetcherService<S>,
+    clock: &dyn Clock,
+    job_name: &str,
+) {
+    while let Some(window) = fetcher.client().maintenance_window(clock.now()) {
+        metrics::record_scheduler_job_deferred(job_name);
+        warn!(
+            job = %job_name,
+            until = %window.end,
+            reason = window.reason.as_deref().unwrap_or(""),
+            "ENTSOE maintenance window active, deferring job"
+        );
+        let wait = (window.end - clock.now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::*;
+    use crate::config::{MisfirePolicy, RetryQueueConfig};
+    use crate::entsoe::CannedEntsoeSource;
+    use crate::models::Price;
+    use crate::storage::InMemoryPriceStore;
+
+    fn store() -> Arc<InMemoryPriceStore> {
+        Arc::new(InMemoryPriceStore::with_zones(vec![
+            InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo"),
+            InMemoryPriceStore::zone("NO2", "NO", "Europe/Oslo"),
+        ]))
+    }
+
+    /// Start a scheduler on an embedded runner with its virtual clock at `start`.
+    async fn start_scheduler(
+        store: Arc<InMemoryPriceStore>,
+        policy: MisfirePolicy,
+        start: DateTime<Utc>,
+    ) -> (PriceFetchScheduler<CannedEntsoeSource>, EmbeddedJobRunner, Arc<VirtualClock>) {
+        start_configured_scheduler(store, true, policy, start).await
+    }
+
+    async fn start_configured_scheduler(
+        store: Arc<InMemoryPriceStore>,
+        fetch_jobs: bool,
+        policy: MisfirePolicy,
+        start: DateTime<Utc>,
+    ) -> (PriceFetchScheduler<CannedEntsoeSource>, EmbeddedJobRunner, Arc<VirtualClock>) {
+        let clock = Arc::new(VirtualClock::new(start));
+        let runner = EmbeddedJobRunner::new(Arc::clone(&clock));
+        let fetcher = Arc::new(
+            FetcherService::new(Arc::new(CannedEntsoeSource::new()), store.clone()).with_clock(clock.clone()),
+        );
+        let mut scheduler = PriceFetchScheduler::with_runner(
+            fetcher,
+            store,
+            SchedulerConfig {
+                enabled: fetch_jobs,
+                fetch_times_cet: Vec::new(),
+                misfire_policy: policy,
+                generation_cron: None,
+                load_cron: None,
+                flows_cron: None,
+                job_lock_ttl_seconds: 1800,
+                retry_queue: RetryQueueConfig {
+                    enabled: false,
+                    cron: "0 */5 * * * *".to_string(),
+                    initial_backoff_seconds: 300,
+                    max_backoff_seconds: 3600,
+                    expiry_hours: 48,
+                },
+            },
+            RetentionConfig {
+                enabled: true,
+                price_retention_days: 1095,
+                fetch_log_retention_days: 90,
+                cleanup_cron: "0 30 3 * * *".to_string(),
+            },
+            Box::new(runner.clone()),
+            clock.clone(),
+        );
+        scheduler.start().await.unwrap();
+        (scheduler, runner, clock)
+    }
+
+    /// Store a full day of day-ahead prices for `zone` on the real next UTC
+    /// day, which is what the conditional jobs check.
+    async fn store_tomorrow(store: &InMemoryPriceStore, zone: &str, today: DateTime<Utc>) {
+        let tomorrow = (today + Duration::days(1)).date_naive();
+        let prices: Vec<Price> = (0..24)
+            .map(|hour| {
+                Price::from_mwh(
+                    tomorrow.and_hms_opt(hour, 0, 0).unwrap().and_utc(),
+                    zone.to_string(),
+                    40.0,
+                    "PT60M".to_string(),
+                )
+            })
+            .collect();
+        store.upsert_prices(&prices).await.unwrap();
+    }
+
+    fn full_runs(store: &InMemoryPriceStore) -> usize {
+        store.fetch_logs().iter().filter(|log| log.bidding_zone.is_none()).count()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_jobs_fire_at_oslo_times() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 12, 30, 0).unwrap();
+        let (scheduler, runner, _clock) = start_scheduler(store(), MisfirePolicy::Skip, start).await;
+
+        tokio::time::sleep(std::time::Duration::from_secs(24 * 3600)).await;
+
+        let firings: Vec<(String, DateTime<Utc>)> = runner.firings().into_iter().map(|f| (f.name, f.at)).collect();
+        let at = |d: u32, h: u32, m: u32| Utc.with_ymd_and_hms(2025, 1, d, h, m, 0).unwrap();
+        assert_eq!(
+            firings,
+            vec![
+                ("retry_1_14:00".to_string(), at(15, 13, 0)),
+                ("retry_2_15:00".to_string(), at(15, 14, 0)),
+                ("retry_3_16:00".to_string(), at(15, 15, 0)),
+                ("price_partitions".to_string(), at(16, 2, 0)),
+                ("retention_cleanup".to_string(), at(16, 2, 30)),
+                ("primary_fetch_13:00".to_string(), at(16, 12, 0)),
+            ]
+        );
+        scheduler.shutdown().await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retention_runs_with_fetch_jobs_disabled() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 12, 30, 0).unwrap();
+        let store = store();
+        let (scheduler, runner, _clock) = start_configured_scheduler(store.clone(), false, MisfirePolicy::RunOnce, start).await;
+
+        tokio::time::sleep(std::time::Duration::from_secs(24 * 3600)).await;
+
+        let firings: Vec<(String, DateTime<Utc>)> = runner.firings().into_iter().map(|f| (f.name, f.at)).collect();
+        assert_eq!(
+            firings,
+            vec![("retention_cleanup".to_string(), Utc.with_ymd_and_hms(2025, 1, 16, 2, 30, 0).unwrap())]
+        );
+        // No misfire catch-up either
+        assert_eq!(full_runs(&store), 0);
+        let jobs: Vec<String> = scheduler.history().jobs().into_iter().map(|job| job.name).collect();
+        assert_eq!(jobs, vec!["retention_cleanup".to_string()]);
+        scheduler.shutdown().await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_conditional_jobs_fetch_only_while_tomorrow_is_missing() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 12, 30, 0).unwrap();
+
+        let complete = store();
+        store_tomorrow(&complete, "NO1", start).await;
+        store_tomorrow(&complete, "NO2", start).await;
+        let (scheduler, _, _) = start_scheduler(complete.clone(), MisfirePolicy::Skip, start).await;
+        tokio::time::sleep(std::time::Duration::from_secs(4 * 3600)).await;
+        assert_eq!(full_runs(&complete), 0);
+        scheduler.shutdown().await.unwrap();
+
+        let partial = store();
+        store_tomorrow(&partial, "NO1", start).await;
+        let (scheduler, _, _) = start_scheduler(partial.clone(), MisfirePolicy::Skip, start).await;
+        tokio::time::sleep(std::time::Duration::from_secs(4 * 3600)).await;
+        assert_eq!(full_runs(&partial), 3);
+        scheduler.shutdown().await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_paused_jobs_skip_their_firings() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 12, 30, 0).unwrap();
+        let store = store();
+        let (scheduler, runner, _) = start_scheduler(store.clone(), MisfirePolicy::Skip, start).await;
+        let history = scheduler.history();
+        history.set_paused("retry_1_14:00", true).await.unwrap();
+        history.set_paused("retry_2_15:00", true).await.unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_secs(4 * 3600)).await;
+        assert_eq!(runner.firings().len(), 3);
+        assert_eq!(full_runs(&store), 1);
+        assert!(history.job("retry_1_14:00").unwrap().last_run.is_none());
+        assert!(history.job("retry_3_16:00").unwrap().last_run.is_some());
+        scheduler.shutdown().await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_resume_after_suspend_catches_up_missed_primary_fetch() {
+        // Fetch logs carry real timestamps, so run the virtual clock after them
+        let day = Utc::now().date_naive() + Duration::days(7);
+        let start = day.and_hms_opt(9, 0, 0).unwrap().and_utc();
+        let store = store();
+        let (scheduler, runner, clock) = start_scheduler(store.clone(), MisfirePolicy::RunOnce, start).await;
+
+        // Nothing recorded yet, so startup catches up once
+        tokio::time::sleep(std::time::Duration::from_secs(300)).await;
+        assert_eq!(full_runs(&store), 1);
+
+        // Sleep through today's 13:00 Oslo fire time
+        clock.suspend(Duration::hours(6));
+        tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+        assert_eq!(full_runs(&store), 2);
+
+        // The missed fire time is skipped rather than run late
+        tokio::time::sleep(std::time::Duration::from_secs(3 * 3600)).await;
+        assert!(runner.firings().is_empty());
+        assert_eq!(full_runs(&store), 2);
+        scheduler.shutdown().await.unwrap();
+    }
+}
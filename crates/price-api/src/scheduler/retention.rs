@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::config::RetentionConfig;
+use crate::metrics;
+use crate::models::Task;
+use crate::storage::PriceStore;
+use crate::tasks::{self, TaskHandler};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct RetentionPayload {
+    /// Time the retention windows are counted back from.
+    pub now: DateTime<Utc>,
+}
+
+/// Runs the retention cleanup queued by the scheduler's daily job.
+pub struct RetentionTask {
+    repository: Arc<dyn PriceStore>,
+    retention: RetentionConfig,
+}
+
+impl RetentionTask {
+    pub fn new(repository: Arc<dyn PriceStore>, retention: RetentionConfig) -> Self {
+        Self { repository, retention }
+    }
+}
+
+#[async_trait]
+impl TaskHandler for RetentionTask {
+    fn kind(&self) -> &'static str {
+        tasks::RETENTION
+    }
+
+    async fn run(&self, task: &Task) -> Result<()> {
+        let payload: RetentionPayload = serde_json::from_str(&task.payload)?;
+        info!(
+            price_retention_days = self.retention.price_retention_days,
+            fetch_log_retention_days = self.retention.fetch_log_retention_days,
+            "Starting retention cleanup"
+        );
+        let (prices_deleted, logs_deleted) =
+            run_retention_cleanup(self.repository.as_ref(), &self.retention, payload.now).await?;
+        info!(
+            prices_deleted = prices_deleted,
+            fetch_logs_deleted = logs_deleted,
+            "Retention cleanup completed"
+        );
+        Ok(())
+    }
+}
+
+/// Delete prices, generation, load, flows, fetch logs and finished tasks
+/// older than the configured retention windows before `now`. Finished tasks
+/// are kept as long as fetch logs.
+/// Returns the number of (prices, fetch logs) removed.
+async fn run_retention_cleanup(
+    repository: &dyn PriceStore,
+    retention: &RetentionConfig,
+    now: DateTime<Utc>,
+) -> Result<(u64, u64)> {
+    let price_cutoff = now - Duration::days(retention.price_retention_days as i64);
+    let prices_deleted = repository.delete_old_prices(price_cutoff).await?;
+    metrics::record_retention_deleted("electricity_prices", prices_deleted);
+
+    // Generation, load and flows follow the price retention window
+    let generation_deleted = repository.delete_old_generation(price_cutoff).await?;
+    metrics::record_retention_deleted("generation_per_type", generation_deleted);
+
+    let load_deleted = repository.delete_old_load(price_cutoff).await?;
+    metrics::record_retention_deleted("load_values", load_deleted);
+
+    let flows_deleted = repository.delete_old_flows(price_cutoff).await?;
+    metrics::record_retention_deleted("cross_border_flows", flows_deleted);
+
+    let log_cutoff = now - Duration::days(retention.fetch_log_retention_days as i64);
+    let logs_deleted = repository.delete_old_fetch_logs(log_cutoff).await?;
+    metrics::record_retention_deleted("fetch_log", logs_deleted);
+
+    let tasks_deleted = repository.delete_finished_tasks(log_cutoff).await?;
+    metrics::record_retention_deleted("tasks", tasks_deleted);
+
+    let keys_deleted = repository.delete_expired_idempotency_keys(now).await?;
+    metrics::record_retention_deleted("idempotency_keys", keys_deleted);
+
+    Ok((prices_deleted, logs_deleted))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::config::TaskQueueConfig;
+    use crate::models::Price;
+    use crate::storage::InMemoryPriceStore;
+
+    #[tokio::test]
+    async fn test_retention_task_deletes_prices_past_the_window() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 3, 30, 0).unwrap();
+        let store = Arc::new(InMemoryPriceStore::new());
+        let old = now - Duration::days(31);
+        let recent = now - Duration::days(29);
+        store
+            .upsert_prices(&[
+                Price::from_mwh(old, "NO1".to_string(), 40.0, "PT60M".to_string()),
+                Price::from_mwh(recent, "NO1".to_string(), 50.0, "PT60M".to_string()),
+            ])
+            .await
+            .unwrap();
+        let retention = RetentionConfig {
+            enabled: true,
+            price_retention_days: 30,
+            fetch_log_retention_days: 30,
+            cleanup_cron: "0 30 3 * * *".to_string(),
+        };
+        let task = RetentionTask::new(store.clone(), retention);
+        let queued = tasks::new_task(&TaskQueueConfig::default(), tasks::RETENTION, &RetentionPayload { now }, None, now)
+            .unwrap();
+        let queued = store.enqueue_task(&queued, now).await.unwrap().unwrap();
+
+        task.run(&queued).await.unwrap();
+
+        let remaining = store
+            .get_prices_by_zone("NO1", crate::models::Market::DayAhead, old, now)
+            .await
+            .unwrap();
+        assert_eq!(remaining.iter().map(|p| p.timestamp).collect::<Vec<_>>(), vec![recent]);
+    }
+}
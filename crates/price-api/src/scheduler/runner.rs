@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Europe::Oslo;
+use croner::Cron;
+use futures::future::BoxFuture;
+use tokio_cron_scheduler::{Job, JobScheduler};
+
+/// Scheduled job body. Receives the time it fired at.
+pub type JobFn = Arc<dyn Fn(DateTime<Utc>) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Runs jobs on six-field cron expressions evaluated in Europe/Oslo.
+#[async_trait]
+pub trait JobRunner: Send + Sync {
+    async fn add(&self, name: &str, cron_expr: &str, job: JobFn) -> Result<()>;
+
+    async fn start(&self) -> Result<()>;
+
+    async fn shutdown(&mut self) -> Result<()>;
+}
+
+/// Parse a six-field cron expression the way the runners interpret it.
+pub(super) fn parse_cron(cron_expr: &str) -> Result<Cron> {
+    Cron::new(cron_expr)
+        .with_seconds_required()
+        .with_dom_and_dow()
+        .parse()
+        .map_err(|e| anyhow!("Invalid cron expression {}: {}", cron_expr, e))
+}
+
+/// First fire time of `cron` strictly after `now`, evaluated in Europe/Oslo.
+pub(super) fn next_fire_time(cron: &Cron, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let next = cron.find_next_occurrence(&now.with_timezone(&Oslo), false).ok()?;
+    Some(next.with_timezone(&Utc))
+}
+
+/// [`JobRunner`] backed by `tokio-cron-scheduler`, used in production.
+pub struct CronJobRunner {
+    scheduler: JobScheduler,
+}
+
+impl CronJobRunner {
+    pub async fn new() -> Result<Self> {
+        Ok(Self {
+            scheduler: JobScheduler::new().await?,
+        })
+    }
+}
+
+#[async_trait]
+impl JobRunner for CronJobRunner {
+    async fn add(&self, _name: &str, cron_expr: &str, job: JobFn) -> Result<()> {
+        let job = Job::new_async_tz(cron_expr, chrono_tz::Europe::Oslo, move |_uuid, _lock| job(Utc::now()))?;
+        self.scheduler.add(job).await?;
+        Ok(())
+    }
+
+    async fn start(&self) -> Result<()> {
+        self.scheduler.start().await?;
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        self.scheduler.shutdown().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Database-backed queue for background work, so backfill jobs, gap repairs
+//! and retention runs share one place to be retried, limited and observed
+//! instead of each spawning its own tokio task. Any replica may queue a task;
+//! the [`TaskWorker`] of whichever replica claims it first runs it.
+
+mod worker;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::config::TaskQueueConfig;
+use crate::models::{NewTask, Task};
+
+pub use worker::TaskWorker;
+
+/// Task kind of an admin backfill job, see [`BackfillJobTask`](crate::fetcher::BackfillJobTask).
+pub const BACKFILL_JOB: &str = "backfill_job";
+/// Task kind of a gap repair after a missed fetch, see [`GapRepairTask`](crate::fetcher::GapRepairTask).
+pub const GAP_REPAIR: &str = "gap_repair";
+/// Task kind of a retention run, see [`RetentionTask`](crate::scheduler::RetentionTask).
+pub const RETENTION: &str = "retention";
+
+/// Runs the tasks of one kind.
+#[async_trait]
+pub trait TaskHandler: Send + Sync {
+    /// Kind of task this handler runs.
+    fn kind(&self) -> &'static str;
+
+    /// Run one attempt of `task`. An error retries it with backoff until its
+    /// attempts run out.
+    async fn run(&self, task: &Task) -> anyhow::Result<()>;
+
+    /// Called once `task` failed its last attempt.
+    async fn failed(&self, _task: &Task, _error: &str) {}
+}
+
+/// Task of `kind` due at `now`, with `payload` as its JSON arguments.
+pub fn new_task<T: Serialize>(
+    config: &TaskQueueConfig,
+    kind: &str,
+    payload: &T,
+    dedupe_key: Option<String>,
+    now: DateTime<Utc>,
+) -> Result<NewTask, serde_json::Error> {
+    Ok(NewTask {
+        kind: kind.to_string(),
+        payload: serde_json::to_string(payload)?,
+        dedupe_key,
+        max_attempts: config.max_attempts.max(1),
+        run_after: now,
+    })
+}
+
+/// Delay after the `attempts`-th failed attempt, doubling from the initial
+/// backoff up to the maximum.
+pub(crate) fn task_backoff(config: &TaskQueueConfig, attempts: i32) -> Duration {
+    let factor = 2u64.saturating_pow((attempts - 1).max(0) as u32);
+    let seconds = config.initial_backoff_seconds.saturating_mul(factor).min(config.max_backoff_seconds);
+    Duration::seconds(seconds as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_backoff_doubles_up_to_the_maximum() {
+        let config = TaskQueueConfig {
+            initial_backoff_seconds: 60,
+            max_backoff_seconds: 300,
+            ..TaskQueueConfig::default()
+        };
+        let delays: Vec<i64> = (1..=5).map(|attempts| task_backoff(&config, attempts).num_seconds()).collect();
+        assert_eq!(delays, vec![60, 120, 240, 300, 300]);
+    }
+}
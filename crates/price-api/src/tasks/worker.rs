@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::clock::{Clock, SystemClock};
+use crate::config::TaskQueueConfig;
+use crate::metrics;
+use crate::models::{Task, TaskStatus};
+use crate::storage::PriceStore;
+
+use super::{task_backoff, TaskHandler};
+
+/// Claims due tasks from the queue and runs them with their kind's handler,
+/// renewing each task's lease while it runs.
+pub struct TaskWorker {
+    repository: Arc<dyn PriceStore>,
+    clock: Arc<dyn Clock>,
+    config: TaskQueueConfig,
+    handlers: HashMap<&'static str, Arc<dyn TaskHandler>>,
+}
+
+impl TaskWorker {
+    pub fn new(repository: Arc<dyn PriceStore>, config: TaskQueueConfig) -> Self {
+        Self {
+            repository,
+            clock: Arc::new(SystemClock),
+            config,
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Read task due times and leases from `clock` instead of the system time.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Run tasks of the handler's kind with it.
+    pub fn with_handler(mut self, handler: Arc<dyn TaskHandler>) -> Self {
+        self.handlers.insert(handler.kind(), handler);
+        self
+    }
+
+    /// Poll for due tasks until the returned handle is aborted, running up
+    /// to `concurrency` of them at once.
+    pub fn start(self) -> JoinHandle<()> {
+        let worker = Arc::new(self);
+        let mut kinds: Vec<&str> = worker.handlers.keys().copied().collect();
+        kinds.sort_unstable();
+        info!(concurrency = worker.config.concurrency, kinds = ?kinds, "Task worker started");
+
+        tokio::spawn(async move {
+            let slots = Arc::new(Semaphore::new(worker.config.concurrency.max(1)));
+            let poll_interval = Duration::from_secs(worker.config.poll_interval_seconds.max(1));
+            loop {
+                let slot = Arc::clone(&slots).acquire_owned().await.expect("task slots are never closed");
+                match worker.claim().await {
+                    Some(task) => {
+                        let worker = Arc::clone(&worker);
+                        tokio::spawn(async move {
+                            worker.run(task).await;
+                            drop(slot);
+                        });
+                    }
+                    None => {
+                        drop(slot);
+                        worker.sample_queue().await;
+                        tokio::time::sleep(poll_interval).await;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Run due tasks one at a time until none is left due, returning how
+    /// many attempts ran.
+    pub async fn run_due(&self) -> usize {
+        let mut attempts = 0;
+        while let Some(task) = self.claim().await {
+            self.run(task).await;
+            attempts += 1;
+        }
+        attempts
+    }
+
+    fn lease(&self) -> Duration {
+        Duration::from_secs(self.config.lease_seconds.max(3))
+    }
+
+    async fn claim(&self) -> Option<Task> {
+        let now = self.clock.now();
+        let lease_until = now + chrono::Duration::from_std(self.lease()).unwrap_or(chrono::Duration::MAX);
+        match self.repository.claim_task(now, lease_until).await {
+            Ok(task) => task,
+            Err(e) => {
+                warn!(error = %e, "Failed to claim a task");
+                None
+            }
+        }
+    }
+
+    /// Run one attempt of a claimed task and record how it went: succeeded,
+    /// queued again with backoff, or failed for good.
+    #[tracing::instrument(skip(self, task), fields(task_id = task.id, kind = %task.kind, attempt = task.attempts))]
+    async fn run(&self, task: Task) {
+        let start = Instant::now();
+        let handler = self.handlers.get(task.kind.as_str());
+        let result = match handler {
+            // Its previous worker went away during the last attempt
+            _ if task.attempts > task.max_attempts => Err(anyhow::anyhow!(
+                "Lease lapsed on attempt {} of {}",
+                task.max_attempts,
+                task.max_attempts
+            )),
+            // Possibly queued by a newer replica; retried in case one picks it up
+            None => Err(anyhow::anyhow!("No handler for task kind '{}' on this replica", task.kind)),
+            Some(handler) => {
+                info!("Running task");
+                match self.run_leased(handler.as_ref(), &task).await {
+                    Some(result) => result,
+                    None => {
+                        metrics::record_task_attempt(&task.kind, "lease_lost", start.elapsed());
+                        warn!("Task was claimed by another worker after its lease lapsed, abandoning this attempt");
+                        return;
+                    }
+                }
+            }
+        };
+
+        let now = self.clock.now();
+        let (outcome, saved) = match &result {
+            Ok(()) => {
+                info!(duration_ms = start.elapsed().as_millis(), "Task succeeded");
+                (
+                    "succeeded",
+                    self.repository.finish_task(task.id, task.attempts, TaskStatus::Succeeded, None, now).await,
+                )
+            }
+            Err(e) if task.attempts < task.max_attempts => {
+                let run_after = now + task_backoff(&self.config, task.attempts);
+                warn!(error = %e, retry_at = %run_after, "Task attempt failed, retrying");
+                (
+                    "retried",
+                    self.repository.retry_task(task.id, task.attempts, &e.to_string(), run_after, now).await,
+                )
+            }
+            Err(e) => {
+                error!(error = %e, "Task failed on its last attempt");
+                (
+                    "failed",
+                    self.repository
+                        .finish_task(task.id, task.attempts, TaskStatus::Failed, Some(&e.to_string()), now)
+                        .await,
+                )
+            }
+        };
+        metrics::record_task_attempt(&task.kind, outcome, start.elapsed());
+
+        match saved {
+            Ok(true) => {
+                if let (Some(handler), "failed", Err(e)) = (handler, outcome, &result) {
+                    handler.failed(&task, &e.to_string()).await;
+                }
+            }
+            Ok(false) => warn!(outcome = outcome, "Task was claimed by another worker before its outcome was saved"),
+            Err(e) => error!(error = %e, outcome = outcome, "Failed to save task outcome"),
+        }
+    }
+
+    /// Run the handler while renewing the task's lease. `None` once the
+    /// lease was lost to another worker, which then owns the task.
+    async fn run_leased(&self, handler: &dyn TaskHandler, task: &Task) -> Option<anyhow::Result<()>> {
+        let lease = self.lease();
+        let run = handler.run(task);
+        tokio::pin!(run);
+        let mut heartbeat = tokio::time::interval(lease / 3);
+        heartbeat.tick().await;
+        loop {
+            tokio::select! {
+                result = &mut run => return Some(result),
+                _ = heartbeat.tick() => {
+                    let lease_until = self.clock.now() + chrono::Duration::from_std(lease).unwrap_or(chrono::Duration::MAX);
+                    match self.repository.renew_task_lease(task.id, task.attempts, lease_until).await {
+                        Ok(true) => {}
+                        Ok(false) => return None,
+                        Err(e) => warn!(error = %e, "Failed to renew task lease"),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Publish the number of tasks in each status.
+    async fn sample_queue(&self) {
+        match self.repository.count_tasks_by_status().await {
+            Ok(counts) => {
+                for status in TaskStatus::ALL {
+                    metrics::update_task_queue_tasks(status.as_str(), counts.get(&status).copied().unwrap_or(0));
+                }
+            }
+            Err(e) => warn!(error = %e, "Failed to count queued tasks"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::models::NewTask;
+    use crate::storage::InMemoryPriceStore;
+
+    /// Fails its first `failures` attempts, recording the tasks given up on.
+    struct Flaky {
+        failures: usize,
+        attempts: AtomicUsize,
+        given_up: Mutex<Vec<String>>,
+    }
+
+    impl Flaky {
+        fn new(failures: usize) -> Arc<Self> {
+            Arc::new(Self {
+                failures,
+                attempts: AtomicUsize::new(0),
+                given_up: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl TaskHandler for Flaky {
+        fn kind(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn run(&self, _task: &Task) -> anyhow::Result<()> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            anyhow::ensure!(attempt > self.failures, "attempt {} failed", attempt);
+            Ok(())
+        }
+
+        async fn failed(&self, _task: &Task, error: &str) {
+            self.given_up.lock().unwrap().push(error.to_string());
+        }
+    }
+
+    fn config() -> TaskQueueConfig {
+        TaskQueueConfig {
+            max_attempts: 3,
+            initial_backoff_seconds: 60,
+            max_backoff_seconds: 600,
+            lease_seconds: 300,
+            ..TaskQueueConfig::default()
+        }
+    }
+
+    async fn enqueue(store: &InMemoryPriceStore, clock: &FixedClock) -> Task {
+        let task = NewTask {
+            kind: "flaky".to_string(),
+            payload: "{}".to_string(),
+            dedupe_key: None,
+            max_attempts: config().max_attempts,
+            run_after: clock.now(),
+        };
+        store.enqueue_task(&task, clock.now()).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_failed_attempts_are_retried_with_backoff() {
+        let store = Arc::new(InMemoryPriceStore::new());
+        let clock = Arc::new(FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 15, 3, 0, 0).unwrap()));
+        let handler = Flaky::new(1);
+        let worker = TaskWorker::new(store.clone(), config())
+            .with_clock(clock.clone())
+            .with_handler(handler.clone());
+        let task = enqueue(&store, &clock).await;
+
+        assert_eq!(worker.run_due().await, 1);
+        let retried = store.get_task(task.id).await.unwrap();
+        assert_eq!((retried.status, retried.attempts), (TaskStatus::Queued, 1));
+        assert_eq!(retried.last_error.as_deref(), Some("attempt 1 failed"));
+        assert_eq!(retried.run_after, clock.now() + chrono::Duration::seconds(60));
+
+        // Not due again until the backoff has passed
+        assert_eq!(worker.run_due().await, 0);
+        clock.advance(chrono::Duration::seconds(60));
+        assert_eq!(worker.run_due().await, 1);
+        let done = store.get_task(task.id).await.unwrap();
+        assert_eq!((done.status, done.attempts), (TaskStatus::Succeeded, 2));
+        assert_eq!(done.finished_at, Some(clock.now()));
+        assert!(handler.given_up.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_task_fails_once_its_attempts_run_out() {
+        let store = Arc::new(InMemoryPriceStore::new());
+        let clock = Arc::new(FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 15, 3, 0, 0).unwrap()));
+        let handler = Flaky::new(usize::MAX);
+        let worker = TaskWorker::new(store.clone(), config())
+            .with_clock(clock.clone())
+            .with_handler(handler.clone());
+        let task = enqueue(&store, &clock).await;
+
+        for _ in 0..3 {
+            assert_eq!(worker.run_due().await, 1);
+            clock.advance(chrono::Duration::minutes(10));
+        }
+        let failed = store.get_task(task.id).await.unwrap();
+        assert_eq!((failed.status, failed.attempts), (TaskStatus::Failed, 3));
+        assert_eq!(*handler.given_up.lock().unwrap(), vec!["attempt 3 failed".to_string()]);
+        assert_eq!(worker.run_due().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_task_with_a_lapsed_lease_is_claimed_again() {
+        let store = Arc::new(InMemoryPriceStore::new());
+        let clock = Arc::new(FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 15, 3, 0, 0).unwrap()));
+        let handler = Flaky::new(0);
+        let worker = TaskWorker::new(store.clone(), config())
+            .with_clock(clock.clone())
+            .with_handler(handler.clone());
+        let task = enqueue(&store, &clock).await;
+
+        // Claimed by a worker that then went away
+        let lease_until = clock.now() + chrono::Duration::minutes(5);
+        let abandoned = store.claim_task(clock.now(), lease_until).await.unwrap().unwrap();
+        assert_eq!(worker.run_due().await, 0);
+
+        clock.set(lease_until);
+        assert_eq!(worker.run_due().await, 1);
+        let done = store.get_task(task.id).await.unwrap();
+        assert_eq!((done.status, done.attempts), (TaskStatus::Succeeded, 2));
+        // The first worker can no longer record an outcome
+        assert!(!store
+            .finish_task(abandoned.id, abandoned.attempts, TaskStatus::Failed, Some("late"), clock.now())
+            .await
+            .unwrap());
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::{Market, Price};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// First complete set of prices for tomorrow stored for a zone.
+    TomorrowPricesAvailable {
+        zone_code: String,
+        date: NaiveDate,
+        price_count: usize,
+    },
+    /// Fetching a zone failed after retries.
+    FetchFailed {
+        zone_code: String,
+        date: NaiveDate,
+        error: String,
+    },
+    /// A backfill filled missing hours for a zone.
+    GapRepaired {
+        zone_code: String,
+        date: NaiveDate,
+        price_count: usize,
+    },
+    /// A fetch run stored prices, with how each fetched zone's data compares
+    /// to what was stored before. Zones that failed or had no data are omitted.
+    FetchCompleted {
+        date: NaiveDate,
+        market: Market,
+        zones: Vec<ZoneFetchDiff>,
+    },
+}
+
+impl WebhookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WebhookEvent::TomorrowPricesAvailable { .. } => "tomorrow_prices_available",
+            WebhookEvent::FetchFailed { .. } => "fetch_failed",
+            WebhookEvent::GapRepaired { .. } => "gap_repaired",
+            WebhookEvent::FetchCompleted { .. } => "fetch_completed",
+        }
+    }
+}
+
+/// JSON body sent to webhook endpoints. The `id` is stable across retries so
+/// receivers can deduplicate.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: WebhookEvent,
+}
+
+impl WebhookPayload {
+    pub fn new(event: WebhookEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            occurred_at: Utc::now(),
+            event,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffStatus {
+    /// At least one interval was not stored before and none changed.
+    New,
+    /// Every interval was already stored with the same price.
+    Unchanged,
+    /// At least one stored price changed.
+    Revised,
+}
+
+/// How the prices fetched for one zone compare to the stored ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ZoneFetchDiff {
+    pub zone_code: String,
+    pub status: DiffStatus,
+    pub new_count: usize,
+    pub revised_count: usize,
+    pub unchanged_count: usize,
+}
+
+impl ZoneFetchDiff {
+    pub fn between<'a>(zone_code: &str, stored: &[Price], fetched: impl IntoIterator<Item = &'a Price>) -> Self {
+        let stored: HashMap<DateTime<Utc>, _> = stored.iter().map(|p| (p.timestamp, p.price_kwh)).collect();
+
+        let (mut new_count, mut revised_count, mut unchanged_count) = (0, 0, 0);
+        for price in fetched {
+            match stored.get(&price.timestamp) {
+                None => new_count += 1,
+                Some(previous) if *previous != price.price_kwh => revised_count += 1,
+                Some(_) => unchanged_count += 1,
+            }
+        }
+
+        let status = if revised_count > 0 {
+            DiffStatus::Revised
+        } else if new_count > 0 {
+            DiffStatus::New
+        } else {
+            DiffStatus::Unchanged
+        };
+
+        Self {
+            zone_code: zone_code.to_string(),
+            status,
+            new_count,
+            revised_count,
+            unchanged_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn price(hour: u32, mwh: f64) -> Price {
+        Price::from_mwh(
+            Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap(),
+            "NO1".to_string(),
+            mwh,
+            "PT60M".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_diff_classifies_zone_data() {
+        let stored = vec![price(0, 50.0), price(1, 60.0)];
+
+        let same = ZoneFetchDiff::between("NO1", &stored, &[price(0, 50.0), price(1, 60.0)]);
+        assert_eq!(same.status, DiffStatus::Unchanged);
+        assert_eq!(same.unchanged_count, 2);
+
+        let extended = ZoneFetchDiff::between("NO1", &stored, &[price(1, 60.0), price(2, 70.0)]);
+        assert_eq!((extended.status, extended.new_count), (DiffStatus::New, 1));
+
+        let revised = ZoneFetchDiff::between("NO1", &stored, &[price(0, 55.0), price(2, 70.0)]);
+        assert_eq!(revised.status, DiffStatus::Revised);
+        assert_eq!((revised.new_count, revised.revised_count, revised.unchanged_count), (1, 1, 0));
+    }
+
+    #[test]
+    fn test_fetch_completed_payload_shape() {
+        let event = WebhookEvent::FetchCompleted {
+            date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            market: Market::DayAhead,
+            zones: vec![ZoneFetchDiff::between("NO1", &[], &[price(0, 50.0)])],
+        };
+        let json = serde_json::to_value(WebhookPayload::new(event)).unwrap();
+        assert_eq!(json["type"], "fetch_completed");
+        assert_eq!(json["market"], "day_ahead");
+        assert_eq!(json["zones"][0]["status"], "new");
+        assert_eq!(json["zones"][0]["new_count"], 1);
+    }
+}
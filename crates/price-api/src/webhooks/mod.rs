@@ -0,0 +1,5 @@
+mod event;
+mod notifier;
+
+pub use event::{DiffStatus, WebhookEvent, WebhookPayload, ZoneFetchDiff};
+pub use notifier::{sign_payload, WebhookNotifier};
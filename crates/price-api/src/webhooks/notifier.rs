@@ -0,0 +1,236 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hmac::{Hmac, Mac};
+use reqwest::{Client, StatusCode};
+use sha2::Sha256;
+use tracing::{debug, error, warn};
+
+use crate::config::WebhookConfig;
+use crate::metrics;
+
+use super::event::{WebhookEvent, WebhookPayload};
+
+/// Hex HMAC-SHA256 over `"{timestamp}.{body}"`. Receivers recompute it from the
+/// `X-Webhook-Timestamp` header and the raw body.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Delivers signed event payloads to the configured endpoints, retrying
+/// transient failures with exponential backoff.
+pub struct WebhookNotifier {
+    client: Client,
+    urls: Vec<String>,
+    secret: String,
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl WebhookNotifier {
+    pub fn from_config(config: &WebhookConfig) -> Result<Self, String> {
+        if config.secret.is_empty() {
+            return Err("webhooks.secret must be set when webhooks are enabled".to_string());
+        }
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .map_err(|e| format!("Failed to build webhook client: {}", e))?;
+
+        Ok(Self {
+            client,
+            urls: config.urls.clone(),
+            secret: config.secret.clone(),
+            max_attempts: config.max_attempts.max(1),
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+        })
+    }
+
+    /// Deliver `event` in the background so fetches are never held up by
+    /// slow receivers.
+    pub fn notify(self: &Arc<Self>, event: WebhookEvent) {
+        if self.urls.is_empty() {
+            return;
+        }
+        let notifier = Arc::clone(self);
+        tokio::spawn(async move {
+            notifier.deliver(event).await;
+        });
+    }
+
+    /// Deliver `event` to every endpoint, returning how many accepted it.
+    pub async fn deliver(&self, event: WebhookEvent) -> usize {
+        let payload = WebhookPayload::new(event);
+        let body = match serde_json::to_string(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                error!(error = %e, "Failed to serialize webhook payload");
+                return 0;
+            }
+        };
+
+        let mut delivered = 0;
+        for url in &self.urls {
+            let ok = self.deliver_to(url, &payload, &body).await;
+            metrics::record_webhook_delivery(payload.event.name(), ok);
+            if ok {
+                delivered += 1;
+            }
+        }
+        delivered
+    }
+
+    async fn deliver_to(&self, url: &str, payload: &WebhookPayload, body: &str) -> bool {
+        let event = payload.event.name();
+
+        for attempt in 0..self.max_attempts {
+            if attempt > 0 {
+                let backoff = self.initial_backoff * 2u32.saturating_pow(attempt - 1);
+                tokio::time::sleep(backoff.min(Duration::from_secs(60))).await;
+            }
+
+            let timestamp = chrono::Utc::now().timestamp();
+            let start = Instant::now();
+            let result = self
+                .client
+                .post(url)
+                .header("Content-Type", "application/json")
+                .header("X-Webhook-Id", payload.id.to_string())
+                .header("X-Webhook-Event", event)
+                .header("X-Webhook-Timestamp", timestamp.to_string())
+                .header(
+                    "X-Webhook-Signature",
+                    format!("sha256={}", sign_payload(&self.secret, timestamp, body)),
+                )
+                .body(body.to_string())
+                .send()
+                .await;
+            metrics::record_webhook_attempt_duration(event, start.elapsed());
+
+            match result {
+                Ok(response) if response.status().is_success() => {
+                    metrics::record_webhook_attempt(event, "success");
+                    debug!(url = %url, event = event, attempt = attempt + 1, "Webhook delivered");
+                    return true;
+                }
+                Ok(response) if !is_retryable(response.status()) => {
+                    metrics::record_webhook_attempt(event, "rejected");
+                    warn!(url = %url, event = event, status = %response.status(), "Webhook rejected, not retrying");
+                    return false;
+                }
+                Ok(response) => {
+                    metrics::record_webhook_attempt(event, "retryable");
+                    warn!(url = %url, event = event, attempt = attempt + 1, status = %response.status(), "Webhook delivery failed");
+                }
+                Err(e) => {
+                    metrics::record_webhook_attempt(event, "error");
+                    warn!(url = %url, event = event, attempt = attempt + 1, error = %e, "Webhook delivery failed");
+                }
+            }
+        }
+
+        error!(url = %url, event = event, attempts = self.max_attempts, "Giving up on webhook delivery");
+        false
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::REQUEST_TIMEOUT
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{extract::State, http::HeaderMap, routing::post, Router};
+    use chrono::NaiveDate;
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Receiver {
+        calls: Arc<AtomicUsize>,
+        accepted: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+    }
+
+    async fn flaky_handler(State(receiver): State<Receiver>, headers: HeaderMap, body: String) -> StatusCode {
+        if receiver.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+        receiver.accepted.lock().await.push((headers, body));
+        StatusCode::NO_CONTENT
+    }
+
+    fn config(url: String, max_attempts: u32) -> WebhookConfig {
+        WebhookConfig {
+            enabled: true,
+            urls: vec![url],
+            secret: "s3cret".to_string(),
+            timeout_seconds: 5,
+            max_attempts,
+            initial_backoff_ms: 1,
+        }
+    }
+
+    fn event() -> WebhookEvent {
+        WebhookEvent::FetchFailed {
+            zone_code: "NO1".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            error: "boom".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_signature_covers_timestamp_and_body() {
+        let signature = sign_payload("key", 1700000000, "{}");
+        assert_eq!(signature.len(), 64);
+        assert_eq!(signature, sign_payload("key", 1700000000, "{}"));
+        assert_ne!(signature, sign_payload("key", 1700000001, "{}"));
+        assert_ne!(signature, sign_payload("other", 1700000000, "{}"));
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries_transient_failures_and_signs_body() {
+        let receiver = Receiver::default();
+        let app = Router::new().route("/hook", post(flaky_handler)).with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let notifier = WebhookNotifier::from_config(&config(format!("http://{}/hook", addr), 3)).unwrap();
+        assert_eq!(notifier.deliver(event()).await, 1);
+        assert_eq!(receiver.calls.load(Ordering::SeqCst), 2);
+
+        let accepted = receiver.accepted.lock().await;
+        let (headers, body) = &accepted[0];
+        let timestamp: i64 = headers["x-webhook-timestamp"].to_str().unwrap().parse().unwrap();
+        assert_eq!(
+            headers["x-webhook-signature"].to_str().unwrap(),
+            format!("sha256={}", sign_payload("s3cret", timestamp, body))
+        );
+        assert_eq!(headers["x-webhook-event"], "fetch_failed");
+
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["type"], "fetch_failed");
+        assert_eq!(json["zone_code"], "NO1");
+        assert_eq!(json["date"], "2025-01-15");
+    }
+
+    #[tokio::test]
+    async fn test_delivery_gives_up_after_max_attempts() {
+        let notifier = WebhookNotifier::from_config(&config("http://127.0.0.1:9/unreachable".to_string(), 2)).unwrap();
+        assert_eq!(notifier.deliver(event()).await, 0);
+    }
+
+    #[test]
+    fn test_from_config_requires_a_secret() {
+        let mut config = config("http://127.0.0.1:9/hook".to_string(), 1);
+        config.secret = String::new();
+        assert!(WebhookNotifier::from_config(&config).is_err());
+    }
+}
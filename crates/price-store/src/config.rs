@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseBackend {
+    #[default]
+    Postgres,
+    Sqlite,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DatabaseConfig {
+    #[serde(default)]
+    pub backend: DatabaseBackend,
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub connect_timeout_seconds: u64,
+    /// Log statements slower than this at WARN, tagged with the issuing
+    /// request's span. Unset keeps sqlx's default (1s).
+    #[serde(default)]
+    pub slow_query_threshold_ms: Option<u64>,
+    /// Postgres `statement_timeout` set on every connection, so a query with
+    /// a degraded plan is cancelled instead of holding its connection.
+    /// Migrations run without it. Unset leaves the server default; SQLite
+    /// ignores it.
+    #[serde(default)]
+    pub statement_timeout_ms: Option<u64>,
+    /// Postgres read replica the API handlers read from; writes, the fetcher
+    /// and the scheduler stay on `url`. Unset reads from `url`.
+    #[serde(default)]
+    pub read_url: Option<String>,
+    /// Apply the embedded Postgres migrations on connect. Off by default,
+    /// since a database whose schema was created without sqlx (no
+    /// `_sqlx_migrations` table) cannot be migrated; SQLite always migrates.
+    pub run_migrations: bool,
+    /// Retries after a failed Postgres connection attempt at startup, so the
+    /// service can start before the database accepts connections.
+    pub connect_retries: u32,
+    /// Wait before the first retry, doubled after each further failure.
+    pub connect_retry_initial_ms: u64,
+    /// Longest wait between retries.
+    pub connect_retry_max_ms: u64,
+    /// Start without connecting to Postgres: the pools connect on first use,
+    /// and migrations run once the database answers.
+    pub lazy_connect: bool,
+}
+
+impl DatabaseConfig {
+    /// Wait before retry number `retry` (0 for the first).
+    pub fn connect_retry_delay(&self, retry: u32) -> Duration {
+        let delay = self.connect_retry_initial_ms.saturating_mul(1u64 << retry.min(32));
+        Duration::from_millis(delay.min(self.connect_retry_max_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connect_retry_delay_doubles_up_to_max() {
+        let config = DatabaseConfig {
+            backend: DatabaseBackend::Postgres,
+            url: String::new(),
+            max_connections: 1,
+            min_connections: 0,
+            connect_timeout_seconds: 5,
+            slow_query_threshold_ms: None,
+            statement_timeout_ms: None,
+            read_url: None,
+            run_migrations: false,
+            connect_retries: 10,
+            connect_retry_initial_ms: 500,
+            connect_retry_max_ms: 5_000,
+            lazy_connect: false,
+        };
+        let delays: Vec<u64> = (0..6).map(|retry| config.connect_retry_delay(retry).as_millis() as u64).collect();
+        assert_eq!(delays, vec![500, 1_000, 2_000, 4_000, 5_000, 5_000]);
+        assert_eq!(config.connect_retry_delay(u32::MAX), Duration::from_millis(5_000));
+    }
+}
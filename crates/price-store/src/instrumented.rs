@@ -0,0 +1,646 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::{BoxStream, StreamExt};
+
+use entsoe_core::models::{
+    AlertEvent, AlertRule, ApiKey, BackfillJob, BiddingZone, CrossBorderFlow, FetchLog, FetchRetry, FetchStatus, FlowKind, GenerationPoint,
+    IdempotencyRecord, LoadValue, Market, NameTranslation, NewAlertRule, NewBackfillJob, NewTask, Price, Task, TaskStatus, ZoneDefinition,
+};
+
+use super::error::StorageError;
+use super::metrics;
+use super::store::{DayGap, FetchLogFilter, FetchLogPage, PoolStatus, PriceCoverage, PriceStore, TableStats, ZoneStats};
+
+/// Records every operation's latency in `database_query_duration_seconds`,
+/// labelled with the method name, before delegating to the wrapped store.
+/// [`connect`](crate::connect) wraps each backend in one, so callers do not
+/// time their own queries.
+pub struct InstrumentedStore {
+    inner: Arc<dyn PriceStore>,
+}
+
+impl InstrumentedStore {
+    pub fn new(inner: Arc<dyn PriceStore>) -> Self {
+        Self { inner }
+    }
+}
+
+async fn timed<T>(operation: &str, query: impl Future<Output = T>) -> T {
+    let start = Instant::now();
+    let result = query.await;
+    metrics::record_db_query_duration(operation, start.elapsed());
+    result
+}
+
+/// Records a streamed query's duration once the stream is dropped, whether
+/// it was read to the end or abandoned.
+struct StreamTimer {
+    operation: &'static str,
+    start: Instant,
+}
+
+impl Drop for StreamTimer {
+    fn drop(&mut self) {
+        metrics::record_db_query_duration(self.operation, self.start.elapsed());
+    }
+}
+
+#[async_trait]
+impl PriceStore for InstrumentedStore {
+    async fn health_check(&self) -> Result<(), StorageError> {
+        timed("health_check", self.inner.health_check()).await
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        self.inner.pool_status()
+    }
+
+    async fn migrate(&self) -> Result<(), StorageError> {
+        timed("migrate", self.inner.migrate()).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Price Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_prices(&self, prices: &[Price]) -> Result<usize, StorageError> {
+        timed("upsert_prices", self.inner.upsert_prices(prices)).await
+    }
+
+    async fn get_prices_by_zone(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Price>, StorageError> {
+        timed("get_prices_by_zone", self.inner.get_prices_by_zone(zone_code, market, start, end)).await
+    }
+
+    fn stream_prices_by_zone<'a>(
+        &'a self,
+        zone_code: &'a str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxStream<'a, Result<Price, StorageError>> {
+        let timer = StreamTimer {
+            operation: "stream_prices_by_zone",
+            start: Instant::now(),
+        };
+        self.inner
+            .stream_prices_by_zone(zone_code, market, start, end)
+            .map(move |price| {
+                let _ = &timer;
+                price
+            })
+            .boxed()
+    }
+
+    async fn get_prices_by_country(
+        &self,
+        country_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<Price>>, StorageError> {
+        timed("get_prices_by_country", self.inner.get_prices_by_country(country_code, market, start, end)).await
+    }
+
+    async fn get_latest_prices(&self, since: Option<DateTime<Utc>>) -> Result<Vec<Price>, StorageError> {
+        timed("get_latest_prices", self.inner.get_latest_prices(since)).await
+    }
+
+    async fn get_current_prices(&self, at: DateTime<Utc>) -> Result<Vec<Price>, StorageError> {
+        timed("get_current_prices", self.inner.get_current_prices(at)).await
+    }
+
+    async fn get_price_at(&self, zone_code: &str, market: Market, at: DateTime<Utc>) -> Result<Option<Price>, StorageError> {
+        timed("get_price_at", self.inner.get_price_at(zone_code, market, at)).await
+    }
+
+    async fn get_zone_stats(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<ZoneStats, StorageError> {
+        timed("get_zone_stats", self.inner.get_zone_stats(zone_code, market, start, end)).await
+    }
+
+    async fn get_price_coverage(&self, zone_code: &str, market: Market) -> Result<Option<PriceCoverage>, StorageError> {
+        timed("get_price_coverage", self.inner.get_price_coverage(zone_code, market)).await
+    }
+
+    async fn delete_old_prices(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        timed("delete_old_prices", self.inner.delete_old_prices(older_than)).await
+    }
+
+    async fn ensure_price_partitions(&self, from: NaiveDate, months_ahead: u32) -> Result<Vec<String>, StorageError> {
+        timed("ensure_price_partitions", self.inner.ensure_price_partitions(from, months_ahead)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Generation Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_generation(&self, points: &[GenerationPoint]) -> Result<usize, StorageError> {
+        timed("upsert_generation", self.inner.upsert_generation(points)).await
+    }
+
+    async fn get_generation_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<GenerationPoint>, StorageError> {
+        timed("get_generation_by_zone", self.inner.get_generation_by_zone(zone_code, start, end)).await
+    }
+
+    async fn delete_old_generation(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        timed("delete_old_generation", self.inner.delete_old_generation(older_than)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Load Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_load(&self, values: &[LoadValue]) -> Result<usize, StorageError> {
+        timed("upsert_load", self.inner.upsert_load(values)).await
+    }
+
+    async fn get_load_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LoadValue>, StorageError> {
+        timed("get_load_by_zone", self.inner.get_load_by_zone(zone_code, start, end)).await
+    }
+
+    async fn delete_old_load(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        timed("delete_old_load", self.inner.delete_old_load(older_than)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Cross-Border Flow Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_flows(&self, flows: &[CrossBorderFlow]) -> Result<usize, StorageError> {
+        timed("upsert_flows", self.inner.upsert_flows(flows)).await
+    }
+
+    async fn get_flows_by_zone(
+        &self,
+        zone_code: &str,
+        kind: FlowKind,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<CrossBorderFlow>, StorageError> {
+        timed("get_flows_by_zone", self.inner.get_flows_by_zone(zone_code, kind, start, end)).await
+    }
+
+    async fn delete_old_flows(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        timed("delete_old_flows", self.inner.delete_old_flows(older_than)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Name Translation Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_name_translations(&self, translations: &[NameTranslation]) -> Result<usize, StorageError> {
+        timed("upsert_name_translations", self.inner.upsert_name_translations(translations)).await
+    }
+
+    async fn get_name_translations(&self, locale: &str) -> Result<Vec<NameTranslation>, StorageError> {
+        timed("get_name_translations", self.inner.get_name_translations(locale)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Alert Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn create_alert_rule(&self, rule: &NewAlertRule) -> Result<AlertRule, StorageError> {
+        timed("create_alert_rule", self.inner.create_alert_rule(rule)).await
+    }
+
+    async fn list_alert_rules(&self, zone_code: Option<&str>) -> Result<Vec<AlertRule>, StorageError> {
+        timed("list_alert_rules", self.inner.list_alert_rules(zone_code)).await
+    }
+
+    async fn get_alert_rule(&self, id: i64) -> Result<AlertRule, StorageError> {
+        timed("get_alert_rule", self.inner.get_alert_rule(id)).await
+    }
+
+    async fn delete_alert_rule(&self, id: i64) -> Result<bool, StorageError> {
+        timed("delete_alert_rule", self.inner.delete_alert_rule(id)).await
+    }
+
+    async fn insert_alert_events(&self, events: &[AlertEvent]) -> Result<Vec<AlertEvent>, StorageError> {
+        timed("insert_alert_events", self.inner.insert_alert_events(events)).await
+    }
+
+    async fn complete_alert_event(
+        &self,
+        rule_id: i64,
+        price_timestamp: DateTime<Utc>,
+        delivered: bool,
+        error_message: Option<String>,
+    ) -> Result<(), StorageError> {
+        timed(
+            "complete_alert_event",
+            self.inner.complete_alert_event(rule_id, price_timestamp, delivered, error_message),
+        )
+        .await
+    }
+
+    async fn get_alert_events(&self, rule_id: i64, limit: i64) -> Result<Vec<AlertEvent>, StorageError> {
+        timed("get_alert_events", self.inner.get_alert_events(rule_id, limit)).await
+    }
+
+    async fn failed_alert_events(&self, from: DateTime<Utc>) -> Result<Vec<AlertEvent>, StorageError> {
+        timed("failed_alert_events", self.inner.failed_alert_events(from)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // API Key Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn create_api_key(&self, key: &ApiKey) -> Result<(), StorageError> {
+        timed("create_api_key", self.inner.create_api_key(key)).await
+    }
+
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, StorageError> {
+        timed("list_api_keys", self.inner.list_api_keys()).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Job Lock Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn try_acquire_job_lock(
+        &self,
+        job_name: &str,
+        target_date: NaiveDate,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        timed(
+            "try_acquire_job_lock",
+            self.inner.try_acquire_job_lock(job_name, target_date, holder, now, expires_at),
+        )
+        .await
+    }
+
+    async fn renew_job_lock(
+        &self,
+        job_name: &str,
+        target_date: NaiveDate,
+        holder: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        timed("renew_job_lock", self.inner.renew_job_lock(job_name, target_date, holder, expires_at)).await
+    }
+
+    async fn release_job_lock(&self, job_name: &str, target_date: NaiveDate, holder: &str) -> Result<(), StorageError> {
+        timed("release_job_lock", self.inner.release_job_lock(job_name, target_date, holder)).await
+    }
+
+    async fn set_job_paused(&self, job_name: &str, paused: bool, now: DateTime<Utc>) -> Result<(), StorageError> {
+        timed("set_job_paused", self.inner.set_job_paused(job_name, paused, now)).await
+    }
+
+    async fn paused_jobs(&self) -> Result<Vec<String>, StorageError> {
+        timed("paused_jobs", self.inner.paused_jobs()).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Idempotency Key Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn claim_idempotency_key(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        timed(
+            "claim_idempotency_key",
+            self.inner.claim_idempotency_key(key, fingerprint, now, expires_at),
+        )
+        .await
+    }
+
+    async fn get_idempotency_key(&self, key: &str) -> Result<Option<IdempotencyRecord>, StorageError> {
+        timed("get_idempotency_key", self.inner.get_idempotency_key(key)).await
+    }
+
+    async fn complete_idempotency_key(
+        &self,
+        key: &str,
+        claimed_at: DateTime<Utc>,
+        status_code: i32,
+        response_body: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), StorageError> {
+        timed(
+            "complete_idempotency_key",
+            self.inner.complete_idempotency_key(key, claimed_at, status_code, response_body, expires_at),
+        )
+        .await
+    }
+
+    async fn release_idempotency_key(&self, key: &str, claimed_at: DateTime<Utc>) -> Result<(), StorageError> {
+        timed("release_idempotency_key", self.inner.release_idempotency_key(key, claimed_at)).await
+    }
+
+    async fn delete_expired_idempotency_keys(&self, now: DateTime<Utc>) -> Result<u64, StorageError> {
+        timed("delete_expired_idempotency_keys", self.inner.delete_expired_idempotency_keys(now)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Retry Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn enqueue_fetch_retries(&self, retries: &[FetchRetry]) -> Result<(), StorageError> {
+        timed("enqueue_fetch_retries", self.inner.enqueue_fetch_retries(retries)).await
+    }
+
+    async fn get_due_fetch_retries(&self, now: DateTime<Utc>) -> Result<Vec<FetchRetry>, StorageError> {
+        timed("get_due_fetch_retries", self.inner.get_due_fetch_retries(now)).await
+    }
+
+    async fn reschedule_fetch_retry(
+        &self,
+        zone_code: &str,
+        target_date: NaiveDate,
+        next_attempt_at: DateTime<Utc>,
+        error: Option<&str>,
+    ) -> Result<(), StorageError> {
+        timed(
+            "reschedule_fetch_retry",
+            self.inner.reschedule_fetch_retry(zone_code, target_date, next_attempt_at, error),
+        )
+        .await
+    }
+
+    async fn delete_fetch_retry(&self, zone_code: &str, target_date: NaiveDate) -> Result<(), StorageError> {
+        timed("delete_fetch_retry", self.inner.delete_fetch_retry(zone_code, target_date)).await
+    }
+
+    async fn delete_expired_fetch_retries(&self, now: DateTime<Utc>) -> Result<u64, StorageError> {
+        timed("delete_expired_fetch_retries", self.inner.delete_expired_fetch_retries(now)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Backfill Job Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn create_backfill_job(&self, job: &NewBackfillJob, now: DateTime<Utc>) -> Result<BackfillJob, StorageError> {
+        timed("create_backfill_job", self.inner.create_backfill_job(job, now)).await
+    }
+
+    async fn get_backfill_job(&self, id: i64) -> Result<BackfillJob, StorageError> {
+        timed("get_backfill_job", self.inner.get_backfill_job(id)).await
+    }
+
+    async fn update_backfill_job(&self, job: &BackfillJob) -> Result<bool, StorageError> {
+        timed("update_backfill_job", self.inner.update_backfill_job(job)).await
+    }
+
+    async fn cancel_backfill_job(&self, id: i64, now: DateTime<Utc>) -> Result<bool, StorageError> {
+        timed("cancel_backfill_job", self.inner.cancel_backfill_job(id, now)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Task Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn enqueue_task(&self, task: &NewTask, now: DateTime<Utc>) -> Result<Option<Task>, StorageError> {
+        timed("enqueue_task", self.inner.enqueue_task(task, now)).await
+    }
+
+    async fn claim_task(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>) -> Result<Option<Task>, StorageError> {
+        timed("claim_task", self.inner.claim_task(now, lease_until)).await
+    }
+
+    async fn renew_task_lease(&self, id: i64, attempt: i32, lease_until: DateTime<Utc>) -> Result<bool, StorageError> {
+        timed("renew_task_lease", self.inner.renew_task_lease(id, attempt, lease_until)).await
+    }
+
+    async fn finish_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        status: TaskStatus,
+        error: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        timed("finish_task", self.inner.finish_task(id, attempt, status, error, now)).await
+    }
+
+    async fn retry_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        error: &str,
+        run_after: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        timed("retry_task", self.inner.retry_task(id, attempt, error, run_after, now)).await
+    }
+
+    async fn get_task(&self, id: i64) -> Result<Task, StorageError> {
+        timed("get_task", self.inner.get_task(id)).await
+    }
+
+    async fn list_tasks(&self, status: Option<TaskStatus>, limit: i64) -> Result<Vec<Task>, StorageError> {
+        timed("list_tasks", self.inner.list_tasks(status, limit)).await
+    }
+
+    async fn count_tasks_by_status(&self) -> Result<HashMap<TaskStatus, i64>, StorageError> {
+        timed("count_tasks_by_status", self.inner.count_tasks_by_status()).await
+    }
+
+    async fn delete_finished_tasks(&self, before: DateTime<Utc>) -> Result<u64, StorageError> {
+        timed("delete_finished_tasks", self.inner.delete_finished_tasks(before)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Zone Registry Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn load_zones(&self) -> Result<Vec<BiddingZone>, StorageError> {
+        timed("load_zones", self.inner.load_zones()).await
+    }
+
+    async fn get_zone_by_code(&self, zone_code: &str) -> Result<BiddingZone, StorageError> {
+        timed("get_zone_by_code", self.inner.get_zone_by_code(zone_code)).await
+    }
+
+    async fn get_zone_by_eic(&self, eic_code: &str) -> Result<BiddingZone, StorageError> {
+        timed("get_zone_by_eic", self.inner.get_zone_by_eic(eic_code)).await
+    }
+
+    async fn get_zones_by_country(&self, country_code: &str) -> Result<Vec<BiddingZone>, StorageError> {
+        timed("get_zones_by_country", self.inner.get_zones_by_country(country_code)).await
+    }
+
+    async fn get_countries(&self) -> Result<Vec<(String, String)>, StorageError> {
+        timed("get_countries", self.inner.get_countries()).await
+    }
+
+    async fn insert_missing_zones(&self, zones: &[ZoneDefinition]) -> Result<Vec<String>, StorageError> {
+        timed("insert_missing_zones", self.inner.insert_missing_zones(zones)).await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Log Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn log_fetch_start(
+        &self,
+        zone_code: Option<String>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        correlation_id: Option<String>,
+    ) -> Result<i64, StorageError> {
+        timed("log_fetch_start", self.inner.log_fetch_start(zone_code, period_start, period_end, correlation_id)).await
+    }
+
+    async fn log_fetch_complete(
+        &self,
+        fetch_id: i64,
+        status: FetchStatus,
+        records_inserted: i32,
+        error_message: Option<String>,
+        http_status: Option<i32>,
+        duration_ms: i32,
+    ) -> Result<(), StorageError> {
+        timed(
+            "log_fetch_complete",
+            self.inner
+                .log_fetch_complete(fetch_id, status, records_inserted, error_message, http_status, duration_ms),
+        )
+        .await
+    }
+
+    async fn log_fetch_endpoint(&self, fetch_id: i64, endpoint: &str) -> Result<(), StorageError> {
+        timed("log_fetch_endpoint", self.inner.log_fetch_endpoint(fetch_id, endpoint)).await
+    }
+
+    async fn get_recent_fetch_logs(&self, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        timed("get_recent_fetch_logs", self.inner.get_recent_fetch_logs(limit)).await
+    }
+
+    async fn list_fetch_logs(&self, filter: &FetchLogFilter, page: FetchLogPage, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        timed("list_fetch_logs", self.inner.list_fetch_logs(filter, page, limit)).await
+    }
+
+    async fn count_fetch_logs(&self, filter: &FetchLogFilter) -> Result<i64, StorageError> {
+        timed("count_fetch_logs", self.inner.count_fetch_logs(filter)).await
+    }
+
+    async fn get_fetch_logs_by_zone(&self, zone_code: &str, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        timed("get_fetch_logs_by_zone", self.inner.get_fetch_logs_by_zone(zone_code, limit)).await
+    }
+
+    async fn get_latest_fetch_logs(&self) -> Result<Vec<FetchLog>, StorageError> {
+        timed("get_latest_fetch_logs", self.inner.get_latest_fetch_logs()).await
+    }
+
+    async fn get_last_successful_fetch(&self) -> Result<Option<DateTime<Utc>>, StorageError> {
+        timed("get_last_successful_fetch", self.inner.get_last_successful_fetch()).await
+    }
+
+    async fn get_last_fetch_run(&self) -> Result<Option<DateTime<Utc>>, StorageError> {
+        timed("get_last_fetch_run", self.inner.get_last_fetch_run()).await
+    }
+
+    async fn delete_old_fetch_logs(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        timed("delete_old_fetch_logs", self.inner.delete_old_fetch_logs(older_than)).await
+    }
+
+    async fn has_day_ahead_data(&self, zone_code: &str, date: NaiveDate) -> Result<bool, StorageError> {
+        timed("has_day_ahead_data", self.inner.has_day_ahead_data(zone_code, date)).await
+    }
+
+    async fn find_gaps(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<DayGap>, StorageError> {
+        timed("find_gaps", self.inner.find_gaps(start_date, end_date, zone_codes)).await
+    }
+
+    async fn find_estimated_hours(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<(NaiveDate, String, i64)>, StorageError> {
+        timed("find_estimated_hours", self.inner.find_estimated_hours(start_date, end_date, zone_codes)).await
+    }
+
+    async fn get_table_stats(&self) -> Result<Vec<TableStats>, StorageError> {
+        timed("get_table_stats", self.inner.get_table_stats()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::TimeZone;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use super::*;
+    use crate::memory::InMemoryPriceStore;
+
+    #[tokio::test]
+    async fn test_records_duration_per_operation() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+
+        let store = InstrumentedStore::new(Arc::new(InMemoryPriceStore::new()));
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap();
+        store.health_check().await.unwrap();
+        store.get_prices_by_zone("NO1", Market::DayAhead, start, end).await.unwrap();
+        let streamed: Vec<_> = store.stream_prices_by_zone("NO1", Market::DayAhead, start, end).collect().await;
+        assert!(streamed.is_empty());
+        store.pool_status();
+
+        let samples: HashMap<String, usize> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, _, _, _)| key.key().name() == metrics::DATABASE_QUERY_DURATION_SECONDS)
+            .filter_map(|(key, _, _, value)| {
+                let operation = key.key().labels().find(|l| l.key() == "operation")?.value().to_string();
+                match value {
+                    DebugValue::Histogram(values) => Some((operation, values.len())),
+                    _ => None,
+                }
+            })
+            .collect();
+        assert_eq!(
+            samples,
+            HashMap::from([
+                ("health_check".to_string(), 1),
+                ("get_prices_by_zone".to_string(), 1),
+                ("stream_prices_by_zone".to_string(), 1),
+            ])
+        );
+    }
+}
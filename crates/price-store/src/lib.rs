@@ -0,0 +1,95 @@
+pub mod config;
+pub mod error;
+pub mod instrumented;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod memory;
+pub mod metrics;
+pub mod repository;
+pub mod retry;
+pub mod sqlite;
+pub mod store;
+pub mod stored_price;
+pub mod write_coordinator;
+pub mod zone_seed;
+
+use std::sync::Arc;
+
+use crate::config::{DatabaseBackend, DatabaseConfig};
+
+pub use error::StorageError;
+pub use instrumented::InstrumentedStore;
+pub use retry::retry_with_backoff;
+#[cfg(any(test, feature = "test-utils"))]
+pub use memory::InMemoryPriceStore;
+pub use repository::{PriceRepository, BULK_COPY_MIN_ROWS};
+pub use sqlite::SqlitePriceStore;
+pub use store::{DayGap, FetchLogFilter, FetchLogPage, PoolStatus, PriceCoverage, PriceStore, TableStats, ZoneStats, PRICE_PARTITION_MONTHS_AHEAD};
+pub use stored_price::StoredPrice;
+pub use write_coordinator::{WriteCoordinator, WritePriority};
+pub use zone_seed::{bundled_zones, sync_zones, ZoneSyncReport};
+
+/// Connect to the storage backend selected by `DatabaseConfig::backend`.
+/// The store records every operation's duration in the storage metrics.
+pub async fn connect(config: &DatabaseConfig) -> Result<Arc<dyn PriceStore>, StorageError> {
+    let store: Arc<dyn PriceStore> = match config.backend {
+        DatabaseBackend::Postgres => Arc::new(PriceRepository::from_config(config).await?),
+        DatabaseBackend::Sqlite => Arc::new(SqlitePriceStore::from_config(config).await?),
+    };
+    Ok(instrumented(store))
+}
+
+fn instrumented(store: Arc<dyn PriceStore>) -> Arc<dyn PriceStore> {
+    Arc::new(InstrumentedStore::new(store))
+}
+
+/// Apply every embedded migration, including those that rewrite stored
+/// prices and are therefore skipped at startup. For the `migrate`
+/// subcommand, run while the service is stopped.
+pub async fn migrate_offline(config: &DatabaseConfig) -> Result<(), StorageError> {
+    let config = DatabaseConfig {
+        run_migrations: false,
+        lazy_connect: false,
+        ..config.clone()
+    };
+    match config.backend {
+        DatabaseBackend::Postgres => PriceRepository::from_config(&config).await?.migrate_offline().await,
+        // SQLite migrates on connect
+        DatabaseBackend::Sqlite => SqlitePriceStore::from_config(&config).await.map(|_| ()),
+    }
+}
+
+/// For `lazy_connect`: wait until `store` answers, retrying with the
+/// configured backoff indefinitely, then apply migrations if enabled.
+pub async fn wait_until_connected(config: &DatabaseConfig, store: &dyn PriceStore) -> Result<(), StorageError> {
+    retry_with_backoff(config, None, "Database health check", || store.health_check()).await?;
+    if config.run_migrations {
+        store.migrate().await?;
+    }
+    Ok(())
+}
+
+/// [`connect`], plus the store API handlers read through: one that sends
+/// reads to `database.read_url` when it is set, otherwise the same store.
+pub async fn connect_with_reader(
+    config: &DatabaseConfig,
+) -> Result<(Arc<dyn PriceStore>, Arc<dyn PriceStore>), StorageError> {
+    match config.backend {
+        DatabaseBackend::Postgres => {
+            let primary = PriceRepository::from_config(config).await?;
+            let reader = primary.with_read_replica(config).await?;
+            let primary = instrumented(Arc::new(primary));
+            let reader = match reader {
+                Some(reader) => instrumented(Arc::new(reader)),
+                None => Arc::clone(&primary),
+            };
+            Ok((primary, reader))
+        }
+        DatabaseBackend::Sqlite if config.read_url.is_some() => Err(StorageError::InvalidInput(
+            "database.read_url is only supported with the Postgres backend".to_string(),
+        )),
+        DatabaseBackend::Sqlite => {
+            let store = instrumented(Arc::new(SqlitePriceStore::from_config(config).await?));
+            Ok((Arc::clone(&store), store))
+        }
+    }
+}
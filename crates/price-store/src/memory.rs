@@ -0,0 +1,1186 @@
+use std::cmp::Reverse;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, DurationRound, NaiveDate, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use rust_decimal::Decimal;
+
+use entsoe_core::models::{
+    AlertEvent, AlertRule, ApiKey, BackfillJob, BackfillJobStatus, BiddingZone, CrossBorderFlow, FetchLog, FetchRetry, FetchStatus, FlowKind, GenerationPoint,
+    IdempotencyRecord, LoadValue, Market, NameTranslation, NewAlertRule, NewBackfillJob, NewTask, Price, Task, TaskStatus, ZoneDefinition,
+};
+
+use super::error::StorageError;
+use super::store::{gap_check_day, DayGap, FetchLogFilter, FetchLogPage, PoolStatus, PriceCoverage, PriceStore, TableStats, ZoneStats};
+
+#[derive(Default)]
+struct MemoryState {
+    zones: Vec<BiddingZone>,
+    prices: BTreeMap<(String, Market, DateTime<Utc>), Price>,
+    generation: BTreeMap<(String, DateTime<Utc>, String), GenerationPoint>,
+    load: BTreeMap<(String, DateTime<Utc>), LoadValue>,
+    flows: BTreeMap<(DateTime<Utc>, String, String, FlowKind), CrossBorderFlow>,
+    translations: Vec<NameTranslation>,
+    fetch_logs: Vec<FetchLog>,
+    alert_rules: Vec<AlertRule>,
+    next_alert_rule_id: i64,
+    alert_events: BTreeMap<(i64, DateTime<Utc>), AlertEvent>,
+    api_keys: Vec<ApiKey>,
+    /// Holder and expiry per (job name, target date).
+    job_locks: HashMap<(String, NaiveDate), (String, DateTime<Utc>)>,
+    /// Paused scheduler jobs and when they were paused.
+    job_pauses: BTreeMap<String, DateTime<Utc>>,
+    idempotency_keys: HashMap<String, IdempotencyRecord>,
+    fetch_retries: BTreeMap<(String, NaiveDate), FetchRetry>,
+    backfill_jobs: Vec<BackfillJob>,
+    tasks: Vec<Task>,
+}
+
+/// In-memory `PriceStore` for tests. Not intended for production use: data
+/// lives only as long as the value and nothing is persisted.
+#[derive(Default)]
+pub struct InMemoryPriceStore {
+    state: Mutex<MemoryState>,
+}
+
+impl InMemoryPriceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_zones(zones: Vec<BiddingZone>) -> Self {
+        let store = Self::default();
+        store.state.lock().unwrap().zones = zones;
+        store
+    }
+
+    /// Build an active zone with sensible defaults for tests.
+    pub fn zone(zone_code: &str, country_code: &str, timezone: &str) -> BiddingZone {
+        BiddingZone {
+            zone_code: zone_code.to_string(),
+            zone_name: zone_code.to_string(),
+            country_code: country_code.to_string(),
+            country_name: country_code.to_string(),
+            eic_code: format!("EIC-{}", zone_code),
+            timezone: timezone.to_string(),
+            active: true,
+            valid_from: None,
+            valid_to: None,
+            predecessor_code: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Snapshot of all stored prices ordered by zone, market and timestamp.
+    pub fn prices(&self) -> Vec<Price> {
+        self.state.lock().unwrap().prices.values().cloned().collect()
+    }
+
+    /// Snapshot of all fetch log entries in insertion order.
+    pub fn fetch_logs(&self) -> Vec<FetchLog> {
+        self.state.lock().unwrap().fetch_logs.clone()
+    }
+}
+
+fn fetch_log_matches(log: &FetchLog, filter: &FetchLogFilter) -> bool {
+    filter.zone_code.as_ref().is_none_or(|zone_code| log.bidding_zone.as_ref() == Some(zone_code))
+        && filter.status.as_ref().is_none_or(|status| log.status == *status)
+        && filter.started_from.is_none_or(|from| log.fetch_started_at >= from)
+        && filter.started_before.is_none_or(|before| log.fetch_started_at < before)
+        && filter.correlation_id.as_ref().is_none_or(|id| log.correlation_id.as_ref() == Some(id))
+}
+
+#[async_trait]
+impl PriceStore for InMemoryPriceStore {
+    async fn migrate(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            active_connections: 0,
+            idle_connections: 0,
+            max_connections: 0,
+        }
+    }
+
+    async fn upsert_prices(&self, prices: &[Price]) -> Result<usize, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        for price in prices {
+            state
+                .prices
+                .insert((price.bidding_zone.clone(), price.market, price.timestamp), price.clone());
+        }
+        Ok(prices.len())
+    }
+
+    async fn get_prices_by_zone(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Price>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .prices
+            .range((zone_code.to_string(), market, start)..(zone_code.to_string(), market, end))
+            .map(|(_, p)| p.clone())
+            .collect())
+    }
+
+    fn stream_prices_by_zone<'a>(
+        &'a self,
+        zone_code: &'a str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxStream<'a, Result<Price, StorageError>> {
+        stream::once(self.get_prices_by_zone(zone_code, market, start, end))
+            .flat_map(|result| match result {
+                Ok(prices) => stream::iter(prices.into_iter().map(Ok)).boxed(),
+                Err(e) => stream::once(async { Err(e) }).boxed(),
+            })
+            .boxed()
+    }
+
+    async fn get_prices_by_country(
+        &self,
+        country_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<Price>>, StorageError> {
+        let zones = self.get_zones_by_country(country_code).await?;
+        let mut grouped = HashMap::new();
+        for zone in zones {
+            let prices = self.get_prices_by_zone(&zone.zone_code, market, start, end).await?;
+            if !prices.is_empty() {
+                grouped.insert(zone.zone_code, prices);
+            }
+        }
+        Ok(grouped)
+    }
+
+    async fn get_latest_prices(&self, since: Option<DateTime<Utc>>) -> Result<Vec<Price>, StorageError> {
+        let state = self.state.lock().unwrap();
+
+        let mut latest: BTreeMap<String, Price> = BTreeMap::new();
+        for ((zone, market, _), price) in &state.prices {
+            if *market == Market::DayAhead {
+                latest.insert(zone.clone(), price.clone());
+            }
+        }
+
+        Ok(latest
+            .into_values()
+            .filter(|p| since.is_none_or(|since| p.timestamp >= since))
+            .collect())
+    }
+
+    async fn get_current_prices(&self, at: DateTime<Utc>) -> Result<Vec<Price>, StorageError> {
+        let state = self.state.lock().unwrap();
+
+        let mut current: BTreeMap<String, Price> = BTreeMap::new();
+        for ((zone, market, timestamp), price) in &state.prices {
+            if *market == Market::DayAhead && *timestamp <= at && *timestamp > at - Duration::hours(1) {
+                current.insert(zone.clone(), price.clone());
+            }
+        }
+
+        Ok(current.into_values().collect())
+    }
+
+    async fn get_price_at(&self, zone_code: &str, market: Market, at: DateTime<Utc>) -> Result<Option<Price>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .prices
+            .range((zone_code.to_string(), market, at - Duration::days(1))..=(zone_code.to_string(), market, at))
+            .next_back()
+            .map(|(_, price)| price.clone())
+            .filter(|price| price.interval_end() > at))
+    }
+
+    async fn get_zone_stats(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<ZoneStats, StorageError> {
+        let prices = self.get_prices_by_zone(zone_code, market, start, end).await?;
+        let Some(first) = prices.first() else {
+            return Ok(ZoneStats::default());
+        };
+
+        let mut hours: Vec<DateTime<Utc>> = prices
+            .iter()
+            .map(|p| p.timestamp.duration_trunc(Duration::hours(1)).unwrap())
+            .collect();
+        hours.dedup();
+        let longest_gap = hours
+            .windows(2)
+            .map(|w| (w[1] - w[0]).num_hours() - 1)
+            .max()
+            .unwrap_or(0);
+        let total: Decimal = prices.iter().map(|p| p.price_kwh).sum();
+
+        Ok(ZoneStats {
+            price_count: prices.len() as i64,
+            min_price: prices.iter().map(|p| p.price_kwh).min(),
+            max_price: prices.iter().map(|p| p.price_kwh).max(),
+            avg_price: Some(total / Decimal::from(prices.len())),
+            first_timestamp: Some(first.timestamp),
+            last_timestamp: prices.last().map(|p| p.timestamp),
+            covered_hours: hours.len() as i64,
+            longest_interior_gap_hours: longest_gap,
+            currency: Some(first.currency.clone()),
+        })
+    }
+
+    async fn get_price_coverage(&self, zone_code: &str, market: Market) -> Result<Option<PriceCoverage>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let mut zone_prices = state
+            .prices
+            .values()
+            .filter(|p| p.bidding_zone == zone_code && p.market == market);
+        let Some(first) = zone_prices.next() else {
+            return Ok(None);
+        };
+        let last = zone_prices.next_back().unwrap_or(first);
+        Ok(Some(PriceCoverage {
+            first_timestamp: first.timestamp,
+            last_timestamp: last.timestamp,
+            last_resolution: last.resolution.clone(),
+        }))
+    }
+
+    async fn delete_old_prices(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.prices.len();
+        state.prices.retain(|(_, _, ts), _| *ts >= older_than);
+        Ok((before - state.prices.len()) as u64)
+    }
+
+    async fn ensure_price_partitions(&self, _from: NaiveDate, _months_ahead: u32) -> Result<Vec<String>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    async fn upsert_generation(&self, points: &[GenerationPoint]) -> Result<usize, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        for point in points {
+            state.generation.insert(
+                (point.bidding_zone.clone(), point.timestamp, point.psr_type.clone()),
+                point.clone(),
+            );
+        }
+        Ok(points.len())
+    }
+
+    async fn get_generation_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<GenerationPoint>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .generation
+            .values()
+            .filter(|p| p.bidding_zone == zone_code && p.timestamp >= start && p.timestamp < end)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_old_generation(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.generation.len();
+        state.generation.retain(|(_, ts, _), _| *ts >= older_than);
+        Ok((before - state.generation.len()) as u64)
+    }
+
+    async fn upsert_load(&self, values: &[LoadValue]) -> Result<usize, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        for value in values {
+            state
+                .load
+                .insert((value.bidding_zone.clone(), value.timestamp), value.clone());
+        }
+        Ok(values.len())
+    }
+
+    async fn get_load_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LoadValue>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .load
+            .values()
+            .filter(|v| v.bidding_zone == zone_code && v.timestamp >= start && v.timestamp < end)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_old_load(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.load.len();
+        state.load.retain(|(_, ts), _| *ts >= older_than);
+        Ok((before - state.load.len()) as u64)
+    }
+
+    async fn upsert_flows(&self, flows: &[CrossBorderFlow]) -> Result<usize, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        for flow in flows {
+            state.flows.insert(
+                (flow.timestamp, flow.from_zone.clone(), flow.to_zone.clone(), flow.kind),
+                flow.clone(),
+            );
+        }
+        Ok(flows.len())
+    }
+
+    async fn get_flows_by_zone(
+        &self,
+        zone_code: &str,
+        kind: FlowKind,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<CrossBorderFlow>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .flows
+            .values()
+            .filter(|f| {
+                (f.from_zone == zone_code || f.to_zone == zone_code)
+                    && f.kind == kind
+                    && f.timestamp >= start
+                    && f.timestamp < end
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_old_flows(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.flows.len();
+        state.flows.retain(|(ts, _, _, _), _| *ts >= older_than);
+        Ok((before - state.flows.len()) as u64)
+    }
+
+    async fn upsert_name_translations(&self, translations: &[NameTranslation]) -> Result<usize, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        for translation in translations {
+            state
+                .translations
+                .retain(|t| !(t.kind == translation.kind && t.code == translation.code && t.locale == translation.locale));
+            state.translations.push(translation.clone());
+        }
+        Ok(translations.len())
+    }
+
+    async fn get_name_translations(&self, locale: &str) -> Result<Vec<NameTranslation>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state.translations.iter().filter(|t| t.locale == locale).cloned().collect())
+    }
+
+    async fn create_alert_rule(&self, rule: &NewAlertRule) -> Result<AlertRule, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        state.next_alert_rule_id += 1;
+        let rule = AlertRule {
+            id: state.next_alert_rule_id,
+            zone_code: rule.zone_code.clone(),
+            comparison: rule.comparison,
+            threshold: rule.threshold,
+            channel: rule.channel,
+            target: rule.target.clone(),
+            created_at: Utc::now(),
+        };
+        state.alert_rules.push(rule.clone());
+        Ok(rule)
+    }
+
+    async fn list_alert_rules(&self, zone_code: Option<&str>) -> Result<Vec<AlertRule>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .alert_rules
+            .iter()
+            .filter(|rule| zone_code.is_none_or(|zone| rule.zone_code == zone))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_alert_rule(&self, id: i64) -> Result<AlertRule, StorageError> {
+        let state = self.state.lock().unwrap();
+        state
+            .alert_rules
+            .iter()
+            .find(|rule| rule.id == id)
+            .cloned()
+            .ok_or_else(|| StorageError::NotFound(format!("Alert rule not found: {}", id)))
+    }
+
+    async fn delete_alert_rule(&self, id: i64) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.alert_rules.len();
+        state.alert_rules.retain(|rule| rule.id != id);
+        state.alert_events.retain(|(rule_id, _), _| *rule_id != id);
+        Ok(state.alert_rules.len() < before)
+    }
+
+    async fn insert_alert_events(&self, events: &[AlertEvent]) -> Result<Vec<AlertEvent>, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let mut inserted = Vec::new();
+        for event in events {
+            if let Entry::Vacant(entry) = state.alert_events.entry((event.rule_id, event.price_timestamp)) {
+                entry.insert(event.clone());
+                inserted.push(event.clone());
+            }
+        }
+        Ok(inserted)
+    }
+
+    async fn complete_alert_event(
+        &self,
+        rule_id: i64,
+        price_timestamp: DateTime<Utc>,
+        delivered: bool,
+        error_message: Option<String>,
+    ) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let event = state
+            .alert_events
+            .get_mut(&(rule_id, price_timestamp))
+            .ok_or_else(|| StorageError::NotFound(format!("Alert event not found: {} at {}", rule_id, price_timestamp)))?;
+        event.delivered = Some(delivered);
+        event.error_message = error_message;
+        Ok(())
+    }
+
+    async fn get_alert_events(&self, rule_id: i64, limit: i64) -> Result<Vec<AlertEvent>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let mut events: Vec<AlertEvent> =
+            state.alert_events.values().filter(|event| event.rule_id == rule_id).cloned().collect();
+        events.sort_by_key(|event| Reverse((event.fired_at, event.price_timestamp)));
+        events.truncate(limit.max(0) as usize);
+        Ok(events)
+    }
+
+    async fn failed_alert_events(&self, from: DateTime<Utc>) -> Result<Vec<AlertEvent>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .alert_events
+            .values()
+            .filter(|event| event.delivered == Some(false) && event.price_timestamp >= from)
+            .cloned()
+            .collect())
+    }
+
+    async fn create_api_key(&self, key: &ApiKey) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        if state.api_keys.iter().any(|k| k.name == key.name || k.key_sha256 == key.key_sha256) {
+            return Err(StorageError::InvalidInput(format!("API key already exists: {}", key.name)));
+        }
+        state.api_keys.push(key.clone());
+        Ok(())
+    }
+
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, StorageError> {
+        let mut keys = self.state.lock().unwrap().api_keys.clone();
+        keys.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(keys)
+    }
+
+    async fn try_acquire_job_lock(
+        &self,
+        job_name: &str,
+        target_date: NaiveDate,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let key = (job_name.to_string(), target_date);
+        if state.job_locks.get(&key).is_some_and(|(_, held_until)| *held_until > now) {
+            return Ok(false);
+        }
+        state.job_locks.insert(key, (holder.to_string(), expires_at));
+        Ok(true)
+    }
+
+    async fn renew_job_lock(
+        &self,
+        job_name: &str,
+        target_date: NaiveDate,
+        holder: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        match state.job_locks.get_mut(&(job_name.to_string(), target_date)) {
+            Some((held_by, held_until)) if held_by == holder => {
+                *held_until = expires_at;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn release_job_lock(&self, job_name: &str, target_date: NaiveDate, holder: &str) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let key = (job_name.to_string(), target_date);
+        if state.job_locks.get(&key).is_some_and(|(held_by, _)| held_by == holder) {
+            state.job_locks.remove(&key);
+        }
+        Ok(())
+    }
+
+    async fn set_job_paused(&self, job_name: &str, paused: bool, now: DateTime<Utc>) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        if paused {
+            state.job_pauses.entry(job_name.to_string()).or_insert(now);
+        } else {
+            state.job_pauses.remove(job_name);
+        }
+        Ok(())
+    }
+
+    async fn paused_jobs(&self) -> Result<Vec<String>, StorageError> {
+        Ok(self.state.lock().unwrap().job_pauses.keys().cloned().collect())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Idempotency Key Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn claim_idempotency_key(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        if state.idempotency_keys.get(key).is_some_and(|record| record.expires_at > now) {
+            return Ok(false);
+        }
+        state.idempotency_keys.insert(
+            key.to_string(),
+            IdempotencyRecord {
+                key: key.to_string(),
+                fingerprint: fingerprint.to_string(),
+                status_code: None,
+                response_body: None,
+                created_at: now,
+                expires_at,
+            },
+        );
+        Ok(true)
+    }
+
+    async fn get_idempotency_key(&self, key: &str) -> Result<Option<IdempotencyRecord>, StorageError> {
+        Ok(self.state.lock().unwrap().idempotency_keys.get(key).cloned())
+    }
+
+    async fn complete_idempotency_key(
+        &self,
+        key: &str,
+        claimed_at: DateTime<Utc>,
+        status_code: i32,
+        response_body: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(record) = state.idempotency_keys.get_mut(key).filter(|record| record.created_at == claimed_at) {
+            record.status_code = Some(status_code);
+            record.response_body = Some(response_body.to_string());
+            record.expires_at = expires_at;
+        }
+        Ok(())
+    }
+
+    async fn release_idempotency_key(&self, key: &str, claimed_at: DateTime<Utc>) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        if state
+            .idempotency_keys
+            .get(key)
+            .is_some_and(|record| record.created_at == claimed_at && record.status_code.is_none())
+        {
+            state.idempotency_keys.remove(key);
+        }
+        Ok(())
+    }
+
+    async fn delete_expired_idempotency_keys(&self, now: DateTime<Utc>) -> Result<u64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.idempotency_keys.len();
+        state.idempotency_keys.retain(|_, record| record.expires_at > now);
+        Ok((before - state.idempotency_keys.len()) as u64)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Retry Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn enqueue_fetch_retries(&self, retries: &[FetchRetry]) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        for retry in retries {
+            match state.fetch_retries.entry((retry.zone_code.clone(), retry.target_date)) {
+                Entry::Occupied(mut queued) => queued.get_mut().last_error = retry.last_error.clone(),
+                Entry::Vacant(slot) => {
+                    slot.insert(retry.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_due_fetch_retries(&self, now: DateTime<Utc>) -> Result<Vec<FetchRetry>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let mut due: Vec<FetchRetry> = state
+            .fetch_retries
+            .values()
+            .filter(|r| r.next_attempt_at <= now && r.expires_at > now)
+            .cloned()
+            .collect();
+        due.sort_by_key(|r| r.next_attempt_at);
+        Ok(due)
+    }
+
+    async fn reschedule_fetch_retry(
+        &self,
+        zone_code: &str,
+        target_date: NaiveDate,
+        next_attempt_at: DateTime<Utc>,
+        error: Option<&str>,
+    ) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(retry) = state.fetch_retries.get_mut(&(zone_code.to_string(), target_date)) {
+            retry.attempts += 1;
+            retry.next_attempt_at = next_attempt_at;
+            retry.last_error = error.map(str::to_string);
+        }
+        Ok(())
+    }
+
+    async fn delete_fetch_retry(&self, zone_code: &str, target_date: NaiveDate) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        state.fetch_retries.remove(&(zone_code.to_string(), target_date));
+        Ok(())
+    }
+
+    async fn delete_expired_fetch_retries(&self, now: DateTime<Utc>) -> Result<u64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.fetch_retries.len();
+        state.fetch_retries.retain(|_, r| r.expires_at > now);
+        Ok((before - state.fetch_retries.len()) as u64)
+    }
+
+    async fn create_backfill_job(&self, job: &NewBackfillJob, now: DateTime<Utc>) -> Result<BackfillJob, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let job = BackfillJob {
+            id: state.backfill_jobs.len() as i64 + 1,
+            start_date: job.start_date,
+            end_date: job.end_date,
+            zones: job.zones.clone(),
+            status: BackfillJobStatus::Running,
+            dates_total: job.dates_total(),
+            dates_done: 0,
+            prices_stored: 0,
+            errors: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            finished_at: None,
+        };
+        state.backfill_jobs.push(job.clone());
+        Ok(job)
+    }
+
+    async fn get_backfill_job(&self, id: i64) -> Result<BackfillJob, StorageError> {
+        let state = self.state.lock().unwrap();
+        state
+            .backfill_jobs
+            .iter()
+            .find(|job| job.id == id)
+            .cloned()
+            .ok_or_else(|| StorageError::NotFound(format!("Backfill job not found: {}", id)))
+    }
+
+    async fn update_backfill_job(&self, job: &BackfillJob) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        match state.backfill_jobs.iter_mut().find(|stored| stored.id == job.id) {
+            Some(stored) if stored.status == BackfillJobStatus::Running => {
+                *stored = job.clone();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn cancel_backfill_job(&self, id: i64, now: DateTime<Utc>) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        match state.backfill_jobs.iter_mut().find(|job| job.id == id) {
+            Some(job) if job.status == BackfillJobStatus::Running => {
+                job.status = BackfillJobStatus::Cancelled;
+                job.updated_at = now;
+                job.finished_at = Some(now);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn enqueue_task(&self, task: &NewTask, now: DateTime<Utc>) -> Result<Option<Task>, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let duplicate = task.dedupe_key.is_some()
+            && state
+                .tasks
+                .iter()
+                .any(|queued| queued.dedupe_key == task.dedupe_key && !queued.status.is_finished());
+        if duplicate {
+            return Ok(None);
+        }
+        let task = Task {
+            id: state.tasks.last().map_or(1, |last| last.id + 1),
+            kind: task.kind.clone(),
+            payload: task.payload.clone(),
+            dedupe_key: task.dedupe_key.clone(),
+            status: TaskStatus::Queued,
+            attempts: 0,
+            max_attempts: task.max_attempts,
+            last_error: None,
+            run_after: task.run_after,
+            lease_expires_at: None,
+            created_at: now,
+            updated_at: now,
+            finished_at: None,
+        };
+        state.tasks.push(task.clone());
+        Ok(Some(task))
+    }
+
+    async fn claim_task(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>) -> Result<Option<Task>, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let due = state
+            .tasks
+            .iter_mut()
+            .filter(|task| match task.status {
+                TaskStatus::Queued => task.run_after <= now,
+                TaskStatus::Running => task.lease_expires_at.is_some_and(|lease| lease <= now),
+                _ => false,
+            })
+            .min_by_key(|task| (task.run_after, task.id));
+        Ok(due.map(|task| {
+            task.status = TaskStatus::Running;
+            task.attempts += 1;
+            task.lease_expires_at = Some(lease_until);
+            task.updated_at = now;
+            task.clone()
+        }))
+    }
+
+    async fn renew_task_lease(&self, id: i64, attempt: i32, lease_until: DateTime<Utc>) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        match state.tasks.iter_mut().find(|task| task.id == id) {
+            Some(task) if task.status == TaskStatus::Running && task.attempts == attempt => {
+                task.lease_expires_at = Some(lease_until);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn finish_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        status: TaskStatus,
+        error: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        match state.tasks.iter_mut().find(|task| task.id == id) {
+            Some(task) if task.status == TaskStatus::Running && task.attempts == attempt => {
+                task.status = status;
+                if let Some(error) = error {
+                    task.last_error = Some(error.to_string());
+                }
+                task.lease_expires_at = None;
+                task.updated_at = now;
+                task.finished_at = Some(now);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn retry_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        error: &str,
+        run_after: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        match state.tasks.iter_mut().find(|task| task.id == id) {
+            Some(task) if task.status == TaskStatus::Running && task.attempts == attempt => {
+                task.status = TaskStatus::Queued;
+                task.last_error = Some(error.to_string());
+                task.run_after = run_after;
+                task.lease_expires_at = None;
+                task.updated_at = now;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn get_task(&self, id: i64) -> Result<Task, StorageError> {
+        let state = self.state.lock().unwrap();
+        state
+            .tasks
+            .iter()
+            .find(|task| task.id == id)
+            .cloned()
+            .ok_or_else(|| StorageError::NotFound(format!("Task not found: {}", id)))
+    }
+
+    async fn list_tasks(&self, status: Option<TaskStatus>, limit: i64) -> Result<Vec<Task>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let mut tasks: Vec<Task> = state
+            .tasks
+            .iter()
+            .filter(|task| status.is_none_or(|status| task.status == status))
+            .cloned()
+            .collect();
+        tasks.sort_by_key(|task| Reverse((task.created_at, task.id)));
+        tasks.truncate(limit.max(0) as usize);
+        Ok(tasks)
+    }
+
+    async fn count_tasks_by_status(&self) -> Result<HashMap<TaskStatus, i64>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let mut counts = HashMap::new();
+        for task in &state.tasks {
+            *counts.entry(task.status).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    async fn delete_finished_tasks(&self, before: DateTime<Utc>) -> Result<u64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before_count = state.tasks.len();
+        state
+            .tasks
+            .retain(|task| !(task.status.is_finished() && task.finished_at.is_some_and(|finished| finished < before)));
+        Ok((before_count - state.tasks.len()) as u64)
+    }
+
+    async fn load_zones(&self) -> Result<Vec<BiddingZone>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let mut zones: Vec<BiddingZone> = state.zones.iter().filter(|z| z.active).cloned().collect();
+        zones.sort_by(|a, b| (&a.country_code, &a.zone_code).cmp(&(&b.country_code, &b.zone_code)));
+        Ok(zones)
+    }
+
+    async fn get_zone_by_code(&self, zone_code: &str) -> Result<BiddingZone, StorageError> {
+        let state = self.state.lock().unwrap();
+        state
+            .zones
+            .iter()
+            .find(|z| z.zone_code == zone_code)
+            .cloned()
+            .ok_or_else(|| StorageError::NotFound(format!("Zone not found: {}", zone_code)))
+    }
+
+    async fn get_zone_by_eic(&self, eic_code: &str) -> Result<BiddingZone, StorageError> {
+        let state = self.state.lock().unwrap();
+        state
+            .zones
+            .iter()
+            .find(|z| z.eic_code == eic_code)
+            .cloned()
+            .ok_or_else(|| StorageError::NotFound(format!("Zone not found for EIC: {}", eic_code)))
+    }
+
+    async fn get_zones_by_country(&self, country_code: &str) -> Result<Vec<BiddingZone>, StorageError> {
+        Ok(self
+            .load_zones()
+            .await?
+            .into_iter()
+            .filter(|z| z.country_code == country_code)
+            .collect())
+    }
+
+    async fn get_countries(&self) -> Result<Vec<(String, String)>, StorageError> {
+        let mut countries: Vec<(String, String)> = self
+            .load_zones()
+            .await?
+            .into_iter()
+            .map(|z| (z.country_code, z.country_name))
+            .collect();
+        countries.sort();
+        countries.dedup();
+        Ok(countries)
+    }
+
+    async fn insert_missing_zones(&self, zones: &[ZoneDefinition]) -> Result<Vec<String>, StorageError> {
+        let today = Utc::now().date_naive();
+        let mut state = self.state.lock().unwrap();
+        let mut inserted = Vec::new();
+        for zone in zones {
+            let exists = state
+                .zones
+                .iter()
+                .any(|z| z.zone_code == zone.zone_code || z.eic_code == zone.eic_code);
+            if exists {
+                continue;
+            }
+            state.zones.push(BiddingZone {
+                zone_code: zone.zone_code.clone(),
+                zone_name: zone.zone_name.clone(),
+                country_code: zone.country_code.clone(),
+                country_name: zone.country_name.clone(),
+                eic_code: zone.eic_code.clone(),
+                timezone: zone.timezone.clone(),
+                active: zone.is_active_on(today),
+                valid_from: zone.valid_from,
+                valid_to: zone.valid_to,
+                predecessor_code: zone.predecessor_code.clone(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            });
+            inserted.push(zone.zone_code.clone());
+        }
+        Ok(inserted)
+    }
+
+    async fn log_fetch_start(
+        &self,
+        zone_code: Option<String>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        correlation_id: Option<String>,
+    ) -> Result<i64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let mut log = FetchLog::new(zone_code, period_start, period_end);
+        log.id = state.fetch_logs.len() as i64 + 1;
+        log.correlation_id = correlation_id;
+        let id = log.id;
+        state.fetch_logs.push(log);
+        Ok(id)
+    }
+
+    async fn log_fetch_complete(
+        &self,
+        fetch_id: i64,
+        status: FetchStatus,
+        records_inserted: i32,
+        error_message: Option<String>,
+        http_status: Option<i32>,
+        duration_ms: i32,
+    ) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let log = state
+            .fetch_logs
+            .iter_mut()
+            .find(|l| l.id == fetch_id)
+            .ok_or_else(|| StorageError::NotFound(format!("Fetch log not found: {}", fetch_id)))?;
+
+        log.fetch_completed_at = Some(Utc::now());
+        log.status = status;
+        log.records_inserted = Some(records_inserted);
+        log.error_message = error_message;
+        log.http_status = http_status;
+        log.duration_ms = Some(duration_ms);
+        Ok(())
+    }
+
+    async fn log_fetch_endpoint(&self, fetch_id: i64, endpoint: &str) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(log) = state.fetch_logs.iter_mut().find(|l| l.id == fetch_id) {
+            log.endpoint = Some(endpoint.to_string());
+        }
+        Ok(())
+    }
+
+    async fn get_recent_fetch_logs(&self, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state.fetch_logs.iter().rev().take(limit as usize).cloned().collect())
+    }
+
+    async fn list_fetch_logs(&self, filter: &FetchLogFilter, page: FetchLogPage, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let mut logs: Vec<&FetchLog> = state.fetch_logs.iter().filter(|log| fetch_log_matches(log, filter)).collect();
+        logs.sort_by_key(|log| Reverse((log.fetch_started_at, log.id)));
+        let skip = match page {
+            FetchLogPage::Offset(offset) => offset.max(0) as usize,
+            FetchLogPage::After { started_at, id } => logs
+                .iter()
+                .take_while(|log| (log.fetch_started_at, log.id) >= (started_at, id))
+                .count(),
+        };
+        Ok(logs.into_iter().skip(skip).take(limit.max(0) as usize).cloned().collect())
+    }
+
+    async fn count_fetch_logs(&self, filter: &FetchLogFilter) -> Result<i64, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state.fetch_logs.iter().filter(|log| fetch_log_matches(log, filter)).count() as i64)
+    }
+
+    async fn get_last_successful_fetch(&self) -> Result<Option<DateTime<Utc>>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .fetch_logs
+            .iter()
+            .filter(|l| matches!(l.status, FetchStatus::Success))
+            .filter_map(|l| l.fetch_completed_at)
+            .max())
+    }
+
+    async fn get_last_fetch_run(&self) -> Result<Option<DateTime<Utc>>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .fetch_logs
+            .iter()
+            .filter(|l| l.bidding_zone.is_none())
+            .map(|l| l.fetch_started_at)
+            .max())
+    }
+
+    async fn get_fetch_logs_by_zone(&self, zone_code: &str, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .fetch_logs
+            .iter()
+            .rev()
+            .filter(|l| l.bidding_zone.as_deref() == Some(zone_code))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_latest_fetch_logs(&self) -> Result<Vec<FetchLog>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let mut latest: BTreeMap<&str, &FetchLog> = BTreeMap::new();
+        for log in &state.fetch_logs {
+            if let Some(zone_code) = log.bidding_zone.as_deref() {
+                latest.insert(zone_code, log);
+            }
+        }
+        Ok(latest.into_values().cloned().collect())
+    }
+
+    async fn delete_old_fetch_logs(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.fetch_logs.len();
+        state.fetch_logs.retain(|l| l.fetch_started_at >= older_than);
+        Ok((before - state.fetch_logs.len()) as u64)
+    }
+
+    async fn has_day_ahead_data(&self, zone_code: &str, date: NaiveDate) -> Result<bool, StorageError> {
+        let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let end = start + Duration::days(1);
+        Ok(!self.get_prices_by_zone(zone_code, Market::DayAhead, start, end).await?.is_empty())
+    }
+
+    async fn find_gaps(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<DayGap>, StorageError> {
+        let mut sorted_zones: Vec<&String> = zone_codes.iter().collect();
+        sorted_zones.sort();
+        let timezones: HashMap<String, String> = {
+            let state = self.state.lock().unwrap();
+            state.zones.iter().map(|z| (z.zone_code.clone(), z.timezone.clone())).collect()
+        };
+
+        let mut gaps = Vec::new();
+        let mut current = start_date;
+        while current <= end_date {
+            for zone_code in &sorted_zones {
+                let day = gap_check_day(current, timezones.get(*zone_code).map(String::as_str));
+                let hours: std::collections::HashSet<DateTime<Utc>> = self
+                    .get_prices_by_zone(zone_code, Market::DayAhead, day.start, day.end)
+                    .await?
+                    .iter()
+                    .filter_map(|p| p.timestamp.duration_trunc(Duration::hours(1)).ok())
+                    .collect();
+                let hours = hours.len() as i64;
+                if hours < day.hours() {
+                    gaps.push(DayGap {
+                        date: current,
+                        zone_code: (*zone_code).clone(),
+                        hours,
+                        expected_hours: day.hours(),
+                    });
+                }
+            }
+            current = current.succ_opt().unwrap();
+        }
+        Ok(gaps)
+    }
+
+    async fn find_estimated_hours(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<(NaiveDate, String, i64)>, StorageError> {
+        let mut hours: std::collections::BTreeMap<(NaiveDate, String), std::collections::HashSet<DateTime<Utc>>> =
+            std::collections::BTreeMap::new();
+        for price in self.prices() {
+            let date = price.timestamp.date_naive();
+            if price.market == Market::DayAhead
+                && price.filled()
+                && (start_date..=end_date).contains(&date)
+                && zone_codes.contains(&price.bidding_zone)
+            {
+                if let Ok(hour) = price.timestamp.duration_trunc(Duration::hours(1)) {
+                    hours.entry((date, price.bidding_zone)).or_default().insert(hour);
+                }
+            }
+        }
+        Ok(hours
+            .into_iter()
+            .map(|((date, zone_code), hours)| (date, zone_code, hours.len() as i64))
+            .collect())
+    }
+
+    async fn get_table_stats(&self) -> Result<Vec<TableStats>, StorageError> {
+        let state = self.state.lock().unwrap();
+        let table = |table_name: &str, row_count: usize, oldest_entry: Option<DateTime<Utc>>| TableStats {
+            table_name: table_name.to_string(),
+            row_count: row_count as i64,
+            table_bytes: None,
+            index_bytes: None,
+            oldest_entry,
+        };
+
+        Ok(vec![
+            table(
+                "electricity_prices",
+                state.prices.len(),
+                state.prices.values().map(|p| p.timestamp).min(),
+            ),
+            table(
+                "fetch_log",
+                state.fetch_logs.len(),
+                state.fetch_logs.iter().map(|l| l.fetch_started_at).min(),
+            ),
+            table("bidding_zones", state.zones.len(), None),
+        ])
+    }
+}
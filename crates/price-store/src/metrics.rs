@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use entsoe_core::metrics::{describe, MetricDescription, MetricKind};
+use metrics::{counter, gauge, histogram, Unit};
+use tracing::warn;
+
+pub const DATABASE_QUERY_DURATION_SECONDS: &str = "database_query_duration_seconds";
+pub const DATABASE_SLOW_QUERIES_TOTAL: &str = "database_slow_queries_total";
+pub const DATABASE_WRITE_QUEUE_DEPTH: &str = "database_write_queue_depth";
+pub const DATABASE_WRITE_WAIT_SECONDS: &str = "database_write_wait_seconds";
+
+/// Metrics recorded by the storage layer.
+pub const STORAGE_METRICS: &[MetricDescription] = &[
+    describe(
+        DATABASE_QUERY_DURATION_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["operation"],
+        "Storage query latency by operation",
+    ),
+    describe(
+        DATABASE_SLOW_QUERIES_TOTAL,
+        MetricKind::Counter,
+        Some(Unit::Count),
+        &["operation"],
+        "Storage queries at or above database.slow_query_threshold_ms, by operation",
+    ),
+    describe(
+        DATABASE_WRITE_QUEUE_DEPTH,
+        MetricKind::Gauge,
+        None,
+        &["priority"],
+        "Price upserts waiting for the write coordinator",
+    ),
+    describe(
+        DATABASE_WRITE_WAIT_SECONDS,
+        MetricKind::Histogram,
+        Some(Unit::Seconds),
+        &["priority"],
+        "Time price upserts waited for the write coordinator",
+    ),
+];
+
+/// Slow query threshold in milliseconds; zero when unset.
+static SLOW_QUERY_THRESHOLD_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Count and log queries recorded at or above `threshold` as slow.
+pub fn set_slow_query_threshold(threshold: Option<Duration>) {
+    let threshold_ms = threshold.map_or(0, |threshold| threshold.as_millis() as u64);
+    SLOW_QUERY_THRESHOLD_MS.store(threshold_ms, Ordering::Relaxed);
+}
+
+pub fn record_db_query_duration(operation: &str, duration: Duration) {
+    histogram!(DATABASE_QUERY_DURATION_SECONDS, "operation" => operation.to_string())
+        .record(duration.as_secs_f64());
+
+    let threshold_ms = SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed);
+    if threshold_ms > 0 && duration >= Duration::from_millis(threshold_ms) {
+        counter!(DATABASE_SLOW_QUERIES_TOTAL, "operation" => operation.to_string()).increment(1);
+        warn!(
+            operation = %operation,
+            duration_ms = duration.as_millis() as u64,
+            threshold_ms = threshold_ms,
+            "Slow database query"
+        );
+    }
+}
+
+pub fn update_write_queue_depth(priority: &str, depth: usize) {
+    gauge!(DATABASE_WRITE_QUEUE_DEPTH, "priority" => priority.to_string()).set(depth as f64);
+}
+
+pub fn record_write_wait(priority: &str, duration: Duration) {
+    histogram!(DATABASE_WRITE_WAIT_SECONDS, "priority" => priority.to_string()).record(duration.as_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use super::*;
+
+    #[test]
+    fn test_counts_queries_at_or_above_the_threshold() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        set_slow_query_threshold(Some(Duration::from_millis(100)));
+        ::metrics::with_local_recorder(&recorder, || {
+            record_db_query_duration("get_prices_by_zone", Duration::from_millis(100));
+            record_db_query_duration("get_prices_by_zone", Duration::from_millis(250));
+            record_db_query_duration("health_check", Duration::from_millis(99));
+        });
+        set_slow_query_threshold(None);
+
+        let slow: Vec<(String, u64)> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, _, _, _)| key.key().name() == DATABASE_SLOW_QUERIES_TOTAL)
+            .filter_map(|(key, _, _, value)| {
+                let operation = key.key().labels().find(|l| l.key() == "operation")?.value().to_string();
+                match value {
+                    DebugValue::Counter(count) => Some((operation, count)),
+                    _ => None,
+                }
+            })
+            .collect();
+        assert_eq!(slow, vec![("get_prices_by_zone".to_string(), 2)]);
+    }
+}
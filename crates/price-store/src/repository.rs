@@ -0,0 +1,2178 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc};
+use futures::stream::{BoxStream, StreamExt};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgRow};
+use sqlx::{ConnectOptions, PgPool, Row};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration as StdDuration;
+
+use crate::config::DatabaseConfig;
+use crate::metrics;
+use crate::retry::retry_with_backoff;
+use entsoe_core::models::{
+    AlertChannel, AlertComparison, AlertEvent, AlertRule, ApiKey, ApiRole, BackfillJob, BackfillJobStatus, BiddingZone, CrossBorderFlow, FetchLog, FetchRetry, FetchStatus,
+    FlowKind, GenerationPoint, IdempotencyRecord, LoadValue, Market, NameTranslation, NewAlertRule, NewBackfillJob, NewTask, Price,
+    Task, TaskStatus, ZoneDefinition,
+};
+
+use super::error::StorageError;
+use super::store::{DayGap, FetchLogFilter, FetchLogPage, PoolStatus, PriceCoverage, PriceStore, TableStats, ZoneStats};
+use super::stored_price::{prices_from_rows, StoredPrice, COPY_COLUMNS};
+
+/// Batches of at least this many prices are written with `COPY` rather than
+/// a single UNNEST upsert.
+pub const BULK_COPY_MIN_ROWS: usize = 10_000;
+
+/// Rows encoded and sent per `COPY` chunk, so a large batch is streamed
+/// rather than encoded whole before sending.
+const COPY_CHUNK_ROWS: usize = 1_000;
+
+/// Migrations that rewrite a whole table, by version. Startup refuses to
+/// apply them to a database that already holds prices; the `migrate`
+/// subcommand applies them while the service is stopped.
+const OFFLINE_MIGRATIONS: &[(i64, &str)] = &[(20250610000000, "partition_electricity_prices")];
+
+pub struct PriceRepository {
+    pool: PgPool,
+    /// Where read queries go: a read replica for the API's repository,
+    /// otherwise the same pool as writes.
+    read_pool: PgPool,
+    /// First days of the UTC months whose price partition is known to exist.
+    partitions: Mutex<HashSet<NaiveDate>>,
+}
+
+/// Open a pool to `url` with `config`'s sizing and slow statement logging,
+/// retrying with backoff while the server is unreachable. With
+/// `lazy_connect` the pool connects on first use instead.
+async fn connect_pool(config: &DatabaseConfig, url: &str) -> Result<PgPool, StorageError> {
+    let mut options = PgConnectOptions::from_str(url)?;
+    if let Some(threshold_ms) = config.slow_query_threshold_ms {
+        options = options.log_slow_statements(
+            log::LevelFilter::Warn,
+            StdDuration::from_millis(threshold_ms),
+        );
+        metrics::set_slow_query_threshold(Some(StdDuration::from_millis(threshold_ms)));
+    }
+    if let Some(timeout_ms) = config.statement_timeout_ms {
+        options = options.options([("statement_timeout", timeout_ms.to_string())]);
+    }
+
+    let pool_options = PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(StdDuration::from_secs(config.connect_timeout_seconds));
+    if config.lazy_connect {
+        return Ok(pool_options.connect_lazy_with(options));
+    }
+    retry_with_backoff(config, Some(config.connect_retries), "Postgres connect", || {
+        let pool_options = pool_options.clone();
+        let options = options.clone();
+        async move { Ok(pool_options.connect_with(options).await?) }
+    })
+    .await
+}
+
+fn alert_rule_from_row(row: &PgRow) -> Result<AlertRule, StorageError> {
+    let comparison: String = row.try_get("comparison")?;
+    let channel: String = row.try_get("channel")?;
+    Ok(AlertRule {
+        id: row.try_get("id")?,
+        zone_code: row.try_get("zone_code")?,
+        comparison: AlertComparison::parse(&comparison)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored alert comparison: {}", comparison)))?,
+        threshold: row.try_get("threshold")?,
+        channel: AlertChannel::parse(&channel)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored alert channel: {}", channel)))?,
+        target: row.try_get("target")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+fn backfill_job_from_row(row: &PgRow) -> Result<BackfillJob, StorageError> {
+    let status: String = row.try_get("status")?;
+    let zones: Option<String> = row.try_get("zones")?;
+    let errors: String = row.try_get("errors")?;
+    Ok(BackfillJob {
+        id: row.try_get("id")?,
+        start_date: row.try_get("start_date")?,
+        end_date: row.try_get("end_date")?,
+        zones: zones.as_deref().map(|zones| json_list("zones", zones)).transpose()?,
+        status: BackfillJobStatus::parse(&status)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored backfill job status: {}", status)))?,
+        dates_total: row.try_get("dates_total")?,
+        dates_done: row.try_get("dates_done")?,
+        prices_stored: row.try_get("prices_stored")?,
+        errors: json_list("errors", &errors)?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+        finished_at: row.try_get("finished_at")?,
+    })
+}
+
+/// [`FetchLogFilter`] as a condition on binds `$1` to `$5`.
+const FETCH_LOG_FILTER: &str = "($1::TEXT IS NULL OR bidding_zone = $1) AND ($2::TEXT IS NULL OR status = $2) \
+                                AND ($3::TIMESTAMPTZ IS NULL OR fetch_started_at >= $3) \
+                                AND ($4::TIMESTAMPTZ IS NULL OR fetch_started_at < $4) \
+                                AND ($5::TEXT IS NULL OR correlation_id = $5)";
+
+const TASK_COLUMNS: &str = "id, kind, payload, dedupe_key, status, attempts, max_attempts, last_error, run_after, \
+                            lease_expires_at, created_at, updated_at, finished_at";
+
+fn task_from_row(row: &PgRow) -> Result<Task, StorageError> {
+    let status: String = row.try_get("status")?;
+    Ok(Task {
+        id: row.try_get("id")?,
+        kind: row.try_get("kind")?,
+        payload: row.try_get("payload")?,
+        dedupe_key: row.try_get("dedupe_key")?,
+        status: TaskStatus::parse(&status)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored task status: {}", status)))?,
+        attempts: row.try_get("attempts")?,
+        max_attempts: row.try_get("max_attempts")?,
+        last_error: row.try_get("last_error")?,
+        run_after: row.try_get("run_after")?,
+        lease_expires_at: row.try_get("lease_expires_at")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+        finished_at: row.try_get("finished_at")?,
+    })
+}
+
+/// A JSON array of strings stored in a text column.
+fn json_list(column: &str, value: &str) -> Result<Vec<String>, StorageError> {
+    serde_json::from_str(value).map_err(|e| StorageError::QueryError(format!("Invalid stored {}: {}", column, e)))
+}
+
+fn alert_event_from_row(row: &PgRow) -> Result<AlertEvent, StorageError> {
+    Ok(AlertEvent {
+        rule_id: row.try_get("rule_id")?,
+        price_timestamp: row.try_get("price_timestamp")?,
+        price_kwh: row.try_get("price_kwh")?,
+        fired_at: row.try_get("fired_at")?,
+        delivered: row.try_get("delivered")?,
+        error_message: row.try_get("error_message")?,
+    })
+}
+
+fn api_key_from_row(row: &PgRow) -> Result<ApiKey, StorageError> {
+    let role: String = row.try_get("role")?;
+    Ok(ApiKey {
+        name: row.try_get("name")?,
+        key_sha256: row.try_get("key_sha256")?,
+        role: ApiRole::parse(&role)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored API key role: {}", role)))?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+impl PriceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            read_pool: pool.clone(),
+            pool,
+            partitions: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub async fn from_config(config: &DatabaseConfig) -> Result<Self, StorageError> {
+        let repository = Self::new(connect_pool(config, &config.url).await?);
+        // A lazy pool has no connection yet; the caller migrates once
+        // `wait_until_connected` succeeds
+        if config.run_migrations && !config.lazy_connect {
+            repository.migrate().await?;
+        }
+        Ok(repository)
+    }
+
+    /// A repository that writes through this one's pool but sends read
+    /// queries to the replica at `database.read_url`, or `None` when no
+    /// replica is configured. Reads may lag writes by the replication delay.
+    pub async fn with_read_replica(&self, config: &DatabaseConfig) -> Result<Option<Self>, StorageError> {
+        let Some(read_url) = &config.read_url else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            pool: self.pool.clone(),
+            read_pool: connect_pool(config, read_url).await?,
+            partitions: Mutex::new(HashSet::new()),
+        }))
+    }
+
+    /// Apply every embedded migration, including the ones in
+    /// [`OFFLINE_MIGRATIONS`] that [`migrate`](PriceStore::migrate) leaves
+    /// alone. For the `migrate` subcommand, with the service stopped.
+    pub async fn migrate_offline(&self) -> Result<(), StorageError> {
+        // database.statement_timeout_ms bounds the service's queries, not a
+        // migration rewriting a table
+        let options = (*self.pool.connect_options()).clone().options([("statement_timeout", "0")]);
+        let pool = PgPoolOptions::new().max_connections(1).connect_with(options).await?;
+        let migrated = sqlx::migrate!("../../migrations").run(&pool).await;
+        pool.close().await;
+        migrated.map_err(|e| StorageError::PoolError(format!("Postgres migration failed: {}", e)))
+    }
+
+    /// The first of [`OFFLINE_MIGRATIONS`] still to be applied to a database
+    /// that holds prices. A new database is migrated in one go at startup.
+    async fn pending_offline_migration(&self) -> Result<Option<&'static (i64, &'static str)>, StorageError> {
+        let migrated: bool = sqlx::query_scalar(
+            "SELECT to_regclass('_sqlx_migrations') IS NOT NULL AND to_regclass('electricity_prices') IS NOT NULL",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        if !migrated {
+            return Ok(None);
+        }
+        let versions: Vec<i64> = OFFLINE_MIGRATIONS.iter().map(|(version, _)| *version).collect();
+        let applied: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE version = ANY($1) AND success")
+            .bind(&versions)
+            .fetch_all(&self.pool)
+            .await?;
+        let Some(pending) = OFFLINE_MIGRATIONS.iter().find(|(version, _)| !applied.contains(version)) else {
+            return Ok(None);
+        };
+        let has_prices: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM electricity_prices)")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(has_prices.then_some(pending))
+    }
+
+    /// Create the monthly partitions `prices` fall in before they are
+    /// written, so they never land in the default partition.
+    async fn ensure_partitions_for(&self, prices: &[Price]) -> Result<(), StorageError> {
+        let months: BTreeSet<NaiveDate> = prices
+            .iter()
+            .map(|price| price.timestamp.date_naive().with_day(1).unwrap())
+            .collect();
+        let missing: Vec<NaiveDate> = {
+            let known = self.partitions.lock().unwrap();
+            months.into_iter().filter(|month| !known.contains(month)).collect()
+        };
+        for month in missing {
+            let _: bool = sqlx::query_scalar("SELECT create_electricity_prices_partition($1)")
+                .bind(month)
+                .fetch_one(&self.pool)
+                .await?;
+            self.partitions.lock().unwrap().insert(month);
+        }
+        Ok(())
+    }
+
+    /// Upsert a large batch of prices by streaming it with `COPY ... FROM
+    /// STDIN`, [`COPY_CHUNK_ROWS`] rows at a time, into a temporary staging table and merging that into
+    /// `electricity_prices` with the same conflict handling as
+    /// [`upsert_prices`](PriceStore::upsert_prices). Batches smaller than
+    /// [`BULK_COPY_MIN_ROWS`] go through the UNNEST upsert instead.
+    pub async fn bulk_insert_copy(&self, prices: &[Price]) -> Result<usize, StorageError> {
+        if prices.len() < BULK_COPY_MIN_ROWS {
+            return self.upsert_prices(prices).await;
+        }
+        self.ensure_partitions_for(prices).await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "CREATE TEMP TABLE electricity_prices_staging (LIKE electricity_prices INCLUDING DEFAULTS) ON COMMIT DROP",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let mut copy = tx
+            .copy_in_raw(&format!("COPY electricity_prices_staging ({}) FROM STDIN (FORMAT csv)", COPY_COLUMNS))
+            .await?;
+        let mut csv = String::with_capacity(COPY_CHUNK_ROWS * 128);
+        for chunk in prices.chunks(COPY_CHUNK_ROWS) {
+            csv.clear();
+            for row in chunk.iter().map(StoredPrice::from) {
+                row.write_copy_csv(&mut csv);
+            }
+            if let Err(e) = copy.send(csv.as_bytes()).await {
+                copy.abort(e.to_string()).await?;
+                return Err(e.into());
+            }
+        }
+        copy.finish().await?;
+
+        let result = sqlx::query(&format!(
+            r#"
+            INSERT INTO electricity_prices ({columns})
+            SELECT {columns} FROM electricity_prices_staging
+            ON CONFLICT (timestamp, bidding_zone, market)
+            DO UPDATE SET
+                price_kwh = EXCLUDED.price_kwh,
+                currency = EXCLUDED.currency,
+                resolution = EXCLUDED.resolution,
+                fetched_at = EXCLUDED.fetched_at,
+                gap_fill = EXCLUDED.gap_fill
+            "#,
+            columns = COPY_COLUMNS
+        ))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    pub async fn begin_transaction(
+        &self,
+    ) -> Result<sqlx::Transaction<'_, sqlx::Postgres>, StorageError> {
+        self.pool.begin().await.map_err(StorageError::from)
+    }
+}
+
+#[async_trait]
+impl PriceStore for PriceRepository {
+    async fn health_check(&self) -> Result<(), StorageError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            active_connections: self.pool.size(),
+            idle_connections: self.pool.num_idle() as u32,
+            max_connections: self.pool.options().get_max_connections(),
+        }
+    }
+
+    /// Apply the migrations embedded from `migrations/` that are not yet
+    /// recorded in `_sqlx_migrations`. Concurrent replicas wait on sqlx's
+    /// advisory lock, so only one applies them. Fails instead when one of
+    /// [`OFFLINE_MIGRATIONS`] would rewrite stored prices.
+    async fn migrate(&self) -> Result<(), StorageError> {
+        if let Some((version, name)) = self.pending_offline_migration().await? {
+            return Err(StorageError::PoolError(format!(
+                "Postgres migration {}_{} rewrites stored prices; stop the service and run `entsoe-price-fetcher migrate`",
+                version, name
+            )));
+        }
+        self.migrate_offline().await
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Price Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_prices(&self, prices: &[Price]) -> Result<usize, StorageError> {
+        if prices.is_empty() {
+            return Ok(0);
+        }
+        self.ensure_partitions_for(prices).await?;
+        if prices.len() >= BULK_COPY_MIN_ROWS {
+            return self.bulk_insert_copy(prices).await;
+        }
+
+        let mut timestamps: Vec<DateTime<Utc>> = Vec::with_capacity(prices.len());
+        let mut bidding_zones: Vec<String> = Vec::with_capacity(prices.len());
+        let mut prices_kwh: Vec<rust_decimal::Decimal> = Vec::with_capacity(prices.len());
+        let mut currencies: Vec<String> = Vec::with_capacity(prices.len());
+        let mut resolutions: Vec<String> = Vec::with_capacity(prices.len());
+        let mut fetched_ats: Vec<DateTime<Utc>> = Vec::with_capacity(prices.len());
+        let mut markets: Vec<String> = Vec::with_capacity(prices.len());
+        let mut gap_fills: Vec<Option<String>> = Vec::with_capacity(prices.len());
+
+        for row in prices.iter().map(StoredPrice::from) {
+            timestamps.push(row.timestamp);
+            bidding_zones.push(row.bidding_zone);
+            prices_kwh.push(row.price_kwh);
+            currencies.push(row.currency);
+            resolutions.push(row.resolution);
+            fetched_ats.push(row.fetched_at);
+            markets.push(row.market);
+            gap_fills.push(row.gap_fill);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO electricity_prices (timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill)
+            SELECT * FROM UNNEST($1::timestamptz[], $2::varchar[], $3::numeric[], $4::varchar[], $5::varchar[], $6::timestamptz[], $7::varchar[], $8::varchar[])
+            ON CONFLICT (timestamp, bidding_zone, market)
+            DO UPDATE SET
+                price_kwh = EXCLUDED.price_kwh,
+                currency = EXCLUDED.currency,
+                resolution = EXCLUDED.resolution,
+                fetched_at = EXCLUDED.fetched_at,
+                gap_fill = EXCLUDED.gap_fill
+            "#,
+        )
+        .bind(&timestamps)
+        .bind(&bidding_zones)
+        .bind(&prices_kwh)
+        .bind(&currencies)
+        .bind(&resolutions)
+        .bind(&fetched_ats)
+        .bind(&markets)
+        .bind(&gap_fills)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn get_prices_by_zone(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Price>, StorageError> {
+        let prices = sqlx::query_as::<_, StoredPrice>(
+            r#"
+            SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices
+            WHERE bidding_zone = $1 AND market = $4 AND timestamp >= $2 AND timestamp < $3
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(zone_code)
+        .bind(start)
+        .bind(end)
+        .bind(market.as_str())
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        prices_from_rows(prices)
+    }
+
+    fn stream_prices_by_zone<'a>(
+        &'a self,
+        zone_code: &'a str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxStream<'a, Result<Price, StorageError>> {
+        sqlx::query_as::<_, StoredPrice>(
+            r#"
+            SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices
+            WHERE bidding_zone = $1 AND market = $4 AND timestamp >= $2 AND timestamp < $3
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(zone_code)
+        .bind(start)
+        .bind(end)
+        .bind(market.as_str())
+        .fetch(&self.read_pool)
+        .map(|row| Price::try_from(row?))
+        .boxed()
+    }
+
+    async fn get_prices_by_country(
+        &self,
+        country_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<Price>>, StorageError> {
+        let rows = sqlx::query_as::<_, StoredPrice>(
+            r#"
+            SELECT ep.timestamp, ep.bidding_zone, ep.price_kwh, ep.currency, ep.resolution, ep.fetched_at, ep.market, ep.gap_fill
+            FROM electricity_prices ep
+            JOIN bidding_zones bz ON ep.bidding_zone = bz.zone_code
+            WHERE bz.country_code = $1
+              AND bz.active = TRUE
+              AND ep.market = $4
+              AND ep.timestamp >= $2 AND ep.timestamp < $3
+            ORDER BY ep.bidding_zone, ep.timestamp ASC
+            "#,
+        )
+        .bind(country_code)
+        .bind(start)
+        .bind(end)
+        .bind(market.as_str())
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        let mut grouped: HashMap<String, Vec<Price>> = HashMap::new();
+        for price in prices_from_rows(rows)? {
+            grouped
+                .entry(price.bidding_zone.clone())
+                .or_default()
+                .push(price);
+        }
+
+        Ok(grouped)
+    }
+
+    async fn get_latest_prices(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Price>, StorageError> {
+        let prices = match since {
+            Some(since) => {
+                sqlx::query_as::<_, StoredPrice>(
+                    r#"
+                    SELECT DISTINCT ON (bidding_zone) timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+                    FROM electricity_prices
+                    WHERE market = 'day_ahead' AND timestamp >= $1
+                    ORDER BY bidding_zone, timestamp DESC
+                    "#,
+                )
+                .bind(since)
+                .fetch_all(&self.read_pool)
+                .await?
+            }
+            None => {
+                sqlx::query_as::<_, StoredPrice>(
+                    r#"
+                    SELECT DISTINCT ON (bidding_zone) timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+                    FROM electricity_prices
+                    WHERE market = 'day_ahead'
+                    ORDER BY bidding_zone, timestamp DESC
+                    "#,
+                )
+                .fetch_all(&self.read_pool)
+                .await?
+            }
+        };
+
+        prices_from_rows(prices)
+    }
+
+    async fn get_current_prices(&self, at: DateTime<Utc>) -> Result<Vec<Price>, StorageError> {
+        let prices = sqlx::query_as::<_, StoredPrice>(
+            r#"
+            SELECT DISTINCT ON (bidding_zone) timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices
+            WHERE market = 'day_ahead' AND timestamp <= $1 AND timestamp > $2
+            ORDER BY bidding_zone, timestamp DESC
+            "#,
+        )
+        .bind(at)
+        .bind(at - Duration::hours(1))
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        prices_from_rows(prices)
+    }
+
+    async fn get_price_at(&self, zone_code: &str, market: Market, at: DateTime<Utc>) -> Result<Option<Price>, StorageError> {
+        // No stored resolution is longer than a day, which bounds the partitions scanned
+        let row = sqlx::query_as::<_, StoredPrice>(
+            r#"
+            SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices
+            WHERE bidding_zone = $1 AND market = $2 AND timestamp <= $3 AND timestamp > $4
+            ORDER BY timestamp DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(zone_code)
+        .bind(market.as_str())
+        .bind(at)
+        .bind(at - Duration::days(1))
+        .fetch_optional(&self.read_pool)
+        .await?;
+
+        let price = row.map(Price::try_from).transpose()?;
+        Ok(price.filter(|price| price.interval_end() > at))
+    }
+
+    async fn get_zone_stats(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<ZoneStats, StorageError> {
+        let row = sqlx::query(
+            r#"
+            WITH zone_prices AS (
+                SELECT timestamp, price_kwh, currency
+                FROM electricity_prices
+                WHERE bidding_zone = $1 AND market = $4 AND timestamp >= $2 AND timestamp < $3
+            ),
+            hours AS (
+                SELECT hour, LAG(hour) OVER (ORDER BY hour) AS prev_hour
+                FROM (SELECT DISTINCT date_trunc('hour', timestamp) AS hour FROM zone_prices) h
+            )
+            SELECT
+                COUNT(*) AS price_count,
+                MIN(price_kwh) AS min_price,
+                MAX(price_kwh) AS max_price,
+                AVG(price_kwh) AS avg_price,
+                MIN(timestamp) AS first_timestamp,
+                MAX(timestamp) AS last_timestamp,
+                MIN(currency) AS currency,
+                (SELECT COUNT(*) FROM hours) AS covered_hours,
+                COALESCE(
+                    (SELECT MAX(EXTRACT(EPOCH FROM hour - prev_hour))::bigint / 3600 - 1 FROM hours),
+                    0
+                ) AS longest_interior_gap_hours
+            FROM zone_prices
+            "#,
+        )
+        .bind(zone_code)
+        .bind(start)
+        .bind(end)
+        .bind(market.as_str())
+        .fetch_one(&self.read_pool)
+        .await?;
+
+        Ok(ZoneStats {
+            price_count: row.try_get("price_count")?,
+            min_price: row.try_get("min_price")?,
+            max_price: row.try_get("max_price")?,
+            avg_price: row.try_get("avg_price")?,
+            first_timestamp: row.try_get("first_timestamp")?,
+            last_timestamp: row.try_get("last_timestamp")?,
+            covered_hours: row.try_get("covered_hours")?,
+            longest_interior_gap_hours: row.try_get("longest_interior_gap_hours")?,
+            currency: row.try_get("currency")?,
+        })
+    }
+
+    async fn get_price_coverage(&self, zone_code: &str, market: Market) -> Result<Option<PriceCoverage>, StorageError> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                (SELECT MIN(timestamp) FROM electricity_prices WHERE bidding_zone = $1 AND market = $2) AS first_timestamp,
+                timestamp AS last_timestamp,
+                resolution AS last_resolution
+            FROM electricity_prices
+            WHERE bidding_zone = $1 AND market = $2
+            ORDER BY timestamp DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(zone_code)
+        .bind(market.as_str())
+        .fetch_optional(&self.read_pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(PriceCoverage {
+                first_timestamp: row.try_get("first_timestamp")?,
+                last_timestamp: row.try_get("last_timestamp")?,
+                last_resolution: row.try_get("last_resolution")?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Drops the monthly partitions that end before `older_than`, then
+    /// deletes the older rows left in the partially expired month.
+    async fn delete_old_prices(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let dropped: i64 = sqlx::query_scalar("SELECT drop_electricity_prices_partitions($1)")
+            .bind(older_than)
+            .fetch_one(&self.pool)
+            .await?;
+        self.partitions.lock().unwrap().clear();
+
+        let result = sqlx::query("DELETE FROM electricity_prices WHERE timestamp < $1")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(dropped as u64 + result.rows_affected())
+    }
+
+    async fn ensure_price_partitions(&self, from: NaiveDate, months_ahead: u32) -> Result<Vec<String>, StorageError> {
+        let first = from.with_day(1).unwrap();
+        let mut created = Vec::new();
+        for offset in 0..=months_ahead {
+            let month = first + Months::new(offset);
+            let was_created: bool = sqlx::query_scalar("SELECT create_electricity_prices_partition($1)")
+                .bind(month)
+                .fetch_one(&self.pool)
+                .await?;
+            if was_created {
+                created.push(format!("electricity_prices_{}", month.format("%Y_%m")));
+            }
+            self.partitions.lock().unwrap().insert(month);
+        }
+        Ok(created)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Generation Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_generation(&self, points: &[GenerationPoint]) -> Result<usize, StorageError> {
+        if points.is_empty() {
+            return Ok(0);
+        }
+
+        let mut timestamps: Vec<DateTime<Utc>> = Vec::with_capacity(points.len());
+        let mut bidding_zones: Vec<String> = Vec::with_capacity(points.len());
+        let mut psr_types: Vec<String> = Vec::with_capacity(points.len());
+        let mut quantities: Vec<rust_decimal::Decimal> = Vec::with_capacity(points.len());
+        let mut resolutions: Vec<String> = Vec::with_capacity(points.len());
+        let mut fetched_ats: Vec<DateTime<Utc>> = Vec::with_capacity(points.len());
+
+        for point in points {
+            timestamps.push(point.timestamp);
+            bidding_zones.push(point.bidding_zone.clone());
+            psr_types.push(point.psr_type.clone());
+            quantities.push(point.quantity_mw);
+            resolutions.push(point.resolution.clone());
+            fetched_ats.push(point.fetched_at);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO generation_per_type (timestamp, bidding_zone, psr_type, quantity_mw, resolution, fetched_at)
+            SELECT * FROM UNNEST($1::timestamptz[], $2::varchar[], $3::varchar[], $4::numeric[], $5::varchar[], $6::timestamptz[])
+            ON CONFLICT (timestamp, bidding_zone, psr_type)
+            DO UPDATE SET
+                quantity_mw = EXCLUDED.quantity_mw,
+                resolution = EXCLUDED.resolution,
+                fetched_at = EXCLUDED.fetched_at
+            "#,
+        )
+        .bind(&timestamps)
+        .bind(&bidding_zones)
+        .bind(&psr_types)
+        .bind(&quantities)
+        .bind(&resolutions)
+        .bind(&fetched_ats)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn get_generation_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<GenerationPoint>, StorageError> {
+        let points = sqlx::query_as::<_, GenerationPoint>(
+            r#"
+            SELECT timestamp, bidding_zone, psr_type, quantity_mw, resolution, fetched_at
+            FROM generation_per_type
+            WHERE bidding_zone = $1 AND timestamp >= $2 AND timestamp < $3
+            ORDER BY timestamp ASC, psr_type ASC
+            "#,
+        )
+        .bind(zone_code)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(points)
+    }
+
+    async fn delete_old_generation(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM generation_per_type WHERE timestamp < $1")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Load Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_load(&self, values: &[LoadValue]) -> Result<usize, StorageError> {
+        if values.is_empty() {
+            return Ok(0);
+        }
+
+        let mut timestamps: Vec<DateTime<Utc>> = Vec::with_capacity(values.len());
+        let mut bidding_zones: Vec<String> = Vec::with_capacity(values.len());
+        let mut loads: Vec<rust_decimal::Decimal> = Vec::with_capacity(values.len());
+        let mut resolutions: Vec<String> = Vec::with_capacity(values.len());
+        let mut fetched_ats: Vec<DateTime<Utc>> = Vec::with_capacity(values.len());
+
+        for value in values {
+            timestamps.push(value.timestamp);
+            bidding_zones.push(value.bidding_zone.clone());
+            loads.push(value.load_mw);
+            resolutions.push(value.resolution.clone());
+            fetched_ats.push(value.fetched_at);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO load_values (timestamp, bidding_zone, load_mw, resolution, fetched_at)
+            SELECT * FROM UNNEST($1::timestamptz[], $2::varchar[], $3::numeric[], $4::varchar[], $5::timestamptz[])
+            ON CONFLICT (timestamp, bidding_zone)
+            DO UPDATE SET
+                load_mw = EXCLUDED.load_mw,
+                resolution = EXCLUDED.resolution,
+                fetched_at = EXCLUDED.fetched_at
+            "#,
+        )
+        .bind(&timestamps)
+        .bind(&bidding_zones)
+        .bind(&loads)
+        .bind(&resolutions)
+        .bind(&fetched_ats)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn get_load_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LoadValue>, StorageError> {
+        let values = sqlx::query_as::<_, LoadValue>(
+            r#"
+            SELECT timestamp, bidding_zone, load_mw, resolution, fetched_at
+            FROM load_values
+            WHERE bidding_zone = $1 AND timestamp >= $2 AND timestamp < $3
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(zone_code)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(values)
+    }
+
+    async fn delete_old_load(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM load_values WHERE timestamp < $1")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Cross-Border Flow Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_flows(&self, flows: &[CrossBorderFlow]) -> Result<usize, StorageError> {
+        if flows.is_empty() {
+            return Ok(0);
+        }
+
+        let mut timestamps: Vec<DateTime<Utc>> = Vec::with_capacity(flows.len());
+        let mut from_zones: Vec<String> = Vec::with_capacity(flows.len());
+        let mut to_zones: Vec<String> = Vec::with_capacity(flows.len());
+        let mut kinds: Vec<String> = Vec::with_capacity(flows.len());
+        let mut quantities: Vec<rust_decimal::Decimal> = Vec::with_capacity(flows.len());
+        let mut resolutions: Vec<String> = Vec::with_capacity(flows.len());
+        let mut fetched_ats: Vec<DateTime<Utc>> = Vec::with_capacity(flows.len());
+
+        for flow in flows {
+            timestamps.push(flow.timestamp);
+            from_zones.push(flow.from_zone.clone());
+            to_zones.push(flow.to_zone.clone());
+            kinds.push(flow.kind.as_str().to_string());
+            quantities.push(flow.quantity_mw);
+            resolutions.push(flow.resolution.clone());
+            fetched_ats.push(flow.fetched_at);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO cross_border_flows (timestamp, from_zone, to_zone, kind, quantity_mw, resolution, fetched_at)
+            SELECT * FROM UNNEST($1::timestamptz[], $2::varchar[], $3::varchar[], $4::varchar[], $5::numeric[], $6::varchar[], $7::timestamptz[])
+            ON CONFLICT (timestamp, from_zone, to_zone, kind)
+            DO UPDATE SET
+                quantity_mw = EXCLUDED.quantity_mw,
+                resolution = EXCLUDED.resolution,
+                fetched_at = EXCLUDED.fetched_at
+            "#,
+        )
+        .bind(&timestamps)
+        .bind(&from_zones)
+        .bind(&to_zones)
+        .bind(&kinds)
+        .bind(&quantities)
+        .bind(&resolutions)
+        .bind(&fetched_ats)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn get_flows_by_zone(
+        &self,
+        zone_code: &str,
+        kind: FlowKind,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<CrossBorderFlow>, StorageError> {
+        let flows = sqlx::query_as::<_, CrossBorderFlow>(
+            r#"
+            SELECT timestamp, from_zone, to_zone, kind, quantity_mw, resolution, fetched_at
+            FROM cross_border_flows
+            WHERE (from_zone = $1 OR to_zone = $1) AND kind = $2 AND timestamp >= $3 AND timestamp < $4
+            ORDER BY timestamp ASC, from_zone, to_zone
+            "#,
+        )
+        .bind(zone_code)
+        .bind(kind.as_str())
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(flows)
+    }
+
+    async fn delete_old_flows(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM cross_border_flows WHERE timestamp < $1")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Name Translation Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_name_translations(&self, translations: &[NameTranslation]) -> Result<usize, StorageError> {
+        if translations.is_empty() {
+            return Ok(0);
+        }
+
+        let mut kinds: Vec<String> = Vec::with_capacity(translations.len());
+        let mut codes: Vec<String> = Vec::with_capacity(translations.len());
+        let mut locales: Vec<String> = Vec::with_capacity(translations.len());
+        let mut names: Vec<String> = Vec::with_capacity(translations.len());
+
+        for translation in translations {
+            kinds.push(translation.kind.as_str().to_string());
+            codes.push(translation.code.clone());
+            locales.push(translation.locale.clone());
+            names.push(translation.name.clone());
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO name_translations (kind, code, locale, name)
+            SELECT * FROM UNNEST($1::varchar[], $2::varchar[], $3::varchar[], $4::varchar[])
+            ON CONFLICT (kind, code, locale)
+            DO UPDATE SET name = EXCLUDED.name
+            "#,
+        )
+        .bind(&kinds)
+        .bind(&codes)
+        .bind(&locales)
+        .bind(&names)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn get_name_translations(&self, locale: &str) -> Result<Vec<NameTranslation>, StorageError> {
+        let translations = sqlx::query_as::<_, NameTranslation>(
+            r#"
+            SELECT kind, code, locale, name
+            FROM name_translations
+            WHERE locale = $1
+            ORDER BY kind, code
+            "#,
+        )
+        .bind(locale)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(translations)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Alert Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn create_alert_rule(&self, rule: &NewAlertRule) -> Result<AlertRule, StorageError> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO alert_rules (zone_code, comparison, threshold, channel, target)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, zone_code, comparison, threshold, channel, target, created_at
+            "#,
+        )
+        .bind(&rule.zone_code)
+        .bind(rule.comparison.as_str())
+        .bind(rule.threshold)
+        .bind(rule.channel.as_str())
+        .bind(&rule.target)
+        .fetch_one(&self.pool)
+        .await?;
+
+        alert_rule_from_row(&row)
+    }
+
+    async fn list_alert_rules(&self, zone_code: Option<&str>) -> Result<Vec<AlertRule>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, zone_code, comparison, threshold, channel, target, created_at
+            FROM alert_rules
+            WHERE $1::varchar IS NULL OR zone_code = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(zone_code)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.iter().map(alert_rule_from_row).collect()
+    }
+
+    async fn get_alert_rule(&self, id: i64) -> Result<AlertRule, StorageError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, zone_code, comparison, threshold, channel, target, created_at
+            FROM alert_rules
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.read_pool)
+        .await?
+        .ok_or_else(|| StorageError::NotFound(format!("Alert rule not found: {}", id)))?;
+
+        alert_rule_from_row(&row)
+    }
+
+    async fn delete_alert_rule(&self, id: i64) -> Result<bool, StorageError> {
+        let result = sqlx::query("DELETE FROM alert_rules WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_alert_events(&self, events: &[AlertEvent]) -> Result<Vec<AlertEvent>, StorageError> {
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = Vec::new();
+
+        for event in events {
+            let row = sqlx::query(
+                r#"
+                INSERT INTO alert_events (rule_id, price_timestamp, price_kwh, fired_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (rule_id, price_timestamp) DO NOTHING
+                RETURNING rule_id, price_timestamp, price_kwh, fired_at, delivered, error_message
+                "#,
+            )
+            .bind(event.rule_id)
+            .bind(event.price_timestamp)
+            .bind(event.price_kwh)
+            .bind(event.fired_at)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(row) = row {
+                inserted.push(alert_event_from_row(&row)?);
+            }
+        }
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn complete_alert_event(
+        &self,
+        rule_id: i64,
+        price_timestamp: DateTime<Utc>,
+        delivered: bool,
+        error_message: Option<String>,
+    ) -> Result<(), StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE alert_events
+            SET delivered = $3, error_message = $4
+            WHERE rule_id = $1 AND price_timestamp = $2
+            "#,
+        )
+        .bind(rule_id)
+        .bind(price_timestamp)
+        .bind(delivered)
+        .bind(&error_message)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(StorageError::NotFound(format!(
+                "Alert event not found: {} at {}",
+                rule_id, price_timestamp
+            )));
+        }
+        Ok(())
+    }
+
+    async fn get_alert_events(&self, rule_id: i64, limit: i64) -> Result<Vec<AlertEvent>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT rule_id, price_timestamp, price_kwh, fired_at, delivered, error_message
+            FROM alert_events
+            WHERE rule_id = $1
+            ORDER BY fired_at DESC, price_timestamp DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(rule_id)
+        .bind(limit)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.iter().map(alert_event_from_row).collect()
+    }
+
+    async fn failed_alert_events(&self, from: DateTime<Utc>) -> Result<Vec<AlertEvent>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT rule_id, price_timestamp, price_kwh, fired_at, delivered, error_message
+            FROM alert_events
+            WHERE delivered = FALSE AND price_timestamp >= $1
+            ORDER BY rule_id, price_timestamp
+            "#,
+        )
+        .bind(from)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.iter().map(alert_event_from_row).collect()
+    }
+
+    async fn create_api_key(&self, key: &ApiKey) -> Result<(), StorageError> {
+        sqlx::query("INSERT INTO api_keys (key_sha256, name, role, created_at) VALUES ($1, $2, $3, $4)")
+            .bind(&key.key_sha256)
+            .bind(&key.name)
+            .bind(key.role.as_str())
+            .bind(key.created_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, StorageError> {
+        let rows = sqlx::query("SELECT key_sha256, name, role, created_at FROM api_keys ORDER BY name")
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(api_key_from_row).collect()
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Job Lock Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn try_acquire_job_lock(
+        &self,
+        job_name: &str,
+        target_date: chrono::NaiveDate,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO job_locks (job_name, target_date, holder, acquired_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (job_name, target_date) DO UPDATE SET
+                holder = EXCLUDED.holder,
+                acquired_at = EXCLUDED.acquired_at,
+                expires_at = EXCLUDED.expires_at
+            WHERE job_locks.expires_at <= EXCLUDED.acquired_at
+            "#,
+        )
+        .bind(job_name)
+        .bind(target_date)
+        .bind(holder)
+        .bind(now)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn renew_job_lock(
+        &self,
+        job_name: &str,
+        target_date: chrono::NaiveDate,
+        holder: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query("UPDATE job_locks SET expires_at = $4 WHERE job_name = $1 AND target_date = $2 AND holder = $3")
+            .bind(job_name)
+            .bind(target_date)
+            .bind(holder)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn release_job_lock(&self, job_name: &str, target_date: chrono::NaiveDate, holder: &str) -> Result<(), StorageError> {
+        sqlx::query("DELETE FROM job_locks WHERE job_name = $1 AND target_date = $2 AND holder = $3")
+            .bind(job_name)
+            .bind(target_date)
+            .bind(holder)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_job_paused(&self, job_name: &str, paused: bool, now: DateTime<Utc>) -> Result<(), StorageError> {
+        if paused {
+            sqlx::query("INSERT INTO scheduler_job_pauses (job_name, paused_at) VALUES ($1, $2) ON CONFLICT (job_name) DO NOTHING")
+                .bind(job_name)
+                .bind(now)
+                .execute(&self.pool)
+                .await?;
+        } else {
+            sqlx::query("DELETE FROM scheduler_job_pauses WHERE job_name = $1")
+                .bind(job_name)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn paused_jobs(&self) -> Result<Vec<String>, StorageError> {
+        Ok(sqlx::query_scalar("SELECT job_name FROM scheduler_job_pauses ORDER BY job_name")
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Idempotency Key Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn claim_idempotency_key(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, fingerprint, status_code, response_body, created_at, expires_at)
+            VALUES ($1, $2, NULL, NULL, $3, $4)
+            ON CONFLICT (key) DO UPDATE SET
+                fingerprint = EXCLUDED.fingerprint,
+                status_code = NULL,
+                response_body = NULL,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at
+            WHERE idempotency_keys.expires_at <= EXCLUDED.created_at
+            "#,
+        )
+        .bind(key)
+        .bind(fingerprint)
+        .bind(now)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn get_idempotency_key(&self, key: &str) -> Result<Option<IdempotencyRecord>, StorageError> {
+        // Read from the primary: the key was just claimed or completed there
+        let record = sqlx::query_as::<_, IdempotencyRecord>(
+            "SELECT key, fingerprint, status_code, response_body, created_at, expires_at FROM idempotency_keys WHERE key = $1",
+        )
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(record)
+    }
+
+    async fn complete_idempotency_key(
+        &self,
+        key: &str,
+        claimed_at: DateTime<Utc>,
+        status_code: i32,
+        response_body: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            UPDATE idempotency_keys
+            SET status_code = $3, response_body = $4, expires_at = $5
+            WHERE key = $1 AND created_at = $2
+            "#,
+        )
+        .bind(key)
+        .bind(claimed_at)
+        .bind(status_code)
+        .bind(response_body)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn release_idempotency_key(&self, key: &str, claimed_at: DateTime<Utc>) -> Result<(), StorageError> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE key = $1 AND created_at = $2 AND status_code IS NULL")
+            .bind(key)
+            .bind(claimed_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_expired_idempotency_keys(&self, now: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= $1")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Retry Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn enqueue_fetch_retries(&self, retries: &[FetchRetry]) -> Result<(), StorageError> {
+        let mut tx = self.pool.begin().await?;
+        for retry in retries {
+            sqlx::query(
+                r#"
+                INSERT INTO fetch_retry_queue
+                    (zone_code, target_date, attempts, last_error, enqueued_at, next_attempt_at, expires_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (zone_code, target_date) DO UPDATE SET last_error = EXCLUDED.last_error
+                "#,
+            )
+            .bind(&retry.zone_code)
+            .bind(retry.target_date)
+            .bind(retry.attempts)
+            .bind(&retry.last_error)
+            .bind(retry.enqueued_at)
+            .bind(retry.next_attempt_at)
+            .bind(retry.expires_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_due_fetch_retries(&self, now: DateTime<Utc>) -> Result<Vec<FetchRetry>, StorageError> {
+        let retries = sqlx::query_as::<_, FetchRetry>(
+            r#"
+            SELECT zone_code, target_date, attempts, last_error, enqueued_at, next_attempt_at, expires_at
+            FROM fetch_retry_queue
+            WHERE next_attempt_at <= $1 AND expires_at > $1
+            ORDER BY next_attempt_at
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(retries)
+    }
+
+    async fn reschedule_fetch_retry(
+        &self,
+        zone_code: &str,
+        target_date: NaiveDate,
+        next_attempt_at: DateTime<Utc>,
+        error: Option<&str>,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            UPDATE fetch_retry_queue
+            SET attempts = attempts + 1, next_attempt_at = $1, last_error = $2
+            WHERE zone_code = $3 AND target_date = $4
+            "#,
+        )
+        .bind(next_attempt_at)
+        .bind(error)
+        .bind(zone_code)
+        .bind(target_date)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_fetch_retry(&self, zone_code: &str, target_date: NaiveDate) -> Result<(), StorageError> {
+        sqlx::query("DELETE FROM fetch_retry_queue WHERE zone_code = $1 AND target_date = $2")
+            .bind(zone_code)
+            .bind(target_date)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_expired_fetch_retries(&self, now: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM fetch_retry_queue WHERE expires_at <= $1")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Backfill Job Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn create_backfill_job(&self, job: &NewBackfillJob, now: DateTime<Utc>) -> Result<BackfillJob, StorageError> {
+        let zones = job.zones.as_ref().map(|zones| serde_json::json!(zones).to_string());
+        let row = sqlx::query(
+            r#"
+            INSERT INTO backfill_jobs (start_date, end_date, zones, status, dates_total, created_at, updated_at)
+            VALUES ($1, $2, $3, 'running', $4, $5, $5)
+            RETURNING id, start_date, end_date, zones, status, dates_total, dates_done, prices_stored, errors,
+                      created_at, updated_at, finished_at
+            "#,
+        )
+        .bind(job.start_date)
+        .bind(job.end_date)
+        .bind(zones)
+        .bind(job.dates_total())
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?;
+
+        backfill_job_from_row(&row)
+    }
+
+    async fn get_backfill_job(&self, id: i64) -> Result<BackfillJob, StorageError> {
+        // Read from the primary, which the running job writes its progress to
+        let row = sqlx::query(
+            r#"
+            SELECT id, start_date, end_date, zones, status, dates_total, dates_done, prices_stored, errors,
+                   created_at, updated_at, finished_at
+            FROM backfill_jobs
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| StorageError::NotFound(format!("Backfill job not found: {}", id)))?;
+
+        backfill_job_from_row(&row)
+    }
+
+    async fn update_backfill_job(&self, job: &BackfillJob) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE backfill_jobs
+            SET status = $2, dates_done = $3, prices_stored = $4, errors = $5, updated_at = $6, finished_at = $7
+            WHERE id = $1 AND status = 'running'
+            "#,
+        )
+        .bind(job.id)
+        .bind(job.status.as_str())
+        .bind(job.dates_done)
+        .bind(job.prices_stored)
+        .bind(serde_json::json!(job.errors).to_string())
+        .bind(job.updated_at)
+        .bind(job.finished_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn cancel_backfill_job(&self, id: i64, now: DateTime<Utc>) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE backfill_jobs
+            SET status = 'cancelled', updated_at = $2, finished_at = $2
+            WHERE id = $1 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Task Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn enqueue_task(&self, task: &NewTask, now: DateTime<Utc>) -> Result<Option<Task>, StorageError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO tasks (kind, payload, dedupe_key, status, max_attempts, run_after, created_at, updated_at)
+            VALUES ($1, $2, $3, 'queued', $4, $5, $6, $6)
+            ON CONFLICT (dedupe_key) WHERE status IN ('queued', 'running') DO NOTHING
+            RETURNING {}
+            "#,
+            TASK_COLUMNS
+        ))
+        .bind(&task.kind)
+        .bind(&task.payload)
+        .bind(&task.dedupe_key)
+        .bind(task.max_attempts)
+        .bind(task.run_after)
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(task_from_row).transpose()
+    }
+
+    async fn claim_task(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>) -> Result<Option<Task>, StorageError> {
+        // SKIP LOCKED lets concurrent workers each claim a different task
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE tasks
+            SET status = 'running', attempts = attempts + 1, lease_expires_at = $2, updated_at = $1
+            WHERE id = (
+                SELECT id FROM tasks
+                WHERE (status = 'queued' AND run_after <= $1) OR (status = 'running' AND lease_expires_at <= $1)
+                ORDER BY run_after, id
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            TASK_COLUMNS
+        ))
+        .bind(now)
+        .bind(lease_until)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(task_from_row).transpose()
+    }
+
+    async fn renew_task_lease(&self, id: i64, attempt: i32, lease_until: DateTime<Utc>) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tasks
+            SET lease_expires_at = $3
+            WHERE id = $1 AND attempts = $2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(attempt)
+        .bind(lease_until)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn finish_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        status: TaskStatus,
+        error: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tasks
+            SET status = $3, last_error = COALESCE($4, last_error), lease_expires_at = NULL, updated_at = $5,
+                finished_at = $5
+            WHERE id = $1 AND attempts = $2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(attempt)
+        .bind(status.as_str())
+        .bind(error)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn retry_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        error: &str,
+        run_after: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tasks
+            SET status = 'queued', last_error = $3, run_after = $4, lease_expires_at = NULL, updated_at = $5
+            WHERE id = $1 AND attempts = $2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(attempt)
+        .bind(error)
+        .bind(run_after)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_task(&self, id: i64) -> Result<Task, StorageError> {
+        let row = sqlx::query(&format!("SELECT {} FROM tasks WHERE id = $1", TASK_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(format!("Task not found: {}", id)))?;
+
+        task_from_row(&row)
+    }
+
+    async fn list_tasks(&self, status: Option<TaskStatus>, limit: i64) -> Result<Vec<Task>, StorageError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM tasks
+            WHERE $1::TEXT IS NULL OR status = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2
+            "#,
+            TASK_COLUMNS
+        ))
+        .bind(status.map(|status| status.as_str()))
+        .bind(limit)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.iter().map(task_from_row).collect()
+    }
+
+    async fn count_tasks_by_status(&self) -> Result<HashMap<TaskStatus, i64>, StorageError> {
+        let rows: Vec<(String, i64)> = sqlx::query_as("SELECT status, COUNT(*) FROM tasks GROUP BY status")
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|(status, count)| {
+                TaskStatus::parse(&status)
+                    .map(|status| (status, count))
+                    .ok_or_else(|| StorageError::QueryError(format!("Invalid stored task status: {}", status)))
+            })
+            .collect()
+    }
+
+    async fn delete_finished_tasks(&self, before: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM tasks WHERE status IN ('succeeded', 'failed') AND finished_at < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Zone Registry Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn load_zones(&self) -> Result<Vec<BiddingZone>, StorageError> {
+        let zones = sqlx::query_as::<_, BiddingZone>(
+            r#"
+            SELECT zone_code, zone_name, country_code, country_name, eic_code, timezone, active,
+                   valid_from, valid_to, predecessor_code, created_at, updated_at
+            FROM bidding_zones
+            WHERE active = TRUE
+            ORDER BY country_code, zone_code
+            "#,
+        )
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(zones)
+    }
+
+    async fn get_zone_by_code(&self, zone_code: &str) -> Result<BiddingZone, StorageError> {
+        sqlx::query_as::<_, BiddingZone>(
+            r#"
+            SELECT zone_code, zone_name, country_code, country_name, eic_code, timezone, active,
+                   valid_from, valid_to, predecessor_code, created_at, updated_at
+            FROM bidding_zones
+            WHERE zone_code = $1
+            "#,
+        )
+        .bind(zone_code)
+        .fetch_optional(&self.read_pool)
+        .await?
+        .ok_or_else(|| StorageError::NotFound(format!("Zone not found: {}", zone_code)))
+    }
+
+    async fn get_zone_by_eic(&self, eic_code: &str) -> Result<BiddingZone, StorageError> {
+        sqlx::query_as::<_, BiddingZone>(
+            r#"
+            SELECT zone_code, zone_name, country_code, country_name, eic_code, timezone, active,
+                   valid_from, valid_to, predecessor_code, created_at, updated_at
+            FROM bidding_zones
+            WHERE eic_code = $1
+            "#,
+        )
+        .bind(eic_code)
+        .fetch_optional(&self.read_pool)
+        .await?
+        .ok_or_else(|| StorageError::NotFound(format!("Zone not found for EIC: {}", eic_code)))
+    }
+
+    async fn get_zones_by_country(
+        &self,
+        country_code: &str,
+    ) -> Result<Vec<BiddingZone>, StorageError> {
+        let zones = sqlx::query_as::<_, BiddingZone>(
+            r#"
+            SELECT zone_code, zone_name, country_code, country_name, eic_code, timezone, active,
+                   valid_from, valid_to, predecessor_code, created_at, updated_at
+            FROM bidding_zones
+            WHERE country_code = $1 AND active = TRUE
+            ORDER BY zone_code
+            "#,
+        )
+        .bind(country_code)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(zones)
+    }
+
+    async fn get_countries(&self) -> Result<Vec<(String, String)>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT country_code, country_name
+            FROM bidding_zones
+            WHERE active = TRUE
+            ORDER BY country_code
+            "#,
+        )
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        let countries = rows
+            .iter()
+            .map(|row| {
+                let code: String = row.get("country_code");
+                let name: String = row.get("country_name");
+                (code, name)
+            })
+            .collect();
+
+        Ok(countries)
+    }
+
+    async fn insert_missing_zones(&self, zones: &[ZoneDefinition]) -> Result<Vec<String>, StorageError> {
+        let today = Utc::now().date_naive();
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = Vec::new();
+        for zone in zones {
+            let zone_code: Option<String> = sqlx::query_scalar(
+                r#"
+                INSERT INTO bidding_zones (zone_code, zone_name, country_code, country_name, eic_code, timezone,
+                                           valid_from, valid_to, predecessor_code, active)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT DO NOTHING
+                RETURNING zone_code
+                "#,
+            )
+            .bind(&zone.zone_code)
+            .bind(&zone.zone_name)
+            .bind(&zone.country_code)
+            .bind(&zone.country_name)
+            .bind(&zone.eic_code)
+            .bind(&zone.timezone)
+            .bind(zone.valid_from)
+            .bind(zone.valid_to)
+            .bind(&zone.predecessor_code)
+            .bind(zone.is_active_on(today))
+            .fetch_optional(&mut *tx)
+            .await?;
+            inserted.extend(zone_code);
+        }
+        tx.commit().await?;
+
+        Ok(inserted)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Log Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn log_fetch_start(
+        &self,
+        zone_code: Option<String>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        correlation_id: Option<String>,
+    ) -> Result<i64, StorageError> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO fetch_log (fetch_started_at, bidding_zone, period_start, period_end, status, correlation_id)
+            VALUES (NOW(), $1, $2, $3, 'pending', $4)
+            RETURNING id
+            "#,
+        )
+        .bind(&zone_code)
+        .bind(period_start)
+        .bind(period_end)
+        .bind(&correlation_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.get("id"))
+    }
+
+    async fn log_fetch_complete(
+        &self,
+        fetch_id: i64,
+        status: FetchStatus,
+        records_inserted: i32,
+        error_message: Option<String>,
+        http_status: Option<i32>,
+        duration_ms: i32,
+    ) -> Result<(), StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE fetch_log
+            SET fetch_completed_at = NOW(),
+                status = $1::text,
+                records_inserted = $2,
+                error_message = $3,
+                http_status = $4,
+                duration_ms = $5
+            WHERE id = $6
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(records_inserted)
+        .bind(&error_message)
+        .bind(http_status)
+        .bind(duration_ms)
+        .bind(fetch_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(StorageError::NotFound(format!(
+                "Fetch log not found: {}",
+                fetch_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn log_fetch_endpoint(&self, fetch_id: i64, endpoint: &str) -> Result<(), StorageError> {
+        sqlx::query("UPDATE fetch_log SET endpoint = $1 WHERE id = $2")
+            .bind(endpoint)
+            .bind(fetch_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_recent_fetch_logs(&self, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        let logs = sqlx::query_as::<_, FetchLog>(
+            r#"
+            SELECT id, fetch_started_at, fetch_completed_at, bidding_zone, period_start, period_end,
+                   status, records_inserted, error_message, http_status, duration_ms, endpoint,
+                   correlation_id
+            FROM fetch_log
+            ORDER BY fetch_started_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(logs)
+    }
+
+    async fn list_fetch_logs(&self, filter: &FetchLogFilter, page: FetchLogPage, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        let (offset, after) = match page {
+            FetchLogPage::Offset(offset) => (offset, None),
+            FetchLogPage::After { started_at, id } => (0, Some((started_at, id))),
+        };
+        let logs = sqlx::query_as::<_, FetchLog>(&format!(
+            r#"
+            SELECT id, fetch_started_at, fetch_completed_at, bidding_zone, period_start, period_end,
+                   status, records_inserted, error_message, http_status, duration_ms, endpoint,
+                   correlation_id
+            FROM fetch_log
+            WHERE {}
+              AND ($6::TIMESTAMPTZ IS NULL OR (fetch_started_at, id) < ($6, $7))
+            ORDER BY fetch_started_at DESC, id DESC
+            LIMIT $8 OFFSET $9
+            "#,
+            FETCH_LOG_FILTER
+        ))
+        .bind(filter.zone_code.as_deref())
+        .bind(filter.status.as_ref().map(|status| status.as_str()))
+        .bind(filter.started_from)
+        .bind(filter.started_before)
+        .bind(filter.correlation_id.as_deref())
+        .bind(after.map(|(started_at, _)| started_at))
+        .bind(after.map(|(_, id)| id))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(logs)
+    }
+
+    async fn count_fetch_logs(&self, filter: &FetchLogFilter) -> Result<i64, StorageError> {
+        let count = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM fetch_log WHERE {}", FETCH_LOG_FILTER))
+            .bind(filter.zone_code.as_deref())
+            .bind(filter.status.as_ref().map(|status| status.as_str()))
+            .bind(filter.started_from)
+            .bind(filter.started_before)
+            .bind(filter.correlation_id.as_deref())
+            .fetch_one(&self.read_pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn get_last_successful_fetch(&self) -> Result<Option<DateTime<Utc>>, StorageError> {
+        let completed_at = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT MAX(fetch_completed_at) FROM fetch_log WHERE status = 'success'",
+        )
+        .fetch_one(&self.read_pool)
+        .await?;
+
+        Ok(completed_at)
+    }
+
+    async fn get_last_fetch_run(&self) -> Result<Option<DateTime<Utc>>, StorageError> {
+        let started_at = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT MAX(fetch_started_at) FROM fetch_log WHERE bidding_zone IS NULL",
+        )
+        .fetch_one(&self.read_pool)
+        .await?;
+
+        Ok(started_at)
+    }
+
+    async fn get_fetch_logs_by_zone(
+        &self,
+        zone_code: &str,
+        limit: i64,
+    ) -> Result<Vec<FetchLog>, StorageError> {
+        let logs = sqlx::query_as::<_, FetchLog>(
+            r#"
+            SELECT id, fetch_started_at, fetch_completed_at, bidding_zone, period_start, period_end,
+                   status, records_inserted, error_message, http_status, duration_ms, endpoint,
+                   correlation_id
+            FROM fetch_log
+            WHERE bidding_zone = $1
+            ORDER BY fetch_started_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(zone_code)
+        .bind(limit)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(logs)
+    }
+
+    async fn get_latest_fetch_logs(&self) -> Result<Vec<FetchLog>, StorageError> {
+        let logs = sqlx::query_as::<_, FetchLog>(
+            r#"
+            SELECT DISTINCT ON (bidding_zone) id, fetch_started_at, fetch_completed_at, bidding_zone, period_start,
+                   period_end, status, records_inserted, error_message, http_status, duration_ms, endpoint,
+                   correlation_id
+            FROM fetch_log
+            WHERE bidding_zone IS NOT NULL
+            ORDER BY bidding_zone, fetch_started_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(logs)
+    }
+
+    async fn delete_old_fetch_logs(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM fetch_log WHERE fetch_started_at < $1")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn has_day_ahead_data(&self, zone_code: &str, date: chrono::NaiveDate) -> Result<bool, StorageError> {
+        let day_end = date.succ_opt().unwrap();
+
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM electricity_prices
+            WHERE bidding_zone = $1
+              AND market = 'day_ahead'
+              AND timestamp >= $2::date
+              AND timestamp < $3::date
+            "#,
+        )
+        .bind(zone_code)
+        .bind(date)
+        .bind(day_end)
+        .fetch_one(&self.read_pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    async fn find_gaps(
+        &self,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<DayGap>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            WITH date_range AS (
+                SELECT generate_series($1::date, $2::date, '1 day'::interval)::date AS date
+            ),
+            zones AS (
+                SELECT z.zone_code, COALESCE(bz.timezone, 'UTC') AS timezone
+                FROM unnest($3::varchar[]) AS z(zone_code)
+                LEFT JOIN bidding_zones bz ON bz.zone_code = z.zone_code
+            ),
+            delivery_days AS (
+                SELECT
+                    d.date,
+                    z.zone_code,
+                    d.date::timestamp AT TIME ZONE z.timezone AS day_start,
+                    (d.date + 1)::timestamp AT TIME ZONE z.timezone AS day_end
+                FROM date_range d
+                CROSS JOIN zones z
+            ),
+            day_counts AS (
+                SELECT
+                    dd.date,
+                    dd.zone_code,
+                    (
+                        SELECT COUNT(DISTINCT date_trunc('hour', p.timestamp AT TIME ZONE 'UTC'))
+                        FROM electricity_prices p
+                        WHERE p.bidding_zone = dd.zone_code
+                          AND p.market = 'day_ahead'
+                          AND p.timestamp >= dd.day_start
+                          AND p.timestamp < dd.day_end
+                    ) AS hour_count,
+                    (EXTRACT(EPOCH FROM dd.day_end - dd.day_start) / 3600)::bigint AS expected_hours
+                FROM delivery_days dd
+            )
+            SELECT date, zone_code, hour_count, expected_hours
+            FROM day_counts
+            WHERE hour_count < expected_hours
+            ORDER BY date, zone_code
+            "#,
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(zone_codes)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        let gaps = rows
+            .iter()
+            .map(|row| DayGap {
+                date: row.get("date"),
+                zone_code: row.get("zone_code"),
+                hours: row.get("hour_count"),
+                expected_hours: row.get("expected_hours"),
+            })
+            .collect();
+
+        Ok(gaps)
+    }
+
+    async fn find_estimated_hours(
+        &self,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<(chrono::NaiveDate, String, i64)>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                date(timestamp AT TIME ZONE 'UTC') AS price_date,
+                bidding_zone,
+                COUNT(DISTINCT date_trunc('hour', timestamp)) AS estimated_count
+            FROM electricity_prices
+            WHERE timestamp >= $1::date
+              AND timestamp < ($2::date + interval '1 day')
+              AND bidding_zone = ANY($3::varchar[])
+              AND market = 'day_ahead'
+              AND gap_fill IS NOT NULL
+            GROUP BY date(timestamp AT TIME ZONE 'UTC'), bidding_zone
+            ORDER BY price_date, bidding_zone
+            "#,
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(zone_codes)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("price_date"), row.get("bidding_zone"), row.get("estimated_count")))
+            .collect())
+    }
+
+    async fn get_table_stats(&self) -> Result<Vec<TableStats>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                t.table_name,
+                t.row_count,
+                t.oldest_entry,
+                -- A partitioned table's own size is zero; sum its partitions
+                (SELECT SUM(pg_table_size(p.relid))::BIGINT FROM pg_partition_tree(t.table_name::regclass) p) AS table_bytes,
+                (SELECT SUM(pg_indexes_size(p.relid))::BIGINT FROM pg_partition_tree(t.table_name::regclass) p) AS index_bytes
+            FROM (
+                SELECT 'electricity_prices' AS table_name,
+                       (SELECT COUNT(*) FROM electricity_prices) AS row_count,
+                       (SELECT MIN(timestamp) FROM electricity_prices) AS oldest_entry
+                UNION ALL
+                SELECT 'fetch_log',
+                       (SELECT COUNT(*) FROM fetch_log),
+                       (SELECT MIN(fetch_started_at) FROM fetch_log)
+                UNION ALL
+                SELECT 'bidding_zones',
+                       (SELECT COUNT(*) FROM bidding_zones),
+                       NULL
+            ) t
+            "#,
+        )
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(TableStats {
+                    table_name: row.try_get("table_name")?,
+                    row_count: row.try_get("row_count")?,
+                    table_bytes: row.try_get("table_bytes")?,
+                    index_bytes: row.try_get("index_bytes")?,
+                    oldest_entry: row.try_get("oldest_entry")?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,84 @@
+use std::future::Future;
+
+use tracing::warn;
+
+use crate::config::DatabaseConfig;
+use crate::error::StorageError;
+
+/// Run `attempt` until it succeeds, waiting `config`'s connect backoff after
+/// each failure. Gives up with the last error after `max_retries` retries,
+/// or keeps trying when it is `None`.
+pub async fn retry_with_backoff<T, F, Fut>(
+    config: &DatabaseConfig,
+    max_retries: Option<u32>,
+    what: &str,
+    mut attempt: F,
+) -> Result<T, StorageError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, StorageError>>,
+{
+    let mut retry = 0;
+    loop {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(e) if max_retries.is_some_and(|max| retry >= max) => return Err(e),
+            Err(e) => {
+                let delay = config.connect_retry_delay(retry);
+                warn!(what = %what, retry = retry + 1, delay_ms = delay.as_millis() as u64, error = %e, "Database not available, retrying");
+                tokio::time::sleep(delay).await;
+                retry += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::config::DatabaseBackend;
+
+    fn config() -> DatabaseConfig {
+        DatabaseConfig {
+            backend: DatabaseBackend::Postgres,
+            url: String::new(),
+            max_connections: 1,
+            min_connections: 0,
+            connect_timeout_seconds: 5,
+            slow_query_threshold_ms: None,
+            statement_timeout_ms: None,
+            read_url: None,
+            run_migrations: false,
+            connect_retries: 3,
+            connect_retry_initial_ms: 100,
+            connect_retry_max_ms: 1_000,
+            lazy_connect: false,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retries_until_success_or_limit() {
+        let attempts = AtomicU32::new(0);
+        let failing_until = |ok_at: u32| {
+            let attempts = &attempts;
+            move || async move {
+                if attempts.fetch_add(1, Ordering::SeqCst) + 1 >= ok_at {
+                    Ok(())
+                } else {
+                    Err(StorageError::PoolError("refused".to_string()))
+                }
+            }
+        };
+
+        assert!(retry_with_backoff(&config(), Some(3), "test", failing_until(4)).await.is_ok());
+        assert_eq!(attempts.swap(0, Ordering::SeqCst), 4);
+
+        assert!(retry_with_backoff(&config(), Some(3), "test", failing_until(5)).await.is_err());
+        assert_eq!(attempts.swap(0, Ordering::SeqCst), 4);
+
+        assert!(retry_with_backoff(&config(), None, "test", failing_until(20)).await.is_ok());
+        assert_eq!(attempts.load(Ordering::SeqCst), 20);
+    }
+}
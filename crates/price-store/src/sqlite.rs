@@ -0,0 +1,2793 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, DurationRound, NaiveDate, Utc};
+use futures::stream::{BoxStream, StreamExt};
+use rust_decimal::Decimal;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
+use sqlx::{ConnectOptions, Row, SqlitePool};
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+use std::time::Duration as StdDuration;
+use tracing::warn;
+
+use crate::config::DatabaseConfig;
+use crate::metrics;
+use entsoe_core::models::{
+    AlertChannel, AlertComparison, AlertEvent, AlertRule, ApiKey, ApiRole, BackfillJob, BackfillJobStatus, BiddingZone, CrossBorderFlow, FetchLog, FetchRetry, FetchStatus,
+    FlowKind, GenerationPoint, IdempotencyRecord, LoadValue, Market, NameKind, NameTranslation, NewAlertRule, NewBackfillJob,
+    NewTask, Price, Task, TaskStatus, ZoneDefinition,
+};
+
+use super::error::StorageError;
+use super::store::{gap_check_day, DayGap, FetchLogFilter, FetchLogPage, PoolStatus, PriceCoverage, PriceStore, TableStats, ZoneStats};
+use super::stored_price::StoredPrice;
+
+/// SQLite-backed store for single-node deployments that don't run Postgres.
+/// The schema is embedded and migrated on connect, whatever `run_migrations`
+/// says, since nothing else creates it.
+pub struct SqlitePriceStore {
+    pool: SqlitePool,
+}
+
+fn price_from_row(row: &SqliteRow) -> Result<Price, StorageError> {
+    let price_kwh: String = row.try_get("price_kwh")?;
+    Price::try_from(StoredPrice {
+        timestamp: row.try_get("timestamp")?,
+        bidding_zone: row.try_get("bidding_zone")?,
+        price_kwh: StoredPrice::parse_price_kwh(&price_kwh)?,
+        currency: row.try_get("currency")?,
+        resolution: row.try_get("resolution")?,
+        fetched_at: row.try_get("fetched_at")?,
+        market: row.try_get("market")?,
+        gap_fill: row.try_get("gap_fill")?,
+    })
+}
+
+fn generation_from_row(row: &SqliteRow) -> Result<GenerationPoint, StorageError> {
+    let quantity: String = row.try_get("quantity_mw")?;
+    Ok(GenerationPoint {
+        timestamp: row.try_get("timestamp")?,
+        bidding_zone: row.try_get("bidding_zone")?,
+        psr_type: row.try_get("psr_type")?,
+        quantity_mw: Decimal::from_str(&quantity)
+            .map_err(|e| StorageError::QueryError(format!("Invalid stored quantity {}: {}", quantity, e)))?,
+        resolution: row.try_get("resolution")?,
+        fetched_at: row.try_get("fetched_at")?,
+    })
+}
+
+fn load_from_row(row: &SqliteRow) -> Result<LoadValue, StorageError> {
+    let load: String = row.try_get("load_mw")?;
+    Ok(LoadValue {
+        timestamp: row.try_get("timestamp")?,
+        bidding_zone: row.try_get("bidding_zone")?,
+        load_mw: Decimal::from_str(&load)
+            .map_err(|e| StorageError::QueryError(format!("Invalid stored load {}: {}", load, e)))?,
+        resolution: row.try_get("resolution")?,
+        fetched_at: row.try_get("fetched_at")?,
+    })
+}
+
+fn flow_from_row(row: &SqliteRow) -> Result<CrossBorderFlow, StorageError> {
+    let quantity: String = row.try_get("quantity_mw")?;
+    let kind: String = row.try_get("kind")?;
+    Ok(CrossBorderFlow {
+        timestamp: row.try_get("timestamp")?,
+        from_zone: row.try_get("from_zone")?,
+        to_zone: row.try_get("to_zone")?,
+        kind: FlowKind::parse(&kind)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored flow kind: {}", kind)))?,
+        quantity_mw: Decimal::from_str(&quantity)
+            .map_err(|e| StorageError::QueryError(format!("Invalid stored quantity {}: {}", quantity, e)))?,
+        resolution: row.try_get("resolution")?,
+        fetched_at: row.try_get("fetched_at")?,
+    })
+}
+
+fn translation_from_row(row: &SqliteRow) -> Result<NameTranslation, StorageError> {
+    let kind: String = row.try_get("kind")?;
+    Ok(NameTranslation {
+        kind: NameKind::parse(&kind)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored name kind: {}", kind)))?,
+        code: row.try_get("code")?,
+        locale: row.try_get("locale")?,
+        name: row.try_get("name")?,
+    })
+}
+
+fn fetch_log_from_row(row: &SqliteRow) -> Result<FetchLog, StorageError> {
+    let status: String = row.try_get("status")?;
+    Ok(FetchLog {
+        id: row.try_get("id")?,
+        fetch_started_at: row.try_get("fetch_started_at")?,
+        fetch_completed_at: row.try_get("fetch_completed_at")?,
+        bidding_zone: row.try_get("bidding_zone")?,
+        period_start: row.try_get("period_start")?,
+        period_end: row.try_get("period_end")?,
+        status: FetchStatus::parse(&status)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid fetch status: {}", status)))?,
+        records_inserted: row.try_get("records_inserted")?,
+        error_message: row.try_get("error_message")?,
+        http_status: row.try_get("http_status")?,
+        duration_ms: row.try_get("duration_ms")?,
+        endpoint: row.try_get("endpoint")?,
+        correlation_id: row.try_get("correlation_id")?,
+    })
+}
+
+fn alert_rule_from_row(row: &SqliteRow) -> Result<AlertRule, StorageError> {
+    let comparison: String = row.try_get("comparison")?;
+    let channel: String = row.try_get("channel")?;
+    let threshold: String = row.try_get("threshold")?;
+    Ok(AlertRule {
+        id: row.try_get("id")?,
+        zone_code: row.try_get("zone_code")?,
+        comparison: AlertComparison::parse(&comparison)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored alert comparison: {}", comparison)))?,
+        threshold: Decimal::from_str(&threshold)
+            .map_err(|e| StorageError::QueryError(format!("Invalid stored threshold {}: {}", threshold, e)))?,
+        channel: AlertChannel::parse(&channel)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored alert channel: {}", channel)))?,
+        target: row.try_get("target")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+fn backfill_job_from_row(row: &SqliteRow) -> Result<BackfillJob, StorageError> {
+    let status: String = row.try_get("status")?;
+    let zones: Option<String> = row.try_get("zones")?;
+    let errors: String = row.try_get("errors")?;
+    Ok(BackfillJob {
+        id: row.try_get("id")?,
+        start_date: row.try_get("start_date")?,
+        end_date: row.try_get("end_date")?,
+        zones: zones.as_deref().map(|zones| json_list("zones", zones)).transpose()?,
+        status: BackfillJobStatus::parse(&status)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored backfill job status: {}", status)))?,
+        dates_total: row.try_get("dates_total")?,
+        dates_done: row.try_get("dates_done")?,
+        prices_stored: row.try_get("prices_stored")?,
+        errors: json_list("errors", &errors)?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+        finished_at: row.try_get("finished_at")?,
+    })
+}
+
+/// [`FetchLogFilter`] as a condition on binds `?1` to `?5`.
+const FETCH_LOG_FILTER: &str = "(?1 IS NULL OR bidding_zone = ?1) AND (?2 IS NULL OR status = ?2) \
+                                AND (?3 IS NULL OR fetch_started_at >= ?3) AND (?4 IS NULL OR fetch_started_at < ?4) \
+                                AND (?5 IS NULL OR correlation_id = ?5)";
+
+const TASK_COLUMNS: &str = "id, kind, payload, dedupe_key, status, attempts, max_attempts, last_error, run_after, \
+                            lease_expires_at, created_at, updated_at, finished_at";
+
+fn task_from_row(row: &SqliteRow) -> Result<Task, StorageError> {
+    let status: String = row.try_get("status")?;
+    Ok(Task {
+        id: row.try_get("id")?,
+        kind: row.try_get("kind")?,
+        payload: row.try_get("payload")?,
+        dedupe_key: row.try_get("dedupe_key")?,
+        status: TaskStatus::parse(&status)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored task status: {}", status)))?,
+        attempts: row.try_get("attempts")?,
+        max_attempts: row.try_get("max_attempts")?,
+        last_error: row.try_get("last_error")?,
+        run_after: row.try_get("run_after")?,
+        lease_expires_at: row.try_get("lease_expires_at")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+        finished_at: row.try_get("finished_at")?,
+    })
+}
+
+/// A JSON array of strings stored in a text column.
+fn json_list(column: &str, value: &str) -> Result<Vec<String>, StorageError> {
+    serde_json::from_str(value).map_err(|e| StorageError::QueryError(format!("Invalid stored {}: {}", column, e)))
+}
+
+fn alert_event_from_row(row: &SqliteRow) -> Result<AlertEvent, StorageError> {
+    let price_kwh: String = row.try_get("price_kwh")?;
+    Ok(AlertEvent {
+        rule_id: row.try_get("rule_id")?,
+        price_timestamp: row.try_get("price_timestamp")?,
+        price_kwh: Decimal::from_str(&price_kwh)
+            .map_err(|e| StorageError::QueryError(format!("Invalid stored price {}: {}", price_kwh, e)))?,
+        fired_at: row.try_get("fired_at")?,
+        delivered: row.try_get("delivered")?,
+        error_message: row.try_get("error_message")?,
+    })
+}
+
+fn api_key_from_row(row: &SqliteRow) -> Result<ApiKey, StorageError> {
+    let role: String = row.try_get("role")?;
+    Ok(ApiKey {
+        name: row.try_get("name")?,
+        key_sha256: row.try_get("key_sha256")?,
+        role: ApiRole::parse(&role)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored API key role: {}", role)))?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+impl SqlitePriceStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn from_config(config: &DatabaseConfig) -> Result<Self, StorageError> {
+        let mut options = SqliteConnectOptions::from_str(&config.url)?
+            .create_if_missing(true)
+            .foreign_keys(true);
+        if let Some(threshold_ms) = config.slow_query_threshold_ms {
+            options = options.log_slow_statements(
+                log::LevelFilter::Warn,
+                StdDuration::from_millis(threshold_ms),
+            );
+            metrics::set_slow_query_threshold(Some(StdDuration::from_millis(threshold_ms)));
+        }
+        if config.statement_timeout_ms.is_some() {
+            warn!("database.statement_timeout_ms only applies to Postgres and is ignored for SQLite");
+        }
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(config.max_connections)
+            .min_connections(config.min_connections)
+            .acquire_timeout(StdDuration::from_secs(config.connect_timeout_seconds))
+            .connect_with(options)
+            .await?;
+
+        let store = Self { pool };
+        store.migrate().await?;
+        Ok(store)
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl PriceStore for SqlitePriceStore {
+    async fn health_check(&self) -> Result<(), StorageError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            active_connections: self.pool.size(),
+            idle_connections: self.pool.num_idle() as u32,
+            max_connections: self.pool.options().get_max_connections(),
+        }
+    }
+
+    /// Apply the migrations embedded from `migrations/sqlite/` that are not
+    /// yet recorded.
+    async fn migrate(&self) -> Result<(), StorageError> {
+        sqlx::migrate!("../../migrations/sqlite")
+            .run(&self.pool)
+            .await
+            .map_err(|e| StorageError::PoolError(format!("SQLite migration failed: {}", e)))
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Price Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_prices(&self, prices: &[Price]) -> Result<usize, StorageError> {
+        if prices.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let mut affected = 0u64;
+
+        for row in prices.iter().map(StoredPrice::from) {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO electricity_prices (timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (timestamp, bidding_zone, market)
+                DO UPDATE SET
+                    price_kwh = excluded.price_kwh,
+                    currency = excluded.currency,
+                    resolution = excluded.resolution,
+                    fetched_at = excluded.fetched_at,
+                    gap_fill = excluded.gap_fill
+                "#,
+            )
+            .bind(row.timestamp)
+            .bind(&row.bidding_zone)
+            .bind(row.price_kwh.to_string())
+            .bind(&row.currency)
+            .bind(&row.resolution)
+            .bind(row.fetched_at)
+            .bind(&row.market)
+            .bind(&row.gap_fill)
+            .execute(&mut *tx)
+            .await?;
+            affected += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(affected as usize)
+    }
+
+    async fn get_prices_by_zone(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Price>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices
+            WHERE bidding_zone = ? AND market = ? AND timestamp >= ? AND timestamp < ?
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(zone_code)
+        .bind(market.as_str())
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(price_from_row).collect()
+    }
+
+    fn stream_prices_by_zone<'a>(
+        &'a self,
+        zone_code: &'a str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxStream<'a, Result<Price, StorageError>> {
+        sqlx::query(
+            r#"
+            SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices
+            WHERE bidding_zone = ? AND market = ? AND timestamp >= ? AND timestamp < ?
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(zone_code)
+        .bind(market.as_str())
+        .bind(start)
+        .bind(end)
+        .fetch(&self.pool)
+        .map(|row| price_from_row(&row?))
+        .boxed()
+    }
+
+    async fn get_prices_by_country(
+        &self,
+        country_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<Price>>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT ep.timestamp, ep.bidding_zone, ep.price_kwh, ep.currency, ep.resolution, ep.fetched_at, ep.market, ep.gap_fill
+            FROM electricity_prices ep
+            JOIN bidding_zones bz ON ep.bidding_zone = bz.zone_code
+            WHERE bz.country_code = ?
+              AND bz.active = TRUE
+              AND ep.market = ?
+              AND ep.timestamp >= ? AND ep.timestamp < ?
+            ORDER BY ep.bidding_zone, ep.timestamp ASC
+            "#,
+        )
+        .bind(country_code)
+        .bind(market.as_str())
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut grouped: HashMap<String, Vec<Price>> = HashMap::new();
+        for row in &rows {
+            let price = price_from_row(row)?;
+            grouped
+                .entry(price.bidding_zone.clone())
+                .or_default()
+                .push(price);
+        }
+
+        Ok(grouped)
+    }
+
+    async fn get_latest_prices(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Price>, StorageError> {
+        // SQLite has no DISTINCT ON; pick each zone's newest row via a correlated subquery.
+        let rows = sqlx::query(
+            r#"
+            SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices ep
+            WHERE market = 'day_ahead'
+              AND timestamp = (
+                SELECT MAX(timestamp) FROM electricity_prices latest
+                WHERE latest.bidding_zone = ep.bidding_zone AND latest.market = 'day_ahead'
+            )
+              AND (?1 IS NULL OR timestamp >= ?1)
+            ORDER BY bidding_zone
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(price_from_row).collect()
+    }
+
+    async fn get_current_prices(&self, at: DateTime<Utc>) -> Result<Vec<Price>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices ep
+            WHERE market = 'day_ahead'
+              AND timestamp = (
+                SELECT MAX(timestamp) FROM electricity_prices current
+                WHERE current.bidding_zone = ep.bidding_zone AND current.market = 'day_ahead'
+                  AND current.timestamp <= ?1 AND current.timestamp > ?2
+            )
+            ORDER BY bidding_zone
+            "#,
+        )
+        .bind(at)
+        .bind(at - Duration::hours(1))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(price_from_row).collect()
+    }
+
+    async fn get_price_at(&self, zone_code: &str, market: Market, at: DateTime<Utc>) -> Result<Option<Price>, StorageError> {
+        let row = sqlx::query(
+            r#"
+            SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+            FROM electricity_prices
+            WHERE bidding_zone = ? AND market = ? AND timestamp <= ? AND timestamp > ?
+            ORDER BY timestamp DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(zone_code)
+        .bind(market.as_str())
+        .bind(at)
+        .bind(at - Duration::days(1))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let price = row.as_ref().map(price_from_row).transpose()?;
+        Ok(price.filter(|price| price.interval_end() > at))
+    }
+
+    async fn get_zone_stats(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<ZoneStats, StorageError> {
+        // Prices are stored as TEXT, so aggregates go through REAL.
+        let row = sqlx::query(
+            r#"
+            WITH zone_prices AS (
+                SELECT timestamp, CAST(price_kwh AS REAL) AS price_kwh, currency
+                FROM electricity_prices
+                WHERE bidding_zone = ? AND market = ? AND timestamp >= ? AND timestamp < ?
+            ),
+            hours AS (
+                SELECT hour, LAG(hour) OVER (ORDER BY hour) AS prev_hour
+                FROM (SELECT DISTINCT strftime('%Y-%m-%d %H:00:00', timestamp) AS hour FROM zone_prices)
+            )
+            SELECT
+                COUNT(*) AS price_count,
+                MIN(price_kwh) AS min_price,
+                MAX(price_kwh) AS max_price,
+                AVG(price_kwh) AS avg_price,
+                MIN(timestamp) AS first_timestamp,
+                MAX(timestamp) AS last_timestamp,
+                MIN(currency) AS currency,
+                (SELECT COUNT(*) FROM hours) AS covered_hours,
+                COALESCE(
+                    (SELECT CAST(ROUND(MAX(julianday(hour) - julianday(prev_hour)) * 24) AS INTEGER) - 1 FROM hours),
+                    0
+                ) AS longest_interior_gap_hours
+            FROM zone_prices
+            "#,
+        )
+        .bind(zone_code)
+        .bind(market.as_str())
+        .bind(start)
+        .bind(end)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let decimal = |column: &str| -> Result<Option<Decimal>, StorageError> {
+            let value: Option<f64> = row.try_get(column)?;
+            Ok(value.and_then(Decimal::from_f64_retain).map(|d| d.round_dp(6)))
+        };
+
+        Ok(ZoneStats {
+            price_count: row.try_get("price_count")?,
+            min_price: decimal("min_price")?,
+            max_price: decimal("max_price")?,
+            avg_price: decimal("avg_price")?,
+            first_timestamp: row.try_get("first_timestamp")?,
+            last_timestamp: row.try_get("last_timestamp")?,
+            covered_hours: row.try_get("covered_hours")?,
+            longest_interior_gap_hours: row.try_get("longest_interior_gap_hours")?,
+            currency: row.try_get("currency")?,
+        })
+    }
+
+    async fn get_price_coverage(&self, zone_code: &str, market: Market) -> Result<Option<PriceCoverage>, StorageError> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                (SELECT MIN(timestamp) FROM electricity_prices WHERE bidding_zone = ?1 AND market = ?2) AS first_timestamp,
+                timestamp AS last_timestamp,
+                resolution AS last_resolution
+            FROM electricity_prices
+            WHERE bidding_zone = ?1 AND market = ?2
+            ORDER BY timestamp DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(zone_code)
+        .bind(market.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(PriceCoverage {
+                first_timestamp: row.try_get("first_timestamp")?,
+                last_timestamp: row.try_get("last_timestamp")?,
+                last_resolution: row.try_get("last_resolution")?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn delete_old_prices(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM electricity_prices WHERE timestamp < ?")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// SQLite keeps prices in a single table.
+    async fn ensure_price_partitions(&self, _from: NaiveDate, _months_ahead: u32) -> Result<Vec<String>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Generation Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_generation(&self, points: &[GenerationPoint]) -> Result<usize, StorageError> {
+        if points.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let mut affected = 0u64;
+
+        for point in points {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO generation_per_type (timestamp, bidding_zone, psr_type, quantity_mw, resolution, fetched_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                ON CONFLICT (timestamp, bidding_zone, psr_type)
+                DO UPDATE SET
+                    quantity_mw = excluded.quantity_mw,
+                    resolution = excluded.resolution,
+                    fetched_at = excluded.fetched_at
+                "#,
+            )
+            .bind(point.timestamp)
+            .bind(&point.bidding_zone)
+            .bind(&point.psr_type)
+            .bind(point.quantity_mw.to_string())
+            .bind(&point.resolution)
+            .bind(point.fetched_at)
+            .execute(&mut *tx)
+            .await?;
+            affected += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(affected as usize)
+    }
+
+    async fn get_generation_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<GenerationPoint>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT timestamp, bidding_zone, psr_type, quantity_mw, resolution, fetched_at
+            FROM generation_per_type
+            WHERE bidding_zone = ? AND timestamp >= ? AND timestamp < ?
+            ORDER BY timestamp ASC, psr_type ASC
+            "#,
+        )
+        .bind(zone_code)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(generation_from_row).collect()
+    }
+
+    async fn delete_old_generation(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM generation_per_type WHERE timestamp < ?")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Load Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_load(&self, values: &[LoadValue]) -> Result<usize, StorageError> {
+        if values.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let mut affected = 0u64;
+
+        for value in values {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO load_values (timestamp, bidding_zone, load_mw, resolution, fetched_at)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT (timestamp, bidding_zone)
+                DO UPDATE SET
+                    load_mw = excluded.load_mw,
+                    resolution = excluded.resolution,
+                    fetched_at = excluded.fetched_at
+                "#,
+            )
+            .bind(value.timestamp)
+            .bind(&value.bidding_zone)
+            .bind(value.load_mw.to_string())
+            .bind(&value.resolution)
+            .bind(value.fetched_at)
+            .execute(&mut *tx)
+            .await?;
+            affected += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(affected as usize)
+    }
+
+    async fn get_load_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LoadValue>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT timestamp, bidding_zone, load_mw, resolution, fetched_at
+            FROM load_values
+            WHERE bidding_zone = ? AND timestamp >= ? AND timestamp < ?
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(zone_code)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(load_from_row).collect()
+    }
+
+    async fn delete_old_load(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM load_values WHERE timestamp < ?")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Cross-Border Flow Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_flows(&self, flows: &[CrossBorderFlow]) -> Result<usize, StorageError> {
+        if flows.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let mut affected = 0u64;
+
+        for flow in flows {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO cross_border_flows (timestamp, from_zone, to_zone, kind, quantity_mw, resolution, fetched_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (timestamp, from_zone, to_zone, kind)
+                DO UPDATE SET
+                    quantity_mw = excluded.quantity_mw,
+                    resolution = excluded.resolution,
+                    fetched_at = excluded.fetched_at
+                "#,
+            )
+            .bind(flow.timestamp)
+            .bind(&flow.from_zone)
+            .bind(&flow.to_zone)
+            .bind(flow.kind.as_str())
+            .bind(flow.quantity_mw.to_string())
+            .bind(&flow.resolution)
+            .bind(flow.fetched_at)
+            .execute(&mut *tx)
+            .await?;
+            affected += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(affected as usize)
+    }
+
+    async fn get_flows_by_zone(
+        &self,
+        zone_code: &str,
+        kind: FlowKind,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<CrossBorderFlow>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT timestamp, from_zone, to_zone, kind, quantity_mw, resolution, fetched_at
+            FROM cross_border_flows
+            WHERE (from_zone = ?1 OR to_zone = ?1) AND kind = ?2 AND timestamp >= ?3 AND timestamp < ?4
+            ORDER BY timestamp ASC, from_zone, to_zone
+            "#,
+        )
+        .bind(zone_code)
+        .bind(kind.as_str())
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(flow_from_row).collect()
+    }
+
+    async fn delete_old_flows(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM cross_border_flows WHERE timestamp < ?")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Name Translation Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_name_translations(&self, translations: &[NameTranslation]) -> Result<usize, StorageError> {
+        if translations.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let mut affected = 0u64;
+
+        for translation in translations {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO name_translations (kind, code, locale, name)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT (kind, code, locale)
+                DO UPDATE SET name = excluded.name
+                "#,
+            )
+            .bind(translation.kind.as_str())
+            .bind(&translation.code)
+            .bind(&translation.locale)
+            .bind(&translation.name)
+            .execute(&mut *tx)
+            .await?;
+            affected += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(affected as usize)
+    }
+
+    async fn get_name_translations(&self, locale: &str) -> Result<Vec<NameTranslation>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT kind, code, locale, name
+            FROM name_translations
+            WHERE locale = ?
+            ORDER BY kind, code
+            "#,
+        )
+        .bind(locale)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(translation_from_row).collect()
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Alert Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn create_alert_rule(&self, rule: &NewAlertRule) -> Result<AlertRule, StorageError> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO alert_rules (zone_code, comparison, threshold, channel, target, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            RETURNING id, zone_code, comparison, threshold, channel, target, created_at
+            "#,
+        )
+        .bind(&rule.zone_code)
+        .bind(rule.comparison.as_str())
+        .bind(rule.threshold.to_string())
+        .bind(rule.channel.as_str())
+        .bind(&rule.target)
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        alert_rule_from_row(&row)
+    }
+
+    async fn list_alert_rules(&self, zone_code: Option<&str>) -> Result<Vec<AlertRule>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, zone_code, comparison, threshold, channel, target, created_at
+            FROM alert_rules
+            WHERE ?1 IS NULL OR zone_code = ?1
+            ORDER BY id
+            "#,
+        )
+        .bind(zone_code)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(alert_rule_from_row).collect()
+    }
+
+    async fn get_alert_rule(&self, id: i64) -> Result<AlertRule, StorageError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, zone_code, comparison, threshold, channel, target, created_at
+            FROM alert_rules
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| StorageError::NotFound(format!("Alert rule not found: {}", id)))?;
+
+        alert_rule_from_row(&row)
+    }
+
+    async fn delete_alert_rule(&self, id: i64) -> Result<bool, StorageError> {
+        let result = sqlx::query("DELETE FROM alert_rules WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_alert_events(&self, events: &[AlertEvent]) -> Result<Vec<AlertEvent>, StorageError> {
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = Vec::new();
+
+        for event in events {
+            let row = sqlx::query(
+                r#"
+                INSERT INTO alert_events (rule_id, price_timestamp, price_kwh, fired_at)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT (rule_id, price_timestamp) DO NOTHING
+                RETURNING rule_id, price_timestamp, price_kwh, fired_at, delivered, error_message
+                "#,
+            )
+            .bind(event.rule_id)
+            .bind(event.price_timestamp)
+            .bind(event.price_kwh.to_string())
+            .bind(event.fired_at)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(row) = row {
+                inserted.push(alert_event_from_row(&row)?);
+            }
+        }
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn complete_alert_event(
+        &self,
+        rule_id: i64,
+        price_timestamp: DateTime<Utc>,
+        delivered: bool,
+        error_message: Option<String>,
+    ) -> Result<(), StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE alert_events
+            SET delivered = ?3, error_message = ?4
+            WHERE rule_id = ?1 AND price_timestamp = ?2
+            "#,
+        )
+        .bind(rule_id)
+        .bind(price_timestamp)
+        .bind(delivered)
+        .bind(&error_message)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(StorageError::NotFound(format!(
+                "Alert event not found: {} at {}",
+                rule_id, price_timestamp
+            )));
+        }
+        Ok(())
+    }
+
+    async fn get_alert_events(&self, rule_id: i64, limit: i64) -> Result<Vec<AlertEvent>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT rule_id, price_timestamp, price_kwh, fired_at, delivered, error_message
+            FROM alert_events
+            WHERE rule_id = ?
+            ORDER BY fired_at DESC, price_timestamp DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(rule_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(alert_event_from_row).collect()
+    }
+
+    async fn failed_alert_events(&self, from: DateTime<Utc>) -> Result<Vec<AlertEvent>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT rule_id, price_timestamp, price_kwh, fired_at, delivered, error_message
+            FROM alert_events
+            WHERE delivered = FALSE AND price_timestamp >= ?
+            ORDER BY rule_id, price_timestamp
+            "#,
+        )
+        .bind(from)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(alert_event_from_row).collect()
+    }
+
+    async fn create_api_key(&self, key: &ApiKey) -> Result<(), StorageError> {
+        sqlx::query("INSERT INTO api_keys (key_sha256, name, role, created_at) VALUES (?, ?, ?, ?)")
+            .bind(&key.key_sha256)
+            .bind(&key.name)
+            .bind(key.role.as_str())
+            .bind(key.created_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, StorageError> {
+        let rows = sqlx::query("SELECT key_sha256, name, role, created_at FROM api_keys ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(api_key_from_row).collect()
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Job Lock Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn try_acquire_job_lock(
+        &self,
+        job_name: &str,
+        target_date: NaiveDate,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO job_locks (job_name, target_date, holder, acquired_at, expires_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (job_name, target_date) DO UPDATE SET
+                holder = excluded.holder,
+                acquired_at = excluded.acquired_at,
+                expires_at = excluded.expires_at
+            WHERE job_locks.expires_at <= excluded.acquired_at
+            "#,
+        )
+        .bind(job_name)
+        .bind(target_date)
+        .bind(holder)
+        .bind(now)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn renew_job_lock(
+        &self,
+        job_name: &str,
+        target_date: NaiveDate,
+        holder: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query("UPDATE job_locks SET expires_at = ? WHERE job_name = ? AND target_date = ? AND holder = ?")
+            .bind(expires_at)
+            .bind(job_name)
+            .bind(target_date)
+            .bind(holder)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn release_job_lock(&self, job_name: &str, target_date: NaiveDate, holder: &str) -> Result<(), StorageError> {
+        sqlx::query("DELETE FROM job_locks WHERE job_name = ? AND target_date = ? AND holder = ?")
+            .bind(job_name)
+            .bind(target_date)
+            .bind(holder)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_job_paused(&self, job_name: &str, paused: bool, now: DateTime<Utc>) -> Result<(), StorageError> {
+        if paused {
+            sqlx::query("INSERT INTO scheduler_job_pauses (job_name, paused_at) VALUES (?, ?) ON CONFLICT (job_name) DO NOTHING")
+                .bind(job_name)
+                .bind(now)
+                .execute(&self.pool)
+                .await?;
+        } else {
+            sqlx::query("DELETE FROM scheduler_job_pauses WHERE job_name = ?")
+                .bind(job_name)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn paused_jobs(&self) -> Result<Vec<String>, StorageError> {
+        Ok(sqlx::query_scalar("SELECT job_name FROM scheduler_job_pauses ORDER BY job_name")
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Idempotency Key Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn claim_idempotency_key(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, fingerprint, status_code, response_body, created_at, expires_at)
+            VALUES (?, ?, NULL, NULL, ?, ?)
+            ON CONFLICT (key) DO UPDATE SET
+                fingerprint = excluded.fingerprint,
+                status_code = NULL,
+                response_body = NULL,
+                created_at = excluded.created_at,
+                expires_at = excluded.expires_at
+            WHERE idempotency_keys.expires_at <= excluded.created_at
+            "#,
+        )
+        .bind(key)
+        .bind(fingerprint)
+        .bind(now)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn get_idempotency_key(&self, key: &str) -> Result<Option<IdempotencyRecord>, StorageError> {
+        // Read from the primary: the key was just claimed or completed there
+        let record = sqlx::query_as::<_, IdempotencyRecord>(
+            "SELECT key, fingerprint, status_code, response_body, created_at, expires_at FROM idempotency_keys WHERE key = ?",
+        )
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(record)
+    }
+
+    async fn complete_idempotency_key(
+        &self,
+        key: &str,
+        claimed_at: DateTime<Utc>,
+        status_code: i32,
+        response_body: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            UPDATE idempotency_keys
+            SET status_code = ?3, response_body = ?4, expires_at = ?5
+            WHERE key = ?1 AND created_at = ?2
+            "#,
+        )
+        .bind(key)
+        .bind(claimed_at)
+        .bind(status_code)
+        .bind(response_body)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn release_idempotency_key(&self, key: &str, claimed_at: DateTime<Utc>) -> Result<(), StorageError> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE key = ? AND created_at = ? AND status_code IS NULL")
+            .bind(key)
+            .bind(claimed_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_expired_idempotency_keys(&self, now: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= ?")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Retry Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn enqueue_fetch_retries(&self, retries: &[FetchRetry]) -> Result<(), StorageError> {
+        let mut tx = self.pool.begin().await?;
+        for retry in retries {
+            sqlx::query(
+                r#"
+                INSERT INTO fetch_retry_queue
+                    (zone_code, target_date, attempts, last_error, enqueued_at, next_attempt_at, expires_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (zone_code, target_date) DO UPDATE SET last_error = excluded.last_error
+                "#,
+            )
+            .bind(&retry.zone_code)
+            .bind(retry.target_date)
+            .bind(retry.attempts)
+            .bind(&retry.last_error)
+            .bind(retry.enqueued_at)
+            .bind(retry.next_attempt_at)
+            .bind(retry.expires_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_due_fetch_retries(&self, now: DateTime<Utc>) -> Result<Vec<FetchRetry>, StorageError> {
+        let retries = sqlx::query_as::<_, FetchRetry>(
+            r#"
+            SELECT zone_code, target_date, attempts, last_error, enqueued_at, next_attempt_at, expires_at
+            FROM fetch_retry_queue
+            WHERE next_attempt_at <= ?1 AND expires_at > ?1
+            ORDER BY next_attempt_at
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(retries)
+    }
+
+    async fn reschedule_fetch_retry(
+        &self,
+        zone_code: &str,
+        target_date: NaiveDate,
+        next_attempt_at: DateTime<Utc>,
+        error: Option<&str>,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            UPDATE fetch_retry_queue
+            SET attempts = attempts + 1, next_attempt_at = ?, last_error = ?
+            WHERE zone_code = ? AND target_date = ?
+            "#,
+        )
+        .bind(next_attempt_at)
+        .bind(error)
+        .bind(zone_code)
+        .bind(target_date)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_fetch_retry(&self, zone_code: &str, target_date: NaiveDate) -> Result<(), StorageError> {
+        sqlx::query("DELETE FROM fetch_retry_queue WHERE zone_code = ? AND target_date = ?")
+            .bind(zone_code)
+            .bind(target_date)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_expired_fetch_retries(&self, now: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM fetch_retry_queue WHERE expires_at <= ?")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Backfill Job Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn create_backfill_job(&self, job: &NewBackfillJob, now: DateTime<Utc>) -> Result<BackfillJob, StorageError> {
+        let zones = job.zones.as_ref().map(|zones| serde_json::json!(zones).to_string());
+        let row = sqlx::query(
+            r#"
+            INSERT INTO backfill_jobs (start_date, end_date, zones, status, dates_total, created_at, updated_at)
+            VALUES (?1, ?2, ?3, 'running', ?4, ?5, ?5)
+            RETURNING id, start_date, end_date, zones, status, dates_total, dates_done, prices_stored, errors,
+                      created_at, updated_at, finished_at
+            "#,
+        )
+        .bind(job.start_date)
+        .bind(job.end_date)
+        .bind(zones)
+        .bind(job.dates_total())
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?;
+
+        backfill_job_from_row(&row)
+    }
+
+    async fn get_backfill_job(&self, id: i64) -> Result<BackfillJob, StorageError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, start_date, end_date, zones, status, dates_total, dates_done, prices_stored, errors,
+                   created_at, updated_at, finished_at
+            FROM backfill_jobs
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| StorageError::NotFound(format!("Backfill job not found: {}", id)))?;
+
+        backfill_job_from_row(&row)
+    }
+
+    async fn update_backfill_job(&self, job: &BackfillJob) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE backfill_jobs
+            SET status = ?2, dates_done = ?3, prices_stored = ?4, errors = ?5, updated_at = ?6, finished_at = ?7
+            WHERE id = ?1 AND status = 'running'
+            "#,
+        )
+        .bind(job.id)
+        .bind(job.status.as_str())
+        .bind(job.dates_done)
+        .bind(job.prices_stored)
+        .bind(serde_json::json!(job.errors).to_string())
+        .bind(job.updated_at)
+        .bind(job.finished_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn cancel_backfill_job(&self, id: i64, now: DateTime<Utc>) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE backfill_jobs
+            SET status = 'cancelled', updated_at = ?2, finished_at = ?2
+            WHERE id = ?1 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Task Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn enqueue_task(&self, task: &NewTask, now: DateTime<Utc>) -> Result<Option<Task>, StorageError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO tasks (kind, payload, dedupe_key, status, max_attempts, run_after, created_at, updated_at)
+            VALUES (?1, ?2, ?3, 'queued', ?4, ?5, ?6, ?6)
+            ON CONFLICT DO NOTHING
+            RETURNING {}
+            "#,
+            TASK_COLUMNS
+        ))
+        .bind(&task.kind)
+        .bind(&task.payload)
+        .bind(&task.dedupe_key)
+        .bind(task.max_attempts)
+        .bind(task.run_after)
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(task_from_row).transpose()
+    }
+
+    async fn claim_task(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>) -> Result<Option<Task>, StorageError> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE tasks
+            SET status = 'running', attempts = attempts + 1, lease_expires_at = ?2, updated_at = ?1
+            WHERE id = (
+                SELECT id FROM tasks
+                WHERE (status = 'queued' AND run_after <= ?1) OR (status = 'running' AND lease_expires_at <= ?1)
+                ORDER BY run_after, id
+                LIMIT 1
+            )
+            RETURNING {}
+            "#,
+            TASK_COLUMNS
+        ))
+        .bind(now)
+        .bind(lease_until)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(task_from_row).transpose()
+    }
+
+    async fn renew_task_lease(&self, id: i64, attempt: i32, lease_until: DateTime<Utc>) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tasks
+            SET lease_expires_at = ?3
+            WHERE id = ?1 AND attempts = ?2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(attempt)
+        .bind(lease_until)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn finish_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        status: TaskStatus,
+        error: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tasks
+            SET status = ?3, last_error = COALESCE(?4, last_error), lease_expires_at = NULL, updated_at = ?5,
+                finished_at = ?5
+            WHERE id = ?1 AND attempts = ?2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(attempt)
+        .bind(status.as_str())
+        .bind(error)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn retry_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        error: &str,
+        run_after: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tasks
+            SET status = 'queued', last_error = ?3, run_after = ?4, lease_expires_at = NULL, updated_at = ?5
+            WHERE id = ?1 AND attempts = ?2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(attempt)
+        .bind(error)
+        .bind(run_after)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_task(&self, id: i64) -> Result<Task, StorageError> {
+        let row = sqlx::query(&format!("SELECT {} FROM tasks WHERE id = ?", TASK_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(format!("Task not found: {}", id)))?;
+
+        task_from_row(&row)
+    }
+
+    async fn list_tasks(&self, status: Option<TaskStatus>, limit: i64) -> Result<Vec<Task>, StorageError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM tasks
+            WHERE ?1 IS NULL OR status = ?1
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?2
+            "#,
+            TASK_COLUMNS
+        ))
+        .bind(status.map(|status| status.as_str()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(task_from_row).collect()
+    }
+
+    async fn count_tasks_by_status(&self) -> Result<HashMap<TaskStatus, i64>, StorageError> {
+        let rows: Vec<(String, i64)> = sqlx::query_as("SELECT status, COUNT(*) FROM tasks GROUP BY status")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|(status, count)| {
+                TaskStatus::parse(&status)
+                    .map(|status| (status, count))
+                    .ok_or_else(|| StorageError::QueryError(format!("Invalid stored task status: {}", status)))
+            })
+            .collect()
+    }
+
+    async fn delete_finished_tasks(&self, before: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM tasks WHERE status IN ('succeeded', 'failed') AND finished_at < ?")
+            .bind(before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Zone Registry Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn load_zones(&self) -> Result<Vec<BiddingZone>, StorageError> {
+        let zones = sqlx::query_as::<_, BiddingZone>(
+            r#"
+            SELECT zone_code, zone_name, country_code, country_name, eic_code, timezone, active,
+                   valid_from, valid_to, predecessor_code, created_at, updated_at
+            FROM bidding_zones
+            WHERE active = TRUE
+            ORDER BY country_code, zone_code
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(zones)
+    }
+
+    async fn get_zone_by_code(&self, zone_code: &str) -> Result<BiddingZone, StorageError> {
+        sqlx::query_as::<_, BiddingZone>(
+            r#"
+            SELECT zone_code, zone_name, country_code, country_name, eic_code, timezone, active,
+                   valid_from, valid_to, predecessor_code, created_at, updated_at
+            FROM bidding_zones
+            WHERE zone_code = ?
+            "#,
+        )
+        .bind(zone_code)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| StorageError::NotFound(format!("Zone not found: {}", zone_code)))
+    }
+
+    async fn get_zone_by_eic(&self, eic_code: &str) -> Result<BiddingZone, StorageError> {
+        sqlx::query_as::<_, BiddingZone>(
+            r#"
+            SELECT zone_code, zone_name, country_code, country_name, eic_code, timezone, active,
+                   valid_from, valid_to, predecessor_code, created_at, updated_at
+            FROM bidding_zones
+            WHERE eic_code = ?
+            "#,
+        )
+        .bind(eic_code)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| StorageError::NotFound(format!("Zone not found for EIC: {}", eic_code)))
+    }
+
+    async fn get_zones_by_country(
+        &self,
+        country_code: &str,
+    ) -> Result<Vec<BiddingZone>, StorageError> {
+        let zones = sqlx::query_as::<_, BiddingZone>(
+            r#"
+            SELECT zone_code, zone_name, country_code, country_name, eic_code, timezone, active,
+                   valid_from, valid_to, predecessor_code, created_at, updated_at
+            FROM bidding_zones
+            WHERE country_code = ? AND active = TRUE
+            ORDER BY zone_code
+            "#,
+        )
+        .bind(country_code)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(zones)
+    }
+
+    async fn get_countries(&self) -> Result<Vec<(String, String)>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT country_code, country_name
+            FROM bidding_zones
+            WHERE active = TRUE
+            ORDER BY country_code
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let countries = rows
+            .iter()
+            .map(|row| {
+                let code: String = row.get("country_code");
+                let name: String = row.get("country_name");
+                (code, name)
+            })
+            .collect();
+
+        Ok(countries)
+    }
+
+    async fn insert_missing_zones(&self, zones: &[ZoneDefinition]) -> Result<Vec<String>, StorageError> {
+        let today = Utc::now().date_naive();
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = Vec::new();
+        for zone in zones {
+            let zone_code: Option<String> = sqlx::query_scalar(
+                r#"
+                INSERT INTO bidding_zones (zone_code, zone_name, country_code, country_name, eic_code, timezone,
+                                           valid_from, valid_to, predecessor_code, active)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT DO NOTHING
+                RETURNING zone_code
+                "#,
+            )
+            .bind(&zone.zone_code)
+            .bind(&zone.zone_name)
+            .bind(&zone.country_code)
+            .bind(&zone.country_name)
+            .bind(&zone.eic_code)
+            .bind(&zone.timezone)
+            .bind(zone.valid_from)
+            .bind(zone.valid_to)
+            .bind(&zone.predecessor_code)
+            .bind(zone.is_active_on(today))
+            .fetch_optional(&mut *tx)
+            .await?;
+            inserted.extend(zone_code);
+        }
+        tx.commit().await?;
+
+        Ok(inserted)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Log Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn log_fetch_start(
+        &self,
+        zone_code: Option<String>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        correlation_id: Option<String>,
+    ) -> Result<i64, StorageError> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO fetch_log (fetch_started_at, bidding_zone, period_start, period_end, status, correlation_id)
+            VALUES (?, ?, ?, ?, 'pending', ?)
+            RETURNING id
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(&zone_code)
+        .bind(period_start)
+        .bind(period_end)
+        .bind(&correlation_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.get("id"))
+    }
+
+    async fn log_fetch_complete(
+        &self,
+        fetch_id: i64,
+        status: FetchStatus,
+        records_inserted: i32,
+        error_message: Option<String>,
+        http_status: Option<i32>,
+        duration_ms: i32,
+    ) -> Result<(), StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE fetch_log
+            SET fetch_completed_at = ?,
+                status = ?,
+                records_inserted = ?,
+                error_message = ?,
+                http_status = ?,
+                duration_ms = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(status.as_str())
+        .bind(records_inserted)
+        .bind(&error_message)
+        .bind(http_status)
+        .bind(duration_ms)
+        .bind(fetch_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(StorageError::NotFound(format!(
+                "Fetch log not found: {}",
+                fetch_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn log_fetch_endpoint(&self, fetch_id: i64, endpoint: &str) -> Result<(), StorageError> {
+        sqlx::query("UPDATE fetch_log SET endpoint = ? WHERE id = ?")
+            .bind(endpoint)
+            .bind(fetch_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_recent_fetch_logs(&self, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, fetch_started_at, fetch_completed_at, bidding_zone, period_start, period_end,
+                   status, records_inserted, error_message, http_status, duration_ms, endpoint,
+                   correlation_id
+            FROM fetch_log
+            ORDER BY fetch_started_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(fetch_log_from_row).collect()
+    }
+
+    async fn list_fetch_logs(&self, filter: &FetchLogFilter, page: FetchLogPage, limit: i64) -> Result<Vec<FetchLog>, StorageError> {
+        let (offset, after) = match page {
+            FetchLogPage::Offset(offset) => (offset, None),
+            FetchLogPage::After { started_at, id } => (0, Some((started_at, id))),
+        };
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT id, fetch_started_at, fetch_completed_at, bidding_zone, period_start, period_end,
+                   status, records_inserted, error_message, http_status, duration_ms, endpoint,
+                   correlation_id
+            FROM fetch_log
+            WHERE {}
+              AND (?6 IS NULL OR fetch_started_at < ?6 OR (fetch_started_at = ?6 AND id < ?7))
+            ORDER BY fetch_started_at DESC, id DESC
+            LIMIT ?8 OFFSET ?9
+            "#,
+            FETCH_LOG_FILTER
+        ))
+        .bind(filter.zone_code.as_deref())
+        .bind(filter.status.as_ref().map(|status| status.as_str()))
+        .bind(filter.started_from)
+        .bind(filter.started_before)
+        .bind(filter.correlation_id.as_deref())
+        .bind(after.map(|(started_at, _)| started_at))
+        .bind(after.map(|(_, id)| id))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(fetch_log_from_row).collect()
+    }
+
+    async fn count_fetch_logs(&self, filter: &FetchLogFilter) -> Result<i64, StorageError> {
+        let count = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM fetch_log WHERE {}", FETCH_LOG_FILTER))
+            .bind(filter.zone_code.as_deref())
+            .bind(filter.status.as_ref().map(|status| status.as_str()))
+            .bind(filter.started_from)
+            .bind(filter.started_before)
+            .bind(filter.correlation_id.as_deref())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn get_last_successful_fetch(&self) -> Result<Option<DateTime<Utc>>, StorageError> {
+        let completed_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            r#"
+            SELECT fetch_completed_at
+            FROM fetch_log
+            WHERE status = 'success' AND fetch_completed_at IS NOT NULL
+            ORDER BY fetch_completed_at DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(completed_at)
+    }
+
+    async fn get_last_fetch_run(&self) -> Result<Option<DateTime<Utc>>, StorageError> {
+        let started_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            r#"
+            SELECT fetch_started_at
+            FROM fetch_log
+            WHERE bidding_zone IS NULL
+            ORDER BY fetch_started_at DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(started_at)
+    }
+
+    async fn get_fetch_logs_by_zone(
+        &self,
+        zone_code: &str,
+        limit: i64,
+    ) -> Result<Vec<FetchLog>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, fetch_started_at, fetch_completed_at, bidding_zone, period_start, period_end,
+                   status, records_inserted, error_message, http_status, duration_ms, endpoint,
+                   correlation_id
+            FROM fetch_log
+            WHERE bidding_zone = ?
+            ORDER BY fetch_started_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(zone_code)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(fetch_log_from_row).collect()
+    }
+
+    async fn get_latest_fetch_logs(&self) -> Result<Vec<FetchLog>, StorageError> {
+        // SQLite has no DISTINCT ON; pick each zone's newest row via a correlated subquery.
+        let rows = sqlx::query(
+            r#"
+            SELECT id, fetch_started_at, fetch_completed_at, bidding_zone, period_start, period_end,
+                   status, records_inserted, error_message, http_status, duration_ms, endpoint,
+                   correlation_id
+            FROM fetch_log fl
+            WHERE id = (
+                SELECT id FROM fetch_log latest
+                WHERE latest.bidding_zone = fl.bidding_zone
+                ORDER BY latest.fetch_started_at DESC, latest.id DESC
+                LIMIT 1
+            )
+            ORDER BY bidding_zone
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(fetch_log_from_row).collect()
+    }
+
+    async fn delete_old_fetch_logs(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError> {
+        let result = sqlx::query("DELETE FROM fetch_log WHERE fetch_started_at < ?")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn has_day_ahead_data(&self, zone_code: &str, date: NaiveDate) -> Result<bool, StorageError> {
+        let day_end = date.succ_opt().unwrap();
+
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM electricity_prices
+            WHERE bidding_zone = ?
+              AND market = 'day_ahead'
+              AND timestamp >= ?
+              AND timestamp < ?
+            "#,
+        )
+        .bind(zone_code)
+        .bind(date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .bind(day_end.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    async fn find_gaps(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<DayGap>, StorageError> {
+        // Local days start and end within a day of the UTC ones
+        let range_start = start_date.pred_opt().unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let range_end = end_date.succ_opt().unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc() + Duration::days(1);
+
+        let timezones: HashMap<String, String> = sqlx::query("SELECT zone_code, timezone FROM bidding_zones")
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|row| Ok((row.try_get("zone_code")?, row.try_get("timezone")?)))
+            .collect::<Result<_, StorageError>>()?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT bidding_zone, timestamp
+            FROM electricity_prices
+            WHERE market = 'day_ahead' AND timestamp >= ? AND timestamp < ?
+            "#,
+        )
+        .bind(range_start)
+        .bind(range_end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut hours: HashMap<String, BTreeSet<DateTime<Utc>>> = HashMap::new();
+        for row in &rows {
+            let timestamp: DateTime<Utc> = row.try_get("timestamp")?;
+            if let Ok(hour) = timestamp.duration_trunc(Duration::hours(1)) {
+                hours.entry(row.try_get("bidding_zone")?).or_default().insert(hour);
+            }
+        }
+
+        let mut sorted_zones: Vec<&String> = zone_codes.iter().collect();
+        sorted_zones.sort();
+
+        let mut gaps = Vec::new();
+        let mut current = start_date;
+        while current <= end_date {
+            for zone_code in &sorted_zones {
+                let day = gap_check_day(current, timezones.get(*zone_code).map(String::as_str));
+                let existing = hours
+                    .get(*zone_code)
+                    .map_or(0, |hours| hours.range(day.start..day.end).count() as i64);
+                if existing < day.hours() {
+                    gaps.push(DayGap {
+                        date: current,
+                        zone_code: (*zone_code).clone(),
+                        hours: existing,
+                        expected_hours: day.hours(),
+                    });
+                }
+            }
+            current = current.succ_opt().unwrap();
+        }
+
+        Ok(gaps)
+    }
+
+    async fn find_estimated_hours(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<(NaiveDate, String, i64)>, StorageError> {
+        let range_start = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let range_end = end_date.succ_opt().unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        let rows = sqlx::query(
+            r#"
+            SELECT substr(timestamp, 1, 10) AS price_date, bidding_zone,
+                COUNT(DISTINCT substr(timestamp, 1, 13)) AS estimated_count
+            FROM electricity_prices
+            WHERE market = 'day_ahead' AND gap_fill IS NOT NULL AND timestamp >= ? AND timestamp < ?
+            GROUP BY substr(timestamp, 1, 10), bidding_zone
+            ORDER BY price_date, bidding_zone
+            "#,
+        )
+        .bind(range_start)
+        .bind(range_end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut estimated = Vec::new();
+        for row in &rows {
+            let zone_code: String = row.try_get("bidding_zone")?;
+            if !zone_codes.contains(&zone_code) {
+                continue;
+            }
+            let date_str: String = row.try_get("price_date")?;
+            let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
+                .map_err(|e| StorageError::QueryError(format!("Invalid stored date {}: {}", date_str, e)))?;
+            estimated.push((date, zone_code, row.try_get("estimated_count")?));
+        }
+
+        Ok(estimated)
+    }
+
+    /// SQLite reports no per-table sizes without the optional dbstat
+    /// extension, so only row counts and the oldest entries are returned.
+    async fn get_table_stats(&self) -> Result<Vec<TableStats>, StorageError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT 'electricity_prices' AS table_name,
+                   (SELECT COUNT(*) FROM electricity_prices) AS row_count,
+                   (SELECT MIN(timestamp) FROM electricity_prices) AS oldest_entry
+            UNION ALL
+            SELECT 'fetch_log',
+                   (SELECT COUNT(*) FROM fetch_log),
+                   (SELECT MIN(fetch_started_at) FROM fetch_log)
+            UNION ALL
+            SELECT 'bidding_zones',
+                   (SELECT COUNT(*) FROM bidding_zones),
+                   NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(TableStats {
+                    table_name: row.try_get("table_name")?,
+                    row_count: row.try_get("row_count")?,
+                    table_bytes: None,
+                    index_bytes: None,
+                    oldest_entry: row.try_get("oldest_entry")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entsoe_core::models::GapFill;
+    use crate::config::DatabaseBackend;
+    use chrono::TimeZone;
+
+    async fn memory_store() -> SqlitePriceStore {
+        let config = DatabaseConfig {
+            backend: DatabaseBackend::Sqlite,
+            url: "sqlite::memory:".to_string(),
+            max_connections: 1,
+            min_connections: 1,
+            connect_timeout_seconds: 5,
+            slow_query_threshold_ms: None,
+            statement_timeout_ms: None,
+            read_url: None,
+            run_migrations: true,
+            connect_retries: 0,
+            connect_retry_initial_ms: 500,
+            connect_retry_max_ms: 5_000,
+            lazy_connect: false,
+        };
+        SqlitePriceStore::from_config(&config).await.unwrap()
+    }
+
+    fn price(hour: u32, zone: &str, mwh: f64) -> Price {
+        Price::from_mwh(
+            Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap(),
+            zone.to_string(),
+            mwh,
+            "PT60M".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_schema_is_migrated_with_run_migrations_off() {
+        let config = DatabaseConfig {
+            backend: DatabaseBackend::Sqlite,
+            url: "sqlite::memory:".to_string(),
+            max_connections: 1,
+            min_connections: 1,
+            connect_timeout_seconds: 5,
+            slow_query_threshold_ms: None,
+            statement_timeout_ms: None,
+            read_url: None,
+            run_migrations: false,
+            connect_retries: 0,
+            connect_retry_initial_ms: 500,
+            connect_retry_max_ms: 5_000,
+            lazy_connect: false,
+        };
+        let store = SqlitePriceStore::from_config(&config).await.unwrap();
+        store.upsert_prices(&[price(0, "NO1", 40.0)]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_query_prices() {
+        let store = memory_store().await;
+        let prices = vec![price(0, "NO1", 50.0), price(1, "NO1", 60.5)];
+        assert_eq!(store.upsert_prices(&prices).await.unwrap(), 2);
+
+        // Re-upserting updates in place rather than duplicating
+        store.upsert_prices(&[price(1, "NO1", 70.0)]).await.unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 16, 0, 0, 0).unwrap();
+        let stored = store.get_prices_by_zone("NO1", Market::DayAhead, start, end).await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[1].price_kwh, Decimal::from_str("0.07").unwrap());
+
+        let latest = store.get_latest_prices(None).await.unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].timestamp, stored[1].timestamp);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_stores_and_replaces_the_gap_fill_method() {
+        let store = memory_store().await;
+        let mut interpolated = price(1, "NO1", 70.0);
+        interpolated.gap_fill = Some(GapFill::LinearInterpolation);
+        store.upsert_prices(&[price(0, "NO1", 50.0), interpolated]).await.unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 16, 0, 0, 0).unwrap();
+        let stored = store.get_prices_by_zone("NO1", Market::DayAhead, start, end).await.unwrap();
+        assert_eq!((stored[0].gap_fill, stored[1].gap_fill), (None, Some(GapFill::LinearInterpolation)));
+
+        // Once ENTSOE publishes the price, the re-fetched row is no longer filled
+        store.upsert_prices(&[price(1, "NO1", 72.0)]).await.unwrap();
+        let stored = store.get_prices_by_zone("NO1", Market::DayAhead, start, end).await.unwrap();
+        assert_eq!(stored[1].gap_fill, None);
+    }
+
+    #[tokio::test]
+    async fn test_markets_are_stored_side_by_side() {
+        let store = memory_store().await;
+        store
+            .upsert_prices(&[price(0, "NO1", 50.0), price(0, "NO1", 80.0).with_market(Market::Intraday)])
+            .await
+            .unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let end = start + Duration::days(1);
+        let intraday = store.get_prices_by_zone("NO1", Market::Intraday, start, end).await.unwrap();
+        assert_eq!(intraday.len(), 1);
+        assert_eq!(intraday[0].market, Market::Intraday);
+        assert_eq!(intraday[0].price_kwh, Decimal::from_str("0.08").unwrap());
+
+        let latest = store.get_latest_prices(None).await.unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].market, Market::DayAhead);
+    }
+
+    #[tokio::test]
+    async fn test_stream_prices_by_zone_yields_range_in_order() {
+        let store = memory_store().await;
+        store
+            .upsert_prices(&[price(5, "NO1", 50.0), price(2, "NO1", 40.0), price(3, "NO2", 30.0), price(9, "NO1", 20.0)])
+            .await
+            .unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let streamed: Vec<Price> = store
+            .stream_prices_by_zone("NO1", Market::DayAhead, start, start + Duration::hours(9))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        let hours: Vec<_> = streamed.iter().map(|p| (p.timestamp - start).num_hours()).collect();
+        assert_eq!(hours, vec![2, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_price_at_finds_the_interval_covering_a_timestamp() {
+        let store = memory_store().await;
+        let mut quarter = price(2, "NO1", 80.0);
+        quarter.resolution = "PT15M".to_string();
+        store.upsert_prices(&[price(0, "NO1", 50.0), price(1, "NO1", 60.0), quarter]).await.unwrap();
+
+        let at = |hour, minute| Utc.with_ymd_and_hms(2025, 1, 15, hour, minute, 0).unwrap();
+        let found = store.get_price_at("NO1", Market::DayAhead, at(1, 59)).await.unwrap().unwrap();
+        assert_eq!(found.timestamp, at(1, 0));
+        assert_eq!(found.price_kwh, price(1, "NO1", 60.0).price_kwh);
+        assert_eq!(store.get_price_at("NO1", Market::DayAhead, at(0, 0)).await.unwrap().unwrap().timestamp, at(0, 0));
+
+        // Past the end of the last interval, or before the first, nothing covers it
+        assert!(store.get_price_at("NO1", Market::DayAhead, at(2, 15)).await.unwrap().is_none());
+        assert!(store.get_price_at("NO1", Market::DayAhead, at(0, 0) - Duration::seconds(1)).await.unwrap().is_none());
+        assert!(store.get_price_at("NO1", Market::Intraday, at(1, 30)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_find_gaps_counts_existing_hours() {
+        let store = memory_store().await;
+        let prices: Vec<Price> = (0..10).map(|h| price(h, "NO2", 40.0)).collect();
+        store.upsert_prices(&prices).await.unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let gaps = store
+            .find_gaps(date, date, &["NO1".to_string(), "NO2".to_string()])
+            .await
+            .unwrap();
+        let gap = |zone_code: &str, hours| DayGap {
+            date,
+            zone_code: zone_code.to_string(),
+            hours,
+            expected_hours: 24,
+        };
+        assert_eq!(gaps, vec![gap("NO1", 0), gap("NO2", 10)]);
+    }
+
+    #[tokio::test]
+    async fn test_find_gaps_checks_local_days_by_their_length() {
+        let store = memory_store().await;
+        // Oslo's 30 March 2025 lasts 23 hours, from 23:00 UTC the day before
+        let date = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+        let day_start = Utc.with_ymd_and_hms(2025, 3, 29, 23, 0, 0).unwrap();
+        let quarters = |zone: &str, hours: i64| -> Vec<Price> {
+            (0..hours * 4)
+                .map(|quarter| {
+                    let at = day_start + Duration::minutes(15 * quarter);
+                    Price::from_mwh(at, zone.to_string(), 40.0, "PT15M".to_string())
+                })
+                .collect()
+        };
+        store.upsert_prices(&quarters("NO1", 23)).await.unwrap();
+        store.upsert_prices(&quarters("NO2", 22)).await.unwrap();
+
+        let gaps = store
+            .find_gaps(date, date, &["NO1".to_string(), "NO2".to_string()])
+            .await
+            .unwrap();
+        let expected = DayGap {
+            date,
+            zone_code: "NO2".to_string(),
+            hours: 22,
+            expected_hours: 23,
+        };
+        assert_eq!(gaps, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn test_find_estimated_hours_counts_filled_hours() {
+        let store = memory_store().await;
+        let mut prices: Vec<Price> = (0..4).map(|h| price(h, "NO2", 40.0)).collect();
+        prices[1].gap_fill = Some(GapFill::ForwardFill);
+        prices[2].gap_fill = Some(GapFill::ForwardFill);
+        prices.push(price(0, "NO3", 40.0));
+        store.upsert_prices(&prices).await.unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let estimated = store
+            .find_estimated_hours(date, date, &["NO2".to_string(), "NO3".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(estimated, vec![(date, "NO2".to_string(), 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_job_lock_is_exclusive_until_released_or_expired() {
+        let store = memory_store().await;
+        let date = NaiveDate::from_ymd_opt(2025, 1, 16).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let ttl = Duration::minutes(30);
+
+        assert!(store.try_acquire_job_lock("price_fetch", date, "a", now, now + ttl).await.unwrap());
+        assert!(!store.try_acquire_job_lock("price_fetch", date, "b", now, now + ttl).await.unwrap());
+        assert!(store.try_acquire_job_lock("price_fetch", date.succ_opt().unwrap(), "b", now, now + ttl).await.unwrap());
+
+        // Only the holder releases its lock
+        store.release_job_lock("price_fetch", date, "b").await.unwrap();
+        assert!(!store.try_acquire_job_lock("price_fetch", date, "b", now, now + ttl).await.unwrap());
+        store.release_job_lock("price_fetch", date, "a").await.unwrap();
+        assert!(store.try_acquire_job_lock("price_fetch", date, "b", now, now + ttl).await.unwrap());
+
+        // An expired lock is taken over
+        let later = now + ttl;
+        assert!(store.try_acquire_job_lock("price_fetch", date, "c", later, later + ttl).await.unwrap());
+
+        // Only the current holder renews it
+        assert!(!store.renew_job_lock("price_fetch", date, "b", later + ttl * 2).await.unwrap());
+        assert!(store.renew_job_lock("price_fetch", date, "c", later + ttl * 2).await.unwrap());
+        assert!(!store.try_acquire_job_lock("price_fetch", date, "d", later + ttl, later + ttl * 3).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_is_held_until_completed_and_expired() {
+        let store = memory_store().await;
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let timeout = now + Duration::minutes(30);
+
+        assert!(store.claim_idempotency_key("k1", "abc", now, timeout).await.unwrap());
+        assert!(!store.claim_idempotency_key("k1", "abc", now, timeout).await.unwrap());
+        let record = store.get_idempotency_key("k1").await.unwrap().unwrap();
+        assert_eq!((record.fingerprint.as_str(), record.status_code), ("abc", None));
+
+        // A completion fenced by another claim's time is ignored
+        store.complete_idempotency_key("k1", timeout, 200, "{}", now + Duration::days(1)).await.unwrap();
+        assert_eq!(store.get_idempotency_key("k1").await.unwrap().unwrap().status_code, None);
+
+        store
+            .complete_idempotency_key("k1", now, 202, r#"{"job_id":1}"#, now + Duration::days(1))
+            .await
+            .unwrap();
+        let record = store.get_idempotency_key("k1").await.unwrap().unwrap();
+        assert_eq!(record.status_code, Some(202));
+        assert_eq!(record.response_body.as_deref(), Some(r#"{"job_id":1}"#));
+
+        // A completed key is not released, and outlives the in-progress timeout
+        store.release_idempotency_key("k1", now).await.unwrap();
+        assert!(!store.claim_idempotency_key("k1", "abc", timeout, timeout + Duration::minutes(30)).await.unwrap());
+
+        // A released key is claimed again
+        assert!(store.claim_idempotency_key("k2", "def", now, timeout).await.unwrap());
+        store.release_idempotency_key("k2", now).await.unwrap();
+        assert!(store.get_idempotency_key("k2").await.unwrap().is_none());
+        assert!(store.claim_idempotency_key("k2", "def", now, timeout).await.unwrap());
+
+        assert_eq!(store.delete_expired_idempotency_keys(timeout).await.unwrap(), 1);
+        assert!(store.get_idempotency_key("k1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_retry_queue_schedules_and_expires_retries() {
+        let store = memory_store().await;
+        let date = NaiveDate::from_ymd_opt(2025, 1, 16).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let retry = |zone_code: &str, expires_at| FetchRetry {
+            zone_code: zone_code.to_string(),
+            target_date: date,
+            attempts: 0,
+            last_error: Some("rate limited".to_string()),
+            enqueued_at: now,
+            next_attempt_at: now,
+            expires_at,
+        };
+
+        store
+            .enqueue_fetch_retries(&[retry("NO1", now + Duration::days(1)), retry("NO2", now + Duration::hours(1))])
+            .await
+            .unwrap();
+        store
+            .reschedule_fetch_retry("NO1", date, now + Duration::minutes(10), Some("timeout"))
+            .await
+            .unwrap();
+
+        // Re-queueing keeps the schedule and only takes the newer error
+        let mut requeued = retry("NO1", now + Duration::days(1));
+        requeued.last_error = Some("server error".to_string());
+        store.enqueue_fetch_retries(&[requeued]).await.unwrap();
+
+        let due: Vec<String> = store.get_due_fetch_retries(now).await.unwrap().into_iter().map(|r| r.zone_code).collect();
+        assert_eq!(due, vec!["NO2"]);
+        let later = store.get_due_fetch_retries(now + Duration::minutes(10)).await.unwrap();
+        assert_eq!(later.len(), 2);
+        assert_eq!(later[1].zone_code, "NO1");
+        assert_eq!(later[1].attempts, 1);
+        assert_eq!(later[1].last_error.as_deref(), Some("server error"));
+
+        assert_eq!(store.delete_expired_fetch_retries(now + Duration::hours(1)).await.unwrap(), 1);
+        store.delete_fetch_retry("NO1", date).await.unwrap();
+        assert!(store.get_due_fetch_retries(now + Duration::days(2)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_job_progress_and_cancellation() {
+        let store = memory_store().await;
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let request = NewBackfillJob {
+            start_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2025, 1, 14).unwrap(),
+            zones: Some(vec!["NO1".to_string(), "NO2".to_string()]),
+        };
+
+        let mut job = store.create_backfill_job(&request, now).await.unwrap();
+        assert_eq!((job.status, job.dates_total, job.dates_done), (BackfillJobStatus::Running, 14, 0));
+        job.dates_done = 7;
+        job.prices_stored = 168;
+        job.errors.push("NO2 2025-01-03: rate limited".to_string());
+        job.updated_at = now + Duration::minutes(1);
+        assert!(store.update_backfill_job(&job).await.unwrap());
+
+        let stored = store.get_backfill_job(job.id).await.unwrap();
+        assert_eq!(stored.zones, request.zones);
+        assert_eq!((stored.dates_done, stored.prices_stored), (7, 168));
+        assert_eq!(stored.errors, job.errors);
+
+        // Progress is no longer saved once cancelled
+        assert!(store.cancel_backfill_job(job.id, now + Duration::minutes(2)).await.unwrap());
+        assert!(!store.cancel_backfill_job(job.id, now + Duration::minutes(3)).await.unwrap());
+        job.dates_done = 14;
+        assert!(!store.update_backfill_job(&job).await.unwrap());
+        let stored = store.get_backfill_job(job.id).await.unwrap();
+        assert_eq!((stored.status, stored.dates_done), (BackfillJobStatus::Cancelled, 7));
+        assert_eq!(stored.finished_at, Some(now + Duration::minutes(2)));
+
+        assert!(store.get_backfill_job(job.id + 1).await.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_task_queue_claims_retries_and_dedupes() {
+        let store = memory_store().await;
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 3, 30, 0).unwrap();
+        let task = |key: &str, run_after| NewTask {
+            kind: "retention".to_string(),
+            payload: "{}".to_string(),
+            dedupe_key: Some(key.to_string()),
+            max_attempts: 3,
+            run_after,
+        };
+
+        let first = store.enqueue_task(&task("a", now), now).await.unwrap().unwrap();
+        assert!(store.enqueue_task(&task("a", now), now).await.unwrap().is_none());
+        let later = store.enqueue_task(&task("b", now + Duration::hours(1)), now).await.unwrap().unwrap();
+
+        // Only the due task is claimed, and only once while its lease holds
+        let lease = now + Duration::minutes(5);
+        let claimed = store.claim_task(now, lease).await.unwrap().unwrap();
+        assert_eq!((claimed.id, claimed.status, claimed.attempts), (first.id, TaskStatus::Running, 1));
+        assert_eq!(claimed.lease_expires_at, Some(lease));
+        assert!(store.claim_task(now, lease).await.unwrap().is_none());
+        assert!(store.renew_task_lease(first.id, 1, lease + Duration::minutes(5)).await.unwrap());
+
+        let retry_at = now + Duration::minutes(1);
+        assert!(store.retry_task(first.id, 1, "timeout", retry_at, now).await.unwrap());
+        assert!(!store.renew_task_lease(first.id, 1, lease).await.unwrap());
+        let claimed = store.claim_task(retry_at, lease).await.unwrap().unwrap();
+        assert_eq!((claimed.attempts, claimed.last_error.as_deref()), (2, Some("timeout")));
+        assert!(!store.finish_task(first.id, 1, TaskStatus::Succeeded, None, retry_at).await.unwrap());
+        assert!(store.finish_task(first.id, 2, TaskStatus::Succeeded, None, retry_at).await.unwrap());
+
+        // A finished task no longer holds its dedupe key
+        let again = store.enqueue_task(&task("a", now), retry_at).await.unwrap().unwrap();
+        let counts = store.count_tasks_by_status().await.unwrap();
+        assert_eq!(counts.get(&TaskStatus::Queued), Some(&2));
+        assert_eq!(counts.get(&TaskStatus::Succeeded), Some(&1));
+        let queued: Vec<i64> = store
+            .list_tasks(Some(TaskStatus::Queued), 10)
+            .await
+            .unwrap()
+            .iter()
+            .map(|task| task.id)
+            .collect();
+        assert_eq!(queued, vec![again.id, later.id]);
+
+        assert_eq!(store.delete_finished_tasks(retry_at).await.unwrap(), 0);
+        assert_eq!(store.delete_finished_tasks(retry_at + Duration::seconds(1)).await.unwrap(), 1);
+        assert!(store.get_task(first.id).await.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_price_coverage_spans_first_to_last_interval() {
+        let store = memory_store().await;
+        assert!(store.get_price_coverage("NO1", Market::DayAhead).await.unwrap().is_none());
+
+        store
+            .upsert_prices(&[price(5, "NO1", 40.0), price(2, "NO1", 40.0), price(9, "NO2", 40.0)])
+            .await
+            .unwrap();
+
+        let coverage = store.get_price_coverage("NO1", Market::DayAhead).await.unwrap().unwrap();
+        assert_eq!(coverage.first_timestamp, Utc.with_ymd_and_hms(2025, 1, 15, 2, 0, 0).unwrap());
+        assert_eq!(coverage.last_timestamp, Utc.with_ymd_and_hms(2025, 1, 15, 5, 0, 0).unwrap());
+        assert_eq!(coverage.end(), Utc.with_ymd_and_hms(2025, 1, 15, 6, 0, 0).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_zone_stats_single_pass() {
+        let store = memory_store().await;
+        let prices: Vec<Price> = [0, 1, 2, 6, 7]
+            .into_iter()
+            .map(|h| price(h, "NO1", 40.0 + h as f64))
+            .collect();
+        store.upsert_prices(&prices).await.unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let stats = store.get_zone_stats("NO1", Market::DayAhead, start, start + Duration::days(1)).await.unwrap();
+        assert_eq!(stats.price_count, 5);
+        assert_eq!(stats.covered_hours, 5);
+        assert_eq!(stats.longest_interior_gap_hours, 3);
+        assert_eq!(stats.min_price, Some(Decimal::from_str("0.04").unwrap()));
+        assert_eq!(stats.max_price, Some(Decimal::from_str("0.047").unwrap()));
+        assert_eq!(stats.last_timestamp, Some(Utc.with_ymd_and_hms(2025, 1, 15, 7, 0, 0).unwrap()));
+
+        let empty = store.get_zone_stats("NO2", Market::DayAhead, start, start + Duration::days(1)).await.unwrap();
+        assert_eq!(empty.price_count, 0);
+        assert!(empty.first_timestamp.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_zone_stats_report_the_zone_currency() {
+        let store = memory_store().await;
+        let gbp = Price {
+            currency: "GBP".to_string(),
+            ..price(0, "NO1", 80.0)
+        };
+        store.upsert_prices(&[gbp]).await.unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let stats = store.get_zone_stats("NO1", Market::DayAhead, start, start + Duration::days(1)).await.unwrap();
+        assert_eq!(stats.currency.as_deref(), Some("GBP"));
+        let empty = store.get_zone_stats("NO2", Market::DayAhead, start, start + Duration::days(1)).await.unwrap();
+        assert!(empty.currency.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_table_stats_count_rows_and_oldest_entry() {
+        let store = memory_store().await;
+        store.upsert_prices(&[price(3, "NO1", 50.0), price(1, "NO1", 55.0)]).await.unwrap();
+
+        let stats = store.get_table_stats().await.unwrap();
+        let prices = stats.iter().find(|t| t.table_name == "electricity_prices").unwrap();
+        assert_eq!(prices.row_count, 2);
+        assert_eq!(prices.oldest_entry, Some(Utc.with_ymd_and_hms(2025, 1, 15, 1, 0, 0).unwrap()));
+        assert!(prices.table_bytes.is_none());
+
+        let fetch_log = stats.iter().find(|t| t.table_name == "fetch_log").unwrap();
+        assert_eq!(fetch_log.row_count, 0);
+        assert!(fetch_log.oldest_entry.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_generation_upsert_query_and_retention() {
+        let store = memory_store().await;
+        let point = |hour: u32, psr_type: &str, mw: i64| GenerationPoint {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap(),
+            bidding_zone: "NO1".to_string(),
+            psr_type: psr_type.to_string(),
+            quantity_mw: Decimal::from(mw),
+            resolution: "PT60M".to_string(),
+            fetched_at: Utc::now(),
+        };
+        store
+            .upsert_generation(&[point(1, "B19", 10), point(1, "B12", 1200), point(0, "B12", 1100)])
+            .await
+            .unwrap();
+        store.upsert_generation(&[point(1, "B12", 1250)]).await.unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let stored = store.get_generation_by_zone("NO1", start, start + Duration::days(1)).await.unwrap();
+        let keys: Vec<(u32, &str)> = stored
+            .iter()
+            .map(|p| (chrono::Timelike::hour(&p.timestamp), p.psr_type.as_str()))
+            .collect();
+        assert_eq!(keys, vec![(0, "B12"), (1, "B12"), (1, "B19")]);
+        assert_eq!(stored[1].quantity_mw, Decimal::from(1250));
+
+        assert_eq!(store.delete_old_generation(start + Duration::hours(1)).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_upsert_query_and_retention() {
+        let store = memory_store().await;
+        let value = |hour: u32, mw: i64| LoadValue {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap(),
+            bidding_zone: "NO1".to_string(),
+            load_mw: Decimal::from(mw),
+            resolution: "PT60M".to_string(),
+            fetched_at: Utc::now(),
+        };
+        store.upsert_load(&[value(1, 4200), value(0, 4100)]).await.unwrap();
+        store.upsert_load(&[value(1, 4250)]).await.unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let stored = store.get_load_by_zone("NO1", start, start + Duration::days(1)).await.unwrap();
+        let loads: Vec<Decimal> = stored.iter().map(|v| v.load_mw).collect();
+        assert_eq!(loads, vec![Decimal::from(4100), Decimal::from(4250)]);
+
+        assert_eq!(store.delete_old_load(start + Duration::hours(1)).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_flows_upsert_query_both_directions() {
+        let store = memory_store().await;
+        let flow = |hour: u32, from: &str, to: &str, kind: FlowKind, mw: i64| CrossBorderFlow {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap(),
+            from_zone: from.to_string(),
+            to_zone: to.to_string(),
+            kind,
+            quantity_mw: Decimal::from(mw),
+            resolution: "PT60M".to_string(),
+            fetched_at: Utc::now(),
+        };
+        store
+            .upsert_flows(&[
+                flow(0, "NO1", "NO2", FlowKind::Physical, 300),
+                flow(0, "NO2", "NO1", FlowKind::Physical, 0),
+                flow(0, "NO1", "NO2", FlowKind::Scheduled, 250),
+                flow(0, "NO2", "NO5", FlowKind::Physical, 100),
+            ])
+            .await
+            .unwrap();
+        store.upsert_flows(&[flow(0, "NO1", "NO2", FlowKind::Physical, 320)]).await.unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let stored = store
+            .get_flows_by_zone("NO1", FlowKind::Physical, start, start + Duration::days(1))
+            .await
+            .unwrap();
+        let keys: Vec<(&str, &str, Decimal)> = stored
+            .iter()
+            .map(|f| (f.from_zone.as_str(), f.to_zone.as_str(), f.quantity_mw))
+            .collect();
+        assert_eq!(keys, vec![("NO1", "NO2", Decimal::from(320)), ("NO2", "NO1", Decimal::ZERO)]);
+
+        assert_eq!(store.delete_old_flows(start + Duration::hours(1)).await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_name_translations_seeded_and_upserted() {
+        let store = memory_store().await;
+        let seeded = store.get_name_translations("de").await.unwrap();
+        assert!(seeded.iter().any(|t| t.kind == NameKind::Country && t.code == "NO" && t.name == "Norwegen"));
+
+        store
+            .upsert_name_translations(&[NameTranslation {
+                kind: NameKind::Zone,
+                code: "NO1".to_string(),
+                locale: "de".to_string(),
+                name: "Oslo".to_string(),
+            }])
+            .await
+            .unwrap();
+        let updated = store.get_name_translations("de").await.unwrap();
+        assert_eq!(updated.len(), seeded.len());
+        assert!(updated.iter().any(|t| t.kind == NameKind::Zone && t.code == "NO1" && t.name == "Oslo"));
+        assert!(store.get_name_translations("fr").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_alert_events_fire_once_and_go_with_their_rule() {
+        let store = memory_store().await;
+        let rule = store
+            .create_alert_rule(&NewAlertRule {
+                zone_code: "NO1".to_string(),
+                comparison: AlertComparison::Below,
+                threshold: Decimal::ZERO,
+                channel: AlertChannel::Mqtt,
+                target: "prices/no1".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(store.list_alert_rules(Some("NO1")).await.unwrap().len(), 1);
+        assert!(store.list_alert_rules(Some("SE3")).await.unwrap().is_empty());
+
+        let hour = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let event = AlertEvent::new(rule.id, hour, Decimal::from_str("-0.0021").unwrap());
+        assert_eq!(store.insert_alert_events(std::slice::from_ref(&event)).await.unwrap().len(), 1);
+        assert!(store.insert_alert_events(&[event]).await.unwrap().is_empty());
+
+        store.complete_alert_event(rule.id, hour, true, None).await.unwrap();
+        let history = store.get_alert_events(rule.id, 10).await.unwrap();
+        assert_eq!((history.len(), history[0].delivered), (1, Some(true)));
+        assert_eq!(history[0].price_kwh, Decimal::from_str("-0.0021").unwrap());
+
+        assert!(store.delete_alert_rule(rule.id).await.unwrap());
+        assert!(!store.delete_alert_rule(rule.id).await.unwrap());
+        assert!(store.get_alert_events(rule.id, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_round_trip_and_reject_duplicates() {
+        let store = memory_store().await;
+        let key = |name: &str, hash: &str, role| ApiKey {
+            name: name.to_string(),
+            key_sha256: hash.repeat(64),
+            role,
+            created_at: Utc.with_ymd_and_hms(2025, 5, 10, 0, 0, 0).unwrap(),
+        };
+        store.create_api_key(&key("ops", "a", ApiRole::Admin)).await.unwrap();
+        store.create_api_key(&key("dashboard", "b", ApiRole::Read)).await.unwrap();
+        assert!(store.create_api_key(&key("ops", "c", ApiRole::Read)).await.is_err());
+
+        let keys = store.list_api_keys().await.unwrap();
+        assert_eq!(keys, vec![key("dashboard", "b", ApiRole::Read), key("ops", "a", ApiRole::Admin)]);
+    }
+
+    #[tokio::test]
+    async fn test_insert_missing_zones_skips_existing() {
+        let store = memory_store().await;
+        let zones: Vec<_> = crate::bundled_zones()
+            .into_iter()
+            .filter(|z| z.country_code == "NO" || z.zone_code == "SE3")
+            .collect();
+
+        // NO1-NO5 are seeded by the migration
+        assert_eq!(store.insert_missing_zones(&zones).await.unwrap(), vec!["SE3"]);
+        assert!(store.insert_missing_zones(&zones).await.unwrap().is_empty());
+        assert_eq!(store.get_zone_by_code("SE3").await.unwrap().timezone, "Europe/Stockholm");
+
+        let lineage: Vec<_> = crate::bundled_zones()
+            .into_iter()
+            .filter(|z| z.zone_code == "DE-AT-LU" || z.zone_code == "DE-LU")
+            .collect();
+        store.insert_missing_zones(&lineage).await.unwrap();
+        let de_lu = store.get_zone_by_code("DE-LU").await.unwrap();
+        assert_eq!(de_lu.valid_from, NaiveDate::from_ymd_opt(2018, 10, 1));
+        assert_eq!(de_lu.predecessor_code.as_deref(), Some("DE-AT-LU"));
+        let de_at_lu = store.get_zone_by_code("DE-AT-LU").await.unwrap();
+        assert_eq!(de_at_lu.valid_to, de_lu.valid_from);
+        // Split zones are kept for their history but no longer listed
+        assert!(!de_at_lu.active);
+        assert!(!store.load_zones().await.unwrap().iter().any(|z| z.zone_code == "DE-AT-LU"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_logs_filter_and_page_newest_first() {
+        let store = memory_store().await;
+        let now = Utc::now();
+        let mut ids = Vec::new();
+        for (zone, status) in [("NO1", FetchStatus::Success), ("NO2", FetchStatus::Error), ("NO1", FetchStatus::Error), ("NO1", FetchStatus::Success)] {
+            let id = store.log_fetch_start(Some(zone.to_string()), now, now + Duration::days(1), None).await.unwrap();
+            store.log_fetch_complete(id, status, 0, None, None, 1).await.unwrap();
+            ids.push(id);
+        }
+
+        let no1 = FetchLogFilter {
+            zone_code: Some("NO1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(store.count_fetch_logs(&no1).await.unwrap(), 3);
+        let errors = FetchLogFilter {
+            status: Some(FetchStatus::Error),
+            ..Default::default()
+        };
+        assert_eq!(store.count_fetch_logs(&errors).await.unwrap(), 2);
+        let future = FetchLogFilter {
+            started_from: Some(now + Duration::hours(1)),
+            ..Default::default()
+        };
+        assert_eq!(store.count_fetch_logs(&future).await.unwrap(), 0);
+
+        let first = store.list_fetch_logs(&no1, FetchLogPage::Offset(0), 2).await.unwrap();
+        assert_eq!(first.iter().map(|log| log.id).collect::<Vec<_>>(), vec![ids[3], ids[2]]);
+        let last = &first[1];
+        let after = FetchLogPage::After {
+            started_at: last.fetch_started_at,
+            id: last.id,
+        };
+        let next = store.list_fetch_logs(&no1, after, 2).await.unwrap();
+        assert_eq!(next.iter().map(|log| log.id).collect::<Vec<_>>(), vec![ids[0]]);
+        let skipped = store.list_fetch_logs(&FetchLogFilter::default(), FetchLogPage::Offset(3), 10).await.unwrap();
+        assert_eq!(skipped.iter().map(|log| log.id).collect::<Vec<_>>(), vec![ids[0]]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_log_roundtrip() {
+        let store = memory_store().await;
+        let now = Utc::now();
+        let id = store
+            .log_fetch_start(Some("NO1".to_string()), now, now + Duration::days(1), Some("req-1".to_string()))
+            .await
+            .unwrap();
+        store
+            .log_fetch_complete(id, FetchStatus::NoData, 0, None, Some(200), 12)
+            .await
+            .unwrap();
+        store.log_fetch_endpoint(id, "https://backup.example/api").await.unwrap();
+
+        let logs = store.get_fetch_logs_by_zone("NO1", 10).await.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert!(matches!(logs[0].status, FetchStatus::NoData));
+        assert_eq!(logs[0].endpoint.as_deref(), Some("https://backup.example/api"));
+        assert_eq!(logs[0].correlation_id.as_deref(), Some("req-1"));
+        let by_request = FetchLogFilter {
+            correlation_id: Some("req-1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(store.count_fetch_logs(&by_request).await.unwrap(), 1);
+        assert_eq!(store.load_zones().await.unwrap().len(), 5);
+
+        assert_eq!(store.get_last_successful_fetch().await.unwrap(), None);
+        let id = store.log_fetch_start(None, now, now + Duration::days(1), None).await.unwrap();
+        store
+            .log_fetch_complete(id, FetchStatus::Success, 24, None, Some(200), 40)
+            .await
+            .unwrap();
+        let completed_at = store.get_recent_fetch_logs(1).await.unwrap()[0].fetch_completed_at;
+        assert!(completed_at.is_some());
+        assert_eq!(store.get_last_successful_fetch().await.unwrap(), completed_at);
+        let run_started_at = store.get_recent_fetch_logs(1).await.unwrap()[0].fetch_started_at;
+
+        let retry = store.log_fetch_start(Some("NO1".to_string()), now, now + Duration::days(1), None).await.unwrap();
+        store.log_fetch_start(Some("NO2".to_string()), now, now + Duration::days(1), None).await.unwrap();
+        let latest: Vec<(Option<String>, i64)> = store
+            .get_latest_fetch_logs()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|log| (log.bidding_zone, log.id))
+            .collect();
+        assert_eq!(latest, vec![(Some("NO1".to_string()), retry), (Some("NO2".to_string()), retry + 1)]);
+        assert_eq!(store.get_last_fetch_run().await.unwrap(), Some(run_started_at));
+    }
+}
@@ -0,0 +1,555 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use futures::stream::BoxStream;
+use rust_decimal::Decimal;
+
+use entsoe_core::models::{
+    AlertEvent, AlertRule, ApiKey, BackfillJob, BiddingZone, CrossBorderFlow, DeliveryDay, FetchLog, FetchRetry, FetchStatus, FlowKind, GenerationPoint,
+    IdempotencyRecord, LoadValue, Market, NameTranslation, NewAlertRule, NewBackfillJob, NewTask, Price, Task, TaskStatus, ZoneDefinition,
+};
+
+use super::error::StorageError;
+
+/// Months of price partitions kept ready ahead of the current one.
+pub const PRICE_PARTITION_MONTHS_AHEAD: u32 = 3;
+
+pub struct PoolStatus {
+    pub active_connections: u32,
+    pub idle_connections: u32,
+    pub max_connections: u32,
+}
+
+/// Range of stored prices for a single zone.
+#[derive(Debug, Clone)]
+pub struct PriceCoverage {
+    pub first_timestamp: DateTime<Utc>,
+    pub last_timestamp: DateTime<Utc>,
+    pub last_resolution: String,
+}
+
+impl PriceCoverage {
+    /// Exclusive end of the stored data: the last interval's start plus its
+    /// resolution (one hour if the resolution cannot be parsed).
+    pub fn end(&self) -> DateTime<Utc> {
+        let resolution = entsoe_core::entsoe::parse_resolution(&self.last_resolution).unwrap_or(Duration::hours(1));
+        self.last_timestamp + resolution
+    }
+}
+
+/// Price statistics and hourly coverage for one zone over a query range.
+#[derive(Debug, Clone, Default)]
+pub struct ZoneStats {
+    pub price_count: i64,
+    pub min_price: Option<Decimal>,
+    pub max_price: Option<Decimal>,
+    pub avg_price: Option<Decimal>,
+    pub first_timestamp: Option<DateTime<Utc>>,
+    pub last_timestamp: Option<DateTime<Utc>>,
+    /// Distinct UTC hours with at least one price.
+    pub covered_hours: i64,
+    /// Longest run of missing hours between two covered hours (edges excluded).
+    pub longest_interior_gap_hours: i64,
+    /// Currency the prices are quoted in; `None` without prices.
+    pub currency: Option<String>,
+}
+
+/// Row count and on-disk size of one table.
+#[derive(Debug, Clone)]
+pub struct TableStats {
+    pub table_name: String,
+    pub row_count: i64,
+    /// Heap size in bytes, if the backend reports sizes.
+    pub table_bytes: Option<i64>,
+    /// Combined size of the table's indexes in bytes, if the backend reports sizes.
+    pub index_bytes: Option<i64>,
+    /// Oldest row by the column retention prunes on, for time-keyed tables.
+    pub oldest_entry: Option<DateTime<Utc>>,
+}
+
+/// A zone's local delivery day with fewer hourly day-ahead prices than it
+/// has hours, as reported by [`PriceStore::find_gaps`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayGap {
+    pub date: NaiveDate,
+    pub zone_code: String,
+    /// Distinct hours with a price; sub-hourly prices cover an hour together.
+    pub hours: i64,
+    /// Hours in the day: 24, or 23 or 25 when clocks change.
+    pub expected_hours: i64,
+}
+
+impl DayGap {
+    pub fn missing_hours(&self) -> i64 {
+        self.expected_hours - self.hours
+    }
+}
+
+/// `date` in a zone's `timezone`, or the UTC day when the zone or its
+/// timezone is unknown, for [`PriceStore::find_gaps`].
+pub(crate) fn gap_check_day(date: NaiveDate, timezone: Option<&str>) -> DeliveryDay {
+    let timezone = timezone.and_then(|tz| tz.parse().ok()).unwrap_or(chrono_tz::UTC);
+    DeliveryDay::new(date, &timezone)
+}
+
+/// Which fetch logs to list or count; unset fields match every log.
+#[derive(Debug, Clone, Default)]
+pub struct FetchLogFilter {
+    pub zone_code: Option<String>,
+    pub status: Option<FetchStatus>,
+    /// Logs started at or after this.
+    pub started_from: Option<DateTime<Utc>>,
+    /// Logs started before this.
+    pub started_before: Option<DateTime<Utc>>,
+    /// Logs of fetches triggered by the API request with this correlation ID.
+    pub correlation_id: Option<String>,
+}
+
+/// Where a page of fetch logs, ordered newest first, begins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchLogPage {
+    /// Skip this many logs.
+    Offset(i64),
+    /// Logs after this one in the order: started earlier, or at the same
+    /// time with a lower id. Unlike an offset, unaffected by logs written
+    /// while paging.
+    After { started_at: DateTime<Utc>, id: i64 },
+}
+
+/// Storage backend for prices, generation, load, the zone registry and the
+/// fetch log.
+#[async_trait]
+pub trait PriceStore: Send + Sync {
+    async fn health_check(&self) -> Result<(), StorageError>;
+
+    fn pool_status(&self) -> PoolStatus;
+
+    /// Apply the backend's embedded schema migrations that are not yet
+    /// recorded as applied.
+    async fn migrate(&self) -> Result<(), StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Price Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    // Reads that do not take a `market` (latest prices, tomorrow checks and
+    // gap detection) only consider day-ahead prices.
+
+    async fn upsert_prices(&self, prices: &[Price]) -> Result<usize, StorageError>;
+
+    async fn get_prices_by_zone(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Price>, StorageError>;
+
+    /// [`get_prices_by_zone`](Self::get_prices_by_zone) as a stream of rows
+    /// in timestamp order, so exports spanning months are never held in
+    /// memory at once.
+    fn stream_prices_by_zone<'a>(
+        &'a self,
+        zone_code: &'a str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxStream<'a, Result<Price, StorageError>>;
+
+    async fn get_prices_by_country(
+        &self,
+        country_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<Price>>, StorageError>;
+
+    /// Each zone's newest day-ahead price, unless it is older than `since`.
+    async fn get_latest_prices(&self, since: Option<DateTime<Utc>>) -> Result<Vec<Price>, StorageError>;
+
+    /// Each zone's price for the interval containing `at`: the newest price
+    /// at or before `at` and less than an hour older.
+    async fn get_current_prices(&self, at: DateTime<Utc>) -> Result<Vec<Price>, StorageError>;
+
+    /// A zone's price for the interval containing `at`: the newest price at
+    /// or before `at`, if its resolution reaches past `at`.
+    async fn get_price_at(&self, zone_code: &str, market: Market, at: DateTime<Utc>) -> Result<Option<Price>, StorageError>;
+
+    /// Price statistics and hourly completeness, computed in a single query.
+    async fn get_zone_stats(
+        &self,
+        zone_code: &str,
+        market: Market,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<ZoneStats, StorageError>;
+
+    /// First and last stored price for a zone and market, or `None` if it has no data.
+    async fn get_price_coverage(&self, zone_code: &str, market: Market) -> Result<Option<PriceCoverage>, StorageError>;
+
+    async fn delete_old_prices(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError>;
+
+    /// Create the monthly price partitions from the UTC month of `from`
+    /// through `months_ahead` months later, on backends that partition
+    /// prices. Returns the names of the partitions created.
+    async fn ensure_price_partitions(&self, from: NaiveDate, months_ahead: u32) -> Result<Vec<String>, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Generation Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_generation(&self, points: &[GenerationPoint]) -> Result<usize, StorageError>;
+
+    /// Generation points for a zone ordered by timestamp, then production type.
+    async fn get_generation_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<GenerationPoint>, StorageError>;
+
+    async fn delete_old_generation(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Load Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_load(&self, values: &[LoadValue]) -> Result<usize, StorageError>;
+
+    async fn get_load_by_zone(
+        &self,
+        zone_code: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LoadValue>, StorageError>;
+
+    async fn delete_old_load(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Cross-Border Flow Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_flows(&self, flows: &[CrossBorderFlow]) -> Result<usize, StorageError>;
+
+    /// Flows of `kind` into or out of `zone_code`, ordered by timestamp.
+    async fn get_flows_by_zone(
+        &self,
+        zone_code: &str,
+        kind: FlowKind,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<CrossBorderFlow>, StorageError>;
+
+    async fn delete_old_flows(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Name Translation Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn upsert_name_translations(&self, translations: &[NameTranslation]) -> Result<usize, StorageError>;
+
+    /// Zone and country display names in `locale`.
+    async fn get_name_translations(&self, locale: &str) -> Result<Vec<NameTranslation>, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Alert Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn create_alert_rule(&self, rule: &NewAlertRule) -> Result<AlertRule, StorageError>;
+
+    /// Rules ordered by id, optionally only those for `zone_code`.
+    async fn list_alert_rules(&self, zone_code: Option<&str>) -> Result<Vec<AlertRule>, StorageError>;
+
+    async fn get_alert_rule(&self, id: i64) -> Result<AlertRule, StorageError>;
+
+    /// Delete a rule and its history. Returns false if it did not exist.
+    async fn delete_alert_rule(&self, id: i64) -> Result<bool, StorageError>;
+
+    /// Record fired alerts, skipping any rule and price hour that already
+    /// fired. Returns only the newly recorded events.
+    async fn insert_alert_events(&self, events: &[AlertEvent]) -> Result<Vec<AlertEvent>, StorageError>;
+
+    async fn complete_alert_event(
+        &self,
+        rule_id: i64,
+        price_timestamp: DateTime<Utc>,
+        delivered: bool,
+        error_message: Option<String>,
+    ) -> Result<(), StorageError>;
+
+    /// Most recently fired events for a rule, newest first.
+    async fn get_alert_events(&self, rule_id: i64, limit: i64) -> Result<Vec<AlertEvent>, StorageError>;
+
+    /// Events whose delivery failed for prices at or after `from`, by rule
+    /// and price timestamp.
+    async fn failed_alert_events(&self, from: DateTime<Utc>) -> Result<Vec<AlertEvent>, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // API Key Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Register a key by its SHA-256. Fails if the name or key is already taken.
+    async fn create_api_key(&self, key: &ApiKey) -> Result<(), StorageError>;
+
+    /// Every registered key, ordered by name.
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Job Lock Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Take the lock on `job_name` for `target_date` as `holder` until
+    /// `expires_at`, unless another holder's lock has not expired by `now`.
+    /// Returns whether the lock was taken.
+    async fn try_acquire_job_lock(
+        &self,
+        job_name: &str,
+        target_date: NaiveDate,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError>;
+
+    /// Extend `holder`'s lock to `expires_at`. Returns false when the lock
+    /// has since been released or taken over by another holder.
+    async fn renew_job_lock(
+        &self,
+        job_name: &str,
+        target_date: NaiveDate,
+        holder: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError>;
+
+    /// Release `holder`'s lock; a lock that has since been taken over is kept.
+    async fn release_job_lock(&self, job_name: &str, target_date: NaiveDate, holder: &str) -> Result<(), StorageError>;
+
+    /// Pause or resume the scheduler job `job_name` on every replica.
+    async fn set_job_paused(&self, job_name: &str, paused: bool, now: DateTime<Utc>) -> Result<(), StorageError>;
+
+    /// Names of the scheduler jobs currently paused.
+    async fn paused_jobs(&self) -> Result<Vec<String>, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Idempotency Key Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Claim `key` for a request with `fingerprint` at `now`, held until
+    /// `expires_at`, unless the key is already held or its stored response has
+    /// not expired by `now`. Returns whether the key was claimed.
+    async fn claim_idempotency_key(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, StorageError>;
+
+    async fn get_idempotency_key(&self, key: &str) -> Result<Option<IdempotencyRecord>, StorageError>;
+
+    /// Store the response to the request that claimed `key` at `claimed_at`,
+    /// kept until `expires_at`. Nothing is stored once the key was claimed
+    /// again by another request.
+    async fn complete_idempotency_key(
+        &self,
+        key: &str,
+        claimed_at: DateTime<Utc>,
+        status_code: i32,
+        response_body: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), StorageError>;
+
+    /// Release the claim taken at `claimed_at` so the request can be retried
+    /// with the same key.
+    async fn release_idempotency_key(&self, key: &str, claimed_at: DateTime<Utc>) -> Result<(), StorageError>;
+
+    /// Remove keys that expired by `now`, returning how many.
+    async fn delete_expired_idempotency_keys(&self, now: DateTime<Utc>) -> Result<u64, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Retry Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Queue zone/dates for retry. One that is already queued keeps its
+    /// attempts and schedule and only takes the newer error.
+    async fn enqueue_fetch_retries(&self, retries: &[FetchRetry]) -> Result<(), StorageError>;
+
+    /// Queued retries due by `now` that have not expired, earliest first.
+    async fn get_due_fetch_retries(&self, now: DateTime<Utc>) -> Result<Vec<FetchRetry>, StorageError>;
+
+    /// Count a failed retry and schedule the next one at `next_attempt_at`.
+    async fn reschedule_fetch_retry(
+        &self,
+        zone_code: &str,
+        target_date: NaiveDate,
+        next_attempt_at: DateTime<Utc>,
+        error: Option<&str>,
+    ) -> Result<(), StorageError>;
+
+    /// Remove a zone/date from the retry queue.
+    async fn delete_fetch_retry(&self, zone_code: &str, target_date: NaiveDate) -> Result<(), StorageError>;
+
+    /// Remove retries that expired by `now`, returning how many.
+    async fn delete_expired_fetch_retries(&self, now: DateTime<Utc>) -> Result<u64, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Backfill Job Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Record a job started at `now`, running with no progress yet.
+    async fn create_backfill_job(&self, job: &NewBackfillJob, now: DateTime<Utc>) -> Result<BackfillJob, StorageError>;
+
+    async fn get_backfill_job(&self, id: i64) -> Result<BackfillJob, StorageError>;
+
+    /// Save a job's progress and status. Nothing is saved once the job is no
+    /// longer running, e.g. after it was cancelled; returns whether it was.
+    async fn update_backfill_job(&self, job: &BackfillJob) -> Result<bool, StorageError>;
+
+    /// Mark a running job cancelled at `now`. Returns false if it was not
+    /// running.
+    async fn cancel_backfill_job(&self, id: i64, now: DateTime<Utc>) -> Result<bool, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Task Queue Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Queue a task at `now`. Returns `None`, queuing nothing, while an
+    /// unfinished task holds the same dedupe key.
+    async fn enqueue_task(&self, task: &NewTask, now: DateTime<Utc>) -> Result<Option<Task>, StorageError>;
+
+    /// Claim the oldest task due by `now`, queued or running on a lapsed
+    /// lease, counting an attempt and leasing it until `lease_until`.
+    async fn claim_task(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>) -> Result<Option<Task>, StorageError>;
+
+    /// Extend the lease of a task still running its `attempt`th attempt.
+    /// Returns false once it finished or was claimed again.
+    async fn renew_task_lease(&self, id: i64, attempt: i32, lease_until: DateTime<Utc>) -> Result<bool, StorageError>;
+
+    /// End a task running its `attempt`th attempt as `Succeeded` or `Failed`.
+    /// Returns false once it was claimed again.
+    async fn finish_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        status: TaskStatus,
+        error: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError>;
+
+    /// Queue a task whose `attempt`th attempt failed to run again from
+    /// `run_after`. Returns false once it was claimed again.
+    async fn retry_task(
+        &self,
+        id: i64,
+        attempt: i32,
+        error: &str,
+        run_after: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError>;
+
+    async fn get_task(&self, id: i64) -> Result<Task, StorageError>;
+
+    /// Most recently created tasks first, optionally only those in `status`.
+    async fn list_tasks(&self, status: Option<TaskStatus>, limit: i64) -> Result<Vec<Task>, StorageError>;
+
+    /// Number of tasks in each status; statuses without any are left out.
+    async fn count_tasks_by_status(&self) -> Result<HashMap<TaskStatus, i64>, StorageError>;
+
+    /// Delete tasks that finished before `before`, returning how many.
+    async fn delete_finished_tasks(&self, before: DateTime<Utc>) -> Result<u64, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Zone Registry Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    async fn load_zones(&self) -> Result<Vec<BiddingZone>, StorageError>;
+
+    async fn get_zone_by_code(&self, zone_code: &str) -> Result<BiddingZone, StorageError>;
+
+    async fn get_zone_by_eic(&self, eic_code: &str) -> Result<BiddingZone, StorageError>;
+
+    async fn get_zones_by_country(&self, country_code: &str) -> Result<Vec<BiddingZone>, StorageError>;
+
+    async fn get_countries(&self) -> Result<Vec<(String, String)>, StorageError>;
+
+    /// Insert zones whose code and EIC code are both unknown, as active zones.
+    /// Existing zones, including deactivated ones, are left untouched.
+    /// Returns the codes of the inserted zones.
+    async fn insert_missing_zones(&self, zones: &[ZoneDefinition]) -> Result<Vec<String>, StorageError>;
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Fetch Log Operations
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Open a pending fetch log entry. `correlation_id` is that of the API
+    /// request the fetch runs for, if any.
+    async fn log_fetch_start(
+        &self,
+        zone_code: Option<String>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        correlation_id: Option<String>,
+    ) -> Result<i64, StorageError>;
+
+    async fn log_fetch_complete(
+        &self,
+        fetch_id: i64,
+        status: FetchStatus,
+        records_inserted: i32,
+        error_message: Option<String>,
+        http_status: Option<i32>,
+        duration_ms: i32,
+    ) -> Result<(), StorageError>;
+
+    /// Record the ENTSOE base URL that served a fetch.
+    async fn log_fetch_endpoint(&self, fetch_id: i64, endpoint: &str) -> Result<(), StorageError>;
+
+    async fn get_recent_fetch_logs(&self, limit: i64) -> Result<Vec<FetchLog>, StorageError>;
+
+    /// Up to `limit` logs matching `filter` from `page` on, newest first
+    /// (ties broken by id).
+    async fn list_fetch_logs(&self, filter: &FetchLogFilter, page: FetchLogPage, limit: i64) -> Result<Vec<FetchLog>, StorageError>;
+
+    /// How many logs match `filter`.
+    async fn count_fetch_logs(&self, filter: &FetchLogFilter) -> Result<i64, StorageError>;
+
+    async fn get_fetch_logs_by_zone(&self, zone_code: &str, limit: i64) -> Result<Vec<FetchLog>, StorageError>;
+
+    /// The most recently started fetch of each zone.
+    async fn get_latest_fetch_logs(&self) -> Result<Vec<FetchLog>, StorageError>;
+
+    /// When the most recent successful fetch completed, if any has.
+    async fn get_last_successful_fetch(&self) -> Result<Option<DateTime<Utc>>, StorageError>;
+
+    /// When the most recent fetch logged without a zone, i.e. a full run,
+    /// started, if any has.
+    async fn get_last_fetch_run(&self) -> Result<Option<DateTime<Utc>>, StorageError>;
+
+    async fn delete_old_fetch_logs(&self, older_than: DateTime<Utc>) -> Result<u64, StorageError>;
+
+    /// Whether any day-ahead price exists for `zone_code` on the UTC `date`.
+    async fn has_day_ahead_data(&self, zone_code: &str, date: NaiveDate) -> Result<bool, StorageError>;
+
+    /// Delivery days in the inclusive range, in each zone's local time, on
+    /// which the zone has fewer hourly day-ahead prices than the day has
+    /// hours. Ordered by date, then zone. A zone missing from the registry is
+    /// checked by UTC days.
+    async fn find_gaps(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<DayGap>, StorageError>;
+
+    /// Dates with gap-filled day-ahead prices for given zones in date range,
+    /// as (date, zone_code, estimated_hours) for days with any.
+    async fn find_estimated_hours(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        zone_codes: &[String],
+    ) -> Result<Vec<(NaiveDate, String, i64)>, StorageError>;
+
+    /// Row counts and sizes for electricity_prices, fetch_log and bidding_zones.
+    async fn get_table_stats(&self) -> Result<Vec<TableStats>, StorageError>;
+}
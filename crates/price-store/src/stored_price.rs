@@ -0,0 +1,150 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+use entsoe_core::models::{GapFill, Market, Price};
+
+use super::error::StorageError;
+
+/// A row of `electricity_prices`, as the repositories read and write it.
+/// Converted to and from [`Price`] at the storage boundary so the table and
+/// the domain model can change independently.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct StoredPrice {
+    pub timestamp: DateTime<Utc>,
+    pub bidding_zone: String,
+    pub price_kwh: Decimal,
+    pub currency: String,
+    pub resolution: String,
+    pub fetched_at: DateTime<Utc>,
+    /// `electricity_prices.market` value, see [`Market::as_str`].
+    pub market: String,
+    /// `electricity_prices.gap_fill` value, see [`GapFill::as_str`].
+    pub gap_fill: Option<String>,
+}
+
+impl StoredPrice {
+    /// Read `price_kwh` from its text form, as SQLite stores it.
+    pub fn parse_price_kwh(value: &str) -> Result<Decimal, StorageError> {
+        Decimal::from_str(value).map_err(|e| StorageError::QueryError(format!("Invalid stored price {}: {}", value, e)))
+    }
+
+    /// Append the row as a line of `COPY ... (FORMAT csv)` input, in
+    /// [`COPY_COLUMNS`] order. An absent `gap_fill` is written unquoted
+    /// empty, which COPY reads as NULL.
+    pub fn write_copy_csv(&self, out: &mut String) {
+        let quoted = |value: &str| format!("\"{}\"", value.replace('"', "\"\""));
+        let fields = [
+            self.timestamp.to_rfc3339(),
+            quoted(&self.bidding_zone),
+            self.price_kwh.to_string(),
+            quoted(&self.currency),
+            quoted(&self.resolution),
+            self.fetched_at.to_rfc3339(),
+            quoted(&self.market),
+            self.gap_fill.as_deref().map(quoted).unwrap_or_default(),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+}
+
+/// Columns written by [`StoredPrice::write_copy_csv`].
+pub const COPY_COLUMNS: &str = "timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill";
+
+impl From<&Price> for StoredPrice {
+    fn from(price: &Price) -> Self {
+        Self {
+            timestamp: price.timestamp,
+            bidding_zone: price.bidding_zone.clone(),
+            price_kwh: price.price_kwh,
+            currency: price.currency.clone(),
+            resolution: price.resolution.clone(),
+            fetched_at: price.fetched_at,
+            market: price.market.as_str().to_string(),
+            gap_fill: price.gap_fill.map(|method| method.as_str().to_string()),
+        }
+    }
+}
+
+impl TryFrom<StoredPrice> for Price {
+    type Error = StorageError;
+
+    fn try_from(row: StoredPrice) -> Result<Self, Self::Error> {
+        let market = Market::parse(&row.market)
+            .ok_or_else(|| StorageError::QueryError(format!("Invalid stored market: {}", row.market)))?;
+        let gap_fill = match row.gap_fill.as_deref() {
+            Some(value) => Some(
+                GapFill::parse(value)
+                    .ok_or_else(|| StorageError::QueryError(format!("Invalid stored gap fill: {}", value)))?,
+            ),
+            None => None,
+        };
+        Ok(Price {
+            timestamp: row.timestamp,
+            bidding_zone: row.bidding_zone,
+            price_kwh: row.price_kwh,
+            currency: row.currency,
+            resolution: row.resolution,
+            fetched_at: row.fetched_at,
+            market,
+            gap_fill,
+        })
+    }
+}
+
+/// Convert rows read from `electricity_prices`, failing on the first invalid one.
+pub(super) fn prices_from_rows(rows: Vec<StoredPrice>) -> Result<Vec<Price>, StorageError> {
+    rows.into_iter().map(Price::try_from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trips_price_and_rejects_unknown_market() {
+        let at = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let price = Price::from_mwh(at, "NO1".to_string(), 42.5, "PT15M".to_string()).with_market(Market::Intraday);
+
+        let row = StoredPrice::from(&price);
+        assert_eq!(row.market, "intraday");
+        let restored = Price::try_from(row.clone()).unwrap();
+        assert_eq!((restored.price_kwh, restored.market), (price.price_kwh, Market::Intraday));
+
+        let unknown = StoredPrice {
+            market: "balancing".to_string(),
+            ..row.clone()
+        };
+        assert!(matches!(Price::try_from(unknown), Err(StorageError::QueryError(_))));
+
+        let interpolated = StoredPrice {
+            gap_fill: Some("linear_interpolation".to_string()),
+            ..row
+        };
+        assert_eq!(Price::try_from(interpolated).unwrap().gap_fill, Some(GapFill::LinearInterpolation));
+    }
+
+    #[test]
+    fn test_copy_csv_quotes_text_and_leaves_null_unquoted() {
+        let at = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let mut price = Price::from_mwh(at, "NO1".to_string(), 42.5, "PT60M".to_string());
+        price.fetched_at = at;
+        let mut out = String::new();
+        StoredPrice::from(&price).write_copy_csv(&mut out);
+        let filled = Price {
+            gap_fill: Some(GapFill::ForwardFill),
+            ..price.clone()
+        };
+        StoredPrice::from(&filled).write_copy_csv(&mut out);
+        assert_eq!(
+            out,
+            "2025-01-15T12:00:00+00:00,\"NO1\",0.0425,\"EUR\",\"PT60M\",2025-01-15T12:00:00+00:00,\"day_ahead\",\n\
+             2025-01-15T12:00:00+00:00,\"NO1\",0.0425,\"EUR\",\"PT60M\",2025-01-15T12:00:00+00:00,\"day_ahead\",\"forward_fill\"\n"
+        );
+    }
+}
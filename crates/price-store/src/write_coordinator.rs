@@ -0,0 +1,209 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use tokio::sync::Notify;
+
+use crate::metrics;
+
+/// Who a price write is for. Waiting scheduled writes are admitted before
+/// waiting backfill writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WritePriority {
+    Scheduled,
+    Backfill,
+}
+
+impl WritePriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WritePriority::Scheduled => "scheduled",
+            WritePriority::Backfill => "backfill",
+        }
+    }
+}
+
+#[derive(Default)]
+struct WriteQueue {
+    running: usize,
+    waiting_scheduled: usize,
+    waiting_backfill: usize,
+}
+
+impl WriteQueue {
+    fn waiting(&mut self, priority: WritePriority) -> &mut usize {
+        match priority {
+            WritePriority::Scheduled => &mut self.waiting_scheduled,
+            WritePriority::Backfill => &mut self.waiting_backfill,
+        }
+    }
+}
+
+/// Limits how many large price upserts run at once, so an API-triggered
+/// backfill and the daily fetch do not contend on the prices unique index.
+/// Backfill writes yield to any waiting scheduled write.
+pub struct WriteCoordinator {
+    max_concurrent: usize,
+    queue: Mutex<WriteQueue>,
+    released: Notify,
+}
+
+impl WriteCoordinator {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+            queue: Mutex::new(WriteQueue::default()),
+            released: Notify::new(),
+        }
+    }
+
+    /// Run `write` once admitted at `priority`.
+    pub async fn run<F, Fut, T>(&self, priority: WritePriority, write: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let _permit = self.acquire(priority).await;
+        write().await
+    }
+
+    async fn acquire(&self, priority: WritePriority) -> WritePermit<'_> {
+        let start = Instant::now();
+        let mut waiter = Waiter {
+            coordinator: self,
+            priority,
+            admitted: false,
+        };
+        self.update_waiting(priority, |waiting| *waiting += 1);
+
+        loop {
+            // Register before checking so a release in between is not missed
+            let released = self.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            if self.try_admit(priority) {
+                waiter.admitted = true;
+                metrics::record_write_wait(priority.as_str(), start.elapsed());
+                return WritePermit { coordinator: self };
+            }
+            released.await;
+        }
+    }
+
+    fn try_admit(&self, priority: WritePriority) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+        let yields = priority == WritePriority::Backfill && queue.waiting_scheduled > 0;
+        if queue.running >= self.max_concurrent || yields {
+            return false;
+        }
+        queue.running += 1;
+        let waiting = queue.waiting(priority);
+        *waiting -= 1;
+        metrics::update_write_queue_depth(priority.as_str(), *waiting);
+        true
+    }
+
+    fn update_waiting(&self, priority: WritePriority, update: impl FnOnce(&mut usize)) {
+        let mut queue = self.queue.lock().unwrap();
+        let waiting = queue.waiting(priority);
+        update(waiting);
+        metrics::update_write_queue_depth(priority.as_str(), *waiting);
+    }
+}
+
+/// Removes a cancelled waiter from the queue; a backfill may have been
+/// yielding to it.
+struct Waiter<'a> {
+    coordinator: &'a WriteCoordinator,
+    priority: WritePriority,
+    admitted: bool,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        if !self.admitted {
+            self.coordinator.update_waiting(self.priority, |waiting| *waiting -= 1);
+            self.coordinator.released.notify_waiters();
+        }
+    }
+}
+
+struct WritePermit<'a> {
+    coordinator: &'a WriteCoordinator,
+}
+
+impl Drop for WritePermit<'_> {
+    fn drop(&mut self) {
+        self.coordinator.queue.lock().unwrap().running -= 1;
+        self.coordinator.released.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_waiting_scheduled_write_goes_before_backfill() {
+        let coordinator = Arc::new(WriteCoordinator::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let (hold_tx, hold_rx) = tokio::sync::oneshot::channel::<()>();
+        let first = tokio::spawn({
+            let coordinator = Arc::clone(&coordinator);
+            async move { coordinator.run(WritePriority::Backfill, || async { hold_rx.await.ok() }).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let spawn_write = |priority: WritePriority, name: &'static str| {
+            let coordinator = Arc::clone(&coordinator);
+            let order = Arc::clone(&order);
+            tokio::spawn(async move {
+                coordinator.run(priority, || async { order.lock().unwrap().push(name) }).await;
+            })
+        };
+        let backfill = spawn_write(WritePriority::Backfill, "backfill");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let scheduled = spawn_write(WritePriority::Scheduled, "scheduled");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        {
+            let queue = coordinator.queue.lock().unwrap();
+            assert_eq!((queue.running, queue.waiting_scheduled, queue.waiting_backfill), (1, 1, 1));
+        }
+
+        hold_tx.send(()).unwrap();
+        for handle in [backfill, scheduled] {
+            handle.await.unwrap();
+        }
+        first.await.unwrap();
+
+        assert_eq!(*order.lock().unwrap(), vec!["scheduled", "backfill"]);
+        assert_eq!(coordinator.queue.lock().unwrap().running, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_scheduled_waiter_unblocks_backfill() {
+        let coordinator = Arc::new(WriteCoordinator::new(1));
+        let permit = coordinator.acquire(WritePriority::Backfill).await;
+
+        let scheduled = tokio::spawn({
+            let coordinator = Arc::clone(&coordinator);
+            async move { coordinator.run(WritePriority::Scheduled, || async {}).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        scheduled.abort();
+        let _ = scheduled.await;
+        drop(permit);
+
+        let written = tokio::time::timeout(
+            Duration::from_secs(1),
+            coordinator.run(WritePriority::Backfill, || async { true }),
+        )
+        .await;
+        assert_eq!(written, Ok(true));
+    }
+}
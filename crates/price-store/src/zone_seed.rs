@@ -0,0 +1,73 @@
+//! Canonical list of European bidding zones shipped with the crate, used to
+//! seed the zone registry of fresh deployments.
+
+use entsoe_core::models::ZoneDefinition;
+
+use super::error::StorageError;
+use super::store::PriceStore;
+
+const BUNDLED_ZONES: &str = include_str!("bidding_zones.json");
+
+/// The bundled bidding zones (zone code, EIC code, timezone).
+pub fn bundled_zones() -> Vec<ZoneDefinition> {
+    serde_json::from_str(BUNDLED_ZONES).expect("bundled bidding_zones.json is valid")
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ZoneSyncReport {
+    /// Bundled zones considered after the country filter.
+    pub candidates: usize,
+    /// Codes of the zones that were missing and have been inserted.
+    pub inserted: Vec<String>,
+}
+
+/// Insert bundled zones missing from the registry. An empty `countries`
+/// list syncs every bundled zone; otherwise only zones of those countries.
+pub async fn sync_zones(store: &dyn PriceStore, countries: &[String]) -> Result<ZoneSyncReport, StorageError> {
+    let zones: Vec<ZoneDefinition> = bundled_zones()
+        .into_iter()
+        .filter(|zone| countries.is_empty() || countries.iter().any(|c| c.eq_ignore_ascii_case(&zone.country_code)))
+        .collect();
+
+    let inserted = store.insert_missing_zones(&zones).await?;
+    Ok(ZoneSyncReport {
+        candidates: zones.len(),
+        inserted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::InMemoryPriceStore;
+
+    #[test]
+    fn test_bundled_zones_are_consistent() {
+        let zones = bundled_zones();
+        let codes: HashSet<_> = zones.iter().map(|z| z.zone_code.as_str()).collect();
+        let eics: HashSet<_> = zones.iter().map(|z| z.eic_code.as_str()).collect();
+        assert_eq!(codes.len(), zones.len());
+        assert_eq!(eics.len(), zones.len());
+
+        for zone in &zones {
+            assert_eq!(zone.eic_code.len(), 16, "{}", zone.zone_code);
+            assert!(zone.zone_code.len() <= 20, "{}", zone.zone_code);
+            assert!(zone.timezone.parse::<chrono_tz::Tz>().is_ok(), "{}", zone.timezone);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_inserts_only_missing_zones() {
+        let store = InMemoryPriceStore::with_zones(vec![InMemoryPriceStore::zone("NO1", "NO", "Europe/Oslo")]);
+
+        let report = sync_zones(&store, &["no".to_string()]).await.unwrap();
+        assert_eq!(report.candidates, 5);
+        assert_eq!(report.inserted, vec!["NO2", "NO3", "NO4", "NO5"]);
+
+        let again = sync_zones(&store, &["NO".to_string()]).await.unwrap();
+        assert!(again.inserted.is_empty());
+        assert_eq!(store.load_zones().await.unwrap().len(), 5);
+    }
+}
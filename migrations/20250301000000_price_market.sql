@@ -0,0 +1,12 @@
+-- Distinguish day-ahead prices from intraday auction results. Existing rows
+-- are day-ahead prices.
+ALTER TABLE electricity_prices
+    ADD COLUMN market VARCHAR(20) NOT NULL DEFAULT 'day_ahead'
+    CHECK (market IN ('day_ahead', 'intraday'));
+
+ALTER TABLE electricity_prices DROP CONSTRAINT electricity_prices_pkey;
+ALTER TABLE electricity_prices ADD PRIMARY KEY (timestamp, bidding_zone, market);
+
+DROP INDEX idx_electricity_prices_zone;
+CREATE INDEX idx_electricity_prices_zone
+    ON electricity_prices (bidding_zone, market, timestamp DESC);
@@ -0,0 +1,15 @@
+-- Actual generation per production type (ENTSOE A75), at the resolution
+-- published for each zone.
+CREATE TABLE generation_per_type (
+    timestamp       TIMESTAMPTZ NOT NULL,
+    bidding_zone    VARCHAR(20) NOT NULL REFERENCES bidding_zones(zone_code),
+    psr_type        VARCHAR(4) NOT NULL,
+    quantity_mw     NUMERIC(12,3) NOT NULL,
+    resolution      VARCHAR(10) NOT NULL DEFAULT 'PT60M',
+    fetched_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (timestamp, bidding_zone, psr_type)
+);
+
+CREATE INDEX idx_generation_per_type_zone
+    ON generation_per_type (bidding_zone, timestamp DESC);
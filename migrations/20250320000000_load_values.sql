@@ -0,0 +1,13 @@
+-- Actual total load (ENTSOE A65), at the resolution published for each zone.
+CREATE TABLE load_values (
+    timestamp       TIMESTAMPTZ NOT NULL,
+    bidding_zone    VARCHAR(20) NOT NULL REFERENCES bidding_zones(zone_code),
+    load_mw         NUMERIC(12,3) NOT NULL,
+    resolution      VARCHAR(10) NOT NULL DEFAULT 'PT60M',
+    fetched_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (timestamp, bidding_zone)
+);
+
+CREATE INDEX idx_load_values_zone
+    ON load_values (bidding_zone, timestamp DESC);
@@ -0,0 +1,18 @@
+-- Cross-border flows between bidding zones: measured physical flows (ENTSOE
+-- A11) and scheduled commercial exchanges (A09). Rows are directional.
+CREATE TABLE cross_border_flows (
+    timestamp       TIMESTAMPTZ NOT NULL,
+    from_zone       VARCHAR(20) NOT NULL REFERENCES bidding_zones(zone_code),
+    to_zone         VARCHAR(20) NOT NULL REFERENCES bidding_zones(zone_code),
+    kind            VARCHAR(20) NOT NULL CHECK (kind IN ('physical', 'scheduled')),
+    quantity_mw     NUMERIC(12,3) NOT NULL,
+    resolution      VARCHAR(10) NOT NULL DEFAULT 'PT60M',
+    fetched_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (timestamp, from_zone, to_zone, kind)
+);
+
+CREATE INDEX idx_cross_border_flows_from
+    ON cross_border_flows (from_zone, kind, timestamp DESC);
+CREATE INDEX idx_cross_border_flows_to
+    ON cross_border_flows (to_zone, kind, timestamp DESC);
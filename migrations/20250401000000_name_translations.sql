@@ -0,0 +1,43 @@
+-- Display names of zones and countries per locale, served with ?lang=.
+-- Codes are not foreign keys so translations can be loaded before a zone is
+-- registered.
+CREATE TABLE name_translations (
+    kind            VARCHAR(10) NOT NULL CHECK (kind IN ('zone', 'country')),
+    code            VARCHAR(20) NOT NULL,
+    locale          VARCHAR(10) NOT NULL,
+    name            VARCHAR(100) NOT NULL,
+
+    PRIMARY KEY (kind, code, locale)
+);
+
+INSERT INTO name_translations (kind, code, locale, name) VALUES
+    ('zone', 'NO1', 'nb', 'Østlandet'),
+    ('zone', 'NO1', 'sv', 'Östra Norge'),
+    ('zone', 'NO1', 'de', 'Ostnorwegen'),
+    ('zone', 'NO2', 'nb', 'Sørlandet'),
+    ('zone', 'NO2', 'sv', 'Södra Norge'),
+    ('zone', 'NO2', 'de', 'Südnorwegen'),
+    ('zone', 'NO3', 'nb', 'Midt-Norge'),
+    ('zone', 'NO3', 'sv', 'Mellersta Norge'),
+    ('zone', 'NO3', 'de', 'Mittelnorwegen'),
+    ('zone', 'NO4', 'nb', 'Nord-Norge'),
+    ('zone', 'NO4', 'sv', 'Norra Norge'),
+    ('zone', 'NO4', 'de', 'Nordnorwegen'),
+    ('zone', 'NO5', 'nb', 'Vestlandet'),
+    ('zone', 'NO5', 'sv', 'Västra Norge'),
+    ('zone', 'NO5', 'de', 'Westnorwegen'),
+    ('country', 'NO', 'nb', 'Norge'),
+    ('country', 'NO', 'sv', 'Norge'),
+    ('country', 'NO', 'de', 'Norwegen'),
+    ('country', 'SE', 'nb', 'Sverige'),
+    ('country', 'SE', 'sv', 'Sverige'),
+    ('country', 'SE', 'de', 'Schweden'),
+    ('country', 'DK', 'nb', 'Danmark'),
+    ('country', 'DK', 'sv', 'Danmark'),
+    ('country', 'DK', 'de', 'Dänemark'),
+    ('country', 'FI', 'nb', 'Finland'),
+    ('country', 'FI', 'sv', 'Finland'),
+    ('country', 'FI', 'de', 'Finnland'),
+    ('country', 'DE', 'nb', 'Tyskland'),
+    ('country', 'DE', 'sv', 'Tyskland'),
+    ('country', 'DE', 'de', 'Deutschland');
@@ -0,0 +1,26 @@
+-- User-registered price alert rules and the hours each one has fired for.
+CREATE TABLE alert_rules (
+    id              BIGSERIAL PRIMARY KEY,
+    zone_code       VARCHAR(20) NOT NULL REFERENCES bidding_zones(zone_code),
+    comparison      VARCHAR(10) NOT NULL CHECK (comparison IN ('above', 'below')),
+    threshold       NUMERIC(12, 6) NOT NULL,
+    channel         VARCHAR(10) NOT NULL CHECK (channel IN ('webhook', 'mqtt', 'email')),
+    -- Webhook URL, MQTT topic or email address
+    target          TEXT NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_alert_rules_zone ON alert_rules (zone_code);
+
+-- One row per rule and matching price hour, so refetching a day does not
+-- fire the same alert twice.
+CREATE TABLE alert_events (
+    rule_id         BIGINT NOT NULL REFERENCES alert_rules(id) ON DELETE CASCADE,
+    price_timestamp TIMESTAMPTZ NOT NULL,
+    price_kwh       NUMERIC(12, 6) NOT NULL,
+    fired_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered       BOOLEAN,
+    error_message   TEXT,
+
+    PRIMARY KEY (rule_id, price_timestamp)
+);
@@ -0,0 +1,2 @@
+-- ENTSOE base URL that served a fetch, when fallback endpoints are configured.
+ALTER TABLE fetch_log ADD COLUMN endpoint TEXT;
@@ -0,0 +1,3 @@
+-- Method that filled in a price for a position missing from the ENTSOE
+-- document (forward_fill, linear_interpolation); NULL for published prices.
+ALTER TABLE electricity_prices ADD COLUMN gap_fill VARCHAR(32);
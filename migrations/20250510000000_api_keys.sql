@@ -0,0 +1,8 @@
+-- API keys managed outside the config file. Only the SHA-256 of each key is
+-- stored; `read` keys may call /api/v1, `admin` keys also /api/v1/admin.
+CREATE TABLE api_keys (
+    key_sha256      CHAR(64) PRIMARY KEY,
+    name            VARCHAR(100) NOT NULL UNIQUE,
+    role            VARCHAR(10) NOT NULL CHECK (role IN ('read', 'admin')),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Per-job locks so a manual trigger and a scheduled run, on this replica or
+-- another, never execute the same fetch for the same date concurrently.
+-- A lock whose holder died is taken over once it expires.
+CREATE TABLE job_locks (
+    job_name        VARCHAR(64) NOT NULL,
+    target_date     DATE NOT NULL,
+    holder          VARCHAR(128) NOT NULL,
+    acquired_at     TIMESTAMPTZ NOT NULL,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (job_name, target_date)
+);
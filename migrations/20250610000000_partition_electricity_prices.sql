@@ -0,0 +1,114 @@
+-- Range-partition electricity_prices by UTC month so range queries scan only
+-- the months they cover and retention drops whole months instead of deleting
+-- row by row. The service creates upcoming months' partitions with
+-- create_electricity_prices_partition(); a row outside every monthly
+-- partition lands in electricity_prices_default until its month is created.
+
+-- Create the partition for the UTC month containing `month_start`, moving any
+-- of its rows out of the default partition. Returns false if it exists.
+CREATE FUNCTION create_electricity_prices_partition(month_start DATE)
+RETURNS BOOLEAN
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    first_day DATE := date_trunc('month', month_start)::DATE;
+    lower_bound TIMESTAMPTZ := first_day::TIMESTAMP AT TIME ZONE 'UTC';
+    upper_bound TIMESTAMPTZ := (first_day + INTERVAL '1 month') AT TIME ZONE 'UTC';
+    partition_name TEXT := 'electricity_prices_' || to_char(first_day, 'YYYY_MM');
+BEGIN
+    -- Replicas creating the same month at once take turns
+    PERFORM pg_advisory_xact_lock(hashtext('electricity_prices_partitions'));
+    IF to_regclass(partition_name) IS NOT NULL THEN
+        RETURN FALSE;
+    END IF;
+
+    EXECUTE format(
+        'CREATE TABLE %I (LIKE electricity_prices INCLUDING DEFAULTS INCLUDING CONSTRAINTS)',
+        partition_name
+    );
+    EXECUTE format(
+        'WITH moved AS (
+            DELETE FROM electricity_prices_default WHERE timestamp >= %L AND timestamp < %L RETURNING *
+        )
+        INSERT INTO %I SELECT * FROM moved',
+        lower_bound, upper_bound, partition_name
+    );
+    EXECUTE format(
+        'ALTER TABLE electricity_prices ATTACH PARTITION %I FOR VALUES FROM (%L) TO (%L)',
+        partition_name, lower_bound, upper_bound
+    );
+    RETURN TRUE;
+END;
+$$;
+
+-- Drop the monthly partitions that end at or before `cutoff`. Returns the
+-- number of rows they held.
+CREATE FUNCTION drop_electricity_prices_partitions(cutoff TIMESTAMPTZ)
+RETURNS BIGINT
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    partition_name TEXT;
+    partition_rows BIGINT;
+    dropped BIGINT := 0;
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('electricity_prices_partitions'));
+    FOR partition_name IN
+        SELECT c.relname
+        FROM pg_inherits i
+        JOIN pg_class c ON c.oid = i.inhrelid
+        WHERE i.inhparent = 'electricity_prices'::regclass
+          AND c.relname ~ '^electricity_prices_[0-9]{4}_[0-9]{2}$'
+    LOOP
+        IF (to_date(right(partition_name, 7), 'YYYY_MM') + INTERVAL '1 month') AT TIME ZONE 'UTC' <= cutoff THEN
+            EXECUTE format('SELECT COUNT(*) FROM %I', partition_name) INTO partition_rows;
+            EXECUTE format('DROP TABLE %I', partition_name);
+            dropped := dropped + partition_rows;
+        END IF;
+    END LOOP;
+    RETURN dropped;
+END;
+$$;
+
+ALTER TABLE electricity_prices RENAME TO electricity_prices_unpartitioned;
+ALTER TABLE electricity_prices_unpartitioned RENAME CONSTRAINT electricity_prices_pkey TO electricity_prices_unpartitioned_pkey;
+DROP INDEX idx_electricity_prices_timestamp;
+DROP INDEX idx_electricity_prices_zone;
+
+CREATE TABLE electricity_prices (
+    timestamp       TIMESTAMPTZ NOT NULL,
+    bidding_zone    VARCHAR(20) NOT NULL REFERENCES bidding_zones(zone_code),
+    price_kwh       NUMERIC(12,6) NOT NULL,  -- EUR per kWh (converted from MWh)
+    currency        VARCHAR(3) NOT NULL DEFAULT 'EUR',
+    resolution      VARCHAR(10) NOT NULL DEFAULT 'PT60M',
+    fetched_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    market          VARCHAR(20) NOT NULL DEFAULT 'day_ahead'
+                    CHECK (market IN ('day_ahead', 'intraday')),
+    gap_fill        VARCHAR(32),
+
+    PRIMARY KEY (timestamp, bidding_zone, market)
+) PARTITION BY RANGE (timestamp);
+
+CREATE INDEX idx_electricity_prices_timestamp
+    ON electricity_prices USING BRIN (timestamp)
+    WITH (pages_per_range = 128);
+
+CREATE INDEX idx_electricity_prices_zone
+    ON electricity_prices (bidding_zone, market, timestamp DESC);
+
+CREATE TABLE electricity_prices_default PARTITION OF electricity_prices DEFAULT;
+
+-- One partition per month of existing data, through the current month
+SELECT create_electricity_prices_partition(month::DATE)
+FROM generate_series(
+    date_trunc('month', COALESCE((SELECT MIN(timestamp) FROM electricity_prices_unpartitioned), NOW()) AT TIME ZONE 'UTC'),
+    date_trunc('month', GREATEST((SELECT MAX(timestamp) FROM electricity_prices_unpartitioned), NOW()) AT TIME ZONE 'UTC'),
+    INTERVAL '1 month'
+) AS month;
+
+INSERT INTO electricity_prices
+    (timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill)
+SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at, market, gap_fill
+FROM electricity_prices_unpartitioned;
+
+DROP TABLE electricity_prices_unpartitioned;
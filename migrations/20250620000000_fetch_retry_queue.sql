@@ -0,0 +1,14 @@
+-- Zones whose day-ahead fetch failed, retried by the fetch_retry_queue job
+-- with backoff until they succeed or expire.
+CREATE TABLE fetch_retry_queue (
+    zone_code       VARCHAR(20) NOT NULL,
+    target_date     DATE NOT NULL,
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    enqueued_at     TIMESTAMPTZ NOT NULL,
+    next_attempt_at TIMESTAMPTZ NOT NULL,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (zone_code, target_date)
+);
+
+CREATE INDEX idx_fetch_retry_queue_next_attempt ON fetch_retry_queue (next_attempt_at);
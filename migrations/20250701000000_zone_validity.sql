@@ -0,0 +1,17 @@
+-- Validity periods of bidding zones that were split or merged. valid_from is
+-- the first and valid_to the first no longer covered delivery date; prices
+-- before valid_from are stored under predecessor_code. No foreign key, so a
+-- zone can be registered before its predecessor.
+ALTER TABLE bidding_zones ADD COLUMN valid_from DATE;
+ALTER TABLE bidding_zones ADD COLUMN valid_to DATE;
+ALTER TABLE bidding_zones ADD COLUMN predecessor_code VARCHAR(20);
+
+-- DE-AT-LU split into DE-LU and AT on 2018-10-01
+INSERT INTO bidding_zones (zone_code, zone_name, country_code, country_name, eic_code, timezone, valid_to)
+SELECT 'DE-AT-LU', 'Germany-Austria-Luxembourg', 'DE', 'Germany', '10Y1001A1001A63L', 'Europe/Berlin', DATE '2018-10-01'
+WHERE EXISTS (SELECT 1 FROM bidding_zones WHERE zone_code IN ('DE-LU', 'AT'))
+ON CONFLICT DO NOTHING;
+
+UPDATE bidding_zones
+SET valid_from = DATE '2018-10-01', predecessor_code = 'DE-AT-LU'
+WHERE zone_code IN ('DE-LU', 'AT') AND valid_from IS NULL;
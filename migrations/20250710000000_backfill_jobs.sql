@@ -0,0 +1,19 @@
+-- Backfills started through the admin API, run in the background and
+-- polled for progress. Cancelling sets the status, which the running job
+-- checks between chunks of days.
+CREATE TABLE backfill_jobs (
+    id              BIGSERIAL PRIMARY KEY,
+    start_date      DATE NOT NULL,
+    end_date        DATE NOT NULL,
+    -- JSON array of zone codes; NULL for every active zone
+    zones           TEXT,
+    status          VARCHAR(10) NOT NULL CHECK (status IN ('running', 'completed', 'cancelled', 'failed')),
+    dates_total     INTEGER NOT NULL,
+    dates_done      INTEGER NOT NULL DEFAULT 0,
+    prices_stored   BIGINT NOT NULL DEFAULT 0,
+    -- JSON array of error messages
+    errors          TEXT NOT NULL DEFAULT '[]',
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at     TIMESTAMPTZ
+);
@@ -0,0 +1,24 @@
+-- Background work (backfill jobs, gap repairs, retention runs) queued for
+-- whichever replica's worker claims it first. A running task holds a lease
+-- that its worker renews; one whose lease lapsed is claimed again.
+CREATE TABLE tasks (
+    id               BIGSERIAL PRIMARY KEY,
+    kind             VARCHAR(50) NOT NULL,
+    -- JSON arguments for the task's handler
+    payload          TEXT NOT NULL DEFAULT '{}',
+    dedupe_key       VARCHAR(200),
+    status           VARCHAR(10) NOT NULL CHECK (status IN ('queued', 'running', 'succeeded', 'failed')),
+    attempts         INTEGER NOT NULL DEFAULT 0,
+    max_attempts     INTEGER NOT NULL,
+    last_error       TEXT,
+    run_after        TIMESTAMPTZ NOT NULL,
+    lease_expires_at TIMESTAMPTZ,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at      TIMESTAMPTZ
+);
+
+CREATE INDEX idx_tasks_due ON tasks (status, run_after);
+
+-- At most one unfinished task per dedupe key
+CREATE UNIQUE INDEX idx_tasks_dedupe_key ON tasks (dedupe_key) WHERE status IN ('queued', 'running');
@@ -0,0 +1,14 @@
+-- Responses to admin POSTs sent with an Idempotency-Key header, replayed when
+-- the same request is retried. A key whose first request never completed
+-- (its replica died) can be claimed again once expires_at has passed.
+CREATE TABLE idempotency_keys (
+    key              VARCHAR(255) PRIMARY KEY,
+    -- SHA-256 of the method, path and body the key was first used with
+    fingerprint      VARCHAR(64) NOT NULL,
+    status_code      INTEGER,
+    response_body    TEXT,
+    created_at       TIMESTAMPTZ NOT NULL,
+    expires_at       TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys (expires_at);
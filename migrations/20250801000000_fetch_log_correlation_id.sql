@@ -0,0 +1,7 @@
+-- Correlation ID of the API request that triggered a fetch; NULL for
+-- scheduled runs.
+ALTER TABLE fetch_log ADD COLUMN correlation_id TEXT;
+
+CREATE INDEX idx_fetch_log_correlation_id
+    ON fetch_log (correlation_id)
+    WHERE correlation_id IS NOT NULL;
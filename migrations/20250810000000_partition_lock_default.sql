@@ -0,0 +1,43 @@
+-- create_electricity_prices_partition moved a month's rows out of the default
+-- partition and then attached the new partition; a row for that month written
+-- in between landed in the default partition again and failed the ATTACH. Lock
+-- the default partition before moving rows, so writers wait until the month's
+-- partition is attached. The service now also creates a month's partition
+-- before writing prices into it, so the move is the exception.
+CREATE OR REPLACE FUNCTION create_electricity_prices_partition(month_start DATE)
+RETURNS BOOLEAN
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    first_day DATE := date_trunc('month', month_start)::DATE;
+    lower_bound TIMESTAMPTZ := first_day::TIMESTAMP AT TIME ZONE 'UTC';
+    upper_bound TIMESTAMPTZ := (first_day + INTERVAL '1 month') AT TIME ZONE 'UTC';
+    partition_name TEXT := 'electricity_prices_' || to_char(first_day, 'YYYY_MM');
+BEGIN
+    -- Replicas creating the same month at once take turns
+    PERFORM pg_advisory_xact_lock(hashtext('electricity_prices_partitions'));
+    IF to_regclass(partition_name) IS NOT NULL THEN
+        RETURN FALSE;
+    END IF;
+
+    EXECUTE format(
+        'CREATE TABLE %I (LIKE electricity_prices INCLUDING DEFAULTS INCLUDING CONSTRAINTS)',
+        partition_name
+    );
+    -- ATTACH takes this lock anyway; taking it first keeps writes out of
+    -- the default partition until the month has its own
+    LOCK TABLE electricity_prices_default IN ACCESS EXCLUSIVE MODE;
+    EXECUTE format(
+        'WITH moved AS (
+            DELETE FROM electricity_prices_default WHERE timestamp >= %L AND timestamp < %L RETURNING *
+        )
+        INSERT INTO %I SELECT * FROM moved',
+        lower_bound, upper_bound, partition_name
+    );
+    EXECUTE format(
+        'ALTER TABLE electricity_prices ATTACH PARTITION %I FOR VALUES FROM (%L) TO (%L)',
+        partition_name, lower_bound, upper_bound
+    );
+    RETURN TRUE;
+END;
+$$;
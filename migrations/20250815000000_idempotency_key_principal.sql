@@ -0,0 +1,4 @@
+-- Keys are now stored prefixed with the name of the caller that sent them, so
+-- two API keys using the same Idempotency-Key never share a response. The
+-- prefix can push a 255-character key past the old column width.
+ALTER TABLE idempotency_keys ALTER COLUMN key TYPE TEXT;
@@ -0,0 +1,4 @@
+-- 20250701000000_zone_validity inserted DE-AT-LU as an active zone, so it was
+-- listed, counted by readiness and fetched alongside its successors. Zones
+-- that no longer exist stay in the registry for lookups by code, inactive.
+UPDATE bidding_zones SET active = FALSE WHERE valid_to <= CURRENT_DATE;
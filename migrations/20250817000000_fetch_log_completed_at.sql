@@ -0,0 +1,5 @@
+-- /ready reads the newest successful fetch on every call; without an index
+-- that is a scan of the whole fetch log.
+CREATE INDEX idx_fetch_log_success_completed_at
+    ON fetch_log (fetch_completed_at)
+    WHERE status = 'success';
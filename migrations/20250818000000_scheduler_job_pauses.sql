@@ -0,0 +1,6 @@
+-- Scheduler jobs paused through the admin API. Kept in the database so a
+-- pause holds on every replica and across restarts until the job is resumed.
+CREATE TABLE scheduler_job_pauses (
+    job_name TEXT PRIMARY KEY,
+    paused_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,72 @@
+-- SQLite schema for lightweight deployments. Mirrors the Postgres schema in
+-- ../20241231000000_initial_schema.sql; timestamps are stored as RFC3339 text
+-- and prices as decimal text to preserve precision.
+
+-- Bidding zones registry table
+CREATE TABLE bidding_zones (
+    zone_code       TEXT PRIMARY KEY,
+    zone_name       TEXT NOT NULL,
+    country_code    TEXT NOT NULL,
+    country_name    TEXT NOT NULL,
+    eic_code        TEXT NOT NULL UNIQUE,
+    timezone        TEXT NOT NULL DEFAULT 'Europe/Oslo',
+    active          BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at      TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now')),
+    updated_at      TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+);
+
+CREATE INDEX idx_bidding_zones_country ON bidding_zones(country_code);
+
+-- Electricity prices table
+CREATE TABLE electricity_prices (
+    timestamp       TEXT NOT NULL,
+    bidding_zone    TEXT NOT NULL REFERENCES bidding_zones(zone_code),
+    price_kwh       TEXT NOT NULL,
+    currency        TEXT NOT NULL DEFAULT 'EUR',
+    resolution      TEXT NOT NULL DEFAULT 'PT60M',
+    fetched_at      TEXT NOT NULL,
+
+    PRIMARY KEY (timestamp, bidding_zone)
+);
+
+CREATE INDEX idx_electricity_prices_zone
+    ON electricity_prices (bidding_zone, timestamp DESC);
+
+-- Fetch log table for tracking API calls
+CREATE TABLE fetch_log (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    fetch_started_at TEXT NOT NULL,
+    fetch_completed_at TEXT,
+    bidding_zone    TEXT REFERENCES bidding_zones(zone_code),
+    period_start    TEXT NOT NULL,
+    period_end      TEXT NOT NULL,
+    status          TEXT NOT NULL CHECK (status IN ('pending', 'success', 'nodata', 'error', 'ratelimited')),
+    records_inserted INTEGER DEFAULT 0,
+    error_message   TEXT,
+    http_status     INTEGER,
+    duration_ms     INTEGER
+);
+
+CREATE INDEX idx_fetch_log_recent
+    ON fetch_log (fetch_started_at DESC, status);
+
+CREATE INDEX idx_fetch_log_zone
+    ON fetch_log (bidding_zone, fetch_started_at DESC);
+
+-- Seed Norwegian bidding zones
+INSERT INTO bidding_zones (zone_code, zone_name, country_code, country_name, eic_code, timezone) VALUES
+    ('NO1', 'Oslo', 'NO', 'Norway', '10YNO-1--------2', 'Europe/Oslo'),
+    ('NO2', 'Kristiansand', 'NO', 'Norway', '10YNO-2--------T', 'Europe/Oslo'),
+    ('NO3', 'Trondheim', 'NO', 'Norway', '10YNO-3--------J', 'Europe/Oslo'),
+    ('NO4', 'Tromsø', 'NO', 'Norway', '10YNO-4--------9', 'Europe/Oslo'),
+    ('NO5', 'Bergen', 'NO', 'Norway', '10Y1001A1001A48H', 'Europe/Oslo');
+
+CREATE TRIGGER update_bidding_zones_updated_at
+    AFTER UPDATE ON bidding_zones
+    FOR EACH ROW
+    WHEN NEW.updated_at = OLD.updated_at
+BEGIN
+    UPDATE bidding_zones
+    SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now')
+    WHERE zone_code = NEW.zone_code;
+END;
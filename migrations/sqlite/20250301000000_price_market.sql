@@ -0,0 +1,23 @@
+-- Mirrors ../20250301000000_price_market.sql. SQLite cannot alter a primary
+-- key, so the table is rebuilt with the market column.
+CREATE TABLE electricity_prices_new (
+    timestamp       TEXT NOT NULL,
+    bidding_zone    TEXT NOT NULL REFERENCES bidding_zones(zone_code),
+    price_kwh       TEXT NOT NULL,
+    currency        TEXT NOT NULL DEFAULT 'EUR',
+    resolution      TEXT NOT NULL DEFAULT 'PT60M',
+    fetched_at      TEXT NOT NULL,
+    market          TEXT NOT NULL DEFAULT 'day_ahead' CHECK (market IN ('day_ahead', 'intraday')),
+
+    PRIMARY KEY (timestamp, bidding_zone, market)
+);
+
+INSERT INTO electricity_prices_new (timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at)
+SELECT timestamp, bidding_zone, price_kwh, currency, resolution, fetched_at
+FROM electricity_prices;
+
+DROP TABLE electricity_prices;
+ALTER TABLE electricity_prices_new RENAME TO electricity_prices;
+
+CREATE INDEX idx_electricity_prices_zone
+    ON electricity_prices (bidding_zone, market, timestamp DESC);
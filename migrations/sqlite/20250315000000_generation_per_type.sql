@@ -0,0 +1,14 @@
+-- Mirrors ../20250315000000_generation_per_type.sql.
+CREATE TABLE generation_per_type (
+    timestamp       TEXT NOT NULL,
+    bidding_zone    TEXT NOT NULL REFERENCES bidding_zones(zone_code),
+    psr_type        TEXT NOT NULL,
+    quantity_mw     TEXT NOT NULL,
+    resolution      TEXT NOT NULL DEFAULT 'PT60M',
+    fetched_at      TEXT NOT NULL,
+
+    PRIMARY KEY (timestamp, bidding_zone, psr_type)
+);
+
+CREATE INDEX idx_generation_per_type_zone
+    ON generation_per_type (bidding_zone, timestamp DESC);
@@ -0,0 +1,13 @@
+-- Mirrors ../20250320000000_load_values.sql.
+CREATE TABLE load_values (
+    timestamp       TEXT NOT NULL,
+    bidding_zone    TEXT NOT NULL REFERENCES bidding_zones(zone_code),
+    load_mw         TEXT NOT NULL,
+    resolution      TEXT NOT NULL DEFAULT 'PT60M',
+    fetched_at      TEXT NOT NULL,
+
+    PRIMARY KEY (timestamp, bidding_zone)
+);
+
+CREATE INDEX idx_load_values_zone
+    ON load_values (bidding_zone, timestamp DESC);
@@ -0,0 +1,17 @@
+-- Mirrors ../20250325000000_cross_border_flows.sql.
+CREATE TABLE cross_border_flows (
+    timestamp       TEXT NOT NULL,
+    from_zone       TEXT NOT NULL REFERENCES bidding_zones(zone_code),
+    to_zone         TEXT NOT NULL REFERENCES bidding_zones(zone_code),
+    kind            TEXT NOT NULL CHECK (kind IN ('physical', 'scheduled')),
+    quantity_mw     TEXT NOT NULL,
+    resolution      TEXT NOT NULL DEFAULT 'PT60M',
+    fetched_at      TEXT NOT NULL,
+
+    PRIMARY KEY (timestamp, from_zone, to_zone, kind)
+);
+
+CREATE INDEX idx_cross_border_flows_from
+    ON cross_border_flows (from_zone, kind, timestamp DESC);
+CREATE INDEX idx_cross_border_flows_to
+    ON cross_border_flows (to_zone, kind, timestamp DESC);
@@ -0,0 +1,41 @@
+-- Mirrors ../20250401000000_name_translations.sql.
+CREATE TABLE name_translations (
+    kind            TEXT NOT NULL CHECK (kind IN ('zone', 'country')),
+    code            TEXT NOT NULL,
+    locale          TEXT NOT NULL,
+    name            TEXT NOT NULL,
+
+    PRIMARY KEY (kind, code, locale)
+);
+
+INSERT INTO name_translations (kind, code, locale, name) VALUES
+    ('zone', 'NO1', 'nb', 'Østlandet'),
+    ('zone', 'NO1', 'sv', 'Östra Norge'),
+    ('zone', 'NO1', 'de', 'Ostnorwegen'),
+    ('zone', 'NO2', 'nb', 'Sørlandet'),
+    ('zone', 'NO2', 'sv', 'Södra Norge'),
+    ('zone', 'NO2', 'de', 'Südnorwegen'),
+    ('zone', 'NO3', 'nb', 'Midt-Norge'),
+    ('zone', 'NO3', 'sv', 'Mellersta Norge'),
+    ('zone', 'NO3', 'de', 'Mittelnorwegen'),
+    ('zone', 'NO4', 'nb', 'Nord-Norge'),
+    ('zone', 'NO4', 'sv', 'Norra Norge'),
+    ('zone', 'NO4', 'de', 'Nordnorwegen'),
+    ('zone', 'NO5', 'nb', 'Vestlandet'),
+    ('zone', 'NO5', 'sv', 'Västra Norge'),
+    ('zone', 'NO5', 'de', 'Westnorwegen'),
+    ('country', 'NO', 'nb', 'Norge'),
+    ('country', 'NO', 'sv', 'Norge'),
+    ('country', 'NO', 'de', 'Norwegen'),
+    ('country', 'SE', 'nb', 'Sverige'),
+    ('country', 'SE', 'sv', 'Sverige'),
+    ('country', 'SE', 'de', 'Schweden'),
+    ('country', 'DK', 'nb', 'Danmark'),
+    ('country', 'DK', 'sv', 'Danmark'),
+    ('country', 'DK', 'de', 'Dänemark'),
+    ('country', 'FI', 'nb', 'Finland'),
+    ('country', 'FI', 'sv', 'Finland'),
+    ('country', 'FI', 'de', 'Finnland'),
+    ('country', 'DE', 'nb', 'Tyskland'),
+    ('country', 'DE', 'sv', 'Tyskland'),
+    ('country', 'DE', 'de', 'Deutschland');
@@ -0,0 +1,23 @@
+-- Mirrors ../20250410000000_price_alerts.sql.
+CREATE TABLE alert_rules (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    zone_code       TEXT NOT NULL REFERENCES bidding_zones(zone_code),
+    comparison      TEXT NOT NULL CHECK (comparison IN ('above', 'below')),
+    threshold       TEXT NOT NULL,
+    channel         TEXT NOT NULL CHECK (channel IN ('webhook', 'mqtt', 'email')),
+    target          TEXT NOT NULL,
+    created_at      TEXT NOT NULL
+);
+
+CREATE INDEX idx_alert_rules_zone ON alert_rules (zone_code);
+
+CREATE TABLE alert_events (
+    rule_id         INTEGER NOT NULL REFERENCES alert_rules(id) ON DELETE CASCADE,
+    price_timestamp TEXT NOT NULL,
+    price_kwh       TEXT NOT NULL,
+    fired_at        TEXT NOT NULL,
+    delivered       INTEGER,
+    error_message   TEXT,
+
+    PRIMARY KEY (rule_id, price_timestamp)
+);
@@ -0,0 +1,2 @@
+-- Mirrors ../20250420000000_fetch_log_endpoint.sql.
+ALTER TABLE fetch_log ADD COLUMN endpoint TEXT;
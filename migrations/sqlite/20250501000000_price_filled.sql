@@ -0,0 +1,2 @@
+-- Mirrors ../20250501000000_price_filled.sql.
+ALTER TABLE electricity_prices ADD COLUMN gap_fill TEXT;
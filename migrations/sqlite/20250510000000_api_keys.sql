@@ -0,0 +1,7 @@
+-- Mirrors ../20250510000000_api_keys.sql.
+CREATE TABLE api_keys (
+    key_sha256      TEXT PRIMARY KEY,
+    name            TEXT NOT NULL UNIQUE,
+    role            TEXT NOT NULL CHECK (role IN ('read', 'admin')),
+    created_at      TEXT NOT NULL
+);
@@ -0,0 +1,9 @@
+-- Mirrors ../20250601000000_job_locks.sql.
+CREATE TABLE job_locks (
+    job_name        TEXT NOT NULL,
+    target_date     TEXT NOT NULL,
+    holder          TEXT NOT NULL,
+    acquired_at     TEXT NOT NULL,
+    expires_at      TEXT NOT NULL,
+    PRIMARY KEY (job_name, target_date)
+);
@@ -0,0 +1,13 @@
+-- Mirrors ../20250620000000_fetch_retry_queue.sql.
+CREATE TABLE fetch_retry_queue (
+    zone_code       TEXT NOT NULL,
+    target_date     TEXT NOT NULL,
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    enqueued_at     TEXT NOT NULL,
+    next_attempt_at TEXT NOT NULL,
+    expires_at      TEXT NOT NULL,
+    PRIMARY KEY (zone_code, target_date)
+);
+
+CREATE INDEX idx_fetch_retry_queue_next_attempt ON fetch_retry_queue (next_attempt_at);
@@ -0,0 +1,13 @@
+-- Mirrors ../20250701000000_zone_validity.sql.
+ALTER TABLE bidding_zones ADD COLUMN valid_from TEXT;
+ALTER TABLE bidding_zones ADD COLUMN valid_to TEXT;
+ALTER TABLE bidding_zones ADD COLUMN predecessor_code TEXT;
+
+INSERT INTO bidding_zones (zone_code, zone_name, country_code, country_name, eic_code, timezone, valid_to)
+SELECT 'DE-AT-LU', 'Germany-Austria-Luxembourg', 'DE', 'Germany', '10Y1001A1001A63L', 'Europe/Berlin', '2018-10-01'
+WHERE EXISTS (SELECT 1 FROM bidding_zones WHERE zone_code IN ('DE-LU', 'AT'))
+ON CONFLICT DO NOTHING;
+
+UPDATE bidding_zones
+SET valid_from = '2018-10-01', predecessor_code = 'DE-AT-LU'
+WHERE zone_code IN ('DE-LU', 'AT') AND valid_from IS NULL;
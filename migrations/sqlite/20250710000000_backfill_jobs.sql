@@ -0,0 +1,15 @@
+-- Mirrors ../20250710000000_backfill_jobs.sql.
+CREATE TABLE backfill_jobs (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    start_date      TEXT NOT NULL,
+    end_date        TEXT NOT NULL,
+    zones           TEXT,
+    status          TEXT NOT NULL CHECK (status IN ('running', 'completed', 'cancelled', 'failed')),
+    dates_total     INTEGER NOT NULL,
+    dates_done      INTEGER NOT NULL DEFAULT 0,
+    prices_stored   INTEGER NOT NULL DEFAULT 0,
+    errors          TEXT NOT NULL DEFAULT '[]',
+    created_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL,
+    finished_at     TEXT
+);
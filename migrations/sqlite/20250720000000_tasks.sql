@@ -0,0 +1,20 @@
+-- Mirrors ../20250720000000_tasks.sql.
+CREATE TABLE tasks (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind             TEXT NOT NULL,
+    payload          TEXT NOT NULL DEFAULT '{}',
+    dedupe_key       TEXT,
+    status           TEXT NOT NULL CHECK (status IN ('queued', 'running', 'succeeded', 'failed')),
+    attempts         INTEGER NOT NULL DEFAULT 0,
+    max_attempts     INTEGER NOT NULL,
+    last_error       TEXT,
+    run_after        TEXT NOT NULL,
+    lease_expires_at TEXT,
+    created_at       TEXT NOT NULL,
+    updated_at       TEXT NOT NULL,
+    finished_at      TEXT
+);
+
+CREATE INDEX idx_tasks_due ON tasks (status, run_after);
+
+CREATE UNIQUE INDEX idx_tasks_dedupe_key ON tasks (dedupe_key) WHERE status IN ('queued', 'running');
@@ -0,0 +1,11 @@
+-- Mirrors ../20250730000000_idempotency_keys.sql.
+CREATE TABLE idempotency_keys (
+    key              TEXT PRIMARY KEY,
+    fingerprint      TEXT NOT NULL,
+    status_code      INTEGER,
+    response_body    TEXT,
+    created_at       TEXT NOT NULL,
+    expires_at       TEXT NOT NULL
+);
+
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys (expires_at);
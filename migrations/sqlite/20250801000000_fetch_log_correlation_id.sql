@@ -0,0 +1,6 @@
+-- Mirrors ../20250801000000_fetch_log_correlation_id.sql.
+ALTER TABLE fetch_log ADD COLUMN correlation_id TEXT;
+
+CREATE INDEX idx_fetch_log_correlation_id
+    ON fetch_log (correlation_id)
+    WHERE correlation_id IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Mirrors ../20250816000000_retire_split_zones.sql.
+UPDATE bidding_zones SET active = FALSE WHERE valid_to <= date('now');
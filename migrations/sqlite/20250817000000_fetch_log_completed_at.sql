@@ -0,0 +1,4 @@
+-- Mirrors ../20250817000000_fetch_log_completed_at.sql.
+CREATE INDEX idx_fetch_log_success_completed_at
+    ON fetch_log (fetch_completed_at)
+    WHERE status = 'success';
@@ -0,0 +1,5 @@
+-- Mirrors ../20250818000000_scheduler_job_pauses.sql.
+CREATE TABLE scheduler_job_pauses (
+    job_name TEXT PRIMARY KEY,
+    paused_at TEXT NOT NULL
+);
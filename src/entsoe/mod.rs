@@ -1,8 +0,0 @@
-mod client;
-mod error;
-mod validation;
-mod xml;
-
-pub use client::EntsoeClient;
-pub use error::EntsoeError;
-pub use validation::validate_and_fill_period;
@@ -1,3 +0,0 @@
-mod service;
-
-pub use service::{BackfillSummary, FetchSummary, FetcherService};
@@ -1,16 +0,0 @@
-pub mod api;
-pub mod config;
-pub mod entsoe;
-pub mod fetcher;
-pub mod metrics;
-pub mod models;
-pub mod scheduler;
-pub mod storage;
-
-pub use api::{create_router, AppError, AppState, CorrelationId};
-pub use config::AppConfig;
-pub use entsoe::{EntsoeClient, EntsoeError};
-pub use fetcher::{FetchSummary, FetcherService};
-pub use metrics::init_metrics;
-pub use scheduler::PriceFetchScheduler;
-pub use storage::{PoolStatus, PriceRepository, StorageError};
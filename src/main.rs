@@ -1,79 +1,538 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 
-use anyhow::Result;
-use tokio::net::TcpListener;
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
 use tokio::signal;
-use tracing::{error, info};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
 
-use entsoe_price_fetcher::{
-    create_router, init_metrics, AppConfig, EntsoeClient, FetcherService, PriceFetchScheduler,
-    PriceRepository,
+use price_api::config::{MisfirePolicy, ZoneRegistryConfig};
+use price_api::fetcher::{BackfillJobTask, GapRepairTask};
+use price_api::scheduler::RetentionTask;
+use price_api::tasks::TaskWorker;
+use price_api::{
+    bind_listener, create_scoped_router, generate_api_key, hash_api_key, init_metrics, init_tracing, models, serve_grpc, storage, AlertService, ApiKeyAuth, AppConfig,
+    AppState, EntsoeClient, FetcherService, FxRateService, InfluxExporter, JwtVerifier, PriceCache, PriceFetchScheduler, RateLimiter, ReadinessGate, ResponseSigner,
+    WebhookNotifier,
 };
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let metrics_handle = init_metrics();
+    let config = AppConfig::load()?;
+    let _log_guard = init_tracing(&config.deployment, &config.log_file)?;
+    let metrics_handle = init_metrics(&config.deployment);
+    info!("Configuration loaded successfully");
 
-    let log_format = std::env::var("LOG_FORMAT").unwrap_or_else(|_| "json".to_string());
-    let default_filter = std::env::var("RUST_LOG").unwrap_or_else(|_| "entsoe_price_fetcher=info,tower_http=info".to_string());
-    let env_filter = tracing_subscriber::EnvFilter::new(default_filter);
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    if log_format == "json" {
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(tracing_subscriber::fmt::layer().json())
-            .init();
-    } else {
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(tracing_subscriber::fmt::layer())
-            .init();
+    // `migrate` applies the embedded schema migrations and exits, even when
+    // `database.run_migrations` is off, including those that rewrite stored
+    // prices and are never applied at startup
+    if args.first().map(String::as_str) == Some("migrate") {
+        storage::migrate_offline(&config.database).await?;
+        info!(backend = ?config.database.backend, "Database migrations applied");
+        return Ok(());
     }
 
-    let config = AppConfig::load()?;
-    info!("Configuration loaded successfully");
+    let (repository, read_repository) = storage::connect_with_reader(&config.database).await?;
+    info!(
+        backend = ?config.database.backend,
+        read_replica = config.database.read_url.is_some(),
+        "Database connection pool initialized"
+    );
 
-    let repository = Arc::new(PriceRepository::from_config(&config.database).await?);
-    info!("Database connection pool initialized");
+    // `sync-zones [COUNTRY...]` seeds the zone registry and exits
+    if args.first().map(String::as_str) == Some("sync-zones") {
+        let countries = if args.len() > 1 { args[1..].to_vec() } else { config.zones.sync_countries.clone() };
+        let report = storage::sync_zones(repository.as_ref(), &countries).await?;
+        info!(candidates = report.candidates, inserted = ?report.inserted, "Zone registry synced");
+        return Ok(());
+    }
 
+    // `api-key add NAME ROLE` registers a new `read` or `admin` key in the
+    // api_keys table and prints it; only its hash is stored
+    if args.first().map(String::as_str) == Some("api-key") && args.get(1).map(String::as_str) == Some("add") {
+        let usage = "Usage: api-key add NAME read|admin";
+        let name = args.get(2).context(usage)?;
+        let role = args.get(3).and_then(|role| models::ApiRole::parse(role)).context(usage)?;
+        let key = generate_api_key();
+        repository
+            .create_api_key(&models::ApiKey {
+                name: name.clone(),
+                key_sha256: hash_api_key(&key),
+                role,
+                created_at: Utc::now(),
+            })
+            .await?;
+        info!(name = %name, role = role.as_str(), "API key added");
+        println!("{}", key);
+        return Ok(());
+    }
+
+    // With lazy_connect this waits for the database in the background instead
+    if !config.database.lazy_connect {
+        prepare_database(repository.as_ref(), &config.zones).await?;
+    }
+
+    config.entsoe.price_bounds.validate().map_err(anyhow::Error::msg)?;
+    config.country_average.validate().map_err(anyhow::Error::msg)?;
+    config.readiness.validate().map_err(anyhow::Error::msg)?;
     let client = Arc::new(EntsoeClient::new(&config.entsoe)?);
     info!("ENTSOE client initialized");
 
-    let fetcher = Arc::new(FetcherService::new(Arc::clone(&client), Arc::clone(&repository)));
-    
-    let scheduler = if config.scheduler.enabled {
-        let scheduler = PriceFetchScheduler::new(Arc::clone(&fetcher)).await?;
-        scheduler.start().await?;
-        info!("Scheduler started with fetch times at 13:00, 14:00, 15:00, 16:00 CET");
+    // `fetch-eic EIC [DATE] [TIMEZONE]` prints day-ahead prices for an area
+    // that need not be in the zone registry, without storing them, and exits
+    if args.first().map(String::as_str) == Some("fetch-eic") {
+        let eic_code = args.get(1).context("Usage: fetch-eic EIC [YYYY-MM-DD] [TIMEZONE]")?;
+        anyhow::ensure!(models::is_valid_eic(eic_code), "Invalid EIC code: {}", eic_code);
+        let date = match args.get(2) {
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d").context("Invalid date, use YYYY-MM-DD")?,
+            None => Utc::now().date_naive(),
+        };
+        let timezone = args.get(3).map(String::as_str).unwrap_or("Europe/Brussels");
+        timezone
+            .parse::<chrono_tz::Tz>()
+            .map_err(|_| anyhow::anyhow!("Invalid timezone: {}", timezone))?;
+
+        let fetcher = FetcherService::new(Arc::clone(&client), Arc::clone(&repository));
+        let prices = fetcher.preview_eic(eic_code, date, models::Market::DayAhead, timezone).await?;
+        println!("{}", serde_json::to_string_pretty(&prices)?);
+        return Ok(());
+    }
+
+    // `zone add [--code CODE] [--eic EIC] [--timezone TZ] [--name NAME]
+    // [--country CC] [--country-name NAME] [--backfill-days N]` validates a
+    // new zone against ENTSOE, registers it and optionally backfills it.
+    // Values not given as flags are prompted for
+    if args.first().map(String::as_str) == Some("zone") && args.get(1).map(String::as_str) == Some("add") {
+        let flags = &args[2..];
+        let zone_code = flag_or_prompt(flags, "--code", "Zone code", None)?.to_uppercase();
+        let eic_code = flag_or_prompt(flags, "--eic", "EIC code", None)?;
+        let timezone = flag_or_prompt(flags, "--timezone", "Timezone", Some("Europe/Brussels"))?;
+        let zone_name = flag_or_prompt(flags, "--name", "Zone name", Some(&zone_code))?;
+        let country_prefix: String = zone_code.chars().take(2).collect();
+        let country_code = flag_or_prompt(flags, "--country", "Country code", Some(&country_prefix))?.to_uppercase();
+        let country_name = flag_or_prompt(flags, "--country-name", "Country name", Some(&country_code))?;
+        let backfill_days: u32 = flag_or_prompt(flags, "--backfill-days", "Days to backfill", Some("0"))?
+            .parse()
+            .context("--backfill-days must be a number of days")?;
+
+        let zone = models::ZoneDefinition {
+            zone_code,
+            zone_name,
+            country_code,
+            country_name,
+            eic_code,
+            timezone,
+            valid_from: None,
+            valid_to: None,
+            predecessor_code: None,
+        };
+        let fetcher = FetcherService::new(Arc::clone(&client), Arc::clone(&repository));
+        let onboarding = fetcher.onboard_zone(&zone, Utc::now().date_naive(), backfill_days).await?;
+        info!(zone_code = %zone.zone_code, test_prices = onboarding.test_prices, "Zone added");
+        if let Some(backfill) = onboarding.backfill {
+            info!(
+                dates_checked = backfill.dates_checked,
+                prices_stored = backfill.prices_stored,
+                errors = backfill.errors.len(),
+                "Zone backfilled"
+            );
+            for error in &backfill.errors {
+                error!(error = %error, "Backfill fetch failed");
+            }
+        }
+        return Ok(());
+    }
+
+    // `reprocess-archive PATH...` re-parses stored A44 documents (files, or
+    // the *.xml files in directories) and re-upserts their prices
+    if args.first().map(String::as_str) == Some("reprocess-archive") {
+        anyhow::ensure!(args.len() > 1, "Usage: reprocess-archive PATH...");
+        let documents = read_archive(&args[1..])?;
+        let fetcher = FetcherService::new(Arc::clone(&client), Arc::clone(&repository));
+        let summary = fetcher.reprocess_documents(&documents).await?;
+        info!(
+            documents = documents.len(),
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            no_data = summary.no_data,
+            prices_stored = summary.total_prices_stored,
+            "Archive reprocessed"
+        );
+        for error in &summary.errors {
+            error!(error = %error, "Document not reprocessed");
+        }
+        anyhow::ensure!(summary.failed == 0, "{} of {} documents failed", summary.failed, documents.len());
+        return Ok(());
+    }
+
+    let cache = if config.cache.enabled {
+        let cache = Arc::new(PriceCache::new(&config.cache));
+        if let Err(e) = cache.warm(repository.as_ref()).await {
+            warn!(error = %e, "Initial price cache warm-up failed, serving from database");
+        }
+        Some(cache)
+    } else {
+        None
+    };
+
+    let mut fetcher = FetcherService::new(Arc::clone(&client), Arc::clone(&repository))
+        .with_job_lock_ttl(std::time::Duration::from_secs(config.scheduler.job_lock_ttl_seconds))
+        .with_task_queue(config.tasks.clone());
+    if config.entsoe.fetch_intraday {
+        fetcher = fetcher.with_intraday();
+    }
+    if !config.entsoe.flow_pairs.is_empty() {
+        fetcher = fetcher.with_flow_pairs(config.entsoe.flow_pairs.clone());
+    }
+    if config.scheduler.retry_queue.enabled {
+        fetcher = fetcher.with_retry_queue(config.scheduler.retry_queue.clone());
+    }
+    if let Some(cache) = &cache {
+        fetcher = fetcher.with_cache(Arc::clone(cache));
+    }
+    if config.webhooks.enabled {
+        let webhooks = WebhookNotifier::from_config(&config.webhooks).map_err(anyhow::Error::msg)?;
+        info!(endpoints = config.webhooks.urls.len(), "Webhook notifications enabled");
+        fetcher = fetcher.with_webhooks(Arc::new(webhooks));
+    }
+    if config.influx.enabled {
+        let influx = InfluxExporter::from_config(&config.influx).map_err(anyhow::Error::msg)?;
+        info!(url = %config.influx.url, bucket = %config.influx.bucket, "InfluxDB price export enabled");
+        fetcher = fetcher.with_influx(Arc::new(influx));
+    }
+    let alerts = if config.alerts.enabled {
+        let alerts = Arc::new(AlertService::from_config(&config.alerts, Arc::clone(&repository))?);
+        info!("Price alerts enabled");
+        fetcher = fetcher.with_alerts(Arc::clone(&alerts));
+        Some(alerts)
+    } else {
+        None
+    };
+    let fetcher = Arc::new(fetcher);
+
+    // Replicas without a worker still queue tasks for the others
+    let task_worker = if config.tasks.enabled {
+        let worker = TaskWorker::new(Arc::clone(&repository), config.tasks.clone())
+            .with_handler(Arc::new(BackfillJobTask::new(Arc::clone(&fetcher))))
+            .with_handler(Arc::new(GapRepairTask::new(Arc::clone(&fetcher))))
+            .with_handler(Arc::new(RetentionTask::new(Arc::clone(&repository), config.retention.clone())));
+        Some(worker)
+    } else {
+        info!("Task worker disabled in configuration");
+        let queues_tasks =
+            config.retention.enabled || (config.scheduler.enabled && config.scheduler.misfire_policy == MisfirePolicy::Backfill);
+        if queues_tasks {
+            warn!("Scheduled retention runs and gap repairs are only queued here; they run on replicas with tasks.enabled");
+        }
+        None
+    };
+
+    // Started by start_background once the database is ready. Retention is
+    // scheduled here too, so it runs with the fetch jobs disabled
+    let scheduler = if config.scheduler.enabled || config.retention.enabled {
+        let scheduler = PriceFetchScheduler::new(
+            Arc::clone(&fetcher),
+            Arc::clone(&repository),
+            config.scheduler.clone(),
+            config.retention.clone(),
+        )
+        .await?;
         Some(scheduler)
     } else {
-        info!("Scheduler disabled in configuration");
+        info!("Scheduler and retention cleanup disabled in configuration");
         None
     };
 
-    let router = create_router(Arc::clone(&repository), metrics_handle, Some(Arc::clone(&fetcher)));
-    let addr = format!("{}:{}", config.server.host, config.server.port);
-    let listener = TcpListener::bind(&addr).await?;
-    info!(host = %config.server.host, port = %config.server.port, "API server listening");
+    let fx = if config.fx.enabled {
+        let fx = Arc::new(FxRateService::from_config(&config.fx)?);
+        info!(default_source = ?config.fx.default_source, "FX rate service initialized");
+        Some(fx)
+    } else {
+        None
+    };
 
-    let server_handle = tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, router).await {
-            error!(error = %e, "API server error");
+    let state = AppState::new(
+        read_repository,
+        metrics_handle,
+        Some(Arc::clone(&fetcher)),
+        fx,
+        cache,
+        if config.database.lazy_connect {
+            ReadinessGate::new(config.readiness.clone()).awaiting_database()
+        } else {
+            ReadinessGate::new(config.readiness.clone())
+        },
+    )
+    .with_retention(config.retention.clone())
+    .with_query_defaults(config.query_defaults)
+    .with_resolutions(config.resolution)
+    .with_tariffs(config.tariffs)
+    .with_country_average(config.country_average)
+    .with_deployment(&config.deployment);
+    let state = if config.metrics.price_gauges {
+        state.with_price_gauges()
+    } else {
+        state
+    };
+    let state = if config.cache_control.enabled {
+        state.with_cache_control(config.cache_control.clone())
+    } else {
+        state
+    };
+    let state = if config.idempotency.enabled {
+        state.with_idempotency(config.idempotency.clone())
+    } else {
+        state
+    };
+    let state = match &scheduler {
+        Some(scheduler) => state.with_scheduler(scheduler.history()),
+        None => state,
+    };
+    let state = match alerts {
+        Some(alerts) => state.with_alerts(alerts),
+        None => state,
+    };
+    let state = if config.signing.enabled {
+        let signer = ResponseSigner::from_config(&config.signing).map_err(anyhow::Error::msg)?;
+        info!(key_id = %config.signing.key_id, "Response signing enabled");
+        state.with_signer(signer)
+    } else {
+        state
+    };
+    let state = if config.auth.enabled {
+        // A lazily connected database is waited for here, as keys cannot be added later
+        let stored_keys = if config.auth.database_keys {
+            let retries = (!config.database.lazy_connect).then_some(0);
+            storage::retry_with_backoff(&config.database, retries, "Load API keys", || repository.list_api_keys()).await?
+        } else {
+            Vec::new()
+        };
+        let mut auth = ApiKeyAuth::new(&config.auth, stored_keys);
+        if config.auth.jwt.enabled {
+            let jwt = JwtVerifier::from_config(&config.auth.jwt).map_err(anyhow::Error::msg)?;
+            if let Err(e) = jwt.refresh().await {
+                warn!(error = %e, "Failed to fetch JWKS at startup; retrying on the first bearer token");
+            }
+            info!(issuer = %config.auth.jwt.issuer, "JWT bearer auth enabled");
+            auth = auth.with_jwt(Arc::new(jwt));
+        } else if auth.key_count() == 0 {
+            warn!("API key auth is enabled but no keys are configured; admin routes will reject every request");
         }
-    });
+        info!(keys = auth.key_count(), require_read_key = config.auth.require_read_key, "API key auth enabled");
+        state.with_auth(Arc::new(auth))
+    } else {
+        state
+    };
+    let state = if config.rate_limit.enabled {
+        info!(
+            requests_per_window = config.rate_limit.requests_per_window,
+            window_seconds = config.rate_limit.window_seconds,
+            "API rate limiting enabled"
+        );
+        state.with_rate_limiter(Arc::new(RateLimiter::new(&config.rate_limit)))
+    } else {
+        state
+    };
+
+    // With lazy_connect the scheduler and task worker wait for the database,
+    // as every job they run needs it
+    let (mut background, mut pending) = (None, None);
+    if config.database.lazy_connect {
+        let repository = Arc::clone(&repository);
+        let readiness = Arc::clone(&state.readiness);
+        let database = config.database.clone();
+        let zones = config.zones.clone();
+        pending = Some(tokio::spawn(async move {
+            storage::wait_until_connected(&database, repository.as_ref())
+                .await
+                .context("Database migrations failed after connecting")?;
+            info!("Database connected");
+            prepare_database(repository.as_ref(), &zones).await.context("Startup zone sync failed")?;
+            readiness.database_connected();
+            start_background(scheduler, task_worker).await
+        }));
+    } else {
+        background = Some(start_background(scheduler, task_worker).await?);
+    }
 
-    signal::ctrl_c().await?;
-    info!("Shutdown signal received");
+    // Bind every listener before serving so a bad address fails startup
+    let mut listeners = Vec::new();
+    for listener_config in config.server.listeners() {
+        let listener = bind_listener(&listener_config)
+            .await
+            .with_context(|| format!("Failed to bind listener '{}'", listener_config.name))?;
+        info!(
+            listener = %listener_config.name,
+            address = %listener.local_addr()?,
+            scope = listener_config.scope.as_str(),
+            "API server listening"
+        );
+        listeners.push((listener_config, listener));
+    }
 
-    server_handle.abort();
+    let server_handles: Vec<_> = listeners
+        .into_iter()
+        .map(|(listener_config, listener)| {
+            let router = create_scoped_router(state.clone(), listener_config.scope);
+            tokio::spawn(async move {
+                // The peer address identifies anonymous callers to the rate limiter
+                let service = router.into_make_service_with_connect_info::<SocketAddr>();
+                if let Err(e) = axum::serve(listener, service).await {
+                    error!(listener = %listener_config.name, error = %e, "API server error");
+                }
+            })
+        })
+        .collect();
 
-    if let Some(scheduler) = scheduler {
-        if let Err(e) = scheduler.shutdown().await {
-            error!(error = %e, "Error shutting down scheduler");
+    let grpc_handle = if config.grpc.enabled {
+        let listener = bind_listener(&config.grpc.listener())
+            .await
+            .context("Failed to bind gRPC listener")?;
+        info!(address = %listener.local_addr()?, "gRPC server listening");
+        let state = state.clone();
+        let grpc = config.grpc.clone();
+        Some(tokio::spawn(async move {
+            if let Err(e) = serve_grpc(listener, state, &grpc).await {
+                error!(error = %e, "gRPC server error");
+            }
+        }))
+    } else {
+        None
+    };
+
+    // A lazily connected database that cannot be migrated or prepared stops
+    // the service, rather than leaving /ready reporting it as connecting
+    let mut failure = None;
+    match pending {
+        Some(pending) => tokio::select! {
+            result = signal::ctrl_c() => result?,
+            result = pending => match result.map_err(anyhow::Error::from).and_then(|started| started) {
+                Ok(started) => {
+                    background = Some(started);
+                    signal::ctrl_c().await?;
+                }
+                Err(e) => {
+                    error!(error = %format_args!("{:#}", e), "Database setup failed, stopping");
+                    failure = Some(e);
+                }
+            },
+        },
+        None => signal::ctrl_c().await?,
+    }
+    if failure.is_none() {
+        info!("Shutdown signal received");
+    }
+
+    for handle in server_handles.into_iter().chain(grpc_handle) {
+        handle.abort();
+    }
+
+    if let Some(Background { scheduler, task_worker }) = background {
+        if let Some(scheduler) = scheduler {
+            if let Err(e) = scheduler.shutdown().await {
+                error!(error = %e, "Error shutting down scheduler");
+            }
+        }
+
+        // Tasks still running are picked up elsewhere once their lease lapses
+        if let Some(task_worker) = task_worker {
+            task_worker.abort();
         }
     }
 
     info!("Application stopped");
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// The scheduler and task worker, once started.
+struct Background {
+    scheduler: Option<PriceFetchScheduler>,
+    task_worker: Option<JoinHandle<()>>,
+}
+
+async fn start_background(mut scheduler: Option<PriceFetchScheduler>, task_worker: Option<TaskWorker>) -> Result<Background> {
+    if let Some(scheduler) = scheduler.as_mut() {
+        scheduler.start().await?;
+        info!("Scheduler started with fetch times at 13:00, 14:00, 15:00, 16:00 CET");
+    }
+    Ok(Background {
+        scheduler,
+        task_worker: task_worker.map(TaskWorker::start),
+    })
+}
+
+/// Sync the zone registry if configured and create the coming price
+/// partitions, once the database is reachable.
+async fn prepare_database(repository: &dyn storage::PriceStore, zones: &ZoneRegistryConfig) -> Result<()> {
+    if zones.sync_on_startup {
+        let report = storage::sync_zones(repository, &zones.sync_countries).await?;
+        info!(candidates = report.candidates, inserted = ?report.inserted, "Zone registry synced at startup");
+    }
+
+    // The scheduler keeps partitions ahead too, but ingestion may start first
+    match repository.ensure_price_partitions(Utc::now().date_naive(), storage::PRICE_PARTITION_MONTHS_AHEAD).await {
+        Ok(created) if !created.is_empty() => info!(partitions = ?created, "Price partitions created"),
+        Ok(_) => {}
+        Err(e) => warn!(error = %e, "Failed to create price partitions, new months fall into the default partition"),
+    }
     Ok(())
 }
+
+/// Value following `name` in `flags`, or one read from stdin after prompting
+/// with `label`. An empty answer takes `default` when there is one.
+fn flag_or_prompt(flags: &[String], name: &str, label: &str, default: Option<&str>) -> Result<String> {
+    if let Some(position) = flags.iter().position(|flag| flag == name) {
+        return flags
+            .get(position + 1)
+            .cloned()
+            .with_context(|| format!("{} needs a value", name));
+    }
+
+    loop {
+        match default {
+            Some(default) => print!("{} [{}]: ", label, default),
+            None => print!("{}: ", label),
+        }
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        anyhow::ensure!(std::io::stdin().read_line(&mut answer)? > 0, "{} is required (pass {})", label, name);
+        match (answer.trim(), default) {
+            ("", Some(default)) => return Ok(default.to_string()),
+            ("", None) => continue,
+            (answer, _) => return Ok(answer.to_string()),
+        }
+    }
+}
+
+/// `(path, contents)` of each file in `paths`, expanding directories to
+/// their `*.xml` files in name order.
+fn read_archive(paths: &[String]) -> Result<Vec<(String, String)>> {
+    let mut files = Vec::new();
+    for path in paths.iter().map(std::path::Path::new) {
+        if path.is_dir() {
+            let mut entries: Vec<_> = std::fs::read_dir(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| file.extension().is_some_and(|ext| ext == "xml"))
+                .collect();
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+
+    files
+        .into_iter()
+        .map(|file| {
+            let body = std::fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))?;
+            Ok((file.display().to_string(), body))
+        })
+        .collect()
+}
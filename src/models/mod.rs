@@ -1,7 +0,0 @@
-pub mod price;
-pub mod bidding_zone;
-pub mod fetch_log;
-
-pub use price::Price;
-pub use bidding_zone::BiddingZone;
-pub use fetch_log::{FetchLog, FetchStatus};
@@ -1,5 +0,0 @@
-pub mod error;
-pub mod repository;
-
-pub use error::StorageError;
-pub use repository::{PoolStatus, PriceRepository};
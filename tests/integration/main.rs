@@ -0,0 +1,70 @@
+//! End-to-end tests: Postgres (docker compose), a mock ENTSOE server and the
+//! real service binary with the scheduler disabled.
+//!
+//! Run with `cargo test --features integration`.
+
+mod harness;
+mod mock_entsoe;
+mod storage;
+
+use chrono::{Duration, SecondsFormat, Utc};
+use serde_json::Value;
+
+use harness::TestApp;
+
+#[tokio::test]
+async fn test_manual_fetch_populates_api_and_metrics() {
+    let app = TestApp::start().await;
+
+    let ready: Value = app.get("/ready").await.json().await.unwrap();
+    assert_eq!(ready["status"], "ready");
+    assert_eq!(ready["active_zones"], 5);
+
+    let response = app.post("/api/v1/admin/fetch").await;
+    assert_eq!(response.status(), 200);
+    let fetch: Value = response.json().await.unwrap();
+    assert_eq!(fetch["status"], "success");
+    assert_eq!(fetch["failed"], 0);
+    assert_eq!(fetch["succeeded"], 10, "today and tomorrow for NO1-NO5: {fetch}");
+    assert!(app.entsoe.requests() >= 10);
+
+    let today = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let prices: Value = app
+        .get(&format!(
+            "/api/v1/prices/zone/NO1?start={}&end={}",
+            today.to_rfc3339_opts(SecondsFormat::Secs, true),
+            (today + Duration::days(1)).to_rfc3339_opts(SecondsFormat::Secs, true)
+        ))
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(prices["zone_code"], "NO1");
+    assert_eq!(prices["prices"].as_array().unwrap().len(), 24);
+
+    let latest: Value = app.get("/api/v1/prices/latest").await.json().await.unwrap();
+    assert_eq!(latest["prices"].as_array().unwrap().len(), 5);
+
+    let zone_fetch: Value = app
+        .post(&format!("/api/v1/admin/fetch/zone/NO2?date={}", today.date_naive()))
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(zone_fetch["status"], "success");
+    assert!(zone_fetch["fetch_id"].as_i64().unwrap() > 0);
+
+    let metrics = app.get("/metrics").await.text().await.unwrap();
+    assert!(metrics.contains("entsoe_fetch_attempts_total{zone_code=\"NO1\",status=\"success\"}"));
+    assert!(metrics.contains("http_requests_total{method=\"GET\",path=\"/api/v1/prices/zone/{zone}\",status=\"200\"}"));
+    assert!(metrics.contains("entsoe_active_zones 5"));
+}
+
+#[tokio::test]
+async fn test_unknown_zone_returns_not_found() {
+    let app = TestApp::start().await;
+
+    let response = app.get("/api/v1/prices/zone/XX9").await;
+    assert_eq!(response.status(), 404);
+    assert!(response.headers().contains_key("x-correlation-id"));
+}
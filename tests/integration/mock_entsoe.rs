@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use chrono::{NaiveDateTime, TimeZone, Utc};
+
+/// Minimal stand-in for the ENTSOE transparency API: answers every A44
+/// request with hourly prices covering the requested period.
+pub struct MockEntsoe {
+    pub addr: SocketAddr,
+    requests: Arc<AtomicUsize>,
+}
+
+impl MockEntsoe {
+    pub async fn start() -> Self {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route("/api", get(day_ahead_prices))
+            .with_state(Arc::clone(&requests));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        Self { addr, requests }
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}/api", self.addr)
+    }
+
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+async fn day_ahead_prices(
+    State(requests): State<Arc<AtomicUsize>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<String, StatusCode> {
+    requests.fetch_add(1, Ordering::SeqCst);
+
+    let parse = |key: &str| {
+        params
+            .get(key)
+            .and_then(|v| NaiveDateTime::parse_from_str(v, "%Y%m%d%H%M").ok())
+            .map(|dt| Utc.from_utc_datetime(&dt))
+            .ok_or(StatusCode::BAD_REQUEST)
+    };
+    let start = parse("periodStart")?;
+    let end = parse("periodEnd")?;
+
+    let hours = (end - start).num_hours();
+    let points: String = (1..=hours)
+        .map(|pos| format!("<Point><position>{}</position><price.amount>{}.25</price.amount></Point>", pos, 40 + pos))
+        .collect();
+
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+  <mRID>mock</mRID>
+  <TimeSeries>
+    <currency_Unit.name>EUR</currency_Unit.name>
+    <price_Measure_Unit.name>MWH</price_Measure_Unit.name>
+    <Period>
+      <timeInterval><start>{}</start><end>{}</end></timeInterval>
+      <resolution>PT60M</resolution>
+      {}
+    </Period>
+  </TimeSeries>
+</Publication_MarketDocument>"#,
+        start.format("%Y-%m-%dT%H:%MZ"),
+        end.format("%Y-%m-%dT%H:%MZ"),
+        points
+    ))
+}
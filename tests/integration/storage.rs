@@ -0,0 +1,162 @@
+//! The Postgres repository against a real database.
+
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use price_api::models::{Market, Price};
+use price_api::storage::config::{DatabaseBackend, DatabaseConfig};
+use price_api::storage::{DayGap, BULK_COPY_MIN_ROWS};
+use price_api::{PriceRepository, PriceStore};
+
+use crate::harness::fresh_database;
+
+fn database_config(url: &str, run_migrations: bool) -> DatabaseConfig {
+    DatabaseConfig {
+        backend: DatabaseBackend::Postgres,
+        url: url.to_string(),
+        max_connections: 2,
+        min_connections: 0,
+        connect_timeout_seconds: 30,
+        slow_query_threshold_ms: None,
+        statement_timeout_ms: None,
+        read_url: None,
+        run_migrations,
+        connect_retries: 0,
+        connect_retry_initial_ms: 500,
+        connect_retry_max_ms: 5_000,
+        lazy_connect: false,
+    }
+}
+
+async fn partition_of(repository: &PriceRepository, timestamp: DateTime<Utc>) -> String {
+    sqlx::query_scalar("SELECT tableoid::regclass::text FROM electricity_prices WHERE timestamp = $1")
+        .bind(timestamp)
+        .fetch_one(repository.pool())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_price_writes_land_in_their_month_partition() {
+    let (url, _lock) = fresh_database().await;
+    let repository = PriceRepository::from_config(&database_config(&url, false)).await.unwrap();
+
+    // A month outside the partitions created at migration time
+    let backfilled = Utc.with_ymd_and_hms(2019, 3, 5, 0, 0, 0).unwrap();
+    let price = Price::from_mwh(backfilled, "NO1".to_string(), 40.0, "PT60M".to_string());
+    assert_eq!(repository.upsert_prices(&[price]).await.unwrap(), 1);
+    assert_eq!(partition_of(&repository, backfilled).await, "electricity_prices_2019_03");
+
+    // Rows already in the default partition move when their month is created
+    let stranded = Utc.with_ymd_and_hms(2018, 6, 1, 0, 0, 0).unwrap();
+    sqlx::query("INSERT INTO electricity_prices_default (timestamp, bidding_zone, price_kwh) VALUES ($1, 'NO1', 0.04)")
+        .bind(stranded)
+        .execute(repository.pool())
+        .await
+        .unwrap();
+    let created = repository
+        .ensure_price_partitions(NaiveDate::from_ymd_opt(2018, 6, 1).unwrap(), 0)
+        .await
+        .unwrap();
+    assert_eq!(created, vec!["electricity_prices_2018_06"]);
+    assert_eq!(partition_of(&repository, stranded).await, "electricity_prices_2018_06");
+}
+
+#[tokio::test]
+async fn test_startup_leaves_price_table_rewrite_to_migrate_command() {
+    let (url, _lock) = fresh_database().await;
+    let repository = PriceRepository::from_config(&database_config(&url, false)).await.unwrap();
+    let price = Price::from_mwh(Utc::now(), "NO1".to_string(), 40.0, "PT60M".to_string());
+    repository.upsert_prices(&[price]).await.unwrap();
+
+    // As if the database predates the partitioning migration
+    sqlx::query("DELETE FROM _sqlx_migrations WHERE version = 20250610000000")
+        .execute(repository.pool())
+        .await
+        .unwrap();
+
+    let error = PriceRepository::from_config(&database_config(&url, true)).await.err().unwrap();
+    assert!(error.to_string().contains("entsoe-price-fetcher migrate"), "{error}");
+}
+
+#[tokio::test]
+async fn test_migrations_run_without_statement_timeout() {
+    let (url, _lock) = fresh_database().await;
+    let config = DatabaseConfig {
+        statement_timeout_ms: Some(1),
+        ..database_config(&url, false)
+    };
+    let repository = PriceRepository::from_config(&config).await.unwrap();
+    let admin = sqlx::PgPool::connect(&url).await.unwrap();
+    sqlx::query("DROP SCHEMA public CASCADE").execute(&admin).await.unwrap();
+    sqlx::query("CREATE SCHEMA public").execute(&admin).await.unwrap();
+    admin.close().await;
+
+    repository.migrate_offline().await.unwrap();
+
+    let timeout: String = sqlx::query_scalar("SHOW statement_timeout").fetch_one(repository.pool()).await.unwrap();
+    assert_eq!(timeout, "1ms");
+}
+
+#[tokio::test]
+async fn test_job_pauses_are_shared_between_repositories() {
+    let (url, _lock) = fresh_database().await;
+    let first = PriceRepository::from_config(&database_config(&url, false)).await.unwrap();
+    let second = PriceRepository::from_config(&database_config(&url, false)).await.unwrap();
+
+    first.set_job_paused("retention_cleanup", true, Utc::now()).await.unwrap();
+    // Pausing twice keeps the one row
+    second.set_job_paused("retention_cleanup", true, Utc::now()).await.unwrap();
+    assert_eq!(second.paused_jobs().await.unwrap(), vec!["retention_cleanup"]);
+
+    second.set_job_paused("retention_cleanup", false, Utc::now()).await.unwrap();
+    assert!(first.paused_jobs().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_bulk_copy_upserts_large_batches() {
+    let (url, _lock) = fresh_database().await;
+    let repository = PriceRepository::from_config(&database_config(&url, false)).await.unwrap();
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let batch = |mwh: f64| -> Vec<Price> {
+        (0..BULK_COPY_MIN_ROWS as i64 + 1)
+            .map(|i| Price::from_mwh(start + Duration::minutes(15 * i), "NO1".to_string(), mwh, "PT15M".to_string()))
+            .collect()
+    };
+
+    assert_eq!(repository.bulk_insert_copy(&batch(40.0)).await.unwrap(), BULK_COPY_MIN_ROWS + 1);
+    // A second batch over the same intervals updates the rows in place
+    assert_eq!(repository.bulk_insert_copy(&batch(55.5)).await.unwrap(), BULK_COPY_MIN_ROWS + 1);
+
+    let end = start + Duration::minutes(15 * (BULK_COPY_MIN_ROWS as i64 + 1));
+    let stored = repository.get_prices_by_zone("NO1", Market::DayAhead, start, end).await.unwrap();
+    assert_eq!(stored.len(), BULK_COPY_MIN_ROWS + 1);
+    assert!(stored.iter().all(|price| price.price_kwh == "0.0555".parse().unwrap()));
+    assert_eq!(partition_of(&repository, start + Duration::days(60)).await, "electricity_prices_2024_03");
+}
+
+#[tokio::test]
+async fn test_find_gaps_checks_local_days_by_their_length() {
+    let (url, _lock) = fresh_database().await;
+    let repository = PriceRepository::from_config(&database_config(&url, false)).await.unwrap();
+    // Oslo's 30 March 2025 lasts 23 hours, from 23:00 UTC the day before
+    let date = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+    let day_start = Utc.with_ymd_and_hms(2025, 3, 29, 23, 0, 0).unwrap();
+    let quarters = |zone: &str, hours: i64| -> Vec<Price> {
+        (0..hours * 4)
+            .map(|quarter| Price::from_mwh(day_start + Duration::minutes(15 * quarter), zone.to_string(), 40.0, "PT15M".to_string()))
+            .collect()
+    };
+    repository.upsert_prices(&quarters("NO1", 23)).await.unwrap();
+    repository.upsert_prices(&quarters("NO2", 22)).await.unwrap();
+
+    let gaps = repository
+        .find_gaps(date, date, &["NO1".to_string(), "NO2".to_string()])
+        .await
+        .unwrap();
+    let expected = DayGap {
+        date,
+        zone_code: "NO2".to_string(),
+        hours: 22,
+        expected_hours: 23,
+    };
+    assert_eq!(gaps, vec![expected]);
+}